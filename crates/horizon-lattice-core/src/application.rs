@@ -345,6 +345,23 @@ impl Application {
         id
     }
 
+    /// Start a repeating timer that calls `callback` at the specified interval.
+    ///
+    /// The callback runs on the event loop thread. Unlike timers started with
+    /// [`start_repeating_timer`](Self::start_repeating_timer), its ticks are not
+    /// dispatched to the event handler. Stop it with [`stop_timer`](Self::stop_timer),
+    /// which may also be called from within the callback.
+    pub fn start_repeating_timer_with<F>(&self, interval: Duration, callback: F) -> TimerId
+    where
+        F: FnMut() + Send + 'static,
+    {
+        let id = self
+            .timers
+            .start_repeating_with(interval, Box::new(callback));
+        let _ = self.proxy.send_event(LatticeEvent::WakeUp);
+        id
+    }
+
     /// Stop a timer.
     pub fn stop_timer(&self, id: TimerId) -> Result<()> {
         self.timers.stop(id)
@@ -694,9 +711,11 @@ impl ApplicationHandler<LatticeEvent> for AppHandler<'_> {
                 event_loop.exit();
                 return;
             }
-            LatticeEvent::Timer { .. } => {
-                // Dispatch to user handler.
-                if let Some(ref handler) = *self.app.event_handler.read() {
+            LatticeEvent::Timer { id } => {
+                // Run the timer's callback, or dispatch to user handler.
+                if !self.app.timers.run_callback(*id)
+                    && let Some(ref handler) = *self.app.event_handler.read()
+                {
                     handler(&event);
                 }
             }
@@ -739,6 +758,11 @@ impl ApplicationHandler<LatticeEvent> for AppHandler<'_> {
             tracing::trace!(target: "horizon_lattice_core::event_loop", count = timer_events.len(), "processing timer events");
         }
         for event in timer_events {
+            if let LatticeEvent::Timer { id } = event
+                && self.app.timers.run_callback(id)
+            {
+                continue;
+            }
             if let Some(ref handler) = *self.app.event_handler.read() {
                 handler(&event);
             }
@@ -766,6 +790,7 @@ impl ApplicationHandler<LatticeEvent> for AppHandler<'_> {
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
 
     use super::*;
 
@@ -783,6 +808,30 @@ mod tests {
         assert!(!manager.is_active(id));
     }
 
+    #[test]
+    fn test_timer_manager_callbacks() {
+        let manager = SharedTimerManager::new();
+        let ticks = Arc::new(AtomicUsize::new(0));
+        let ticks_clone = ticks.clone();
+
+        let id = manager.start_repeating_with(
+            Duration::from_millis(10),
+            Box::new(move || {
+                ticks_clone.fetch_add(1, Ordering::SeqCst);
+            }),
+        );
+        assert!(manager.run_callback(id));
+        assert!(manager.run_callback(id));
+        assert_eq!(ticks.load(Ordering::SeqCst), 2);
+
+        // Stopped timers drop their callback
+        manager.stop(id).unwrap();
+        assert!(!manager.run_callback(id));
+
+        let plain = manager.start_repeating(Duration::from_millis(10));
+        assert!(!manager.run_callback(plain));
+    }
+
     #[test]
     fn test_task_queue_basic() {
         let queue = SharedTaskQueue::new();
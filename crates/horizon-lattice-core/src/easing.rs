@@ -45,7 +45,7 @@ pub enum Easing {
 /// # Example
 ///
 /// ```
-/// use horizon_lattice::widget::animation::{ease, Easing};
+/// use horizon_lattice_core::easing::{ease, Easing};
 ///
 /// // Linear: output equals input
 /// assert_eq!(ease(Easing::Linear, 0.5), 0.5);
@@ -12,6 +12,7 @@
 //! - **Signal/Slot System**: Type-safe inter-object communication
 //! - **Property System**: Reactive properties with change notification
 //! - **Timers**: One-shot and repeating timer system
+//! - **Easing**: Easing curves for widget and style animations
 //! - **Task Queue**: Deferred/idle task processing
 //! - **Scheduler**: Background work scheduling with one-shot and periodic tasks
 //!
@@ -98,6 +99,7 @@
 mod application;
 #[cfg(feature = "tokio")]
 pub mod async_runtime;
+pub mod easing;
 mod error;
 mod event;
 pub mod invocation;
@@ -115,13 +117,15 @@ mod timer;
 pub mod worker;
 
 pub use application::{Application, WindowEventHandler};
+pub use easing::{Easing, ease};
 pub use error::{
     LatticeError, Result, SchedulerError, SignalError, ThreadError, ThreadPoolError, TimerError,
 };
 pub use event::{EventPriority, LatticeEvent};
 pub use logging::{ObjectTreeDebug, PerfSpan, TreeFormatOptions, TreeStyle};
 pub use meta::{
-    MetaError, MetaObject, MetaProperty, MetaResult, MethodMeta, SignalMeta, TypeRegistry,
+    DynamicSlot, MetaError, MetaObject, MetaProperty, MetaResult, MethodMeta, SignalConnectFn,
+    SignalDisconnectFn, SignalMeta, TypeRegistry, connect_by_name, disconnect_by_name,
     init_type_registry,
 };
 pub use object::{
@@ -13,6 +13,7 @@
 //! - Dynamic property access by name
 //! - Signal/slot introspection
 //! - Dynamic method invocation
+//! - Connecting signals to slots by name ([`connect_by_name`])
 //! - Object factory pattern (create by type name)
 //!
 //! # Architecture
@@ -52,8 +53,12 @@
 
 use std::any::{Any, TypeId};
 use std::fmt;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
 
 use crate::Object;
+use crate::signal::ConnectionId;
 
 /// Error types for meta-object operations.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -322,6 +327,9 @@ impl MetaObject {
     }
 
     /// Invoke a method by name with dynamic arguments.
+    ///
+    /// The argument count and types are checked against the method's
+    /// parameters before it is called.
     pub fn invoke(
         &self,
         obj: &mut dyn Object,
@@ -333,15 +341,7 @@ impl MetaObject {
             .ok_or_else(|| MetaError::MethodNotFound {
                 name: method_name.to_string(),
             })?;
-
-        if args.len() != method.param_types.len() {
-            return Err(MetaError::ArgumentCount {
-                expected: method.param_types.len(),
-                got: args.len(),
-            });
-        }
-
-        Ok((method.invoke)(obj, args))
+        method.call(obj, args)
     }
 }
 
@@ -479,6 +479,7 @@ unsafe impl Sync for MetaProperty {}
 /// - [`crate::Signal`] - The actual signal implementation
 /// - [`MetaObject`] - Contains a list of `SignalMeta` descriptors
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct SignalMeta {
     /// Signal name (e.g., "clicked", "textChanged").
     pub name: &'static str,
@@ -494,10 +495,30 @@ pub struct SignalMeta {
     /// This is used internally for signal dispatch and is assigned
     /// by the `#[derive(Object)]` macro.
     pub index: usize,
+
+    /// Parameter `TypeId`s, parallel to `param_types`, or empty if unknown.
+    param_type_ids: &'static [TypeId],
+
+    /// Type-erased connect function, if the signal can be connected
+    /// dynamically.
+    connect: Option<SignalConnectFn>,
+
+    /// Type-erased disconnect function, paired with `connect`.
+    disconnect: Option<SignalDisconnectFn>,
 }
 
+/// A type-erased slot receiving boxed signal arguments.
+pub type DynamicSlot = Box<dyn Fn(&[Box<dyn Any>]) + Send + Sync>;
+
+/// Connects a [`DynamicSlot`] to a signal on the given object.
+pub type SignalConnectFn = fn(&dyn Object, DynamicSlot) -> ConnectionId;
+
+/// Disconnects a connection made by a [`SignalConnectFn`], returning `true`
+/// if it existed.
+pub type SignalDisconnectFn = fn(&dyn Object, ConnectionId) -> bool;
+
 impl SignalMeta {
-    /// Create a new SignalMeta (typically called by generated code).
+    /// Create a new SignalMeta without dynamic connection support.
     pub const fn new(
         name: &'static str,
         param_types: &'static [&'static str],
@@ -507,9 +528,45 @@ impl SignalMeta {
             name,
             param_types,
             index,
+            param_type_ids: &[],
+            connect: None,
+            disconnect: None,
         }
     }
 
+    /// Create a new SignalMeta that can be connected by name (typically
+    /// called by generated code).
+    ///
+    /// `param_type_ids` must be parallel to `param_types`; they are used to
+    /// check slot compatibility in [`connect_by_name`].
+    pub const fn with_connection(
+        name: &'static str,
+        param_types: &'static [&'static str],
+        param_type_ids: &'static [TypeId],
+        index: usize,
+        connect: SignalConnectFn,
+        disconnect: SignalDisconnectFn,
+    ) -> Self {
+        Self {
+            name,
+            param_types,
+            index,
+            param_type_ids,
+            connect: Some(connect),
+            disconnect: Some(disconnect),
+        }
+    }
+
+    /// Get the parameter `TypeId`s, or an empty slice if they are not known.
+    pub const fn param_type_ids(&self) -> &'static [TypeId] {
+        self.param_type_ids
+    }
+
+    /// Check whether the signal can be connected by name.
+    pub const fn is_connectable(&self) -> bool {
+        self.connect.is_some()
+    }
+
     /// Get the number of parameters this signal takes.
     pub const fn param_count(&self) -> usize {
         self.param_types.len()
@@ -519,13 +576,14 @@ impl SignalMeta {
 /// Metadata for a callable method.
 ///
 /// Enables dynamic method invocation by name, similar to Qt's
-/// `QMetaObject::invokeMethod()`. Methods are registered with
-/// the `#[method]` attribute.
+/// `QMetaObject::invokeMethod()`. Methods are registered by marking them
+/// `#[slot]` or `#[invokable]` in a `#[methods]` impl block.
 ///
 /// # Related Types
 ///
 /// - [`MetaObject`] - Contains a list of `MethodMeta` descriptors
 /// - [`MetaObject::invoke`] - Invokes a method by name
+#[non_exhaustive]
 pub struct MethodMeta {
     /// Method name (e.g., "increment", "setText").
     pub name: &'static str,
@@ -533,38 +591,63 @@ pub struct MethodMeta {
     /// Parameter type names.
     pub param_types: &'static [&'static str],
 
+    /// Parameter `TypeId`s, parallel to `param_types`, or empty if unchecked.
+    param_type_ids: &'static [TypeId],
+
     /// Return type name.
     pub return_type: &'static str,
 
     /// Type-erased method invoker.
     ///
     /// Takes a mutable object reference and boxed arguments,
-    /// returns a boxed result.
+    /// returns a boxed result. The arguments have already been checked.
     pub invoke: fn(&mut dyn Object, &[Box<dyn Any>]) -> Box<dyn Any>,
 }
 
 impl MethodMeta {
-    /// Create a new MethodMeta (typically called by generated code).
+    /// Create a new MethodMeta whose argument types are not checked.
     pub const fn new(
         name: &'static str,
         param_types: &'static [&'static str],
         return_type: &'static str,
         invoke: fn(&mut dyn Object, &[Box<dyn Any>]) -> Box<dyn Any>,
+    ) -> Self {
+        Self::with_param_types(name, param_types, &[], return_type, invoke)
+    }
+
+    /// Create a new MethodMeta whose arguments are checked against
+    /// `param_type_ids` before it is invoked (typically called by generated
+    /// code).
+    pub const fn with_param_types(
+        name: &'static str,
+        param_types: &'static [&'static str],
+        param_type_ids: &'static [TypeId],
+        return_type: &'static str,
+        invoke: fn(&mut dyn Object, &[Box<dyn Any>]) -> Box<dyn Any>,
     ) -> Self {
         Self {
             name,
             param_types,
+            param_type_ids,
             return_type,
             invoke,
         }
     }
 
+    /// Get the parameter `TypeId`s that arguments are checked against, or an
+    /// empty slice if they are not checked.
+    pub const fn param_type_ids(&self) -> &'static [TypeId] {
+        self.param_type_ids
+    }
+
     /// Get the number of parameters this method takes.
     pub const fn param_count(&self) -> usize {
         self.param_types.len()
     }
 
     /// Invoke this method on an object.
+    ///
+    /// Fails if the argument count or any argument type does not match.
     pub fn call(&self, obj: &mut dyn Object, args: &[Box<dyn Any>]) -> MetaResult<Box<dyn Any>> {
         if args.len() != self.param_types.len() {
             return Err(MetaError::ArgumentCount {
@@ -572,10 +655,116 @@ impl MethodMeta {
                 got: args.len(),
             });
         }
+        for (index, (arg, type_id)) in args.iter().zip(self.param_type_ids).enumerate() {
+            if (**arg).type_id() != *type_id {
+                return Err(MetaError::ArgumentTypeMismatch {
+                    index,
+                    expected: self.param_types[index],
+                });
+            }
+        }
         Ok((self.invoke)(obj, args))
     }
 }
 
+/// Connect a signal to a slot, both looked up by name.
+///
+/// The slot may take fewer parameters than the signal provides; extra
+/// signal arguments are dropped, as in Qt. Each slot parameter must have
+/// the same type as the corresponding signal parameter.
+///
+/// The connection holds the receiver weakly: once the receiver is dropped
+/// the slot is no longer called. The receiver is locked while the slot
+/// runs, so a slot must not cause the same receiver to be locked again.
+///
+/// # Errors
+///
+/// - [`MetaError::SignalNotFound`] if the sender has no such signal, or the
+///   signal cannot be connected dynamically
+/// - [`MetaError::MethodNotFound`] if the receiver has no such method
+/// - [`MetaError::ArgumentCount`] if the slot takes more parameters than
+///   the signal provides
+/// - [`MetaError::ArgumentTypeMismatch`] if a parameter type differs
+pub fn connect_by_name<R: Object>(
+    sender: &dyn Object,
+    signal_name: &str,
+    receiver: &Arc<Mutex<R>>,
+    slot_name: &str,
+) -> MetaResult<ConnectionId> {
+    let signal_not_found = || MetaError::SignalNotFound {
+        name: signal_name.to_string(),
+    };
+    let signal = sender
+        .meta_object()
+        .and_then(|meta| meta.signal(signal_name))
+        .ok_or_else(signal_not_found)?;
+    let connect = signal.connect.ok_or_else(signal_not_found)?;
+
+    let receiver_meta = receiver
+        .lock()
+        .meta_object()
+        .ok_or_else(|| MetaError::MethodNotFound {
+            name: slot_name.to_string(),
+        })?;
+    let method = receiver_meta
+        .method(slot_name)
+        .ok_or_else(|| MetaError::MethodNotFound {
+            name: slot_name.to_string(),
+        })?;
+
+    let arg_count = method.param_count();
+    if arg_count > signal.param_count() {
+        return Err(MetaError::ArgumentCount {
+            expected: arg_count,
+            got: signal.param_count(),
+        });
+    }
+    for (index, (slot_type, signal_type)) in method
+        .param_type_ids
+        .iter()
+        .zip(signal.param_type_ids)
+        .enumerate()
+    {
+        if slot_type != signal_type {
+            return Err(MetaError::ArgumentTypeMismatch {
+                index,
+                expected: method.param_types[index],
+            });
+        }
+    }
+
+    let receiver = Arc::downgrade(receiver);
+    let method_name = method.name;
+    let slot = move |args: &[Box<dyn Any>]| {
+        let Some(receiver) = receiver.upgrade() else {
+            return;
+        };
+        let mut receiver = receiver.lock();
+        if let Err(e) = receiver_meta.invoke(&mut *receiver, method_name, &args[..arg_count]) {
+            tracing::warn!(target: "horizon_lattice_core::meta", "Failed to invoke slot {}: {}", method_name, e);
+        }
+    };
+    Ok(connect(sender, Box::new(slot)))
+}
+
+/// Disconnect a connection made with [`connect_by_name`].
+///
+/// Returns `true` if the connection existed.
+pub fn disconnect_by_name(
+    sender: &dyn Object,
+    signal_name: &str,
+    id: ConnectionId,
+) -> MetaResult<bool> {
+    let disconnect = sender
+        .meta_object()
+        .and_then(|meta| meta.signal(signal_name))
+        .and_then(|signal| signal.disconnect)
+        .ok_or_else(|| MetaError::SignalNotFound {
+            name: signal_name.to_string(),
+        })?;
+    Ok(disconnect(sender, id))
+}
+
 impl fmt::Debug for MethodMeta {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("MethodMeta")
@@ -918,6 +1107,14 @@ mod tests {
         Box::new(())
     }
 
+    // Type-erased method invoker for add_count
+    fn test_widget_invoke_add_count(obj: &mut dyn Object, args: &[Box<dyn Any>]) -> Box<dyn Any> {
+        let widget = crate::object_cast_mut::<TestWidget>(obj).unwrap();
+        let amount = *args[0].downcast_ref::<i32>().unwrap();
+        widget.set_count(widget.count() + amount);
+        Box::new(widget.count())
+    }
+
     // Static meta-object for TestWidget (simulates what #[derive(Object)] would generate)
     static TEST_WIDGET_META: MetaObject = MetaObject {
         type_id: TypeId::of::<TestWidget>(),
@@ -944,28 +1141,20 @@ mod tests {
             },
         ],
         signals: &[
-            SignalMeta {
-                name: "clicked",
-                param_types: &[],
-                index: 0,
-            },
-            SignalMeta {
-                name: "textChanged",
-                param_types: &["String"],
-                index: 1,
-            },
-            SignalMeta {
-                name: "countChanged",
-                param_types: &["i32"],
-                index: 2,
-            },
+            SignalMeta::new("clicked", &[], 0),
+            SignalMeta::new("textChanged", &["String"], 1),
+            SignalMeta::new("countChanged", &["i32"], 2),
+        ],
+        methods: &[
+            MethodMeta::new("increment", &[], "()", test_widget_invoke_increment),
+            MethodMeta::with_param_types(
+                "addCount",
+                &["i32"],
+                &[TypeId::of::<i32>()],
+                "i32",
+                test_widget_invoke_add_count,
+            ),
         ],
-        methods: &[MethodMeta {
-            name: "increment",
-            param_types: &[],
-            return_type: "()",
-            invoke: test_widget_invoke_increment,
-        }],
         create: None,
     };
 
@@ -1072,6 +1261,31 @@ mod tests {
         assert_eq!(widget.count(), 6);
     }
 
+    #[test]
+    fn test_meta_method_argument_checks() {
+        setup();
+
+        let mut widget = TestWidget::new();
+        let meta = widget.meta_object().unwrap();
+
+        let result = meta
+            .invoke(&mut widget, "addCount", &[Box::new(3i32)])
+            .unwrap();
+        assert_eq!(*result.downcast::<i32>().unwrap(), 3);
+
+        let result = meta.invoke(&mut widget, "addCount", &[Box::new(3u8)]);
+        assert_eq!(
+            result.unwrap_err(),
+            MetaError::ArgumentTypeMismatch {
+                index: 0,
+                expected: "i32"
+            }
+        );
+        let result = meta.invoke(&mut widget, "addCount", &[]);
+        assert!(matches!(result, Err(MetaError::ArgumentCount { .. })));
+        assert_eq!(widget.count(), 3);
+    }
+
     #[test]
     fn test_meta_property_names() {
         setup();
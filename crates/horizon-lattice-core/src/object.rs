@@ -143,8 +143,13 @@ struct ObjectData {
     dynamic_properties: HashMap<String, Box<dyn Any + Send + Sync>>,
     /// Widget state for state propagation (None for non-widget objects).
     widget_state: Option<WidgetState>,
+    /// Callbacks run once the object has been destroyed.
+    destroy_callbacks: Vec<DestroyCallback>,
 }
 
+/// A callback run when an object is destroyed.
+type DestroyCallback = Box<dyn FnOnce() + Send + Sync>;
+
 impl ObjectData {
     fn new(type_id: TypeId, type_name: &'static str) -> Self {
         Self {
@@ -155,6 +160,7 @@ impl ObjectData {
             children: Vec::new(),
             dynamic_properties: HashMap::new(),
             widget_state: None,
+            destroy_callbacks: Vec::new(),
         }
     }
 }
@@ -196,6 +202,15 @@ impl ObjectRegistry {
     /// also destroys all children.
     #[tracing::instrument(skip(self), target = "horizon_lattice_core::object", level = "trace")]
     pub fn destroy(&mut self, id: ObjectId) -> ObjectResult<()> {
+        for callback in self.remove_tree(id)? {
+            callback();
+        }
+        Ok(())
+    }
+
+    /// Remove an object and its descendants, returning their destroy
+    /// callbacks so they can run once the registry is unlocked.
+    fn remove_tree(&mut self, id: ObjectId) -> ObjectResult<Vec<DestroyCallback>> {
         // First collect all children to destroy (depth-first).
         let children_to_destroy = self.collect_descendants(id)?;
         tracing::trace!(target: "horizon_lattice_core::object", ?id, descendant_count = children_to_destroy.len(), "destroying object tree");
@@ -209,11 +224,30 @@ impl ObjectRegistry {
         }
 
         // Destroy all descendants (children first, then self).
-        for child_id in children_to_destroy {
-            self.objects.remove(child_id);
+        let mut callbacks = Vec::new();
+        for object_id in children_to_destroy.into_iter().chain([id]) {
+            if let Some(data) = self.objects.remove(object_id) {
+                callbacks.extend(data.destroy_callbacks);
+            }
         }
-        self.objects.remove(id);
 
+        Ok(callbacks)
+    }
+
+    /// Run `callback` once the object has been destroyed.
+    ///
+    /// Callbacks run after the object and its descendants are removed from
+    /// the registry.
+    pub fn on_destroyed(
+        &mut self,
+        id: ObjectId,
+        callback: impl FnOnce() + Send + Sync + 'static,
+    ) -> ObjectResult<()> {
+        let data = self
+            .objects
+            .get_mut(id)
+            .ok_or(ObjectError::InvalidObjectId)?;
+        data.destroy_callbacks.push(Box::new(callback));
         Ok(())
     }
 
@@ -952,8 +986,24 @@ impl SharedObjectRegistry {
     }
 
     /// Destroy an object and its children.
+    ///
+    /// Destroy callbacks run after the registry lock is released, so they
+    /// may use the registry.
     pub fn destroy(&self, id: ObjectId) -> ObjectResult<()> {
-        self.inner.write().destroy(id)
+        let callbacks = self.inner.write().remove_tree(id)?;
+        for callback in callbacks {
+            callback();
+        }
+        Ok(())
+    }
+
+    /// Run `callback` once the object has been destroyed.
+    pub fn on_destroyed(
+        &self,
+        id: ObjectId,
+        callback: impl FnOnce() + Send + Sync + 'static,
+    ) -> ObjectResult<()> {
+        self.inner.write().on_destroyed(id, callback)
     }
 
     /// Check if an object exists.
@@ -1655,6 +1705,35 @@ mod tests {
         assert!(!registry.contains(grandchild_id));
     }
 
+    #[test]
+    fn test_destroy_callbacks() {
+        setup();
+        let registry = global_registry().unwrap();
+
+        let parent_id = registry.register::<TestObject>();
+        let child_id = registry.register::<ChildObject>();
+        registry.set_parent(child_id, Some(parent_id)).unwrap();
+
+        // Callbacks run for descendants too, and may use the registry.
+        let destroyed = std::sync::Arc::new(Mutex::new(Vec::new()));
+        for id in [parent_id, child_id] {
+            let destroyed = destroyed.clone();
+            registry
+                .on_destroyed(id, move || {
+                    let registry = global_registry().unwrap();
+                    destroyed.lock().push((id, registry.contains(id)));
+                })
+                .unwrap();
+        }
+
+        registry.destroy(parent_id).unwrap();
+        assert_eq!(
+            *destroyed.lock(),
+            vec![(child_id, false), (parent_id, false)]
+        );
+        assert!(registry.on_destroyed(parent_id, || {}).is_err());
+    }
+
     #[test]
     fn test_circular_parentage_rejected() {
         setup();
@@ -9,6 +9,7 @@
 //!
 //! - [`Property<T>`] - A reactive property with optional change notification
 //! - [`Binding<T>`] - A computed property that derives its value from others
+//!   and tracks its dependencies automatically
 //! - [`PropertyMeta`] - Runtime metadata for property introspection
 //! - [`ReadOnlyProperty`] - A read-only view of a property
 //!
@@ -51,10 +52,92 @@
 //! [`Signal`]: crate::Signal
 
 use std::any::TypeId;
+use std::cell::RefCell;
 use std::fmt;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+
+use crate::object::{ObjectId, global_registry};
+use crate::signal::Signal;
+
+/// Something that caches a value derived from properties.
+trait Dependent: Send + Sync {
+    /// Called when a value the dependent read has changed.
+    fn mark_dirty(self: Arc<Self>);
+}
+
+/// The dependents of a property or binding.
+///
+/// Entries are weak so that dropping a binding never leaks through the
+/// properties it read; dead entries are pruned as new ones are added.
+#[derive(Default)]
+struct Dependents {
+    list: Mutex<Vec<Weak<dyn Dependent>>>,
+}
+
+impl Dependents {
+    fn add(&self, dependent: &Weak<dyn Dependent>) {
+        let mut list = self.list.lock();
+        list.retain(|d| d.strong_count() > 0);
+        if !list.iter().any(|d| Weak::ptr_eq(d, dependent)) {
+            list.push(dependent.clone());
+        }
+    }
+
+    fn remove(&self, dependent: &Weak<dyn Dependent>) {
+        self.list
+            .lock()
+            .retain(|d| d.strong_count() > 0 && !Weak::ptr_eq(d, dependent));
+    }
+
+    fn notify(&self) {
+        // Collect first so dependents may re-subscribe while being notified.
+        let dependents: Vec<_> = self.list.lock().iter().filter_map(Weak::upgrade).collect();
+        for dependent in dependents {
+            dependent.mark_dirty();
+        }
+    }
+
+    /// Record a read in the binding currently being evaluated, if any.
+    fn track(self: &Arc<Self>) {
+        TRACKING.with(|frames| {
+            if let Some(frame) = frames.borrow_mut().last_mut()
+                && !frame.iter().any(|d| Arc::ptr_eq(d, self))
+            {
+                frame.push(self.clone());
+            }
+        });
+    }
+}
+
+thread_local! {
+    /// The sources read by each binding being evaluated on this thread,
+    /// innermost last.
+    static TRACKING: RefCell<Vec<Vec<Arc<Dependents>>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A tracking frame that is popped even if the computation panics.
+struct TrackingFrame;
+
+impl TrackingFrame {
+    fn push() -> Self {
+        TRACKING.with(|frames| frames.borrow_mut().push(Vec::new()));
+        Self
+    }
+
+    fn finish(self) -> Vec<Arc<Dependents>> {
+        std::mem::forget(self);
+        TRACKING.with(|frames| frames.borrow_mut().pop().unwrap_or_default())
+    }
+}
+
+impl Drop for TrackingFrame {
+    fn drop(&mut self) {
+        TRACKING.with(|frames| frames.borrow_mut().pop());
+    }
+}
 
 /// A reactive property that tracks changes.
 ///
@@ -75,6 +158,10 @@ use parking_lot::RwLock;
 /// }
 /// ```
 ///
+/// Reading a property inside a [`Binding`] computation records it as a
+/// dependency of the binding; a later change through [`set`](Self::set) or
+/// [`replace`](Self::replace) marks the binding dirty.
+///
 /// # Thread Safety
 ///
 /// `Property<T>` uses interior mutability with `RwLock` and is `Send + Sync`.
@@ -105,6 +192,7 @@ use parking_lot::RwLock;
 /// [`Signal`]: crate::Signal
 pub struct Property<T> {
     value: RwLock<T>,
+    dependents: Arc<Dependents>,
 }
 
 impl<T: Clone> Property<T> {
@@ -112,6 +200,7 @@ impl<T: Clone> Property<T> {
     pub fn new(value: T) -> Self {
         Self {
             value: RwLock::new(value),
+            dependents: Arc::default(),
         }
     }
 
@@ -119,6 +208,7 @@ impl<T: Clone> Property<T> {
     ///
     /// This clones the value. For large types, consider using `with()` instead.
     pub fn get(&self) -> T {
+        self.dependents.track();
         self.value.read().clone()
     }
 
@@ -129,16 +219,24 @@ impl<T: Clone> Property<T> {
     where
         F: FnOnce(&T) -> R,
     {
+        self.dependents.track();
         f(&self.value.read())
     }
 
     /// Set the value without change notification.
     ///
     /// This is useful during initialization or batch updates where you
-    /// want to defer notifications.
+    /// want to defer notifications. Bindings that depend on the property
+    /// are not marked dirty until [`notify_bindings`](Self::notify_bindings)
+    /// is called.
     pub fn set_silent(&self, value: T) {
         *self.value.write() = value;
     }
+
+    /// Mark every binding that depends on this property dirty.
+    pub fn notify_bindings(&self) {
+        self.dependents.notify();
+    }
 }
 
 impl<T: Clone + PartialEq> Property<T> {
@@ -153,6 +251,8 @@ impl<T: Clone + PartialEq> Property<T> {
         let mut current = self.value.write();
         if *current != value {
             *current = value;
+            drop(current);
+            self.notify_bindings();
             true
         } else {
             false
@@ -167,6 +267,8 @@ impl<T: Clone + PartialEq> Property<T> {
         let mut current = self.value.write();
         if *current != value {
             let old = std::mem::replace(&mut *current, value);
+            drop(current);
+            self.notify_bindings();
             Some(old)
         } else {
             None
@@ -232,14 +334,22 @@ impl<'a, T: Clone> ReadOnlyProperty<'a, T> {
 
 /// A computed property that derives its value from a computation.
 ///
-/// `Binding<T>` caches its computed value and only recalculates when
-/// explicitly invalidated via [`invalidate`](Self::invalidate). This is useful
-/// for derived properties that depend on multiple source [`Property`] values.
+/// `Binding<T>` caches its computed value and recalculates lazily once it is
+/// dirty. Every [`Property`] or other `Binding` read during the computation is
+/// recorded as a dependency, and changing one of them marks the binding dirty
+/// and emits [`changed`](Self::changed). The dependencies are recorded afresh
+/// on each computation, so conditional reads are tracked correctly.
+///
+/// A binding can also drive a value elsewhere, such as a widget property,
+/// through [`bind_to`](Self::bind_to) or [`connect_value`](Self::connect_value).
+/// Bound bindings recompute eagerly when a dependency changes.
+///
+/// Cloning a binding shares its computation, cache and connections.
 ///
 /// # Related Types
 ///
 /// - [`Property`] - Source properties that bindings typically depend on
-/// - [`crate::Signal`] - Can be used to trigger [`invalidate`](Self::invalidate)
+/// - [`crate::Signal`] - Used for the [`changed`](Self::changed) notification
 ///
 /// # Example
 ///
@@ -258,66 +368,179 @@ impl<'a, T: Clone> ReadOnlyProperty<'a, T> {
 ///
 /// assert_eq!(full_name.get(), "John Doe");
 ///
-/// // After changing a dependency, invalidate to recalculate
+/// // Changing a dependency marks the binding dirty
 /// first_name.set("Jane".to_string());
-/// full_name.invalidate();
+/// assert!(full_name.is_dirty());
 /// assert_eq!(full_name.get(), "Jane Doe");
+///
+/// // Bindings can depend on other bindings
+/// let full = full_name.clone();
+/// let greeting = Binding::new(move || format!("Hello, {}!", full.get()));
+/// assert_eq!(greeting.get(), "Hello, Jane Doe!");
+///
+/// last_name.set("Smith".to_string());
+/// assert_eq!(greeting.get(), "Hello, Jane Smith!");
 /// ```
 pub struct Binding<T> {
+    node: Arc<BindingNode<T>>,
+}
+
+type ValueSink<T> = Box<dyn FnMut(T) -> bool + Send>;
+
+struct BindingNode<T> {
     /// The computation function.
     compute: Box<dyn Fn() -> T + Send + Sync>,
     /// Cached value.
     cached: RwLock<Option<T>>,
     /// Whether the cache needs refreshing.
     dirty: AtomicBool,
+    /// Dependency lists this binding is subscribed to.
+    sources: Mutex<Vec<Weak<Dependents>>>,
+    /// Bindings that read this binding.
+    dependents: Arc<Dependents>,
+    /// Consumers of the value, pushed on every change, with their IDs.
+    sinks: Mutex<Vec<(u64, ValueSink<T>)>>,
+    /// ID of the next connected sink.
+    next_sink_id: AtomicU64,
+    /// Whether the sinks are being updated, to break update cycles.
+    pushing: AtomicBool,
+    /// Emitted when the binding becomes dirty.
+    changed: Signal<()>,
+}
+
+impl<T: Clone + Send + Sync + 'static> BindingNode<T> {
+    fn value(self: &Arc<Self>) -> T {
+        if self.dirty.swap(false, Ordering::AcqRel) || self.cached.read().is_none() {
+            let value = self.evaluate();
+            *self.cached.write() = Some(value.clone());
+            value
+        } else {
+            self.cached.read().clone().unwrap()
+        }
+    }
+
+    /// Run the computation and subscribe to the sources it read.
+    fn evaluate(self: &Arc<Self>) -> T {
+        let frame = TrackingFrame::push();
+        let value = (self.compute)();
+        let sources = frame.finish();
+
+        let this: Weak<dyn Dependent> = Arc::downgrade(self) as Weak<dyn Dependent>;
+        let mut subscribed = self.sources.lock();
+        for source in subscribed.drain(..).filter_map(|s| s.upgrade()) {
+            source.remove(&this);
+        }
+        for source in &sources {
+            source.add(&this);
+        }
+        *subscribed = sources.iter().map(Arc::downgrade).collect();
+        value
+    }
+
+    fn push_to_sinks(self: &Arc<Self>) {
+        if self.sinks.lock().is_empty() || self.pushing.swap(true, Ordering::AcqRel) {
+            return;
+        }
+        let mut guard = PushGuard {
+            node: self,
+            sinks: Vec::new(),
+        };
+        let value = self.value();
+        guard.sinks = std::mem::take(&mut *self.sinks.lock());
+        guard.sinks.retain_mut(|(_, sink)| sink(value.clone()));
+    }
+
+    /// Connect `sink` unless it disconnects itself on the current value.
+    fn add_sink(self: &Arc<Self>, mut sink: ValueSink<T>) -> Option<u64> {
+        if !sink(self.value()) {
+            return None;
+        }
+        let id = self.next_sink_id.fetch_add(1, Ordering::Relaxed);
+        self.sinks.lock().push((id, sink));
+        Some(id)
+    }
+
+    fn remove_sink(&self, id: u64) {
+        self.sinks.lock().retain(|(sink_id, _)| *sink_id != id);
+    }
+}
+
+/// Puts the sinks taken for a push back and ends the push, even if a sink
+/// panics.
+struct PushGuard<'a, T> {
+    node: &'a BindingNode<T>,
+    sinks: Vec<(u64, ValueSink<T>)>,
+}
+
+impl<T> Drop for PushGuard<'_, T> {
+    fn drop(&mut self) {
+        // Keep sinks connected while the others were running.
+        let mut current = self.node.sinks.lock();
+        let mut sinks = std::mem::take(&mut self.sinks);
+        sinks.append(&mut current);
+        *current = sinks;
+        drop(current);
+        self.node.pushing.store(false, Ordering::Release);
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> Dependent for BindingNode<T> {
+    fn mark_dirty(self: Arc<Self>) {
+        if self.dirty.swap(true, Ordering::AcqRel) {
+            // Already dirty: dependents were notified when it became dirty.
+            return;
+        }
+        self.changed.emit(());
+        self.dependents.notify();
+        self.push_to_sinks();
+    }
 }
 
 impl<T: Clone + Send + Sync + 'static> Binding<T> {
     /// Create a new binding with a computation function.
     ///
     /// The function will be called lazily when `get()` is first called,
-    /// and again after each `invalidate()` call.
+    /// and again after a dependency changes or `invalidate()` is called.
     pub fn new<F>(compute: F) -> Self
     where
         F: Fn() -> T + Send + Sync + 'static,
     {
         Self {
-            compute: Box::new(compute),
-            cached: RwLock::new(None),
-            dirty: AtomicBool::new(true),
+            node: Arc::new(BindingNode {
+                compute: Box::new(compute),
+                cached: RwLock::new(None),
+                dirty: AtomicBool::new(true),
+                sources: Mutex::new(Vec::new()),
+                dependents: Arc::default(),
+                sinks: Mutex::new(Vec::new()),
+                next_sink_id: AtomicU64::new(0),
+                pushing: AtomicBool::new(false),
+                changed: Signal::new(),
+            }),
         }
     }
 
     /// Get the current value, computing it if necessary.
     ///
     /// If the binding is dirty or has never been computed, the computation
-    /// function is called and the result is cached.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the internal `RwLock` is poisoned (i.e., a thread panicked
-    /// while holding the lock).
+    /// function is called and the result is cached. Reading a binding
+    /// inside another binding's computation records it as a dependency.
     pub fn get(&self) -> T {
-        if self.dirty.load(Ordering::Acquire) || self.cached.read().is_none() {
-            let value = (self.compute)();
-            *self.cached.write() = Some(value.clone());
-            self.dirty.store(false, Ordering::Release);
-            value
-        } else {
-            self.cached.read().clone().unwrap()
-        }
+        self.node.dependents.track();
+        self.node.value()
     }
 
     /// Mark the binding as dirty, causing recalculation on next `get()`.
     ///
-    /// Call this when any dependency of the binding changes.
+    /// Dependencies are tracked automatically; this is only needed when the
+    /// computation reads state that is not a [`Property`] or `Binding`.
     pub fn invalidate(&self) {
-        self.dirty.store(true, Ordering::Release);
+        self.node.clone().mark_dirty();
     }
 
     /// Check if the binding needs recalculation.
     pub fn is_dirty(&self) -> bool {
-        self.dirty.load(Ordering::Acquire)
+        self.node.dirty.load(Ordering::Acquire)
     }
 
     /// Force immediate recalculation and return the new value.
@@ -325,13 +548,78 @@ impl<T: Clone + Send + Sync + 'static> Binding<T> {
         self.invalidate();
         self.get()
     }
+
+    /// Signal emitted when the binding becomes dirty.
+    ///
+    /// The signal fires once per change: read the value to re-arm it.
+    pub fn changed(&self) -> &Signal<()> {
+        &self.node.changed
+    }
+
+    /// Pass the current value to `sink`, and again whenever it changes.
+    ///
+    /// The sink returns `false` to disconnect itself. Sinks are owned by the
+    /// binding, so a sink that captures a clone of the binding keeps it
+    /// alive.
+    pub fn connect_value<F>(&self, sink: F)
+    where
+        F: FnMut(T) -> bool + Send + 'static,
+    {
+        self.node.add_sink(Box::new(sink));
+    }
+
+    /// Use the binding as the value source of an object's property.
+    ///
+    /// `setter` is called with the current value now and with each new
+    /// value, until the target object is destroyed. The setter is dropped
+    /// when the target is destroyed.
+    pub fn bind_to<F>(&self, target: ObjectId, mut setter: F)
+    where
+        F: FnMut(T) + Send + 'static,
+    {
+        let Ok(registry) = global_registry() else {
+            return;
+        };
+        // The alive check covers a target destroyed while a push is running.
+        let sink = self.node.add_sink(Box::new(move |value| {
+            let alive = global_registry().is_ok_and(|registry| registry.contains(target));
+            if alive {
+                setter(value);
+            }
+            alive
+        }));
+        if let Some(sink) = sink {
+            let node = Arc::downgrade(&self.node);
+            let registered = registry.on_destroyed(target, move || {
+                if let Some(node) = node.upgrade() {
+                    node.remove_sink(sink);
+                }
+            });
+            if registered.is_err() {
+                self.node.remove_sink(sink);
+            }
+        }
+    }
+
+    /// Get the number of connected value sinks.
+    pub fn sink_count(&self) -> usize {
+        self.node.sinks.lock().len()
+    }
+}
+
+impl<T> Clone for Binding<T> {
+    fn clone(&self) -> Self {
+        Self {
+            node: self.node.clone(),
+        }
+    }
 }
 
 impl<T: Clone + fmt::Debug + Send + Sync + 'static> fmt::Debug for Binding<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("Binding")
             .field("dirty", &self.is_dirty())
-            .field("cached", &*self.cached.read())
+            .field("cached", &*self.node.cached.read())
             .finish()
     }
 }
@@ -589,6 +877,152 @@ mod tests {
         assert_eq!(binding.refresh(), 15);
     }
 
+    #[test]
+    fn test_binding_tracks_dependencies() {
+        let use_a = Arc::new(Property::new(true));
+        let a = Arc::new(Property::new(1));
+        let b = Arc::new(Property::new(2));
+
+        let (use_a_c, a_c, b_c) = (use_a.clone(), a.clone(), b.clone());
+        let binding = Binding::new(move || if use_a_c.get() { a_c.get() } else { b_c.get() });
+        assert_eq!(binding.get(), 1);
+
+        // Only properties read by the last computation are dependencies.
+        b.set(20);
+        assert!(!binding.is_dirty());
+        a.set(10);
+        assert!(binding.is_dirty());
+        assert_eq!(binding.get(), 10);
+
+        use_a.set(false);
+        assert_eq!(binding.get(), 20);
+        a.set(100);
+        assert!(!binding.is_dirty());
+
+        // Silent sets are only seen after an explicit notification.
+        b.set_silent(30);
+        assert!(!binding.is_dirty());
+        b.notify_bindings();
+        assert_eq!(binding.get(), 30);
+    }
+
+    #[test]
+    fn test_binding_chain_and_changed_signal() {
+        use std::sync::atomic::AtomicUsize;
+
+        let width = Arc::new(Property::new(2.0f32));
+        let w = width.clone();
+        let area = Binding::new(move || w.get() * w.get());
+        let a = area.clone();
+        let label = Binding::new(move || format!("{:.0}", a.get()));
+        assert_eq!(label.get(), "4");
+
+        let changes = Arc::new(AtomicUsize::new(0));
+        let changes_clone = changes.clone();
+        label.changed().connect(move |_| {
+            changes_clone.fetch_add(1, Ordering::SeqCst);
+        });
+
+        width.set(3.0);
+        width.set(4.0);
+        assert_eq!(changes.load(Ordering::SeqCst), 1);
+        assert_eq!(label.get(), "16");
+        width.set(5.0);
+        assert_eq!(changes.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_binding_value_sinks() {
+        let source = Arc::new(Property::new(1));
+        let s = source.clone();
+        let doubled = Binding::new(move || s.get() * 2);
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        doubled.connect_value(move |v| {
+            received_clone.lock().push(v);
+            v < 6
+        });
+
+        source.set(2);
+        source.set(3);
+        source.set(4);
+        assert_eq!(*received.lock(), vec![2, 4, 6]);
+        assert_eq!(doubled.sink_count(), 0);
+    }
+
+    #[test]
+    fn test_binding_survives_panicking_sink() {
+        let source = Arc::new(Property::new(1));
+        let s = source.clone();
+        let binding = Binding::new(move || s.get());
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let received_clone = received.clone();
+        binding.connect_value(move |v| {
+            received_clone.lock().push(v);
+            true
+        });
+        binding.connect_value(|v| {
+            assert!(v != 2, "sink failed");
+            true
+        });
+
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| source.set(2)));
+        assert!(result.is_err());
+
+        // The binding keeps pushing to its sinks after the panic.
+        source.set(3);
+        assert_eq!(*received.lock(), vec![1, 2, 3]);
+        assert_eq!(binding.sink_count(), 2);
+    }
+
+    struct Target {
+        base: crate::ObjectBase,
+    }
+
+    impl crate::Object for Target {
+        fn object_id(&self) -> ObjectId {
+            self.base.id()
+        }
+    }
+
+    #[test]
+    fn test_binding_teardown() {
+        crate::init_global_registry();
+        let source = Arc::new(Property::new(1));
+
+        // Bound targets are released once the object is destroyed.
+        let target = Target {
+            base: crate::ObjectBase::new::<Target>(),
+        };
+        let target_value = Arc::new(Property::new(0));
+        let s = source.clone();
+        let binding = Binding::new(move || s.get() + 1);
+        let tv = target_value.clone();
+        binding.bind_to(target.base.id(), move |v| tv.set_silent(v));
+        assert_eq!(target_value.get(), 2);
+        source.set(5);
+        assert_eq!(target_value.get(), 6);
+
+        drop(target);
+        assert_eq!(binding.sink_count(), 0);
+        assert_eq!(Arc::strong_count(&target_value), 1);
+        source.set(7);
+        assert_eq!(target_value.get(), 6);
+
+        // Dropped bindings are pruned from their sources.
+        drop(binding);
+        let s = source.clone();
+        let other = Binding::new(move || s.get());
+        other.get();
+        assert_eq!(source.dependents.list.lock().len(), 1);
+
+        // Bindings keep their last value when a source is dropped.
+        drop(source);
+        assert_eq!(other.get(), 7);
+    }
+
     #[test]
     fn test_property_meta() {
         let meta = PropertyMeta::new::<i32>("count", "i32", false, Some("count_changed"));
@@ -3,7 +3,7 @@
 //! Provides one-shot and repeating timers that integrate with the event loop.
 
 use std::cmp::Ordering;
-use std::collections::BinaryHeap;
+use std::collections::{BinaryHeap, HashMap};
 use std::time::{Duration, Instant};
 
 use parking_lot::Mutex;
@@ -236,9 +236,14 @@ impl Default for TimerManager {
     }
 }
 
+/// A callback invoked on the event loop thread when its timer fires.
+type TimerCallback = Box<dyn FnMut() + Send + 'static>;
+
 /// A thread-safe wrapper around `TimerManager` for use from the application.
 pub(crate) struct SharedTimerManager {
     inner: Mutex<TimerManager>,
+    /// Callbacks of timers started with a callback, keyed by timer.
+    callbacks: Mutex<HashMap<TimerId, TimerCallback>>,
 }
 
 #[allow(dead_code)]
@@ -246,6 +251,7 @@ impl SharedTimerManager {
     pub fn new() -> Self {
         Self {
             inner: Mutex::new(TimerManager::new()),
+            callbacks: Mutex::new(HashMap::new()),
         }
     }
 
@@ -257,10 +263,33 @@ impl SharedTimerManager {
         self.inner.lock().start_repeating(interval)
     }
 
+    pub fn start_repeating_with(&self, interval: Duration, callback: TimerCallback) -> TimerId {
+        let id = self.inner.lock().start_repeating(interval);
+        self.callbacks.lock().insert(id, callback);
+        id
+    }
+
     pub fn stop(&self, id: TimerId) -> Result<()> {
+        self.callbacks.lock().remove(&id);
         self.inner.lock().stop(id)
     }
 
+    /// Run the callback of a fired timer.
+    ///
+    /// Returns `false` if the timer has no callback, in which case its event
+    /// should be dispatched to the event handler instead. The callback runs
+    /// without any lock held, so it may start or stop timers itself.
+    pub fn run_callback(&self, id: TimerId) -> bool {
+        let Some(mut callback) = self.callbacks.lock().remove(&id) else {
+            return false;
+        };
+        callback();
+        if self.is_active(id) {
+            self.callbacks.lock().entry(id).or_insert(callback);
+        }
+        true
+    }
+
     pub fn is_active(&self, id: TimerId) -> bool {
         self.inner.lock().is_active(id)
     }
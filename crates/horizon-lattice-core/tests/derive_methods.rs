@@ -0,0 +1,173 @@
+//! Integration tests for the #[methods] attribute macro.
+
+use horizon_lattice_core::meta::MetaError;
+use horizon_lattice_core::object::init_global_registry;
+use horizon_lattice_core::property::Property;
+use horizon_lattice_core::signal::Signal;
+use horizon_lattice_core::{Object, connect_by_name, disconnect_by_name, object::ObjectBase};
+use horizon_lattice_macros::{Object, methods};
+use parking_lot::Mutex;
+use std::any::TypeId;
+use std::sync::Arc;
+
+fn setup() {
+    init_global_registry();
+}
+
+#[derive(Object)]
+#[object(methods, no_factory)]
+struct Document {
+    base: ObjectBase,
+
+    #[property]
+    path: Property<String>,
+
+    #[property]
+    reloads: Property<i32>,
+}
+
+#[methods]
+impl Document {
+    fn new() -> Self {
+        Self {
+            base: ObjectBase::new::<Self>(),
+            path: Property::new(String::new()),
+            reloads: Property::new(0),
+        }
+    }
+
+    #[slot]
+    fn reload(&mut self) {
+        self.reloads.set(self.reloads.get() + 1);
+    }
+
+    #[slot]
+    fn open(&mut self, path: String) {
+        self.path.set(path);
+    }
+
+    #[invokable(name = "lineCount")]
+    fn line_count(&self, bytes: usize, width: usize) -> usize {
+        bytes.div_ceil(width)
+    }
+
+    // Not registered.
+    fn helper(&self) -> i32 {
+        self.reloads.get()
+    }
+}
+
+#[allow(dead_code)] // Signal fields are tested via macro-generated accessors
+#[derive(Object)]
+#[object(no_factory)]
+struct FileDialog {
+    base: ObjectBase,
+
+    #[signal]
+    accepted: Signal<()>,
+
+    #[signal]
+    file_selected: Signal<(String, bool)>,
+
+    #[signal]
+    size_selected: Signal<usize>,
+}
+
+impl FileDialog {
+    fn new() -> Self {
+        Self {
+            base: ObjectBase::new::<Self>(),
+            accepted: Signal::new(),
+            file_selected: Signal::new(),
+            size_selected: Signal::new(),
+        }
+    }
+}
+
+#[test]
+fn test_methods_are_registered() {
+    setup();
+    let doc = Document::new();
+    let meta = doc.meta_object().unwrap();
+
+    assert_eq!(meta.method_names(), vec!["reload", "open", "lineCount"]);
+    let open = meta.method("open").unwrap();
+    assert_eq!(open.param_types, &["String"]);
+    assert_eq!(open.param_type_ids(), &[TypeId::of::<String>()]);
+    assert_eq!(open.return_type, "()");
+    assert_eq!(meta.method("lineCount").unwrap().return_type, "usize");
+    assert!(meta.method("helper").is_none());
+    assert_eq!(doc.helper(), 0);
+}
+
+#[test]
+fn test_invoke_by_name() {
+    setup();
+    let mut doc = Document::new();
+    let meta = Document::META;
+
+    meta.invoke(&mut doc, "reload", &[]).unwrap();
+    meta.invoke(&mut doc, "open", &[Box::new("a.txt".to_string())])
+        .unwrap();
+    assert_eq!(doc.reloads.get(), 1);
+    assert_eq!(doc.path.get(), "a.txt");
+
+    let lines = meta
+        .invoke(
+            &mut doc,
+            "lineCount",
+            &[Box::new(250usize), Box::new(80usize)],
+        )
+        .unwrap();
+    assert_eq!(*lines.downcast::<usize>().unwrap(), 4);
+
+    let result = meta.invoke(&mut doc, "open", &[Box::new("a.txt")]);
+    assert_eq!(
+        result.unwrap_err(),
+        MetaError::ArgumentTypeMismatch {
+            index: 0,
+            expected: "String"
+        }
+    );
+}
+
+#[test]
+fn test_connect_signals_to_slots_by_name() {
+    setup();
+    let dialog = FileDialog::new();
+    let doc = Arc::new(Mutex::new(Document::new()));
+
+    // Slots may ignore trailing signal arguments.
+    connect_by_name(&dialog, "file_selected", &doc, "open").unwrap();
+    let reload = connect_by_name(&dialog, "accepted", &doc, "reload").unwrap();
+
+    dialog.file_selected.emit(("b.txt".to_string(), true));
+    dialog.accepted.emit(());
+    assert_eq!(doc.lock().path.get(), "b.txt");
+    assert_eq!(doc.lock().reloads.get(), 1);
+
+    assert!(disconnect_by_name(&dialog, "accepted", reload).unwrap());
+    dialog.accepted.emit(());
+    assert_eq!(doc.lock().reloads.get(), 1);
+
+    assert!(matches!(
+        connect_by_name(&dialog, "size_selected", &doc, "open"),
+        Err(MetaError::ArgumentTypeMismatch { index: 0, .. })
+    ));
+    assert!(matches!(
+        connect_by_name(&dialog, "accepted", &doc, "open"),
+        Err(MetaError::ArgumentCount { .. })
+    ));
+    assert!(matches!(
+        connect_by_name(&dialog, "closed", &doc, "reload"),
+        Err(MetaError::SignalNotFound { .. })
+    ));
+    assert!(matches!(
+        connect_by_name(&dialog, "accepted", &doc, "helper"),
+        Err(MetaError::MethodNotFound { .. })
+    ));
+
+    // Connections don't keep the receiver alive.
+    drop(doc);
+    dialog.file_selected.emit(("c.txt".to_string(), false));
+}
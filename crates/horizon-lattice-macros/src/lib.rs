@@ -1,7 +1,12 @@
 //! Procedural macros for Horizon Lattice meta-object system.
 //!
 //! This crate provides the `#[derive(Object)]` macro and related attribute macros
-//! for defining signals, properties, and slots.
+//! for defining signals, properties, and slots, and the `#[derive(ModelRow)]`
+//! macro for showing structs as rows of a table model.
+//!
+//! Signals declared with `#[signal]` can be connected dynamically through the
+//! meta-object, and methods marked in a `#[methods]` impl block can be invoked
+//! by name, so signals can be connected to slots by name at runtime.
 //!
 //! # Attributes
 //!
@@ -53,12 +58,80 @@
 //!     // ...
 //! }
 //! ```
+//!
+//! Object attributes:
+//! - `no_factory`: Don't generate a factory function (no `Default` required)
+//! - `methods`: Register the methods of the type's `#[methods]` impl block
+//!
+//! ## `#[methods]`
+//!
+//! Attribute for an `impl` block that registers methods marked `#[slot]` or
+//! `#[invokable]` for dynamic invocation:
+//!
+//! ```ignore
+//! #[derive(Object)]
+//! #[object(methods)]
+//! struct Document {
+//!     base: ObjectBase,
+//!
+//!     #[signal]
+//!     path_changed: Signal<String>,
+//! }
+//!
+//! #[methods]
+//! impl Document {
+//!     #[slot]
+//!     fn reload(&mut self) { /* ... */ }
+//!
+//!     #[invokable(name = "lineCount")]
+//!     fn line_count(&self) -> usize { /* ... */ }
+//!
+//!     #[slot]
+//!     fn open(&mut self, path: String) { /* ... */ }
+//! }
+//!
+//! let count = Document::META.invoke(&mut doc, "lineCount", &[])?;
+//! connect_by_name(&other, "path_changed", &document, "open")?;
+//! ```
+//!
+//! Method attributes:
+//! - `name = "method_name"`: Registers the method under a different name
+//!
+//! Registered methods must take `self` by reference, and their parameters
+//! must be owned `Clone` types; arguments are type-checked before the call.
+//!
+//! ## `#[column]`
+//!
+//! Configures a field of a `#[derive(ModelRow)]` struct as a table column:
+//!
+//! ```ignore
+//! #[derive(ModelRow)]
+//! struct Task {
+//!     #[column(header = "Title", role = Display, editable)]
+//!     title: String,
+//!
+//!     #[column(header = "Done", role = CheckState, editable)]
+//!     done: bool,
+//!
+//!     #[column(skip)]
+//!     id: u64,
+//! }
+//!
+//! let model = TableModel::from_rows(tasks);
+//! ```
+//!
+//! Column attributes:
+//! - `header = "Header"`: The column header (defaults to the field name)
+//! - `role = Role`: The `ItemRole` that returns the field (defaults to `Display`)
+//! - `editable`: Writes edits back to the field
+//! - `skip`: The field isn't a column
 
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::{format_ident, quote};
 use syn::{
-    Attribute, Data, DeriveInput, Expr, ExprLit, Field, Fields, Ident, Lit, Type, parse_macro_input,
+    Attribute, Data, DeriveInput, Expr, ExprLit, Field, Fields, FnArg, Ident, ImplItem, ItemImpl,
+    Lit, ReturnType, Type, parse_macro_input,
 };
 
 /// Derive the `Object` trait and generate meta-object information.
@@ -119,6 +192,7 @@ struct SignalInfo {
 /// Parsed struct-level object attributes.
 struct ObjectAttrs {
     no_factory: bool,
+    methods: bool,
 }
 
 fn impl_derive_object(input: &DeriveInput) -> syn::Result<TokenStream2> {
@@ -178,8 +252,18 @@ fn impl_derive_object(input: &DeriveInput) -> syn::Result<TokenStream2> {
     // Generate property metadata array
     let property_meta = generate_property_meta(struct_name, &properties);
 
+    // Generate dynamic connect/disconnect functions for signals
+    let signal_fns = generate_signal_fns(struct_name, &signals);
+
     // Generate signal metadata array
-    let signal_meta = generate_signal_meta(&signals);
+    let signal_meta = generate_signal_meta(struct_name, &signals);
+
+    // Link the methods registered by #[methods]
+    let methods = if object_attrs.methods {
+        quote! { #struct_name::METHODS }
+    } else {
+        quote! { &[] }
+    };
 
     // Generate factory function
     let factory = if object_attrs.no_factory {
@@ -194,6 +278,8 @@ fn impl_derive_object(input: &DeriveInput) -> syn::Result<TokenStream2> {
     let expanded = quote! {
         #getter_setter_fns
 
+        #signal_fns
+
         /// Static meta-object for this type (generated by #[derive(Object)]).
         #[allow(non_upper_case_globals)]
         static #meta_object_name: horizon_lattice_core::meta::MetaObject = horizon_lattice_core::meta::MetaObject {
@@ -202,7 +288,7 @@ fn impl_derive_object(input: &DeriveInput) -> syn::Result<TokenStream2> {
             parent: None,
             properties: &#property_meta,
             signals: &#signal_meta,
-            methods: &[],
+            methods: #methods,
             create: #factory,
         };
 
@@ -247,7 +333,10 @@ fn impl_derive_object(input: &DeriveInput) -> syn::Result<TokenStream2> {
 
 /// Parse struct-level #[object(...)] attributes.
 fn parse_object_attrs(attrs: &[Attribute]) -> syn::Result<ObjectAttrs> {
-    let mut result = ObjectAttrs { no_factory: false };
+    let mut result = ObjectAttrs {
+        no_factory: false,
+        methods: false,
+    };
 
     for attr in attrs {
         if !attr.path().is_ident("object") {
@@ -257,6 +346,8 @@ fn parse_object_attrs(attrs: &[Attribute]) -> syn::Result<ObjectAttrs> {
         attr.parse_nested_meta(|meta| {
             if meta.path.is_ident("no_factory") {
                 result.no_factory = true;
+            } else if meta.path.is_ident("methods") {
+                result.methods = true;
             }
             Ok(())
         })?;
@@ -559,8 +650,82 @@ fn generate_property_meta(struct_name: &Ident, properties: &[PropertyInfo]) -> T
     quote! { [#(#meta_entries),*] }
 }
 
+/// Element types of a signal's argument type: `()` has none, a tuple has
+/// one per element, and any other type is a single argument.
+fn signal_arg_types(ty: &Type) -> Vec<Type> {
+    match ty {
+        Type::Tuple(tuple) => tuple.elems.iter().cloned().collect(),
+        _ => vec![ty.clone()],
+    }
+}
+
+/// Generate type-erased connect and disconnect functions for signals.
+fn generate_signal_fns(struct_name: &Ident, signals: &[SignalInfo]) -> TokenStream2 {
+    let fns: Vec<TokenStream2> = signals
+        .iter()
+        .map(|signal| {
+            let field_name = &signal.field_name;
+            let args_type = &signal.args_type;
+            let connect_name = format_ident!(
+                "__{}_{}_connect",
+                struct_name.to_string().to_lowercase(),
+                field_name
+            );
+            let disconnect_name = format_ident!(
+                "__{}_{}_disconnect",
+                struct_name.to_string().to_lowercase(),
+                field_name
+            );
+
+            let boxed_args: Vec<TokenStream2> = match args_type {
+                Type::Tuple(tuple) => (0..tuple.elems.len())
+                    .map(|i| {
+                        let index = syn::Index::from(i);
+                        quote! { Box::new(args.#index.clone()) as Box<dyn std::any::Any> }
+                    })
+                    .collect(),
+                _ => vec![quote! { Box::new(args.clone()) as Box<dyn std::any::Any> }],
+            };
+
+            // Signals without arguments don't read them.
+            let args_pat = if boxed_args.is_empty() {
+                quote! { _args }
+            } else {
+                quote! { args }
+            };
+
+            quote! {
+                #[allow(non_snake_case)]
+                fn #connect_name(
+                    obj: &dyn horizon_lattice_core::Object,
+                    slot: horizon_lattice_core::meta::DynamicSlot,
+                ) -> horizon_lattice_core::signal::ConnectionId {
+                    let typed = horizon_lattice_core::object_cast::<#struct_name>(obj)
+                        .expect("object_cast failed in generated connect");
+                    typed.#field_name.connect(move |#args_pat: &#args_type| {
+                        let boxed: Vec<Box<dyn std::any::Any>> = vec![#(#boxed_args),*];
+                        slot(&boxed);
+                    })
+                }
+
+                #[allow(non_snake_case)]
+                fn #disconnect_name(
+                    obj: &dyn horizon_lattice_core::Object,
+                    id: horizon_lattice_core::signal::ConnectionId,
+                ) -> bool {
+                    let typed = horizon_lattice_core::object_cast::<#struct_name>(obj)
+                        .expect("object_cast failed in generated disconnect");
+                    typed.#field_name.disconnect(id)
+                }
+            }
+        })
+        .collect();
+
+    quote! { #(#fns)* }
+}
+
 /// Generate signal metadata array.
-fn generate_signal_meta(signals: &[SignalInfo]) -> TokenStream2 {
+fn generate_signal_meta(struct_name: &Ident, signals: &[SignalInfo]) -> TokenStream2 {
     let meta_entries: Vec<TokenStream2> = signals
         .iter()
         .enumerate()
@@ -573,16 +738,468 @@ fn generate_signal_meta(signals: &[SignalInfo]) -> TokenStream2 {
                     quote! { #name }
                 })
                 .collect();
+            let param_type_ids: Vec<TokenStream2> = signal_arg_types(&signal.args_type)
+                .iter()
+                .map(|ty| quote! { std::any::TypeId::of::<#ty>() })
+                .collect();
+            let connect_name = format_ident!(
+                "__{}_{}_connect",
+                struct_name.to_string().to_lowercase(),
+                signal.field_name
+            );
+            let disconnect_name = format_ident!(
+                "__{}_{}_disconnect",
+                struct_name.to_string().to_lowercase(),
+                signal.field_name
+            );
 
             quote! {
-                horizon_lattice_core::meta::SignalMeta {
-                    name: #signal_name_str,
-                    param_types: &[#(#param_types),*],
-                    index: #index,
-                }
+                horizon_lattice_core::meta::SignalMeta::with_connection(
+                    #signal_name_str,
+                    &[#(#param_types),*],
+                    &[#(#param_type_ids),*],
+                    #index,
+                    #connect_name,
+                    #disconnect_name,
+                )
             }
         })
         .collect();
 
     quote! { [#(#meta_entries),*] }
 }
+
+/// Register methods of an `impl` block for dynamic invocation.
+///
+/// Methods marked `#[slot]` or `#[invokable]` get a `MethodMeta` entry in
+/// the type's `METHODS` table, which `#[object(methods)]` links into the
+/// type's `MetaObject`. Other methods are left untouched.
+///
+/// # Example
+///
+/// ```ignore
+/// #[methods]
+/// impl Counter {
+///     #[slot]
+///     fn reset(&mut self) {
+///         self.value.set(0);
+///     }
+///
+///     #[invokable(name = "addTo")]
+///     fn add_to(&self, amount: i32) -> i32 {
+///         self.value.get() + amount
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn methods(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as ItemImpl);
+
+    match impl_methods(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Parsed registered method information.
+struct MethodInfo {
+    fn_name: Ident,
+    meta_name: String,
+    param_types: Vec<Type>,
+    return_type: Type,
+}
+
+fn impl_methods(mut input: ItemImpl) -> syn::Result<TokenStream2> {
+    if input.trait_.is_some() {
+        return Err(syn::Error::new_spanned(
+            &input,
+            "#[methods] only supports inherent impl blocks",
+        ));
+    }
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "#[methods] does not support generic impl blocks",
+        ));
+    }
+
+    let self_ty = input.self_ty.clone();
+    let type_name = match &*self_ty {
+        Type::Path(type_path) => match type_path.path.segments.last() {
+            Some(segment) => segment.ident.to_string().to_lowercase(),
+            None => return Err(syn::Error::new_spanned(&self_ty, "unsupported self type")),
+        },
+        _ => return Err(syn::Error::new_spanned(&self_ty, "unsupported self type")),
+    };
+
+    let mut methods = Vec::new();
+    for item in &mut input.items {
+        if let ImplItem::Fn(method) = item
+            && let Some(info) = parse_method(method)?
+        {
+            methods.push(info);
+        }
+    }
+
+    let invokers = methods.iter().map(|method| {
+        let fn_name = &method.fn_name;
+        let invoker_name = format_ident!("__{}_{}_invoke", type_name, fn_name);
+        let args = method.param_types.iter().enumerate().map(|(i, ty)| {
+            quote! {
+                args[#i]
+                    .downcast_ref::<#ty>()
+                    .expect("argument type checked in MethodMeta::call")
+                    .clone()
+            }
+        });
+
+        quote! {
+            #[allow(non_snake_case)]
+            fn #invoker_name(
+                obj: &mut dyn horizon_lattice_core::Object,
+                args: &[Box<dyn std::any::Any>],
+            ) -> Box<dyn std::any::Any> {
+                let typed = horizon_lattice_core::object_cast_mut::<#self_ty>(obj)
+                    .expect("object_cast_mut failed in generated invoker");
+                Box::new(typed.#fn_name(#(#args),*))
+            }
+        }
+    });
+
+    let meta_entries = methods.iter().map(|method| {
+        let meta_name = &method.meta_name;
+        let invoker_name = format_ident!("__{}_{}_invoke", type_name, method.fn_name);
+        let param_type_names = method.param_types.iter().map(type_to_string);
+        let param_types = &method.param_types;
+        let return_type = &method.return_type;
+        let return_type_name = type_to_string(return_type);
+
+        quote! {
+            horizon_lattice_core::meta::MethodMeta::with_param_types(
+                #meta_name,
+                &[#(#param_type_names),*],
+                &[#(std::any::TypeId::of::<#param_types>()),*],
+                #return_type_name,
+                #invoker_name,
+            )
+        }
+    });
+
+    Ok(quote! {
+        #input
+
+        #(#invokers)*
+
+        impl #self_ty {
+            /// Methods registered for dynamic invocation (generated by #[methods]).
+            ///
+            /// Linked into the type's `MetaObject` by `#[object(methods)]`.
+            #[allow(dead_code)]
+            pub const METHODS: &'static [horizon_lattice_core::meta::MethodMeta] = &[
+                #(#meta_entries),*
+            ];
+        }
+    })
+}
+
+/// Parse a method marked `#[slot]` or `#[invokable]`, removing the marker.
+fn parse_method(method: &mut syn::ImplItemFn) -> syn::Result<Option<MethodInfo>> {
+    let mut registered = false;
+    let mut meta_name = method.sig.ident.to_string();
+
+    for attr in &method.attrs {
+        if !attr.path().is_ident("slot") && !attr.path().is_ident("invokable") {
+            continue;
+        }
+        registered = true;
+        if matches!(attr.meta, syn::Meta::List(_)) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    let value: syn::LitStr = meta.value()?.parse()?;
+                    meta_name = value.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `name = \"...\"`"))
+                }
+            })?;
+        }
+    }
+    method
+        .attrs
+        .retain(|attr| !attr.path().is_ident("slot") && !attr.path().is_ident("invokable"));
+
+    if !registered {
+        return Ok(None);
+    }
+
+    let sig = &method.sig;
+    match sig.receiver() {
+        Some(receiver) if receiver.reference.is_some() => {}
+        _ => {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "registered methods must take `&self` or `&mut self`",
+            ));
+        }
+    }
+    if !sig.generics.params.is_empty() || sig.asyncness.is_some() {
+        return Err(syn::Error::new_spanned(
+            sig,
+            "registered methods cannot be generic or async",
+        ));
+    }
+
+    let mut param_types = Vec::new();
+    for input in &sig.inputs {
+        if let FnArg::Typed(pat_type) = input {
+            if let Type::Reference(_) = &*pat_type.ty {
+                return Err(syn::Error::new_spanned(
+                    &pat_type.ty,
+                    "registered method parameters must be owned types",
+                ));
+            }
+            param_types.push((*pat_type.ty).clone());
+        }
+    }
+
+    let return_type = match &sig.output {
+        ReturnType::Default => syn::parse_quote!(()),
+        ReturnType::Type(_, ty) => (**ty).clone(),
+    };
+
+    Ok(Some(MethodInfo {
+        fn_name: sig.ident.clone(),
+        meta_name,
+        param_types,
+        return_type,
+    }))
+}
+
+/// Derive the `ModelRow` trait, which shows a struct as a row of a table model.
+///
+/// Every named field is a column, in declaration order, unless marked
+/// `#[column(skip)]`. Field types must implement `ColumnValue`.
+///
+/// # Example
+///
+/// ```ignore
+/// use horizon_lattice::model::{ModelRow, TableModel};
+///
+/// #[derive(ModelRow)]
+/// struct Contact {
+///     #[column(header = "Name", editable)]
+///     name: String,
+///     #[column(header = "Favorite", role = CheckState, editable)]
+///     favorite: bool,
+///     #[column(skip)]
+///     id: u64,
+/// }
+///
+/// let model = TableModel::from_rows(contacts);
+/// ```
+#[proc_macro_derive(ModelRow, attributes(column))]
+pub fn derive_model_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match impl_derive_model_row(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// Parsed column information.
+struct ColumnInfo {
+    field_name: Ident,
+    header: String,
+    role: Expr,
+    editable: bool,
+}
+
+fn impl_derive_model_row(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "ModelRow derive only supports structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "ModelRow derive only supports structs",
+            ));
+        }
+    };
+
+    let mut columns = Vec::new();
+    for field in fields.iter() {
+        if let Some(column) = parse_column_field(field)? {
+            columns.push(column);
+        }
+    }
+
+    let column_count = columns.len();
+    let mut header_arms = Vec::new();
+    let mut data_arms = Vec::new();
+    let mut flags_arms = Vec::new();
+    let mut set_data_arms = Vec::new();
+
+    for (index, column) in columns.iter().enumerate() {
+        let ColumnInfo {
+            field_name,
+            header,
+            role,
+            editable,
+        } = column;
+
+        header_arms.push(quote! {
+            #index => horizon_lattice::model::ItemData::from(#header),
+        });
+        data_arms.push(quote! {
+            #index => horizon_lattice::model::column_data(
+                &self.#field_name,
+                horizon_lattice::model::ItemRole::#role,
+                role,
+            ),
+        });
+        flags_arms.push(quote! {
+            #index => horizon_lattice::model::column_flags(
+                horizon_lattice::model::ItemRole::#role,
+                #editable,
+            ),
+        });
+        if *editable {
+            set_data_arms.push(quote! {
+                #index => horizon_lattice::model::set_column_data(
+                    &mut self.#field_name,
+                    horizon_lattice::model::ItemRole::#role,
+                    &value,
+                    role,
+                ),
+            });
+        }
+    }
+
+    let set_data_body = if set_data_arms.is_empty() {
+        quote! { false }
+    } else {
+        quote! {
+            match column {
+                #(#set_data_arms)*
+                _ => false,
+            }
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics horizon_lattice::model::ModelRow for #struct_name #ty_generics #where_clause {
+            fn column_count() -> usize {
+                #column_count
+            }
+
+            fn header_data(
+                column: usize,
+                role: horizon_lattice::model::ItemRole,
+            ) -> horizon_lattice::model::ItemData {
+                if role != horizon_lattice::model::ItemRole::Display {
+                    return horizon_lattice::model::ItemData::None;
+                }
+                match column {
+                    #(#header_arms)*
+                    _ => horizon_lattice::model::ItemData::None,
+                }
+            }
+
+            fn data(
+                &self,
+                column: usize,
+                role: horizon_lattice::model::ItemRole,
+            ) -> horizon_lattice::model::ItemData {
+                match column {
+                    #(#data_arms)*
+                    _ => horizon_lattice::model::ItemData::None,
+                }
+            }
+
+            fn flags(&self, column: usize) -> horizon_lattice::model::ItemFlags {
+                match column {
+                    #(#flags_arms)*
+                    _ => horizon_lattice::model::ItemFlags::new(),
+                }
+            }
+
+            #[allow(unused_variables)]
+            fn set_data(
+                &mut self,
+                column: usize,
+                value: horizon_lattice::model::ItemData,
+                role: horizon_lattice::model::ItemRole,
+            ) -> bool {
+                #set_data_body
+            }
+        }
+    })
+}
+
+/// Parse a field's #[column(...)] attributes, or `None` for skipped fields.
+fn parse_column_field(field: &Field) -> syn::Result<Option<ColumnInfo>> {
+    let field_name = match &field.ident {
+        Some(name) => name.clone(),
+        None => return Ok(None),
+    };
+
+    let mut header = field_name.to_string();
+    let mut role: Expr = syn::parse_quote!(Display);
+    let mut editable = false;
+    let mut skip = false;
+
+    for attr in &field.attrs {
+        if !attr.path().is_ident("column") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("header") {
+                let value: Expr = meta.value()?.parse()?;
+                match value {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(lit_str),
+                        ..
+                    }) => header = lit_str.value(),
+                    other => {
+                        return Err(syn::Error::new_spanned(
+                            other,
+                            "expected a string literal, as in `header = \"Name\"`",
+                        ));
+                    }
+                }
+            } else if meta.path.is_ident("role") {
+                role = meta.value()?.parse()?;
+            } else if meta.path.is_ident("editable") {
+                editable = true;
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+            } else {
+                return Err(meta.error("expected `header`, `role`, `editable` or `skip`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    if skip {
+        return Ok(None);
+    }
+
+    Ok(Some(ColumnInfo {
+        field_name,
+        header,
+        role,
+        editable,
+    }))
+}
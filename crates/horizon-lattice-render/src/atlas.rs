@@ -13,7 +13,7 @@ use std::path::Path;
 
 use crate::context::GraphicsContext;
 use crate::error::{RenderError, RenderResult};
-use crate::image::Image;
+use crate::image::{Image, check_rgba_len};
 use crate::scalable_image::{ScalableImage, scaled_path};
 use crate::svg::SvgImage;
 
@@ -408,6 +408,8 @@ pub struct ImageManager {
     atlases: Vec<Arc<TextureAtlas>>,
     /// Default atlas size for new atlases.
     default_atlas_size: u32,
+    /// Whether loaded images keep a CPU copy of their pixels.
+    keep_pixels: bool,
 }
 
 impl ImageManager {
@@ -421,9 +423,25 @@ impl ImageManager {
         Ok(Self {
             atlases: Vec::new(),
             default_atlas_size: size,
+            keep_pixels: false,
         })
     }
 
+    /// Set whether images loaded from now on keep a CPU copy of their pixels.
+    ///
+    /// The copy lets the [`SoftwareRenderer`](crate::SoftwareRenderer),
+    /// [`PdfRenderer`](crate::PdfRenderer) and [`SvgRenderer`](crate::SvgRenderer)
+    /// draw atlas images, at the cost of keeping each image in memory twice.
+    /// Off by default.
+    pub fn set_keep_pixels(&mut self, keep_pixels: bool) {
+        self.keep_pixels = keep_pixels;
+    }
+
+    /// Check whether loaded images keep a CPU copy of their pixels.
+    pub fn keeps_pixels(&self) -> bool {
+        self.keep_pixels
+    }
+
     /// Load an image from a file path.
     pub fn load_file(&mut self, path: impl AsRef<std::path::Path>) -> RenderResult<Image> {
         let img = image::open(path.as_ref())
@@ -572,14 +590,8 @@ impl ImageManager {
     /// Load raw RGBA pixel data.
     pub fn load_rgba(&mut self, data: &[u8], width: u32, height: u32) -> RenderResult<Image> {
         // Validate data size
-        let expected_size = (width * height * 4) as usize;
-        if data.len() != expected_size {
-            return Err(RenderError::ImageLoad(format!(
-                "Invalid image data size: expected {} bytes, got {}",
-                expected_size,
-                data.len()
-            )));
-        }
+        check_rgba_len(data, width, height)?;
+        let pixels = self.keep_pixels.then(|| Arc::from(data));
 
         // Try existing atlases first
         for atlas in &self.atlases {
@@ -587,7 +599,8 @@ impl ImageManager {
                 atlas.upload(&allocation, data)?;
                 return Ok(Image {
                     allocation,
-                    atlas: atlas.clone(),
+                    atlas: Some(atlas.clone()),
+                    pixels,
                     width,
                     height,
                 });
@@ -607,7 +620,8 @@ impl ImageManager {
         let atlas = Arc::new(new_atlas);
         let image = Image {
             allocation,
-            atlas: atlas.clone(),
+            atlas: Some(atlas.clone()),
+            pixels,
             width,
             height,
         };
@@ -0,0 +1,381 @@
+//! Shader-based blending for blend modes that read the destination.
+//!
+//! Modes like [`Overlay`](BlendMode::Overlay) or [`Hue`](BlendMode::Hue)
+//! can't be expressed as hardware blend states, because their result depends
+//! on the destination color. [`BackdropBlender`] copies the part of the target
+//! under a draw into a backdrop texture, then draws a source texture into the
+//! target with `blend.wgsl`, which reads both and writes the blended result.
+//!
+//! The source is a texture holding everything drawn in the blend mode, such
+//! as a compositor layer or the renderer's content for a frame, so the shapes
+//! are blended with the backdrop as a group.
+
+use std::collections::HashMap;
+
+use tracing::debug;
+
+use crate::context::GraphicsContext;
+use crate::paint::BlendMode;
+use crate::types::Rect;
+
+/// Uniforms for the backdrop blend shader.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct BlendUniforms {
+    /// Target size in pixels.
+    viewport_size: [f32; 2],
+    /// Position of the blended rectangle in pixels.
+    rect_offset: [f32; 2],
+    /// Size of the blended rectangle in pixels.
+    rect_size: [f32; 2],
+    /// Opacity applied to the source.
+    opacity: f32,
+    /// Blend mode index in `blend.wgsl`.
+    mode: u32,
+}
+
+/// Pipelines for one target format.
+struct BlendPipelines {
+    /// Blends the source with the backdrop, replacing the target.
+    blend: wgpu::RenderPipeline,
+    /// Draws the source with normal blending, for targets that can't be copied.
+    fallback: wgpu::RenderPipeline,
+}
+
+/// Blends source textures into render targets with backdrop blend modes.
+pub(crate) struct BackdropBlender {
+    /// Blend shader module.
+    shader: wgpu::ShaderModule,
+    /// Bind group layout for the uniforms.
+    uniform_layout: wgpu::BindGroupLayout,
+    /// Bind group layout for the source and backdrop textures.
+    texture_layout: wgpu::BindGroupLayout,
+    /// Pipeline layout for the blend pipelines.
+    pipeline_layout: wgpu::PipelineLayout,
+    /// Pipelines by target format.
+    pipelines: HashMap<wgpu::TextureFormat, BlendPipelines>,
+    /// Sampler for the source texture.
+    sampler: wgpu::Sampler,
+    /// Copy of the target taken before each blend, reused across blends.
+    backdrop: Option<wgpu::Texture>,
+}
+
+impl BackdropBlender {
+    /// Create a blender.
+    pub(crate) fn new(device: &wgpu::Device) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("blend_shader"),
+            source: wgpu::ShaderSource::Wgsl(include_str!("shaders/blend.wgsl").into()),
+        });
+
+        let uniform_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("blend_uniform_bind_group_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let texture_entry = |binding, filterable| wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility: wgpu::ShaderStages::FRAGMENT,
+            ty: wgpu::BindingType::Texture {
+                sample_type: wgpu::TextureSampleType::Float { filterable },
+                view_dimension: wgpu::TextureViewDimension::D2,
+                multisampled: false,
+            },
+            count: None,
+        };
+        let texture_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("blend_texture_bind_group_layout"),
+            entries: &[
+                texture_entry(0, true),
+                wgpu::BindGroupLayoutEntry {
+                    binding: 1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count: None,
+                },
+                texture_entry(2, false),
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("blend_pipeline_layout"),
+            bind_group_layouts: &[&uniform_layout, &texture_layout],
+            push_constant_ranges: &[],
+        });
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("blend_source_sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+
+        Self {
+            shader,
+            uniform_layout,
+            texture_layout,
+            pipeline_layout,
+            pipelines: HashMap::new(),
+            sampler,
+            backdrop: None,
+        }
+    }
+
+    /// Blend `source` into the `rect` of `target` with a backdrop blend mode.
+    ///
+    /// `source` is stretched over `rect` and scaled by `opacity`. Work
+    /// submitted before this call is part of the backdrop. If `target`
+    /// doesn't allow `COPY_SRC`, the source is drawn with normal blending.
+    pub(crate) fn blend(
+        &mut self,
+        target: &wgpu::Texture,
+        target_view: &wgpu::TextureView,
+        source: &wgpu::TextureView,
+        rect: Rect,
+        opacity: f32,
+        mode: BlendMode,
+    ) {
+        let target_bounds = Rect::new(0.0, 0.0, target.width() as f32, target.height() as f32);
+        let Some(visible) = rect.intersect(&target_bounds) else {
+            return;
+        };
+        let left = visible.left().floor() as u32;
+        let top = visible.top().floor() as u32;
+        let right = (visible.right().ceil() as u32).min(target.width());
+        let bottom = (visible.bottom().ceil() as u32).min(target.height());
+        if right <= left || bottom <= top {
+            return;
+        }
+
+        let ctx = GraphicsContext::get();
+        let device = ctx.device();
+        let queue = ctx.queue();
+
+        let can_copy = target.usage().contains(wgpu::TextureUsages::COPY_SRC);
+        let shader_mode = match mode.backdrop_shader_mode() {
+            Some(shader_mode) if can_copy => shader_mode,
+            _ => {
+                debug!(
+                    target: "horizon_lattice_render::blend",
+                    ?mode,
+                    "target can't be copied for backdrop blending, using Normal"
+                );
+                0
+            }
+        };
+        let use_backdrop = shader_mode != 0;
+
+        if use_backdrop {
+            self.ensure_backdrop(device, target);
+        }
+        let format = target.format();
+        self.ensure_pipelines(device, format);
+
+        let uniforms = BlendUniforms {
+            viewport_size: [target.width() as f32, target.height() as f32],
+            rect_offset: [rect.left(), rect.top()],
+            rect_size: [rect.width(), rect.height()],
+            opacity,
+            mode: shader_mode,
+        };
+        let uniform_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("blend_uniform_buffer"),
+            size: std::mem::size_of::<BlendUniforms>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        queue.write_buffer(&uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blend_uniform_bind_group"),
+            layout: &self.uniform_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        // The fallback pipeline never reads the backdrop, but the layout needs
+        // a texture bound; the source view will do.
+        let backdrop_view = self
+            .backdrop
+            .as_ref()
+            .filter(|_| use_backdrop)
+            .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()));
+        let texture_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("blend_texture_bind_group"),
+            layout: &self.texture_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(source),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::TextureView(
+                        backdrop_view.as_ref().unwrap_or(source),
+                    ),
+                },
+            ],
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("blend_encoder"),
+        });
+
+        if let Some(backdrop) = self.backdrop.as_ref().filter(|_| use_backdrop) {
+            let origin = wgpu::Origin3d {
+                x: left,
+                y: top,
+                z: 0,
+            };
+            encoder.copy_texture_to_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: target,
+                    mip_level: 0,
+                    origin,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::TexelCopyTextureInfo {
+                    texture: backdrop,
+                    mip_level: 0,
+                    origin,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                wgpu::Extent3d {
+                    width: right - left,
+                    height: bottom - top,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("blend_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            let pipelines = &self.pipelines[&format];
+            render_pass.set_pipeline(if use_backdrop {
+                &pipelines.blend
+            } else {
+                &pipelines.fallback
+            });
+            // Only the copied pixels have a valid backdrop.
+            render_pass.set_scissor_rect(left, top, right - left, bottom - top);
+            render_pass.set_bind_group(0, &uniform_bind_group, &[]);
+            render_pass.set_bind_group(1, &texture_bind_group, &[]);
+            render_pass.draw(0..6, 0..1);
+        }
+
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    /// Make sure the backdrop texture can hold a copy of `target`.
+    fn ensure_backdrop(&mut self, device: &wgpu::Device, target: &wgpu::Texture) {
+        let fits = self.backdrop.as_ref().is_some_and(|backdrop| {
+            backdrop.format() == target.format()
+                && backdrop.width() >= target.width()
+                && backdrop.height() >= target.height()
+        });
+        if fits {
+            return;
+        }
+
+        self.backdrop = Some(device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("blend_backdrop"),
+            size: wgpu::Extent3d {
+                width: target.width(),
+                height: target.height(),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: target.format(),
+            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+            view_formats: &[],
+        }));
+    }
+
+    /// Make sure the blend pipelines for `format` exist.
+    fn ensure_pipelines(&mut self, device: &wgpu::Device, format: wgpu::TextureFormat) {
+        if self.pipelines.contains_key(&format) {
+            return;
+        }
+
+        let create = |entry_point: &str, blend: wgpu::BlendState| {
+            device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+                label: Some(&format!("blend_pipeline_{entry_point}_{format:?}")),
+                layout: Some(&self.pipeline_layout),
+                vertex: wgpu::VertexState {
+                    module: &self.shader,
+                    entry_point: Some("vs_main"),
+                    buffers: &[],
+                    compilation_options: Default::default(),
+                },
+                fragment: Some(wgpu::FragmentState {
+                    module: &self.shader,
+                    entry_point: Some(entry_point),
+                    targets: &[Some(wgpu::ColorTargetState {
+                        format,
+                        blend: Some(blend),
+                        write_mask: wgpu::ColorWrites::ALL,
+                    })],
+                    compilation_options: Default::default(),
+                }),
+                primitive: wgpu::PrimitiveState {
+                    topology: wgpu::PrimitiveTopology::TriangleList,
+                    strip_index_format: None,
+                    front_face: wgpu::FrontFace::Ccw,
+                    cull_mode: None,
+                    polygon_mode: wgpu::PolygonMode::Fill,
+                    unclipped_depth: false,
+                    conservative: false,
+                },
+                depth_stencil: None,
+                multisample: wgpu::MultisampleState::default(),
+                multiview: None,
+                cache: None,
+            })
+        };
+
+        let pipelines = BlendPipelines {
+            blend: create("fs_main", wgpu::BlendState::REPLACE),
+            fallback: create("fs_source", wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING),
+        };
+        self.pipelines.insert(format, pipelines);
+        debug!(
+            target: "horizon_lattice_render::blend",
+            ?format,
+            "created backdrop blend pipelines"
+        );
+    }
+}
@@ -1,7 +1,8 @@
 //! Damage tracking for efficient partial rendering.
 //!
 //! This module provides [`DamageTracker`] for tracking which regions of the screen
-//! have changed and need to be repainted. This enables optimizations like:
+//! have changed and need to be repainted, and [`Region`] for describing them.
+//! This enables optimizations like:
 //!
 //! - Only re-rendering areas that have changed
 //! - Using scissor rectangles to limit GPU work
@@ -9,20 +10,268 @@
 //!
 //! # Architecture
 //!
-//! The damage tracking system uses a simple union-based approach where dirty regions
-//! are accumulated into a single bounding rectangle. This is efficient for typical
-//! UI patterns and integrates well with GPU scissor-based rendering.
+//! Dirty rectangles are accumulated into a [`Region`], a set of non-overlapping
+//! rectangles. Damage in distant corners of the window (a blinking caret and a
+//! spinner, say) stays as two small rectangles instead of growing into one
+//! bounding box that covers most of the window. Each rectangle maps directly
+//! to a GPU scissor rectangle.
 //!
-//! More sophisticated approaches (region lists, R-trees) can be added if profiling
-//! shows they would provide benefit.
+//! When a region fragments into too many rectangles, it collapses into its
+//! bounding box, since the per-rectangle overhead then outweighs the savings.
 
-use crate::types::Rect;
+use crate::types::{Point, Rect};
+
+/// A set of non-overlapping rectangles.
+///
+/// Regions support union, intersection and subtraction. Adjacent rectangles
+/// that line up are merged, so a region built from touching rectangles stays
+/// small.
+///
+/// # Example
+///
+/// ```
+/// use horizon_lattice_render::damage::Region;
+/// use horizon_lattice_render::Rect;
+///
+/// let mut region = Region::from_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+/// region.subtract_rect(&Rect::new(25.0, 25.0, 50.0, 50.0));
+///
+/// assert_eq!(region.area(), 100.0 * 100.0 - 50.0 * 50.0);
+/// assert!(!region.intersects_rect(&Rect::new(40.0, 40.0, 10.0, 10.0)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Region {
+    /// Non-overlapping, non-empty rectangles.
+    rects: Vec<Rect>,
+}
+
+impl Region {
+    /// Create an empty region.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a region covering a single rectangle.
+    ///
+    /// An empty rectangle gives an empty region.
+    pub fn from_rect(rect: Rect) -> Self {
+        let mut region = Self::new();
+        region.union_rect(rect);
+        region
+    }
+
+    /// Check if the region covers no area.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rects.is_empty()
+    }
+
+    /// Get the non-overlapping rectangles that make up the region.
+    #[inline]
+    pub fn rects(&self) -> &[Rect] {
+        &self.rects
+    }
+
+    /// Get the bounding box of the region.
+    ///
+    /// Returns `None` if the region is empty.
+    pub fn bounding_rect(&self) -> Option<Rect> {
+        self.rects.iter().copied().reduce(|a, b| a.union(&b))
+    }
+
+    /// Get the total area covered by the region.
+    pub fn area(&self) -> f32 {
+        self.rects.iter().map(|r| r.width() * r.height()).sum()
+    }
+
+    /// Check if a point is inside the region.
+    pub fn contains(&self, point: Point) -> bool {
+        self.rects.iter().any(|r| r.contains(point))
+    }
+
+    /// Check if a rectangle overlaps the region.
+    pub fn intersects_rect(&self, rect: &Rect) -> bool {
+        self.rects.iter().any(|r| r.intersect(rect).is_some())
+    }
+
+    /// Add a rectangle to the region.
+    pub fn union_rect(&mut self, rect: Rect) {
+        if rect.width() <= 0.0 || rect.height() <= 0.0 {
+            return;
+        }
+
+        // Rectangles inside the new one are replaced by it
+        self.rects.retain(|r| !contains_rect(&rect, r));
+
+        // Add the parts of the new rectangle that aren't covered yet
+        let mut pieces = vec![rect];
+        for existing in &self.rects {
+            pieces = pieces
+                .into_iter()
+                .flat_map(|piece| subtract(piece, existing))
+                .collect();
+            if pieces.is_empty() {
+                return;
+            }
+        }
+        self.rects.extend(pieces);
+        self.coalesce();
+    }
+
+    /// Remove a rectangle from the region.
+    pub fn subtract_rect(&mut self, rect: &Rect) {
+        if !self.intersects_rect(rect) {
+            return;
+        }
+        self.rects = self.rects.iter().flat_map(|r| subtract(*r, rect)).collect();
+        self.coalesce();
+    }
+
+    /// Limit the region to a rectangle.
+    pub fn intersect_rect(&mut self, rect: &Rect) {
+        self.rects = self
+            .rects
+            .iter()
+            .filter_map(|r| r.intersect(rect))
+            .collect();
+    }
+
+    /// Compute the union of two regions.
+    pub fn union(&self, other: &Region) -> Region {
+        let mut result = self.clone();
+        for rect in &other.rects {
+            result.union_rect(*rect);
+        }
+        result
+    }
+
+    /// Compute the intersection of two regions.
+    pub fn intersect(&self, other: &Region) -> Region {
+        // Both sides are non-overlapping, so the pairwise intersections are too
+        let mut result = Region {
+            rects: self
+                .rects
+                .iter()
+                .flat_map(|a| other.rects.iter().filter_map(|b| a.intersect(b)))
+                .collect(),
+        };
+        result.coalesce();
+        result
+    }
+
+    /// Compute the parts of this region that aren't in `other`.
+    pub fn subtract(&self, other: &Region) -> Region {
+        let mut result = self.clone();
+        for rect in &other.rects {
+            result.subtract_rect(rect);
+        }
+        result
+    }
+
+    /// Move the region by an offset.
+    pub fn translate(&mut self, dx: f32, dy: f32) {
+        for rect in &mut self.rects {
+            *rect = rect.offset(dx, dy);
+        }
+    }
+
+    /// Remove all rectangles from the region.
+    pub fn clear(&mut self) {
+        self.rects.clear();
+    }
+
+    /// Merge rectangles that share a full edge.
+    fn coalesce(&mut self) {
+        let mut merged = true;
+        while merged {
+            merged = false;
+            'outer: for i in 0..self.rects.len() {
+                for j in (i + 1)..self.rects.len() {
+                    if let Some(joined) = join(&self.rects[i], &self.rects[j]) {
+                        self.rects[i] = joined;
+                        self.rects.swap_remove(j);
+                        merged = true;
+                        break 'outer;
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl From<Rect> for Region {
+    fn from(rect: Rect) -> Self {
+        Self::from_rect(rect)
+    }
+}
+
+/// Check if `outer` fully contains `inner`.
+fn contains_rect(outer: &Rect, inner: &Rect) -> bool {
+    outer.left() <= inner.left()
+        && outer.top() <= inner.top()
+        && outer.right() >= inner.right()
+        && outer.bottom() >= inner.bottom()
+}
+
+/// Split the parts of `rect` outside `hole` into up to four rectangles.
+fn subtract(rect: Rect, hole: &Rect) -> Vec<Rect> {
+    let Some(overlap) = rect.intersect(hole) else {
+        return vec![rect];
+    };
+
+    let mut pieces = Vec::with_capacity(4);
+    // Full-width bands above and below the hole
+    if overlap.top() > rect.top() {
+        pieces.push(Rect::new(
+            rect.left(),
+            rect.top(),
+            rect.width(),
+            overlap.top() - rect.top(),
+        ));
+    }
+    if overlap.bottom() < rect.bottom() {
+        pieces.push(Rect::new(
+            rect.left(),
+            overlap.bottom(),
+            rect.width(),
+            rect.bottom() - overlap.bottom(),
+        ));
+    }
+    // Pieces left and right of the hole
+    if overlap.left() > rect.left() {
+        pieces.push(Rect::new(
+            rect.left(),
+            overlap.top(),
+            overlap.left() - rect.left(),
+            overlap.height(),
+        ));
+    }
+    if overlap.right() < rect.right() {
+        pieces.push(Rect::new(
+            overlap.right(),
+            overlap.top(),
+            rect.right() - overlap.right(),
+            overlap.height(),
+        ));
+    }
+    pieces
+}
+
+/// Join two rectangles that share a full edge into one.
+fn join(a: &Rect, b: &Rect) -> Option<Rect> {
+    let same_rows = a.top() == b.top() && a.bottom() == b.bottom();
+    let same_columns = a.left() == b.left() && a.right() == b.right();
+    let touch_horizontally = a.right() == b.left() || b.right() == a.left();
+    let touch_vertically = a.bottom() == b.top() || b.bottom() == a.top();
+
+    ((same_rows && touch_horizontally) || (same_columns && touch_vertically)).then(|| a.union(b))
+}
 
 /// Tracks damaged (dirty) regions that need repainting.
 ///
-/// The damage tracker accumulates dirty rectangles and provides the combined
-/// damage region for efficient partial rendering. All damage is accumulated
-/// into a single bounding rectangle for simplicity and GPU efficiency.
+/// The damage tracker accumulates dirty rectangles into a [`Region`] for
+/// efficient partial rendering. If the region fragments into too many
+/// rectangles, it collapses into its bounding box.
 ///
 /// # Example
 ///
@@ -36,7 +285,10 @@ use crate::types::Rect;
 /// tracker.add_damage(Rect::new(10.0, 10.0, 50.0, 30.0));
 /// tracker.add_damage(Rect::new(100.0, 100.0, 20.0, 20.0));
 ///
-/// // Get the combined damage region
+/// // The damage stays as two separate rectangles
+/// assert_eq!(tracker.region().rects().len(), 2);
+///
+/// // Get the bounding box of the damage
 /// if let Some(damage) = tracker.damage_region() {
 ///     println!("Need to repaint: {:?}", damage);
 /// }
@@ -47,7 +299,7 @@ use crate::types::Rect;
 #[derive(Debug, Clone, Default)]
 pub struct DamageTracker {
     /// The accumulated damage region (union of all dirty rects).
-    damage: Option<Rect>,
+    damage: Region,
     /// Total number of damage regions added this frame.
     damage_count: u32,
     /// Whether full repaint is needed (damage exceeds threshold).
@@ -68,7 +320,7 @@ impl DamageTracker {
     /// when full repaint is more efficient.
     pub fn with_viewport(viewport: Rect) -> Self {
         Self {
-            damage: None,
+            damage: Region::new(),
             damage_count: 0,
             full_repaint: false,
             viewport: Some(viewport),
@@ -80,14 +332,17 @@ impl DamageTracker {
     /// This should be called when the window/surface is resized.
     pub fn set_viewport(&mut self, viewport: Rect) {
         self.viewport = Some(viewport);
+        if self.full_repaint {
+            self.damage = Region::from_rect(viewport);
+        }
         // Check if current damage exceeds the threshold for full repaint
         self.check_full_repaint_threshold();
     }
 
     /// Add a damaged region that needs repainting.
     ///
-    /// The region is unioned with any existing damage to create
-    /// a combined bounding rectangle.
+    /// The region is unioned with any existing damage. Separate rectangles
+    /// stay separate unless there are too many of them.
     pub fn add_damage(&mut self, rect: Rect) {
         // Skip empty rects
         if rect.width() <= 0.0 || rect.height() <= 0.0 {
@@ -105,11 +360,7 @@ impl DamageTracker {
         };
 
         self.damage_count += 1;
-
-        self.damage = Some(match self.damage {
-            Some(existing) => existing.union(&rect),
-            None => rect,
-        });
+        self.damage.union_rect(rect);
 
         // Check if we should switch to full repaint
         self.check_full_repaint_threshold();
@@ -126,11 +377,11 @@ impl DamageTracker {
     /// Mark the entire viewport as damaged (full repaint needed).
     pub fn invalidate_all(&mut self) {
         self.full_repaint = true;
-        self.damage = self.viewport;
+        self.damage = self.viewport.map(Region::from_rect).unwrap_or_default();
         self.damage_count = 1;
     }
 
-    /// Get the current damage region.
+    /// Get the bounding box of the current damage.
     ///
     /// Returns `None` if no damage has been recorded.
     /// Returns the viewport if full repaint is flagged.
@@ -138,14 +389,22 @@ impl DamageTracker {
         if self.full_repaint {
             self.viewport
         } else {
-            self.damage
+            self.damage.bounding_rect()
         }
     }
 
+    /// Get the current damage as a region.
+    ///
+    /// Covers the viewport if full repaint is flagged.
+    #[inline]
+    pub fn region(&self) -> &Region {
+        &self.damage
+    }
+
     /// Check if any damage has been recorded.
     #[inline]
     pub fn has_damage(&self) -> bool {
-        self.damage.is_some() || self.full_repaint
+        !self.damage.is_empty() || self.full_repaint
     }
 
     /// Check if a full repaint is needed.
@@ -169,7 +428,7 @@ impl DamageTracker {
     ///
     /// Should be called after rendering the damaged regions.
     pub fn clear(&mut self) {
-        self.damage = None;
+        self.damage.clear();
         self.damage_count = 0;
         self.full_repaint = false;
     }
@@ -183,17 +442,16 @@ impl DamageTracker {
     ///
     /// Returns 0.0 if no damage or no viewport, 1.0 for full damage.
     pub fn damage_ratio(&self) -> f32 {
-        match (&self.damage, &self.viewport) {
-            (Some(damage), Some(viewport)) => {
-                let damage_area = damage.width() * damage.height();
+        match &self.viewport {
+            Some(viewport) => {
                 let viewport_area = viewport.width() * viewport.height();
                 if viewport_area > 0.0 {
-                    (damage_area / viewport_area).min(1.0)
+                    (self.damage.area() / viewport_area).min(1.0)
                 } else {
                     0.0
                 }
             }
-            _ => 0.0,
+            None => 0.0,
         }
     }
 
@@ -205,15 +463,24 @@ impl DamageTracker {
         // If we have too many fragmented regions, also switch to full repaint
         const MAX_DAMAGE_COUNT: u32 = 100;
 
+        // Beyond this many rectangles, repaint the bounding box instead
+        const MAX_REGION_RECTS: usize = 16;
+
         if self.damage_count > MAX_DAMAGE_COUNT {
             self.full_repaint = true;
-            self.damage = self.viewport;
+            self.damage = self.viewport.map(Region::from_rect).unwrap_or_default();
             return;
         }
 
+        if self.damage.rects().len() > MAX_REGION_RECTS
+            && let Some(bounds) = self.damage.bounding_rect()
+        {
+            self.damage = Region::from_rect(bounds);
+        }
+
         if self.damage_ratio() > FULL_REPAINT_THRESHOLD {
             self.full_repaint = true;
-            self.damage = self.viewport;
+            self.damage = self.viewport.map(Region::from_rect).unwrap_or_default();
         }
     }
 }
@@ -335,6 +602,73 @@ mod tests {
         assert!(tracker.needs_full_repaint());
     }
 
+    #[test]
+    fn test_distant_damage_stays_separate() {
+        let mut tracker = DamageTracker::with_viewport(Rect::new(0.0, 0.0, 800.0, 600.0));
+        tracker.add_damage(Rect::new(0.0, 0.0, 10.0, 20.0));
+        tracker.add_damage(Rect::new(760.0, 560.0, 40.0, 40.0));
+
+        assert_eq!(tracker.region().rects().len(), 2);
+        assert_eq!(tracker.region().area(), 200.0 + 1600.0);
+        assert!(!tracker.needs_full_repaint());
+    }
+
+    #[test]
+    fn test_fragmented_damage_collapses() {
+        let mut tracker = DamageTracker::with_viewport(Rect::new(0.0, 0.0, 1000.0, 1000.0));
+        for i in 0..16 {
+            tracker.add_damage(Rect::new(i as f32 * 40.0, i as f32 * 40.0, 10.0, 10.0));
+        }
+        assert_eq!(tracker.region().rects().len(), 16);
+
+        // One more rectangle collapses the region into its bounding box
+        tracker.add_damage(Rect::new(640.0, 640.0, 10.0, 10.0));
+        assert_eq!(
+            tracker.region().rects(),
+            &[Rect::new(0.0, 0.0, 650.0, 650.0)]
+        );
+    }
+
+    #[test]
+    fn test_region_union() {
+        let mut region = Region::from_rect(Rect::new(0.0, 0.0, 50.0, 50.0));
+        region.union_rect(Rect::new(25.0, 25.0, 50.0, 50.0));
+
+        // Overlap is only counted once
+        assert_eq!(region.area(), 2.0 * 2500.0 - 625.0);
+        assert!(region.contains(Point::new(70.0, 70.0)));
+        assert!(!region.contains(Point::new(70.0, 10.0)));
+
+        // Touching rectangles merge
+        let mut strip = Region::from_rect(Rect::new(0.0, 0.0, 10.0, 10.0));
+        strip.union_rect(Rect::new(10.0, 0.0, 10.0, 10.0));
+        assert_eq!(strip.rects(), &[Rect::new(0.0, 0.0, 20.0, 10.0)]);
+
+        // A covering rectangle replaces what it contains
+        strip.union_rect(Rect::new(-5.0, -5.0, 40.0, 40.0));
+        assert_eq!(strip.rects(), &[Rect::new(-5.0, -5.0, 40.0, 40.0)]);
+    }
+
+    #[test]
+    fn test_region_subtract_and_intersect() {
+        let full = Region::from_rect(Rect::new(0.0, 0.0, 100.0, 100.0));
+        let hole = Region::from_rect(Rect::new(25.0, 25.0, 50.0, 50.0));
+
+        let ring = full.subtract(&hole);
+        assert_eq!(ring.rects().len(), 4);
+        assert_eq!(ring.area(), 10000.0 - 2500.0);
+        assert!(!ring.intersects_rect(&Rect::new(30.0, 30.0, 10.0, 10.0)));
+        assert!(ring.intersect(&hole).is_empty());
+
+        // Subtracting the hole's complement leaves the hole
+        assert_eq!(full.subtract(&ring), hole);
+        assert_eq!(ring.union(&hole).area(), 10000.0);
+
+        let mut clipped = ring.clone();
+        clipped.intersect_rect(&Rect::new(0.0, 0.0, 100.0, 25.0));
+        assert_eq!(clipped.rects(), &[Rect::new(0.0, 0.0, 100.0, 25.0)]);
+    }
+
     #[test]
     fn test_empty_rect_ignored() {
         let mut tracker = DamageTracker::new();
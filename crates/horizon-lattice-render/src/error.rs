@@ -53,6 +53,14 @@ pub enum RenderError {
     /// Glyph atlas error.
     #[error("glyph atlas error: {0}")]
     GlyphAtlas(String),
+
+    /// Failed to write a PDF document.
+    #[error("failed to write PDF: {0}")]
+    Pdf(String),
+
+    /// Failed to write an SVG document.
+    #[error("failed to write SVG: {0}")]
+    Svg(String),
 }
 
 /// Result type for render operations.
@@ -10,8 +10,9 @@ use bytemuck::{Pod, Zeroable};
 use tracing::debug;
 
 use crate::atlas::TextureAtlas;
+use crate::blend::BackdropBlender;
 use crate::context::GraphicsContext;
-use crate::damage::DamageTracker;
+use crate::damage::{DamageTracker, Region};
 use crate::error::RenderResult;
 use crate::gradient::{GradientAtlas, create_gradient_bind_group_layout};
 use crate::image::{Image, ImageScaleMode, NinePatch};
@@ -33,8 +34,8 @@ const PAINT_TYPE_RADIAL_GRADIENT_TEX: u32 = 4;
 
 /// Blend modes that can be implemented with hardware blending.
 /// Returns the wgpu BlendState for the given blend mode.
-/// Complex blend modes (Overlay, SoftLight, etc.) that require shader-based
-/// blending return the same state as Normal with a warning logged.
+/// Backdrop blend modes (Overlay, SoftLight, etc.) are blended by a shader
+/// and return the same state as Normal.
 fn blend_state_for_mode(mode: BlendMode) -> wgpu::BlendState {
     use wgpu::{BlendComponent, BlendFactor, BlendOperation, BlendState};
 
@@ -230,25 +231,39 @@ fn blend_state_for_mode(mode: BlendMode) -> wgpu::BlendState {
             },
         },
 
-        // Complex blend modes that require shader-based implementation.
-        // For now, fall back to Normal blending.
-        // TODO: Implement shader-based blending for these modes.
+        // Modes that read the backdrop can't be expressed as a blend state.
+        // Their content is drawn normally into a scratch texture and blended
+        // with the backdrop by the BackdropBlender.
         BlendMode::Overlay
         | BlendMode::ColorDodge
         | BlendMode::ColorBurn
         | BlendMode::HardLight
         | BlendMode::SoftLight
         | BlendMode::Difference
-        | BlendMode::Exclusion => {
-            tracing::debug!(
-                mode = ?mode,
-                "Complex blend mode not yet implemented, falling back to Normal"
-            );
-            BlendState::PREMULTIPLIED_ALPHA_BLENDING
-        }
+        | BlendMode::Exclusion
+        | BlendMode::Hue
+        | BlendMode::Saturation
+        | BlendMode::Color
+        | BlendMode::Luminosity => BlendState::PREMULTIPLIED_ALPHA_BLENDING,
     }
 }
 
+/// Convert a rectangle to a scissor rectangle inside `target`.
+///
+/// Returns a zero-sized rectangle if they don't overlap.
+fn scissor_bounds(rect: &Rect, target: &wgpu::Texture) -> (u32, u32, u32, u32) {
+    let left = (rect.left().max(0.0).floor() as u32).min(target.width());
+    let top = (rect.top().max(0.0).floor() as u32).min(target.height());
+    let right = (rect.right().max(0.0).ceil() as u32).min(target.width());
+    let bottom = (rect.bottom().max(0.0).ceil() as u32).min(target.height());
+    (
+        left,
+        top,
+        right.saturating_sub(left),
+        bottom.saturating_sub(top),
+    )
+}
+
 /// Vertex data for rectangles with gradient support.
 ///
 /// Supports solid colors, linear gradients, and radial gradients with 2 color stops.
@@ -555,6 +570,69 @@ struct ImageBatch {
     indices: Vec<u32>,
 }
 
+/// Draws recorded in a single blend mode, waiting to be rendered.
+#[derive(Default)]
+struct DrawBatch {
+    /// Blend mode the batch is drawn in.
+    blend_mode: BlendMode,
+    /// Rectangle vertices.
+    vertices: Vec<RectVertex>,
+    /// Rectangle indices.
+    indices: Vec<u32>,
+    /// Shadow vertices.
+    shadow_vertices: Vec<ShadowVertex>,
+    /// Shadow indices.
+    shadow_indices: Vec<u32>,
+    /// Vertices for texture-based gradients.
+    gradient_tex_vertices: Vec<RectVertex>,
+    /// Indices for texture-based gradients.
+    gradient_tex_indices: Vec<u32>,
+    /// Image batches (one per atlas used).
+    image_batches: Vec<ImageBatch>,
+}
+
+impl DrawBatch {
+    /// Returns `true` if nothing has been drawn into the batch.
+    fn is_empty(&self) -> bool {
+        self.shadow_indices.is_empty() && !self.has_content()
+    }
+
+    /// Returns `true` if the batch has rectangles, gradients or images.
+    fn has_content(&self) -> bool {
+        !self.indices.is_empty()
+            || !self.gradient_tex_indices.is_empty()
+            || self
+                .image_batches
+                .iter()
+                .any(|batch| !batch.indices.is_empty())
+    }
+
+    /// Switch to drawing in `mode`.
+    ///
+    /// Draws already recorded in another mode are sealed into `sealed`, so
+    /// they're rendered first and later draws blend against them.
+    fn switch_mode(&mut self, mode: BlendMode, sealed: &mut Vec<DrawBatch>) {
+        if self.blend_mode == mode {
+            return;
+        }
+        if !self.is_empty() {
+            sealed.push(std::mem::take(self));
+        }
+        self.blend_mode = mode;
+    }
+
+    /// Remove all recorded draws, keeping the allocations.
+    fn clear(&mut self) {
+        self.vertices.clear();
+        self.indices.clear();
+        self.shadow_vertices.clear();
+        self.shadow_indices.clear();
+        self.gradient_tex_vertices.clear();
+        self.gradient_tex_indices.clear();
+        self.image_batches.clear();
+    }
+}
+
 /// Maximum number of vertices per batch.
 const MAX_VERTICES: usize = 65536;
 
@@ -678,12 +756,10 @@ pub struct GpuRenderer {
     bind_group: wgpu::BindGroup,
 
     // Batching state
-    /// Vertices waiting to be rendered.
-    vertices: Vec<RectVertex>,
-    /// Indices waiting to be rendered.
-    indices: Vec<u32>,
-    /// Blend mode of the current batch.
-    batch_blend_mode: BlendMode,
+    /// Draws waiting to be rendered in the current blend mode.
+    batch: DrawBatch,
+    /// Batches sealed by a blend mode change, rendered before `batch`.
+    sealed_batches: Vec<DrawBatch>,
 
     // Frame statistics
     /// Draw calls this frame.
@@ -719,11 +795,14 @@ pub struct GpuRenderer {
     #[allow(dead_code)]
     image_bind_group_layout: wgpu::BindGroupLayout,
 
-    /// Image batches (one per atlas used this frame).
-    image_batches: Vec<ImageBatch>,
-
     /// Damage tracker for dirty region optimization.
     damage_tracker: DamageTracker,
+    /// Copy of the last surface frame, repainted only where damaged.
+    retained_frame: Option<wgpu::Texture>,
+    /// Whether the retained frame holds the last presented frame.
+    retained_valid: bool,
+    /// Vertex buffer for the quad that clears damaged areas.
+    damage_clear_buffer: wgpu::Buffer,
 
     // === Stencil clipping support ===
     /// Stencil texture for advanced clipping.
@@ -746,10 +825,6 @@ pub struct GpuRenderer {
     shadow_vertex_buffer: wgpu::Buffer,
     /// Index buffer for shadows.
     shadow_index_buffer: wgpu::Buffer,
-    /// Shadow vertices waiting to be rendered.
-    shadow_vertices: Vec<ShadowVertex>,
-    /// Shadow indices waiting to be rendered.
-    shadow_indices: Vec<u32>,
 
     // === Multi-stop gradient support ===
     /// Gradient texture atlas for gradients with >2 stops.
@@ -759,10 +834,12 @@ pub struct GpuRenderer {
     gradient_bind_group_layout: wgpu::BindGroupLayout,
     /// Pipeline for texture-based gradient rendering.
     gradient_tex_pipeline: wgpu::RenderPipeline,
-    /// Vertices for texture-based gradients (separate batch).
-    gradient_tex_vertices: Vec<RectVertex>,
-    /// Indices for texture-based gradients.
-    gradient_tex_indices: Vec<u32>,
+
+    // === Backdrop blend modes ===
+    /// Blends content drawn in backdrop blend modes with the target.
+    backdrop_blender: BackdropBlender,
+    /// Scratch texture the content is drawn into before blending.
+    blend_source: Option<wgpu::Texture>,
 }
 
 impl GpuRenderer {
@@ -1071,6 +1148,14 @@ impl GpuRenderer {
             mapped_at_creation: false,
         });
 
+        // Create vertex buffer for clearing damaged areas
+        let damage_clear_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("damage_clear_buffer"),
+            size: (6 * std::mem::size_of::<RectVertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         // === Multi-stop gradient support ===
         // gradient_bind_group_layout was created earlier (needed for rect pipeline layout)
         let gradient_atlas = GradientAtlas::new(device, &gradient_bind_group_layout);
@@ -1133,9 +1218,8 @@ impl GpuRenderer {
             uniform_buffer,
             bind_group,
 
-            vertices: Vec::with_capacity(MAX_VERTICES),
-            indices: Vec::with_capacity(MAX_INDICES),
-            batch_blend_mode: BlendMode::Normal,
+            batch: DrawBatch::default(),
+            sealed_batches: Vec::new(),
 
             draw_calls: 0,
             vertex_count: 0,
@@ -1153,9 +1237,11 @@ impl GpuRenderer {
             image_vertex_buffer,
             image_index_buffer,
             image_bind_group_layout,
-            image_batches: Vec::new(),
 
             damage_tracker: DamageTracker::new(),
+            retained_frame: None, // Created lazily when needed
+            retained_valid: false,
+            damage_clear_buffer,
 
             // Stencil clipping
             stencil_texture: None, // Created lazily when needed
@@ -1169,15 +1255,15 @@ impl GpuRenderer {
             shadow_pipeline,
             shadow_vertex_buffer,
             shadow_index_buffer,
-            shadow_vertices: Vec::with_capacity(MAX_VERTICES),
-            shadow_indices: Vec::with_capacity(MAX_INDICES),
 
             // Multi-stop gradients
             gradient_atlas,
             gradient_bind_group_layout,
             gradient_tex_pipeline,
-            gradient_tex_vertices: Vec::with_capacity(MAX_VERTICES / 4),
-            gradient_tex_indices: Vec::with_capacity(MAX_INDICES / 4),
+
+            // Backdrop blend modes
+            backdrop_blender: BackdropBlender::new(device),
+            blend_source: None, // Created lazily when needed
         })
     }
 
@@ -1229,6 +1315,13 @@ impl GpuRenderer {
     ///
     /// This should be called after `end_frame()` to actually submit the
     /// rendered content to the surface.
+    ///
+    /// If damage has been recorded and the last frame is retained, only the
+    /// damaged rectangles are cleared and redrawn, and the frame carries them
+    /// as [`SurfaceFrame::damage_hint`](crate::SurfaceFrame::damage_hint). The surface
+    /// doesn't keep earlier frames, so the retained frame is then copied to
+    /// it in full and the whole frame is presented. The recorded damage is
+    /// cleared once the frame has been rendered.
     pub fn render_to_surface(&mut self, surface: &mut RenderSurface) -> RenderResult<FrameStats> {
         let frame = surface.get_current_frame()?;
         let Some(frame) = frame else {
@@ -1249,6 +1342,32 @@ impl GpuRenderer {
             self.ensure_stencil_texture(device, width, height);
         }
 
+        // Repaint only the damaged areas of the retained frame when possible
+        let frame_texture = &frame.texture().texture;
+        let retain = self.prepare_retained_frame(device, frame_texture);
+        let damage = (retain && self.retained_valid && !self.damage_tracker.needs_full_repaint())
+            .then(|| self.damage_tracker.region().clone());
+        let retained_view = self
+            .retained_frame
+            .as_ref()
+            .filter(|_| retain)
+            .map(|texture| texture.create_view(&wgpu::TextureViewDescriptor::default()));
+        let target_view = retained_view.as_ref().unwrap_or(&frame.view);
+
+        // Scissor rectangles to draw the content through
+        let clips: Vec<Option<Rect>> = match &damage {
+            Some(region) => region
+                .rects()
+                .iter()
+                .filter_map(|rect| match &self.scissor_rect {
+                    Some(scissor) => rect.intersect(scissor),
+                    None => Some(*rect),
+                })
+                .map(Some)
+                .collect(),
+            None => vec![self.scissor_rect],
+        };
+
         // Update uniform buffer
         let uniforms = Uniforms {
             transform: self.state.transform().to_mat4().to_cols_array_2d(),
@@ -1257,232 +1376,231 @@ impl GpuRenderer {
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
 
-        // Upload vertex and index data for rectangles
-        if !self.vertices.is_empty() {
-            queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&self.vertices));
-            queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&self.indices));
-        }
-
-        // Upload gradient atlas if needed
-        if !self.gradient_tex_vertices.is_empty() {
-            self.gradient_atlas.upload(queue);
-        }
-
-        // Upload vertex and index data for shadows
-        if !self.shadow_vertices.is_empty() {
-            queue.write_buffer(
-                &self.shadow_vertex_buffer,
-                0,
-                bytemuck::cast_slice(&self.shadow_vertices),
-            );
-            queue.write_buffer(
-                &self.shadow_index_buffer,
-                0,
-                bytemuck::cast_slice(&self.shadow_indices),
-            );
-        }
-
-        // Ensure we have the rect pipeline for the current blend mode
-        let batch_blend_mode = self.batch_blend_mode;
-        let _ = self.get_rect_pipeline(batch_blend_mode);
-        let _ = self.get_image_pipeline(batch_blend_mode);
-
-        // Create command encoder
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("render_encoder"),
-        });
+        // Batches are rendered in order, so each blends with the ones before it
+        let target_texture = self
+            .retained_frame
+            .as_ref()
+            .filter(|_| retain)
+            .unwrap_or(frame_texture)
+            .clone();
+        for (index, batch) in self.take_batches().into_iter().enumerate() {
+            self.batch = batch;
+
+            // Upload vertex and index data for the content
+            self.upload_content();
+
+            // Upload vertex and index data for shadows
+            if !self.batch.shadow_vertices.is_empty() {
+                queue.write_buffer(
+                    &self.shadow_vertex_buffer,
+                    0,
+                    bytemuck::cast_slice(&self.batch.shadow_vertices),
+                );
+                queue.write_buffer(
+                    &self.shadow_index_buffer,
+                    0,
+                    bytemuck::cast_slice(&self.batch.shadow_indices),
+                );
+            }
 
-        // Get stencil attachment if needed
-        let depth_stencil_attachment = if use_stencil {
-            self.stencil_texture
-                .as_ref()
-                .map(|tex| wgpu::RenderPassDepthStencilAttachment {
-                    view: tex.view(),
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(1.0),
-                        store: wgpu::StoreOp::Store,
-                    }),
-                    stencil_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(0),
-                        store: wgpu::StoreOp::Store,
-                    }),
-                })
-        } else {
-            None
-        };
+            // Ensure we have the pipelines for the current blend mode. Content in
+            // a backdrop blend mode is drawn normally, then blended as a group.
+            let backdrop_blend = self.batch.blend_mode.needs_backdrop() && self.batch.has_content();
+            let content_blend_mode = if backdrop_blend {
+                BlendMode::Normal
+            } else {
+                self.batch.blend_mode
+            };
+            let _ = self.get_rect_pipeline(content_blend_mode);
+            let _ = self.get_image_pipeline(content_blend_mode);
 
-        // Begin render pass
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("main_render_pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &frame.view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(self.clear_color.to_wgpu()),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment,
-                timestamp_writes: None,
-                occlusion_query_set: None,
+            // Create command encoder
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("render_encoder"),
             });
 
-            // Apply scissor if set
-            if let Some(scissor) = &self.scissor_rect {
-                render_pass.set_scissor_rect(
-                    scissor.left().max(0.0) as u32,
-                    scissor.top().max(0.0) as u32,
-                    scissor.width().max(0.0) as u32,
-                    scissor.height().max(0.0) as u32,
-                );
-            }
+            // Damaged areas are cleared here; the rest keeps the last frame.
+            // Later batches are drawn over the earlier ones.
+            let load = if index > 0 {
+                wgpu::LoadOp::Load
+            } else if damage.is_some() {
+                self.clear_damaged_areas(&mut encoder, target_view, &clips);
+                wgpu::LoadOp::Load
+            } else {
+                wgpu::LoadOp::Clear(self.clear_color.to_wgpu())
+            };
 
-            // Process pending clip operations
-            if !self.pending_clips.is_empty() {
-                let clips: Vec<_> = self.pending_clips.drain(..).collect();
-                let mut current_stencil_ref = 0u32;
+            // Get stencil attachment if needed
+            let depth_stencil_attachment = if use_stencil {
+                self.stencil_texture
+                    .as_ref()
+                    .map(|tex| wgpu::RenderPassDepthStencilAttachment {
+                        view: tex.view(),
+                        depth_ops: Some(wgpu::Operations {
+                            load: if index > 0 {
+                                wgpu::LoadOp::Load
+                            } else {
+                                wgpu::LoadOp::Clear(1.0)
+                            },
+                            store: wgpu::StoreOp::Store,
+                        }),
+                        stencil_ops: Some(wgpu::Operations {
+                            load: if index > 0 {
+                                wgpu::LoadOp::Load
+                            } else {
+                                wgpu::LoadOp::Clear(0)
+                            },
+                            store: wgpu::StoreOp::Store,
+                        }),
+                    })
+            } else {
+                None
+            };
 
-                for (shape, is_push) in clips {
-                    let (clip_vertices, clip_indices) = self.clip_shape_to_vertices(&shape);
+            // Content is tested against the stencil while clips are active
+            let stencil_reference = self.clip_stack.has_clips().then(|| self.clip_stack.depth());
 
-                    // Upload clip geometry
-                    queue.write_buffer(
-                        &self.vertex_buffer,
-                        0,
-                        bytemuck::cast_slice(&clip_vertices),
+            // Begin render pass
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("main_render_pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: target_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                // Apply scissor if set
+                if let Some(scissor) = &self.scissor_rect {
+                    render_pass.set_scissor_rect(
+                        scissor.left().max(0.0) as u32,
+                        scissor.top().max(0.0) as u32,
+                        scissor.width().max(0.0) as u32,
+                        scissor.height().max(0.0) as u32,
                     );
-                    queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&clip_indices));
-
-                    if is_push {
-                        // Push clip: increment stencil where shape is drawn
-                        render_pass.set_pipeline(&self.push_clip_pipeline);
-                        render_pass.set_stencil_reference(current_stencil_ref);
-                        current_stencil_ref += 1;
-                    } else {
-                        // Pop clip: decrement stencil where shape is drawn
-                        render_pass.set_pipeline(&self.pop_clip_pipeline);
-                        render_pass.set_stencil_reference(current_stencil_ref);
-                        current_stencil_ref = current_stencil_ref.saturating_sub(1);
+                }
+
+                // Process pending clip operations
+                if !self.pending_clips.is_empty() {
+                    let clips: Vec<_> = self.pending_clips.drain(..).collect();
+                    let mut current_stencil_ref = 0u32;
+
+                    for (shape, is_push) in clips {
+                        let (clip_vertices, clip_indices) = self.clip_shape_to_vertices(&shape);
+
+                        // Upload clip geometry
+                        queue.write_buffer(
+                            &self.vertex_buffer,
+                            0,
+                            bytemuck::cast_slice(&clip_vertices),
+                        );
+                        queue.write_buffer(
+                            &self.index_buffer,
+                            0,
+                            bytemuck::cast_slice(&clip_indices),
+                        );
+
+                        if is_push {
+                            // Push clip: increment stencil where shape is drawn
+                            render_pass.set_pipeline(&self.push_clip_pipeline);
+                            render_pass.set_stencil_reference(current_stencil_ref);
+                            current_stencil_ref += 1;
+                        } else {
+                            // Pop clip: decrement stencil where shape is drawn
+                            render_pass.set_pipeline(&self.pop_clip_pipeline);
+                            render_pass.set_stencil_reference(current_stencil_ref);
+                            current_stencil_ref = current_stencil_ref.saturating_sub(1);
+                        }
+
+                        render_pass.set_bind_group(0, &self.bind_group, &[]);
+                        render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
+                        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+                        render_pass.set_index_buffer(
+                            self.index_buffer.slice(..),
+                            wgpu::IndexFormat::Uint32,
+                        );
+                        render_pass.draw_indexed(0..clip_indices.len() as u32, 0, 0..1);
+                        self.draw_calls += 1;
                     }
 
-                    render_pass.set_bind_group(0, &self.bind_group, &[]);
-                    render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                    render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                    render_pass
-                        .set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                    render_pass.draw_indexed(0..clip_indices.len() as u32, 0, 0..1);
-                    self.draw_calls += 1;
+                    // Re-upload content geometry after clip operations
+                    self.upload_content();
                 }
 
-                // Re-upload content geometry after clip operations
-                if !self.vertices.is_empty() {
-                    queue.write_buffer(
-                        &self.vertex_buffer,
-                        0,
-                        bytemuck::cast_slice(&self.vertices),
-                    );
-                    queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&self.indices));
-                }
-            }
+                for clip in &clips {
+                    if let Some(rect) = clip {
+                        let (x, y, width, height) = scissor_bounds(rect, frame_texture);
+                        if width == 0 || height == 0 {
+                            continue;
+                        }
+                        render_pass.set_scissor_rect(x, y, width, height);
+                    }
 
-            // Render shadows (before rectangles so they appear behind)
-            if !self.shadow_indices.is_empty() {
-                render_pass.set_pipeline(&self.shadow_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-                render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, self.shadow_vertex_buffer.slice(..));
-                render_pass.set_index_buffer(
-                    self.shadow_index_buffer.slice(..),
-                    wgpu::IndexFormat::Uint32,
-                );
-                render_pass.draw_indexed(0..self.shadow_indices.len() as u32, 0, 0..1);
-                self.draw_calls += 1;
-            }
+                    // Render shadows (before rectangles so they appear behind)
+                    if !self.batch.shadow_indices.is_empty() {
+                        render_pass.set_pipeline(&self.shadow_pipeline);
+                        render_pass.set_bind_group(0, &self.bind_group, &[]);
+                        render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
+                        render_pass.set_vertex_buffer(0, self.shadow_vertex_buffer.slice(..));
+                        render_pass.set_index_buffer(
+                            self.shadow_index_buffer.slice(..),
+                            wgpu::IndexFormat::Uint32,
+                        );
+                        render_pass.draw_indexed(
+                            0..self.batch.shadow_indices.len() as u32,
+                            0,
+                            0..1,
+                        );
+                        self.draw_calls += 1;
+                    }
 
-            // Render rectangles
-            if !self.indices.is_empty() {
-                // Use stencil pipeline if we have active clips
-                if self.clip_stack.has_clips() {
-                    render_pass.set_pipeline(&self.stencil_rect_pipeline);
-                    render_pass.set_stencil_reference(self.clip_stack.depth());
-                } else {
-                    // Get pipeline for current blend mode (already ensured to exist)
-                    let rect_pipeline = self.rect_pipelines.get(&batch_blend_mode).unwrap();
-                    render_pass.set_pipeline(rect_pipeline);
+                    // Render content, unless it's blended with the backdrop below
+                    if !backdrop_blend {
+                        self.draw_content(&mut render_pass, content_blend_mode, stencil_reference);
+                    }
                 }
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-                render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                render_pass
-                    .set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.draw_indexed(0..self.indices.len() as u32, 0, 0..1);
-                self.draw_calls += 1;
             }
 
-            // Render texture-based gradients (multi-stop gradients)
-            if !self.gradient_tex_indices.is_empty() {
-                // Upload gradient texture vertices
-                queue.write_buffer(
-                    &self.vertex_buffer,
-                    0,
-                    bytemuck::cast_slice(&self.gradient_tex_vertices),
+            // Submit
+            if backdrop_blend {
+                self.blend_content(
+                    encoder,
+                    &target_texture,
+                    target_view,
+                    use_stencil,
+                    stencil_reference,
+                    &clips,
                 );
-                queue.write_buffer(
-                    &self.index_buffer,
-                    0,
-                    bytemuck::cast_slice(&self.gradient_tex_indices),
-                );
-
-                render_pass.set_pipeline(&self.gradient_tex_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-                render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                render_pass
-                    .set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.draw_indexed(0..self.gradient_tex_indices.len() as u32, 0, 0..1);
-                self.draw_calls += 1;
+            } else {
+                queue.submit(std::iter::once(encoder.finish()));
             }
+        }
 
-            // Render images (one draw call per atlas)
-            if !self.image_batches.is_empty() {
-                // Get pipeline for current blend mode (already ensured to exist)
-                let image_pipeline = self.image_pipelines.get(&batch_blend_mode).unwrap();
-                render_pass.set_pipeline(image_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-
-                for batch in &self.image_batches {
-                    if batch.indices.is_empty() {
-                        continue;
-                    }
-
-                    // Upload batch vertices and indices
-                    queue.write_buffer(
-                        &self.image_vertex_buffer,
-                        0,
-                        bytemuck::cast_slice(&batch.vertices),
-                    );
-                    queue.write_buffer(
-                        &self.image_index_buffer,
-                        0,
-                        bytemuck::cast_slice(&batch.indices),
-                    );
-
-                    render_pass.set_bind_group(1, batch.atlas.bind_group(), &[]);
-                    render_pass.set_vertex_buffer(0, self.image_vertex_buffer.slice(..));
-                    render_pass.set_index_buffer(
-                        self.image_index_buffer.slice(..),
-                        wgpu::IndexFormat::Uint32,
-                    );
-                    render_pass.draw_indexed(0..batch.indices.len() as u32, 0, 0..1);
-                    self.draw_calls += 1;
-                }
-            }
+        // Copy the retained frame to the surface
+        if retain {
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("retained_frame_encoder"),
+            });
+            encoder.copy_texture_to_texture(
+                target_texture.as_image_copy(),
+                frame_texture.as_image_copy(),
+                frame_texture.size(),
+            );
+            queue.submit(std::iter::once(encoder.finish()));
         }
+        self.retained_valid = retain;
 
-        // Submit
-        queue.submit(std::iter::once(encoder.finish()));
+        let mut frame = frame;
+        frame.set_damage_hint(damage);
         frame.present();
+        self.damage_tracker.clear();
 
         let stats = FrameStats {
             draw_calls: self.draw_calls,
@@ -1491,11 +1609,7 @@ impl GpuRenderer {
         };
 
         // Reset for next frame
-        self.vertices.clear();
-        self.indices.clear();
-        self.image_batches.clear();
-        self.gradient_tex_vertices.clear();
-        self.gradient_tex_indices.clear();
+        self.batch.clear();
         self.gradient_atlas.clear();
         self.draw_calls = 0;
         self.vertex_count = 0;
@@ -1522,54 +1636,351 @@ impl GpuRenderer {
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
 
-        // Upload vertex and index data
-        if !self.vertices.is_empty() {
-            queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&self.vertices));
-            queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&self.indices));
+        // Batches are rendered in order, so each blends with the ones before it
+        for (index, batch) in self.take_batches().into_iter().enumerate() {
+            self.batch = batch;
+
+            // Upload vertex and index data for the content
+            self.upload_content();
+
+            // Upload shadow vertex and index data
+            if !self.batch.shadow_vertices.is_empty() {
+                queue.write_buffer(
+                    &self.shadow_vertex_buffer,
+                    0,
+                    bytemuck::cast_slice(&self.batch.shadow_vertices),
+                );
+                queue.write_buffer(
+                    &self.shadow_index_buffer,
+                    0,
+                    bytemuck::cast_slice(&self.batch.shadow_indices),
+                );
+            }
+
+            // Ensure we have the pipelines for the current blend mode. Content in
+            // a backdrop blend mode is drawn normally, then blended as a group.
+            let backdrop_blend = self.batch.blend_mode.needs_backdrop() && self.batch.has_content();
+            let content_blend_mode = if backdrop_blend {
+                BlendMode::Normal
+            } else {
+                self.batch.blend_mode
+            };
+            let _ = self.get_rect_pipeline(content_blend_mode);
+            let _ = self.get_image_pipeline(content_blend_mode);
+
+            // Create command encoder
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("offscreen_render_encoder"),
+            });
+
+            // Begin render pass
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("offscreen_render_pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: surface.view(),
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: if index > 0 {
+                                wgpu::LoadOp::Load
+                            } else {
+                                wgpu::LoadOp::Clear(self.clear_color.to_wgpu())
+                            },
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                // Apply scissor if set
+                if let Some(scissor) = &self.scissor_rect {
+                    render_pass.set_scissor_rect(
+                        scissor.left().max(0.0) as u32,
+                        scissor.top().max(0.0) as u32,
+                        scissor.width().max(0.0) as u32,
+                        scissor.height().max(0.0) as u32,
+                    );
+                }
+
+                // Render shadows (before rectangles so they appear behind)
+                if !self.batch.shadow_indices.is_empty() {
+                    render_pass.set_pipeline(&self.shadow_pipeline);
+                    render_pass.set_bind_group(0, &self.bind_group, &[]);
+                    render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
+                    render_pass.set_vertex_buffer(0, self.shadow_vertex_buffer.slice(..));
+                    render_pass.set_index_buffer(
+                        self.shadow_index_buffer.slice(..),
+                        wgpu::IndexFormat::Uint32,
+                    );
+                    render_pass.draw_indexed(0..self.batch.shadow_indices.len() as u32, 0, 0..1);
+                    self.draw_calls += 1;
+                }
+
+                // Render content, unless it's blended with the backdrop below
+                if !backdrop_blend {
+                    self.draw_content(&mut render_pass, content_blend_mode, None);
+                }
+            }
+
+            // Submit (no present for offscreen)
+            if backdrop_blend {
+                let clips = [self.scissor_rect];
+                self.blend_content(
+                    encoder,
+                    surface.texture(),
+                    surface.view(),
+                    false,
+                    None,
+                    &clips,
+                );
+            } else {
+                queue.submit(std::iter::once(encoder.finish()));
+            }
+        }
+
+        let stats = FrameStats {
+            draw_calls: self.draw_calls,
+            vertices: self.vertex_count,
+            state_changes: self.state_changes,
+        };
+
+        // Reset for next frame
+        self.batch.clear();
+        self.gradient_atlas.clear();
+        self.draw_calls = 0;
+        self.vertex_count = 0;
+        self.state_changes = 0;
+        self.in_frame = false;
+
+        Ok(stats)
+    }
+
+    /// Take the batches recorded this frame, in drawing order.
+    ///
+    /// There's always at least one batch, so a frame without draws still
+    /// clears its target.
+    fn take_batches(&mut self) -> Vec<DrawBatch> {
+        let mut batches = std::mem::take(&mut self.sealed_batches);
+        let current = std::mem::take(&mut self.batch);
+        if batches.is_empty() || !current.is_empty() {
+            batches.push(current);
         }
+        batches
+    }
 
-        // Upload shadow vertex and index data
-        if !self.shadow_vertices.is_empty() {
+    /// Upload the batched rectangles, gradients and images.
+    ///
+    /// Buffer writes all land before the submitted commands run, so each kind
+    /// of geometry gets its own part of the shared buffers: texture gradients
+    /// follow the rectangles, and the image batches follow each other.
+    fn upload_content(&mut self) {
+        let queue = GraphicsContext::get().queue();
+        let batch = &self.batch;
+
+        let vertex_size = std::mem::size_of::<RectVertex>() as u64;
+        let index_size = std::mem::size_of::<u32>() as u64;
+        if !batch.vertices.is_empty() {
             queue.write_buffer(
-                &self.shadow_vertex_buffer,
+                &self.vertex_buffer,
                 0,
-                bytemuck::cast_slice(&self.shadow_vertices),
+                bytemuck::cast_slice(&batch.vertices),
+            );
+            queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&batch.indices));
+        }
+
+        if !batch.gradient_tex_indices.is_empty() {
+            self.gradient_atlas.upload(queue);
+            queue.write_buffer(
+                &self.vertex_buffer,
+                batch.vertices.len() as u64 * vertex_size,
+                bytemuck::cast_slice(&batch.gradient_tex_vertices),
             );
             queue.write_buffer(
-                &self.shadow_index_buffer,
-                0,
-                bytemuck::cast_slice(&self.shadow_indices),
+                &self.index_buffer,
+                batch.indices.len() as u64 * index_size,
+                bytemuck::cast_slice(&batch.gradient_tex_indices),
             );
         }
 
-        // Ensure we have the pipelines for the current blend mode
-        let batch_blend_mode = self.batch_blend_mode;
-        let _ = self.get_rect_pipeline(batch_blend_mode);
-        let _ = self.get_image_pipeline(batch_blend_mode);
+        let image_vertex_size = std::mem::size_of::<ImageVertex>() as u64;
+        let (mut vertex_offset, mut index_offset) = (0, 0);
+        for image_batch in batch
+            .image_batches
+            .iter()
+            .filter(|b| !b.vertices.is_empty())
+        {
+            queue.write_buffer(
+                &self.image_vertex_buffer,
+                vertex_offset * image_vertex_size,
+                bytemuck::cast_slice(&image_batch.vertices),
+            );
+            queue.write_buffer(
+                &self.image_index_buffer,
+                index_offset * index_size,
+                bytemuck::cast_slice(&image_batch.indices),
+            );
+            vertex_offset += image_batch.vertices.len() as u64;
+            index_offset += image_batch.indices.len() as u64;
+        }
+    }
+
+    /// Encode the batched rectangles, gradients and images.
+    ///
+    /// The geometry must have been uploaded with [`upload_content`](Self::upload_content),
+    /// so the content can be encoded once per clip without uploading it again.
+    /// Rectangles are tested against the stencil when `stencil_reference` is set.
+    fn draw_content(
+        &mut self,
+        render_pass: &mut wgpu::RenderPass<'_>,
+        blend_mode: BlendMode,
+        stencil_reference: Option<u32>,
+    ) {
+        let rect_indices = self.batch.indices.len() as u32;
+
+        // Render rectangles
+        if rect_indices > 0 {
+            // Use stencil pipeline if we have active clips
+            if let Some(reference) = stencil_reference {
+                render_pass.set_pipeline(&self.stencil_rect_pipeline);
+                render_pass.set_stencil_reference(reference);
+            } else {
+                // Get pipeline for current blend mode (already ensured to exist)
+                let rect_pipeline = self.rect_pipelines.get(&blend_mode).unwrap();
+                render_pass.set_pipeline(rect_pipeline);
+            }
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(0..rect_indices, 0, 0..1);
+            self.draw_calls += 1;
+        }
+
+        // Render texture-based gradients (multi-stop gradients), stored after the rectangles
+        if !self.batch.gradient_tex_indices.is_empty() {
+            let gradient_indices =
+                rect_indices..rect_indices + self.batch.gradient_tex_indices.len() as u32;
+            render_pass.set_pipeline(&self.gradient_tex_pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.draw_indexed(gradient_indices, self.batch.vertices.len() as i32, 0..1);
+            self.draw_calls += 1;
+        }
+
+        // Render images (one draw call per atlas)
+        if !self.batch.image_batches.is_empty() {
+            // Get pipeline for current blend mode (already ensured to exist)
+            let image_pipeline = self.image_pipelines.get(&blend_mode).unwrap();
+            render_pass.set_pipeline(image_pipeline);
+            render_pass.set_bind_group(0, &self.bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.image_vertex_buffer.slice(..));
+            render_pass
+                .set_index_buffer(self.image_index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+
+            let (mut base_vertex, mut first_index) = (0, 0);
+            for batch in &self.batch.image_batches {
+                let index_count = batch.indices.len() as u32;
+                if index_count > 0 {
+                    render_pass.set_bind_group(1, batch.atlas.bind_group(), &[]);
+                    render_pass.draw_indexed(
+                        first_index..first_index + index_count,
+                        base_vertex,
+                        0..1,
+                    );
+                    self.draw_calls += 1;
+                }
+                base_vertex += batch.vertices.len() as i32;
+                first_index += index_count;
+            }
+        }
+    }
 
-        // Create command encoder
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("offscreen_render_encoder"),
+    /// Draw the batched content blended with the backdrop in the current blend mode.
+    ///
+    /// The content is drawn with normal blending into a scratch texture the
+    /// size of the target, which the backdrop blender then blends into the
+    /// target within each of `clips`. `encoder` holds the work below the
+    /// content and is submitted here.
+    fn blend_content(
+        &mut self,
+        encoder: wgpu::CommandEncoder,
+        target: &wgpu::Texture,
+        target_view: &wgpu::TextureView,
+        use_stencil: bool,
+        stencil_reference: Option<u32>,
+        clips: &[Option<Rect>],
+    ) {
+        let ctx = GraphicsContext::get();
+        let device = ctx.device();
+        let mut encoder = encoder;
+
+        let fits = self.blend_source.as_ref().is_some_and(|source| {
+            source.format() == target.format()
+                && source.width() == target.width()
+                && source.height() == target.height()
         });
+        if !fits {
+            self.blend_source = Some(device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("blend_source"),
+                size: wgpu::Extent3d {
+                    width: target.width(),
+                    height: target.height(),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: target.format(),
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            }));
+        }
+        let source_view = self
+            .blend_source
+            .as_ref()
+            .unwrap()
+            .create_view(&wgpu::TextureViewDescriptor::default());
+
+        // Keep the clip shapes already drawn into the stencil
+        let depth_stencil_attachment = if use_stencil {
+            self.stencil_texture
+                .as_ref()
+                .map(|tex| wgpu::RenderPassDepthStencilAttachment {
+                    view: tex.view(),
+                    depth_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    }),
+                    stencil_ops: Some(wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    }),
+                })
+        } else {
+            None
+        };
 
-        // Begin render pass
         {
             let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("offscreen_render_pass"),
+                label: Some("blend_source_pass"),
                 color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: surface.view(),
+                    view: &source_view,
                     resolve_target: None,
                     ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(self.clear_color.to_wgpu()),
+                        load: wgpu::LoadOp::Clear(wgpu::Color::TRANSPARENT),
                         store: wgpu::StoreOp::Store,
                     },
                 })],
-                depth_stencil_attachment: None,
+                depth_stencil_attachment,
                 timestamp_writes: None,
                 occlusion_query_set: None,
             });
 
-            // Apply scissor if set
             if let Some(scissor) = &self.scissor_rect {
                 render_pass.set_scissor_rect(
                     scissor.left().max(0.0) as u32,
@@ -1579,124 +1990,122 @@ impl GpuRenderer {
                 );
             }
 
-            // Render shadows (before rectangles so they appear behind)
-            if !self.shadow_indices.is_empty() {
-                render_pass.set_pipeline(&self.shadow_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-                render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, self.shadow_vertex_buffer.slice(..));
-                render_pass.set_index_buffer(
-                    self.shadow_index_buffer.slice(..),
-                    wgpu::IndexFormat::Uint32,
-                );
-                render_pass.draw_indexed(0..self.shadow_indices.len() as u32, 0, 0..1);
-                self.draw_calls += 1;
-            }
-
-            // Render rectangles
-            if !self.indices.is_empty() {
-                let rect_pipeline = self.rect_pipelines.get(&batch_blend_mode).unwrap();
-                render_pass.set_pipeline(rect_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-                render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                render_pass
-                    .set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.draw_indexed(0..self.indices.len() as u32, 0, 0..1);
-                self.draw_calls += 1;
-            }
-
-            // Render texture-based gradients (multi-stop gradients)
-            if !self.gradient_tex_indices.is_empty() {
-                // Upload gradient atlas
-                self.gradient_atlas.upload(queue);
+            self.draw_content(&mut render_pass, BlendMode::Normal, stencil_reference);
+        }
 
-                // Upload gradient texture vertices
-                queue.write_buffer(
-                    &self.vertex_buffer,
-                    0,
-                    bytemuck::cast_slice(&self.gradient_tex_vertices),
-                );
-                queue.write_buffer(
-                    &self.index_buffer,
-                    0,
-                    bytemuck::cast_slice(&self.gradient_tex_indices),
-                );
+        ctx.queue().submit(std::iter::once(encoder.finish()));
+
+        let bounds = Rect::new(0.0, 0.0, target.width() as f32, target.height() as f32);
+        for clip in clips {
+            self.backdrop_blender.blend(
+                target,
+                target_view,
+                &source_view,
+                clip.unwrap_or(bounds),
+                1.0,
+                self.batch.blend_mode,
+            );
+            self.draw_calls += 1;
+        }
+    }
 
-                render_pass.set_pipeline(&self.gradient_tex_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-                render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                render_pass
-                    .set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.draw_indexed(0..self.gradient_tex_indices.len() as u32, 0, 0..1);
-                self.draw_calls += 1;
-            }
+    /// Make sure the retained frame matches the surface frame, if it's used.
+    ///
+    /// Returns `true` if this frame is rendered to the retained frame and then
+    /// copied to the surface. That's the case when damage has been recorded
+    /// and the surface allows copying into it.
+    fn prepare_retained_frame(&mut self, device: &wgpu::Device, frame: &wgpu::Texture) -> bool {
+        if !self.damage_tracker.has_damage()
+            || !frame.usage().contains(wgpu::TextureUsages::COPY_DST)
+        {
+            self.retained_valid = false;
+            return false;
+        }
 
-            // Render images (one draw call per atlas)
-            if !self.image_batches.is_empty() {
-                let image_pipeline = self.image_pipelines.get(&batch_blend_mode).unwrap();
-                render_pass.set_pipeline(image_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
+        let fits = self.retained_frame.as_ref().is_some_and(|retained| {
+            retained.format() == frame.format()
+                && retained.width() == frame.width()
+                && retained.height() == frame.height()
+        });
+        if !fits {
+            self.retained_frame = Some(device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("retained_frame"),
+                size: frame.size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: frame.format(),
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING
+                    | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            }));
+            self.retained_valid = false;
+        }
+        true
+    }
 
-                for batch in &self.image_batches {
-                    if batch.indices.is_empty() {
-                        continue;
-                    }
+    /// Fill the damaged areas of the target with the clear color.
+    fn clear_damaged_areas(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        target_view: &wgpu::TextureView,
+        clips: &[Option<Rect>],
+    ) {
+        let queue = GraphicsContext::get().queue();
+        let (width, height) = (self.viewport_size.width, self.viewport_size.height);
+        let rect_pos = [0.0, 0.0];
+        let rect_size = [width, height];
+        let vertices: Vec<RectVertex> = [
+            [0.0, 0.0],
+            [width, 0.0],
+            [width, height],
+            [0.0, 0.0],
+            [width, height],
+            [0.0, height],
+        ]
+        .iter()
+        .map(|pos| RectVertex::solid(*pos, self.clear_color, rect_pos, rect_size, [0.0; 4]))
+        .collect();
+        queue.write_buffer(
+            &self.damage_clear_buffer,
+            0,
+            bytemuck::cast_slice(&vertices),
+        );
 
-                    // Upload batch vertices and indices
-                    queue.write_buffer(
-                        &self.image_vertex_buffer,
-                        0,
-                        bytemuck::cast_slice(&batch.vertices),
-                    );
-                    queue.write_buffer(
-                        &self.image_index_buffer,
-                        0,
-                        bytemuck::cast_slice(&batch.indices),
-                    );
+        let _ = self.get_rect_pipeline(BlendMode::Source);
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("damage_clear_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
 
-                    render_pass.set_bind_group(1, batch.atlas.bind_group(), &[]);
-                    render_pass.set_vertex_buffer(0, self.image_vertex_buffer.slice(..));
-                    render_pass.set_index_buffer(
-                        self.image_index_buffer.slice(..),
-                        wgpu::IndexFormat::Uint32,
-                    );
-                    render_pass.draw_indexed(0..batch.indices.len() as u32, 0, 0..1);
-                    self.draw_calls += 1;
-                }
+        render_pass.set_pipeline(&self.rect_pipelines[&BlendMode::Source]);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
+        render_pass.set_vertex_buffer(0, self.damage_clear_buffer.slice(..));
+        for rect in clips.iter().flatten() {
+            let (x, y, width, height) = scissor_bounds(rect, self.retained_frame.as_ref().unwrap());
+            if width > 0 && height > 0 {
+                render_pass.set_scissor_rect(x, y, width, height);
+                render_pass.draw(0..6, 0..1);
+                self.draw_calls += 1;
             }
         }
-
-        // Submit (no present for offscreen)
-        queue.submit(std::iter::once(encoder.finish()));
-
-        let stats = FrameStats {
-            draw_calls: self.draw_calls,
-            vertices: self.vertex_count,
-            state_changes: self.state_changes,
-        };
-
-        // Reset for next frame
-        self.vertices.clear();
-        self.indices.clear();
-        self.shadow_vertices.clear();
-        self.shadow_indices.clear();
-        self.image_batches.clear();
-        self.gradient_tex_vertices.clear();
-        self.gradient_tex_indices.clear();
-        self.gradient_atlas.clear();
-        self.draw_calls = 0;
-        self.vertex_count = 0;
-        self.state_changes = 0;
-        self.in_frame = false;
-
-        Ok(stats)
     }
 
     /// Add a filled quad to the batch with solid color.
     fn add_filled_quad(&mut self, rect: Rect, radii: CornerRadii, color: Color) {
-        let base_index = self.vertices.len() as u32;
+        let base_index = self.batch.vertices.len() as u32;
 
         // Apply opacity
         let color = if self.current_opacity < 1.0 {
@@ -1723,7 +2132,7 @@ impl GpuRenderer {
         ];
 
         for pos in positions {
-            self.vertices.push(RectVertex::solid(
+            self.batch.vertices.push(RectVertex::solid(
                 pos,
                 color,
                 rect_pos,
@@ -1733,7 +2142,7 @@ impl GpuRenderer {
         }
 
         // Add indices for two triangles
-        self.indices.extend_from_slice(&[
+        self.batch.indices.extend_from_slice(&[
             base_index,
             base_index + 1,
             base_index + 2,
@@ -1799,10 +2208,11 @@ impl GpuRenderer {
         {
             let tex_v = gradient_id.tex_v();
             let opacity = self.current_opacity;
-            let base_index = self.gradient_tex_vertices.len() as u32;
+            let base_index = self.batch.gradient_tex_vertices.len() as u32;
 
             for pos in positions {
-                self.gradient_tex_vertices
+                self.batch
+                    .gradient_tex_vertices
                     .push(RectVertex::linear_gradient_tex(
                         pos,
                         rect_pos,
@@ -1815,7 +2225,7 @@ impl GpuRenderer {
                     ));
             }
 
-            self.gradient_tex_indices.extend_from_slice(&[
+            self.batch.gradient_tex_indices.extend_from_slice(&[
                 base_index,
                 base_index + 1,
                 base_index + 2,
@@ -1830,14 +2240,14 @@ impl GpuRenderer {
         // Fall through to 2-stop path if atlas is full
 
         // Use the 2-stop gradient path
-        let base_index = self.vertices.len() as u32;
+        let base_index = self.batch.vertices.len() as u32;
         let (stop0_offset, stop0_color, stop1_offset, stop1_color) =
             self.extract_two_stops(&gradient.stops);
         let stop0_color = self.apply_opacity(stop0_color);
         let stop1_color = self.apply_opacity(stop1_color);
 
         for pos in positions {
-            self.vertices.push(RectVertex::linear_gradient(
+            self.batch.vertices.push(RectVertex::linear_gradient(
                 pos,
                 rect_pos,
                 rect_size,
@@ -1851,7 +2261,7 @@ impl GpuRenderer {
             ));
         }
 
-        self.indices.extend_from_slice(&[
+        self.batch.indices.extend_from_slice(&[
             base_index,
             base_index + 1,
             base_index + 2,
@@ -1902,10 +2312,11 @@ impl GpuRenderer {
         {
             let tex_v = gradient_id.tex_v();
             let opacity = self.current_opacity;
-            let base_index = self.gradient_tex_vertices.len() as u32;
+            let base_index = self.batch.gradient_tex_vertices.len() as u32;
 
             for pos in positions {
-                self.gradient_tex_vertices
+                self.batch
+                    .gradient_tex_vertices
                     .push(RectVertex::radial_gradient_tex(
                         pos,
                         rect_pos,
@@ -1918,7 +2329,7 @@ impl GpuRenderer {
                     ));
             }
 
-            self.gradient_tex_indices.extend_from_slice(&[
+            self.batch.gradient_tex_indices.extend_from_slice(&[
                 base_index,
                 base_index + 1,
                 base_index + 2,
@@ -1933,14 +2344,14 @@ impl GpuRenderer {
         // Fall through to 2-stop path if atlas is full
 
         // Use the 2-stop gradient path
-        let base_index = self.vertices.len() as u32;
+        let base_index = self.batch.vertices.len() as u32;
         let (stop0_offset, stop0_color, stop1_offset, stop1_color) =
             self.extract_two_stops(&gradient.stops);
         let stop0_color = self.apply_opacity(stop0_color);
         let stop1_color = self.apply_opacity(stop1_color);
 
         for pos in positions {
-            self.vertices.push(RectVertex::radial_gradient(
+            self.batch.vertices.push(RectVertex::radial_gradient(
                 pos,
                 rect_pos,
                 rect_size,
@@ -1954,7 +2365,7 @@ impl GpuRenderer {
             ));
         }
 
-        self.indices.extend_from_slice(&[
+        self.batch.indices.extend_from_slice(&[
             base_index,
             base_index + 1,
             base_index + 2,
@@ -2258,6 +2669,12 @@ impl GpuRenderer {
     /// Call this when content in the specified region has changed.
     /// The damage will be used to optimize rendering by only updating
     /// the affected areas.
+    ///
+    /// While damage is recorded, [`render_to_surface`](Self::render_to_surface)
+    /// keeps a copy of the last frame and repaints only the damaged
+    /// rectangles of it, each through its own scissor rectangle, if the
+    /// surface [supports it](RenderSurface::supports_damage_repaint). The whole
+    /// frame is still presented.
     pub fn add_damage(&mut self, rect: Rect) {
         self.damage_tracker.add_damage(rect);
     }
@@ -2269,7 +2686,8 @@ impl GpuRenderer {
 
     /// Clear all recorded damage.
     ///
-    /// Should be called after rendering the damaged regions.
+    /// [`render_to_surface`](Self::render_to_surface) does this after each
+    /// frame; call it to drop damage that won't be rendered.
     pub fn clear_damage(&mut self) {
         self.damage_tracker.clear();
     }
@@ -2279,13 +2697,18 @@ impl GpuRenderer {
         self.damage_tracker.has_damage()
     }
 
-    /// Get the current damage region.
+    /// Get the bounding box of the current damage.
     ///
     /// Returns `None` if no damage has been recorded.
     pub fn damage_region(&self) -> Option<Rect> {
         self.damage_tracker.damage_region()
     }
 
+    /// Get the current damage as a region of separate rectangles.
+    pub fn damage(&self) -> &Region {
+        self.damage_tracker.region()
+    }
+
     // =========================================================================
     // Layer Rendering
     // =========================================================================
@@ -2307,127 +2730,102 @@ impl GpuRenderer {
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
 
-        // Upload vertex and index data
-        if !self.vertices.is_empty() {
-            queue.write_buffer(&self.vertex_buffer, 0, bytemuck::cast_slice(&self.vertices));
-            queue.write_buffer(&self.index_buffer, 0, bytemuck::cast_slice(&self.indices));
-        }
-
-        // Upload shadow vertex and index data
-        if !self.shadow_vertices.is_empty() {
-            queue.write_buffer(
-                &self.shadow_vertex_buffer,
-                0,
-                bytemuck::cast_slice(&self.shadow_vertices),
-            );
-            queue.write_buffer(
-                &self.shadow_index_buffer,
-                0,
-                bytemuck::cast_slice(&self.shadow_indices),
-            );
-        }
-
-        // Ensure we have the pipelines for the current blend mode
-        let batch_blend_mode = self.batch_blend_mode;
-        let _ = self.get_rect_pipeline(batch_blend_mode);
-        let _ = self.get_image_pipeline(batch_blend_mode);
+        // Batches are rendered in order, so each blends with the ones before it
+        for (index, batch) in self.take_batches().into_iter().enumerate() {
+            self.batch = batch;
 
-        // Create command encoder
-        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
-            label: Some("layer_render_encoder"),
-        });
+            // Upload vertex and index data for the content
+            self.upload_content();
 
-        // Begin render pass
-        {
-            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
-                label: Some("layer_render_pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: layer.view(),
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(layer.clear_color().to_wgpu()),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: None,
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            });
-
-            // Apply scissor if set
-            if let Some(scissor) = &self.scissor_rect {
-                render_pass.set_scissor_rect(
-                    scissor.left().max(0.0) as u32,
-                    scissor.top().max(0.0) as u32,
-                    scissor.width().max(0.0) as u32,
-                    scissor.height().max(0.0) as u32,
+            // Upload shadow vertex and index data
+            if !self.batch.shadow_vertices.is_empty() {
+                queue.write_buffer(
+                    &self.shadow_vertex_buffer,
+                    0,
+                    bytemuck::cast_slice(&self.batch.shadow_vertices),
                 );
-            }
-
-            // Render shadows (before rectangles so they appear behind)
-            if !self.shadow_indices.is_empty() {
-                render_pass.set_pipeline(&self.shadow_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-                render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, self.shadow_vertex_buffer.slice(..));
-                render_pass.set_index_buffer(
-                    self.shadow_index_buffer.slice(..),
-                    wgpu::IndexFormat::Uint32,
+                queue.write_buffer(
+                    &self.shadow_index_buffer,
+                    0,
+                    bytemuck::cast_slice(&self.batch.shadow_indices),
                 );
-                render_pass.draw_indexed(0..self.shadow_indices.len() as u32, 0, 0..1);
-                self.draw_calls += 1;
             }
 
-            // Render rectangles
-            if !self.indices.is_empty() {
-                let rect_pipeline = self.rect_pipelines.get(&batch_blend_mode).unwrap();
-                render_pass.set_pipeline(rect_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
-                render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
-                render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-                render_pass
-                    .set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
-                render_pass.draw_indexed(0..self.indices.len() as u32, 0, 0..1);
-                self.draw_calls += 1;
-            }
-
-            // Render images (one draw call per atlas)
-            if !self.image_batches.is_empty() {
-                let image_pipeline = self.image_pipelines.get(&batch_blend_mode).unwrap();
-                render_pass.set_pipeline(image_pipeline);
-                render_pass.set_bind_group(0, &self.bind_group, &[]);
+            // Ensure we have the pipelines for the current blend mode. Content in
+            // a backdrop blend mode is drawn normally, then blended as a group.
+            let backdrop_blend = self.batch.blend_mode.needs_backdrop() && self.batch.has_content();
+            let content_blend_mode = if backdrop_blend {
+                BlendMode::Normal
+            } else {
+                self.batch.blend_mode
+            };
+            let _ = self.get_rect_pipeline(content_blend_mode);
+            let _ = self.get_image_pipeline(content_blend_mode);
 
-                for batch in &self.image_batches {
-                    if batch.indices.is_empty() {
-                        continue;
-                    }
+            // Create command encoder
+            let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("layer_render_encoder"),
+            });
 
-                    // Upload batch vertices and indices
-                    queue.write_buffer(
-                        &self.image_vertex_buffer,
-                        0,
-                        bytemuck::cast_slice(&batch.vertices),
-                    );
-                    queue.write_buffer(
-                        &self.image_index_buffer,
-                        0,
-                        bytemuck::cast_slice(&batch.indices),
+            // Begin render pass
+            {
+                let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("layer_render_pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: layer.view(),
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: if index > 0 {
+                                wgpu::LoadOp::Load
+                            } else {
+                                wgpu::LoadOp::Clear(layer.clear_color().to_wgpu())
+                            },
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                });
+
+                // Apply scissor if set
+                if let Some(scissor) = &self.scissor_rect {
+                    render_pass.set_scissor_rect(
+                        scissor.left().max(0.0) as u32,
+                        scissor.top().max(0.0) as u32,
+                        scissor.width().max(0.0) as u32,
+                        scissor.height().max(0.0) as u32,
                     );
+                }
 
-                    render_pass.set_bind_group(1, batch.atlas.bind_group(), &[]);
-                    render_pass.set_vertex_buffer(0, self.image_vertex_buffer.slice(..));
+                // Render shadows (before rectangles so they appear behind)
+                if !self.batch.shadow_indices.is_empty() {
+                    render_pass.set_pipeline(&self.shadow_pipeline);
+                    render_pass.set_bind_group(0, &self.bind_group, &[]);
+                    render_pass.set_bind_group(1, self.gradient_atlas.bind_group(), &[]);
+                    render_pass.set_vertex_buffer(0, self.shadow_vertex_buffer.slice(..));
                     render_pass.set_index_buffer(
-                        self.image_index_buffer.slice(..),
+                        self.shadow_index_buffer.slice(..),
                         wgpu::IndexFormat::Uint32,
                     );
-                    render_pass.draw_indexed(0..batch.indices.len() as u32, 0, 0..1);
+                    render_pass.draw_indexed(0..self.batch.shadow_indices.len() as u32, 0, 0..1);
                     self.draw_calls += 1;
                 }
+
+                // Render content, unless it's blended with the backdrop below
+                if !backdrop_blend {
+                    self.draw_content(&mut render_pass, content_blend_mode, None);
+                }
             }
-        }
 
-        // Submit (no present for layer)
-        queue.submit(std::iter::once(encoder.finish()));
+            // Submit (no present for layer)
+            if backdrop_blend {
+                let clips = [self.scissor_rect];
+                self.blend_content(encoder, layer.texture(), layer.view(), false, None, &clips);
+            } else {
+                queue.submit(std::iter::once(encoder.finish()));
+            }
+        }
 
         let stats = FrameStats {
             draw_calls: self.draw_calls,
@@ -2436,11 +2834,8 @@ impl GpuRenderer {
         };
 
         // Reset for next frame
-        self.vertices.clear();
-        self.indices.clear();
-        self.shadow_vertices.clear();
-        self.shadow_indices.clear();
-        self.image_batches.clear();
+        self.batch.clear();
+        self.gradient_atlas.clear();
         self.draw_calls = 0;
         self.vertex_count = 0;
         self.state_changes = 0;
@@ -2514,20 +2909,21 @@ impl GpuRenderer {
     ) {
         // Find or create batch for this atlas
         let batch_idx = self
+            .batch
             .image_batches
             .iter()
             .position(|b| Arc::ptr_eq(&b.atlas, atlas));
 
         let batch_idx = batch_idx.unwrap_or_else(|| {
-            self.image_batches.push(ImageBatch {
+            self.batch.image_batches.push(ImageBatch {
                 atlas: atlas.clone(),
                 vertices: Vec::new(),
                 indices: Vec::new(),
             });
-            self.image_batches.len() - 1
+            self.batch.image_batches.len() - 1
         });
 
-        let batch = &mut self.image_batches[batch_idx];
+        let batch = &mut self.batch.image_batches[batch_idx];
         let base_index = batch.vertices.len() as u32;
 
         // Unpack UVs
@@ -2805,18 +3201,13 @@ impl Renderer for GpuRenderer {
         self.viewport_size = viewport_size;
         self.in_frame = true;
         self.state.reset();
-        self.vertices.clear();
-        self.indices.clear();
-        self.image_batches.clear();
+        self.batch.clear();
+        self.sealed_batches.clear();
         self.scissor_rect = None;
 
         // Reset blend mode tracking
         self.current_blend_mode = BlendMode::Normal;
-        self.batch_blend_mode = BlendMode::Normal;
-
-        // Reset shadow buffers
-        self.shadow_vertices.clear();
-        self.shadow_indices.clear();
+        self.batch.blend_mode = BlendMode::Normal;
 
         // Reset stencil clipping state
         self.clip_stack.reset();
@@ -3022,7 +3413,7 @@ impl Renderer for GpuRenderer {
         let offset = [shadow.offset_x, shadow.offset_y];
         let inset = shadow.inset;
 
-        let base_index = self.shadow_vertices.len() as u32;
+        let base_index = self.batch.shadow_vertices.len() as u32;
 
         // Four corners of the render quad
         let positions = [
@@ -3033,7 +3424,7 @@ impl Renderer for GpuRenderer {
         ];
 
         for pos in positions {
-            self.shadow_vertices.push(ShadowVertex::new(
+            self.batch.shadow_vertices.push(ShadowVertex::new(
                 pos,
                 color,
                 rect_center,
@@ -3046,7 +3437,7 @@ impl Renderer for GpuRenderer {
         }
 
         // Two triangles for the quad
-        self.shadow_indices.extend_from_slice(&[
+        self.batch.shadow_indices.extend_from_slice(&[
             base_index,
             base_index + 1,
             base_index + 2,
@@ -3079,7 +3470,7 @@ impl Renderer for GpuRenderer {
         let half_width = stroke.width / 2.0;
         let color = self.apply_opacity(stroke.paint.as_solid().unwrap_or(Color::BLACK));
 
-        let base_index = self.vertices.len() as u32;
+        let base_index = self.batch.vertices.len() as u32;
 
         // For lines, we use a minimal rect that doesn't affect SDF calculations
         let rect_pos = [0.0, 0.0];
@@ -3095,7 +3486,7 @@ impl Renderer for GpuRenderer {
         ];
 
         for pos in positions {
-            self.vertices.push(RectVertex::solid(
+            self.batch.vertices.push(RectVertex::solid(
                 pos,
                 color,
                 rect_pos,
@@ -3104,7 +3495,7 @@ impl Renderer for GpuRenderer {
             ));
         }
 
-        self.indices.extend_from_slice(&[
+        self.batch.indices.extend_from_slice(&[
             base_index,
             base_index + 1,
             base_index + 2,
@@ -3188,7 +3579,7 @@ impl Renderer for GpuRenderer {
             }
         };
 
-        let base_index = self.vertices.len() as u32;
+        let base_index = self.batch.vertices.len() as u32;
 
         // For paths, we use a minimal rect that doesn't affect SDF calculations
         let rect_pos = [0.0, 0.0];
@@ -3202,7 +3593,7 @@ impl Renderer for GpuRenderer {
                 .state
                 .transform()
                 .transform_point(Point::new(pos[0], pos[1]));
-            self.vertices.push(RectVertex::solid(
+            self.batch.vertices.push(RectVertex::solid(
                 [p.x, p.y],
                 color,
                 rect_pos,
@@ -3213,7 +3604,7 @@ impl Renderer for GpuRenderer {
 
         // Add indices (offset by base_index)
         for idx in &tessellated.indices {
-            self.indices.push(base_index + idx);
+            self.batch.indices.push(base_index + idx);
         }
 
         self.vertex_count += tessellated.vertices.len() as u32;
@@ -3251,7 +3642,7 @@ impl Renderer for GpuRenderer {
             }
         };
 
-        let base_index = self.vertices.len() as u32;
+        let base_index = self.batch.vertices.len() as u32;
 
         // For paths, we use a minimal rect that doesn't affect SDF calculations
         let rect_pos = [0.0, 0.0];
@@ -3265,7 +3656,7 @@ impl Renderer for GpuRenderer {
                 .state
                 .transform()
                 .transform_point(Point::new(pos[0], pos[1]));
-            self.vertices.push(RectVertex::solid(
+            self.batch.vertices.push(RectVertex::solid(
                 [p.x, p.y],
                 color,
                 rect_pos,
@@ -3276,7 +3667,7 @@ impl Renderer for GpuRenderer {
 
         // Add indices (offset by base_index)
         for idx in &tessellated.indices {
-            self.indices.push(base_index + idx);
+            self.batch.indices.push(base_index + idx);
         }
 
         self.vertex_count += tessellated.vertices.len() as u32;
@@ -3287,14 +3678,8 @@ impl Renderer for GpuRenderer {
             self.current_blend_mode = mode;
             self.state_changes += 1;
 
-            // If we haven't started drawing yet, update the batch blend mode
-            // Otherwise, subsequent draws will use the current blend mode
-            // Note: For proper multi-blend-mode support within a frame,
-            // we would need to flush and render the current batch before switching.
-            // For now, the batch uses the blend mode set before the first draw.
-            if self.vertices.is_empty() && self.shadow_vertices.is_empty() {
-                self.batch_blend_mode = mode;
-            }
+            // Draws so far are rendered before the ones in the new mode
+            self.batch.switch_mode(mode, &mut self.sealed_batches);
         }
     }
 
@@ -3310,6 +3695,10 @@ impl Renderer for GpuRenderer {
         self.current_opacity
     }
 
+    fn as_gpu_renderer(&mut self) -> Option<&mut GpuRenderer> {
+        Some(self)
+    }
+
     fn draw_image(&mut self, image: &Image, dest: Rect, scale_mode: ImageScaleMode) {
         // Calculate source rect (entire image)
         let src = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
@@ -3321,6 +3710,11 @@ impl Renderer for GpuRenderer {
     }
 
     fn draw_image_rect(&mut self, image: &Image, src: Rect, dest: Rect) {
+        let Some(atlas) = image.atlas() else {
+            debug!(target: "horizon_lattice_render::gpu_renderer", "skipping image without a GPU texture");
+            return;
+        };
+
         // Transform the destination rectangle
         let transformed_dest = self.state.transform().transform_rect(&dest);
 
@@ -3345,7 +3739,7 @@ impl Renderer for GpuRenderer {
 
         // Add to appropriate batch
         self.add_image_quad(
-            image.atlas(),
+            atlas,
             transformed_dest,
             [src_u_min, src_v_min, src_u_max, src_v_max],
             tint,
@@ -3353,8 +3747,12 @@ impl Renderer for GpuRenderer {
     }
 
     fn draw_nine_patch(&mut self, nine_patch: &NinePatch, dest: Rect) {
-        let patches = nine_patch.calculate_patches(dest);
         let image = &nine_patch.image;
+        let Some(atlas) = image.atlas() else {
+            debug!(target: "horizon_lattice_render::gpu_renderer", "skipping image without a GPU texture");
+            return;
+        };
+        let patches = nine_patch.calculate_patches(dest);
 
         let (u_min, v_min, u_max, v_max) = image.uv_rect();
         let img_w = image.width() as f32;
@@ -3385,7 +3783,7 @@ impl Renderer for GpuRenderer {
             let patch_v_max = v_min + (src.bottom() / img_h) * v_range;
 
             self.add_image_quad(
-                image.atlas(),
+                atlas,
                 transformed_dest,
                 [patch_u_min, patch_v_min, patch_u_max, patch_v_max],
                 tint,
@@ -3531,4 +3929,31 @@ mod tests {
         let state = blend_state_for_mode(BlendMode::Overlay);
         assert_eq!(state, wgpu::BlendState::PREMULTIPLIED_ALPHA_BLENDING);
     }
+
+    #[test]
+    fn test_draw_batch_switch_mode_seals_drawn_content() {
+        let vertex = RectVertex::solid([0.0; 2], Color::RED, [0.0; 2], [1.0; 2], [0.0; 4]);
+        let mut batch = DrawBatch::default();
+        let mut sealed = Vec::new();
+
+        // Nothing drawn yet: the mode just changes
+        batch.switch_mode(BlendMode::Multiply, &mut sealed);
+        assert!(sealed.is_empty());
+        assert_eq!(batch.blend_mode, BlendMode::Multiply);
+
+        batch.vertices.extend([vertex; 4]);
+        batch.indices.extend([0, 1, 2, 0, 2, 3]);
+
+        // Same mode: keep batching
+        batch.switch_mode(BlendMode::Multiply, &mut sealed);
+        assert!(sealed.is_empty());
+
+        // New mode: earlier draws are sealed and rendered first
+        batch.switch_mode(BlendMode::Overlay, &mut sealed);
+        assert_eq!(sealed.len(), 1);
+        assert_eq!(sealed[0].blend_mode, BlendMode::Multiply);
+        assert_eq!(sealed[0].indices.len(), 6);
+        assert!(batch.is_empty());
+        assert_eq!(batch.blend_mode, BlendMode::Overlay);
+    }
 }
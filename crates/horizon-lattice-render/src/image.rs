@@ -6,15 +6,21 @@
 use std::sync::Arc;
 
 use crate::atlas::{AtlasAllocation, TextureAtlas};
+use crate::error::{RenderError, RenderResult};
 use crate::types::{Rect, Size};
 
-/// A GPU-backed image that can be rendered.
+/// An image that can be rendered.
 ///
 /// Images are stored in texture atlases for efficient batching. Each `Image`
 /// holds a reference to its allocation within an atlas.
 ///
 /// Images are typically loaded through [`ImageManager`](crate::ImageManager),
-/// which handles atlas management automatically.
+/// which handles atlas management automatically. Images created with
+/// [`Image::from_rgba`] live on the CPU only and need no graphics context;
+/// they can be drawn by the [`SoftwareRenderer`](crate::SoftwareRenderer) but
+/// not by the [`GpuRenderer`](crate::GpuRenderer). Atlas images only keep a
+/// CPU copy of their pixels, for the CPU renderers, when the manager is told
+/// to with [`ImageManager::set_keep_pixels`](crate::ImageManager::set_keep_pixels).
 ///
 /// # Example
 ///
@@ -39,8 +45,10 @@ use crate::types::{Rect, Size};
 pub struct Image {
     /// The allocation in the texture atlas.
     pub(crate) allocation: AtlasAllocation,
-    /// Reference to the atlas containing this image.
-    pub(crate) atlas: Arc<TextureAtlas>,
+    /// Reference to the atlas containing this image, if uploaded to the GPU.
+    pub(crate) atlas: Option<Arc<TextureAtlas>>,
+    /// Straight-alpha RGBA8 pixels, row-major, if kept on the CPU.
+    pub(crate) pixels: Option<Arc<[u8]>>,
     /// Original image width in pixels.
     pub(crate) width: u32,
     /// Original image height in pixels.
@@ -48,6 +56,27 @@ pub struct Image {
 }
 
 impl Image {
+    /// Create a CPU-only image from raw RGBA pixel data.
+    ///
+    /// The data must be exactly `width * height * 4` bytes of non-premultiplied
+    /// RGBA in row-major order.
+    pub fn from_rgba(data: &[u8], width: u32, height: u32) -> RenderResult<Self> {
+        check_rgba_len(data, width, height)?;
+        Ok(Self {
+            allocation: AtlasAllocation {
+                x: 0,
+                y: 0,
+                width,
+                height,
+                atlas_size: 0,
+            },
+            atlas: None,
+            pixels: Some(Arc::from(data)),
+            width,
+            height,
+        })
+    }
+
     /// Get the width of the image in pixels.
     #[inline]
     pub fn width(&self) -> u32 {
@@ -69,27 +98,72 @@ impl Image {
     /// Get the UV coordinates for this image within its atlas.
     ///
     /// Returns (u_min, v_min, u_max, v_max) in normalized texture coordinates.
+    ///
+    /// CPU-only images report the full `(0.0, 0.0, 1.0, 1.0)` range.
     #[inline]
     pub fn uv_rect(&self) -> (f32, f32, f32, f32) {
+        if self.atlas.is_none() {
+            return (0.0, 0.0, 1.0, 1.0);
+        }
         self.allocation.uv_rect()
     }
 
+    /// Check whether the image has been uploaded to a GPU texture atlas.
+    #[inline]
+    pub fn is_gpu_backed(&self) -> bool {
+        self.atlas.is_some()
+    }
+
+    /// Get the non-premultiplied RGBA8 pixels, row-major.
+    ///
+    /// Returns `None` for atlas images loaded without keeping their pixels.
+    #[inline]
+    pub fn pixels(&self) -> Option<&[u8]> {
+        self.pixels.as_deref()
+    }
+
+    /// Check whether two images share the same pixel data.
+    pub(crate) fn same_pixels(&self, other: &Image) -> bool {
+        match (&self.pixels, &other.pixels) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
     /// Get the atlas texture view for binding.
     #[allow(dead_code)] // API for future render batching
-    pub(crate) fn texture_view(&self) -> &wgpu::TextureView {
-        self.atlas.texture_view()
+    pub(crate) fn texture_view(&self) -> Option<&wgpu::TextureView> {
+        self.atlas.as_ref().map(|atlas| atlas.texture_view())
     }
 
     /// Get the atlas this image belongs to.
-    pub(crate) fn atlas(&self) -> &Arc<TextureAtlas> {
-        &self.atlas
+    pub(crate) fn atlas(&self) -> Option<&Arc<TextureAtlas>> {
+        self.atlas.as_ref()
     }
 
     /// Get the atlas ID for batching purposes.
     #[allow(dead_code)] // API for future render batching
-    pub(crate) fn atlas_id(&self) -> usize {
-        self.atlas.id()
+    pub(crate) fn atlas_id(&self) -> Option<usize> {
+        self.atlas.as_ref().map(|atlas| atlas.id())
+    }
+}
+
+/// Check that `data` holds exactly `width * height` RGBA8 pixels.
+pub(crate) fn check_rgba_len(data: &[u8], width: u32, height: u32) -> RenderResult<()> {
+    let expected_size = (width as usize)
+        .checked_mul(height as usize)
+        .and_then(|pixels| pixels.checked_mul(4))
+        .ok_or_else(|| {
+            RenderError::ImageLoad(format!("Image dimensions {width}x{height} are too large"))
+        })?;
+    if data.len() != expected_size {
+        return Err(RenderError::ImageLoad(format!(
+            "Invalid image data size: expected {} bytes, got {}",
+            expected_size,
+            data.len()
+        )));
     }
+    Ok(())
 }
 
 impl std::fmt::Debug for Image {
@@ -97,7 +171,7 @@ impl std::fmt::Debug for Image {
         f.debug_struct("Image")
             .field("width", &self.width)
             .field("height", &self.height)
-            .field("atlas_id", &self.atlas.id())
+            .field("atlas_id", &self.atlas_id())
             .finish()
     }
 }
@@ -386,4 +460,18 @@ mod tests {
         assert_eq!(min_width, 25.0);
         assert_eq!(min_height, 20.0);
     }
+
+    #[test]
+    fn test_from_rgba_keeps_pixels() {
+        let image = Image::from_rgba(&[1, 2, 3, 4], 1, 1).unwrap();
+        assert_eq!(image.pixels(), Some(&[1, 2, 3, 4][..]));
+        assert!(!image.is_gpu_backed());
+    }
+
+    #[test]
+    fn test_from_rgba_rejects_overflowing_size() {
+        // 65536 * 65536 * 4 overflows u32
+        assert!(Image::from_rgba(&[], 65536, 65536).is_err());
+        assert!(Image::from_rgba(&[0; 8], 1, 1).is_err());
+    }
 }
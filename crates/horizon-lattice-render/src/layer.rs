@@ -6,6 +6,7 @@
 //! - Group opacity (apply opacity to a group of elements as a whole)
 //! - Caching of complex widget subtrees
 //! - Blur and other post-processing effects
+//! - Blend modes that read the layers below, such as Overlay or Hue
 //! - Efficient scrolling with cached content
 //!
 //! # Architecture
@@ -47,14 +48,15 @@ use std::collections::HashMap;
 
 use tracing::debug;
 
+use crate::blend::BackdropBlender;
 use crate::context::GraphicsContext;
 use crate::error::{RenderError, RenderResult};
 use crate::paint::BlendMode;
 use crate::types::{Color, Point, Rect, Size};
 
 /// Returns the wgpu BlendState for the given blend mode when compositing layers.
-/// Complex blend modes (Overlay, SoftLight, etc.) that require shader-based
-/// blending return Normal blend state with a warning logged.
+/// Backdrop blend modes (Overlay, SoftLight, etc.) are blended by a shader
+/// and return the Normal blend state.
 fn blend_state_for_mode(mode: BlendMode) -> wgpu::BlendState {
     use wgpu::{BlendComponent, BlendFactor, BlendOperation, BlendState};
 
@@ -247,22 +249,18 @@ fn blend_state_for_mode(mode: BlendMode) -> wgpu::BlendState {
             },
         },
 
-        // Complex blend modes require fragment shader destination reads.
-        // Fall back to Normal blending as these cannot be implemented
-        // with wgpu's hardware blend states.
+        // Modes that read the backdrop are blended by the BackdropBlender.
         BlendMode::Overlay
         | BlendMode::ColorDodge
         | BlendMode::ColorBurn
         | BlendMode::HardLight
         | BlendMode::SoftLight
         | BlendMode::Difference
-        | BlendMode::Exclusion => {
-            tracing::debug!(
-                mode = ?mode,
-                "Complex blend mode not supported in layer compositing, using Normal"
-            );
-            BlendState::PREMULTIPLIED_ALPHA_BLENDING
-        }
+        | BlendMode::Exclusion
+        | BlendMode::Hue
+        | BlendMode::Saturation
+        | BlendMode::Color
+        | BlendMode::Luminosity => BlendState::PREMULTIPLIED_ALPHA_BLENDING,
     }
 }
 
@@ -749,6 +747,20 @@ pub struct Compositor {
     uniform_bind_group: wgpu::BindGroup,
     /// Vertex buffer for fullscreen quad.
     vertex_buffer: wgpu::Buffer,
+    /// Blends layers in backdrop blend modes with the layers below.
+    backdrop_blender: BackdropBlender,
+    /// Texture layers are composited into when any layer needs the backdrop.
+    accumulation: Option<Accumulation>,
+}
+
+/// Intermediate target for compositing with backdrop blend modes.
+struct Accumulation {
+    /// Accumulated layers.
+    texture: wgpu::Texture,
+    /// View of the texture.
+    view: wgpu::TextureView,
+    /// Bind group for drawing the texture to the target.
+    bind_group: wgpu::BindGroup,
 }
 
 /// Vertex for compositing shader.
@@ -929,6 +941,8 @@ impl Compositor {
             uniform_buffer,
             uniform_bind_group,
             vertex_buffer,
+            backdrop_blender: BackdropBlender::new(device),
+            accumulation: None,
         })
     }
 
@@ -1020,10 +1034,230 @@ impl Compositor {
         self.composite_pipelines.get(&blend_mode).unwrap()
     }
 
+    /// Returns `true` if any visible layer uses a backdrop blend mode.
+    fn needs_backdrop(&self) -> bool {
+        self.layers
+            .iter()
+            .any(|l| l.opacity > 0.0 && l.blend_mode.needs_backdrop())
+    }
+
+    /// Ensure the pipelines for the hardware blend modes of visible layers exist.
+    fn ensure_pipelines(&mut self) {
+        let blend_modes: Vec<BlendMode> = self
+            .layers
+            .iter()
+            .filter(|l| l.opacity > 0.0 && !l.blend_mode.needs_backdrop())
+            .map(|l| l.blend_mode)
+            .collect();
+        for blend_mode in blend_modes {
+            self.get_or_create_pipeline(blend_mode);
+        }
+    }
+
+    /// Make sure the accumulation texture matches the output size.
+    fn ensure_accumulation(&mut self, device: &wgpu::Device) {
+        let fits = self.accumulation.as_ref().is_some_and(|accum| {
+            accum.texture.width() == self.output_width
+                && accum.texture.height() == self.output_height
+        });
+        if fits {
+            return;
+        }
+
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("compositor_accumulation"),
+            size: wgpu::Extent3d {
+                width: self.output_width.max(1),
+                height: self.output_height.max(1),
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("compositor_accumulation_sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Nearest,
+            min_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("compositor_accumulation_bind_group"),
+            layout: &self.layer_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+        });
+
+        self.accumulation = Some(Accumulation {
+            texture,
+            view,
+            bind_group,
+        });
+    }
+
+    /// Draw a texture to the target in one submission.
+    ///
+    /// Each draw is submitted on its own so it sees the uniforms written for it.
+    fn draw_texture(
+        &self,
+        target_view: &wgpu::TextureView,
+        load: wgpu::LoadOp<wgpu::Color>,
+        blend_mode: BlendMode,
+        bind_group: &wgpu::BindGroup,
+        bounds: Rect,
+        opacity: f32,
+    ) {
+        let ctx = GraphicsContext::get();
+        let queue = ctx.queue();
+
+        let uniforms = CompositeUniforms {
+            viewport_size: [self.output_width as f32, self.output_height as f32],
+            layer_offset: [bounds.left(), bounds.top()],
+            layer_size: [bounds.width(), bounds.height()],
+            opacity,
+            _padding: 0.0,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+
+        let mut encoder = ctx
+            .device()
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("compositor_draw_encoder"),
+            });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("compositor_draw_pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: target_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                occlusion_query_set: None,
+            });
+
+            render_pass.set_pipeline(&self.composite_pipelines[&blend_mode]);
+            render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+            render_pass.set_bind_group(1, bind_group, &[]);
+            render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+            render_pass.draw(0..6, 0..1);
+        }
+        queue.submit(std::iter::once(encoder.finish()));
+    }
+
+    /// Composite layers through the accumulation texture.
+    ///
+    /// Layers in backdrop blend modes need to read the layers below them, which
+    /// a target view can't provide. The layers are composited into the
+    /// accumulation texture, starting from `clear_color`, and the result is
+    /// drawn to the target with `blit_mode`.
+    fn composite_accumulated(
+        &mut self,
+        target_view: &wgpu::TextureView,
+        clear_color: Color,
+        blit_mode: BlendMode,
+    ) -> RenderResult<()> {
+        let ctx = GraphicsContext::try_get().ok_or(RenderError::NotInitialized)?;
+        let device = ctx.device();
+
+        self.ensure_pipelines();
+        self.get_or_create_pipeline(blit_mode);
+        self.ensure_accumulation(device);
+
+        // Clear the accumulation texture
+        let accum = self.accumulation.as_ref().unwrap();
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("compositor_accumulation_encoder"),
+        });
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("compositor_accumulation_clear_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &accum.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(clear_color.to_wgpu()),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        ctx.queue().submit(std::iter::once(encoder.finish()));
+
+        // Composite each layer
+        for layer in &self.layers {
+            if layer.opacity <= 0.0 {
+                continue;
+            }
+
+            if layer.blend_mode.needs_backdrop() {
+                self.backdrop_blender.blend(
+                    &accum.texture,
+                    &accum.view,
+                    layer.view(),
+                    layer.bounds(),
+                    layer.opacity,
+                    layer.blend_mode,
+                );
+            } else {
+                self.draw_texture(
+                    &accum.view,
+                    wgpu::LoadOp::Load,
+                    layer.blend_mode,
+                    &layer.bind_group,
+                    layer.bounds(),
+                    layer.opacity,
+                );
+            }
+        }
+
+        // Draw the result to the target
+        let output = Rect::new(
+            0.0,
+            0.0,
+            self.output_width as f32,
+            self.output_height as f32,
+        );
+        self.draw_texture(
+            target_view,
+            wgpu::LoadOp::Load,
+            blit_mode,
+            &accum.bind_group,
+            output,
+            1.0,
+        );
+
+        Ok(())
+    }
+
     /// Composite all layers to the target view.
     ///
     /// Layers are composited in order (first layer is at the bottom).
-    /// Each layer uses its configured blend mode for compositing.
+    /// Each layer uses its configured blend mode for compositing. Layers in
+    /// backdrop blend modes are blended with everything composited below them.
     pub fn composite_to(
         &mut self,
         target_view: &wgpu::TextureView,
@@ -1033,17 +1267,12 @@ impl Compositor {
         let device = ctx.device();
         let queue = ctx.queue();
 
-        // Ensure all needed blend mode pipelines exist
-        let blend_modes: Vec<BlendMode> = self
-            .layers
-            .iter()
-            .filter(|l| l.opacity > 0.0)
-            .map(|l| l.blend_mode)
-            .collect();
-        for blend_mode in blend_modes {
-            self.get_or_create_pipeline(blend_mode);
+        if self.needs_backdrop() {
+            return self.composite_accumulated(target_view, clear_color, BlendMode::Source);
         }
 
+        self.ensure_pipelines();
+
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("compositor_encoder"),
         });
@@ -1107,22 +1336,21 @@ impl Compositor {
     ///
     /// This is useful when you want to composite layers onto existing content.
     /// Each layer uses its configured blend mode for compositing.
+    ///
+    /// If any layer uses a backdrop blend mode, the layers are blended with
+    /// each other as an isolated group, which is then drawn over the existing
+    /// content with normal blending.
     pub fn composite_over(&mut self, target_view: &wgpu::TextureView) -> RenderResult<()> {
         let ctx = GraphicsContext::try_get().ok_or(RenderError::NotInitialized)?;
         let device = ctx.device();
         let queue = ctx.queue();
 
-        // Ensure all needed blend mode pipelines exist
-        let blend_modes: Vec<BlendMode> = self
-            .layers
-            .iter()
-            .filter(|l| l.opacity > 0.0)
-            .map(|l| l.blend_mode)
-            .collect();
-        for blend_mode in blend_modes {
-            self.get_or_create_pipeline(blend_mode);
+        if self.needs_backdrop() {
+            return self.composite_accumulated(target_view, Color::TRANSPARENT, BlendMode::Normal);
         }
 
+        self.ensure_pipelines();
+
         let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
             label: Some("compositor_over_encoder"),
         });
@@ -82,6 +82,42 @@
 //! # }
 //! ```
 //!
+//! # Rendering Without a GPU
+//!
+//! The [`SoftwareRenderer`] implements the same [`Renderer`] trait on the CPU,
+//! drawing into memory without a graphics context. Use it for headless
+//! rendering, offscreen capture and visual tests:
+//!
+//! ```
+//! use horizon_lattice_render::{Color, Rect, Renderer, Size, SoftwareRenderer};
+//!
+//! let mut renderer = SoftwareRenderer::new();
+//! renderer.begin_frame(Color::WHITE, Size::new(200.0, 100.0));
+//! renderer.fill_rect(Rect::new(10.0, 10.0, 100.0, 50.0), Color::RED);
+//! renderer.end_frame();
+//!
+//! let image = renderer.to_image_buffer();
+//! assert_eq!(image.get_pixel(20, 20), Some(Color::RED));
+//! ```
+//!
+//! # Recording Drawing Commands
+//!
+//! A [`Picture`] is a [`Renderer`] that records its calls instead of drawing
+//! them. The recording can be replayed into any renderer with an extra
+//! transform, or written out as text to compare command streams in tests.
+//!
+//! # Writing PDF Documents
+//!
+//! The [`PdfRenderer`] writes each frame as a page of a PDF document, with
+//! vector paths, gradients, images and embedded font subsets. Its page size
+//! is given in points.
+//!
+//! # Writing SVG Documents
+//!
+//! The [`SvgRenderer`] writes a frame as an SVG document, the counterpart of
+//! loading one with [`SvgImage`]. Text is written as `<text>` elements or as
+//! glyph outlines.
+//!
 //! # Handling Window Events
 //!
 //! The surface needs to be resized when the window is resized:
@@ -99,6 +135,7 @@
 mod animated_image;
 mod async_image;
 mod atlas;
+mod blend;
 mod context;
 pub mod damage;
 mod disk_cache;
@@ -114,12 +151,16 @@ pub mod image_data;
 pub mod layer;
 mod paint;
 mod path;
+mod pdf;
+mod picture;
 mod renderer;
 mod scalable_image;
+mod software_renderer;
 pub mod stencil;
 mod surface;
 mod svg;
 mod svg_cache;
+mod svg_renderer;
 pub mod text;
 mod text_render_pass;
 mod text_renderer;
@@ -141,7 +182,11 @@ pub use surface::{PresentMode, RenderSurface, SurfaceConfig, SurfaceFrame};
 
 // Renderer API
 pub use gpu_renderer::GpuRenderer;
-pub use renderer::{FrameStats, RenderState, RenderStateStack, Renderer};
+pub use pdf::PdfRenderer;
+pub use picture::{DrawCommand, Picture};
+pub use renderer::{DynRenderer, FrameStats, RenderState, RenderStateStack, Renderer};
+pub use software_renderer::SoftwareRenderer;
+pub use svg_renderer::{SvgRenderer, SvgTextMode};
 
 // Drawing types
 pub use paint::{
@@ -183,7 +228,7 @@ pub use image_data::{
 };
 
 // Damage tracking
-pub use damage::DamageTracker;
+pub use damage::{DamageTracker, Region};
 
 // Layer compositing
 pub use layer::{Compositor, Layer, LayerConfig, LayerId};
@@ -508,10 +508,9 @@ impl DashPattern {
 /// let xor = BlendMode::Xor;                 // Either source or dest, not both
 /// ```
 ///
-/// # Hardware-Supported Modes
+/// # Hardware Modes
 ///
-/// The following blend modes are implemented using wgpu hardware blend states
-/// and are fully supported for layer compositing:
+/// The following blend modes are implemented using wgpu hardware blend states:
 ///
 /// - [`Normal`](BlendMode::Normal) - Standard alpha blending (source-over)
 /// - [`Multiply`](BlendMode::Multiply) - Multiplies colors together
@@ -529,71 +528,110 @@ impl DashPattern {
 /// - [`DestinationAtop`](BlendMode::DestinationAtop) - Destination atop source
 /// - [`Xor`](BlendMode::Xor) - Source or destination but not both
 ///
-/// # Unsupported Complex Modes
-///
-/// The following modes require reading the destination color in the fragment shader,
-/// which is not supported by wgpu's blend states. These modes **fall back to Normal
-/// blending** when used in layer compositing:
-///
-/// - [`Overlay`](BlendMode::Overlay)
-/// - [`ColorDodge`](BlendMode::ColorDodge)
-/// - [`ColorBurn`](BlendMode::ColorBurn)
-/// - [`HardLight`](BlendMode::HardLight)
-/// - [`SoftLight`](BlendMode::SoftLight)
-/// - [`Difference`](BlendMode::Difference)
-/// - [`Exclusion`](BlendMode::Exclusion)
-///
-/// These enum variants exist for API completeness but are not currently implemented.
-/// A debug log message is emitted when these modes are used.
+/// # Backdrop Modes
+///
+/// The following modes mix the source with the destination color, so they
+/// are implemented in a shader that reads a copy of the backdrop. They can
+/// be set on the renderer with `set_blend_mode` or used for layer compositing:
+///
+/// - Separable: [`Overlay`](BlendMode::Overlay), [`ColorDodge`](BlendMode::ColorDodge),
+///   [`ColorBurn`](BlendMode::ColorBurn), [`HardLight`](BlendMode::HardLight),
+///   [`SoftLight`](BlendMode::SoftLight), [`Difference`](BlendMode::Difference),
+///   [`Exclusion`](BlendMode::Exclusion)
+/// - Non-separable: [`Hue`](BlendMode::Hue), [`Saturation`](BlendMode::Saturation),
+///   [`Color`](BlendMode::Color), [`Luminosity`](BlendMode::Luminosity)
+///
+/// The formulas follow the W3C Compositing and Blending specification.
+/// On the GPU, changing the blend mode mid-frame flushes the shapes drawn so
+/// far. The shapes drawn in one of these modes until the next change are
+/// composited together, then blended as a group with everything drawn
+/// before them in the frame. The
+/// render target must allow copying (`COPY_SRC`); otherwise the group is
+/// drawn with normal blending.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub enum BlendMode {
-    /// Normal (source-over) blending. **Fully supported.**
+    /// Normal (source-over) blending.
     #[default]
     Normal,
-    /// Multiply colors. **Fully supported.**
+    /// Multiply colors.
     Multiply,
-    /// Screen colors. **Fully supported.**
+    /// Screen colors.
     Screen,
-    /// Overlay. **Unsupported - falls back to Normal.**
+    /// Overlay (multiply or screen, depending on the destination).
     Overlay,
-    /// Darken (min). **Fully supported.**
+    /// Darken (min).
     Darken,
-    /// Lighten (max). **Fully supported.**
+    /// Lighten (max).
     Lighten,
-    /// Color dodge. **Unsupported - falls back to Normal.**
+    /// Color dodge (brighten the destination to reflect the source).
     ColorDodge,
-    /// Color burn. **Unsupported - falls back to Normal.**
+    /// Color burn (darken the destination to reflect the source).
     ColorBurn,
-    /// Hard light. **Unsupported - falls back to Normal.**
+    /// Hard light (multiply or screen, depending on the source).
     HardLight,
-    /// Soft light. **Unsupported - falls back to Normal.**
+    /// Soft light (darken or lighten, depending on the source).
     SoftLight,
-    /// Difference. **Unsupported - falls back to Normal.**
+    /// Difference (absolute difference of the colors).
     Difference,
-    /// Exclusion. **Unsupported - falls back to Normal.**
+    /// Exclusion (like difference, with lower contrast).
     Exclusion,
-    /// Source (replace destination completely). **Fully supported.**
+    /// Hue of the source with the saturation and luminosity of the destination.
+    Hue,
+    /// Saturation of the source with the hue and luminosity of the destination.
+    Saturation,
+    /// Hue and saturation of the source with the luminosity of the destination.
+    Color,
+    /// Luminosity of the source with the hue and saturation of the destination.
+    Luminosity,
+    /// Source (replace destination completely).
     Source,
-    /// Destination (keep destination, ignore source). **Fully supported.**
+    /// Destination (keep destination, ignore source).
     Destination,
-    /// Source in (source where destination alpha). **Fully supported.**
+    /// Source in (source where destination alpha).
     SourceIn,
-    /// Destination in. **Fully supported.**
+    /// Destination in.
     DestinationIn,
-    /// Source out. **Fully supported.**
+    /// Source out.
     SourceOut,
-    /// Destination out. **Fully supported.**
+    /// Destination out.
     DestinationOut,
-    /// Source atop. **Fully supported.**
+    /// Source atop.
     SourceAtop,
-    /// Destination atop. **Fully supported.**
+    /// Destination atop.
     DestinationAtop,
-    /// XOR. **Fully supported.**
+    /// XOR.
     Xor,
-    /// Additive blending. **Fully supported.**
+    /// Additive blending.
     Add,
 }
 
+impl BlendMode {
+    /// Returns `true` if the mode needs to read the destination color, so it
+    /// can't be expressed as a hardware blend state.
+    pub fn needs_backdrop(&self) -> bool {
+        self.backdrop_shader_mode().is_some()
+    }
+
+    /// Returns the mode's index in the backdrop blend shader (`blend.wgsl`).
+    pub(crate) fn backdrop_shader_mode(&self) -> Option<u32> {
+        let index = match self {
+            BlendMode::Overlay => 1,
+            BlendMode::ColorDodge => 2,
+            BlendMode::ColorBurn => 3,
+            BlendMode::HardLight => 4,
+            BlendMode::SoftLight => 5,
+            BlendMode::Difference => 6,
+            BlendMode::Exclusion => 7,
+            BlendMode::Hue => 8,
+            BlendMode::Saturation => 9,
+            BlendMode::Color => 10,
+            BlendMode::Luminosity => 11,
+            _ => return None,
+        };
+        Some(index)
+    }
+}
+
 /// A box shadow definition (CSS box-shadow model).
 ///
 /// Box shadows are rendered using an analytical approximation of Gaussian blur,
@@ -969,6 +1007,17 @@ mod tests {
         assert_ne!(BlendMode::Screen, BlendMode::Overlay);
     }
 
+    #[test]
+    fn test_blend_mode_needs_backdrop() {
+        assert!(!BlendMode::Normal.needs_backdrop());
+        assert!(!BlendMode::Multiply.needs_backdrop());
+        assert!(!BlendMode::Xor.needs_backdrop());
+        assert!(BlendMode::Overlay.needs_backdrop());
+        assert!(BlendMode::Luminosity.needs_backdrop());
+        assert_eq!(BlendMode::Overlay.backdrop_shader_mode(), Some(1));
+        assert_eq!(BlendMode::Luminosity.backdrop_shader_mode(), Some(11));
+    }
+
     #[test]
     fn test_blend_mode_copy() {
         let mode = BlendMode::Multiply;
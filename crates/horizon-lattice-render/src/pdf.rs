@@ -0,0 +1,1404 @@
+//! PDF renderer implementation.
+//!
+//! This module provides the [`PdfRenderer`], which implements the
+//! [`Renderer`] trait by writing a PDF document. Every frame becomes a page
+//! whose size is the viewport size in points, so one unit of drawing is
+//! 1/72 inch.
+//!
+//! Paths, strokes and gradients are written as vector graphics, images as
+//! compressed image objects, and text as embedded font subsets that keep the
+//! text selectable and searchable. Box shadows have no vector equivalent and
+//! are rasterized with a [`SoftwareRenderer`].
+//!
+//! # Example
+//!
+//! ```
+//! use horizon_lattice_render::{Color, PdfRenderer, Rect, Renderer, Size};
+//!
+//! let mut renderer = PdfRenderer::new().with_title("Report");
+//! renderer.begin_frame(Color::WHITE, Size::new(595.0, 842.0));
+//! renderer.fill_rect(Rect::new(72.0, 72.0, 200.0, 100.0), Color::RED);
+//! renderer.end_frame();
+//!
+//! let pdf = renderer.finish().unwrap();
+//! assert!(pdf.starts_with(b"%PDF"));
+//! ```
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::io::Write;
+
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use lyon::path::PathEvent;
+use pdf_writer::types::{
+    BlendMode as PdfBlendMode, CidFontType, ColorSpaceOperand, FontFlags, FunctionShadingType,
+    LineCapStyle, LineJoinStyle, SystemInfo, UnicodeCmap,
+};
+use pdf_writer::{Chunk, Content, Dict, Filter, Finish, Name, Pdf, Ref, Str, TextStr};
+
+use crate::error::{RenderError, RenderResult};
+use crate::image::{Image, ImageScaleMode, NinePatch};
+use crate::paint::{
+    BlendMode, BoxShadow, DashPattern, FillRule, GradientStop, LineCap, LineJoin, Paint, Stroke,
+};
+use crate::path::to_lyon_path;
+use crate::renderer::{FrameStats, RenderStateStack, Renderer};
+use crate::software_renderer::{SoftwareRenderer, scaled_dest};
+use crate::text::{FontFaceId, FontSystem, TextLayout};
+use crate::transform::Transform2D;
+use crate::types::{Color, Path, Point, Rect, RoundedRect, Size};
+
+/// Pixels per point at which box shadows are rasterized.
+const SHADOW_SCALE: f32 = 2.0;
+
+/// The character collection of embedded fonts; glyph IDs are used as CIDs.
+const IDENTITY_SYSTEM_INFO: SystemInfo<'static> = SystemInfo {
+    registry: Str(b"Adobe"),
+    ordering: Str(b"Identity"),
+    supplement: 0,
+};
+
+/// A renderer that writes its frames as the pages of a PDF document.
+///
+/// Call [`begin_frame`](Renderer::begin_frame) and
+/// [`end_frame`](Renderer::end_frame) once per page, then
+/// [`finish`](Self::finish) or [`save`](Self::save) to produce the document.
+/// The clear color passed to `begin_frame` fills the page unless it is
+/// transparent. Drawing outside a frame has no page to go to and is ignored,
+/// but state changes such as transforms and clips still apply.
+///
+/// Blend modes that PDF can't express (the Porter-Duff modes and
+/// [`BlendMode::Add`]) are drawn as [`BlendMode::Normal`]. Gradients whose
+/// stops differ in alpha are drawn with the average alpha of their stops.
+/// Text is drawn with [`draw_text`](Self::draw_text), which needs the
+/// [`FontSystem`] the layout was shaped with.
+pub struct PdfRenderer {
+    title: Option<String>,
+    compress: bool,
+    /// Objects shared between pages: graphics states, patterns and images.
+    chunk: Chunk,
+    next_ref: i32,
+    pages: Vec<PdfPage>,
+    page: Option<PageBuilder>,
+    state: RenderStateStack,
+    /// Rounded-rect and path clips, in page coordinates.
+    stencil_clips: Vec<Path>,
+    graphics_states: HashMap<(u16, BlendMode), usize>,
+    graphics_state_refs: Vec<Ref>,
+    pattern_refs: Vec<Ref>,
+    images: Vec<Image>,
+    image_refs: Vec<Ref>,
+    fonts: Vec<PdfFont>,
+    font_indices: HashMap<FontFaceId, Option<usize>>,
+    stats: FrameStats,
+}
+
+/// A finished page.
+struct PdfPage {
+    size: Size,
+    content: Vec<u8>,
+    resources: PageResources,
+}
+
+/// The page being drawn.
+struct PageBuilder {
+    size: Size,
+    content: Content,
+    resources: PageResources,
+}
+
+/// The shared resources a page uses, by index.
+#[derive(Default)]
+struct PageResources {
+    graphics_states: BTreeSet<usize>,
+    patterns: BTreeSet<usize>,
+    images: BTreeSet<usize>,
+    fonts: BTreeSet<usize>,
+}
+
+/// A font face used by the document, embedded as a subset when finishing.
+struct PdfFont {
+    id: Ref,
+    data: Vec<u8>,
+    index: u32,
+    post_script_name: String,
+    /// The glyphs drawn with the font and the text they were shaped from.
+    glyphs: BTreeMap<u16, String>,
+}
+
+impl Default for PdfRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PdfRenderer {
+    /// Create a renderer for an empty document.
+    pub fn new() -> Self {
+        Self {
+            title: None,
+            compress: true,
+            chunk: Chunk::new(),
+            next_ref: 1,
+            pages: Vec::new(),
+            page: None,
+            state: RenderStateStack::new(),
+            stencil_clips: Vec::new(),
+            graphics_states: HashMap::new(),
+            graphics_state_refs: Vec::new(),
+            pattern_refs: Vec::new(),
+            images: Vec::new(),
+            image_refs: Vec::new(),
+            fonts: Vec::new(),
+            font_indices: HashMap::new(),
+            stats: FrameStats::default(),
+        }
+    }
+
+    /// Set the document title shown by PDF viewers.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set whether streams are compressed (the default).
+    ///
+    /// Uncompressed documents are larger, but their page contents can be read
+    /// as text, which helps when debugging.
+    pub fn with_compression(mut self, compress: bool) -> Self {
+        self.compress = compress;
+        self
+    }
+
+    /// Get the number of pages drawn so far, including the current one.
+    pub fn page_count(&self) -> usize {
+        self.pages.len() + usize::from(self.page.is_some())
+    }
+
+    /// Draw a text layout with its top-left corner at `position`.
+    ///
+    /// Glyphs use `color` unless the layout assigns them their own. The fonts
+    /// are looked up in `font_system` and embedded as subsets containing the
+    /// glyphs drawn. Lines entirely outside the clip rect are skipped, so a
+    /// layout split across pages with clip rects isn't duplicated.
+    pub fn draw_text(
+        &mut self,
+        font_system: &FontSystem,
+        layout: &TextLayout,
+        position: Point,
+        color: Color,
+    ) {
+        if !self.begin_draw() {
+            return;
+        }
+        if !self.set_alpha(color.a) {
+            self.end_draw();
+            return;
+        }
+
+        let clip = self.state.clip_bounds();
+        let transform = *self.state.transform();
+        let text = layout.text();
+        let mut current_font = None;
+        let mut current_color = None;
+        let Some(page) = self.page.as_mut() else {
+            return;
+        };
+        page.content.begin_text();
+        for line in layout.lines() {
+            if let Some(clip) = clip {
+                let bounds = transform.transform_rect(&Rect::new(
+                    position.x,
+                    position.y + line.top_y,
+                    line.width,
+                    line.height,
+                ));
+                if bounds.bottom().min(clip.bottom()) - bounds.top().max(clip.top()) < 0.5 {
+                    continue;
+                }
+            }
+
+            for glyph in &line.glyphs {
+                if glyph.is_inline_element() {
+                    continue;
+                }
+                let Some(font) = self.font_index(font_system, glyph.font_id) else {
+                    continue;
+                };
+                self.fonts[font]
+                    .glyphs
+                    .entry(glyph.glyph_id)
+                    .or_insert_with(|| text.get(glyph.cluster.clone()).unwrap_or("").to_string());
+
+                let glyph_color = glyph
+                    .color
+                    .map_or(color, |[r, g, b, a]| Color::from_rgba8(r, g, b, a));
+                let Some(page) = self.page.as_mut() else {
+                    return;
+                };
+                page.resources.fonts.insert(font);
+                if current_color != Some(glyph_color) {
+                    page.content
+                        .set_fill_rgb(glyph_color.r, glyph_color.g, glyph_color.b);
+                    current_color = Some(glyph_color);
+                }
+                if current_font != Some((font, glyph.font_size)) {
+                    page.content
+                        .set_font(Name(format!("F{font}").as_bytes()), glyph.font_size);
+                    current_font = Some((font, glyph.font_size));
+                }
+                // Undo the page flip so glyphs stand upright.
+                page.content.set_text_matrix([
+                    1.0,
+                    0.0,
+                    0.0,
+                    -1.0,
+                    position.x + glyph.x + glyph.x_offset,
+                    position.y + line.baseline_y + glyph.y + glyph.y_offset,
+                ]);
+                page.content.show(Str(&glyph.glyph_id.to_be_bytes()));
+            }
+        }
+        if let Some(page) = self.page.as_mut() {
+            page.content.end_text();
+        }
+        self.end_draw();
+    }
+
+    /// Finish the document and return its bytes.
+    ///
+    /// A frame that was begun but not ended becomes the last page.
+    pub fn finish(mut self) -> RenderResult<Vec<u8>> {
+        self.finish_page();
+
+        let mut pdf = Pdf::new();
+        let catalog_id = self.alloc();
+        let page_tree_id = self.alloc();
+        let page_ids: Vec<Ref> = (0..self.pages.len()).map(|_| self.alloc()).collect();
+
+        pdf.catalog(catalog_id).pages(page_tree_id);
+        pdf.pages(page_tree_id)
+            .kids(page_ids.iter().copied())
+            .count(page_ids.len() as i32);
+        if let Some(title) = self.title.take() {
+            let info_id = self.alloc();
+            pdf.document_info(info_id).title(TextStr(&title));
+        }
+
+        let font_refs: Vec<Ref> = self.fonts.iter().map(|font| font.id).collect();
+        let pages = std::mem::take(&mut self.pages);
+        for (page, &page_id) in pages.iter().zip(&page_ids) {
+            let content_id = self.alloc();
+            let content = self.encode(&page.content);
+            let mut stream = pdf.stream(content_id, &content);
+            if self.compress {
+                stream.filter(Filter::FlateDecode);
+            }
+            stream.finish();
+
+            let mut page_writer = pdf.page(page_id);
+            page_writer
+                .parent(page_tree_id)
+                .media_box(pdf_writer::Rect::new(
+                    0.0,
+                    0.0,
+                    page.size.width,
+                    page.size.height,
+                ))
+                .contents(content_id);
+            let resources = &page.resources;
+            let mut resource_writer = page_writer.resources();
+            if !resources.graphics_states.is_empty() {
+                write_resource_names(
+                    resource_writer.ext_g_states(),
+                    "Gs",
+                    &resources.graphics_states,
+                    &self.graphics_state_refs,
+                );
+            }
+            if !resources.patterns.is_empty() {
+                write_resource_names(
+                    resource_writer.patterns(),
+                    "P",
+                    &resources.patterns,
+                    &self.pattern_refs,
+                );
+            }
+            if !resources.images.is_empty() {
+                write_resource_names(
+                    resource_writer.x_objects(),
+                    "Im",
+                    &resources.images,
+                    &self.image_refs,
+                );
+            }
+            if !resources.fonts.is_empty() {
+                write_resource_names(resource_writer.fonts(), "F", &resources.fonts, &font_refs);
+            }
+        }
+
+        let fonts = std::mem::take(&mut self.fonts);
+        for (index, font) in fonts.iter().enumerate() {
+            self.write_font(&mut pdf, index, font)?;
+        }
+
+        pdf.extend(&self.chunk);
+        Ok(pdf.finish())
+    }
+
+    /// Finish the document and write it to a file.
+    pub fn save(self, path: impl AsRef<std::path::Path>) -> RenderResult<()> {
+        let bytes = self.finish()?;
+        std::fs::write(path, bytes).map_err(|e| RenderError::Pdf(e.to_string()))
+    }
+
+    // ========================================================================
+    // Document structure
+    // ========================================================================
+
+    /// Allocate a new object reference.
+    fn alloc(&mut self) -> Ref {
+        let id = Ref::new(self.next_ref);
+        self.next_ref += 1;
+        id
+    }
+
+    /// Compress a stream if compression is enabled.
+    fn encode(&self, data: &[u8]) -> Vec<u8> {
+        if !self.compress {
+            return data.to_vec();
+        }
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder
+            .write_all(data)
+            .and_then(|()| encoder.finish())
+            .expect("writing to memory doesn't fail")
+    }
+
+    fn finish_page(&mut self) {
+        if let Some(page) = self.page.take() {
+            self.pages.push(PdfPage {
+                size: page.size,
+                content: page.content.finish(),
+                resources: page.resources,
+            });
+        }
+    }
+
+    /// Get the index of the font for a face, loading its data on first use.
+    fn font_index(&mut self, font_system: &FontSystem, face_id: FontFaceId) -> Option<usize> {
+        if let Some(&index) = self.font_indices.get(&face_id) {
+            return index;
+        }
+        let index = font_system
+            .with_face_data(face_id, |data, index| (data.to_vec(), index))
+            .map(|(data, index)| {
+                let post_script_name = font_system
+                    .face_info(face_id)
+                    .map(|info| info.post_script_name)
+                    .unwrap_or_default();
+                let id = self.alloc();
+                self.fonts.push(PdfFont {
+                    id,
+                    data,
+                    index,
+                    post_script_name,
+                    glyphs: BTreeMap::new(),
+                });
+                self.fonts.len() - 1
+            });
+        self.font_indices.insert(face_id, index);
+        index
+    }
+
+    /// Write a font as a Type 0 font with an embedded subset.
+    fn write_font(&mut self, pdf: &mut Pdf, index: usize, font: &PdfFont) -> RenderResult<()> {
+        let face = ttf_parser::Face::parse(&font.data, font.index)
+            .map_err(|e| RenderError::Pdf(format!("invalid font data: {e}")))?;
+        let glyph_ids: Vec<u16> = font.glyphs.keys().copied().collect();
+        let subset = subsetter::subset(&font.data, font.index, subsetter::Profile::pdf(&glyph_ids))
+            .map_err(|e| RenderError::Pdf(format!("failed to subset font: {e}")))?;
+        let is_cff = face.tables().cff.is_some();
+
+        let cid_font_id = self.alloc();
+        let descriptor_id = self.alloc();
+        let font_file_id = self.alloc();
+        let cmap_id = self.alloc();
+
+        // Subset fonts are named with a six letter tag derived from the index.
+        let mut tag = [b'A'; 6];
+        let mut n = index;
+        for letter in tag.iter_mut().rev() {
+            *letter = b'A' + (n % 26) as u8;
+            n /= 26;
+        }
+        let post_script_name: String = font
+            .post_script_name
+            .chars()
+            .filter(|c| c.is_ascii_graphic() && !"()<>[]{}/%#".contains(*c))
+            .collect();
+        let base_font = format!(
+            "{}+{}",
+            String::from_utf8_lossy(&tag),
+            if post_script_name.is_empty() {
+                format!("Font{index}")
+            } else {
+                post_script_name
+            }
+        );
+
+        pdf.type0_font(font.id)
+            .base_font(Name(base_font.as_bytes()))
+            .encoding_predefined(Name(b"Identity-H"))
+            .descendant_font(cid_font_id)
+            .to_unicode(cmap_id);
+
+        let scale = 1000.0 / f32::from(face.units_per_em());
+        let mut cid_font = pdf.cid_font(cid_font_id);
+        cid_font
+            .subtype(if is_cff {
+                CidFontType::Type0
+            } else {
+                CidFontType::Type2
+            })
+            .base_font(Name(base_font.as_bytes()))
+            .system_info(IDENTITY_SYSTEM_INFO)
+            .font_descriptor(descriptor_id)
+            .default_width(0.0);
+        if !is_cff {
+            cid_font.cid_to_gid_map_predefined(Name(b"Identity"));
+        }
+        let mut widths = cid_font.widths();
+        for &glyph_id in &glyph_ids {
+            let advance = face
+                .glyph_hor_advance(ttf_parser::GlyphId(glyph_id))
+                .unwrap_or(0);
+            widths.consecutive(glyph_id, [f32::from(advance) * scale]);
+        }
+        widths.finish();
+        cid_font.finish();
+
+        let mut flags = FontFlags::SYMBOLIC;
+        if face.is_monospaced() {
+            flags.insert(FontFlags::FIXED_PITCH);
+        }
+        if face.is_italic() {
+            flags.insert(FontFlags::ITALIC);
+        }
+        let bbox = face.global_bounding_box();
+        let mut descriptor = pdf.font_descriptor(descriptor_id);
+        descriptor
+            .name(Name(base_font.as_bytes()))
+            .flags(flags)
+            .bbox(pdf_writer::Rect::new(
+                f32::from(bbox.x_min) * scale,
+                f32::from(bbox.y_min) * scale,
+                f32::from(bbox.x_max) * scale,
+                f32::from(bbox.y_max) * scale,
+            ))
+            .italic_angle(face.italic_angle().unwrap_or(0.0))
+            .ascent(f32::from(face.ascender()) * scale)
+            .descent(f32::from(face.descender()) * scale)
+            .cap_height(f32::from(face.capital_height().unwrap_or(face.ascender())) * scale)
+            .stem_v(10.0 + 0.244 * (f32::from(face.weight().to_number()) - 50.0));
+        if is_cff {
+            descriptor.font_file3(font_file_id);
+        } else {
+            descriptor.font_file2(font_file_id);
+        }
+        descriptor.finish();
+
+        let data = self.encode(&subset);
+        let mut stream = pdf.stream(font_file_id, &data);
+        if self.compress {
+            stream.filter(Filter::FlateDecode);
+        }
+        if is_cff {
+            stream.pair(Name(b"Subtype"), Name(b"OpenType"));
+        }
+        stream.finish();
+
+        let mut cmap = UnicodeCmap::new(Name(b"Custom"), IDENTITY_SYSTEM_INFO);
+        for (&glyph_id, text) in &font.glyphs {
+            if !text.is_empty() {
+                cmap.pair_with_multiple(glyph_id, text.chars());
+            }
+        }
+        let data = self.encode(&cmap.finish());
+        let mut stream = pdf.cmap(cmap_id, &data);
+        if self.compress {
+            stream.filter(Filter::FlateDecode);
+        }
+        stream.finish();
+        Ok(())
+    }
+
+    // ========================================================================
+    // Drawing
+    // ========================================================================
+
+    /// Save the graphics state and apply the clips and the transform.
+    ///
+    /// Returns `false` if there is no page or nothing can be drawn.
+    fn begin_draw(&mut self) -> bool {
+        let Some(page) = self.page.as_mut() else {
+            return false;
+        };
+        let state = self.state.current();
+        if state.opacity <= 0.0 || state.clip.is_some_and(|clip| clip.is_empty()) {
+            return false;
+        }
+
+        let content = &mut page.content;
+        content.save_state();
+        if let Some(clip) = state.clip {
+            content
+                .rect(clip.left(), clip.top(), clip.width(), clip.height())
+                .clip_nonzero()
+                .end_path();
+        }
+        for path in &self.stencil_clips {
+            write_path(content, path);
+            content.clip_nonzero().end_path();
+        }
+        if !state.transform.is_identity() {
+            content.transform(*state.transform.as_array());
+        }
+        self.stats.draw_calls += 1;
+        true
+    }
+
+    fn end_draw(&mut self) {
+        if let Some(page) = self.page.as_mut() {
+            page.content.restore_state();
+        }
+    }
+
+    /// Apply the opacity and blend mode through a graphics state.
+    ///
+    /// Returns `false` if the result is fully transparent.
+    fn set_alpha(&mut self, alpha: f32) -> bool {
+        let state = self.state.current();
+        let alpha = (alpha * state.opacity).clamp(0.0, 1.0);
+        if alpha <= 0.0 {
+            return false;
+        }
+        let (blend_mode, pdf_blend_mode) = match pdf_blend_mode(state.blend_mode) {
+            Some(pdf_mode) => (state.blend_mode, pdf_mode),
+            None => (BlendMode::Normal, PdfBlendMode::Normal),
+        };
+        if alpha >= 1.0 && blend_mode == BlendMode::Normal {
+            return true;
+        }
+
+        let key = ((alpha * 1000.0).round() as u16, blend_mode);
+        let index = match self.graphics_states.get(&key) {
+            Some(&index) => index,
+            None => {
+                let index = self.graphics_state_refs.len();
+                let id = self.alloc();
+                self.graphics_state_refs.push(id);
+                let alpha = f32::from(key.0) / 1000.0;
+                self.chunk
+                    .ext_graphics(id)
+                    .non_stroking_alpha(alpha)
+                    .stroking_alpha(alpha)
+                    .blend_mode(pdf_blend_mode);
+                self.graphics_states.insert(key, index);
+                index
+            }
+        };
+        let Some(page) = self.page.as_mut() else {
+            return false;
+        };
+        page.resources.graphics_states.insert(index);
+        page.content
+            .set_parameters(Name(format!("Gs{index}").as_bytes()));
+        true
+    }
+
+    /// Set the fill or stroke color from a paint.
+    ///
+    /// Returns `false` if the paint is fully transparent.
+    fn set_paint(&mut self, paint: &Paint, stroke: bool) -> bool {
+        match paint {
+            Paint::Solid(color) => self.set_color(*color, stroke),
+            Paint::LinearGradient(gradient) => self.set_gradient(
+                &gradient.stops,
+                FunctionShadingType::Axial,
+                &[
+                    gradient.start.x,
+                    gradient.start.y,
+                    gradient.end.x,
+                    gradient.end.y,
+                ],
+                stroke,
+            ),
+            Paint::RadialGradient(gradient) => {
+                if gradient.radius <= 0.0 {
+                    let Some(last) = gradient.stops.last() else {
+                        return false;
+                    };
+                    return self.set_color(last.color, stroke);
+                }
+                let focus = gradient.focus.unwrap_or(gradient.center);
+                self.set_gradient(
+                    &gradient.stops,
+                    FunctionShadingType::Radial,
+                    &[
+                        focus.x,
+                        focus.y,
+                        0.0,
+                        gradient.center.x,
+                        gradient.center.y,
+                        gradient.radius,
+                    ],
+                    stroke,
+                )
+            }
+        }
+    }
+
+    fn set_color(&mut self, color: Color, stroke: bool) -> bool {
+        let Some(page) = self.page.as_mut() else {
+            return false;
+        };
+        let content = &mut page.content;
+        if stroke {
+            content.set_stroke_rgb(color.r, color.g, color.b);
+        } else {
+            content.set_fill_rgb(color.r, color.g, color.b);
+        }
+        self.set_alpha(color.a)
+    }
+
+    /// Set a shading pattern as the fill or stroke color.
+    fn set_gradient(
+        &mut self,
+        stops: &[GradientStop],
+        kind: FunctionShadingType,
+        coords: &[f32],
+        stroke: bool,
+    ) -> bool {
+        match stops {
+            [] => return false,
+            [stop] => return self.set_color(stop.color, stroke),
+            _ => {}
+        }
+
+        let Some(page_height) = self.page.as_ref().map(|page| page.size.height) else {
+            return false;
+        };
+        let function_id = self.write_gradient_function(stops);
+        let index = self.pattern_refs.len();
+        let pattern_id = self.alloc();
+        self.pattern_refs.push(pattern_id);
+        // Patterns live in the default page space, below the page flip.
+        let matrix = Transform2D::from_matrix(1.0, 0.0, 0.0, -1.0, 0.0, page_height)
+            .then(self.state.transform());
+        let mut pattern = self.chunk.shading_pattern(pattern_id);
+        let mut shading = pattern.function_shading();
+        shading.shading_type(kind);
+        shading.color_space().device_rgb();
+        shading
+            .coords(coords.iter().copied())
+            .function(function_id)
+            .extend([true, true]);
+        shading.finish();
+        pattern.matrix(*matrix.as_array());
+        pattern.finish();
+
+        let Some(page) = self.page.as_mut() else {
+            return false;
+        };
+        page.resources.patterns.insert(index);
+        let name = format!("P{index}");
+        if stroke {
+            page.content
+                .set_stroke_color_space(ColorSpaceOperand::Pattern)
+                .set_stroke_pattern([], Name(name.as_bytes()));
+        } else {
+            page.content
+                .set_fill_color_space(ColorSpaceOperand::Pattern)
+                .set_fill_pattern([], Name(name.as_bytes()));
+        }
+        let alpha = stops.iter().map(|stop| stop.color.a).sum::<f32>() / stops.len() as f32;
+        self.set_alpha(alpha)
+    }
+
+    /// Write the function mapping gradient positions to colors.
+    fn write_gradient_function(&mut self, stops: &[GradientStop]) -> Ref {
+        let mut stops = stops.to_vec();
+        if stops[0].offset > 0.0 {
+            stops.insert(0, GradientStop::new(0.0, stops[0].color));
+        }
+        let last = stops[stops.len() - 1];
+        if last.offset < 1.0 {
+            stops.push(GradientStop::new(1.0, last.color));
+        }
+
+        let mut functions = Vec::with_capacity(stops.len() - 1);
+        for pair in stops.windows(2) {
+            let id = self.alloc();
+            let (a, b) = (pair[0].color, pair[1].color);
+            self.chunk
+                .exponential_function(id)
+                .domain([0.0, 1.0])
+                .c0([a.r, a.g, a.b])
+                .c1([b.r, b.g, b.b])
+                .n(1.0);
+            functions.push(id);
+        }
+        if let [function] = functions[..] {
+            return function;
+        }
+
+        let id = self.alloc();
+        let bounds: Vec<f32> = stops[1..stops.len() - 1]
+            .iter()
+            .map(|stop| stop.offset.clamp(0.0, 1.0))
+            .collect();
+        self.chunk
+            .stitching_function(id)
+            .domain([0.0, 1.0])
+            .functions(functions.iter().copied())
+            .bounds(bounds)
+            .encode(functions.iter().flat_map(|_| [0.0, 1.0]));
+        id
+    }
+
+    /// Get the index of the image object for an image, writing it on first use.
+    fn image_index(&mut self, image: &Image) -> usize {
+        if let Some(index) = self
+            .images
+            .iter()
+            .position(|known| known.same_pixels(image))
+        {
+            return index;
+        }
+
+        let pixels = image.pixels().unwrap_or_default();
+        let rgb: Vec<u8> = pixels
+            .chunks_exact(4)
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect();
+        let alpha: Vec<u8> = pixels.chunks_exact(4).map(|pixel| pixel[3]).collect();
+        let mask_id = if alpha.iter().any(|&a| a < 255) {
+            let id = self.alloc();
+            let data = self.encode(&alpha);
+            let mut mask = self.chunk.image_xobject(id, &data);
+            mask.width(image.width() as i32)
+                .height(image.height() as i32)
+                .bits_per_component(8);
+            mask.color_space().device_gray();
+            if self.compress {
+                mask.filter(Filter::FlateDecode);
+            }
+            mask.finish();
+            Some(id)
+        } else {
+            None
+        };
+
+        let id = self.alloc();
+        let data = self.encode(&rgb);
+        let mut xobject = self.chunk.image_xobject(id, &data);
+        xobject
+            .width(image.width() as i32)
+            .height(image.height() as i32)
+            .bits_per_component(8);
+        xobject.color_space().device_rgb();
+        if let Some(mask_id) = mask_id {
+            xobject.s_mask(mask_id);
+        }
+        if self.compress {
+            xobject.filter(Filter::FlateDecode);
+        }
+        xobject.finish();
+
+        self.images.push(image.clone());
+        self.image_refs.push(id);
+        self.images.len() - 1
+    }
+
+    fn write_stroke_style(&mut self, stroke: &Stroke) {
+        let Some(page) = self.page.as_mut() else {
+            return;
+        };
+        let content = &mut page.content;
+        content
+            .set_line_width(stroke.width)
+            .set_line_cap(match stroke.cap {
+                LineCap::Butt => LineCapStyle::ButtCap,
+                LineCap::Round => LineCapStyle::RoundCap,
+                LineCap::Square => LineCapStyle::ProjectingSquareCap,
+            })
+            .set_line_join(match stroke.join {
+                LineJoin::Miter => LineJoinStyle::MiterJoin,
+                LineJoin::Round => LineJoinStyle::RoundJoin,
+                LineJoin::Bevel => LineJoinStyle::BevelJoin,
+            })
+            .set_miter_limit(stroke.miter_limit);
+        if let Some(DashPattern { pattern, offset }) = &stroke.dash_pattern
+            && pattern.iter().any(|&length| length > 0.0)
+        {
+            content.set_dash_pattern(pattern.iter().copied(), *offset);
+        }
+    }
+}
+
+impl Renderer for PdfRenderer {
+    fn begin_frame(&mut self, clear_color: Color, viewport_size: Size) {
+        self.finish_page();
+        let mut content = Content::new();
+        // Flip the page to y-down coordinates with the origin at the top left.
+        content.transform([1.0, 0.0, 0.0, -1.0, 0.0, viewport_size.height]);
+        self.page = Some(PageBuilder {
+            size: viewport_size,
+            content,
+            resources: PageResources::default(),
+        });
+        self.state.reset();
+        self.stencil_clips.clear();
+        self.stats = FrameStats::default();
+
+        if clear_color.a > 0.0 {
+            self.fill_rect(
+                Rect::new(0.0, 0.0, viewport_size.width, viewport_size.height),
+                clear_color,
+            );
+        }
+    }
+
+    fn end_frame(&mut self) -> FrameStats {
+        self.finish_page();
+        self.stats.clone()
+    }
+
+    fn save(&mut self) {
+        self.state.save();
+    }
+
+    fn restore(&mut self) {
+        self.state.restore();
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    fn transform(&self) -> &Transform2D {
+        self.state.transform()
+    }
+
+    fn set_transform(&mut self, transform: Transform2D) {
+        self.state.set_transform(transform);
+    }
+
+    fn concat_transform(&mut self, transform: &Transform2D) {
+        self.state.concat_transform(transform);
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.state.translate(tx, ty);
+    }
+
+    fn scale(&mut self, sx: f32, sy: f32) {
+        self.state.scale(sx, sy);
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.state.rotate(angle);
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        self.state.clip_rect(rect);
+    }
+
+    fn clip_rounded_rect(&mut self, rrect: RoundedRect) {
+        if rrect.radii.is_zero() {
+            self.clip_rect(rrect.rect);
+            return;
+        }
+        self.clip_path(&Path::rounded_rect(rrect.rect, rrect.radii));
+    }
+
+    fn restore_clip(&mut self) {
+        self.stencil_clips.pop();
+    }
+
+    fn clip_bounds(&self) -> Option<Rect> {
+        self.state.clip_bounds()
+    }
+
+    fn has_stencil_clips(&self) -> bool {
+        !self.stencil_clips.is_empty()
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        if path.is_empty() {
+            return;
+        }
+        self.stencil_clips
+            .push(path.transformed(self.state.transform()));
+    }
+
+    fn fill_rect(&mut self, rect: Rect, paint: impl Into<Paint>) {
+        self.fill_path(&Path::rect(rect), paint, FillRule::NonZero);
+    }
+
+    fn fill_rounded_rect(&mut self, rrect: RoundedRect, paint: impl Into<Paint>) {
+        self.fill_path(
+            &Path::rounded_rect(rrect.rect, rrect.radii),
+            paint,
+            FillRule::NonZero,
+        );
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, stroke: &Stroke) {
+        self.stroke_path(&Path::rect(rect), stroke);
+    }
+
+    fn stroke_rounded_rect(&mut self, rrect: RoundedRect, stroke: &Stroke) {
+        self.stroke_path(&Path::rounded_rect(rrect.rect, rrect.radii), stroke);
+    }
+
+    fn draw_box_shadow(&mut self, rect: Rect, shadow: &BoxShadow) {
+        self.draw_box_shadow_rounded(RoundedRect::new(rect, 0.0), shadow);
+    }
+
+    fn draw_box_shadow_rounded(&mut self, rrect: RoundedRect, shadow: &BoxShadow) {
+        let bounds = shadow.expanded_bounds(rrect.rect);
+        let width = (bounds.width() * SHADOW_SCALE).ceil();
+        let height = (bounds.height() * SHADOW_SCALE).ceil();
+        if width < 1.0 || height < 1.0 || self.page.is_none() {
+            return;
+        }
+
+        let mut raster = SoftwareRenderer::new();
+        raster.begin_frame(Color::TRANSPARENT, Size::new(width, height));
+        raster.scale(SHADOW_SCALE, SHADOW_SCALE);
+        raster.translate(-bounds.left(), -bounds.top());
+        raster.draw_box_shadow_rounded(rrect, shadow);
+        raster.end_frame();
+        let Ok(image) = Image::from_rgba(
+            &raster.to_image_buffer().as_rgba8_bytes(),
+            raster.width(),
+            raster.height(),
+        ) else {
+            return;
+        };
+        let dest = Rect::new(
+            bounds.left(),
+            bounds.top(),
+            width / SHADOW_SCALE,
+            height / SHADOW_SCALE,
+        );
+        self.draw_image_rect(&image, Rect::new(0.0, 0.0, width, height), dest);
+    }
+
+    fn draw_line(&mut self, from: Point, to: Point, stroke: &Stroke) {
+        let mut path = Path::new();
+        path.move_to(from).line_to(to);
+        self.stroke_path(&path, stroke);
+    }
+
+    fn draw_polyline(&mut self, points: &[Point], stroke: &Stroke) {
+        let Some((&first, rest)) = points.split_first() else {
+            return;
+        };
+        let mut path = Path::new();
+        path.move_to(first);
+        for &point in rest {
+            path.line_to(point);
+        }
+        self.stroke_path(&path, stroke);
+    }
+
+    fn fill_ellipse(
+        &mut self,
+        center: Point,
+        radius_x: f32,
+        radius_y: f32,
+        paint: impl Into<Paint>,
+    ) {
+        self.fill_path(
+            &Path::ellipse(center, radius_x, radius_y),
+            paint,
+            FillRule::NonZero,
+        );
+    }
+
+    fn stroke_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, stroke: &Stroke) {
+        self.stroke_path(&Path::ellipse(center, radius_x, radius_y), stroke);
+    }
+
+    fn fill_path(&mut self, path: &Path, paint: impl Into<Paint>, fill_rule: FillRule) {
+        if path.is_empty() {
+            return;
+        }
+        let paint = paint.into();
+        if !self.begin_draw() {
+            return;
+        }
+        if self.set_paint(&paint, false)
+            && let Some(page) = self.page.as_mut()
+        {
+            let content = &mut page.content;
+            write_path(content, path);
+            match fill_rule {
+                FillRule::NonZero => content.fill_nonzero(),
+                FillRule::EvenOdd => content.fill_even_odd(),
+            };
+        }
+        self.end_draw();
+    }
+
+    fn stroke_path(&mut self, path: &Path, stroke: &Stroke) {
+        if path.is_empty() || stroke.width <= 0.0 || !self.begin_draw() {
+            return;
+        }
+        if self.set_paint(&stroke.paint, true) {
+            self.write_stroke_style(stroke);
+            if let Some(page) = self.page.as_mut() {
+                write_path(&mut page.content, path);
+                page.content.stroke();
+            }
+        }
+        self.end_draw();
+    }
+
+    fn draw_image(&mut self, image: &Image, dest: Rect, scale_mode: ImageScaleMode) {
+        let src = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        if scale_mode != ImageScaleMode::Tile {
+            let dest = scaled_dest(image.size(), dest, scale_mode);
+            self.draw_image_rect(image, src, dest);
+            return;
+        }
+
+        if image.width() == 0 || image.height() == 0 {
+            return;
+        }
+        self.save();
+        self.clip_rect(dest);
+        let mut y = dest.top();
+        while y < dest.bottom() {
+            let mut x = dest.left();
+            while x < dest.right() {
+                self.draw_image_rect(image, src, Rect::new(x, y, src.width(), src.height()));
+                x += src.width();
+            }
+            y += src.height();
+        }
+        self.restore();
+    }
+
+    fn draw_image_rect(&mut self, image: &Image, src: Rect, dest: Rect) {
+        if image.width() == 0
+            || image.height() == 0
+            || src.width() <= 0.0
+            || src.height() <= 0.0
+            || dest.width() <= 0.0
+            || dest.height() <= 0.0
+            || self.page.is_none()
+            || image.pixels().is_none()
+        {
+            return;
+        }
+        let index = self.image_index(image);
+        if !self.begin_draw() {
+            return;
+        }
+        if self.set_alpha(1.0)
+            && let Some(page) = self.page.as_mut()
+        {
+            // Place the whole image so that `src` lands on `dest`, clipped
+            // to `dest`. Image space is flipped like the page.
+            let scale_x = dest.width() / src.width();
+            let scale_y = dest.height() / src.height();
+            let width = image.width() as f32 * scale_x;
+            let height = image.height() as f32 * scale_y;
+            let left = dest.left() - src.left() * scale_x;
+            let top = dest.top() - src.top() * scale_y;
+            page.resources.images.insert(index);
+            page.content
+                .rect(dest.left(), dest.top(), dest.width(), dest.height())
+                .clip_nonzero()
+                .end_path()
+                .transform([width, 0.0, 0.0, -height, left, top + height])
+                .x_object(Name(format!("Im{index}").as_bytes()));
+        }
+        self.end_draw();
+    }
+
+    fn draw_nine_patch(&mut self, nine_patch: &NinePatch, dest: Rect) {
+        for (src, dest) in nine_patch.calculate_patches(dest) {
+            self.draw_image_rect(&nine_patch.image, src, dest);
+        }
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        if self.state.current().blend_mode != mode {
+            self.state.current_mut().blend_mode = mode;
+            self.stats.state_changes += 1;
+        }
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.state.current().blend_mode
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.state.current_mut().opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    fn opacity(&self) -> f32 {
+        self.state.current().opacity
+    }
+}
+
+impl std::fmt::Debug for PdfRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PdfRenderer")
+            .field("title", &self.title)
+            .field("pages", &self.page_count())
+            .field("images", &self.images.len())
+            .field("fonts", &self.fonts.len())
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Write the names a page uses for shared resources of one kind.
+fn write_resource_names(mut dict: Dict<'_>, prefix: &str, indices: &BTreeSet<usize>, refs: &[Ref]) {
+    for &index in indices {
+        dict.pair(Name(format!("{prefix}{index}").as_bytes()), refs[index]);
+    }
+}
+
+/// Map a blend mode to its PDF equivalent, if there is one.
+fn pdf_blend_mode(mode: BlendMode) -> Option<PdfBlendMode> {
+    Some(match mode {
+        BlendMode::Normal => PdfBlendMode::Normal,
+        BlendMode::Multiply => PdfBlendMode::Multiply,
+        BlendMode::Screen => PdfBlendMode::Screen,
+        BlendMode::Overlay => PdfBlendMode::Overlay,
+        BlendMode::Darken => PdfBlendMode::Darken,
+        BlendMode::Lighten => PdfBlendMode::Lighten,
+        BlendMode::ColorDodge => PdfBlendMode::ColorDodge,
+        BlendMode::ColorBurn => PdfBlendMode::ColorBurn,
+        BlendMode::HardLight => PdfBlendMode::HardLight,
+        BlendMode::SoftLight => PdfBlendMode::SoftLight,
+        BlendMode::Difference => PdfBlendMode::Difference,
+        BlendMode::Exclusion => PdfBlendMode::Exclusion,
+        BlendMode::Hue => PdfBlendMode::Hue,
+        BlendMode::Saturation => PdfBlendMode::Saturation,
+        BlendMode::Color => PdfBlendMode::Color,
+        BlendMode::Luminosity => PdfBlendMode::Luminosity,
+        _ => return None,
+    })
+}
+
+/// Write a path's segments, converting quadratic curves and arcs to cubics.
+fn write_path(content: &mut Content, path: &Path) {
+    for event in to_lyon_path(path).iter() {
+        match event {
+            PathEvent::Begin { at } => {
+                content.move_to(at.x, at.y);
+            }
+            PathEvent::Line { to, .. } => {
+                content.line_to(to.x, to.y);
+            }
+            PathEvent::Quadratic { from, ctrl, to } => {
+                let c1 = from + (ctrl - from) * (2.0 / 3.0);
+                let c2 = to + (ctrl - to) * (2.0 / 3.0);
+                content.cubic_to(c1.x, c1.y, c2.x, c2.y, to.x, to.y);
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => {
+                content.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y);
+            }
+            PathEvent::End { close, .. } => {
+                if close {
+                    content.close_path();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::{Font, FontFamily};
+
+    fn count(haystack: &[u8], needle: &str) -> usize {
+        haystack
+            .windows(needle.len())
+            .filter(|window| *window == needle.as_bytes())
+            .count()
+    }
+
+    #[test]
+    fn test_pages() {
+        let mut renderer = PdfRenderer::new()
+            .with_compression(false)
+            .with_title("Report");
+        for height in [100.0, 200.0] {
+            renderer.begin_frame(Color::WHITE, Size::new(50.0, height));
+            renderer.fill_rect(Rect::new(10.0, 10.0, 20.0, 20.0), Color::RED);
+            renderer.end_frame();
+        }
+        assert_eq!(renderer.page_count(), 2);
+
+        let pdf = renderer.finish().unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+        assert_eq!(count(&pdf, "/Count 2"), 1);
+        assert_eq!(count(&pdf, "/MediaBox [0 0 50 200]"), 1);
+        assert_eq!(count(&pdf, "(Report)"), 1);
+        // Pages are flipped to y-down coordinates.
+        assert_eq!(count(&pdf, "1 0 0 -1 0 100 cm"), 1);
+        assert_eq!(count(&pdf, "1 0 0 rg"), 2);
+    }
+
+    #[test]
+    fn test_drawing_outside_a_frame_is_ignored() {
+        let gradient = Paint::linear_gradient(
+            Point::new(0.0, 0.0),
+            Point::new(10.0, 0.0),
+            vec![
+                GradientStop::new(0.0, Color::RED),
+                GradientStop::new(1.0, Color::BLUE),
+            ],
+        );
+        let image = Image::from_rgba(&[255, 0, 0, 255], 1, 1).unwrap();
+        let draw = |renderer: &mut PdfRenderer| {
+            renderer.save();
+            renderer.clip_rounded_rect(RoundedRect::new(Rect::new(0.0, 0.0, 10.0, 10.0), 2.0));
+            renderer.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::RED);
+            renderer.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), gradient.clone());
+            renderer.stroke_rect(
+                Rect::new(0.0, 0.0, 10.0, 10.0),
+                &Stroke::new(Color::BLACK, 1.0),
+            );
+            renderer.draw_image(&image, Rect::new(0.0, 0.0, 4.0, 4.0), ImageScaleMode::Tile);
+            renderer.draw_box_shadow(
+                Rect::new(0.0, 0.0, 10.0, 10.0),
+                &BoxShadow::drop_shadow(Color::BLACK, 4.0),
+            );
+            renderer.restore_clip();
+            renderer.restore();
+        };
+
+        let mut renderer = PdfRenderer::new().with_compression(false);
+        draw(&mut renderer);
+        renderer.begin_frame(Color::TRANSPARENT, Size::new(20.0, 20.0));
+        renderer.end_frame();
+        draw(&mut renderer);
+        assert_eq!(renderer.page_count(), 1);
+
+        let pdf = renderer.finish().unwrap();
+        assert_eq!(count(&pdf, "/Count 1"), 1);
+        assert_eq!(count(&pdf, "/Subtype /Image"), 0);
+        assert_eq!(count(&pdf, "/ShadingType"), 0);
+        assert_eq!(count(&pdf, " re"), 0);
+    }
+
+    #[test]
+    fn test_paints_and_resources() {
+        let mut renderer = PdfRenderer::new().with_compression(false);
+        renderer.begin_frame(Color::TRANSPARENT, Size::new(100.0, 100.0));
+
+        renderer.set_opacity(0.5);
+        renderer.set_blend_mode(BlendMode::Multiply);
+        renderer.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::BLUE);
+        renderer.fill_rect(Rect::new(10.0, 0.0, 10.0, 10.0), Color::GREEN);
+        // Porter-Duff modes fall back to normal blending.
+        renderer.set_blend_mode(BlendMode::Xor);
+        renderer.fill_rect(Rect::new(20.0, 0.0, 10.0, 10.0), Color::GREEN);
+        renderer.set_blend_mode(BlendMode::Normal);
+        renderer.set_opacity(1.0);
+
+        let gradient = Paint::linear_gradient(
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            vec![
+                GradientStop::new(0.0, Color::RED),
+                GradientStop::new(0.5, Color::GREEN),
+                GradientStop::new(1.0, Color::BLUE),
+            ],
+        );
+        renderer.clip_rounded_rect(RoundedRect::new(Rect::new(0.0, 20.0, 100.0, 50.0), 8.0));
+        renderer.fill_rect(Rect::new(0.0, 20.0, 100.0, 50.0), gradient);
+        renderer.restore_clip();
+
+        let stroke =
+            Stroke::new(Color::BLACK, 2.0).with_dash(DashPattern::new(vec![4.0, 2.0], 0.0));
+        renderer.stroke_rect(Rect::new(5.0, 80.0, 90.0, 10.0), &stroke);
+
+        let image = Image::from_rgba(&[255, 0, 0, 128, 0, 255, 0, 255], 2, 1).unwrap();
+        renderer.draw_image(
+            &image,
+            Rect::new(0.0, 90.0, 20.0, 10.0),
+            ImageScaleMode::Stretch,
+        );
+        renderer.draw_image(
+            &image,
+            Rect::new(20.0, 90.0, 4.0, 2.0),
+            ImageScaleMode::Tile,
+        );
+        renderer.draw_box_shadow(
+            Rect::new(50.0, 90.0, 10.0, 5.0),
+            &BoxShadow::drop_shadow(Color::BLACK, 4.0),
+        );
+        renderer.end_frame();
+
+        let pdf = renderer.finish().unwrap();
+        // The transparent clear color doesn't fill the page.
+        assert_eq!(count(&pdf, "0 0 100 100 re"), 0);
+        // Equal graphics states are shared.
+        assert_eq!(count(&pdf, "/ca 0.5"), 2);
+        assert_eq!(count(&pdf, "/BM /Multiply"), 1);
+        assert_eq!(count(&pdf, "/BM /Normal"), 1);
+        assert_eq!(count(&pdf, "/ShadingType 2"), 1);
+        assert_eq!(count(&pdf, "/FunctionType 3"), 1);
+        assert_eq!(count(&pdf, "/Pattern cs"), 1);
+        assert_eq!(count(&pdf, "W\nn"), 11);
+        assert_eq!(count(&pdf, "[4 2] 0 d"), 1);
+        // The image is written once and drawn for each tile, the shadow is
+        // an image of its own, and both have soft masks for their alpha.
+        assert_eq!(count(&pdf, "/Subtype /Image"), 4);
+        assert_eq!(count(&pdf, "/SMask"), 2);
+        assert_eq!(count(&pdf, "/Im0 Do"), 5);
+    }
+
+    #[test]
+    fn test_text_embeds_font_subsets() {
+        let mut font_system = FontSystem::new();
+        let font = Font::new(FontFamily::SansSerif, 12.0);
+        let layout = TextLayout::new(&mut font_system, "Hi\nHi", &font);
+        if layout.glyphs().next().is_none() {
+            // No system fonts are available.
+            return;
+        }
+
+        let mut renderer = PdfRenderer::new().with_compression(false);
+        renderer.begin_frame(Color::WHITE, Size::new(100.0, 100.0));
+        let line_height = layout.lines()[0].height;
+        renderer.clip_rect(Rect::new(0.0, 10.0, 100.0, line_height));
+        renderer.draw_text(&font_system, &layout, Point::new(10.0, 10.0), Color::BLACK);
+        renderer.end_frame();
+
+        let pdf = renderer.finish().unwrap();
+        assert_eq!(count(&pdf, "/Subtype /Type0"), 1);
+        assert_eq!(count(&pdf, "/Encoding /Identity-H"), 1);
+        assert_eq!(count(&pdf, "/ToUnicode"), 1);
+        assert_eq!(count(&pdf, "/FontFile2") + count(&pdf, "/FontFile3"), 1);
+        // Only the line inside the clip rect is drawn.
+        assert_eq!(count(&pdf, " Tj"), layout.lines()[0].glyphs.len());
+    }
+}
@@ -0,0 +1,1291 @@
+//! Recorded display lists.
+//!
+//! A [`Picture`] implements the [`Renderer`] trait by recording each call as
+//! a [`DrawCommand`] instead of drawing it. The recording can be replayed into
+//! any other renderer, any number of times, with an extra transform applied
+//! on top. Widgets can use this to cache their paint output between frames,
+//! to draw an item again as a drag pixmap, or to send the same drawing to a
+//! printer or an export backend.
+//!
+//! Text is not part of the [`Renderer`] trait, so pictures record it through
+//! the inherent [`Picture::draw_text`] and replay it through a callback (see
+//! [`Picture::replay_with_text`]).
+//!
+//! A picture's [`Display`](fmt::Display) output is a stable, line-based text
+//! form of its command stream. It can be stored to inspect a recorded frame,
+//! or compared in unit tests to check what a widget paints without a GPU.
+//!
+//! # Example
+//!
+//! ```
+//! use horizon_lattice_render::{
+//!     Color, Picture, Rect, Renderer, Size, SoftwareRenderer, Transform2D,
+//! };
+//!
+//! let mut picture = Picture::new();
+//! picture.begin_frame(Color::TRANSPARENT, Size::new(20.0, 20.0));
+//! picture.fill_rect(Rect::new(0.0, 0.0, 20.0, 20.0), Color::RED);
+//! picture.end_frame();
+//! assert_eq!(picture.to_string(), "picture 20x20\nfill_rect 0 0 20 20 #FF0000\n");
+//!
+//! let mut renderer = SoftwareRenderer::new();
+//! renderer.begin_frame(Color::WHITE, Size::new(64.0, 64.0));
+//! picture.replay(&mut renderer, Transform2D::translate(30.0, 30.0));
+//! renderer.end_frame();
+//! assert_eq!(renderer.pixel(40, 40), Some(Color::RED));
+//! assert_eq!(renderer.pixel(10, 10), Some(Color::WHITE));
+//! ```
+
+use std::fmt;
+
+use crate::image::{Image, ImageScaleMode, NinePatch};
+use crate::paint::{BlendMode, BoxShadow, FillRule, Paint, Stroke};
+use crate::renderer::{FrameStats, RenderStateStack, Renderer};
+use crate::text::TextLayout;
+use crate::transform::Transform2D;
+use crate::types::{Color, CornerRadii, Path, PathCommand, Point, Rect, RoundedRect, Size};
+
+/// A single recorded renderer call.
+///
+/// Geometry is stored as it was passed to the renderer, in the coordinate
+/// space that was current at the time of the call.
+#[derive(Debug, Clone)]
+pub enum DrawCommand {
+    /// [`Renderer::save`].
+    Save,
+    /// [`Renderer::restore`].
+    Restore,
+    /// [`Renderer::reset`].
+    Reset,
+    /// [`Renderer::set_transform`].
+    SetTransform(Transform2D),
+    /// [`Renderer::concat_transform`].
+    ConcatTransform(Transform2D),
+    /// [`Renderer::translate`].
+    Translate {
+        /// Horizontal offset.
+        tx: f32,
+        /// Vertical offset.
+        ty: f32,
+    },
+    /// [`Renderer::scale`].
+    Scale {
+        /// Horizontal scale factor.
+        sx: f32,
+        /// Vertical scale factor.
+        sy: f32,
+    },
+    /// [`Renderer::rotate`], in radians.
+    Rotate(f32),
+    /// [`Renderer::clip_rect`].
+    ClipRect(Rect),
+    /// [`Renderer::clip_rounded_rect`].
+    ClipRoundedRect(RoundedRect),
+    /// [`Renderer::clip_path`].
+    ClipPath(Path),
+    /// [`Renderer::restore_clip`].
+    RestoreClip,
+    /// [`Renderer::fill_rect`].
+    FillRect {
+        /// The rectangle to fill.
+        rect: Rect,
+        /// The fill paint.
+        paint: Paint,
+    },
+    /// [`Renderer::fill_rounded_rect`].
+    FillRoundedRect {
+        /// The rounded rectangle to fill.
+        rect: RoundedRect,
+        /// The fill paint.
+        paint: Paint,
+    },
+    /// [`Renderer::stroke_rect`].
+    StrokeRect {
+        /// The rectangle to stroke.
+        rect: Rect,
+        /// The stroke style.
+        stroke: Stroke,
+    },
+    /// [`Renderer::stroke_rounded_rect`].
+    StrokeRoundedRect {
+        /// The rounded rectangle to stroke.
+        rect: RoundedRect,
+        /// The stroke style.
+        stroke: Stroke,
+    },
+    /// [`Renderer::draw_box_shadow`].
+    BoxShadow {
+        /// The rectangle casting the shadow.
+        rect: Rect,
+        /// The shadow parameters.
+        shadow: BoxShadow,
+    },
+    /// [`Renderer::draw_box_shadow_rounded`].
+    BoxShadowRounded {
+        /// The rounded rectangle casting the shadow.
+        rect: RoundedRect,
+        /// The shadow parameters.
+        shadow: BoxShadow,
+    },
+    /// [`Renderer::draw_line`].
+    Line {
+        /// The start point.
+        from: Point,
+        /// The end point.
+        to: Point,
+        /// The stroke style.
+        stroke: Stroke,
+    },
+    /// [`Renderer::draw_polyline`].
+    Polyline {
+        /// The connected points.
+        points: Vec<Point>,
+        /// The stroke style.
+        stroke: Stroke,
+    },
+    /// [`Renderer::fill_ellipse`].
+    FillEllipse {
+        /// The center of the ellipse.
+        center: Point,
+        /// The horizontal radius.
+        radius_x: f32,
+        /// The vertical radius.
+        radius_y: f32,
+        /// The fill paint.
+        paint: Paint,
+    },
+    /// [`Renderer::stroke_ellipse`].
+    StrokeEllipse {
+        /// The center of the ellipse.
+        center: Point,
+        /// The horizontal radius.
+        radius_x: f32,
+        /// The vertical radius.
+        radius_y: f32,
+        /// The stroke style.
+        stroke: Stroke,
+    },
+    /// [`Renderer::fill_path`].
+    FillPath {
+        /// The path to fill.
+        path: Path,
+        /// The fill paint.
+        paint: Paint,
+        /// The fill rule.
+        fill_rule: FillRule,
+    },
+    /// [`Renderer::stroke_path`].
+    StrokePath {
+        /// The path to stroke.
+        path: Path,
+        /// The stroke style.
+        stroke: Stroke,
+    },
+    /// [`Renderer::draw_image`].
+    Image {
+        /// The image to draw.
+        image: Image,
+        /// The destination rectangle.
+        dest: Rect,
+        /// How the image is scaled into `dest`.
+        scale_mode: ImageScaleMode,
+    },
+    /// [`Renderer::draw_image_rect`].
+    ImageRect {
+        /// The image to draw.
+        image: Image,
+        /// The source rectangle in image pixels.
+        src: Rect,
+        /// The destination rectangle.
+        dest: Rect,
+    },
+    /// [`Renderer::draw_nine_patch`].
+    NinePatch {
+        /// The nine-patch to draw.
+        nine_patch: NinePatch,
+        /// The destination rectangle.
+        dest: Rect,
+    },
+    /// [`Renderer::set_blend_mode`].
+    SetBlendMode(BlendMode),
+    /// [`Renderer::set_opacity`].
+    SetOpacity(f32),
+    /// [`Picture::draw_text`].
+    Text {
+        /// The laid out text.
+        layout: TextLayout,
+        /// The top-left corner of the layout.
+        position: Point,
+        /// The default text color.
+        color: Color,
+    },
+}
+
+impl DrawCommand {
+    /// Check whether the command draws something, as opposed to changing
+    /// renderer state.
+    pub fn is_drawing(&self) -> bool {
+        !matches!(
+            self,
+            Self::Save
+                | Self::Restore
+                | Self::Reset
+                | Self::SetTransform(_)
+                | Self::ConcatTransform(_)
+                | Self::Translate { .. }
+                | Self::Scale { .. }
+                | Self::Rotate(_)
+                | Self::ClipRect(_)
+                | Self::ClipRoundedRect(_)
+                | Self::ClipPath(_)
+                | Self::RestoreClip
+                | Self::SetBlendMode(_)
+                | Self::SetOpacity(_)
+        )
+    }
+}
+
+/// A recorded display list that can be replayed into any [`Renderer`].
+///
+/// Recording starts empty. [`begin_frame`](Renderer::begin_frame) clears the
+/// picture and sets its [`size`](Self::size); the clear color isn't recorded,
+/// so a replayed picture draws over whatever the target already holds.
+/// Drawing into a picture without calling `begin_frame` is also fine.
+///
+/// Calls that can't change anything aren't recorded: a `restore` without a
+/// matching `save`, a `restore_clip` without a stencil clip, and a clip to an
+/// empty path. A rounded clip without rounding is recorded as a
+/// [`ClipRect`](DrawCommand::ClipRect). This keeps every recording balanced,
+/// so replaying it never pops state that belongs to the target.
+#[derive(Debug, Clone, Default)]
+pub struct Picture {
+    size: Size,
+    commands: Vec<DrawCommand>,
+    state: RenderStateStack,
+    /// Number of rounded-rect and path clips currently pushed.
+    stencil_clips: usize,
+    /// Union of the drawn areas, in recording device space.
+    bounds: Option<Rect>,
+}
+
+impl Picture {
+    /// Create an empty picture.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the viewport size passed to the last `begin_frame`.
+    #[inline]
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// Get the recorded commands, in call order.
+    #[inline]
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+
+    /// Get the number of recorded commands.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    /// Check whether nothing has been recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    /// Get the area the recorded drawing covers, before any replay transform.
+    ///
+    /// The bounds are conservative: strokes are outset by half their width,
+    /// paths and curves by their control points, and only rectangular clips
+    /// are taken into account. Returns `None` if nothing visible was drawn.
+    #[inline]
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    /// Remove all commands and reset the recording state.
+    ///
+    /// The size is kept.
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.state.reset();
+        self.stencil_clips = 0;
+        self.bounds = None;
+    }
+
+    /// Record a text layout drawn with its top-left corner at `position`.
+    ///
+    /// `color` is used for glyphs the layout doesn't assign a color to.
+    pub fn draw_text(&mut self, layout: &TextLayout, position: Point, color: Color) {
+        self.add_bounds(Rect::new(
+            position.x,
+            position.y,
+            layout.width(),
+            layout.height(),
+        ));
+        self.commands.push(DrawCommand::Text {
+            layout: layout.clone(),
+            position,
+            color,
+        });
+    }
+
+    /// Replay the picture into `renderer`, with `transform` applied on top of
+    /// the renderer's current transform.
+    ///
+    /// Recorded opacities are multiplied by the renderer's opacity, and
+    /// [`set_transform`](Renderer::set_transform) calls are made relative to
+    /// the replay transform. The renderer's state, including any stencil
+    /// clip the recording leaves pushed, is restored afterwards. Text is
+    /// skipped; use [`replay_with_text`](Self::replay_with_text) to draw it.
+    pub fn replay<R: Renderer + ?Sized>(&self, renderer: &mut R, transform: Transform2D) {
+        self.replay_with_text(renderer, transform, |_, _, _, _| {});
+    }
+
+    /// Replay the picture like [`replay`](Self::replay), passing recorded
+    /// text to `draw_text`.
+    ///
+    /// The callback gets the renderer with the recorded transform applied,
+    /// and the layout, position and color as recorded, for example:
+    ///
+    /// ```ignore
+    /// picture.replay_with_text(&mut renderer, transform, |renderer, layout, position, color| {
+    ///     renderer.draw_text(&mut font_system, layout, position, color);
+    /// });
+    /// ```
+    pub fn replay_with_text<R, F>(&self, renderer: &mut R, transform: Transform2D, mut draw_text: F)
+    where
+        R: Renderer + ?Sized,
+        F: FnMut(&mut R, &TextLayout, Point, Color),
+    {
+        renderer.save();
+        renderer.concat_transform(&transform);
+        let base = *renderer.transform();
+        let base_blend_mode = renderer.blend_mode();
+        let base_opacity = renderer.opacity();
+
+        let mut depth = 0usize;
+        let mut stencil_clips = 0usize;
+        for command in &self.commands {
+            match command {
+                DrawCommand::Save => {
+                    renderer.save();
+                    depth += 1;
+                }
+                DrawCommand::Restore => {
+                    renderer.restore();
+                    depth -= 1;
+                }
+                DrawCommand::Reset => {
+                    for _ in 0..depth {
+                        renderer.restore();
+                    }
+                    depth = 0;
+                    renderer.set_transform(base);
+                    renderer.set_blend_mode(base_blend_mode);
+                    renderer.set_opacity(base_opacity);
+                }
+                DrawCommand::SetTransform(t) => renderer.set_transform(base.then(t)),
+                DrawCommand::ConcatTransform(t) => renderer.concat_transform(t),
+                DrawCommand::Translate { tx, ty } => renderer.translate(*tx, *ty),
+                DrawCommand::Scale { sx, sy } => renderer.scale(*sx, *sy),
+                DrawCommand::Rotate(angle) => renderer.rotate(*angle),
+                DrawCommand::ClipRect(rect) => renderer.clip_rect(*rect),
+                DrawCommand::ClipRoundedRect(rect) => {
+                    renderer.clip_rounded_rect(*rect);
+                    stencil_clips += 1;
+                }
+                DrawCommand::ClipPath(path) => {
+                    renderer.clip_path(path);
+                    stencil_clips += 1;
+                }
+                DrawCommand::RestoreClip => {
+                    renderer.restore_clip();
+                    stencil_clips -= 1;
+                }
+                DrawCommand::FillRect { rect, paint } => renderer.fill_rect(*rect, paint.clone()),
+                DrawCommand::FillRoundedRect { rect, paint } => {
+                    renderer.fill_rounded_rect(*rect, paint.clone())
+                }
+                DrawCommand::StrokeRect { rect, stroke } => renderer.stroke_rect(*rect, stroke),
+                DrawCommand::StrokeRoundedRect { rect, stroke } => {
+                    renderer.stroke_rounded_rect(*rect, stroke)
+                }
+                DrawCommand::BoxShadow { rect, shadow } => renderer.draw_box_shadow(*rect, shadow),
+                DrawCommand::BoxShadowRounded { rect, shadow } => {
+                    renderer.draw_box_shadow_rounded(*rect, shadow)
+                }
+                DrawCommand::Line { from, to, stroke } => renderer.draw_line(*from, *to, stroke),
+                DrawCommand::Polyline { points, stroke } => renderer.draw_polyline(points, stroke),
+                DrawCommand::FillEllipse {
+                    center,
+                    radius_x,
+                    radius_y,
+                    paint,
+                } => renderer.fill_ellipse(*center, *radius_x, *radius_y, paint.clone()),
+                DrawCommand::StrokeEllipse {
+                    center,
+                    radius_x,
+                    radius_y,
+                    stroke,
+                } => renderer.stroke_ellipse(*center, *radius_x, *radius_y, stroke),
+                DrawCommand::FillPath {
+                    path,
+                    paint,
+                    fill_rule,
+                } => renderer.fill_path(path, paint.clone(), *fill_rule),
+                DrawCommand::StrokePath { path, stroke } => renderer.stroke_path(path, stroke),
+                DrawCommand::Image {
+                    image,
+                    dest,
+                    scale_mode,
+                } => renderer.draw_image(image, *dest, *scale_mode),
+                DrawCommand::ImageRect { image, src, dest } => {
+                    renderer.draw_image_rect(image, *src, *dest)
+                }
+                DrawCommand::NinePatch { nine_patch, dest } => {
+                    renderer.draw_nine_patch(nine_patch, *dest)
+                }
+                DrawCommand::SetBlendMode(mode) => renderer.set_blend_mode(*mode),
+                DrawCommand::SetOpacity(opacity) => renderer.set_opacity(opacity * base_opacity),
+                DrawCommand::Text {
+                    layout,
+                    position,
+                    color,
+                } => draw_text(renderer, layout, *position, *color),
+            }
+        }
+
+        for _ in 0..stencil_clips {
+            renderer.restore_clip();
+        }
+        for _ in 0..depth {
+            renderer.restore();
+        }
+        renderer.restore();
+    }
+
+    /// Add a drawn area, in current user space, to the bounds.
+    fn add_bounds(&mut self, rect: Rect) {
+        let mut rect = self.state.transform().transform_rect(&rect);
+        if let Some(clip) = self.state.clip_bounds() {
+            match clip.intersect(&rect) {
+                Some(clipped) => rect = clipped,
+                None => return,
+            }
+        }
+        if rect.is_empty() {
+            return;
+        }
+        self.bounds = Some(match self.bounds {
+            Some(bounds) => bounds.union(&rect),
+            None => rect,
+        });
+    }
+
+    fn add_stroke_bounds(&mut self, rect: Rect, stroke: &Stroke) {
+        self.add_bounds(rect.inflate(stroke.width / 2.0));
+    }
+
+    fn push(&mut self, command: DrawCommand) {
+        self.commands.push(command);
+    }
+}
+
+impl Renderer for Picture {
+    fn begin_frame(&mut self, _clear_color: Color, viewport_size: Size) {
+        self.clear();
+        self.size = viewport_size;
+    }
+
+    fn end_frame(&mut self) -> FrameStats {
+        FrameStats {
+            draw_calls: self.commands.iter().filter(|c| c.is_drawing()).count() as u32,
+            ..FrameStats::default()
+        }
+    }
+
+    fn save(&mut self) {
+        self.state.save();
+        self.push(DrawCommand::Save);
+    }
+
+    fn restore(&mut self) {
+        if self.state.depth() == 0 {
+            return;
+        }
+        self.state.restore();
+        self.push(DrawCommand::Restore);
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+        self.push(DrawCommand::Reset);
+    }
+
+    fn transform(&self) -> &Transform2D {
+        self.state.transform()
+    }
+
+    fn set_transform(&mut self, transform: Transform2D) {
+        self.state.set_transform(transform);
+        self.push(DrawCommand::SetTransform(transform));
+    }
+
+    fn concat_transform(&mut self, transform: &Transform2D) {
+        self.state.concat_transform(transform);
+        self.push(DrawCommand::ConcatTransform(*transform));
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.state.translate(tx, ty);
+        self.push(DrawCommand::Translate { tx, ty });
+    }
+
+    fn scale(&mut self, sx: f32, sy: f32) {
+        self.state.scale(sx, sy);
+        self.push(DrawCommand::Scale { sx, sy });
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.state.rotate(angle);
+        self.push(DrawCommand::Rotate(angle));
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        self.state.clip_rect(rect);
+        self.push(DrawCommand::ClipRect(rect));
+    }
+
+    fn clip_rounded_rect(&mut self, rect: RoundedRect) {
+        if rect.radii.is_zero() {
+            self.clip_rect(rect.rect);
+            return;
+        }
+        self.stencil_clips += 1;
+        self.push(DrawCommand::ClipRoundedRect(rect));
+    }
+
+    fn restore_clip(&mut self) {
+        if self.stencil_clips == 0 {
+            return;
+        }
+        self.stencil_clips -= 1;
+        self.push(DrawCommand::RestoreClip);
+    }
+
+    fn clip_bounds(&self) -> Option<Rect> {
+        self.state.clip_bounds()
+    }
+
+    fn has_stencil_clips(&self) -> bool {
+        self.stencil_clips > 0
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        if path.is_empty() {
+            return;
+        }
+        self.stencil_clips += 1;
+        self.push(DrawCommand::ClipPath(path.clone()));
+    }
+
+    fn fill_rect(&mut self, rect: Rect, paint: impl Into<Paint>) {
+        self.add_bounds(rect);
+        self.push(DrawCommand::FillRect {
+            rect,
+            paint: paint.into(),
+        });
+    }
+
+    fn fill_rounded_rect(&mut self, rect: RoundedRect, paint: impl Into<Paint>) {
+        self.add_bounds(rect.rect);
+        self.push(DrawCommand::FillRoundedRect {
+            rect,
+            paint: paint.into(),
+        });
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, stroke: &Stroke) {
+        self.add_stroke_bounds(rect, stroke);
+        self.push(DrawCommand::StrokeRect {
+            rect,
+            stroke: stroke.clone(),
+        });
+    }
+
+    fn stroke_rounded_rect(&mut self, rect: RoundedRect, stroke: &Stroke) {
+        self.add_stroke_bounds(rect.rect, stroke);
+        self.push(DrawCommand::StrokeRoundedRect {
+            rect,
+            stroke: stroke.clone(),
+        });
+    }
+
+    fn draw_box_shadow(&mut self, rect: Rect, shadow: &BoxShadow) {
+        self.add_bounds(shadow.expanded_bounds(rect));
+        self.push(DrawCommand::BoxShadow {
+            rect,
+            shadow: *shadow,
+        });
+    }
+
+    fn draw_box_shadow_rounded(&mut self, rect: RoundedRect, shadow: &BoxShadow) {
+        self.add_bounds(shadow.expanded_bounds(rect.rect));
+        self.push(DrawCommand::BoxShadowRounded {
+            rect,
+            shadow: *shadow,
+        });
+    }
+
+    fn draw_line(&mut self, from: Point, to: Point, stroke: &Stroke) {
+        self.add_stroke_bounds(points_bounds(&[from, to]), stroke);
+        self.push(DrawCommand::Line {
+            from,
+            to,
+            stroke: stroke.clone(),
+        });
+    }
+
+    fn draw_polyline(&mut self, points: &[Point], stroke: &Stroke) {
+        if points.is_empty() {
+            return;
+        }
+        self.add_stroke_bounds(points_bounds(points), stroke);
+        self.push(DrawCommand::Polyline {
+            points: points.to_vec(),
+            stroke: stroke.clone(),
+        });
+    }
+
+    fn fill_ellipse(
+        &mut self,
+        center: Point,
+        radius_x: f32,
+        radius_y: f32,
+        paint: impl Into<Paint>,
+    ) {
+        self.add_bounds(ellipse_bounds(center, radius_x, radius_y));
+        self.push(DrawCommand::FillEllipse {
+            center,
+            radius_x,
+            radius_y,
+            paint: paint.into(),
+        });
+    }
+
+    fn stroke_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, stroke: &Stroke) {
+        self.add_stroke_bounds(ellipse_bounds(center, radius_x, radius_y), stroke);
+        self.push(DrawCommand::StrokeEllipse {
+            center,
+            radius_x,
+            radius_y,
+            stroke: stroke.clone(),
+        });
+    }
+
+    fn fill_path(&mut self, path: &Path, paint: impl Into<Paint>, fill_rule: FillRule) {
+        let path = path.clone();
+        if let Some(bounds) = path.clone().bounds() {
+            self.add_bounds(bounds);
+        }
+        self.push(DrawCommand::FillPath {
+            path,
+            paint: paint.into(),
+            fill_rule,
+        });
+    }
+
+    fn stroke_path(&mut self, path: &Path, stroke: &Stroke) {
+        let path = path.clone();
+        if let Some(bounds) = path.clone().bounds() {
+            self.add_stroke_bounds(bounds, stroke);
+        }
+        self.push(DrawCommand::StrokePath {
+            path,
+            stroke: stroke.clone(),
+        });
+    }
+
+    fn draw_image(&mut self, image: &Image, dest: Rect, scale_mode: ImageScaleMode) {
+        self.add_bounds(dest);
+        self.push(DrawCommand::Image {
+            image: image.clone(),
+            dest,
+            scale_mode,
+        });
+    }
+
+    fn draw_image_rect(&mut self, image: &Image, src: Rect, dest: Rect) {
+        self.add_bounds(dest);
+        self.push(DrawCommand::ImageRect {
+            image: image.clone(),
+            src,
+            dest,
+        });
+    }
+
+    fn draw_nine_patch(&mut self, nine_patch: &NinePatch, dest: Rect) {
+        self.add_bounds(dest);
+        self.push(DrawCommand::NinePatch {
+            nine_patch: nine_patch.clone(),
+            dest,
+        });
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.state.current_mut().blend_mode = mode;
+        self.push(DrawCommand::SetBlendMode(mode));
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.state.current().blend_mode
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        self.state.current_mut().opacity = opacity;
+        self.push(DrawCommand::SetOpacity(opacity));
+    }
+
+    fn opacity(&self) -> f32 {
+        self.state.current().opacity
+    }
+}
+
+fn points_bounds(points: &[Point]) -> Rect {
+    let mut min = points[0];
+    let mut max = points[0];
+    for point in &points[1..] {
+        min = Point::new(min.x.min(point.x), min.y.min(point.y));
+        max = Point::new(max.x.max(point.x), max.y.max(point.y));
+    }
+    Rect::new(min.x, min.y, max.x - min.x, max.y - min.y)
+}
+
+fn ellipse_bounds(center: Point, radius_x: f32, radius_y: f32) -> Rect {
+    Rect::new(
+        center.x - radius_x,
+        center.y - radius_y,
+        radius_x * 2.0,
+        radius_y * 2.0,
+    )
+}
+
+// ============================================================================
+// Text form
+// ============================================================================
+
+/// Writes the picture as text: a `picture WxH` header, then one command per
+/// line, indented by the `save` nesting depth.
+impl fmt::Display for Picture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "picture {}x{}", self.size.width, self.size.height)?;
+        let mut depth = 0usize;
+        for command in &self.commands {
+            match command {
+                DrawCommand::Restore => depth = depth.saturating_sub(1),
+                DrawCommand::Reset => depth = 0,
+                _ => {}
+            }
+            writeln!(f, "{:indent$}{command}", "", indent = depth * 2)?;
+            if let DrawCommand::Save = command {
+                depth += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the command on one line, as its snake_case renderer call followed
+/// by its arguments. Images are written as their pixel size and text as its
+/// string.
+impl fmt::Display for DrawCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Save => f.write_str("save"),
+            Self::Restore => f.write_str("restore"),
+            Self::Reset => f.write_str("reset"),
+            Self::SetTransform(t) => write!(f, "set_transform {}", TransformText(t)),
+            Self::ConcatTransform(t) => write!(f, "concat_transform {}", TransformText(t)),
+            Self::Translate { tx, ty } => write!(f, "translate {tx} {ty}"),
+            Self::Scale { sx, sy } => write!(f, "scale {sx} {sy}"),
+            Self::Rotate(angle) => write!(f, "rotate {angle}"),
+            Self::ClipRect(rect) => write!(f, "clip_rect {}", RectText(rect)),
+            Self::ClipRoundedRect(rect) => {
+                write!(f, "clip_rounded_rect {}", RoundedRectText(rect))
+            }
+            Self::ClipPath(path) => write!(f, "clip_path {}", PathText(path)),
+            Self::RestoreClip => f.write_str("restore_clip"),
+            Self::FillRect { rect, paint } => {
+                write!(f, "fill_rect {} {}", RectText(rect), PaintText(paint))
+            }
+            Self::FillRoundedRect { rect, paint } => write!(
+                f,
+                "fill_rounded_rect {} {}",
+                RoundedRectText(rect),
+                PaintText(paint)
+            ),
+            Self::StrokeRect { rect, stroke } => {
+                write!(f, "stroke_rect {} {}", RectText(rect), StrokeText(stroke))
+            }
+            Self::StrokeRoundedRect { rect, stroke } => write!(
+                f,
+                "stroke_rounded_rect {} {}",
+                RoundedRectText(rect),
+                StrokeText(stroke)
+            ),
+            Self::BoxShadow { rect, shadow } => {
+                write!(f, "box_shadow {} {}", RectText(rect), ShadowText(shadow))
+            }
+            Self::BoxShadowRounded { rect, shadow } => write!(
+                f,
+                "box_shadow_rounded {} {}",
+                RoundedRectText(rect),
+                ShadowText(shadow)
+            ),
+            Self::Line { from, to, stroke } => write!(
+                f,
+                "line {} {} {} {} {}",
+                from.x,
+                from.y,
+                to.x,
+                to.y,
+                StrokeText(stroke)
+            ),
+            Self::Polyline { points, stroke } => {
+                f.write_str("polyline")?;
+                for point in points {
+                    write!(f, " {} {}", point.x, point.y)?;
+                }
+                write!(f, " {}", StrokeText(stroke))
+            }
+            Self::FillEllipse {
+                center,
+                radius_x,
+                radius_y,
+                paint,
+            } => write!(
+                f,
+                "fill_ellipse {} {} {radius_x} {radius_y} {}",
+                center.x,
+                center.y,
+                PaintText(paint)
+            ),
+            Self::StrokeEllipse {
+                center,
+                radius_x,
+                radius_y,
+                stroke,
+            } => write!(
+                f,
+                "stroke_ellipse {} {} {radius_x} {radius_y} {}",
+                center.x,
+                center.y,
+                StrokeText(stroke)
+            ),
+            Self::FillPath {
+                path,
+                paint,
+                fill_rule,
+            } => write!(
+                f,
+                "fill_path {} {} {fill_rule:?}",
+                PathText(path),
+                PaintText(paint)
+            ),
+            Self::StrokePath { path, stroke } => {
+                write!(f, "stroke_path {} {}", PathText(path), StrokeText(stroke))
+            }
+            Self::Image {
+                image,
+                dest,
+                scale_mode,
+            } => write!(
+                f,
+                "image {}x{} {} {scale_mode:?}",
+                image.width(),
+                image.height(),
+                RectText(dest)
+            ),
+            Self::ImageRect { image, src, dest } => write!(
+                f,
+                "image_rect {}x{} {} {}",
+                image.width(),
+                image.height(),
+                RectText(src),
+                RectText(dest)
+            ),
+            Self::NinePatch { nine_patch, dest } => write!(
+                f,
+                "nine_patch {}x{} borders={} {} {} {} {}",
+                nine_patch.image.width(),
+                nine_patch.image.height(),
+                nine_patch.left,
+                nine_patch.top,
+                nine_patch.right,
+                nine_patch.bottom,
+                RectText(dest)
+            ),
+            Self::SetBlendMode(mode) => write!(f, "set_blend_mode {mode:?}"),
+            Self::SetOpacity(opacity) => write!(f, "set_opacity {opacity}"),
+            Self::Text {
+                layout,
+                position,
+                color,
+            } => write!(
+                f,
+                "text {:?} {} {} {}",
+                layout.text(),
+                position.x,
+                position.y,
+                color.to_hex()
+            ),
+        }
+    }
+}
+
+struct RectText<'a>(&'a Rect);
+
+impl fmt::Display for RectText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rect = self.0;
+        write!(
+            f,
+            "{} {} {} {}",
+            rect.left(),
+            rect.top(),
+            rect.width(),
+            rect.height()
+        )
+    }
+}
+
+struct RoundedRectText<'a>(&'a RoundedRect);
+
+impl fmt::Display for RoundedRectText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let CornerRadii {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        } = self.0.radii;
+        write!(
+            f,
+            "{} radii={top_left} {top_right} {bottom_right} {bottom_left}",
+            RectText(&self.0.rect)
+        )
+    }
+}
+
+struct TransformText<'a>(&'a Transform2D);
+
+impl fmt::Display for TransformText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0.as_array();
+        write!(f, "[{a} {b} {c} {d} {e} {g}]")
+    }
+}
+
+struct PaintText<'a>(&'a Paint);
+
+impl fmt::Display for PaintText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stops = match self.0 {
+            Paint::Solid(color) => return f.write_str(&color.to_hex()),
+            Paint::LinearGradient(gradient) => {
+                write!(
+                    f,
+                    "linear({} {} {} {}",
+                    gradient.start.x, gradient.start.y, gradient.end.x, gradient.end.y
+                )?;
+                &gradient.stops
+            }
+            Paint::RadialGradient(gradient) => {
+                write!(
+                    f,
+                    "radial({} {} {}",
+                    gradient.center.x, gradient.center.y, gradient.radius
+                )?;
+                if let Some(focus) = gradient.focus {
+                    write!(f, " focus={} {}", focus.x, focus.y)?;
+                }
+                &gradient.stops
+            }
+        };
+        for stop in stops {
+            write!(f, " {}:{}", stop.offset, stop.color.to_hex())?;
+        }
+        f.write_str(")")
+    }
+}
+
+struct StrokeText<'a>(&'a Stroke);
+
+impl fmt::Display for StrokeText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stroke = self.0;
+        write!(
+            f,
+            "{} width={} {:?} {:?}",
+            PaintText(&stroke.paint),
+            stroke.width,
+            stroke.cap,
+            stroke.join
+        )?;
+        if stroke.join == crate::paint::LineJoin::Miter {
+            write!(f, " miter={}", stroke.miter_limit)?;
+        }
+        if let Some(dash) = &stroke.dash_pattern {
+            f.write_str(" dash=")?;
+            for (i, length) in dash.pattern.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(",")?;
+                }
+                write!(f, "{length}")?;
+            }
+            write!(f, "@{}", dash.offset)?;
+        }
+        Ok(())
+    }
+}
+
+struct ShadowText<'a>(&'a BoxShadow);
+
+impl fmt::Display for ShadowText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shadow = self.0;
+        write!(
+            f,
+            "{} offset={} {} blur={} spread={}",
+            shadow.color.to_hex(),
+            shadow.offset_x,
+            shadow.offset_y,
+            shadow.blur_radius,
+            shadow.spread_radius
+        )?;
+        if shadow.inset {
+            f.write_str(" inset")?;
+        }
+        Ok(())
+    }
+}
+
+struct PathText<'a>(&'a Path);
+
+impl fmt::Display for PathText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, command) in self.0.commands().iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            match *command {
+                PathCommand::MoveTo(p) => write!(f, "M {} {}", p.x, p.y)?,
+                PathCommand::LineTo(p) => write!(f, "L {} {}", p.x, p.y)?,
+                PathCommand::QuadTo { control, end } => {
+                    write!(f, "Q {} {} {} {}", control.x, control.y, end.x, end.y)?
+                }
+                PathCommand::CubicTo {
+                    control1,
+                    control2,
+                    end,
+                } => write!(
+                    f,
+                    "C {} {} {} {} {} {}",
+                    control1.x, control1.y, control2.x, control2.y, end.x, end.y
+                )?,
+                PathCommand::ArcTo {
+                    radii,
+                    x_rotation,
+                    large_arc,
+                    sweep,
+                    end,
+                } => write!(
+                    f,
+                    "A {} {} {x_rotation} {} {} {} {}",
+                    radii.x,
+                    radii.y,
+                    u8::from(large_arc),
+                    u8::from(sweep),
+                    end.x,
+                    end.y
+                )?,
+                PathCommand::Close => f.write_str("Z")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SoftwareRenderer;
+
+    fn record(paint: impl FnOnce(&mut Picture)) -> Picture {
+        let mut picture = Picture::new();
+        picture.begin_frame(Color::TRANSPARENT, Size::new(100.0, 80.0));
+        paint(&mut picture);
+        picture.end_frame();
+        picture
+    }
+
+    #[test]
+    fn test_records_commands_and_state() {
+        let mut picture = record(|p| {
+            p.fill_rect(Rect::new(0.0, 0.0, 100.0, 80.0), Color::WHITE);
+            p.save();
+            p.translate(10.0, 20.0);
+            p.clip_rect(Rect::new(0.0, 0.0, 50.0, 50.0));
+            p.set_opacity(0.5);
+            p.stroke_rect(
+                Rect::new(5.0, 5.0, 20.0, 10.0),
+                &Stroke::new(Color::BLACK, 2.0),
+            );
+            let mut path = Path::new();
+            path.move_to(Point::new(0.0, 0.0))
+                .line_to(Point::new(10.0, 0.0))
+                .close();
+            p.fill_path(&path, Color::RED, FillRule::EvenOdd);
+
+            assert_eq!(p.transform().translation(), (10.0, 20.0));
+            assert_eq!(p.clip_bounds(), Some(Rect::new(10.0, 20.0, 50.0, 50.0)));
+            assert_eq!(p.opacity(), 0.5);
+            p.restore();
+        });
+
+        assert_eq!(picture.len(), 8);
+        assert_eq!(picture.end_frame().draw_calls, 3);
+        assert_eq!(
+            picture.to_string(),
+            "picture 100x80\n\
+             fill_rect 0 0 100 80 #FFFFFF\n\
+             save\n\
+             \x20 translate 10 20\n\
+             \x20 clip_rect 0 0 50 50\n\
+             \x20 set_opacity 0.5\n\
+             \x20 stroke_rect 5 5 20 10 #000000 width=2 Butt Miter miter=4\n\
+             \x20 fill_path M 0 0 L 10 0 Z #FF0000 EvenOdd\n\
+             restore\n"
+        );
+    }
+
+    #[test]
+    fn test_unbalanced_calls_are_not_recorded() {
+        let picture = record(|p| {
+            p.restore();
+            p.restore_clip();
+            p.clip_path(&Path::new());
+            p.clip_rounded_rect(RoundedRect::new(Rect::new(0.0, 0.0, 10.0, 10.0), 0.0));
+            assert!(!p.has_stencil_clips());
+            p.clip_rounded_rect(RoundedRect::new(Rect::new(0.0, 0.0, 10.0, 10.0), 2.0));
+            assert!(p.has_stencil_clips());
+        });
+
+        assert_eq!(
+            picture.to_string(),
+            "picture 100x80\n\
+             clip_rect 0 0 10 10\n\
+             clip_rounded_rect 0 0 10 10 radii=2 2 2 2\n"
+        );
+    }
+
+    #[test]
+    fn test_replay_matches_direct_rendering() {
+        fn scene(r: &mut impl Renderer) {
+            r.fill_rect(Rect::new(0.0, 0.0, 30.0, 20.0), Color::BLUE);
+            r.save();
+            r.rotate(0.3);
+            r.clip_rounded_rect(RoundedRect::new(Rect::new(5.0, 5.0, 30.0, 30.0), 6.0));
+            r.set_opacity(0.5);
+            r.fill_ellipse(Point::new(20.0, 20.0), 15.0, 10.0, Color::RED);
+            r.restore_clip();
+            r.restore();
+            r.translate(2.0, 40.0);
+            r.draw_line(
+                Point::new(0.0, 0.0),
+                Point::new(40.0, 10.0),
+                &Stroke::new(Color::GREEN, 3.0),
+            );
+        }
+
+        let transform = Transform2D::translate(12.0, 7.0);
+        let mut direct = SoftwareRenderer::new();
+        direct.begin_frame(Color::WHITE, Size::new(64.0, 64.0));
+        direct.set_transform(transform);
+        scene(&mut direct);
+
+        let picture = record(scene);
+        let mut replayed = SoftwareRenderer::new();
+        replayed.begin_frame(Color::WHITE, Size::new(64.0, 64.0));
+        picture.replay(&mut replayed, transform);
+
+        assert_eq!(direct.pixels(), replayed.pixels());
+        assert!(replayed.transform().is_identity());
+    }
+
+    #[test]
+    fn test_replay_into_picture() {
+        let picture = record(|p| {
+            p.set_opacity(0.5);
+            p.set_transform(Transform2D::translate(1.0, 2.0));
+            p.save();
+            p.clip_rounded_rect(RoundedRect::new(Rect::new(0.0, 0.0, 10.0, 10.0), 3.0));
+            p.fill_rect(Rect::new(0.0, 0.0, 4.0, 4.0), Color::RED);
+        });
+
+        let mut target = Picture::new();
+        target.set_opacity(0.5);
+        picture.replay(&mut target, Transform2D::scale(2.0));
+
+        assert_eq!(target.opacity(), 0.5);
+        assert!(target.transform().is_identity());
+        assert!(!target.has_stencil_clips());
+        assert_eq!(
+            target.to_string(),
+            "picture 0x0\n\
+             set_opacity 0.5\n\
+             save\n\
+             \x20 concat_transform [2 0 0 2 0 0]\n\
+             \x20 set_opacity 0.25\n\
+             \x20 set_transform [2 0 0 2 2 4]\n\
+             \x20 save\n\
+             \x20   clip_rounded_rect 0 0 10 10 radii=3 3 3 3\n\
+             \x20   fill_rect 0 0 4 4 #FF0000\n\
+             \x20   restore_clip\n\
+             \x20 restore\n\
+             restore\n"
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        let picture = record(|p| {
+            assert_eq!(p.bounds(), None);
+            p.translate(10.0, 10.0);
+            p.fill_rect(Rect::new(0.0, 0.0, 20.0, 20.0), Color::RED);
+            p.draw_line(
+                Point::new(0.0, 50.0),
+                Point::new(40.0, 50.0),
+                &Stroke::new(Color::BLACK, 4.0),
+            );
+            p.clip_rect(Rect::new(0.0, 0.0, 10.0, 10.0));
+            p.fill_rect(Rect::new(-50.0, -50.0, 500.0, 500.0), Color::RED);
+            p.fill_rect(Rect::new(100.0, 100.0, 5.0, 5.0), Color::RED);
+        });
+
+        assert_eq!(picture.bounds(), Some(Rect::new(8.0, 10.0, 44.0, 52.0)));
+
+        let mut empty = picture.clone();
+        empty.clear();
+        assert!(empty.is_empty());
+        assert_eq!(empty.bounds(), None);
+        assert_eq!(empty.size(), Size::new(100.0, 80.0));
+    }
+}
@@ -4,6 +4,7 @@
 //! for 2D drawing operations. Implementations can use immediate or retained-mode
 //! rendering backends.
 
+use crate::gpu_renderer::GpuRenderer;
 use crate::image::{Image, ImageScaleMode, NinePatch};
 use crate::paint::{BlendMode, BoxShadow, FillRule, Paint, Stroke};
 use crate::transform::{Transform2D, TransformStack};
@@ -348,6 +349,425 @@ pub trait Renderer {
 
     /// Get the current global opacity.
     fn opacity(&self) -> f32;
+
+    // =========================================================================
+    // Backend Access
+    // =========================================================================
+
+    /// Get this renderer as a [`GpuRenderer`], for drawing that this trait
+    /// doesn't cover.
+    ///
+    /// Returns `None` for every other renderer.
+    fn as_gpu_renderer(&mut self) -> Option<&mut GpuRenderer> {
+        None
+    }
+}
+
+/// An object-safe form of [`Renderer`], for drawing through a renderer chosen
+/// at runtime.
+///
+/// `Renderer` has generic methods, so it can't be used as a trait object.
+/// Every renderer implements `DynRenderer`, and `dyn DynRenderer` implements
+/// `Renderer` in turn, so a `&mut dyn DynRenderer` is drawn to with the usual
+/// `Renderer` methods:
+///
+/// ```
+/// use horizon_lattice_render::{Color, DynRenderer, Rect, Renderer, Size, SoftwareRenderer};
+///
+/// fn draw(renderer: &mut dyn DynRenderer) {
+///     renderer.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::RED);
+/// }
+///
+/// let mut renderer = SoftwareRenderer::new();
+/// renderer.begin_frame(Color::WHITE, Size::new(20.0, 20.0));
+/// draw(&mut renderer);
+/// renderer.end_frame();
+/// assert_eq!(renderer.pixel(5, 5), Some(Color::RED));
+/// ```
+///
+/// The methods of this trait mirror those of `Renderer` with a `dyn_` prefix,
+/// so they never shadow the `Renderer` methods on a trait object.
+pub trait DynRenderer {
+    /// See [`Renderer::begin_frame`].
+    fn dyn_begin_frame(&mut self, clear_color: Color, viewport_size: Size);
+    /// See [`Renderer::end_frame`].
+    fn dyn_end_frame(&mut self) -> FrameStats;
+    /// See [`Renderer::save`].
+    fn dyn_save(&mut self);
+    /// See [`Renderer::restore`].
+    fn dyn_restore(&mut self);
+    /// See [`Renderer::reset`].
+    fn dyn_reset(&mut self);
+    /// See [`Renderer::transform`].
+    fn dyn_transform(&self) -> &Transform2D;
+    /// See [`Renderer::set_transform`].
+    fn dyn_set_transform(&mut self, transform: Transform2D);
+    /// See [`Renderer::concat_transform`].
+    fn dyn_concat_transform(&mut self, transform: &Transform2D);
+    /// See [`Renderer::translate`].
+    fn dyn_translate(&mut self, tx: f32, ty: f32);
+    /// See [`Renderer::scale`].
+    fn dyn_scale(&mut self, sx: f32, sy: f32);
+    /// See [`Renderer::rotate`].
+    fn dyn_rotate(&mut self, angle: f32);
+    /// See [`Renderer::clip_rect`].
+    fn dyn_clip_rect(&mut self, rect: Rect);
+    /// See [`Renderer::clip_rounded_rect`].
+    fn dyn_clip_rounded_rect(&mut self, rect: RoundedRect);
+    /// See [`Renderer::restore_clip`].
+    fn dyn_restore_clip(&mut self);
+    /// See [`Renderer::clip_bounds`].
+    fn dyn_clip_bounds(&self) -> Option<Rect>;
+    /// See [`Renderer::has_stencil_clips`].
+    fn dyn_has_stencil_clips(&self) -> bool;
+    /// See [`Renderer::clip_path`].
+    fn dyn_clip_path(&mut self, path: &Path);
+    /// See [`Renderer::fill_rect`].
+    fn dyn_fill_rect(&mut self, rect: Rect, paint: Paint);
+    /// See [`Renderer::fill_rounded_rect`].
+    fn dyn_fill_rounded_rect(&mut self, rect: RoundedRect, paint: Paint);
+    /// See [`Renderer::stroke_rect`].
+    fn dyn_stroke_rect(&mut self, rect: Rect, stroke: &Stroke);
+    /// See [`Renderer::stroke_rounded_rect`].
+    fn dyn_stroke_rounded_rect(&mut self, rect: RoundedRect, stroke: &Stroke);
+    /// See [`Renderer::draw_box_shadow`].
+    fn dyn_draw_box_shadow(&mut self, rect: Rect, shadow: &BoxShadow);
+    /// See [`Renderer::draw_box_shadow_rounded`].
+    fn dyn_draw_box_shadow_rounded(&mut self, rect: RoundedRect, shadow: &BoxShadow);
+    /// See [`Renderer::draw_line`].
+    fn dyn_draw_line(&mut self, from: Point, to: Point, stroke: &Stroke);
+    /// See [`Renderer::draw_polyline`].
+    fn dyn_draw_polyline(&mut self, points: &[Point], stroke: &Stroke);
+    /// See [`Renderer::fill_ellipse`].
+    fn dyn_fill_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, paint: Paint);
+    /// See [`Renderer::stroke_ellipse`].
+    fn dyn_stroke_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, stroke: &Stroke);
+    /// See [`Renderer::fill_path`].
+    fn dyn_fill_path(&mut self, path: &Path, paint: Paint, fill_rule: FillRule);
+    /// See [`Renderer::stroke_path`].
+    fn dyn_stroke_path(&mut self, path: &Path, stroke: &Stroke);
+    /// See [`Renderer::draw_image`].
+    fn dyn_draw_image(&mut self, image: &Image, dest: Rect, scale_mode: ImageScaleMode);
+    /// See [`Renderer::draw_image_rect`].
+    fn dyn_draw_image_rect(&mut self, image: &Image, src: Rect, dest: Rect);
+    /// See [`Renderer::draw_nine_patch`].
+    fn dyn_draw_nine_patch(&mut self, nine_patch: &NinePatch, dest: Rect);
+    /// See [`Renderer::set_blend_mode`].
+    fn dyn_set_blend_mode(&mut self, mode: BlendMode);
+    /// See [`Renderer::blend_mode`].
+    fn dyn_blend_mode(&self) -> BlendMode;
+    /// See [`Renderer::set_opacity`].
+    fn dyn_set_opacity(&mut self, opacity: f32);
+    /// See [`Renderer::opacity`].
+    fn dyn_opacity(&self) -> f32;
+    /// See [`Renderer::as_gpu_renderer`].
+    fn dyn_as_gpu_renderer(&mut self) -> Option<&mut GpuRenderer>;
+}
+
+impl<R: Renderer> DynRenderer for R {
+    fn dyn_begin_frame(&mut self, clear_color: Color, viewport_size: Size) {
+        self.begin_frame(clear_color, viewport_size);
+    }
+
+    fn dyn_end_frame(&mut self) -> FrameStats {
+        self.end_frame()
+    }
+
+    fn dyn_save(&mut self) {
+        self.save();
+    }
+
+    fn dyn_restore(&mut self) {
+        self.restore();
+    }
+
+    fn dyn_reset(&mut self) {
+        self.reset();
+    }
+
+    fn dyn_transform(&self) -> &Transform2D {
+        self.transform()
+    }
+
+    fn dyn_set_transform(&mut self, transform: Transform2D) {
+        self.set_transform(transform);
+    }
+
+    fn dyn_concat_transform(&mut self, transform: &Transform2D) {
+        self.concat_transform(transform);
+    }
+
+    fn dyn_translate(&mut self, tx: f32, ty: f32) {
+        self.translate(tx, ty);
+    }
+
+    fn dyn_scale(&mut self, sx: f32, sy: f32) {
+        self.scale(sx, sy);
+    }
+
+    fn dyn_rotate(&mut self, angle: f32) {
+        self.rotate(angle);
+    }
+
+    fn dyn_clip_rect(&mut self, rect: Rect) {
+        self.clip_rect(rect);
+    }
+
+    fn dyn_clip_rounded_rect(&mut self, rect: RoundedRect) {
+        self.clip_rounded_rect(rect);
+    }
+
+    fn dyn_restore_clip(&mut self) {
+        self.restore_clip();
+    }
+
+    fn dyn_clip_bounds(&self) -> Option<Rect> {
+        self.clip_bounds()
+    }
+
+    fn dyn_has_stencil_clips(&self) -> bool {
+        self.has_stencil_clips()
+    }
+
+    fn dyn_clip_path(&mut self, path: &Path) {
+        self.clip_path(path);
+    }
+
+    fn dyn_fill_rect(&mut self, rect: Rect, paint: Paint) {
+        self.fill_rect(rect, paint);
+    }
+
+    fn dyn_fill_rounded_rect(&mut self, rect: RoundedRect, paint: Paint) {
+        self.fill_rounded_rect(rect, paint);
+    }
+
+    fn dyn_stroke_rect(&mut self, rect: Rect, stroke: &Stroke) {
+        self.stroke_rect(rect, stroke);
+    }
+
+    fn dyn_stroke_rounded_rect(&mut self, rect: RoundedRect, stroke: &Stroke) {
+        self.stroke_rounded_rect(rect, stroke);
+    }
+
+    fn dyn_draw_box_shadow(&mut self, rect: Rect, shadow: &BoxShadow) {
+        self.draw_box_shadow(rect, shadow);
+    }
+
+    fn dyn_draw_box_shadow_rounded(&mut self, rect: RoundedRect, shadow: &BoxShadow) {
+        self.draw_box_shadow_rounded(rect, shadow);
+    }
+
+    fn dyn_draw_line(&mut self, from: Point, to: Point, stroke: &Stroke) {
+        self.draw_line(from, to, stroke);
+    }
+
+    fn dyn_draw_polyline(&mut self, points: &[Point], stroke: &Stroke) {
+        self.draw_polyline(points, stroke);
+    }
+
+    fn dyn_fill_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, paint: Paint) {
+        self.fill_ellipse(center, radius_x, radius_y, paint);
+    }
+
+    fn dyn_stroke_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, stroke: &Stroke) {
+        self.stroke_ellipse(center, radius_x, radius_y, stroke);
+    }
+
+    fn dyn_fill_path(&mut self, path: &Path, paint: Paint, fill_rule: FillRule) {
+        self.fill_path(path, paint, fill_rule);
+    }
+
+    fn dyn_stroke_path(&mut self, path: &Path, stroke: &Stroke) {
+        self.stroke_path(path, stroke);
+    }
+
+    fn dyn_draw_image(&mut self, image: &Image, dest: Rect, scale_mode: ImageScaleMode) {
+        self.draw_image(image, dest, scale_mode);
+    }
+
+    fn dyn_draw_image_rect(&mut self, image: &Image, src: Rect, dest: Rect) {
+        self.draw_image_rect(image, src, dest);
+    }
+
+    fn dyn_draw_nine_patch(&mut self, nine_patch: &NinePatch, dest: Rect) {
+        self.draw_nine_patch(nine_patch, dest);
+    }
+
+    fn dyn_set_blend_mode(&mut self, mode: BlendMode) {
+        self.set_blend_mode(mode);
+    }
+
+    fn dyn_blend_mode(&self) -> BlendMode {
+        self.blend_mode()
+    }
+
+    fn dyn_set_opacity(&mut self, opacity: f32) {
+        self.set_opacity(opacity);
+    }
+
+    fn dyn_opacity(&self) -> f32 {
+        self.opacity()
+    }
+
+    fn dyn_as_gpu_renderer(&mut self) -> Option<&mut GpuRenderer> {
+        self.as_gpu_renderer()
+    }
+}
+
+impl Renderer for dyn DynRenderer + '_ {
+    fn begin_frame(&mut self, clear_color: Color, viewport_size: Size) {
+        self.dyn_begin_frame(clear_color, viewport_size);
+    }
+
+    fn end_frame(&mut self) -> FrameStats {
+        self.dyn_end_frame()
+    }
+
+    fn save(&mut self) {
+        self.dyn_save();
+    }
+
+    fn restore(&mut self) {
+        self.dyn_restore();
+    }
+
+    fn reset(&mut self) {
+        self.dyn_reset();
+    }
+
+    fn transform(&self) -> &Transform2D {
+        self.dyn_transform()
+    }
+
+    fn set_transform(&mut self, transform: Transform2D) {
+        self.dyn_set_transform(transform);
+    }
+
+    fn concat_transform(&mut self, transform: &Transform2D) {
+        self.dyn_concat_transform(transform);
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.dyn_translate(tx, ty);
+    }
+
+    fn scale(&mut self, sx: f32, sy: f32) {
+        self.dyn_scale(sx, sy);
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.dyn_rotate(angle);
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        self.dyn_clip_rect(rect);
+    }
+
+    fn clip_rounded_rect(&mut self, rect: RoundedRect) {
+        self.dyn_clip_rounded_rect(rect);
+    }
+
+    fn restore_clip(&mut self) {
+        self.dyn_restore_clip();
+    }
+
+    fn clip_bounds(&self) -> Option<Rect> {
+        self.dyn_clip_bounds()
+    }
+
+    fn has_stencil_clips(&self) -> bool {
+        self.dyn_has_stencil_clips()
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        self.dyn_clip_path(path);
+    }
+
+    fn fill_rect(&mut self, rect: Rect, paint: impl Into<Paint>) {
+        self.dyn_fill_rect(rect, paint.into());
+    }
+
+    fn fill_rounded_rect(&mut self, rect: RoundedRect, paint: impl Into<Paint>) {
+        self.dyn_fill_rounded_rect(rect, paint.into());
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, stroke: &Stroke) {
+        self.dyn_stroke_rect(rect, stroke);
+    }
+
+    fn stroke_rounded_rect(&mut self, rect: RoundedRect, stroke: &Stroke) {
+        self.dyn_stroke_rounded_rect(rect, stroke);
+    }
+
+    fn draw_box_shadow(&mut self, rect: Rect, shadow: &BoxShadow) {
+        self.dyn_draw_box_shadow(rect, shadow);
+    }
+
+    fn draw_box_shadow_rounded(&mut self, rect: RoundedRect, shadow: &BoxShadow) {
+        self.dyn_draw_box_shadow_rounded(rect, shadow);
+    }
+
+    fn draw_line(&mut self, from: Point, to: Point, stroke: &Stroke) {
+        self.dyn_draw_line(from, to, stroke);
+    }
+
+    fn draw_polyline(&mut self, points: &[Point], stroke: &Stroke) {
+        self.dyn_draw_polyline(points, stroke);
+    }
+
+    fn fill_ellipse(
+        &mut self,
+        center: Point,
+        radius_x: f32,
+        radius_y: f32,
+        paint: impl Into<Paint>,
+    ) {
+        self.dyn_fill_ellipse(center, radius_x, radius_y, paint.into());
+    }
+
+    fn stroke_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, stroke: &Stroke) {
+        self.dyn_stroke_ellipse(center, radius_x, radius_y, stroke);
+    }
+
+    fn fill_path(&mut self, path: &Path, paint: impl Into<Paint>, fill_rule: FillRule) {
+        self.dyn_fill_path(path, paint.into(), fill_rule);
+    }
+
+    fn stroke_path(&mut self, path: &Path, stroke: &Stroke) {
+        self.dyn_stroke_path(path, stroke);
+    }
+
+    fn draw_image(&mut self, image: &Image, dest: Rect, scale_mode: ImageScaleMode) {
+        self.dyn_draw_image(image, dest, scale_mode);
+    }
+
+    fn draw_image_rect(&mut self, image: &Image, src: Rect, dest: Rect) {
+        self.dyn_draw_image_rect(image, src, dest);
+    }
+
+    fn draw_nine_patch(&mut self, nine_patch: &NinePatch, dest: Rect) {
+        self.dyn_draw_nine_patch(nine_patch, dest);
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        self.dyn_set_blend_mode(mode);
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.dyn_blend_mode()
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.dyn_set_opacity(opacity);
+    }
+
+    fn opacity(&self) -> f32 {
+        self.dyn_opacity()
+    }
+
+    fn as_gpu_renderer(&mut self) -> Option<&mut GpuRenderer> {
+        self.dyn_as_gpu_renderer()
+    }
 }
 
 /// Saved renderer state for save/restore operations.
@@ -544,4 +964,11 @@ mod tests {
         stack.clip_rect(Rect::new(50.0, 50.0, 100.0, 100.0));
         assert_eq!(stack.clip_bounds(), Some(Rect::new(50.0, 50.0, 50.0, 50.0)));
     }
+
+    #[test]
+    fn test_as_gpu_renderer() {
+        let mut software = crate::SoftwareRenderer::new();
+        let renderer: &mut dyn DynRenderer = &mut software;
+        assert!(renderer.as_gpu_renderer().is_none());
+    }
 }
@@ -0,0 +1,207 @@
+// Backdrop blend shader.
+// Blends a source texture into a target using a blend mode that reads the
+// destination color, following the W3C Compositing and Blending spec.
+// The backdrop is a copy of the target taken just before the draw.
+
+struct Uniforms {
+    // Target size in pixels
+    viewport_size: vec2<f32>,
+    // Position of the blended rectangle in pixels
+    rect_offset: vec2<f32>,
+    // Size of the blended rectangle in pixels
+    rect_size: vec2<f32>,
+    // Opacity applied to the source
+    opacity: f32,
+    // Blend mode (see the MODE_ constants)
+    mode: u32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@group(1) @binding(0)
+var t_source: texture_2d<f32>;
+
+@group(1) @binding(1)
+var s_source: sampler;
+
+@group(1) @binding(2)
+var t_backdrop: texture_2d<f32>;
+
+// Must match BlendMode::backdrop_shader_mode.
+const MODE_NORMAL: u32 = 0u;
+const MODE_OVERLAY: u32 = 1u;
+const MODE_COLOR_DODGE: u32 = 2u;
+const MODE_COLOR_BURN: u32 = 3u;
+const MODE_HARD_LIGHT: u32 = 4u;
+const MODE_SOFT_LIGHT: u32 = 5u;
+const MODE_DIFFERENCE: u32 = 6u;
+const MODE_EXCLUSION: u32 = 7u;
+const MODE_HUE: u32 = 8u;
+const MODE_SATURATION: u32 = 9u;
+const MODE_COLOR: u32 = 10u;
+const MODE_LUMINOSITY: u32 = 11u;
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Two triangles covering the rectangle
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    let screen = uniforms.rect_offset + corner * uniforms.rect_size;
+
+    var output: VertexOutput;
+    output.clip_position = vec4<f32>(
+        (screen.x / uniforms.viewport_size.x) * 2.0 - 1.0,
+        1.0 - (screen.y / uniforms.viewport_size.y) * 2.0,
+        0.0,
+        1.0,
+    );
+    output.uv = corner;
+    return output;
+}
+
+fn hard_light(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    let multiply = cb * 2.0 * cs;
+    let s = 2.0 * cs - 1.0;
+    let screen = cb + s - cb * s;
+    return select(screen, multiply, cs <= vec3<f32>(0.5));
+}
+
+fn color_dodge(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    let dodged = min(vec3<f32>(1.0), cb / max(vec3<f32>(1.0) - cs, vec3<f32>(1e-6)));
+    let saturated = select(dodged, vec3<f32>(1.0), cs >= vec3<f32>(1.0));
+    return select(saturated, vec3<f32>(0.0), cb <= vec3<f32>(0.0));
+}
+
+fn color_burn(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    let burned = vec3<f32>(1.0) - min(vec3<f32>(1.0), (vec3<f32>(1.0) - cb) / max(cs, vec3<f32>(1e-6)));
+    let saturated = select(burned, vec3<f32>(0.0), cs <= vec3<f32>(0.0));
+    return select(saturated, vec3<f32>(1.0), cb >= vec3<f32>(1.0));
+}
+
+fn soft_light(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+    let d = select(sqrt(cb), ((16.0 * cb - 12.0) * cb + 4.0) * cb, cb <= vec3<f32>(0.25));
+    let darkened = cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb);
+    let lightened = cb + (2.0 * cs - 1.0) * (d - cb);
+    return select(lightened, darkened, cs <= vec3<f32>(0.5));
+}
+
+fn lum(c: vec3<f32>) -> f32 {
+    return dot(c, vec3<f32>(0.3, 0.59, 0.11));
+}
+
+fn clip_color(c: vec3<f32>) -> vec3<f32> {
+    let l = lum(c);
+    let n = min(min(c.r, c.g), c.b);
+    let x = max(max(c.r, c.g), c.b);
+    var result = c;
+    if n < 0.0 && l > n {
+        result = l + (result - l) * l / (l - n);
+    }
+    if x > 1.0 && x > l {
+        result = l + (result - l) * (1.0 - l) / (x - l);
+    }
+    return result;
+}
+
+fn set_lum(c: vec3<f32>, l: f32) -> vec3<f32> {
+    return clip_color(c + (l - lum(c)));
+}
+
+fn sat(c: vec3<f32>) -> f32 {
+    return max(max(c.r, c.g), c.b) - min(min(c.r, c.g), c.b);
+}
+
+fn set_sat(c: vec3<f32>, s: f32) -> vec3<f32> {
+    let n = min(min(c.r, c.g), c.b);
+    let x = max(max(c.r, c.g), c.b);
+    if x > n {
+        return (c - n) * s / (x - n);
+    }
+    return vec3<f32>(0.0);
+}
+
+// B(Cb, Cs) on unpremultiplied colors.
+fn blend_colors(cb: vec3<f32>, cs: vec3<f32>, mode: u32) -> vec3<f32> {
+    switch mode {
+        case MODE_OVERLAY: {
+            return hard_light(cs, cb);
+        }
+        case MODE_COLOR_DODGE: {
+            return color_dodge(cb, cs);
+        }
+        case MODE_COLOR_BURN: {
+            return color_burn(cb, cs);
+        }
+        case MODE_HARD_LIGHT: {
+            return hard_light(cb, cs);
+        }
+        case MODE_SOFT_LIGHT: {
+            return soft_light(cb, cs);
+        }
+        case MODE_DIFFERENCE: {
+            return abs(cb - cs);
+        }
+        case MODE_EXCLUSION: {
+            return cb + cs - 2.0 * cb * cs;
+        }
+        case MODE_HUE: {
+            return set_lum(set_sat(cs, sat(cb)), lum(cb));
+        }
+        case MODE_SATURATION: {
+            return set_lum(set_sat(cb, sat(cs)), lum(cb));
+        }
+        case MODE_COLOR: {
+            return set_lum(cs, lum(cb));
+        }
+        case MODE_LUMINOSITY: {
+            return set_lum(cb, lum(cs));
+        }
+        default: {
+            return cs;
+        }
+    }
+}
+
+fn unpremultiply(c: vec4<f32>) -> vec3<f32> {
+    if c.a <= 0.0 {
+        return vec3<f32>(0.0);
+    }
+    return min(c.rgb / c.a, vec3<f32>(1.0));
+}
+
+@fragment
+fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
+    // Both colors are premultiplied
+    let source = textureSample(t_source, s_source, input.uv) * uniforms.opacity;
+    let size = vec2<i32>(textureDimensions(t_backdrop)) - vec2<i32>(1);
+    let pixel = clamp(vec2<i32>(floor(input.clip_position.xy)), vec2<i32>(0), size);
+    let backdrop = textureLoad(t_backdrop, pixel, 0);
+
+    let mixed = blend_colors(unpremultiply(backdrop), unpremultiply(source), uniforms.mode);
+
+    // co = cs * (1 - ab) + cb * (1 - as) + as * ab * B(Cb, Cs)
+    let color = source.rgb * (1.0 - backdrop.a)
+        + backdrop.rgb * (1.0 - source.a)
+        + source.a * backdrop.a * mixed;
+    let alpha = source.a + backdrop.a * (1.0 - source.a);
+    return vec4<f32>(color, alpha);
+}
+
+// Draws the source with normal blending, for targets whose backdrop can't be copied.
+@fragment
+fn fs_source(input: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(t_source, s_source, input.uv) * uniforms.opacity;
+}
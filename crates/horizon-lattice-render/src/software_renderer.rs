@@ -0,0 +1,1613 @@
+//! CPU software renderer implementation.
+//!
+//! This module provides the [`SoftwareRenderer`], which implements the
+//! [`Renderer`] trait by rasterizing into memory on the CPU. It needs no
+//! graphics context, so widget trees can be rendered headlessly, for example
+//! for offscreen capture or visual tests on machines without a GPU.
+//!
+//! Shapes are flattened into line segments in device space and scan
+//! converted with exact horizontal coverage and several sub-scanlines per
+//! pixel row. Colors are kept premultiplied in `f32` and converted to an
+//! [`ImageBuffer`] on request.
+//!
+//! # Example
+//!
+//! ```
+//! use horizon_lattice_render::{Color, Rect, Renderer, Size, SoftwareRenderer};
+//!
+//! let mut renderer = SoftwareRenderer::new();
+//! renderer.begin_frame(Color::WHITE, Size::new(64.0, 64.0));
+//! renderer.fill_rect(Rect::new(8.0, 8.0, 48.0, 48.0), Color::RED);
+//! renderer.end_frame();
+//!
+//! assert_eq!(renderer.pixel(32, 32), Some(Color::RED));
+//! let image = renderer.to_image_buffer();
+//! assert_eq!(image.dimensions(), (64, 64));
+//! ```
+
+use lyon::path::PathEvent;
+use lyon::path::iterator::PathIterator;
+
+use crate::image::{Image, ImageScaleMode, NinePatch};
+use crate::image_buffer::ImageBuffer;
+use crate::paint::{
+    BlendMode, BoxShadow, DashPattern, FillRule, GradientStop, LinearGradient, Paint,
+    RadialGradient, Stroke,
+};
+use crate::path::{DEFAULT_TOLERANCE, tessellate_stroke, to_lyon_path};
+use crate::renderer::{FrameStats, RenderStateStack, Renderer};
+use crate::text::{FontSystem, GlyphCache, GlyphPixelFormat, TextLayout};
+use crate::transform::Transform2D;
+use crate::types::{Color, CornerRadii, Path, Point, Rect, RoundedRect, Size};
+
+/// Number of sub-scanlines sampled per pixel row for anti-aliasing.
+const SUBSAMPLES: usize = 8;
+
+/// A CPU renderer that draws into an in-memory pixel buffer.
+///
+/// The buffer is (re)allocated by [`begin_frame`](Renderer::begin_frame) at
+/// the viewport size, rounded up to whole pixels. Unlike the
+/// [`GpuRenderer`](crate::GpuRenderer), every [`BlendMode`] is supported, and
+/// images are drawn from their CPU pixels, so images created with
+/// [`Image::from_rgba`] work as well. Atlas images are skipped unless their
+/// [`ImageManager`](crate::ImageManager) keeps pixels.
+pub struct SoftwareRenderer {
+    width: u32,
+    height: u32,
+    /// Premultiplied pixels, row-major.
+    pixels: Vec<Color>,
+    state: RenderStateStack,
+    /// Coverage masks for rounded-rect and path clips, each already
+    /// intersected with the one below it.
+    clip_masks: Vec<Vec<f32>>,
+    glyph_cache: GlyphCache,
+    stats: FrameStats,
+}
+
+impl Default for SoftwareRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SoftwareRenderer {
+    /// Create a renderer with an empty pixel buffer.
+    pub fn new() -> Self {
+        Self {
+            width: 0,
+            height: 0,
+            pixels: Vec::new(),
+            state: RenderStateStack::new(),
+            clip_masks: Vec::new(),
+            glyph_cache: GlyphCache::new(),
+            stats: FrameStats::default(),
+        }
+    }
+
+    /// Get the width of the pixel buffer.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    /// Get the height of the pixel buffer.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Get the premultiplied color of a pixel.
+    ///
+    /// Returns `None` if the coordinates are out of bounds.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        Some(self.pixels[(y * self.width + x) as usize])
+    }
+
+    /// Get the premultiplied pixels, row-major.
+    #[inline]
+    pub fn pixels(&self) -> &[Color] {
+        &self.pixels
+    }
+
+    /// Copy the rendered pixels into an [`ImageBuffer`].
+    pub fn to_image_buffer(&self) -> ImageBuffer {
+        let mut data = Vec::with_capacity(self.pixels.len() * 4);
+        for color in &self.pixels {
+            let a = color.a.clamp(0.0, 1.0);
+            let unpremultiply = |c: f32| {
+                if a > 0.0 {
+                    ((c / a).clamp(0.0, 1.0) * 255.0).round() as u8
+                } else {
+                    0
+                }
+            };
+            data.extend_from_slice(&[
+                unpremultiply(color.r),
+                unpremultiply(color.g),
+                unpremultiply(color.b),
+                (a * 255.0).round() as u8,
+            ]);
+        }
+        ImageBuffer::from_rgba(&data, self.width, self.height)
+            .expect("pixel buffer matches its dimensions")
+    }
+
+    /// Draw a text layout with its top-left corner at `position`.
+    ///
+    /// Glyphs are rasterized through the renderer's [`GlyphCache`] and use
+    /// `color` unless the layout assigns them their own. Glyph bitmaps are
+    /// drawn pixel-aligned at their transformed origin, so rotation and
+    /// scaling only move the glyphs. Subpixel glyphs are drawn as grayscale.
+    pub fn draw_text(
+        &mut self,
+        font_system: &mut FontSystem,
+        layout: &TextLayout,
+        position: Point,
+        color: Color,
+    ) {
+        for line in layout.lines() {
+            for glyph in &line.glyphs {
+                if glyph.is_inline_element() {
+                    continue;
+                }
+
+                let (cache_key, pixel_x, pixel_y) = GlyphCache::cache_key_from_layout_glyph(glyph);
+                let Some(rasterized) = self.glyph_cache.rasterize(font_system, cache_key) else {
+                    continue;
+                };
+                if rasterized.is_empty() {
+                    continue;
+                }
+
+                let origin = self.state.transform().transform_point(Point::new(
+                    position.x + pixel_x as f32,
+                    position.y + line.baseline_y + pixel_y as f32,
+                ));
+                let left = origin.x.round() as i32 + rasterized.offset_x;
+                let top = origin.y.round() as i32 - rasterized.offset_y;
+                let glyph_color = glyph
+                    .color
+                    .map_or(color, |[r, g, b, a]| Color::from_rgba8(r, g, b, a));
+
+                let width = rasterized.width as usize;
+                let bpp = rasterized.format.bytes_per_pixel();
+                let data = &rasterized.data;
+                let coverage = Coverage::from_fn(
+                    IntRect::new(left, top, width, rasterized.height as usize),
+                    |x, y| {
+                        let i = ((y - top) as usize * width + (x - left) as usize) * bpp;
+                        match rasterized.format {
+                            GlyphPixelFormat::Alpha => data[i] as f32 / 255.0,
+                            GlyphPixelFormat::SubpixelRgba => {
+                                (data[i] as f32 + data[i + 1] as f32 + data[i + 2] as f32)
+                                    / (3.0 * 255.0)
+                            }
+                            GlyphPixelFormat::ColorRgba => 1.0,
+                        }
+                    },
+                );
+
+                if rasterized.format == GlyphPixelFormat::ColorRgba {
+                    self.composite(&coverage, |x, y| {
+                        let i = ((y - top) as usize * width + (x - left) as usize) * 4;
+                        Color::from_rgba8(data[i], data[i + 1], data[i + 2], data[i + 3])
+                    });
+                } else {
+                    self.composite(&coverage, |_, _| glyph_color);
+                }
+            }
+        }
+    }
+
+    // ========================================================================
+    // Rasterization
+    // ========================================================================
+
+    /// The device-space pixel bounds that drawing may touch.
+    fn drawable_bounds(&self) -> IntRect {
+        let viewport = IntRect::new(0, 0, self.width as usize, self.height as usize);
+        match self.state.clip_bounds() {
+            Some(clip) => viewport.intersect(&IntRect::enclosing(&clip)),
+            None => viewport,
+        }
+    }
+
+    /// Scan convert device-space edges into a coverage mask.
+    fn rasterize(&self, edges: &mut [Edge], fill_rule: FillRule) -> Coverage {
+        let Some(bounds) = edges_bounds(edges) else {
+            return Coverage::empty();
+        };
+        let bounds = self.drawable_bounds().intersect(&bounds);
+        if bounds.is_empty() {
+            return Coverage::empty();
+        }
+
+        edges.sort_by(|a, b| a.y0.total_cmp(&b.y0));
+        let mut coverage = Coverage::new(bounds);
+        let mut active: Vec<usize> = Vec::new();
+        let mut next = 0;
+        let mut crossings: Vec<(f32, i32)> = Vec::new();
+        let weight = 1.0 / SUBSAMPLES as f32;
+
+        for row in 0..bounds.height {
+            let py = (bounds.y + row as i32) as f32;
+            let line = &mut coverage.data[row * bounds.width..(row + 1) * bounds.width];
+            for sample in 0..SUBSAMPLES {
+                let sy = py + (sample as f32 + 0.5) * weight;
+                while next < edges.len() && edges[next].y0 <= sy {
+                    active.push(next);
+                    next += 1;
+                }
+                active.retain(|&i| edges[i].y1 > sy);
+
+                crossings.clear();
+                crossings.extend(
+                    active
+                        .iter()
+                        .filter(|&&i| edges[i].y0 <= sy)
+                        .map(|&i| (edges[i].x_at(sy), edges[i].winding)),
+                );
+                crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+                let mut winding = 0;
+                for pair in crossings.windows(2) {
+                    winding += pair[0].1;
+                    let inside = match fill_rule {
+                        FillRule::NonZero => winding != 0,
+                        FillRule::EvenOdd => winding % 2 != 0,
+                    };
+                    if inside {
+                        let start = pair[0].0 - bounds.x as f32;
+                        let end = pair[1].0 - bounds.x as f32;
+                        accumulate_span(line, start, end, weight);
+                    }
+                }
+            }
+        }
+
+        for value in &mut coverage.data {
+            *value = value.min(1.0);
+        }
+        coverage
+    }
+
+    /// Rasterize a user-space path with the current transform.
+    fn rasterize_path(&self, path: &Path, fill_rule: FillRule) -> Coverage {
+        let transformed = path.transformed(self.state.transform());
+        let mut edges = Vec::new();
+        for (points, _) in flatten(&transformed, DEFAULT_TOLERANCE) {
+            // Fills implicitly close every subpath.
+            for (i, &from) in points.iter().enumerate() {
+                let to = points[(i + 1) % points.len()];
+                edges.extend(Edge::new(from, to));
+            }
+        }
+        self.rasterize(&mut edges, fill_rule)
+    }
+
+    /// Rasterize the outline of a user-space path with the current transform.
+    fn rasterize_stroke(&self, path: &Path, stroke: &Stroke) -> Coverage {
+        if stroke.width <= 0.0 {
+            return Coverage::empty();
+        }
+
+        let transform = *self.state.transform();
+        // Tessellate in user space, finely enough for the current scale.
+        let scale = transform.determinant().abs().sqrt().max(1e-3);
+        let tolerance = DEFAULT_TOLERANCE / scale;
+        let dashed;
+        let path = match &stroke.dash_pattern {
+            Some(dash) => {
+                dashed = dash_path(path, dash, tolerance);
+                &dashed
+            }
+            None => path,
+        };
+
+        let tessellated = tessellate_stroke(path, stroke, tolerance);
+        let vertices: Vec<Point> = tessellated
+            .vertices
+            .iter()
+            .map(|&[x, y]| transform.transform_point(Point::new(x, y)))
+            .collect();
+
+        // Orient every triangle the same way so overlapping triangles add up
+        // under the non-zero rule instead of cancelling out.
+        let mut edges = Vec::with_capacity(tessellated.indices.len());
+        for triangle in tessellated.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| vertices[triangle[i] as usize]);
+            let area = (b.x - a.x) * (c.y - a.y) - (c.x - a.x) * (b.y - a.y);
+            let (b, c) = if area < 0.0 { (c, b) } else { (b, c) };
+            edges.extend(Edge::new(a, b));
+            edges.extend(Edge::new(b, c));
+            edges.extend(Edge::new(c, a));
+        }
+        self.rasterize(&mut edges, FillRule::NonZero)
+    }
+
+    // ========================================================================
+    // Compositing
+    // ========================================================================
+
+    /// Blend a source color into every pixel covered by `coverage`.
+    ///
+    /// `source` returns the premultiplied color at an integer pixel. Clips,
+    /// opacity and the blend mode are applied here.
+    fn composite(&mut self, coverage: &Coverage, source: impl Fn(i32, i32) -> Color) {
+        let bounds = self.drawable_bounds().intersect(&coverage.bounds);
+        if bounds.is_empty() {
+            return;
+        }
+        self.stats.draw_calls += 1;
+
+        let clip = self.state.clip_bounds();
+        let mask = self.clip_masks.last();
+        let opacity = self.state.current().opacity;
+        let mode = self.state.current().blend_mode;
+
+        for y in bounds.y..bounds.bottom() {
+            for x in bounds.x..bounds.right() {
+                let mut amount = coverage.get(x, y);
+                if amount <= 0.0 {
+                    continue;
+                }
+                let index = y as usize * self.width as usize + x as usize;
+                if let Some(clip) = clip {
+                    amount *= pixel_overlap(&clip, x, y);
+                }
+                if let Some(mask) = mask {
+                    amount *= mask[index];
+                }
+                if amount <= 0.0 {
+                    continue;
+                }
+
+                let src = scale_color(source(x, y), opacity);
+                let dst = self.pixels[index];
+                self.pixels[index] = dst.lerp(blend(src, dst, mode), amount.min(1.0));
+            }
+        }
+    }
+
+    /// Fill a coverage mask with a paint defined in user space.
+    fn composite_paint(&mut self, coverage: &Coverage, paint: &Paint) {
+        match paint {
+            Paint::Solid(color) => {
+                let color = *color;
+                self.composite(coverage, |_, _| color);
+            }
+            Paint::LinearGradient(gradient) => {
+                let shader = PaintShader::new(self.state.transform());
+                self.composite(coverage, |x, y| {
+                    linear_gradient_color(gradient, shader.user_point(x, y))
+                });
+            }
+            Paint::RadialGradient(gradient) => {
+                let shader = PaintShader::new(self.state.transform());
+                self.composite(coverage, |x, y| {
+                    radial_gradient_color(gradient, shader.user_point(x, y))
+                });
+            }
+        }
+    }
+
+    /// Push a clip mask, intersected with the current one.
+    fn push_clip_mask(&mut self, coverage: &Coverage) {
+        let mut mask = vec![0.0; self.pixels.len()];
+        let width = self.width as usize;
+        for y in coverage.bounds.y..coverage.bounds.bottom() {
+            for x in coverage.bounds.x..coverage.bounds.right() {
+                if x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height {
+                    mask[y as usize * width + x as usize] = coverage.get(x, y);
+                }
+            }
+        }
+        if let Some(parent) = self.clip_masks.last() {
+            for (value, parent) in mask.iter_mut().zip(parent) {
+                *value *= parent;
+            }
+        }
+        self.clip_masks.push(mask);
+    }
+
+    /// Evaluate a box shadow over its device-space bounds.
+    fn shadow_coverage(&self, rrect: RoundedRect, shadow: &BoxShadow) -> Coverage {
+        let rect = rrect.rect;
+        let radius = rrect.radii.max();
+        let shape = if shadow.inset {
+            rect.inflate(-shadow.spread_radius)
+        } else {
+            rect.inflate(shadow.spread_radius)
+        }
+        .offset(shadow.offset_x, shadow.offset_y);
+        let shape_radius = if shadow.inset {
+            radius - shadow.spread_radius
+        } else {
+            radius + shadow.spread_radius
+        }
+        .clamp(0.0, shape.width().min(shape.height()).max(0.0) / 2.0);
+
+        if shadow.blur_radius < 0.5 {
+            // Too sharp for the analytic blur; use the shape itself.
+            let shape_path = Path::rounded_rect(shape, CornerRadii::uniform(shape_radius.max(0.0)));
+            let mut coverage = self.rasterize_path(&shape_path, FillRule::NonZero);
+            if shadow.inset {
+                let outer =
+                    self.rasterize_path(&Path::rounded_rect(rect, rrect.radii), FillRule::NonZero);
+                coverage = outer.map(|x, y, value| value * (1.0 - coverage.get(x, y)));
+            }
+            return coverage;
+        }
+
+        let sigma = shadow.sigma();
+        let bounds = if shadow.inset {
+            rect
+        } else {
+            shape.inflate(sigma * 3.0)
+        };
+        let device_bounds = IntRect::enclosing(&self.state.transform().transform_rect(&bounds))
+            .intersect(&self.drawable_bounds());
+        let shader = PaintShader::new(self.state.transform());
+        let blurred = Coverage::from_fn(device_bounds, |x, y| {
+            let p = shader.user_point(x, y);
+            rounded_box_shadow(shape, shape_radius, sigma, p)
+        });
+
+        if shadow.inset {
+            let outer =
+                self.rasterize_path(&Path::rounded_rect(rect, rrect.radii), FillRule::NonZero);
+            outer.map(|x, y, value| value * (1.0 - blurred.get(x, y)))
+        } else {
+            blurred
+        }
+    }
+}
+
+impl Renderer for SoftwareRenderer {
+    fn begin_frame(&mut self, clear_color: Color, viewport_size: Size) {
+        self.width = viewport_size.width.max(0.0).ceil() as u32;
+        self.height = viewport_size.height.max(0.0).ceil() as u32;
+        self.pixels.clear();
+        self.pixels
+            .resize((self.width * self.height) as usize, clear_color);
+        self.state.reset();
+        self.clip_masks.clear();
+        self.stats = FrameStats::default();
+    }
+
+    fn end_frame(&mut self) -> FrameStats {
+        self.stats.clone()
+    }
+
+    fn save(&mut self) {
+        self.state.save();
+    }
+
+    fn restore(&mut self) {
+        self.state.restore();
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    fn transform(&self) -> &Transform2D {
+        self.state.transform()
+    }
+
+    fn set_transform(&mut self, transform: Transform2D) {
+        self.state.set_transform(transform);
+    }
+
+    fn concat_transform(&mut self, transform: &Transform2D) {
+        self.state.concat_transform(transform);
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.state.translate(tx, ty);
+    }
+
+    fn scale(&mut self, sx: f32, sy: f32) {
+        self.state.scale(sx, sy);
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.state.rotate(angle);
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        self.state.clip_rect(rect);
+    }
+
+    fn clip_rounded_rect(&mut self, rrect: RoundedRect) {
+        if rrect.radii.is_zero() {
+            self.clip_rect(rrect.rect);
+            return;
+        }
+        let coverage = self.rasterize_path(
+            &Path::rounded_rect(rrect.rect, rrect.radii),
+            FillRule::NonZero,
+        );
+        self.push_clip_mask(&coverage);
+    }
+
+    fn restore_clip(&mut self) {
+        self.clip_masks.pop();
+    }
+
+    fn clip_bounds(&self) -> Option<Rect> {
+        self.state.clip_bounds()
+    }
+
+    fn has_stencil_clips(&self) -> bool {
+        !self.clip_masks.is_empty()
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        if path.is_empty() {
+            return;
+        }
+        let coverage = self.rasterize_path(path, FillRule::NonZero);
+        self.push_clip_mask(&coverage);
+    }
+
+    fn fill_rect(&mut self, rect: Rect, paint: impl Into<Paint>) {
+        self.fill_path(&Path::rect(rect), paint, FillRule::NonZero);
+    }
+
+    fn fill_rounded_rect(&mut self, rrect: RoundedRect, paint: impl Into<Paint>) {
+        self.fill_path(
+            &Path::rounded_rect(rrect.rect, rrect.radii),
+            paint,
+            FillRule::NonZero,
+        );
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, stroke: &Stroke) {
+        self.stroke_path(&Path::rect(rect), stroke);
+    }
+
+    fn stroke_rounded_rect(&mut self, rrect: RoundedRect, stroke: &Stroke) {
+        self.stroke_path(&Path::rounded_rect(rrect.rect, rrect.radii), stroke);
+    }
+
+    fn draw_box_shadow(&mut self, rect: Rect, shadow: &BoxShadow) {
+        self.draw_box_shadow_rounded(RoundedRect::new(rect, 0.0), shadow);
+    }
+
+    fn draw_box_shadow_rounded(&mut self, rrect: RoundedRect, shadow: &BoxShadow) {
+        let coverage = self.shadow_coverage(rrect, shadow);
+        let color = shadow.color;
+        self.composite(&coverage, |_, _| color);
+    }
+
+    fn draw_line(&mut self, from: Point, to: Point, stroke: &Stroke) {
+        let mut path = Path::new();
+        path.move_to(from).line_to(to);
+        self.stroke_path(&path, stroke);
+    }
+
+    fn draw_polyline(&mut self, points: &[Point], stroke: &Stroke) {
+        let Some((&first, rest)) = points.split_first() else {
+            return;
+        };
+        let mut path = Path::new();
+        path.move_to(first);
+        for &point in rest {
+            path.line_to(point);
+        }
+        self.stroke_path(&path, stroke);
+    }
+
+    fn fill_ellipse(
+        &mut self,
+        center: Point,
+        radius_x: f32,
+        radius_y: f32,
+        paint: impl Into<Paint>,
+    ) {
+        self.fill_path(
+            &Path::ellipse(center, radius_x, radius_y),
+            paint,
+            FillRule::NonZero,
+        );
+    }
+
+    fn stroke_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, stroke: &Stroke) {
+        self.stroke_path(&Path::ellipse(center, radius_x, radius_y), stroke);
+    }
+
+    fn fill_path(&mut self, path: &Path, paint: impl Into<Paint>, fill_rule: FillRule) {
+        if path.is_empty() {
+            return;
+        }
+        let paint = paint.into();
+        let coverage = self.rasterize_path(path, fill_rule);
+        self.composite_paint(&coverage, &paint);
+    }
+
+    fn stroke_path(&mut self, path: &Path, stroke: &Stroke) {
+        if path.is_empty() {
+            return;
+        }
+        let coverage = self.rasterize_stroke(path, stroke);
+        self.composite_paint(&coverage, &stroke.paint);
+    }
+
+    fn draw_image(&mut self, image: &Image, dest: Rect, scale_mode: ImageScaleMode) {
+        let src = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        if scale_mode != ImageScaleMode::Tile {
+            let dest = scaled_dest(image.size(), dest, scale_mode);
+            self.draw_image_rect(image, src, dest);
+            return;
+        }
+
+        if image.width() == 0 || image.height() == 0 {
+            return;
+        }
+        self.save();
+        self.clip_rect(dest);
+        let mut y = dest.top();
+        while y < dest.bottom() {
+            let mut x = dest.left();
+            while x < dest.right() {
+                self.draw_image_rect(image, src, Rect::new(x, y, src.width(), src.height()));
+                x += src.width();
+            }
+            y += src.height();
+        }
+        self.restore();
+    }
+
+    fn draw_image_rect(&mut self, image: &Image, src: Rect, dest: Rect) {
+        if src.width() <= 0.0 || src.height() <= 0.0 || dest.width() <= 0.0 {
+            return;
+        }
+        let Some(pixels) = image.pixels() else {
+            return;
+        };
+        let coverage = self.rasterize_path(&Path::rect(dest), FillRule::NonZero);
+        let shader = PaintShader::new(self.state.transform());
+        let scale_x = src.width() / dest.width();
+        let scale_y = src.height() / dest.height();
+        self.composite(&coverage, |x, y| {
+            let p = shader.user_point(x, y);
+            let u = src.left() + (p.x - dest.left()) * scale_x;
+            let v = src.top() + (p.y - dest.top()) * scale_y;
+            sample_image(image, pixels, src, u, v)
+        });
+    }
+
+    fn draw_nine_patch(&mut self, nine_patch: &NinePatch, dest: Rect) {
+        for (src, dest) in nine_patch.calculate_patches(dest) {
+            if src.width() <= 0.0 || src.height() <= 0.0 || dest.width() <= 0.0 {
+                continue;
+            }
+            self.draw_image_rect(&nine_patch.image, src, dest);
+        }
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        if self.state.current().blend_mode != mode {
+            self.state.current_mut().blend_mode = mode;
+            self.stats.state_changes += 1;
+        }
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.state.current().blend_mode
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.state.current_mut().opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    fn opacity(&self) -> f32 {
+        self.state.current().opacity
+    }
+}
+
+impl std::fmt::Debug for SoftwareRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SoftwareRenderer")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("clip_masks", &self.clip_masks.len())
+            .finish()
+    }
+}
+
+// ============================================================================
+// Geometry helpers
+// ============================================================================
+
+/// An integer pixel rectangle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct IntRect {
+    x: i32,
+    y: i32,
+    width: usize,
+    height: usize,
+}
+
+impl IntRect {
+    fn new(x: i32, y: i32, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// The smallest pixel rectangle containing `rect`.
+    fn enclosing(rect: &Rect) -> Self {
+        let left = rect.left().floor().max(i32::MIN as f32) as i32;
+        let top = rect.top().floor().max(i32::MIN as f32) as i32;
+        let right = rect.right().ceil().min(i32::MAX as f32) as i32;
+        let bottom = rect.bottom().ceil().min(i32::MAX as f32) as i32;
+        Self::new(
+            left,
+            top,
+            right.saturating_sub(left).max(0) as usize,
+            bottom.saturating_sub(top).max(0) as usize,
+        )
+    }
+
+    fn right(&self) -> i32 {
+        self.x + self.width as i32
+    }
+
+    fn bottom(&self) -> i32 {
+        self.y + self.height as i32
+    }
+
+    fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    fn intersect(&self, other: &Self) -> Self {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        let right = self.right().min(other.right());
+        let bottom = self.bottom().min(other.bottom());
+        Self::new(
+            x,
+            y,
+            (right - x).max(0) as usize,
+            (bottom - y).max(0) as usize,
+        )
+    }
+}
+
+/// Per-pixel coverage over a pixel rectangle.
+struct Coverage {
+    bounds: IntRect,
+    data: Vec<f32>,
+}
+
+impl Coverage {
+    fn new(bounds: IntRect) -> Self {
+        Self {
+            bounds,
+            data: vec![0.0; bounds.width * bounds.height],
+        }
+    }
+
+    fn empty() -> Self {
+        Self::new(IntRect::new(0, 0, 0, 0))
+    }
+
+    fn from_fn(bounds: IntRect, f: impl Fn(i32, i32) -> f32) -> Self {
+        let mut data = Vec::with_capacity(bounds.width * bounds.height);
+        for y in bounds.y..bounds.bottom() {
+            for x in bounds.x..bounds.right() {
+                data.push(f(x, y));
+            }
+        }
+        Self { bounds, data }
+    }
+
+    fn map(&self, f: impl Fn(i32, i32, f32) -> f32) -> Self {
+        let bounds = self.bounds;
+        Self::from_fn(bounds, |x, y| f(x, y, self.get(x, y)))
+    }
+
+    fn get(&self, x: i32, y: i32) -> f32 {
+        let b = &self.bounds;
+        if x < b.x || y < b.y || x >= b.right() || y >= b.bottom() {
+            return 0.0;
+        }
+        self.data[(y - b.y) as usize * b.width + (x - b.x) as usize]
+    }
+}
+
+/// A non-horizontal line segment, stored top to bottom.
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    /// +1 for downward edges, -1 for upward ones.
+    winding: i32,
+}
+
+impl Edge {
+    fn new(from: Point, to: Point) -> Option<Self> {
+        if from.y == to.y || !from.y.is_finite() || !to.y.is_finite() {
+            return None;
+        }
+        let (top, bottom, winding) = if from.y < to.y {
+            (from, to, 1)
+        } else {
+            (to, from, -1)
+        };
+        Some(Self {
+            x0: top.x,
+            y0: top.y,
+            x1: bottom.x,
+            y1: bottom.y,
+            winding,
+        })
+    }
+
+    fn x_at(&self, y: f32) -> f32 {
+        self.x0 + (y - self.y0) * (self.x1 - self.x0) / (self.y1 - self.y0)
+    }
+}
+
+fn edges_bounds(edges: &[Edge]) -> Option<IntRect> {
+    let first = edges.first()?;
+    let mut rect = Rect::from_corners(
+        Point::new(first.x0.min(first.x1), first.y0),
+        Point::new(first.x0.max(first.x1), first.y1),
+    );
+    for edge in &edges[1..] {
+        rect = rect.union(&Rect::from_corners(
+            Point::new(edge.x0.min(edge.x1), edge.y0),
+            Point::new(edge.x0.max(edge.x1), edge.y1),
+        ));
+    }
+    Some(IntRect::enclosing(&rect))
+}
+
+/// Add exact horizontal coverage for the span `start..end` to a row.
+fn accumulate_span(line: &mut [f32], start: f32, end: f32, weight: f32) {
+    let start = start.clamp(0.0, line.len() as f32);
+    let end = end.clamp(0.0, line.len() as f32);
+    if start >= end {
+        return;
+    }
+    let first = start as usize;
+    let last = end as usize;
+    if first == last {
+        line[first] += (end - start) * weight;
+        return;
+    }
+    line[first] += (first as f32 + 1.0 - start) * weight;
+    for value in &mut line[first + 1..last] {
+        *value += weight;
+    }
+    if last < line.len() {
+        line[last] += (end - last as f32) * weight;
+    }
+}
+
+/// The fraction of pixel `(x, y)` that lies inside `rect`.
+fn pixel_overlap(rect: &Rect, x: i32, y: i32) -> f32 {
+    let (x, y) = (x as f32, y as f32);
+    let w = (rect.right().min(x + 1.0) - rect.left().max(x)).max(0.0);
+    let h = (rect.bottom().min(y + 1.0) - rect.top().max(y)).max(0.0);
+    w * h
+}
+
+/// Flatten a path into polylines, returning each subpath and whether it is
+/// closed.
+fn flatten(path: &Path, tolerance: f32) -> Vec<(Vec<Point>, bool)> {
+    let mut polylines = Vec::new();
+    let mut current = Vec::new();
+    for event in to_lyon_path(path).iter().flattened(tolerance) {
+        match event {
+            PathEvent::Begin { at } => {
+                current = vec![Point::new(at.x, at.y)];
+            }
+            PathEvent::Line { to, .. } => current.push(Point::new(to.x, to.y)),
+            PathEvent::End { close, .. } => {
+                polylines.push((std::mem::take(&mut current), close));
+            }
+            PathEvent::Quadratic { to, .. } | PathEvent::Cubic { to, .. } => {
+                current.push(Point::new(to.x, to.y));
+            }
+        }
+    }
+    polylines
+}
+
+/// Split a path into dashes.
+///
+/// Dashing restarts at the beginning of every subpath. Invalid patterns
+/// leave the path undashed.
+fn dash_path(path: &Path, dash: &DashPattern, tolerance: f32) -> Path {
+    let total: f32 = dash.pattern.iter().sum();
+    if total <= 0.0
+        || dash
+            .pattern
+            .iter()
+            .any(|&len| len < 0.0 || !len.is_finite())
+    {
+        return path.clone();
+    }
+
+    let mut dashed = Path::new();
+    for (mut points, closed) in flatten(path, tolerance) {
+        if closed && let Some(&first) = points.first() {
+            points.push(first);
+        }
+
+        // Find the pattern entry at the dash offset.
+        let mut index = 0;
+        let mut remaining = dash.pattern[0];
+        let mut skip = dash.offset.rem_euclid(total);
+        while skip > 0.0 {
+            if skip < remaining {
+                remaining -= skip;
+                break;
+            }
+            skip -= remaining;
+            index = (index + 1) % dash.pattern.len();
+            remaining = dash.pattern[index];
+        }
+
+        let mut pen_down = false;
+        for segment in points.windows(2) {
+            let (mut from, to) = (segment[0], segment[1]);
+            let mut length = ((to.x - from.x).powi(2) + (to.y - from.y).powi(2)).sqrt();
+            while length > 0.0 {
+                let on = index.is_multiple_of(2);
+                let step = remaining.min(length);
+                let t = step / length;
+                let next = Point::new(from.x + (to.x - from.x) * t, from.y + (to.y - from.y) * t);
+                if on {
+                    if !pen_down {
+                        dashed.move_to(from);
+                        pen_down = true;
+                    }
+                    dashed.line_to(next);
+                }
+                from = next;
+                length -= step;
+                remaining -= step;
+                if remaining <= 0.0 {
+                    index = (index + 1) % dash.pattern.len();
+                    remaining = dash.pattern[index];
+                    pen_down = false;
+                }
+            }
+        }
+    }
+    dashed
+}
+
+/// Compute where an image is drawn for a non-tiling scale mode.
+pub(crate) fn scaled_dest(image_size: Size, dest: Rect, scale_mode: ImageScaleMode) -> Rect {
+    let scale_x = dest.width() / image_size.width;
+    let scale_y = dest.height() / image_size.height;
+    let scale = match scale_mode {
+        ImageScaleMode::Fit => scale_x.min(scale_y),
+        ImageScaleMode::Fill => scale_x.max(scale_y),
+        ImageScaleMode::Stretch | ImageScaleMode::Tile => return dest,
+    };
+    let size = Size::new(image_size.width * scale, image_size.height * scale);
+    Rect::new(
+        dest.left() + (dest.width() - size.width) / 2.0,
+        dest.top() + (dest.height() - size.height) / 2.0,
+        size.width,
+        size.height,
+    )
+}
+
+// ============================================================================
+// Shading
+// ============================================================================
+
+/// Maps device pixel centers back into user space.
+struct PaintShader {
+    inverse: Transform2D,
+}
+
+impl PaintShader {
+    fn new(transform: &Transform2D) -> Self {
+        Self {
+            inverse: transform.inverse().unwrap_or(Transform2D::IDENTITY),
+        }
+    }
+
+    fn user_point(&self, x: i32, y: i32) -> Point {
+        self.inverse
+            .transform_point(Point::new(x as f32 + 0.5, y as f32 + 0.5))
+    }
+}
+
+fn scale_color(color: Color, factor: f32) -> Color {
+    Color::new(
+        color.r * factor,
+        color.g * factor,
+        color.b * factor,
+        color.a * factor,
+    )
+}
+
+fn gradient_color(stops: &[GradientStop], t: f32) -> Color {
+    let Some(first) = stops.first() else {
+        return Color::TRANSPARENT;
+    };
+    let t = t.clamp(0.0, 1.0);
+    if t <= first.offset {
+        return first.color;
+    }
+    for pair in stops.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if t <= b.offset {
+            let span = b.offset - a.offset;
+            if span <= 0.0 {
+                return b.color;
+            }
+            return a.color.lerp(b.color, (t - a.offset) / span);
+        }
+    }
+    stops[stops.len() - 1].color
+}
+
+fn linear_gradient_color(gradient: &LinearGradient, p: Point) -> Color {
+    let dx = gradient.end.x - gradient.start.x;
+    let dy = gradient.end.y - gradient.start.y;
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq > 0.0 {
+        ((p.x - gradient.start.x) * dx + (p.y - gradient.start.y) * dy) / length_sq
+    } else {
+        0.0
+    };
+    gradient_color(&gradient.stops, t)
+}
+
+fn radial_gradient_color(gradient: &RadialGradient, p: Point) -> Color {
+    let c = gradient.center;
+    let r = gradient.radius;
+    if r <= 0.0 {
+        return gradient_color(&gradient.stops, 1.0);
+    }
+    let f = gradient.focus.unwrap_or(c);
+
+    // Find the circle point on the ray from the focus through `p`; the
+    // gradient position is how far along that ray `p` lies.
+    let (vx, vy) = (p.x - f.x, p.y - f.y);
+    let (fx, fy) = (f.x - c.x, f.y - c.y);
+    let a = vx * vx + vy * vy;
+    if a == 0.0 {
+        return gradient_color(&gradient.stops, 0.0);
+    }
+    let b = vx * fx + vy * fy;
+    let cc = fx * fx + fy * fy - r * r;
+    let discriminant = b * b - a * cc;
+    let t = if discriminant < 0.0 {
+        1.0
+    } else {
+        let k = (-b + discriminant.sqrt()) / a;
+        if k > 0.0 { 1.0 / k } else { 1.0 }
+    };
+    gradient_color(&gradient.stops, t)
+}
+
+/// Bilinearly sample an image at pixel coordinates, staying within `src`.
+fn sample_image(image: &Image, pixels: &[u8], src: Rect, u: f32, v: f32) -> Color {
+    let (width, height) = (image.width() as i32, image.height() as i32);
+    if width == 0 || height == 0 {
+        return Color::TRANSPARENT;
+    }
+    let u = u.clamp(src.left() + 0.5, (src.right() - 0.5).max(src.left() + 0.5)) - 0.5;
+    let v = v.clamp(src.top() + 0.5, (src.bottom() - 0.5).max(src.top() + 0.5)) - 0.5;
+    let (x0, y0) = (u.floor(), v.floor());
+    let (tx, ty) = (u - x0, v - y0);
+    let texel = |x: i32, y: i32| {
+        let x = x.clamp(0, width - 1);
+        let y = y.clamp(0, height - 1);
+        let i = (y * width + x) as usize * 4;
+        Color::from_rgba8(pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3])
+    };
+    let (x0, y0) = (x0 as i32, y0 as i32);
+    let top = texel(x0, y0).lerp(texel(x0 + 1, y0), tx);
+    let bottom = texel(x0, y0 + 1).lerp(texel(x0 + 1, y0 + 1), tx);
+    top.lerp(bottom, ty)
+}
+
+// ============================================================================
+// Box shadows
+// ============================================================================
+
+/// Approximation of the error function (Abramowitz and Stegun 7.1.27).
+fn erf(x: f32) -> f32 {
+    let sign = x.signum();
+    let x = x.abs();
+    let t = 1.0 + (0.278393 + (0.230389 + (0.000972 + 0.078108 * x) * x) * x) * x;
+    sign * (1.0 - 1.0 / (t * t * t * t))
+}
+
+fn gaussian(x: f32, sigma: f32) -> f32 {
+    (-(x * x) / (2.0 * sigma * sigma)).exp() / ((2.0 * std::f32::consts::PI).sqrt() * sigma)
+}
+
+/// The blurred coverage along one row of a rounded rectangle centered at the
+/// origin.
+fn rounded_box_row(x: f32, y: f32, sigma: f32, corner: f32, half: (f32, f32)) -> f32 {
+    let delta = (half.1 - corner - y.abs()).min(0.0);
+    let curved = half.0 - corner + (corner * corner - delta * delta).max(0.0).sqrt();
+    let scale = std::f32::consts::FRAC_1_SQRT_2 / sigma;
+    let low = 0.5 + 0.5 * erf((x - curved) * scale);
+    let high = 0.5 + 0.5 * erf((x + curved) * scale);
+    high - low
+}
+
+/// The Gaussian-blurred coverage of a rounded rectangle at a point.
+///
+/// Blurs exactly along x and integrates numerically along y.
+fn rounded_box_shadow(rect: Rect, corner: f32, sigma: f32, p: Point) -> f32 {
+    if rect.width() <= 0.0 || rect.height() <= 0.0 {
+        return 0.0;
+    }
+    let half = (rect.width() / 2.0, rect.height() / 2.0);
+    let x = p.x - (rect.left() + half.0);
+    let y = p.y - (rect.top() + half.1);
+
+    let low = y - half.1;
+    let high = y + half.1;
+    let start = (-3.0 * sigma).clamp(low, high);
+    let end = (3.0 * sigma).clamp(low, high);
+    const STEPS: usize = 8;
+    let step = (end - start) / STEPS as f32;
+    let mut sample_y = start + step * 0.5;
+    let mut value = 0.0;
+    for _ in 0..STEPS {
+        value += rounded_box_row(x, y - sample_y, sigma, corner, half)
+            * gaussian(sample_y, sigma)
+            * step;
+        sample_y += step;
+    }
+    value.clamp(0.0, 1.0)
+}
+
+// ============================================================================
+// Blending
+// ============================================================================
+
+/// Blend premultiplied `src` over premultiplied `dst`.
+fn blend(src: Color, dst: Color, mode: BlendMode) -> Color {
+    let (sa, da) = (src.a, dst.a);
+    let porter_duff = |fa: f32, fb: f32| {
+        Color::new(
+            src.r * fa + dst.r * fb,
+            src.g * fa + dst.g * fb,
+            src.b * fa + dst.b * fb,
+            sa * fa + da * fb,
+        )
+    };
+
+    // Blend modes work on unpremultiplied components:
+    // co = cs * (1 - ab) + cb * (1 - as) + as * ab * B(Cb, Cs)
+    let unpremultiply = |c: f32, a: f32| if a > 0.0 { (c / a).min(1.0) } else { 0.0 };
+    let composite = |mixed: Rgb| {
+        let channel = |s: f32, d: f32, m: f32| s * (1.0 - da) + d * (1.0 - sa) + sa * da * m;
+        Color::new(
+            channel(src.r, dst.r, mixed[0]),
+            channel(src.g, dst.g, mixed[1]),
+            channel(src.b, dst.b, mixed[2]),
+            sa + da * (1.0 - sa),
+        )
+    };
+
+    let non_separable: Option<fn(Rgb, Rgb) -> Rgb> = match mode {
+        BlendMode::Hue => Some(|b, s| set_lum(set_sat(s, sat(b)), lum(b))),
+        BlendMode::Saturation => Some(|b, s| set_lum(set_sat(b, sat(s)), lum(b))),
+        BlendMode::Color => Some(|b, s| set_lum(s, lum(b))),
+        BlendMode::Luminosity => Some(|b, s| set_lum(b, lum(s))),
+        _ => None,
+    };
+    if let Some(mix) = non_separable {
+        let backdrop = [
+            unpremultiply(dst.r, da),
+            unpremultiply(dst.g, da),
+            unpremultiply(dst.b, da),
+        ];
+        let source = [
+            unpremultiply(src.r, sa),
+            unpremultiply(src.g, sa),
+            unpremultiply(src.b, sa),
+        ];
+        return composite(mix(backdrop, source));
+    }
+
+    let separable: fn(f32, f32) -> f32 = match mode {
+        BlendMode::Normal => return porter_duff(1.0, 1.0 - sa),
+        BlendMode::Source => return src,
+        BlendMode::Destination => return dst,
+        BlendMode::SourceIn => return porter_duff(da, 0.0),
+        BlendMode::DestinationIn => return porter_duff(0.0, sa),
+        BlendMode::SourceOut => return porter_duff(1.0 - da, 0.0),
+        BlendMode::DestinationOut => return porter_duff(0.0, 1.0 - sa),
+        BlendMode::SourceAtop => return porter_duff(da, 1.0 - sa),
+        BlendMode::DestinationAtop => return porter_duff(1.0 - da, sa),
+        BlendMode::Xor => return porter_duff(1.0 - da, 1.0 - sa),
+        BlendMode::Add => {
+            let sum = porter_duff(1.0, 1.0);
+            return Color::new(
+                sum.r.min(1.0),
+                sum.g.min(1.0),
+                sum.b.min(1.0),
+                sum.a.min(1.0),
+            );
+        }
+        BlendMode::Multiply => |b, s| b * s,
+        BlendMode::Screen => |b, s| b + s - b * s,
+        BlendMode::Overlay => |b, s| hard_light(s, b),
+        BlendMode::Darken => f32::min,
+        BlendMode::Lighten => f32::max,
+        BlendMode::ColorDodge => |b, s| {
+            if b == 0.0 {
+                0.0
+            } else if s >= 1.0 {
+                1.0
+            } else {
+                (b / (1.0 - s)).min(1.0)
+            }
+        },
+        BlendMode::ColorBurn => |b, s| {
+            if b >= 1.0 {
+                1.0
+            } else if s <= 0.0 {
+                0.0
+            } else {
+                1.0 - ((1.0 - b) / s).min(1.0)
+            }
+        },
+        BlendMode::HardLight => |b, s| hard_light(b, s),
+        BlendMode::SoftLight => |b, s| {
+            if s <= 0.5 {
+                b - (1.0 - 2.0 * s) * b * (1.0 - b)
+            } else {
+                let d = if b <= 0.25 {
+                    ((16.0 * b - 12.0) * b + 4.0) * b
+                } else {
+                    b.sqrt()
+                };
+                b + (2.0 * s - 1.0) * (d - b)
+            }
+        },
+        BlendMode::Difference => |b, s| (b - s).abs(),
+        BlendMode::Exclusion => |b, s| b + s - 2.0 * b * s,
+        BlendMode::Hue | BlendMode::Saturation | BlendMode::Color | BlendMode::Luminosity => {
+            unreachable!("non-separable modes are handled above")
+        }
+    };
+
+    let mixed = |s: f32, d: f32| separable(unpremultiply(d, da), unpremultiply(s, sa));
+    composite([
+        mixed(src.r, dst.r),
+        mixed(src.g, dst.g),
+        mixed(src.b, dst.b),
+    ])
+}
+
+/// Unpremultiplied red, green and blue components.
+type Rgb = [f32; 3];
+
+/// Luminosity of an unpremultiplied color, as defined for non-separable blend modes.
+fn lum(c: Rgb) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+/// Brings a color with out-of-range components back into range, keeping its luminosity.
+fn clip_color(c: Rgb) -> Rgb {
+    let l = lum(c);
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    c.map(|v| {
+        let mut v = v;
+        if n < 0.0 && l > n {
+            v = l + (v - l) * l / (l - n);
+        }
+        if x > 1.0 && x > l {
+            v = l + (v - l) * (1.0 - l) / (x - l);
+        }
+        v
+    })
+}
+
+fn set_lum(c: Rgb, l: f32) -> Rgb {
+    let d = l - lum(c);
+    clip_color(c.map(|v| v + d))
+}
+
+fn sat(c: Rgb) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: Rgb, s: f32) -> Rgb {
+    let n = c[0].min(c[1]).min(c[2]);
+    let x = c[0].max(c[1]).max(c[2]);
+    if x > n {
+        c.map(|v| (v - n) * s / (x - n))
+    } else {
+        [0.0; 3]
+    }
+}
+
+fn hard_light(b: f32, s: f32) -> f32 {
+    if s <= 0.5 {
+        b * 2.0 * s
+    } else {
+        let s = 2.0 * s - 1.0;
+        b + s - b * s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn renderer(width: f32, height: f32) -> SoftwareRenderer {
+        let mut renderer = SoftwareRenderer::new();
+        renderer.begin_frame(Color::WHITE, Size::new(width, height));
+        renderer
+    }
+
+    fn assert_color_eq(actual: Color, expected: Color) {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.02;
+        assert!(
+            close(actual.r, expected.r)
+                && close(actual.g, expected.g)
+                && close(actual.b, expected.b)
+                && close(actual.a, expected.a),
+            "expected {expected:?}, got {actual:?}"
+        );
+    }
+
+    #[test]
+    fn test_fill_rect_with_transform_and_antialiasing() {
+        let mut r = renderer(20.0, 20.0);
+        r.translate(2.0, 2.0);
+        r.fill_rect(Rect::new(0.0, 0.0, 4.5, 4.0), Color::RED);
+        let stats = r.end_frame();
+
+        assert_eq!(stats.draw_calls, 1);
+        assert_eq!(r.pixel(1, 3), Some(Color::WHITE));
+        assert_eq!(r.pixel(2, 2), Some(Color::RED));
+        assert_eq!(r.pixel(5, 5), Some(Color::RED));
+        // Half-covered column.
+        assert_color_eq(r.pixel(6, 3).unwrap(), Color::new(1.0, 0.5, 0.5, 1.0));
+        assert_eq!(r.pixel(2, 6), Some(Color::WHITE));
+    }
+
+    #[test]
+    fn test_fill_rules_and_strokes() {
+        let mut r = renderer(40.0, 40.0);
+        let mut path = Path::rect(Rect::new(0.0, 0.0, 30.0, 30.0));
+        path.move_to(Point::new(10.0, 10.0))
+            .line_to(Point::new(20.0, 10.0))
+            .line_to(Point::new(20.0, 20.0))
+            .line_to(Point::new(10.0, 20.0))
+            .close();
+        r.fill_path(&path, Color::BLUE, FillRule::EvenOdd);
+        assert_eq!(r.pixel(5, 5), Some(Color::BLUE));
+        assert_eq!(r.pixel(15, 15), Some(Color::WHITE));
+
+        r.fill_path(&path, Color::BLUE, FillRule::NonZero);
+        assert_eq!(r.pixel(15, 15), Some(Color::BLUE));
+
+        r.draw_line(
+            Point::new(0.0, 35.0),
+            Point::new(40.0, 35.0),
+            &Stroke::new(Color::BLACK, 2.0),
+        );
+        assert_eq!(r.pixel(20, 34), Some(Color::BLACK));
+        assert_eq!(r.pixel(20, 35), Some(Color::BLACK));
+        assert_eq!(r.pixel(20, 32), Some(Color::WHITE));
+
+        let dashed = Stroke::new(Color::BLACK, 2.0).with_dash(DashPattern::simple(4.0, 4.0));
+        r.draw_line(Point::new(0.0, 38.0), Point::new(40.0, 38.0), &dashed);
+        assert_eq!(r.pixel(1, 38), Some(Color::BLACK));
+        assert_eq!(r.pixel(5, 38), Some(Color::WHITE));
+    }
+
+    #[test]
+    fn test_gradients() {
+        let mut r = renderer(100.0, 10.0);
+        let gradient = LinearGradient {
+            start: Point::new(0.0, 0.0),
+            end: Point::new(100.0, 0.0),
+            stops: vec![
+                GradientStop::new(0.0, Color::BLACK),
+                GradientStop::new(1.0, Color::WHITE),
+            ],
+        };
+        r.fill_rect(
+            Rect::new(0.0, 0.0, 100.0, 10.0),
+            Paint::LinearGradient(gradient),
+        );
+        assert_color_eq(r.pixel(0, 5).unwrap(), Color::new(0.005, 0.005, 0.005, 1.0));
+        assert_color_eq(
+            r.pixel(49, 5).unwrap(),
+            Color::new(0.495, 0.495, 0.495, 1.0),
+        );
+
+        let radial = RadialGradient {
+            center: Point::new(50.0, 5.0),
+            radius: 10.0,
+            focus: None,
+            stops: vec![
+                GradientStop::new(0.0, Color::RED),
+                GradientStop::new(1.0, Color::BLUE),
+            ],
+        };
+        r.fill_rect(
+            Rect::new(0.0, 0.0, 100.0, 10.0),
+            Paint::RadialGradient(radial),
+        );
+        // The pixel center is sqrt(0.5) from the gradient center.
+        assert_color_eq(r.pixel(50, 5).unwrap(), Color::new(0.93, 0.0, 0.07, 1.0));
+        assert_eq!(r.pixel(80, 5), Some(Color::BLUE));
+    }
+
+    #[test]
+    fn test_clipping() {
+        let mut r = renderer(20.0, 20.0);
+        r.save();
+        r.clip_rect(Rect::new(0.0, 0.0, 10.0, 20.0));
+        r.clip_path(&Path::circle(Point::new(10.0, 10.0), 5.0));
+        assert!(r.has_stencil_clips());
+        r.fill_rect(Rect::new(0.0, 0.0, 20.0, 20.0), Color::GREEN);
+        r.restore_clip();
+        r.restore();
+        assert!(!r.has_stencil_clips());
+        assert_eq!(r.clip_bounds(), None);
+
+        assert_eq!(r.pixel(7, 10), Some(Color::GREEN));
+        assert_eq!(r.pixel(12, 10), Some(Color::WHITE));
+        assert_eq!(r.pixel(2, 2), Some(Color::WHITE));
+
+        r.clip_rounded_rect(RoundedRect::new(Rect::new(0.0, 0.0, 20.0, 20.0), 8.0));
+        r.fill_rect(Rect::new(0.0, 0.0, 20.0, 20.0), Color::BLACK);
+        assert_eq!(r.pixel(0, 0), Some(Color::WHITE));
+        assert_eq!(r.pixel(10, 10), Some(Color::BLACK));
+    }
+
+    #[test]
+    fn test_blend_modes_and_opacity() {
+        let mut r = renderer(4.0, 1.0);
+        r.set_opacity(0.5);
+        r.fill_rect(Rect::new(0.0, 0.0, 1.0, 1.0), Color::BLACK);
+        assert_color_eq(r.pixel(0, 0).unwrap(), Color::new(0.5, 0.5, 0.5, 1.0));
+
+        r.set_opacity(1.0);
+        r.set_blend_mode(BlendMode::Multiply);
+        r.fill_rect(
+            Rect::new(1.0, 0.0, 1.0, 1.0),
+            Color::from_rgb(1.0, 0.5, 0.0),
+        );
+        assert_color_eq(r.pixel(1, 0).unwrap(), Color::new(1.0, 0.5, 0.0, 1.0));
+
+        r.set_blend_mode(BlendMode::Difference);
+        r.fill_rect(
+            Rect::new(2.0, 0.0, 1.0, 1.0),
+            Color::from_rgb(1.0, 0.25, 0.0),
+        );
+        assert_color_eq(r.pixel(2, 0).unwrap(), Color::new(0.0, 0.75, 1.0, 1.0));
+
+        r.set_blend_mode(BlendMode::DestinationOut);
+        r.fill_rect(Rect::new(3.0, 0.0, 1.0, 1.0), Color::BLACK);
+        assert_eq!(r.pixel(3, 0), Some(Color::TRANSPARENT));
+        assert_eq!(r.end_frame().state_changes, 3);
+    }
+
+    #[test]
+    fn test_non_separable_blend_modes() {
+        let mut r = renderer(3.0, 1.0);
+        let gray = Color::from_rgb(0.5, 0.5, 0.5);
+        r.fill_rect(Rect::new(0.0, 0.0, 3.0, 1.0), gray);
+
+        r.set_blend_mode(BlendMode::Color);
+        r.fill_rect(
+            Rect::new(0.0, 0.0, 1.0, 1.0),
+            Color::from_rgb(1.0, 0.0, 0.0),
+        );
+        // Red at the gray's luminosity, clipped back into range.
+        let clipped = 0.5 - 0.3 * 0.5 / 0.7;
+        assert_color_eq(
+            r.pixel(0, 0).unwrap(),
+            Color::new(1.0, clipped, clipped, 1.0),
+        );
+
+        r.set_blend_mode(BlendMode::Luminosity);
+        r.fill_rect(Rect::new(1.0, 0.0, 1.0, 1.0), Color::BLACK);
+        assert_color_eq(r.pixel(1, 0).unwrap(), Color::new(0.0, 0.0, 0.0, 1.0));
+
+        // A hue can't show on an unsaturated backdrop.
+        r.set_blend_mode(BlendMode::Hue);
+        r.fill_rect(
+            Rect::new(2.0, 0.0, 1.0, 1.0),
+            Color::from_rgb(1.0, 0.0, 0.0),
+        );
+        assert_color_eq(r.pixel(2, 0).unwrap(), gray);
+    }
+
+    #[test]
+    fn test_box_shadows() {
+        let mut r = renderer(60.0, 60.0);
+        let rect = Rect::new(20.0, 20.0, 20.0, 20.0);
+        r.draw_box_shadow(rect, &BoxShadow::new(Color::BLACK).with_blur(8.0));
+        let center = r.pixel(30, 30).unwrap();
+        let edge = r.pixel(20, 30).unwrap();
+        let outside = r.pixel(12, 30).unwrap();
+        assert!(center.r < 0.05);
+        assert!((edge.r - 0.5).abs() < 0.1);
+        assert!(outside.r > 0.9 && outside.r < 1.0);
+
+        let mut r = renderer(60.0, 60.0);
+        r.draw_box_shadow(rect, &BoxShadow::new(Color::BLACK).with_blur(4.0).inset());
+        assert_eq!(r.pixel(10, 30), Some(Color::WHITE));
+        assert!(r.pixel(20, 30).unwrap().r < 0.6);
+        assert!(r.pixel(30, 30).unwrap().r > 0.95);
+    }
+
+    #[test]
+    fn test_images_and_nine_patches() {
+        // 2x2 image: red, green / blue, white.
+        let data = [
+            255, 0, 0, 255, 0, 255, 0, 255, //
+            0, 0, 255, 255, 255, 255, 255, 255,
+        ];
+        let image = Image::from_rgba(&data, 2, 2).unwrap();
+        assert!(!image.is_gpu_backed());
+        assert!(Image::from_rgba(&data[..4], 2, 2).is_err());
+
+        let mut r = renderer(8.0, 8.0);
+        r.draw_image(
+            &image,
+            Rect::new(0.0, 0.0, 8.0, 8.0),
+            ImageScaleMode::Stretch,
+        );
+        assert_eq!(r.pixel(0, 0), Some(Color::RED));
+        assert_eq!(r.pixel(7, 0), Some(Color::GREEN));
+        assert_eq!(r.pixel(0, 7), Some(Color::BLUE));
+
+        let mut r = renderer(8.0, 8.0);
+        r.draw_image(&image, Rect::new(0.0, 0.0, 8.0, 8.0), ImageScaleMode::Tile);
+        assert_eq!(r.pixel(2, 0), Some(Color::RED));
+        assert_eq!(r.pixel(7, 7), Some(Color::WHITE));
+
+        let mut r = renderer(8.0, 8.0);
+        r.draw_nine_patch(
+            &NinePatch::new(image.clone(), 1.0),
+            Rect::new(0.0, 0.0, 8.0, 8.0),
+        );
+        assert_eq!(r.pixel(0, 0), Some(Color::RED));
+        assert_eq!(r.pixel(7, 7), Some(Color::WHITE));
+        assert_eq!(r.pixel(7, 0), Some(Color::GREEN));
+    }
+
+    #[test]
+    fn test_image_buffer_output() {
+        let mut r = renderer(2.0, 1.0);
+        r.fill_rect(
+            Rect::new(0.0, 0.0, 1.0, 1.0),
+            Color::from_rgba(1.0, 0.0, 0.0, 0.5),
+        );
+        r.set_blend_mode(BlendMode::Source);
+        r.fill_rect(Rect::new(1.0, 0.0, 1.0, 1.0), Color::TRANSPARENT);
+
+        let image = r.to_image_buffer();
+        assert_eq!(image.as_rgba8_bytes(), vec![255, 128, 128, 255, 0, 0, 0, 0]);
+    }
+}
@@ -9,6 +9,7 @@ use tracing::{debug, info, trace, warn};
 use winit::window::Window;
 
 use crate::context::GraphicsContext;
+use crate::damage::Region;
 use crate::error::{RenderError, RenderResult};
 
 /// Configuration options for surface creation.
@@ -174,8 +175,14 @@ impl RenderSurface {
         // Get initial window size
         let size = window.inner_size();
 
+        // Backdrop blend modes copy the frame, and partial updates copy a
+        // retained frame into it, so allow both where supported
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | (capabilities.usages
+                & (wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST));
+
         let surface_config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format,
             width: size.width.max(1),
             height: size.height.max(1),
@@ -300,7 +307,11 @@ impl RenderSurface {
                 let view = texture
                     .texture
                     .create_view(&wgpu::TextureViewDescriptor::default());
-                Ok(Some(SurfaceFrame { texture, view }))
+                Ok(Some(SurfaceFrame {
+                    texture,
+                    view,
+                    damage_hint: None,
+                }))
             }
             Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
                 // Reconfigure and try again
@@ -313,7 +324,11 @@ impl RenderSurface {
                 let view = texture
                     .texture
                     .create_view(&wgpu::TextureViewDescriptor::default());
-                Ok(Some(SurfaceFrame { texture, view }))
+                Ok(Some(SurfaceFrame {
+                    texture,
+                    view,
+                    damage_hint: None,
+                }))
             }
             Err(wgpu::SurfaceError::Timeout) => {
                 warn!(
@@ -356,6 +371,15 @@ impl RenderSurface {
         self.config.alpha_mode
     }
 
+    /// Check if frames can be repainted only where damaged.
+    ///
+    /// The renderer then repaints the damaged parts of a retained copy of the
+    /// last frame and copies it to the surface. This saves drawing, not
+    /// presentation: the whole frame is still presented.
+    pub fn supports_damage_repaint(&self) -> bool {
+        self.config.usage.contains(wgpu::TextureUsages::COPY_DST)
+    }
+
     /// Check if the surface has been configured.
     pub fn is_configured(&self) -> bool {
         self.is_configured
@@ -414,6 +438,8 @@ pub struct SurfaceFrame {
     texture: wgpu::SurfaceTexture,
     /// A view of the texture for use in render passes.
     pub view: wgpu::TextureView,
+    /// A hint of the parts of the frame that changed since the last frame.
+    damage_hint: Option<Region>,
 }
 
 impl SurfaceFrame {
@@ -421,6 +447,9 @@ impl SurfaceFrame {
     ///
     /// This consumes the frame. If you don't call this explicitly,
     /// the frame will be presented when dropped.
+    ///
+    /// The whole frame is presented; wgpu has no way to pass the
+    /// [damage hint](Self::damage_hint) on to the compositor.
     pub fn present(self) {
         self.texture.present();
     }
@@ -429,6 +458,23 @@ impl SurfaceFrame {
     pub fn texture(&self) -> &wgpu::SurfaceTexture {
         &self.texture
     }
+
+    /// Record which parts of the frame changed since the last frame.
+    ///
+    /// This is only a hint and doesn't limit what is presented; `None` means
+    /// the whole frame changed.
+    pub fn set_damage_hint(&mut self, damage: Option<Region>) {
+        self.damage_hint = damage;
+    }
+
+    /// Get the hint of which parts of the frame changed since the last frame.
+    ///
+    /// Returns `None` if the whole frame changed. [`present`](Self::present)
+    /// ignores it, but platform integrations that present frames themselves
+    /// can pass it on to the compositor.
+    pub fn damage_hint(&self) -> Option<&Region> {
+        self.damage_hint.as_ref()
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,1300 @@
+//! SVG renderer implementation.
+//!
+//! This module provides the [`SvgRenderer`], which implements the [`Renderer`]
+//! trait by writing an SVG document. The document's size is the viewport size
+//! of the frame, in user units.
+//!
+//! Rects, ellipses, lines and paths are written as the matching SVG elements,
+//! gradients as `<linearGradient>` and `<radialGradient>` definitions, clips
+//! as `<clipPath>` elements and images as embedded PNG data. Text is written
+//! as `<text>` elements or as glyph outlines, see [`SvgTextMode`]. Box shadows
+//! have no direct SVG equivalent and are rasterized with a
+//! [`SoftwareRenderer`].
+//!
+//! # Example
+//!
+//! ```
+//! use horizon_lattice_render::{Color, Rect, Renderer, Size, SvgRenderer};
+//!
+//! let mut renderer = SvgRenderer::new().with_title("Chart");
+//! renderer.begin_frame(Color::WHITE, Size::new(200.0, 100.0));
+//! renderer.fill_rect(Rect::new(10.0, 10.0, 50.0, 80.0), Color::RED);
+//! renderer.end_frame();
+//!
+//! let svg = renderer.finish().unwrap();
+//! assert!(svg.contains(r##"<rect x="10" y="10" width="50" height="80" fill="#ff0000"/>"##));
+//! ```
+
+use std::collections::HashMap;
+
+use base64::Engine;
+use lyon::path::PathEvent;
+
+use crate::error::{RenderError, RenderResult};
+use crate::image::{Image, ImageScaleMode, NinePatch};
+use crate::image_buffer::ImageBuffer;
+use crate::paint::{
+    BlendMode, BoxShadow, DashPattern, FillRule, GradientStop, LineCap, LineJoin, Paint, Stroke,
+};
+use crate::path::to_lyon_path;
+use crate::renderer::{FrameStats, RenderStateStack, Renderer};
+use crate::software_renderer::{SoftwareRenderer, scaled_dest};
+use crate::text::{FontFaceId, FontStyle, FontSystem, TextLayout};
+use crate::transform::Transform2D;
+use crate::types::{Color, Path, Point, Rect, RoundedRect, Size};
+
+/// Pixels per unit at which box shadows are rasterized.
+const SHADOW_SCALE: f32 = 2.0;
+
+/// How an [`SvgRenderer`] writes text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SvgTextMode {
+    /// Write `<text>` elements naming the font family, with every character
+    /// placed where it was shaped. The text stays selectable and searchable,
+    /// but looks right only where the fonts are installed.
+    #[default]
+    Text,
+    /// Write the outline of every glyph as a path, so the document looks the
+    /// same everywhere at the cost of size and searchability.
+    Outlines,
+}
+
+/// A renderer that writes its frame as an SVG document.
+///
+/// Call [`begin_frame`](Renderer::begin_frame) and
+/// [`end_frame`](Renderer::end_frame), then [`finish`](Self::finish) or
+/// [`save`](Self::save) to produce the document. Each frame replaces the
+/// previous one. The clear color passed to `begin_frame` fills the document
+/// unless it is transparent.
+///
+/// Blend modes that SVG can't express (the Porter-Duff modes and
+/// [`BlendMode::Add`]) are drawn as [`BlendMode::Normal`]. Text is drawn with
+/// [`draw_text`](Self::draw_text), which needs the [`FontSystem`] the layout
+/// was shaped with.
+pub struct SvgRenderer {
+    title: Option<String>,
+    text_mode: SvgTextMode,
+    size: Option<Size>,
+    /// Clip paths, gradients and glyph outlines.
+    defs: String,
+    body: String,
+    state: RenderStateStack,
+    /// Rounded-rect and path clips, in document coordinates, with a serial
+    /// number that identifies them in `clips`.
+    stencil_clips: Vec<(usize, Path)>,
+    next_stencil_clip: usize,
+    /// The clip path defined for a clip shape inside a parent clip path.
+    clips: HashMap<(Option<usize>, ClipShape), usize>,
+    gradient_count: usize,
+    images: Vec<Image>,
+    /// The glyph outlines defined so far, with the units per em of their
+    /// font, or `None` for glyphs without an outline.
+    glyphs: HashMap<(FontFaceId, u16), Option<(usize, f32)>>,
+    glyph_count: usize,
+    stats: FrameStats,
+}
+
+/// The shape of a clip path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ClipShape {
+    /// The clip rect, by the bits of its coordinates.
+    Rect([u32; 4]),
+    /// A stencil clip, by serial number.
+    Path(usize),
+}
+
+impl Default for SvgRenderer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SvgRenderer {
+    /// Create a renderer for an empty document.
+    pub fn new() -> Self {
+        Self {
+            title: None,
+            text_mode: SvgTextMode::default(),
+            size: None,
+            defs: String::new(),
+            body: String::new(),
+            state: RenderStateStack::new(),
+            stencil_clips: Vec::new(),
+            next_stencil_clip: 0,
+            clips: HashMap::new(),
+            gradient_count: 0,
+            images: Vec::new(),
+            glyphs: HashMap::new(),
+            glyph_count: 0,
+            stats: FrameStats::default(),
+        }
+    }
+
+    /// Set the document title, written as its `<title>` element.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set how text is written.
+    pub fn with_text_mode(mut self, text_mode: SvgTextMode) -> Self {
+        self.text_mode = text_mode;
+        self
+    }
+
+    /// Get how text is written.
+    pub fn text_mode(&self) -> SvgTextMode {
+        self.text_mode
+    }
+
+    /// Finish the document and return its text.
+    ///
+    /// A frame that was begun but not ended is included.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no frame was begun or an image can't be encoded.
+    pub fn finish(self) -> RenderResult<String> {
+        let Some(size) = self.size else {
+            return Err(RenderError::Svg("no frame was drawn".into()));
+        };
+
+        let mut svg = format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" version="1.1" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = size.width,
+            h = size.height,
+        );
+        svg.push('\n');
+        if let Some(title) = &self.title {
+            svg.push_str(&format!("<title>{}</title>\n", escape(title)));
+        }
+        if !self.defs.is_empty() || !self.images.is_empty() {
+            svg.push_str("<defs>\n");
+            svg.push_str(&self.defs);
+            for (index, image) in self.images.iter().enumerate() {
+                let png = ImageBuffer::from_rgba(
+                    image.pixels().unwrap_or_default(),
+                    image.width(),
+                    image.height(),
+                )?
+                .to_png()?;
+                svg.push_str(&format!(
+                    r#"<image id="img{index}" width="{}" height="{}" preserveAspectRatio="none" xlink:href="data:image/png;base64,{}"/>"#,
+                    image.width(),
+                    image.height(),
+                    base64::engine::general_purpose::STANDARD.encode(png),
+                ));
+                svg.push('\n');
+            }
+            svg.push_str("</defs>\n");
+        }
+        svg.push_str(&self.body);
+        svg.push_str("</svg>\n");
+        Ok(svg)
+    }
+
+    /// Finish the document and write it to a file.
+    pub fn save(self, path: impl AsRef<std::path::Path>) -> RenderResult<()> {
+        let svg = self.finish()?;
+        std::fs::write(path, svg).map_err(|e| RenderError::Svg(e.to_string()))
+    }
+
+    /// Draw a text layout with its top-left corner at `position`.
+    ///
+    /// Glyphs use `color` unless the layout assigns them their own. The fonts
+    /// are looked up in `font_system`, for their family names or their glyph
+    /// outlines depending on the [`SvgTextMode`].
+    pub fn draw_text(
+        &mut self,
+        font_system: &FontSystem,
+        layout: &TextLayout,
+        position: Point,
+        color: Color,
+    ) {
+        if !self.begin_draw() {
+            return;
+        }
+        match self.text_mode {
+            SvgTextMode::Text => self.write_text(font_system, layout, position, color),
+            SvgTextMode::Outlines => self.write_outlines(font_system, layout, position, color),
+        }
+        self.end_draw();
+    }
+
+    /// Write a `<text>` element for each run of glyphs sharing a font, size
+    /// and color.
+    fn write_text(
+        &mut self,
+        font_system: &FontSystem,
+        layout: &TextLayout,
+        position: Point,
+        color: Color,
+    ) {
+        let attrs = self.element_attrs(self.state.transform());
+        let text = layout.text();
+        for line in layout.lines() {
+            let glyphs: Vec<_> = line
+                .glyphs
+                .iter()
+                .filter(|glyph| !glyph.is_inline_element())
+                .collect();
+            for run in glyphs.chunk_by(|a, b| {
+                a.font_id == b.font_id && a.font_size == b.font_size && a.color == b.color
+            }) {
+                let glyph_color = run[0]
+                    .color
+                    .map_or(color, |[r, g, b, a]| Color::from_rgba8(r, g, b, a));
+                let Some(fill) = color_attrs("fill", glyph_color) else {
+                    continue;
+                };
+
+                let mut content = String::new();
+                let mut xs = Vec::new();
+                let mut ys = Vec::new();
+                let mut last_cluster = None;
+                for glyph in run {
+                    // Glyphs of one cluster are placed with its first glyph.
+                    if last_cluster.as_ref() == Some(&glyph.cluster) {
+                        continue;
+                    }
+                    last_cluster = Some(glyph.cluster.clone());
+                    let chars: Vec<char> = text
+                        .get(glyph.cluster.clone())
+                        .unwrap_or("")
+                        .chars()
+                        .filter(|c| !c.is_control())
+                        .collect();
+                    let x = position.x + glyph.x + glyph.x_offset;
+                    let y = position.y + line.baseline_y + glyph.y + glyph.y_offset;
+                    // Ligatures share their advance between their characters.
+                    for (i, &c) in chars.iter().enumerate() {
+                        xs.push(x + glyph.width * i as f32 / chars.len() as f32);
+                        ys.push(y);
+                        content.push(c);
+                    }
+                }
+                if content.trim().is_empty() {
+                    continue;
+                }
+
+                let mut font = format!(r#" font-size="{}""#, run[0].font_size);
+                if let Some(info) = font_system.face_info(run[0].font_id) {
+                    let family = info
+                        .families
+                        .first()
+                        .cloned()
+                        .unwrap_or(info.post_script_name);
+                    font.push_str(&format!(
+                        r#" font-family="'{}'""#,
+                        escape(&family.replace('\'', ""))
+                    ));
+                    if info.weight.0 != 400 {
+                        font.push_str(&format!(r#" font-weight="{}""#, info.weight.0));
+                    }
+                    match info.style {
+                        FontStyle::Normal => {}
+                        FontStyle::Italic => font.push_str(r#" font-style="italic""#),
+                        FontStyle::Oblique => font.push_str(r#" font-style="oblique""#),
+                    }
+                }
+                self.body.push_str(&format!(
+                    r#"<text xml:space="preserve" x="{}" y="{}"{font}{fill}{attrs}>{}</text>"#,
+                    number_list(&xs),
+                    number_list(&ys),
+                    escape(&content),
+                ));
+                self.body.push('\n');
+            }
+        }
+    }
+
+    /// Draw every glyph as a use of its outline.
+    fn write_outlines(
+        &mut self,
+        font_system: &FontSystem,
+        layout: &TextLayout,
+        position: Point,
+        color: Color,
+    ) {
+        let transform = *self.state.transform();
+        for line in layout.lines() {
+            for glyph in &line.glyphs {
+                if glyph.is_inline_element() {
+                    continue;
+                }
+                let Some((index, units_per_em)) =
+                    self.glyph_index(font_system, glyph.font_id, glyph.glyph_id)
+                else {
+                    continue;
+                };
+                let glyph_color = glyph
+                    .color
+                    .map_or(color, |[r, g, b, a]| Color::from_rgba8(r, g, b, a));
+                let Some(fill) = color_attrs("fill", glyph_color) else {
+                    continue;
+                };
+                // Outlines are in font units with y pointing up.
+                let scale = glyph.font_size / units_per_em;
+                let glyph_transform = transform
+                    .translated(
+                        position.x + glyph.x + glyph.x_offset,
+                        position.y + line.baseline_y + glyph.y + glyph.y_offset,
+                    )
+                    .scaled_xy(scale, -scale);
+                let attrs = self.element_attrs(&glyph_transform);
+                self.body.push_str(&format!(
+                    r##"<use xlink:href="#glyph{index}"{fill}{attrs}/>"##
+                ));
+                self.body.push('\n');
+            }
+        }
+    }
+
+    /// Get the index of a glyph's outline, defining it on first use.
+    fn glyph_index(
+        &mut self,
+        font_system: &FontSystem,
+        face_id: FontFaceId,
+        glyph_id: u16,
+    ) -> Option<(usize, f32)> {
+        if let Some(&entry) = self.glyphs.get(&(face_id, glyph_id)) {
+            return entry;
+        }
+        let outline = font_system
+            .with_face_data(face_id, |data, index| {
+                let face = ttf_parser::Face::parse(data, index).ok()?;
+                let mut builder = OutlineBuilder(String::new());
+                face.outline_glyph(ttf_parser::GlyphId(glyph_id), &mut builder)?;
+                Some((builder.0, f32::from(face.units_per_em())))
+            })
+            .flatten();
+        let entry = outline.map(|(data, units_per_em)| {
+            let index = self.glyph_count;
+            self.glyph_count += 1;
+            self.defs.push_str(&format!(
+                r#"<path id="glyph{index}" d="{}"/>"#,
+                data.trim_end()
+            ));
+            self.defs.push('\n');
+            (index, units_per_em)
+        });
+        self.glyphs.insert((face_id, glyph_id), entry);
+        entry
+    }
+
+    // ========================================================================
+    // Drawing
+    // ========================================================================
+
+    /// Open a group for the clips, if there are any.
+    ///
+    /// Returns `false` if there is no frame or nothing can be drawn.
+    fn begin_draw(&mut self) -> bool {
+        if self.size.is_none() {
+            return false;
+        }
+        let state = self.state.current();
+        if state.opacity <= 0.0 || state.clip.is_some_and(|clip| clip.is_empty()) {
+            return false;
+        }
+        // Clips are in document coordinates, so they apply to a group outside
+        // the element's transform.
+        self.body.push_str("<g");
+        if let Some(clip) = self.clip_index() {
+            self.body
+                .push_str(&format!(r#" clip-path="url(#clip{clip})""#));
+        }
+        self.body.push('>');
+        self.stats.draw_calls += 1;
+        true
+    }
+
+    fn end_draw(&mut self) {
+        if self.body.ends_with("<g>") {
+            self.body.truncate(self.body.len() - "<g>".len());
+        } else {
+            self.body.push_str("</g>\n");
+        }
+    }
+
+    /// Get the clip path intersecting the clip rect and the stencil clips,
+    /// defining the clip paths on first use.
+    fn clip_index(&mut self) -> Option<usize> {
+        let mut parent = None;
+        if let Some(clip) = self.state.current().clip {
+            let key = (
+                parent,
+                ClipShape::Rect([
+                    clip.left().to_bits(),
+                    clip.top().to_bits(),
+                    clip.width().to_bits(),
+                    clip.height().to_bits(),
+                ]),
+            );
+            parent = Some(self.define_clip(key, || {
+                format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}"/>"#,
+                    clip.left(),
+                    clip.top(),
+                    clip.width(),
+                    clip.height()
+                )
+            }));
+        }
+        for i in 0..self.stencil_clips.len() {
+            let (serial, path) = &self.stencil_clips[i];
+            let key = (parent, ClipShape::Path(*serial));
+            let shape = format!(r#"<path d="{}"/>"#, path_data(path));
+            parent = Some(self.define_clip(key, || shape));
+        }
+        parent
+    }
+
+    fn define_clip(
+        &mut self,
+        key: (Option<usize>, ClipShape),
+        shape: impl FnOnce() -> String,
+    ) -> usize {
+        if let Some(&index) = self.clips.get(&key) {
+            return index;
+        }
+        let index = self.clips.len();
+        self.defs
+            .push_str(&format!(r#"<clipPath id="clip{index}""#));
+        if let Some(parent) = key.0 {
+            self.defs
+                .push_str(&format!(r#" clip-path="url(#clip{parent})""#));
+        }
+        self.defs.push_str(&format!(">{}</clipPath>\n", shape()));
+        self.clips.insert(key, index);
+        index
+    }
+
+    /// Get the transform, opacity and blend mode attributes of an element.
+    fn element_attrs(&self, transform: &Transform2D) -> String {
+        let state = self.state.current();
+        let mut attrs = String::new();
+        if !transform.is_identity() {
+            attrs.push_str(&format!(
+                r#" transform="matrix({})""#,
+                number_list(transform.as_array())
+            ));
+        }
+        if state.opacity < 1.0 {
+            attrs.push_str(&format!(r#" opacity="{}""#, state.opacity));
+        }
+        if let Some(mode) = css_blend_mode(state.blend_mode) {
+            attrs.push_str(&format!(r#" style="mix-blend-mode:{mode}""#));
+        }
+        attrs
+    }
+
+    /// Get the fill or stroke attributes for a paint, defining gradients.
+    ///
+    /// Returns `None` if the paint is fully transparent.
+    fn paint_attrs(&mut self, paint: &Paint, property: &str) -> Option<String> {
+        let (id, stops) = match paint {
+            Paint::Solid(color) => return color_attrs(property, *color),
+            Paint::LinearGradient(gradient) => {
+                if gradient.stops.len() < 2 {
+                    return color_attrs(property, gradient.stops.first()?.color);
+                }
+                let id = self.gradient_count;
+                self.defs.push_str(&format!(
+                    r#"<linearGradient id="grad{id}" gradientUnits="userSpaceOnUse" x1="{}" y1="{}" x2="{}" y2="{}">"#,
+                    gradient.start.x, gradient.start.y, gradient.end.x, gradient.end.y
+                ));
+                (id, &gradient.stops)
+            }
+            Paint::RadialGradient(gradient) => {
+                if gradient.stops.len() < 2 || gradient.radius <= 0.0 {
+                    return color_attrs(property, gradient.stops.last()?.color);
+                }
+                let id = self.gradient_count;
+                self.defs.push_str(&format!(
+                    r#"<radialGradient id="grad{id}" gradientUnits="userSpaceOnUse" cx="{}" cy="{}" r="{}""#,
+                    gradient.center.x, gradient.center.y, gradient.radius
+                ));
+                if let Some(focus) = gradient.focus {
+                    self.defs
+                        .push_str(&format!(r#" fx="{}" fy="{}""#, focus.x, focus.y));
+                }
+                self.defs.push('>');
+                (id, &gradient.stops)
+            }
+        };
+        self.gradient_count += 1;
+        write_stops(&mut self.defs, stops);
+        self.defs.push_str(match paint {
+            Paint::LinearGradient(_) => "</linearGradient>\n",
+            _ => "</radialGradient>\n",
+        });
+        Some(format!(r#" {property}="url(#grad{id})""#))
+    }
+
+    /// Write a filled shape, given as an element name and its geometry.
+    fn fill_shape(&mut self, shape: &str, paint: &Paint, fill_rule: FillRule) {
+        if !self.begin_draw() {
+            return;
+        }
+        if let Some(fill) = self.paint_attrs(paint, "fill") {
+            let rule = match fill_rule {
+                FillRule::NonZero => "",
+                FillRule::EvenOdd => r#" fill-rule="evenodd""#,
+            };
+            let attrs = self.element_attrs(self.state.transform());
+            self.body
+                .push_str(&format!("<{shape}{fill}{rule}{attrs}/>\n"));
+        }
+        self.end_draw();
+    }
+
+    /// Write a stroked shape, given as an element name and its geometry.
+    fn stroke_shape(&mut self, shape: &str, stroke: &Stroke) {
+        if stroke.width <= 0.0 || !self.begin_draw() {
+            return;
+        }
+        if let Some(paint) = self.paint_attrs(&stroke.paint, "stroke") {
+            let style = stroke_attrs(stroke);
+            let attrs = self.element_attrs(self.state.transform());
+            self.body
+                .push_str(&format!(r#"<{shape} fill="none"{paint}{style}{attrs}/>"#));
+            self.body.push('\n');
+        }
+        self.end_draw();
+    }
+
+    /// Get the index of the image definition for an image.
+    fn image_index(&mut self, image: &Image) -> usize {
+        if let Some(index) = self
+            .images
+            .iter()
+            .position(|known| known.same_pixels(image))
+        {
+            return index;
+        }
+        self.images.push(image.clone());
+        self.images.len() - 1
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn begin_frame(&mut self, clear_color: Color, viewport_size: Size) {
+        *self = Self {
+            title: self.title.take(),
+            text_mode: self.text_mode,
+            size: Some(viewport_size),
+            ..Self::new()
+        };
+
+        if clear_color.a > 0.0 {
+            self.fill_rect(
+                Rect::new(0.0, 0.0, viewport_size.width, viewport_size.height),
+                clear_color,
+            );
+        }
+    }
+
+    fn end_frame(&mut self) -> FrameStats {
+        self.stats.clone()
+    }
+
+    fn save(&mut self) {
+        self.state.save();
+    }
+
+    fn restore(&mut self) {
+        self.state.restore();
+    }
+
+    fn reset(&mut self) {
+        self.state.reset();
+    }
+
+    fn transform(&self) -> &Transform2D {
+        self.state.transform()
+    }
+
+    fn set_transform(&mut self, transform: Transform2D) {
+        self.state.set_transform(transform);
+    }
+
+    fn concat_transform(&mut self, transform: &Transform2D) {
+        self.state.concat_transform(transform);
+    }
+
+    fn translate(&mut self, tx: f32, ty: f32) {
+        self.state.translate(tx, ty);
+    }
+
+    fn scale(&mut self, sx: f32, sy: f32) {
+        self.state.scale(sx, sy);
+    }
+
+    fn rotate(&mut self, angle: f32) {
+        self.state.rotate(angle);
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        self.state.clip_rect(rect);
+    }
+
+    fn clip_rounded_rect(&mut self, rrect: RoundedRect) {
+        if rrect.radii.is_zero() {
+            self.clip_rect(rrect.rect);
+            return;
+        }
+        self.clip_path(&Path::rounded_rect(rrect.rect, rrect.radii));
+    }
+
+    fn restore_clip(&mut self) {
+        self.stencil_clips.pop();
+    }
+
+    fn clip_bounds(&self) -> Option<Rect> {
+        self.state.clip_bounds()
+    }
+
+    fn has_stencil_clips(&self) -> bool {
+        !self.stencil_clips.is_empty()
+    }
+
+    fn clip_path(&mut self, path: &Path) {
+        if path.is_empty() {
+            return;
+        }
+        self.stencil_clips.push((
+            self.next_stencil_clip,
+            path.transformed(self.state.transform()),
+        ));
+        self.next_stencil_clip += 1;
+    }
+
+    fn fill_rect(&mut self, rect: Rect, paint: impl Into<Paint>) {
+        self.fill_shape(&rect_element(rect), &paint.into(), FillRule::NonZero);
+    }
+
+    fn fill_rounded_rect(&mut self, rrect: RoundedRect, paint: impl Into<Paint>) {
+        match rounded_rect_element(rrect) {
+            Some(shape) => self.fill_shape(&shape, &paint.into(), FillRule::NonZero),
+            None => self.fill_path(
+                &Path::rounded_rect(rrect.rect, rrect.radii),
+                paint,
+                FillRule::NonZero,
+            ),
+        }
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, stroke: &Stroke) {
+        self.stroke_shape(&rect_element(rect), stroke);
+    }
+
+    fn stroke_rounded_rect(&mut self, rrect: RoundedRect, stroke: &Stroke) {
+        match rounded_rect_element(rrect) {
+            Some(shape) => self.stroke_shape(&shape, stroke),
+            None => self.stroke_path(&Path::rounded_rect(rrect.rect, rrect.radii), stroke),
+        }
+    }
+
+    fn draw_box_shadow(&mut self, rect: Rect, shadow: &BoxShadow) {
+        self.draw_box_shadow_rounded(RoundedRect::new(rect, 0.0), shadow);
+    }
+
+    fn draw_box_shadow_rounded(&mut self, rrect: RoundedRect, shadow: &BoxShadow) {
+        let bounds = shadow.expanded_bounds(rrect.rect);
+        let width = (bounds.width() * SHADOW_SCALE).ceil();
+        let height = (bounds.height() * SHADOW_SCALE).ceil();
+        if width < 1.0 || height < 1.0 || self.size.is_none() {
+            return;
+        }
+
+        let mut raster = SoftwareRenderer::new();
+        raster.begin_frame(Color::TRANSPARENT, Size::new(width, height));
+        raster.scale(SHADOW_SCALE, SHADOW_SCALE);
+        raster.translate(-bounds.left(), -bounds.top());
+        raster.draw_box_shadow_rounded(rrect, shadow);
+        raster.end_frame();
+        let Ok(image) = Image::from_rgba(
+            &raster.to_image_buffer().as_rgba8_bytes(),
+            raster.width(),
+            raster.height(),
+        ) else {
+            return;
+        };
+        let dest = Rect::new(
+            bounds.left(),
+            bounds.top(),
+            width / SHADOW_SCALE,
+            height / SHADOW_SCALE,
+        );
+        self.draw_image_rect(&image, Rect::new(0.0, 0.0, width, height), dest);
+    }
+
+    fn draw_line(&mut self, from: Point, to: Point, stroke: &Stroke) {
+        self.stroke_shape(
+            &format!(
+                r#"line x1="{}" y1="{}" x2="{}" y2="{}""#,
+                from.x, from.y, to.x, to.y
+            ),
+            stroke,
+        );
+    }
+
+    fn draw_polyline(&mut self, points: &[Point], stroke: &Stroke) {
+        if points.is_empty() {
+            return;
+        }
+        let points: Vec<String> = points
+            .iter()
+            .map(|point| format!("{},{}", point.x, point.y))
+            .collect();
+        self.stroke_shape(
+            &format!(r#"polyline points="{}""#, points.join(" ")),
+            stroke,
+        );
+    }
+
+    fn fill_ellipse(
+        &mut self,
+        center: Point,
+        radius_x: f32,
+        radius_y: f32,
+        paint: impl Into<Paint>,
+    ) {
+        self.fill_shape(
+            &ellipse_element(center, radius_x, radius_y),
+            &paint.into(),
+            FillRule::NonZero,
+        );
+    }
+
+    fn stroke_ellipse(&mut self, center: Point, radius_x: f32, radius_y: f32, stroke: &Stroke) {
+        self.stroke_shape(&ellipse_element(center, radius_x, radius_y), stroke);
+    }
+
+    fn fill_path(&mut self, path: &Path, paint: impl Into<Paint>, fill_rule: FillRule) {
+        if path.is_empty() {
+            return;
+        }
+        self.fill_shape(
+            &format!(r#"path d="{}""#, path_data(path)),
+            &paint.into(),
+            fill_rule,
+        );
+    }
+
+    fn stroke_path(&mut self, path: &Path, stroke: &Stroke) {
+        if path.is_empty() {
+            return;
+        }
+        self.stroke_shape(&format!(r#"path d="{}""#, path_data(path)), stroke);
+    }
+
+    fn draw_image(&mut self, image: &Image, dest: Rect, scale_mode: ImageScaleMode) {
+        let src = Rect::new(0.0, 0.0, image.width() as f32, image.height() as f32);
+        if scale_mode != ImageScaleMode::Tile {
+            let dest = scaled_dest(image.size(), dest, scale_mode);
+            self.draw_image_rect(image, src, dest);
+            return;
+        }
+
+        if image.width() == 0 || image.height() == 0 {
+            return;
+        }
+        self.save();
+        self.clip_rect(dest);
+        let mut y = dest.top();
+        while y < dest.bottom() {
+            let mut x = dest.left();
+            while x < dest.right() {
+                self.draw_image_rect(image, src, Rect::new(x, y, src.width(), src.height()));
+                x += src.width();
+            }
+            y += src.height();
+        }
+        self.restore();
+    }
+
+    fn draw_image_rect(&mut self, image: &Image, src: Rect, dest: Rect) {
+        if image.width() == 0
+            || image.height() == 0
+            || src.width() <= 0.0
+            || src.height() <= 0.0
+            || dest.width() <= 0.0
+            || dest.height() <= 0.0
+            || self.size.is_none()
+            || image.pixels().is_none()
+        {
+            return;
+        }
+        // Place the whole image so that `src` lands on `dest`, clipped to
+        // `dest` unless `src` is the whole image.
+        let whole = src.left() <= 0.0
+            && src.top() <= 0.0
+            && src.right() >= image.width() as f32
+            && src.bottom() >= image.height() as f32;
+        if !whole {
+            self.clip_path(&Path::rect(dest));
+        }
+        let index = self.image_index(image);
+        if self.begin_draw() {
+            let scale_x = dest.width() / src.width();
+            let scale_y = dest.height() / src.height();
+            let transform = self
+                .state
+                .transform()
+                .translated(
+                    dest.left() - src.left() * scale_x,
+                    dest.top() - src.top() * scale_y,
+                )
+                .scaled_xy(scale_x, scale_y);
+            let attrs = self.element_attrs(&transform);
+            self.body
+                .push_str(&format!(r##"<use xlink:href="#img{index}"{attrs}/>"##));
+            self.body.push('\n');
+            self.end_draw();
+        }
+        if !whole {
+            self.restore_clip();
+        }
+    }
+
+    fn draw_nine_patch(&mut self, nine_patch: &NinePatch, dest: Rect) {
+        for (src, dest) in nine_patch.calculate_patches(dest) {
+            self.draw_image_rect(&nine_patch.image, src, dest);
+        }
+    }
+
+    fn set_blend_mode(&mut self, mode: BlendMode) {
+        if self.state.current().blend_mode != mode {
+            self.state.current_mut().blend_mode = mode;
+            self.stats.state_changes += 1;
+        }
+    }
+
+    fn blend_mode(&self) -> BlendMode {
+        self.state.current().blend_mode
+    }
+
+    fn set_opacity(&mut self, opacity: f32) {
+        self.state.current_mut().opacity = opacity.clamp(0.0, 1.0);
+    }
+
+    fn opacity(&self) -> f32 {
+        self.state.current().opacity
+    }
+}
+
+impl std::fmt::Debug for SvgRenderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SvgRenderer")
+            .field("title", &self.title)
+            .field("text_mode", &self.text_mode)
+            .field("size", &self.size)
+            .field("images", &self.images.len())
+            .field("glyphs", &self.glyph_count)
+            .finish_non_exhaustive()
+    }
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+/// Collects a glyph outline as SVG path data.
+struct OutlineBuilder(String);
+
+impl ttf_parser::OutlineBuilder for OutlineBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.0.push_str(&format!("M{x} {y} "));
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        self.0.push_str(&format!("L{x} {y} "));
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        self.0.push_str(&format!("Q{x1} {y1} {x} {y} "));
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        self.0.push_str(&format!("C{x1} {y1} {x2} {y2} {x} {y} "));
+    }
+
+    fn close(&mut self) {
+        self.0.push_str("Z ");
+    }
+}
+
+/// Get the path data of a path, converting arcs to cubic curves.
+fn path_data(path: &Path) -> String {
+    let mut data = String::new();
+    for event in to_lyon_path(path).iter() {
+        match event {
+            PathEvent::Begin { at } => data.push_str(&format!("M{} {} ", at.x, at.y)),
+            PathEvent::Line { to, .. } => data.push_str(&format!("L{} {} ", to.x, to.y)),
+            PathEvent::Quadratic { ctrl, to, .. } => {
+                data.push_str(&format!("Q{} {} {} {} ", ctrl.x, ctrl.y, to.x, to.y));
+            }
+            PathEvent::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => data.push_str(&format!(
+                "C{} {} {} {} {} {} ",
+                ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y
+            )),
+            PathEvent::End { close, .. } => {
+                if close {
+                    data.push_str("Z ");
+                }
+            }
+        }
+    }
+    data.truncate(data.trim_end().len());
+    data
+}
+
+fn rect_element(rect: Rect) -> String {
+    format!(
+        r#"rect x="{}" y="{}" width="{}" height="{}""#,
+        rect.left(),
+        rect.top(),
+        rect.width(),
+        rect.height()
+    )
+}
+
+/// Get a `<rect>` for a rounded rect, if all its corners are the same.
+fn rounded_rect_element(rrect: RoundedRect) -> Option<String> {
+    let radii = rrect.radii;
+    let radius = radii.top_left;
+    if radii.top_right != radius || radii.bottom_right != radius || radii.bottom_left != radius {
+        return None;
+    }
+    let mut shape = rect_element(rrect.rect);
+    if radius > 0.0 {
+        shape.push_str(&format!(r#" rx="{radius}""#));
+    }
+    Some(shape)
+}
+
+fn ellipse_element(center: Point, radius_x: f32, radius_y: f32) -> String {
+    format!(
+        r#"ellipse cx="{}" cy="{}" rx="{radius_x}" ry="{radius_y}""#,
+        center.x, center.y
+    )
+}
+
+/// Get the attributes for a solid color, or `None` if it's transparent.
+fn color_attrs(property: &str, color: Color) -> Option<String> {
+    if color.a <= 0.0 {
+        return None;
+    }
+    let mut attrs = format!(r#" {property}="{}""#, hex_color(color));
+    if color.a < 1.0 {
+        attrs.push_str(&format!(r#" {property}-opacity="{}""#, color.a));
+    }
+    Some(attrs)
+}
+
+fn hex_color(color: Color) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        channel(color.r),
+        channel(color.g),
+        channel(color.b)
+    )
+}
+
+fn write_stops(defs: &mut String, stops: &[GradientStop]) {
+    for stop in stops {
+        defs.push_str(&format!(
+            r#"<stop offset="{}" stop-color="{}""#,
+            stop.offset.clamp(0.0, 1.0),
+            hex_color(stop.color)
+        ));
+        if stop.color.a < 1.0 {
+            defs.push_str(&format!(r#" stop-opacity="{}""#, stop.color.a));
+        }
+        defs.push_str("/>");
+    }
+}
+
+/// Get the stroke attributes other than the paint, leaving out defaults.
+fn stroke_attrs(stroke: &Stroke) -> String {
+    let mut attrs = format!(r#" stroke-width="{}""#, stroke.width);
+    match stroke.cap {
+        LineCap::Butt => {}
+        LineCap::Round => attrs.push_str(r#" stroke-linecap="round""#),
+        LineCap::Square => attrs.push_str(r#" stroke-linecap="square""#),
+    }
+    match stroke.join {
+        LineJoin::Miter => {}
+        LineJoin::Round => attrs.push_str(r#" stroke-linejoin="round""#),
+        LineJoin::Bevel => attrs.push_str(r#" stroke-linejoin="bevel""#),
+    }
+    if stroke.miter_limit != 4.0 {
+        attrs.push_str(&format!(r#" stroke-miterlimit="{}""#, stroke.miter_limit));
+    }
+    if let Some(DashPattern { pattern, offset }) = &stroke.dash_pattern
+        && pattern.iter().any(|&length| length > 0.0)
+    {
+        attrs.push_str(&format!(r#" stroke-dasharray="{}""#, number_list(pattern)));
+        if *offset != 0.0 {
+            attrs.push_str(&format!(r#" stroke-dashoffset="{offset}""#));
+        }
+    }
+    attrs
+}
+
+/// Map a blend mode to its CSS name, unless it's normal or has none.
+fn css_blend_mode(mode: BlendMode) -> Option<&'static str> {
+    Some(match mode {
+        BlendMode::Multiply => "multiply",
+        BlendMode::Screen => "screen",
+        BlendMode::Overlay => "overlay",
+        BlendMode::Darken => "darken",
+        BlendMode::Lighten => "lighten",
+        BlendMode::ColorDodge => "color-dodge",
+        BlendMode::ColorBurn => "color-burn",
+        BlendMode::HardLight => "hard-light",
+        BlendMode::SoftLight => "soft-light",
+        BlendMode::Difference => "difference",
+        BlendMode::Exclusion => "exclusion",
+        BlendMode::Hue => "hue",
+        BlendMode::Saturation => "saturation",
+        BlendMode::Color => "color",
+        BlendMode::Luminosity => "luminosity",
+        _ => return None,
+    })
+}
+
+fn number_list(numbers: &[f32]) -> String {
+    let numbers: Vec<String> = numbers.iter().map(f32::to_string).collect();
+    numbers.join(" ")
+}
+
+/// Escape text for use in XML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::svg::SvgImage;
+    use crate::text::{Font, FontFamily};
+
+    fn count(haystack: &str, needle: &str) -> usize {
+        haystack.matches(needle).count()
+    }
+
+    /// Render a document back to straight RGBA pixels at its own size.
+    fn round_trip(svg: &str) -> (SvgImage, Vec<u8>) {
+        let image = SvgImage::from_bytes(svg.as_bytes()).unwrap();
+        let size = image.natural_size();
+        let pixels = image.render_to_rgba(size.width as u32, size.height as u32);
+        (image, pixels)
+    }
+
+    fn pixel(pixels: &[u8], width: u32, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * width + x) * 4) as usize;
+        [pixels[i], pixels[i + 1], pixels[i + 2], pixels[i + 3]]
+    }
+
+    #[test]
+    fn test_shapes_round_trip() {
+        let mut renderer = SvgRenderer::new().with_title("Sales & <Costs>");
+        renderer.begin_frame(Color::WHITE, Size::new(100.0, 50.0));
+        renderer.fill_rect(Rect::new(0.0, 0.0, 20.0, 20.0), Color::RED);
+        renderer.fill_ellipse(Point::new(35.0, 10.0), 10.0, 10.0, Color::BLUE);
+        let mut path = Path::new();
+        path.move_to(Point::new(50.0, 0.0))
+            .line_to(Point::new(70.0, 0.0))
+            .line_to(Point::new(70.0, 20.0))
+            .close();
+        renderer.fill_path(&path, Color::GREEN, FillRule::EvenOdd);
+
+        Renderer::save(&mut renderer);
+        renderer.translate(80.0, 30.0);
+        renderer.scale(2.0, 2.0);
+        renderer.set_opacity(0.5);
+        renderer.fill_rect(Rect::new(0.0, 0.0, 10.0, 10.0), Color::BLACK);
+        renderer.restore();
+
+        let stroke =
+            Stroke::new(Color::BLACK, 2.0).with_dash(DashPattern::new(vec![4.0, 2.0], 1.0));
+        renderer.draw_line(Point::new(0.0, 40.0), Point::new(60.0, 40.0), &stroke);
+        renderer.end_frame();
+
+        let svg = renderer.finish().unwrap();
+        assert_eq!(count(&svg, "<title>Sales &amp; &lt;Costs&gt;</title>"), 1);
+        assert_eq!(count(&svg, "<ellipse"), 1);
+        assert_eq!(count(&svg, r#"fill-rule="evenodd""#), 1);
+        assert_eq!(
+            count(&svg, r#"transform="matrix(2 0 0 2 80 30)" opacity="0.5""#),
+            1
+        );
+        assert_eq!(
+            count(&svg, r#"stroke-dasharray="4 2" stroke-dashoffset="1""#),
+            1
+        );
+
+        let (image, pixels) = round_trip(&svg);
+        assert_eq!(image.natural_size(), Size::new(100.0, 50.0));
+        assert_eq!(pixel(&pixels, 100, 10, 10), [255, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, 100, 35, 10), [0, 0, 255, 255]);
+        assert_eq!(pixel(&pixels, 100, 68, 5), [0, 255, 0, 255]);
+        assert_eq!(pixel(&pixels, 100, 52, 15), [255, 255, 255, 255]);
+        // Half-transparent black over white, scaled to cover 80..100.
+        let [r, g, b, _] = pixel(&pixels, 100, 95, 45);
+        assert!((r as i32 - 128).abs() <= 2 && r == g && g == b);
+        assert_eq!(pixel(&pixels, 100, 1, 40), [0, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, 100, 4, 40), [255, 255, 255, 255]);
+    }
+
+    #[test]
+    fn test_gradients_and_clips_round_trip() {
+        let mut renderer = SvgRenderer::new();
+        renderer.begin_frame(Color::TRANSPARENT, Size::new(100.0, 100.0));
+
+        let linear = Paint::linear_gradient(
+            Point::new(0.0, 0.0),
+            Point::new(100.0, 0.0),
+            vec![
+                GradientStop::new(0.0, Color::RED),
+                GradientStop::new(1.0, Color::BLUE),
+            ],
+        );
+        renderer.clip_rect(Rect::new(0.0, 0.0, 100.0, 50.0));
+        renderer.clip_rounded_rect(RoundedRect::new(Rect::new(0.0, 0.0, 100.0, 40.0), 10.0));
+        renderer.fill_rect(Rect::new(0.0, 0.0, 100.0, 100.0), linear);
+        renderer.restore_clip();
+        renderer.reset();
+
+        let radial = Paint::radial_gradient(
+            Point::new(50.0, 75.0),
+            20.0,
+            None,
+            vec![
+                GradientStop::new(0.0, Color::WHITE),
+                GradientStop::new(1.0, Color::from_rgba(0.0, 0.0, 0.0, 0.0)),
+            ],
+        );
+        renderer.fill_rect(Rect::new(0.0, 50.0, 100.0, 50.0), radial);
+        renderer.end_frame();
+
+        let svg = renderer.finish().unwrap();
+        // The transparent clear color doesn't fill the document.
+        assert_eq!(count(&svg, r##"height="100" fill="#"##), 0);
+        assert_eq!(count(&svg, "<linearGradient"), 1);
+        assert_eq!(count(&svg, "<radialGradient"), 1);
+        assert_eq!(count(&svg, r#"stop-opacity="0""#), 1);
+        // The rounded clip is nested inside the clip rect.
+        assert_eq!(count(&svg, "<clipPath"), 2);
+        assert_eq!(
+            count(&svg, r##"<clipPath id="clip1" clip-path="url(#clip0)">"##),
+            1
+        );
+
+        let (_, pixels) = round_trip(&svg);
+        let [r, _, b, a] = pixel(&pixels, 100, 10, 20);
+        assert!(r > 200 && b < 50 && a == 255);
+        let [r, _, b, _] = pixel(&pixels, 100, 90, 20);
+        assert!(r < 50 && b > 200);
+        // Outside the rounded corner and below the clip.
+        assert_eq!(pixel(&pixels, 100, 1, 1)[3], 0);
+        assert_eq!(pixel(&pixels, 100, 50, 45)[3], 0);
+        assert!(pixel(&pixels, 100, 50, 75).iter().all(|&c| c > 240));
+        assert_eq!(pixel(&pixels, 100, 5, 95)[3], 0);
+    }
+
+    #[test]
+    fn test_images_round_trip() {
+        let mut renderer = SvgRenderer::new();
+        renderer.begin_frame(Color::WHITE, Size::new(40.0, 20.0));
+        let image = Image::from_rgba(&[255, 0, 0, 255, 0, 0, 255, 255], 2, 1).unwrap();
+        renderer.draw_image(
+            &image,
+            Rect::new(0.0, 0.0, 20.0, 10.0),
+            ImageScaleMode::Stretch,
+        );
+        // Only the blue half, through a clip.
+        renderer.draw_image_rect(
+            &image,
+            Rect::new(1.0, 0.0, 1.0, 1.0),
+            Rect::new(20.0, 0.0, 20.0, 20.0),
+        );
+        renderer.draw_box_shadow(
+            Rect::new(5.0, 12.0, 10.0, 4.0),
+            &BoxShadow::drop_shadow(Color::BLACK, 2.0),
+        );
+        renderer.end_frame();
+
+        let svg = renderer.finish().unwrap();
+        // The image is embedded once, the shadow is an image of its own.
+        assert_eq!(count(&svg, "data:image/png;base64,"), 2);
+        assert_eq!(count(&svg, r##"xlink:href="#img0""##), 2);
+        assert_eq!(count(&svg, "<clipPath"), 1);
+
+        let (_, pixels) = round_trip(&svg);
+        assert_eq!(pixel(&pixels, 40, 1, 1), [255, 0, 0, 255]);
+        assert_eq!(pixel(&pixels, 40, 38, 15), [0, 0, 255, 255]);
+        assert!(pixel(&pixels, 40, 10, 14)[0] < 200);
+    }
+
+    #[test]
+    fn test_text_modes() {
+        let mut font_system = FontSystem::new();
+        let font = Font::new(FontFamily::SansSerif, 20.0);
+        let layout = TextLayout::new(&mut font_system, "A<B", &font);
+        if layout.glyphs().next().is_none() {
+            // No system fonts are available.
+            return;
+        }
+
+        let mut renderer = SvgRenderer::new();
+        renderer.begin_frame(Color::WHITE, Size::new(100.0, 40.0));
+        renderer.draw_text(&font_system, &layout, Point::new(10.0, 5.0), Color::BLACK);
+        renderer.end_frame();
+        let svg = renderer.finish().unwrap();
+        assert_eq!(count(&svg, "<text"), 1);
+        assert_eq!(count(&svg, ">A&lt;B</text>"), 1);
+        assert_eq!(count(&svg, r#"font-size="20""#), 1);
+
+        let mut renderer = SvgRenderer::new().with_text_mode(SvgTextMode::Outlines);
+        renderer.begin_frame(Color::WHITE, Size::new(100.0, 40.0));
+        renderer.draw_text(&font_system, &layout, Point::new(10.0, 5.0), Color::BLACK);
+        renderer.draw_text(&font_system, &layout, Point::new(10.0, 5.0), Color::BLACK);
+        renderer.end_frame();
+        let svg = renderer.finish().unwrap();
+        assert_eq!(count(&svg, "<text"), 0);
+        // Outlines are defined once and used for every glyph drawn.
+        assert_eq!(count(&svg, r#"<path id="glyph"#), 3);
+        assert_eq!(count(&svg, r##"<use xlink:href="#glyph"##), 6);
+
+        // The outlines draw without any fonts, unlike `<text>`.
+        let (_, pixels) = round_trip(&svg);
+        let inked = (0..40)
+            .flat_map(|y| (0..100).map(move |x| (x, y)))
+            .filter(|&(x, y)| pixel(&pixels, 100, x, y)[0] < 128)
+            .count();
+        assert!(inked > 20);
+    }
+
+    #[test]
+    fn test_finish_without_frame() {
+        assert!(SvgRenderer::new().finish().is_err());
+    }
+}
@@ -945,6 +945,65 @@ impl TextLayout {
         (layout.width, layout.height)
     }
 
+    /// Get a layout holding only the given lines, moved up so the first of
+    /// them starts at the top.
+    ///
+    /// Backgrounds, decorations and inline elements are kept when they start
+    /// within the lines. Text ranges still refer to the full text. This is
+    /// used to split a layout across pages.
+    pub fn slice_lines(&self, lines: Range<usize>) -> TextLayout {
+        let lines = lines.start.min(self.lines.len())..lines.end.min(self.lines.len());
+        let (top, bottom) = match (self.lines.get(lines.start), lines.end.checked_sub(1)) {
+            (Some(first), Some(last)) if !lines.is_empty() => {
+                let last = &self.lines[last];
+                (first.top_y, last.top_y + last.height)
+            }
+            _ => (0.0, 0.0),
+        };
+        let within = |y: f32| y >= top && y < bottom;
+
+        TextLayout {
+            text: self.text.clone(),
+            lines: self.lines[lines]
+                .iter()
+                .map(|line| LayoutLine {
+                    baseline_y: line.baseline_y - top,
+                    top_y: line.top_y - top,
+                    ..line.clone()
+                })
+                .collect(),
+            width: self.width,
+            height: bottom - top,
+            options: self.options.clone(),
+            is_truncated: self.is_truncated,
+            inline_elements: self
+                .inline_elements
+                .iter()
+                .filter(|(_, _, y)| within(*y))
+                .map(|(element, x, y)| (element.clone(), *x, y - top))
+                .collect(),
+            background_rects: self
+                .background_rects
+                .iter()
+                .filter(|rect| within(rect.y))
+                .map(|rect| BackgroundRect {
+                    y: rect.y - top,
+                    ..rect.clone()
+                })
+                .collect(),
+            decoration_lines: self
+                .decoration_lines
+                .iter()
+                .filter(|line| within(line.y))
+                .map(|line| DecorationLine {
+                    y: line.y - top,
+                    ..line.clone()
+                })
+                .collect(),
+            resolved_direction: self.resolved_direction,
+        }
+    }
+
     /// Find the line containing a given y position.
     pub fn line_at_y(&self, y: f32) -> Option<usize> {
         for (i, line) in self.lines.iter().enumerate() {
@@ -2259,6 +2318,40 @@ mod tests {
         assert_eq!(line.x_for_offset(0), 0.0);
     }
 
+    #[test]
+    fn slice_lines() {
+        let mut layout = TextLayout::default();
+        layout.lines = (0..3)
+            .map(|i| LayoutLine {
+                glyphs: Vec::new(),
+                baseline_y: i as f32 * 20.0 + 15.0,
+                top_y: i as f32 * 20.0,
+                height: 20.0,
+                width: 50.0,
+                text_range: i..i + 1,
+                is_hard_break: true,
+            })
+            .collect();
+        layout.height = 60.0;
+        layout.background_rects = vec![
+            BackgroundRect::new(0.0, 0.0, 10.0, 20.0, [255; 4]),
+            BackgroundRect::new(0.0, 40.0, 10.0, 20.0, [255; 4]),
+        ];
+
+        let slice = layout.slice_lines(1..3);
+        assert_eq!(slice.line_count(), 2);
+        assert_eq!(slice.height(), 40.0);
+        assert_eq!(slice.lines()[0].top_y, 0.0);
+        assert_eq!(slice.lines()[0].baseline_y, 15.0);
+        assert_eq!(slice.lines()[1].text_range, 2..3);
+        assert_eq!(slice.background_rects().len(), 1);
+        assert_eq!(slice.background_rects()[0].y, 20.0);
+
+        let empty = layout.slice_lines(3..5);
+        assert_eq!(empty.line_count(), 0);
+        assert_eq!(empty.height(), 0.0);
+    }
+
     #[test]
     fn vertical_offset_calculation() {
         // Test vertical offset calculation using manually created layout
@@ -6,8 +6,8 @@
 //! ```
 
 use horizon_lattice_render::{
-    Color, GpuRenderer, GraphicsConfig, GraphicsContext, OffscreenConfig, OffscreenSurface, Rect,
-    Renderer, Size, capture::BufferDimensions,
+    BlendMode, Color, GpuRenderer, GraphicsConfig, GraphicsContext, OffscreenConfig,
+    OffscreenSurface, Rect, Renderer, Size, capture::BufferDimensions,
 };
 
 #[test]
@@ -140,6 +140,38 @@ fn test_offscreen_render_and_read() {
     println!("Offscreen render and read test passed");
 }
 
+#[test]
+#[ignore = "requires GPU"]
+fn test_blend_mode_change_mid_frame() {
+    if GraphicsContext::try_get().is_none() {
+        GraphicsContext::init(GraphicsConfig::default()).expect("Failed to init graphics");
+    }
+
+    let surface =
+        OffscreenSurface::new(OffscreenConfig::new(64, 64)).expect("Failed to create surface");
+
+    let mut renderer =
+        GpuRenderer::new_offscreen(&surface).expect("Failed to create offscreen renderer");
+
+    // Overlaying any color on a white backdrop leaves it white. Blended
+    // normally or against the black clear color, the red would show.
+    let viewport = Size::new(64.0, 64.0);
+    renderer.begin_frame(Color::BLACK, viewport);
+    renderer.fill_rect(Rect::new(0.0, 0.0, 64.0, 64.0), Color::WHITE);
+    renderer.set_blend_mode(BlendMode::Overlay);
+    renderer.fill_rect(Rect::new(0.0, 0.0, 64.0, 64.0), Color::RED);
+    renderer.end_frame();
+    renderer
+        .render_to_offscreen(&surface)
+        .expect("Failed to render");
+
+    let pixels = surface.read_pixels().expect("Failed to read pixels");
+    assert!(pixels[0] > 250, "Red channel should stay white");
+    assert!(pixels[1] > 250, "Green channel should stay white");
+    assert!(pixels[2] > 250, "Blue channel should stay white");
+    assert_eq!(pixels[3], 255, "Alpha should be fully opaque");
+}
+
 #[test]
 #[ignore = "requires GPU"]
 fn test_multiple_offscreen_surfaces() {
@@ -0,0 +1,585 @@
+//! Per-widget transition and keyframe animation state.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::time::Duration;
+
+use horizon_lattice_core::{Easing, ObjectId, ease};
+
+use super::{AnimatableProperty, AnimatedValue, AnimationSpec, Keyframes};
+use crate::selector::PseudoElement;
+use crate::style::{ComputedStyle, StyleProperties};
+
+/// Identifies the styled box an animation belongs to: a widget, or one of
+/// its sub-controls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct AnimationKey {
+    widget_id: ObjectId,
+    sub_control: Option<(PseudoElement, Option<(usize, usize)>)>,
+}
+
+impl AnimationKey {
+    /// Key for a widget's own style.
+    pub(crate) fn widget(widget_id: ObjectId) -> Self {
+        Self {
+            widget_id,
+            sub_control: None,
+        }
+    }
+
+    /// Key for a sub-control, told apart from its siblings by `sibling_info`.
+    pub(crate) fn sub_control(
+        widget_id: ObjectId,
+        element: PseudoElement,
+        sibling_info: Option<(usize, usize)>,
+    ) -> Self {
+        Self {
+            widget_id,
+            sub_control: Some((element, sibling_info)),
+        }
+    }
+}
+
+/// Keyframe values of one property, sorted by offset.
+///
+/// The implicit `from` and `to` frames are not stored; they take the
+/// current value of the un-animated style when sampled.
+type PropertyTrack = (AnimatableProperty, Vec<(f32, AnimatedValue)>);
+
+/// Resolve a `@keyframes` rule into per-property tracks.
+pub(crate) fn keyframe_tracks(
+    keyframes: &Keyframes,
+    mut resolve: impl FnMut(&StyleProperties) -> ComputedStyle,
+) -> Vec<PropertyTrack> {
+    let frames: Vec<(f32, &StyleProperties, ComputedStyle)> = keyframes
+        .frames
+        .iter()
+        .map(|frame| (frame.offset, &frame.properties, resolve(&frame.properties)))
+        .collect();
+
+    AnimatableProperty::ALL
+        .iter()
+        .filter_map(|&property| {
+            let track: Vec<(f32, AnimatedValue)> = frames
+                .iter()
+                .filter(|(_, props, _)| property.is_declared_in(props))
+                .map(|(offset, _, style)| (*offset, property.get(style)))
+                .collect();
+            (!track.is_empty()).then_some((property, track))
+        })
+        .collect()
+}
+
+/// Tracks running transitions and keyframe animations for every styled box.
+#[derive(Debug, Default)]
+pub(crate) struct StyleAnimator {
+    states: HashMap<AnimationKey, AnimationState>,
+}
+
+impl StyleAnimator {
+    /// Apply transitions and animations to a freshly computed style.
+    ///
+    /// `target` is the style without animations. Changes to it since the
+    /// previous update start the transitions it declares. `tracks` resolves
+    /// the keyframes of a newly started animation against `target`, or
+    /// returns `None` if no such `@keyframes` rule exists.
+    ///
+    /// Boxes are only tracked while `track` is set or they have animations
+    /// running, so engines without any transitions keep no state.
+    pub(crate) fn update(
+        &mut self,
+        key: AnimationKey,
+        target: ComputedStyle,
+        now: Duration,
+        track: bool,
+        tracks: impl FnMut(&AnimationSpec, &ComputedStyle) -> Option<Vec<PropertyTrack>>,
+    ) -> ComputedStyle {
+        let state = match self.states.entry(key) {
+            Entry::Occupied(entry) => {
+                if !track && !entry.get().is_running() {
+                    entry.remove();
+                    return target;
+                }
+                entry.into_mut()
+            }
+            Entry::Vacant(entry) => {
+                if !track {
+                    return target;
+                }
+                entry.insert(AnimationState::new(target.clone()))
+            }
+        };
+
+        state.start_transitions(&target, now);
+        state.sync_animations(&target, now, tracks);
+        state.target = target;
+        state.sample(now)
+    }
+
+    /// Check whether any box of a widget has transitions or animations running.
+    pub(crate) fn is_animating(&self, widget_id: ObjectId) -> bool {
+        self.states
+            .iter()
+            .any(|(key, state)| key.widget_id == widget_id && state.is_running())
+    }
+
+    /// Check whether any box has transitions or animations running.
+    pub(crate) fn has_running_animations(&self) -> bool {
+        self.states.values().any(AnimationState::is_running)
+    }
+
+    /// Drop all animation state for a widget and its sub-controls.
+    pub(crate) fn remove_widget(&mut self, widget_id: ObjectId) {
+        self.states.retain(|key, _| key.widget_id != widget_id);
+    }
+
+    /// Drop all animation state.
+    pub(crate) fn clear(&mut self) {
+        self.states.clear();
+    }
+}
+
+/// Animation state of one styled box.
+#[derive(Debug)]
+struct AnimationState {
+    /// The un-animated style as of the last update.
+    target: ComputedStyle,
+    transitions: Vec<RunningTransition>,
+    animations: Vec<RunningAnimation>,
+}
+
+impl AnimationState {
+    fn new(target: ComputedStyle) -> Self {
+        Self {
+            target,
+            transitions: vec![],
+            animations: vec![],
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        !self.transitions.is_empty() || self.animations.iter().any(|a| !a.finished)
+    }
+
+    /// Start a transition for every property that changed in `target`,
+    /// beginning at the value currently displayed.
+    fn start_transitions(&mut self, target: &ComputedStyle, now: Duration) {
+        for &property in AnimatableProperty::ALL {
+            let old = property.get(&self.target);
+            let new = property.get(target);
+            if old == new {
+                continue;
+            }
+
+            let mut from = old;
+            if let Some(index) = self.transitions.iter().position(|t| t.property == property) {
+                from = self.transitions.remove(index).sample(now, &from);
+            }
+
+            // As in CSS, the last matching entry of the new style wins
+            let Some(spec) = target
+                .transitions
+                .iter()
+                .rev()
+                .find(|spec| spec.property.covers(property))
+            else {
+                continue;
+            };
+            if spec.duration.is_zero() {
+                continue;
+            }
+
+            self.transitions.push(RunningTransition {
+                property,
+                from,
+                start: now + spec.delay,
+                duration: spec.duration,
+                easing: spec.easing,
+            });
+        }
+    }
+
+    /// Start animations newly listed in `target` and stop those no longer listed.
+    fn sync_animations(
+        &mut self,
+        target: &ComputedStyle,
+        now: Duration,
+        mut tracks: impl FnMut(&AnimationSpec, &ComputedStyle) -> Option<Vec<PropertyTrack>>,
+    ) {
+        if target.animations.is_empty() && self.animations.is_empty() {
+            return;
+        }
+
+        let mut running = std::mem::take(&mut self.animations);
+        for spec in &target.animations {
+            if let Some(index) = running.iter().position(|a| a.spec.name == spec.name) {
+                let mut animation = running.remove(index);
+                animation.spec = spec.clone();
+                self.animations.push(animation);
+            } else if let Some(tracks) = tracks(spec, target) {
+                self.animations.push(RunningAnimation {
+                    spec: spec.clone(),
+                    start: now,
+                    tracks,
+                    finished: false,
+                });
+            }
+        }
+    }
+
+    /// Produce the displayed style at `now`, dropping finished transitions.
+    fn sample(&mut self, now: Duration) -> ComputedStyle {
+        let mut style = self.target.clone();
+
+        let target = &self.target;
+        self.transitions.retain(|transition| {
+            if now >= transition.start + transition.duration {
+                return false;
+            }
+            let to = transition.property.get(target);
+            transition
+                .property
+                .set(&mut style, transition.sample(now, &to));
+            true
+        });
+
+        // Animations override transitions, and later animations earlier ones
+        for animation in &mut self.animations {
+            animation.apply(&mut style, target, now);
+        }
+
+        style
+    }
+}
+
+/// A transition of one property towards the current target value.
+#[derive(Debug)]
+struct RunningTransition {
+    property: AnimatableProperty,
+    from: AnimatedValue,
+    /// When the transition starts moving, after its delay.
+    start: Duration,
+    duration: Duration,
+    easing: Easing,
+}
+
+impl RunningTransition {
+    fn sample(&self, now: Duration, to: &AnimatedValue) -> AnimatedValue {
+        let elapsed = now.saturating_sub(self.start);
+        let progress = elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        self.from.interpolate(to, ease(self.easing, progress))
+    }
+}
+
+/// A keyframe animation started at `start`.
+#[derive(Debug)]
+struct RunningAnimation {
+    spec: AnimationSpec,
+    start: Duration,
+    tracks: Vec<PropertyTrack>,
+    /// Whether the active period has ended.
+    finished: bool,
+}
+
+impl RunningAnimation {
+    fn apply(&mut self, style: &mut ComputedStyle, target: &ComputedStyle, now: Duration) {
+        let Some(progress) = self.progress(now) else {
+            return;
+        };
+        for (property, track) in &self.tracks {
+            let value = sample_track(track, property.get(target), progress, self.spec.easing);
+            property.set(style, value);
+        }
+    }
+
+    /// Progress within the current iteration, after direction is applied, or
+    /// `None` if the animation has no effect at `now`.
+    fn progress(&mut self, now: Duration) -> Option<f32> {
+        let spec = &self.spec;
+        let elapsed = now.saturating_sub(self.start);
+
+        if elapsed < spec.delay {
+            return spec
+                .fill_mode
+                .fills_backwards()
+                .then(|| directed(spec, 0, 0.0));
+        }
+        let active = elapsed - spec.delay;
+
+        if let Some(total) = spec.active_duration()
+            && (active >= total || spec.duration.is_zero())
+        {
+            self.finished = true;
+            if !spec.fill_mode.fills_forwards() {
+                return None;
+            }
+            let count = match spec.iteration_count {
+                super::IterationCount::Count(count) => count.max(0.0),
+                super::IterationCount::Infinite => 1.0,
+            };
+            // End on the last iteration; a fractional count ends part-way
+            let (iteration, fraction) = if count == 0.0 {
+                (0, 0.0)
+            } else if count.fract() == 0.0 {
+                (count as u32 - 1, 1.0)
+            } else {
+                (count as u32, count.fract())
+            };
+            return Some(directed(spec, iteration, fraction));
+        }
+
+        if spec.duration.is_zero() {
+            // An infinite animation with no duration never shows any frame
+            return None;
+        }
+        let cycle = spec.duration.as_secs_f32();
+        let active = active.as_secs_f32();
+        let iteration = (active / cycle).floor();
+        let fraction = (active - iteration * cycle) / cycle;
+        Some(directed(spec, iteration as u32, fraction))
+    }
+}
+
+/// Apply the playback direction of an iteration to its progress.
+fn directed(spec: &AnimationSpec, iteration: u32, fraction: f32) -> f32 {
+    if spec.direction.is_reversed(iteration) {
+        1.0 - fraction
+    } else {
+        fraction
+    }
+}
+
+/// Sample a property track at `progress`, easing each keyframe segment.
+fn sample_track(
+    track: &[(f32, AnimatedValue)],
+    base: AnimatedValue,
+    progress: f32,
+    easing: Easing,
+) -> AnimatedValue {
+    let first = track.first().map(|(offset, _)| *offset).unwrap_or(0.0);
+    let last = track.last().map(|(offset, _)| *offset).unwrap_or(1.0);
+
+    let mut frames: Vec<(f32, &AnimatedValue)> = Vec::with_capacity(track.len() + 2);
+    if first > 0.0 {
+        frames.push((0.0, &base));
+    }
+    frames.extend(track.iter().map(|(offset, value)| (*offset, value)));
+    if last < 1.0 {
+        frames.push((1.0, &base));
+    }
+
+    let next = frames
+        .iter()
+        .position(|(offset, _)| *offset > progress)
+        .unwrap_or(frames.len() - 1)
+        .max(1);
+    let (from_offset, from) = frames[next - 1];
+    let (to_offset, to) = frames[next];
+    if to_offset <= from_offset {
+        return to.clone();
+    }
+    let local = ((progress - from_offset) / (to_offset - from_offset)).clamp(0.0, 1.0);
+    from.interpolate(to, ease(easing, local))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::animation::{
+        AnimationDirection, AnimationFillMode, IterationCount, TransitionProperty, TransitionSpec,
+    };
+    use crate::style::Style;
+    use crate::types::StyleValue;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn styled(opacity: f32, transitions: Vec<TransitionSpec>) -> ComputedStyle {
+        ComputedStyle {
+            opacity,
+            transitions,
+            ..ComputedStyle::default()
+        }
+    }
+
+    fn opacity_transition() -> Vec<TransitionSpec> {
+        vec![
+            TransitionSpec::new(
+                TransitionProperty::Property(AnimatableProperty::Opacity),
+                ms(100),
+            )
+            .with_easing(Easing::Linear),
+        ]
+    }
+
+    fn no_tracks(_: &AnimationSpec, _: &ComputedStyle) -> Option<Vec<PropertyTrack>> {
+        None
+    }
+
+    #[test]
+    fn transition_interpolates_and_settles() {
+        let mut animator = StyleAnimator::default();
+        let key = AnimationKey::widget(ObjectId::default());
+
+        let style = animator.update(
+            key,
+            styled(1.0, opacity_transition()),
+            ms(0),
+            true,
+            no_tracks,
+        );
+        assert_eq!(style.opacity, 1.0);
+        assert!(!animator.has_running_animations());
+
+        let style = animator.update(
+            key,
+            styled(0.0, opacity_transition()),
+            ms(0),
+            true,
+            no_tracks,
+        );
+        assert_eq!(style.opacity, 1.0);
+        assert!(animator.has_running_animations());
+
+        let style = animator.update(
+            key,
+            styled(0.0, opacity_transition()),
+            ms(50),
+            true,
+            no_tracks,
+        );
+        assert!((style.opacity - 0.5).abs() < 1e-4);
+
+        let style = animator.update(
+            key,
+            styled(0.0, opacity_transition()),
+            ms(100),
+            true,
+            no_tracks,
+        );
+        assert_eq!(style.opacity, 0.0);
+        assert!(!animator.has_running_animations());
+    }
+
+    #[test]
+    fn reversed_transition_starts_from_displayed_value() {
+        let mut animator = StyleAnimator::default();
+        let key = AnimationKey::widget(ObjectId::default());
+
+        animator.update(
+            key,
+            styled(1.0, opacity_transition()),
+            ms(0),
+            true,
+            no_tracks,
+        );
+        animator.update(
+            key,
+            styled(0.0, opacity_transition()),
+            ms(0),
+            true,
+            no_tracks,
+        );
+        // Halfway (0.5), head back to 1.0
+        let style = animator.update(
+            key,
+            styled(1.0, opacity_transition()),
+            ms(50),
+            true,
+            no_tracks,
+        );
+        assert!((style.opacity - 0.5).abs() < 1e-4);
+        let style = animator.update(
+            key,
+            styled(1.0, opacity_transition()),
+            ms(100),
+            true,
+            no_tracks,
+        );
+        assert!((style.opacity - 0.75).abs() < 1e-4);
+    }
+
+    #[test]
+    fn untracked_boxes_keep_no_state() {
+        let mut animator = StyleAnimator::default();
+        let key = AnimationKey::widget(ObjectId::default());
+
+        animator.update(key, styled(1.0, vec![]), ms(0), false, no_tracks);
+        let style = animator.update(key, styled(0.0, vec![]), ms(0), false, no_tracks);
+        assert_eq!(style.opacity, 0.0);
+        assert!(animator.states.is_empty());
+    }
+
+    #[test]
+    fn keyframe_animation_alternates() {
+        let mut keyframes = Keyframes::new("fade");
+        keyframes.add_frame(0.0, Style::new().opacity(0.0).build());
+        keyframes.add_frame(1.0, Style::new().opacity(1.0).build());
+        let tracks = keyframe_tracks(&keyframes, |props| ComputedStyle {
+            opacity: props.opacity.as_set().copied().unwrap_or(1.0),
+            ..ComputedStyle::default()
+        });
+
+        let spec = AnimationSpec::new("fade", ms(100))
+            .with_easing(Easing::Linear)
+            .with_iteration_count(IterationCount::Count(2.0))
+            .with_direction(AnimationDirection::Alternate);
+        let target = ComputedStyle {
+            animations: vec![spec],
+            ..ComputedStyle::default()
+        };
+
+        let mut animator = StyleAnimator::default();
+        let key = AnimationKey::widget(ObjectId::default());
+        let mut update = |now| {
+            animator
+                .update(key, target.clone(), now, true, |_, _| Some(tracks.clone()))
+                .opacity
+        };
+
+        assert_eq!(update(ms(0)), 0.0);
+        assert!((update(ms(25)) - 0.25).abs() < 1e-4);
+        // Second iteration plays backwards
+        assert!((update(ms(125)) - 0.75).abs() < 1e-4);
+        // Finished without fill: the un-animated value applies again
+        assert_eq!(update(ms(200)), 1.0);
+        assert!(!animator.has_running_animations());
+    }
+
+    #[test]
+    fn fill_forwards_keeps_final_frame() {
+        let mut keyframes = Keyframes::new("shrink");
+        let props = StyleProperties {
+            font_size: StyleValue::Set(crate::types::LengthValue::px(10.0)),
+            ..StyleProperties::default()
+        };
+        keyframes.add_frame(1.0, props);
+        let tracks = keyframe_tracks(&keyframes, |_| ComputedStyle {
+            font_size: 10.0,
+            ..ComputedStyle::default()
+        });
+
+        let spec = AnimationSpec::new("shrink", ms(100))
+            .with_easing(Easing::Linear)
+            .with_fill_mode(AnimationFillMode::Forwards);
+        let target = ComputedStyle {
+            font_size: 20.0,
+            animations: vec![spec],
+            ..ComputedStyle::default()
+        };
+
+        let mut animator = StyleAnimator::default();
+        let key = AnimationKey::widget(ObjectId::default());
+        // Implicit `from` frame uses the un-animated value
+        let style = animator.update(key, target.clone(), ms(0), true, |_, _| {
+            Some(tracks.clone())
+        });
+        assert_eq!(style.font_size, 20.0);
+        let style = animator.update(key, target.clone(), ms(50), true, |_, _| None);
+        assert_eq!(style.font_size, 15.0);
+        let style = animator.update(key, target, ms(500), true, |_, _| None);
+        assert_eq!(style.font_size, 10.0);
+        assert!(!animator.has_running_animations());
+    }
+}
@@ -0,0 +1,81 @@
+//! Time source for style animations.
+
+use std::time::{Duration, Instant};
+
+/// The time source that drives transitions and keyframe animations.
+///
+/// A system clock follows wall-clock time. A manual clock only moves when
+/// [`advance`](Self::advance) or [`set_elapsed`](Self::set_elapsed) is called,
+/// which makes animated styles deterministic in tests.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use horizon_lattice_style::prelude::AnimationClock;
+///
+/// let mut clock = AnimationClock::manual();
+/// assert_eq!(clock.elapsed(), Duration::ZERO);
+///
+/// clock.advance(Duration::from_millis(16));
+/// assert_eq!(clock.elapsed(), Duration::from_millis(16));
+/// ```
+#[derive(Debug, Clone)]
+pub struct AnimationClock {
+    source: ClockSource,
+}
+
+#[derive(Debug, Clone)]
+enum ClockSource {
+    System(Instant),
+    Manual(Duration),
+}
+
+impl AnimationClock {
+    /// Create a clock that follows wall-clock time.
+    pub fn system() -> Self {
+        Self {
+            source: ClockSource::System(Instant::now()),
+        }
+    }
+
+    /// Create a clock that starts at zero and only moves when advanced.
+    pub fn manual() -> Self {
+        Self {
+            source: ClockSource::Manual(Duration::ZERO),
+        }
+    }
+
+    /// Check whether this is a manual clock.
+    pub fn is_manual(&self) -> bool {
+        matches!(self.source, ClockSource::Manual(_))
+    }
+
+    /// Time elapsed since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        match self.source {
+            ClockSource::System(start) => start.elapsed(),
+            ClockSource::Manual(elapsed) => elapsed,
+        }
+    }
+
+    /// Move a manual clock forward. Has no effect on a system clock.
+    pub fn advance(&mut self, by: Duration) {
+        if let ClockSource::Manual(elapsed) = &mut self.source {
+            *elapsed += by;
+        }
+    }
+
+    /// Set the elapsed time of a manual clock. Has no effect on a system clock.
+    pub fn set_elapsed(&mut self, elapsed: Duration) {
+        if let ClockSource::Manual(current) = &mut self.source {
+            *current = elapsed;
+        }
+    }
+}
+
+impl Default for AnimationClock {
+    fn default() -> Self {
+        Self::system()
+    }
+}
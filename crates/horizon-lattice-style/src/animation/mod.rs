@@ -0,0 +1,49 @@
+//! CSS transitions and keyframe animations.
+//!
+//! A `transition` declaration makes the [`StyleEngine`](crate::resolve::StyleEngine)
+//! interpolate a property whenever its computed value changes, for example
+//! when a button becomes hovered. An `animation` declaration plays a named
+//! `@keyframes` rule. Both are applied on top of the computed style each time
+//! the engine computes it, driven by the engine's [`AnimationClock`].
+//!
+//! ```css
+//! PushButton {
+//!     background-color: #1976d2;
+//!     transition: background-color 150ms ease-out, box-shadow 200ms;
+//! }
+//!
+//! PushButton:hover {
+//!     background-color: #1565c0;
+//! }
+//!
+//! @keyframes pulse {
+//!     from { opacity: 1; }
+//!     50% { opacity: 0.4; }
+//!     to { opacity: 1; }
+//! }
+//!
+//! .busy {
+//!     animation: pulse 1s ease-in-out infinite;
+//! }
+//! ```
+//!
+//! Colors, lengths, edges, corner radii, opacity and box shadows are
+//! interpolated (see [`AnimatableProperty`]); values that cannot be blended,
+//! such as gradients, switch halfway through. Timing functions are the
+//! [`Easing`] curves: `linear`, `ease` (the same as `ease-in-out`),
+//! `ease-in`, `ease-out`, `ease-in-out`, plus the cubic and sine variants
+//! such as `ease-out-cubic`.
+
+mod animator;
+mod clock;
+mod spec;
+mod value;
+
+pub(crate) use animator::{AnimationKey, StyleAnimator, keyframe_tracks};
+pub use clock::AnimationClock;
+pub use horizon_lattice_core::easing::Easing;
+pub use spec::{
+    AnimationDirection, AnimationFillMode, AnimationSpec, IterationCount, Keyframe, Keyframes,
+    TransitionProperty, TransitionSpec,
+};
+pub use value::{AnimatableProperty, AnimatedValue};
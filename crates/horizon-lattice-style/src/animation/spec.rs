@@ -0,0 +1,321 @@
+//! Transition, animation and keyframe declarations.
+
+use std::time::Duration;
+
+use horizon_lattice_core::Easing;
+
+use super::AnimatableProperty;
+use crate::style::StyleProperties;
+
+/// The property a transition applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TransitionProperty {
+    /// Every animatable property (`transition: all 200ms`).
+    All,
+    /// A single property (`transition: opacity 200ms`).
+    Property(AnimatableProperty),
+}
+
+impl TransitionProperty {
+    /// Parse a CSS property name (`all`, `background-color`, ...).
+    pub fn from_css(name: &str) -> Option<Self> {
+        if name.eq_ignore_ascii_case("all") {
+            Some(Self::All)
+        } else {
+            AnimatableProperty::from_css(name).map(Self::Property)
+        }
+    }
+
+    /// Check whether this transition covers a property.
+    pub fn covers(&self, property: AnimatableProperty) -> bool {
+        match self {
+            Self::All => true,
+            Self::Property(p) => *p == property,
+        }
+    }
+}
+
+/// One entry of a `transition` declaration.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use horizon_lattice_style::prelude::*;
+///
+/// // transition: background-color 150ms ease-out
+/// let transition = TransitionSpec::new(
+///     TransitionProperty::Property(AnimatableProperty::Background),
+///     Duration::from_millis(150),
+/// )
+/// .with_easing(Easing::EaseOut);
+///
+/// assert!(transition.property.covers(AnimatableProperty::Background));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransitionSpec {
+    /// The property that transitions.
+    pub property: TransitionProperty,
+    /// How long the transition runs.
+    pub duration: Duration,
+    /// Easing curve applied to the transition progress.
+    pub easing: Easing,
+    /// Delay before the transition starts.
+    pub delay: Duration,
+}
+
+impl TransitionSpec {
+    /// Create a transition with the default `ease` timing and no delay.
+    pub fn new(property: TransitionProperty, duration: Duration) -> Self {
+        Self {
+            property,
+            duration,
+            easing: Easing::EaseInOut,
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Set the easing curve.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set the start delay.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// How many times a keyframe animation repeats.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IterationCount {
+    /// Run a (possibly fractional) number of iterations.
+    Count(f32),
+    /// Repeat forever.
+    Infinite,
+}
+
+/// The direction in which keyframes play on each iteration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationDirection {
+    /// Play from `from` to `to` on every iteration.
+    #[default]
+    Normal,
+    /// Play from `to` to `from` on every iteration.
+    Reverse,
+    /// Play forwards, then backwards, alternating.
+    Alternate,
+    /// Play backwards, then forwards, alternating.
+    AlternateReverse,
+}
+
+impl AnimationDirection {
+    /// Parse a CSS `animation-direction` keyword.
+    pub fn from_css(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "normal" => Some(Self::Normal),
+            "reverse" => Some(Self::Reverse),
+            "alternate" => Some(Self::Alternate),
+            "alternate-reverse" => Some(Self::AlternateReverse),
+            _ => None,
+        }
+    }
+
+    /// Check whether the given zero-based iteration plays backwards.
+    pub fn is_reversed(&self, iteration: u32) -> bool {
+        match self {
+            Self::Normal => false,
+            Self::Reverse => true,
+            Self::Alternate => !iteration.is_multiple_of(2),
+            Self::AlternateReverse => iteration.is_multiple_of(2),
+        }
+    }
+}
+
+/// Whether an animation applies its values outside its active period.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnimationFillMode {
+    /// Only apply values while the animation is running.
+    #[default]
+    None,
+    /// Keep the final values after the animation ends.
+    Forwards,
+    /// Apply the first values during the start delay.
+    Backwards,
+    /// Both `Forwards` and `Backwards`.
+    Both,
+}
+
+impl AnimationFillMode {
+    /// Parse a CSS `animation-fill-mode` keyword.
+    pub fn from_css(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "forwards" => Some(Self::Forwards),
+            "backwards" => Some(Self::Backwards),
+            "both" => Some(Self::Both),
+            _ => None,
+        }
+    }
+
+    /// Check whether values are kept after the animation ends.
+    pub fn fills_forwards(&self) -> bool {
+        matches!(self, Self::Forwards | Self::Both)
+    }
+
+    /// Check whether the first values apply during the start delay.
+    pub fn fills_backwards(&self) -> bool {
+        matches!(self, Self::Backwards | Self::Both)
+    }
+}
+
+/// One entry of an `animation` declaration, referring to `@keyframes` by name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimationSpec {
+    /// Name of the `@keyframes` rule to play.
+    pub name: String,
+    /// Duration of one iteration.
+    pub duration: Duration,
+    /// Easing curve applied between consecutive keyframes.
+    pub easing: Easing,
+    /// Delay before the first iteration starts.
+    pub delay: Duration,
+    /// Number of iterations.
+    pub iteration_count: IterationCount,
+    /// Playback direction.
+    pub direction: AnimationDirection,
+    /// Fill behavior before and after the active period.
+    pub fill_mode: AnimationFillMode,
+}
+
+impl AnimationSpec {
+    /// Create an animation that plays `name` once with the default `ease` timing.
+    pub fn new(name: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            name: name.into(),
+            duration,
+            easing: Easing::EaseInOut,
+            delay: Duration::ZERO,
+            iteration_count: IterationCount::Count(1.0),
+            direction: AnimationDirection::Normal,
+            fill_mode: AnimationFillMode::None,
+        }
+    }
+
+    /// Set the easing curve.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Set the start delay.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Set the number of iterations.
+    pub fn with_iteration_count(mut self, count: IterationCount) -> Self {
+        self.iteration_count = count;
+        self
+    }
+
+    /// Set the playback direction.
+    pub fn with_direction(mut self, direction: AnimationDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Set the fill mode.
+    pub fn with_fill_mode(mut self, fill_mode: AnimationFillMode) -> Self {
+        self.fill_mode = fill_mode;
+        self
+    }
+
+    /// Total length of the active period, or `None` if it repeats forever.
+    pub fn active_duration(&self) -> Option<Duration> {
+        match self.iteration_count {
+            IterationCount::Count(count) => Some(self.duration.mul_f64(count.max(0.0).into())),
+            IterationCount::Infinite => None,
+        }
+    }
+}
+
+/// A single keyframe: the properties declared at an offset of an animation.
+#[derive(Debug, Clone)]
+pub struct Keyframe {
+    /// Position within one iteration, from 0.0 (`from`) to 1.0 (`to`).
+    pub offset: f32,
+    /// Properties declared in the keyframe.
+    pub properties: StyleProperties,
+}
+
+/// A named `@keyframes` rule.
+#[derive(Debug, Clone)]
+pub struct Keyframes {
+    /// The animation name.
+    pub name: String,
+    /// Keyframes sorted by offset.
+    pub frames: Vec<Keyframe>,
+}
+
+impl Keyframes {
+    /// Create an empty keyframes rule.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            frames: vec![],
+        }
+    }
+
+    /// Add a keyframe at `offset` (clamped to 0.0..=1.0), keeping frames sorted.
+    pub fn with_frame(mut self, offset: f32, properties: StyleProperties) -> Self {
+        self.add_frame(offset, properties);
+        self
+    }
+
+    /// Add a keyframe at `offset` (clamped to 0.0..=1.0), keeping frames sorted.
+    ///
+    /// Frames at the same offset keep their declaration order, so later
+    /// declarations win.
+    pub fn add_frame(&mut self, offset: f32, properties: StyleProperties) {
+        let offset = offset.clamp(0.0, 1.0);
+        let index = self.frames.partition_point(|frame| frame.offset <= offset);
+        self.frames.insert(index, Keyframe { offset, properties });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keyframes_stay_sorted() {
+        let keyframes = Keyframes::new("pulse")
+            .with_frame(1.0, StyleProperties::default())
+            .with_frame(0.0, StyleProperties::default())
+            .with_frame(0.5, StyleProperties::default());
+        let offsets: Vec<f32> = keyframes.frames.iter().map(|f| f.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.5, 1.0]);
+    }
+
+    #[test]
+    fn direction_per_iteration() {
+        assert!(!AnimationDirection::Alternate.is_reversed(0));
+        assert!(AnimationDirection::Alternate.is_reversed(1));
+        assert!(AnimationDirection::AlternateReverse.is_reversed(0));
+        assert!(AnimationDirection::Reverse.is_reversed(2));
+    }
+
+    #[test]
+    fn active_duration() {
+        let spec = AnimationSpec::new("spin", Duration::from_millis(200))
+            .with_iteration_count(IterationCount::Count(2.5));
+        assert_eq!(spec.active_duration(), Some(Duration::from_millis(500)));
+
+        let spec = spec.with_iteration_count(IterationCount::Infinite);
+        assert_eq!(spec.active_duration(), None);
+    }
+}
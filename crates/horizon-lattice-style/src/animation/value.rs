@@ -0,0 +1,367 @@
+//! Animatable properties and interpolation of computed values.
+
+use horizon_lattice_render::{BoxShadow, Color, CornerRadii, Paint};
+
+use crate::style::{ComputedStyle, StyleProperties};
+
+/// A computed style property that transitions and keyframe animations can
+/// interpolate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnimatableProperty {
+    /// `background` / `background-color`.
+    Background,
+    /// `color`.
+    Color,
+    /// `border-color`.
+    BorderColor,
+    /// `border-width`.
+    BorderWidth,
+    /// `border-radius`.
+    BorderRadius,
+    /// `margin`.
+    Margin,
+    /// `padding`.
+    Padding,
+    /// `width`.
+    Width,
+    /// `height`.
+    Height,
+    /// `min-width`.
+    MinWidth,
+    /// `min-height`.
+    MinHeight,
+    /// `max-width`.
+    MaxWidth,
+    /// `max-height`.
+    MaxHeight,
+    /// `font-size`.
+    FontSize,
+    /// `line-height`.
+    LineHeight,
+    /// `letter-spacing`.
+    LetterSpacing,
+    /// `opacity`.
+    Opacity,
+    /// `box-shadow`.
+    BoxShadow,
+}
+
+impl AnimatableProperty {
+    /// All animatable properties.
+    pub const ALL: &'static [AnimatableProperty] = &[
+        Self::Background,
+        Self::Color,
+        Self::BorderColor,
+        Self::BorderWidth,
+        Self::BorderRadius,
+        Self::Margin,
+        Self::Padding,
+        Self::Width,
+        Self::Height,
+        Self::MinWidth,
+        Self::MinHeight,
+        Self::MaxWidth,
+        Self::MaxHeight,
+        Self::FontSize,
+        Self::LineHeight,
+        Self::LetterSpacing,
+        Self::Opacity,
+        Self::BoxShadow,
+    ];
+
+    /// Parse a CSS property name.
+    ///
+    /// Longhands such as `padding-left` map to the property they belong to.
+    pub fn from_css(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "background" | "background-color" => Some(Self::Background),
+            "color" => Some(Self::Color),
+            "border-color" => Some(Self::BorderColor),
+            "border-width" => Some(Self::BorderWidth),
+            "border-radius" => Some(Self::BorderRadius),
+            "margin" | "margin-top" | "margin-right" | "margin-bottom" | "margin-left" => {
+                Some(Self::Margin)
+            }
+            "padding" | "padding-top" | "padding-right" | "padding-bottom" | "padding-left" => {
+                Some(Self::Padding)
+            }
+            "width" => Some(Self::Width),
+            "height" => Some(Self::Height),
+            "min-width" => Some(Self::MinWidth),
+            "min-height" => Some(Self::MinHeight),
+            "max-width" => Some(Self::MaxWidth),
+            "max-height" => Some(Self::MaxHeight),
+            "font-size" => Some(Self::FontSize),
+            "line-height" => Some(Self::LineHeight),
+            "letter-spacing" => Some(Self::LetterSpacing),
+            "opacity" => Some(Self::Opacity),
+            "box-shadow" => Some(Self::BoxShadow),
+            _ => None,
+        }
+    }
+
+    /// Check whether the property is declared in a set of style properties.
+    pub fn is_declared_in(&self, props: &StyleProperties) -> bool {
+        match self {
+            Self::Background => props.background.is_set() || props.background_color.is_set(),
+            Self::Color => props.color.is_set(),
+            Self::BorderColor => props.border_color.is_set(),
+            Self::BorderWidth => props.border_width.is_set(),
+            Self::BorderRadius => props.border_radius.is_set(),
+            Self::Margin => props.margin.is_set(),
+            Self::Padding => props.padding.is_set(),
+            Self::Width => props.width.is_set(),
+            Self::Height => props.height.is_set(),
+            Self::MinWidth => props.min_width.is_set(),
+            Self::MinHeight => props.min_height.is_set(),
+            Self::MaxWidth => props.max_width.is_set(),
+            Self::MaxHeight => props.max_height.is_set(),
+            Self::FontSize => props.font_size.is_set(),
+            Self::LineHeight => props.line_height.is_set(),
+            Self::LetterSpacing => props.letter_spacing.is_set(),
+            Self::Opacity => props.opacity.is_set(),
+            Self::BoxShadow => props.box_shadow.is_set(),
+        }
+    }
+
+    /// Read the property from a computed style.
+    pub fn get(&self, style: &ComputedStyle) -> AnimatedValue {
+        match self {
+            Self::Background => AnimatedValue::Paint(style.background.clone()),
+            Self::Color => AnimatedValue::Color(style.color),
+            Self::BorderColor => AnimatedValue::Color(style.border_color),
+            Self::BorderWidth => AnimatedValue::Edges([
+                style.border_top_width,
+                style.border_right_width,
+                style.border_bottom_width,
+                style.border_left_width,
+            ]),
+            Self::BorderRadius => AnimatedValue::Radii(style.border_radius),
+            Self::Margin => AnimatedValue::Edges([
+                style.margin_top,
+                style.margin_right,
+                style.margin_bottom,
+                style.margin_left,
+            ]),
+            Self::Padding => AnimatedValue::Edges([
+                style.padding_top,
+                style.padding_right,
+                style.padding_bottom,
+                style.padding_left,
+            ]),
+            Self::Width => AnimatedValue::OptionalLength(style.width),
+            Self::Height => AnimatedValue::OptionalLength(style.height),
+            Self::MinWidth => AnimatedValue::OptionalLength(style.min_width),
+            Self::MinHeight => AnimatedValue::OptionalLength(style.min_height),
+            Self::MaxWidth => AnimatedValue::OptionalLength(style.max_width),
+            Self::MaxHeight => AnimatedValue::OptionalLength(style.max_height),
+            Self::FontSize => AnimatedValue::Number(style.font_size),
+            Self::LineHeight => AnimatedValue::Number(style.line_height),
+            Self::LetterSpacing => AnimatedValue::Number(style.letter_spacing),
+            Self::Opacity => AnimatedValue::Number(style.opacity),
+            Self::BoxShadow => AnimatedValue::Shadows(style.box_shadow.clone()),
+        }
+    }
+
+    /// Write the property to a computed style.
+    ///
+    /// Values of the wrong kind for the property are ignored.
+    pub fn set(&self, style: &mut ComputedStyle, value: AnimatedValue) {
+        match (self, value) {
+            (Self::Background, AnimatedValue::Paint(paint)) => style.background = paint,
+            (Self::Color, AnimatedValue::Color(color)) => style.color = color,
+            (Self::BorderColor, AnimatedValue::Color(color)) => style.border_color = color,
+            (Self::BorderWidth, AnimatedValue::Edges([top, right, bottom, left])) => {
+                style.border_top_width = top;
+                style.border_right_width = right;
+                style.border_bottom_width = bottom;
+                style.border_left_width = left;
+            }
+            (Self::BorderRadius, AnimatedValue::Radii(radii)) => style.border_radius = radii,
+            (Self::Margin, AnimatedValue::Edges([top, right, bottom, left])) => {
+                style.margin_top = top;
+                style.margin_right = right;
+                style.margin_bottom = bottom;
+                style.margin_left = left;
+            }
+            (Self::Padding, AnimatedValue::Edges([top, right, bottom, left])) => {
+                style.padding_top = top;
+                style.padding_right = right;
+                style.padding_bottom = bottom;
+                style.padding_left = left;
+            }
+            (Self::Width, AnimatedValue::OptionalLength(v)) => style.width = v,
+            (Self::Height, AnimatedValue::OptionalLength(v)) => style.height = v,
+            (Self::MinWidth, AnimatedValue::OptionalLength(v)) => style.min_width = v,
+            (Self::MinHeight, AnimatedValue::OptionalLength(v)) => style.min_height = v,
+            (Self::MaxWidth, AnimatedValue::OptionalLength(v)) => style.max_width = v,
+            (Self::MaxHeight, AnimatedValue::OptionalLength(v)) => style.max_height = v,
+            (Self::FontSize, AnimatedValue::Number(v)) => style.font_size = v,
+            (Self::LineHeight, AnimatedValue::Number(v)) => style.line_height = v,
+            (Self::LetterSpacing, AnimatedValue::Number(v)) => style.letter_spacing = v,
+            (Self::Opacity, AnimatedValue::Number(v)) => style.opacity = v.clamp(0.0, 1.0),
+            (Self::BoxShadow, AnimatedValue::Shadows(shadows)) => style.box_shadow = shadows,
+            _ => {}
+        }
+    }
+}
+
+/// The value of an [`AnimatableProperty`] in a computed style.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnimatedValue {
+    /// A plain number (font size, opacity, ...).
+    Number(f32),
+    /// A length that may be unset (width, max-height, ...).
+    OptionalLength(Option<f32>),
+    /// Four edges in top, right, bottom, left order.
+    Edges([f32; 4]),
+    /// A color.
+    Color(Color),
+    /// A background paint.
+    Paint(Paint),
+    /// Corner radii.
+    Radii(CornerRadii),
+    /// A list of box shadows.
+    Shadows(Vec<BoxShadow>),
+}
+
+impl AnimatedValue {
+    /// Interpolate towards `to` at progress `t`.
+    ///
+    /// Values that cannot be blended, such as a gradient and a solid color or
+    /// a set and an unset length, switch from `self` to `to` halfway through.
+    pub fn interpolate(&self, to: &Self, t: f32) -> Self {
+        match (self, to) {
+            (Self::Number(a), Self::Number(b)) => Self::Number(lerp(*a, *b, t)),
+            (Self::OptionalLength(Some(a)), Self::OptionalLength(Some(b))) => {
+                Self::OptionalLength(Some(lerp(*a, *b, t)))
+            }
+            (Self::Edges(a), Self::Edges(b)) => {
+                Self::Edges(std::array::from_fn(|i| lerp(a[i], b[i], t)))
+            }
+            (Self::Color(a), Self::Color(b)) => Self::Color(a.lerp(*b, t)),
+            (Self::Paint(Paint::Solid(a)), Self::Paint(Paint::Solid(b))) => {
+                Self::Paint(Paint::Solid(a.lerp(*b, t)))
+            }
+            (Self::Radii(a), Self::Radii(b)) => Self::Radii(CornerRadii {
+                top_left: lerp(a.top_left, b.top_left, t),
+                top_right: lerp(a.top_right, b.top_right, t),
+                bottom_right: lerp(a.bottom_right, b.bottom_right, t),
+                bottom_left: lerp(a.bottom_left, b.bottom_left, t),
+            }),
+            (Self::Shadows(a), Self::Shadows(b)) => match interpolate_shadows(a, b, t) {
+                Some(shadows) => Self::Shadows(shadows),
+                None => discrete(self, to, t),
+            },
+            _ => discrete(self, to, t),
+        }
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn discrete(from: &AnimatedValue, to: &AnimatedValue, t: f32) -> AnimatedValue {
+    if t < 0.5 { from.clone() } else { to.clone() }
+}
+
+/// Interpolate shadow lists pairwise, padding the shorter list with
+/// transparent shadows. Returns `None` if paired shadows differ in `inset`.
+fn interpolate_shadows(from: &[BoxShadow], to: &[BoxShadow], t: f32) -> Option<Vec<BoxShadow>> {
+    let len = from.len().max(to.len());
+    (0..len)
+        .map(|i| {
+            let (a, b) = match (from.get(i), to.get(i)) {
+                (Some(a), Some(b)) => (*a, *b),
+                (Some(a), None) => (*a, transparent_shadow(a)),
+                (None, Some(b)) => (transparent_shadow(b), *b),
+                (None, None) => unreachable!(),
+            };
+            (a.inset == b.inset).then(|| BoxShadow {
+                color: a.color.lerp(b.color, t),
+                offset_x: lerp(a.offset_x, b.offset_x, t),
+                offset_y: lerp(a.offset_y, b.offset_y, t),
+                blur_radius: lerp(a.blur_radius, b.blur_radius, t),
+                spread_radius: lerp(a.spread_radius, b.spread_radius, t),
+                inset: a.inset,
+            })
+        })
+        .collect()
+}
+
+fn transparent_shadow(like: &BoxShadow) -> BoxShadow {
+    BoxShadow {
+        color: Color::TRANSPARENT,
+        offset_x: 0.0,
+        offset_y: 0.0,
+        blur_radius: 0.0,
+        spread_radius: 0.0,
+        inset: like.inset,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interpolates_colors_and_numbers() {
+        let from = AnimatedValue::Color(Color::BLACK);
+        let to = AnimatedValue::Color(Color::WHITE);
+        let AnimatedValue::Color(mid) = from.interpolate(&to, 0.5) else {
+            panic!("expected a color");
+        };
+        assert!((mid.r - 0.5).abs() < 1e-6);
+
+        let from = AnimatedValue::Edges([0.0, 4.0, 8.0, 12.0]);
+        let to = AnimatedValue::Edges([4.0, 4.0, 0.0, 0.0]);
+        assert_eq!(
+            from.interpolate(&to, 0.25),
+            AnimatedValue::Edges([1.0, 4.0, 6.0, 9.0])
+        );
+    }
+
+    #[test]
+    fn unset_lengths_switch_halfway() {
+        let from = AnimatedValue::OptionalLength(None);
+        let to = AnimatedValue::OptionalLength(Some(100.0));
+        assert_eq!(from.interpolate(&to, 0.4), from);
+        assert_eq!(from.interpolate(&to, 0.6), to);
+    }
+
+    #[test]
+    fn shadow_lists_are_padded() {
+        let shadow = BoxShadow {
+            color: Color::BLACK,
+            offset_x: 0.0,
+            offset_y: 4.0,
+            blur_radius: 8.0,
+            spread_radius: 0.0,
+            inset: false,
+        };
+        let from = AnimatedValue::Shadows(vec![]);
+        let to = AnimatedValue::Shadows(vec![shadow]);
+        let AnimatedValue::Shadows(mid) = from.interpolate(&to, 0.5) else {
+            panic!("expected shadows");
+        };
+        assert_eq!(mid.len(), 1);
+        assert_eq!(mid[0].offset_y, 2.0);
+        assert_eq!(mid[0].blur_radius, 4.0);
+    }
+
+    #[test]
+    fn property_round_trip() {
+        let mut style = ComputedStyle::default();
+        AnimatableProperty::Padding.set(&mut style, AnimatedValue::Edges([1.0, 2.0, 3.0, 4.0]));
+        assert_eq!(style.padding_left, 4.0);
+        assert_eq!(
+            AnimatableProperty::Padding.get(&style),
+            AnimatedValue::Edges([1.0, 2.0, 3.0, 4.0])
+        );
+        assert_eq!(
+            AnimatableProperty::from_css("padding-left"),
+            Some(AnimatableProperty::Padding)
+        );
+    }
+}
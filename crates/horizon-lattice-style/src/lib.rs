@@ -4,8 +4,10 @@
 
 #![warn(missing_docs)]
 //!
-//! - **Selectors**: Type, class, ID, pseudo-class, and combinator selectors
+//! - **Selectors**: Type, class, ID, attribute, pseudo-class, and combinator selectors
 //! - **Cascading**: Style priority and specificity-based resolution
+//! - **Media Queries**: `@media` rules for color scheme, contrast, window size and resolution
+//! - **Animation**: CSS transitions and `@keyframes` animations
 //! - **CSS Parsing**: Load styles from external .css files
 //! - **Hot Reload**: Automatically reload stylesheets during development
 //! - **Type-safe DSL**: Build styles programmatically with Rust
@@ -65,6 +67,7 @@
 //! engine.add_stylesheet(stylesheet);
 //! ```
 
+pub mod animation;
 pub mod icon_theme;
 pub mod parser;
 pub mod resolve;
@@ -84,16 +87,25 @@ pub use error::{Error, Result};
 
 /// Prelude module with commonly used types.
 pub mod prelude {
+    pub use crate::animation::{
+        AnimatableProperty, AnimationClock, AnimationDirection, AnimationFillMode, AnimationSpec,
+        Easing, IterationCount, Keyframes, TransitionProperty, TransitionSpec,
+    };
     pub use crate::icon_theme::{IconContext, IconLookup, IconName, IconResolver, IconThemeLoader};
     pub use crate::resolve::{StyleContext, StyleEngine, WidgetStyleState};
-    pub use crate::rules::{StylePriority, StyleRule, StyleSheet};
-    pub use crate::selector::{Combinator, PseudoClass, Selector, SelectorPart, Specificity};
+    pub use crate::rules::{
+        ColorSchemePreference, ContrastPreference, MediaEnvironment, MediaQueryList, StylePriority,
+        StyleRule, StyleSheet,
+    };
+    pub use crate::selector::{
+        Combinator, PseudoClass, PseudoElement, Selector, SelectorPart, Specificity,
+    };
     pub use crate::style::{ComputedStyle, Style, StyleProperties};
     pub use crate::theme::{Theme, ThemeVariables};
     pub use crate::types::{BorderStyle, Cursor, EdgeValues, LengthValue, StyleValue, TextAlign};
     pub use crate::widget::{
-        StylePaintContext, StyledWidget, border_box_size, content_rect, margin_rect,
-        paint_background, paint_border, paint_styled_box,
+        StylePaintContext, StyledWidget, border_box_size, content_rect, has_visible_box,
+        margin_rect, paint_background, paint_border, paint_styled_box,
     };
 
     #[cfg(feature = "hot-reload")]
@@ -4,12 +4,24 @@
 //! tokenizes CSS input and constructs [`StyleRule`] objects containing selectors
 //! and their associated style properties.
 
-use crate::rules::StyleRule;
-use crate::selector::{Combinator, NthExpr, PseudoClass, Selector, SelectorPart, TypeSelector};
+use std::time::Duration;
+
+use crate::animation::{
+    AnimationDirection, AnimationFillMode, AnimationSpec, Easing, IterationCount, Keyframes,
+    TransitionProperty, TransitionSpec,
+};
+use crate::rules::{
+    ColorSchemePreference, ContrastPreference, MediaComparison, MediaFeature, MediaQuery,
+    MediaQueryList, MediaType, StyleRule,
+};
+use crate::selector::{
+    AttributeOperator, AttributeSelector, Combinator, NthExpr, PseudoClass, PseudoElement,
+    Selector, SelectorPart, TypeSelector,
+};
 use crate::style::StyleProperties;
 use crate::types::{BorderStyle, Cursor, EdgeValues, LengthValue, StyleValue, TextAlign};
 use crate::{Error, Result};
-use cssparser::{ParseError as CssParseError, Parser, ParserInput, Token};
+use cssparser::{Delimiter, ParseError as CssParseError, Parser, ParserInput, Token};
 use horizon_lattice_render::{
     BoxShadow, Color, CornerRadii,
     text::{FontFamily, FontStyle, FontWeight},
@@ -49,11 +61,44 @@ use horizon_lattice_render::{
 /// assert_eq!(rules.len(), 2);
 /// ```
 pub fn parse_css(css: &str) -> Result<Vec<StyleRule>> {
+    parse_stylesheet(css).map(|parsed| parsed.rules)
+}
+
+/// The contents of a parsed stylesheet.
+#[derive(Debug, Clone, Default)]
+pub struct ParsedStyleSheet {
+    /// Style rules in source order.
+    pub rules: Vec<StyleRule>,
+    /// `@keyframes` rules in source order.
+    pub keyframes: Vec<Keyframes>,
+}
+
+/// Parse a CSS stylesheet string into style rules and at-rules.
+///
+/// Like [`parse_css`], but also returns the `@keyframes` rules of the
+/// stylesheet. Rules inside `@media` blocks carry the block's media query
+/// list in [`StyleRule::media`]. Unsupported at-rules are skipped with a
+/// warning.
+pub fn parse_stylesheet(css: &str) -> Result<ParsedStyleSheet> {
     let mut input = ParserInput::new(css);
     let mut parser = Parser::new(&mut input);
-    let mut rules = vec![];
+    let mut parsed = ParsedStyleSheet::default();
     let mut order = 0u32;
 
+    parse_rule_list(&mut parser, &[], &mut parsed, &mut order);
+
+    Ok(parsed)
+}
+
+/// Parse rules and at-rules until the input is exhausted.
+///
+/// `media` holds the query lists of the enclosing `@media` blocks.
+fn parse_rule_list<'i>(
+    parser: &mut Parser<'i, '_>,
+    media: &[MediaQueryList],
+    parsed: &mut ParsedStyleSheet,
+    order: &mut u32,
+) {
     loop {
         // Skip whitespace and comments
         parser.skip_whitespace();
@@ -62,20 +107,288 @@ pub fn parse_css(css: &str) -> Result<Vec<StyleRule>> {
             break;
         }
 
-        match parse_rule(&mut parser, order) {
-            Ok(rule) => {
-                rules.push(rule);
-                order += 1;
+        let state = parser.state();
+        if let Ok(Token::AtKeyword(name)) = parser.next() {
+            let name = name.to_ascii_lowercase();
+            let result = match name.as_str() {
+                "keyframes" => parse_keyframes(parser).map(|k| parsed.keyframes.push(k)),
+                "media" => parse_media_block(parser, media, parsed, order),
+                _ => Err(Error::parse(
+                    format!("Unsupported at-rule '@{}'", name),
+                    0,
+                    0,
+                )),
+            };
+            if let Err(e) = result {
+                tracing::warn!("CSS parse error: {}", e);
+                skip_to_next_rule(parser);
+            }
+            continue;
+        }
+        parser.reset(&state);
+
+        match parse_rule(parser, *order) {
+            Ok(mut rule) => {
+                rule.media = media.to_vec();
+                parsed.rules.push(rule);
+                *order += 1;
             }
             Err(e) => {
                 tracing::warn!("CSS parse error: {}", e);
                 // Try to recover by skipping to next rule
-                skip_to_next_rule(&mut parser);
+                skip_to_next_rule(parser);
             }
         }
     }
+}
+
+/// Parse the prelude and block of a `@media` rule.
+fn parse_media_block<'i>(
+    parser: &mut Parser<'i, '_>,
+    media: &[MediaQueryList],
+    parsed: &mut ParsedStyleSheet,
+    order: &mut u32,
+) -> Result<()> {
+    let queries = parser
+        .parse_until_before(Delimiter::CurlyBracketBlock, |p| {
+            p.parse_comma_separated(parse_media_query)
+        })
+        .map_err(|e: CssParseError<'_, ()>| {
+            Error::parse(format!("Invalid media query: {:?}", e), 0, 0)
+        })?;
+    if !matches!(parser.next(), Ok(Token::CurlyBracketBlock)) {
+        return Err(Error::parse("Expected '{' after @media query", 0, 0));
+    }
+
+    let mut nested = media.to_vec();
+    nested.push(MediaQueryList::new(queries));
+    parser
+        .parse_nested_block(|block| {
+            parse_rule_list(block, &nested, parsed, order);
+            Ok::<_, CssParseError<'_, ()>>(())
+        })
+        .map_err(|e| Error::parse(format!("Failed to parse @media block: {:?}", e), 0, 0))
+}
+
+/// Parse one media query: `[not|only] [type] [and (feature)]*` or
+/// `(feature) [and (feature)]*`.
+fn parse_media_query<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> std::result::Result<MediaQuery, CssParseError<'i, ()>> {
+    let mut query = MediaQuery::default();
+    let mut expect_feature = true;
+
+    loop {
+        let state = parser.state();
+        match parser.next() {
+            Ok(Token::Ident(ident)) => {
+                let ident = ident.to_ascii_lowercase();
+                match ident.as_str() {
+                    "not" if expect_feature => query.negated = true,
+                    "only" if expect_feature => {}
+                    "and" if !expect_feature => expect_feature = true,
+                    "all" | "screen" | "print" if expect_feature => {
+                        query.media_type = match ident.as_str() {
+                            "screen" => MediaType::Screen,
+                            "print" => MediaType::Print,
+                            _ => MediaType::All,
+                        };
+                        expect_feature = false;
+                    }
+                    _ => return Err(parser.new_custom_error(())),
+                }
+            }
+            Ok(Token::ParenthesisBlock) if expect_feature => {
+                let feature = parser.parse_nested_block(parse_media_feature)?;
+                query.features.push(feature);
+                expect_feature = false;
+            }
+            Err(_) if !expect_feature => {
+                parser.reset(&state);
+                break;
+            }
+            _ => return Err(parser.new_custom_error(())),
+        }
+    }
+
+    Ok(query)
+}
+
+/// Parse the contents of a parenthesized media feature.
+///
+/// Supports `name: value`, `name <op> value` and `value <op> name`, where
+/// `min-` and `max-` prefixes map to `>=` and `<=`.
+fn parse_media_feature<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> std::result::Result<MediaFeature, CssParseError<'i, ()>> {
+    let state = parser.state();
+    let (name, comparison, value) = match parser.next()?.clone() {
+        Token::Ident(name) => {
+            let name = name.to_ascii_lowercase();
+            if parser.try_parse(|p| p.expect_colon()).is_ok() {
+                let (comparison, name) = if let Some(name) = name.strip_prefix("min-") {
+                    (MediaComparison::GreaterOrEqual, name.to_string())
+                } else if let Some(name) = name.strip_prefix("max-") {
+                    (MediaComparison::LessOrEqual, name.to_string())
+                } else {
+                    (MediaComparison::Equal, name)
+                };
+                (name, comparison, parser.next()?.clone())
+            } else {
+                let comparison = parse_media_comparison(parser)?;
+                (name, comparison, parser.next()?.clone())
+            }
+        }
+        _ => {
+            parser.reset(&state);
+            let value = parser.next()?.clone();
+            let comparison = parse_media_comparison(parser)?.flipped();
+            let name = parser.expect_ident()?.to_ascii_lowercase();
+            (name, comparison, value)
+        }
+    };
+    parser.expect_exhausted()?;
+
+    let invalid = || parser.new_custom_error::<_, ()>(());
+    let feature = match name.as_str() {
+        "prefers-color-scheme" => match &value {
+            Token::Ident(v) if v.eq_ignore_ascii_case("light") => {
+                MediaFeature::PrefersColorScheme(ColorSchemePreference::Light)
+            }
+            Token::Ident(v) if v.eq_ignore_ascii_case("dark") => {
+                MediaFeature::PrefersColorScheme(ColorSchemePreference::Dark)
+            }
+            _ => return Err(invalid()),
+        },
+        "prefers-contrast" => match &value {
+            Token::Ident(v) if v.eq_ignore_ascii_case("more") => {
+                MediaFeature::PrefersContrast(ContrastPreference::More)
+            }
+            Token::Ident(v) if v.eq_ignore_ascii_case("less") => {
+                MediaFeature::PrefersContrast(ContrastPreference::Less)
+            }
+            Token::Ident(v) if v.eq_ignore_ascii_case("no-preference") => {
+                MediaFeature::PrefersContrast(ContrastPreference::NoPreference)
+            }
+            _ => return Err(invalid()),
+        },
+        "width" => MediaFeature::Width(comparison, media_length(&value).ok_or_else(invalid)?),
+        "height" => MediaFeature::Height(comparison, media_length(&value).ok_or_else(invalid)?),
+        "resolution" => {
+            MediaFeature::Resolution(comparison, media_resolution(&value).ok_or_else(invalid)?)
+        }
+        _ => {
+            tracing::warn!("Unsupported media feature '{}'", name);
+            MediaFeature::Unknown(name)
+        }
+    };
+    Ok(feature)
+}
+
+/// Parse a range comparison operator (`<`, `<=`, `>`, `>=`, `=`).
+fn parse_media_comparison<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> std::result::Result<MediaComparison, CssParseError<'i, ()>> {
+    let comparison = match parser.next()? {
+        Token::Delim('=') => return Ok(MediaComparison::Equal),
+        Token::Delim('<') => MediaComparison::Less,
+        Token::Delim('>') => MediaComparison::Greater,
+        _ => return Err(parser.new_custom_error(())),
+    };
+    // `<=` and `>=` are tokenized as two delimiters without whitespace between them
+    let or_equal = parser
+        .try_parse(|p| match p.next_including_whitespace()? {
+            Token::Delim('=') => Ok(()),
+            _ => Err(p.new_custom_error::<_, ()>(())),
+        })
+        .is_ok();
+    Ok(match (comparison, or_equal) {
+        (MediaComparison::Less, true) => MediaComparison::LessOrEqual,
+        (MediaComparison::Greater, true) => MediaComparison::GreaterOrEqual,
+        (comparison, _) => comparison,
+    })
+}
+
+/// Convert a media query length to logical pixels.
+fn media_length(token: &Token<'_>) -> Option<f32> {
+    match token {
+        Token::Dimension { value, unit, .. } => match unit.to_ascii_lowercase().as_str() {
+            "px" => Some(*value),
+            "em" | "rem" => Some(*value * 16.0),
+            _ => None,
+        },
+        Token::Number { value, .. } if *value == 0.0 => Some(0.0),
+        _ => None,
+    }
+}
+
+/// Convert a media query resolution to `dppx`.
+fn media_resolution(token: &Token<'_>) -> Option<f32> {
+    match token {
+        Token::Dimension { value, unit, .. } => match unit.to_ascii_lowercase().as_str() {
+            "dppx" | "x" => Some(*value),
+            "dpi" => Some(*value / 96.0),
+            "dpcm" => Some(*value * 2.54 / 96.0),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Parse the prelude and block of a `@keyframes` rule.
+fn parse_keyframes<'i>(parser: &mut Parser<'i, '_>) -> Result<Keyframes> {
+    let name = match parser.next() {
+        Ok(Token::Ident(name)) | Ok(Token::QuotedString(name)) => name.to_string(),
+        _ => {
+            return Err(Error::parse("Expected a name after '@keyframes'", 0, 0));
+        }
+    };
+    if !matches!(parser.next(), Ok(Token::CurlyBracketBlock)) {
+        return Err(Error::parse("Expected '{' after @keyframes name", 0, 0));
+    }
+
+    parser
+        .parse_nested_block(|block| {
+            let mut keyframes = Keyframes::new(name);
+            loop {
+                block.skip_whitespace();
+                if block.is_exhausted() {
+                    break;
+                }
+
+                let offsets = block.parse_until_before(Delimiter::CurlyBracketBlock, |p| {
+                    p.parse_comma_separated(parse_keyframe_offset)
+                });
+                let Ok(offsets) = offsets else {
+                    tracing::warn!("Invalid keyframe selector in @keyframes {}", keyframes.name);
+                    skip_to_next_rule(block);
+                    continue;
+                };
+
+                block.expect_curly_bracket_block()?;
+                let properties = block.parse_nested_block(|p| parse_declarations(p))?;
+                for offset in offsets {
+                    keyframes.add_frame(offset, properties.clone());
+                }
+            }
+            Ok(keyframes)
+        })
+        .map_err(|e: CssParseError<'_, ()>| {
+            Error::parse(format!("Failed to parse @keyframes block: {:?}", e), 0, 0)
+        })
+}
 
-    Ok(rules)
+/// Parse a keyframe selector: `from`, `to` or a percentage.
+fn parse_keyframe_offset<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> std::result::Result<f32, CssParseError<'i, ()>> {
+    let location = parser.current_source_location();
+    match parser.next()?.clone() {
+        Token::Ident(s) if s.eq_ignore_ascii_case("from") => Ok(0.0),
+        Token::Ident(s) if s.eq_ignore_ascii_case("to") => Ok(1.0),
+        Token::Percentage { unit_value, .. } if (0.0..=1.0).contains(&unit_value) => Ok(unit_value),
+        _ => Err(location.new_custom_error(())),
+    }
 }
 
 /// Parse a single CSS rule: selector { declarations }
@@ -130,6 +443,7 @@ fn parse_selector<'i>(parser: &mut Parser<'i, '_>) -> Result<Selector> {
                 if current_part.type_selector.is_none()
                     && current_part.id.is_none()
                     && current_part.classes.is_empty()
+                    && current_part.attributes.is_empty()
                 {
                     current_part.type_selector = Some(TypeSelector::Type(name.to_string()));
                 } else if !is_empty_part(&current_part) {
@@ -161,6 +475,16 @@ fn parse_selector<'i>(parser: &mut Parser<'i, '_>) -> Result<Selector> {
                 current_part.classes.push(class.to_string());
             }
 
+            Token::SquareBracketBlock => {
+                // Attribute selector
+                let attribute = parser
+                    .parse_nested_block(|p| parse_attribute_selector(p))
+                    .map_err(|_: CssParseError<'_, ()>| {
+                        Error::invalid_selector("[", "Invalid attribute selector")
+                    })?;
+                current_part.attributes.push(attribute);
+            }
+
             Token::IDHash(id) => {
                 // ID selector - might start a new part if we already have content
                 if !is_empty_part(&current_part) && current_part.id.is_none() {
@@ -178,6 +502,23 @@ fn parse_selector<'i>(parser: &mut Parser<'i, '_>) -> Result<Selector> {
                 }
             }
 
+            Token::Colon if parser.try_parse(|p| p.expect_colon()).is_ok() => {
+                // Pseudo-element (sub-control)
+                let element_name = parser.expect_ident().map_err(|_| {
+                    Error::invalid_selector("::", "Expected pseudo-element name after '::'")
+                })?;
+
+                let element = PseudoElement::from_css(element_name).ok_or_else(|| {
+                    Error::invalid_selector(format!("::{}", element_name), "Unknown pseudo-element")
+                })?;
+                if current_part.pseudo_element.replace(element).is_some() {
+                    return Err(Error::invalid_selector(
+                        format!("::{}", element_name),
+                        "Only one pseudo-element is allowed per selector part",
+                    ));
+                }
+            }
+
             Token::Colon => {
                 // Pseudo-class
                 let pseudo_name = parser.expect_ident().map_err(|_| {
@@ -196,6 +537,7 @@ fn parse_selector<'i>(parser: &mut Parser<'i, '_>) -> Result<Selector> {
                     "last-child" => PseudoClass::LastChild,
                     "only-child" => PseudoClass::OnlyChild,
                     "empty" => PseudoClass::Empty,
+                    "root" => PseudoClass::Root,
                     "nth-child" => {
                         // Parse nth-child expression
                         let expr = parser.parse_nested_block(|p| parse_nth_expr(p)).map_err(
@@ -284,6 +626,43 @@ fn is_empty_part(part: &SelectorPart) -> bool {
         && part.id.is_none()
         && part.classes.is_empty()
         && part.pseudo_classes.is_empty()
+        && part.attributes.is_empty()
+        && part.pseudo_element.is_none()
+}
+
+/// Parse the contents of an attribute selector (e.g., `severity="error"`).
+fn parse_attribute_selector<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> std::result::Result<AttributeSelector, CssParseError<'i, ()>> {
+    let name = parser.expect_ident()?.to_string();
+
+    if parser.is_exhausted() {
+        return Ok(AttributeSelector::exists(name));
+    }
+
+    let location = parser.current_source_location();
+    let make_operator: fn(String) -> AttributeOperator = match parser.next()?.clone() {
+        Token::Delim('=') => AttributeOperator::Equals,
+        Token::IncludeMatch => AttributeOperator::Includes,
+        Token::DashMatch => AttributeOperator::DashMatch,
+        Token::PrefixMatch => AttributeOperator::Prefix,
+        Token::SuffixMatch => AttributeOperator::Suffix,
+        Token::SubstringMatch => AttributeOperator::Substring,
+        token => return Err(location.new_unexpected_token_error(token)),
+    };
+
+    let location = parser.current_source_location();
+    let value = match parser.next()?.clone() {
+        Token::QuotedString(s) | Token::Ident(s) => s.to_string(),
+        Token::Number { value, .. } => value.to_string(),
+        token => return Err(location.new_unexpected_token_error(token)),
+    };
+    parser.expect_exhausted()?;
+
+    Ok(AttributeSelector {
+        name,
+        operator: make_operator(value),
+    })
 }
 
 /// Parse a simple selector (for :not() argument).
@@ -309,6 +688,10 @@ fn parse_simple_selector<'i>(
             Token::IDHash(id) => {
                 part.id = Some(id.to_string());
             }
+            Token::SquareBracketBlock => {
+                let attribute = parser.parse_nested_block(|p| parse_attribute_selector(p))?;
+                part.attributes.push(attribute);
+            }
             _ => break,
         }
     }
@@ -384,6 +767,28 @@ fn parse_declarations<'i>(
             continue;
         }
 
+        // Custom properties keep their raw value for later var() substitution
+        if let Some(name) = property_name.strip_prefix("--") {
+            let value = consume_declaration_value(parser);
+            props
+                .custom_properties
+                .insert(name.to_string(), value.to_string());
+            let _ = parser.try_parse(|p| p.expect_semicolon());
+            continue;
+        }
+
+        // Values referencing var() are resolved during cascade
+        let state = parser.state();
+        let value = consume_declaration_value(parser);
+        if contains_var_reference(value) {
+            props
+                .variable_declarations
+                .push((property_name, value.to_string()));
+            let _ = parser.try_parse(|p| p.expect_semicolon());
+            continue;
+        }
+        parser.reset(&state);
+
         // Parse property value
         if let Err(e) = parse_property_value(parser, &property_name, &mut props) {
             tracing::warn!("Failed to parse property '{}': {:?}", property_name, e);
@@ -391,6 +796,14 @@ fn parse_declarations<'i>(
             continue;
         }
 
+        // Declarations after a var() one are applied after it too, keeping
+        // source order
+        if !props.variable_declarations.is_empty() {
+            props
+                .variable_declarations
+                .push((property_name, value.to_string()));
+        }
+
         // Skip optional semicolon
         let _ = parser.try_parse(|p| p.expect_semicolon());
     }
@@ -398,6 +811,53 @@ fn parse_declarations<'i>(
     Ok(props)
 }
 
+/// Parse a single declaration whose value is given as text.
+///
+/// Used to parse values after `var()` substitution. Returns `None` if the
+/// value cannot be parsed for the property.
+pub(crate) fn parse_declaration(name: &str, value: &str) -> Option<StyleProperties> {
+    let mut input = ParserInput::new(value);
+    let mut parser = Parser::new(&mut input);
+    let mut props = StyleProperties::default();
+    parse_property_value(&mut parser, name, &mut props).ok()?;
+    Some(props)
+}
+
+/// Consume a declaration value up to (but not including) the next `;`,
+/// returning its source text.
+fn consume_declaration_value<'i>(parser: &mut Parser<'i, '_>) -> &'i str {
+    parser.skip_whitespace();
+    let start = parser.position();
+    loop {
+        let state = parser.state();
+        match parser.next_including_whitespace() {
+            Ok(Token::Semicolon) | Err(_) => {
+                parser.reset(&state);
+                break;
+            }
+            Ok(
+                Token::Function(_)
+                | Token::ParenthesisBlock
+                | Token::SquareBracketBlock
+                | Token::CurlyBracketBlock,
+            ) => {
+                // Consume the block now so resetting to a later state keeps it
+                let _ = parser.parse_nested_block(|p| {
+                    while p.next_including_whitespace().is_ok() {}
+                    Ok::<_, CssParseError<'_, ()>>(())
+                });
+            }
+            Ok(_) => {}
+        }
+    }
+    parser.slice_from(start).trim()
+}
+
+/// Check whether a declaration value contains a `var()` reference.
+fn contains_var_reference(value: &str) -> bool {
+    value.to_ascii_lowercase().contains("var(")
+}
+
 /// Parse a single property value.
 fn parse_property_value<'i>(
     parser: &mut Parser<'i, '_>,
@@ -414,6 +874,7 @@ fn parse_property_value<'i>(
         match ident.as_ref() {
             "inherit" => {
                 set_inherit(name, props);
+                forget_initial(name, props);
                 return Ok(());
             }
             "initial" => {
@@ -422,6 +883,7 @@ fn parse_property_value<'i>(
             }
             "unset" => {
                 set_unset(name, props);
+                forget_initial(name, props);
                 return Ok(());
             }
             _ => {
@@ -534,6 +996,14 @@ fn parse_property_value<'i>(
             }
         }
 
+        // === Animation ===
+        "transition" => {
+            props.transition = StyleValue::Set(parse_list(parser, parse_transition)?);
+        }
+        "animation" => {
+            props.animation = StyleValue::Set(parse_list(parser, parse_animation)?);
+        }
+
         // === Interaction ===
         "cursor" => {
             if let Ok(Token::Ident(s)) = parser.next()
@@ -577,6 +1047,7 @@ fn parse_property_value<'i>(
         }
     }
 
+    forget_initial(name, props);
     Ok(())
 }
 
@@ -601,20 +1072,87 @@ fn set_inherit(name: &str, props: &mut StyleProperties) {
 }
 
 fn set_initial(name: &str, props: &mut StyleProperties) {
+    for field in property_fields(name) {
+        props.set_initial(field);
+    }
+}
+
+/// Drop an earlier `initial` declaration of a property that is declared again.
+fn forget_initial(name: &str, props: &mut StyleProperties) {
+    let fields = property_fields(name);
+    props
+        .initial_properties
+        .retain(|field| !fields.contains(field));
+}
+
+/// Get the [`StyleProperties`] fields a property declaration sets.
+fn property_fields(name: &str) -> &'static [&'static str] {
     match name {
-        "color" => props.color = StyleValue::Initial,
-        "font-size" => props.font_size = StyleValue::Initial,
-        "margin" => props.margin = StyleValue::Initial,
-        "padding" => props.padding = StyleValue::Initial,
-        "background-color" => props.background_color = StyleValue::Initial,
-        _ => {}
+        "margin" | "margin-top" | "margin-right" | "margin-bottom" | "margin-left" => &["margin"],
+        "padding" | "padding-top" | "padding-right" | "padding-bottom" | "padding-left" => {
+            &["padding"]
+        }
+        "border-width" => &["border_width"],
+        "border-color" => &["border_color"],
+        "border-style" => &["border_style"],
+        "border-radius" => &["border_radius"],
+        "background" => &["background", "background_color"],
+        "background-color" => &["background_color"],
+        "color" => &["color"],
+        "font-size" => &["font_size"],
+        "font-weight" => &["font_weight"],
+        "font-style" => &["font_style"],
+        "font-family" => &["font_family"],
+        "text-align" => &["text_align"],
+        "line-height" => &["line_height"],
+        "opacity" => &["opacity"],
+        "box-shadow" => &["box_shadow"],
+        "transition" => &["transition"],
+        "animation" => &["animation"],
+        "cursor" => &["cursor"],
+        "pointer-events" => &["pointer_events"],
+        "width" => &["width"],
+        "height" => &["height"],
+        "min-width" => &["min_width"],
+        "min-height" => &["min_height"],
+        "max-width" => &["max_width"],
+        "max-height" => &["max_height"],
+        _ => &[],
     }
 }
 
 fn set_unset(name: &str, props: &mut StyleProperties) {
     match name {
+        "margin" | "margin-top" | "margin-right" | "margin-bottom" | "margin-left" => {
+            props.margin = StyleValue::Unset
+        }
+        "padding" | "padding-top" | "padding-right" | "padding-bottom" | "padding-left" => {
+            props.padding = StyleValue::Unset
+        }
+        "border-width" => props.border_width = StyleValue::Unset,
+        "border-color" => props.border_color = StyleValue::Unset,
+        "border-style" => props.border_style = StyleValue::Unset,
+        "border-radius" => props.border_radius = StyleValue::Unset,
+        "background-color" | "background" => props.background_color = StyleValue::Unset,
         "color" => props.color = StyleValue::Unset,
         "font-size" => props.font_size = StyleValue::Unset,
+        "font-weight" => props.font_weight = StyleValue::Unset,
+        "font-style" => props.font_style = StyleValue::Unset,
+        "font-family" => props.font_family = StyleValue::Unset,
+        "text-align" => props.text_align = StyleValue::Unset,
+        "line-height" => props.line_height = StyleValue::Unset,
+        "opacity" => props.opacity = StyleValue::Unset,
+        "box-shadow" => props.box_shadow = StyleValue::Unset,
+        "transition" => props.transition = StyleValue::Unset,
+        "animation" => props.animation = StyleValue::Unset,
+        "cursor" => props.cursor = StyleValue::Unset,
+        "pointer-events" => props.pointer_events = StyleValue::Unset,
+        "width" => props.width = StyleValue::Unset,
+        "height" => props.height = StyleValue::Unset,
+        "min-width" => props.min_width = StyleValue::Unset,
+        "min-height" => props.min_height = StyleValue::Unset,
+        "max-width" => props.max_width = StyleValue::Unset,
+        "max-height" => props.max_height = StyleValue::Unset,
         _ => {}
     }
 }
@@ -972,6 +1510,177 @@ fn parse_box_shadow<'i>(
     })
 }
 
+/// Parse a comma-separated list of values up to the end of the declaration.
+///
+/// The keyword `none` yields an empty list.
+fn parse_list<'i, T>(
+    parser: &mut Parser<'i, '_>,
+    parse_item: fn(&mut Parser<'i, '_>) -> std::result::Result<T, CssParseError<'i, ()>>,
+) -> std::result::Result<Vec<T>, CssParseError<'i, ()>> {
+    parser.parse_until_before(Delimiter::Semicolon, |p| {
+        if p.try_parse(|p| p.expect_ident_matching("none")).is_ok() {
+            p.expect_exhausted()?;
+            return Ok(vec![]);
+        }
+        p.parse_comma_separated(parse_item)
+    })
+}
+
+/// Parse one entry of a `transition` value: `<property> <duration> <easing> <delay>`.
+///
+/// The parts may appear in any order; the first time is the duration and the
+/// second the delay.
+fn parse_transition<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> std::result::Result<TransitionSpec, CssParseError<'i, ()>> {
+    let mut property = None;
+    let mut times = vec![];
+    let mut easing = None;
+
+    while !parser.is_exhausted() {
+        let location = parser.current_source_location();
+        let token = parser.next()?.clone();
+        if let Some(time) = parse_time(&token) {
+            times.push(time);
+            continue;
+        }
+        match token {
+            Token::Ident(name) if easing.is_none() && parse_easing(&name).is_some() => {
+                easing = parse_easing(&name);
+            }
+            Token::Ident(name) if property.is_none() => {
+                property = Some(
+                    TransitionProperty::from_css(&name)
+                        .ok_or_else(|| location.new_custom_error(()))?,
+                );
+            }
+            _ => return Err(location.new_custom_error(())),
+        }
+    }
+
+    if times.len() > 2 {
+        return Err(parser.new_custom_error(()));
+    }
+    let mut spec = TransitionSpec::new(
+        property.unwrap_or(TransitionProperty::All),
+        times.first().copied().unwrap_or_default(),
+    );
+    if let Some(delay) = times.get(1) {
+        spec.delay = *delay;
+    }
+    if let Some(easing) = easing {
+        spec.easing = easing;
+    }
+    Ok(spec)
+}
+
+/// Parse one entry of an `animation` value, such as
+/// `pulse 1s ease-in-out 200ms infinite alternate both`.
+///
+/// The parts may appear in any order; the first time is the duration and the
+/// second the delay. The identifier that is not a keyword is the name.
+fn parse_animation<'i>(
+    parser: &mut Parser<'i, '_>,
+) -> std::result::Result<AnimationSpec, CssParseError<'i, ()>> {
+    let mut name = None;
+    let mut times = vec![];
+    let mut easing = None;
+    let mut iteration_count = None;
+    let mut direction = None;
+    let mut fill_mode = None;
+
+    while !parser.is_exhausted() {
+        let location = parser.current_source_location();
+        let token = parser.next()?.clone();
+        if let Some(time) = parse_time(&token) {
+            times.push(time);
+            continue;
+        }
+        match token {
+            Token::Number { value, .. } if iteration_count.is_none() && value >= 0.0 => {
+                iteration_count = Some(IterationCount::Count(value));
+            }
+            Token::Ident(ident) => {
+                if iteration_count.is_none() && ident.eq_ignore_ascii_case("infinite") {
+                    iteration_count = Some(IterationCount::Infinite);
+                } else if easing.is_none() && parse_easing(&ident).is_some() {
+                    easing = parse_easing(&ident);
+                } else if direction.is_none() && AnimationDirection::from_css(&ident).is_some() {
+                    direction = AnimationDirection::from_css(&ident);
+                } else if fill_mode.is_none() && AnimationFillMode::from_css(&ident).is_some() {
+                    fill_mode = AnimationFillMode::from_css(&ident);
+                } else if name.is_none() {
+                    name = Some(ident.to_string());
+                } else {
+                    return Err(location.new_custom_error(()));
+                }
+            }
+            Token::QuotedString(s) if name.is_none() => name = Some(s.to_string()),
+            _ => return Err(location.new_custom_error(())),
+        }
+    }
+
+    let Some(name) = name else {
+        return Err(parser.new_custom_error(()));
+    };
+    if times.len() > 2 {
+        return Err(parser.new_custom_error(()));
+    }
+    let mut spec = AnimationSpec::new(name, times.first().copied().unwrap_or_default());
+    if let Some(delay) = times.get(1) {
+        spec.delay = *delay;
+    }
+    if let Some(easing) = easing {
+        spec.easing = easing;
+    }
+    if let Some(count) = iteration_count {
+        spec.iteration_count = count;
+    }
+    if let Some(direction) = direction {
+        spec.direction = direction;
+    }
+    if let Some(fill_mode) = fill_mode {
+        spec.fill_mode = fill_mode;
+    }
+    Ok(spec)
+}
+
+/// Convert a `s` or `ms` dimension (or a unitless `0`) to a duration.
+fn parse_time(token: &Token<'_>) -> Option<Duration> {
+    match token {
+        Token::Dimension { value, unit, .. } if *value >= 0.0 => {
+            match unit.to_ascii_lowercase().as_str() {
+                // Round to whole microseconds so `150ms` is exactly 150ms
+                "s" => Some(Duration::from_micros((*value * 1e6).round() as u64)),
+                "ms" => Some(Duration::from_micros((*value * 1e3).round() as u64)),
+                _ => None,
+            }
+        }
+        Token::Number { value, .. } if *value == 0.0 => Some(Duration::ZERO),
+        _ => None,
+    }
+}
+
+/// Map a CSS timing function keyword to an easing curve.
+///
+/// `ease` is treated as `ease-in-out`. The cubic and sine curves are
+/// available as `ease-in-cubic`, `ease-out-sine` and so on.
+fn parse_easing(name: &str) -> Option<Easing> {
+    match name.to_ascii_lowercase().as_str() {
+        "linear" => Some(Easing::Linear),
+        "ease" | "ease-in-out" => Some(Easing::EaseInOut),
+        "ease-in" => Some(Easing::EaseIn),
+        "ease-out" => Some(Easing::EaseOut),
+        "ease-in-cubic" => Some(Easing::EaseInCubic),
+        "ease-out-cubic" => Some(Easing::EaseOutCubic),
+        "ease-in-out-cubic" => Some(Easing::EaseInOutCubic),
+        "ease-in-sine" => Some(Easing::EaseInSine),
+        "ease-out-sine" => Some(Easing::EaseOutSine),
+        "ease-in-out-sine" => Some(Easing::EaseInOutSine),
+        _ => None,
+    }
+}
+
 /// Skip to the next rule (error recovery).
 fn skip_to_next_rule(parser: &mut Parser<'_, '_>) {
     let mut depth = 0;
@@ -1018,6 +1727,7 @@ fn skip_declaration(parser: &mut Parser<'_, '_>) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::animation::AnimatableProperty;
 
     #[test]
     fn parse_simple_rule() {
@@ -1085,6 +1795,60 @@ mod tests {
         assert_eq!(rules[0].selector.combinators[0], Combinator::Child);
     }
 
+    #[test]
+    fn parse_attribute_selectors() {
+        let css = r#"LineEdit[severity="error"][modified] { color: red; }"#;
+        let rules = parse_css(css).unwrap();
+
+        assert_eq!(rules.len(), 1);
+        let part = &rules[0].selector.parts[0];
+        assert_eq!(
+            part.type_selector,
+            Some(TypeSelector::Type("LineEdit".into()))
+        );
+        assert_eq!(
+            part.attributes,
+            vec![
+                AttributeSelector::equals("severity", "error"),
+                AttributeSelector::exists("modified"),
+            ]
+        );
+
+        let css = "[kind^=warn] Label[tags~=bold] { color: red; }";
+        let rules = parse_css(css).unwrap();
+        let selector = &rules[0].selector;
+        assert_eq!(selector.parts.len(), 2);
+        assert_eq!(
+            selector.parts[0].attributes[0].operator,
+            AttributeOperator::Prefix("warn".into())
+        );
+        assert_eq!(
+            selector.parts[1].attributes[0].operator,
+            AttributeOperator::Includes("bold".into())
+        );
+    }
+
+    #[test]
+    fn parse_pseudo_element_selectors() {
+        let css =
+            "Slider::handle:hover { background-color: red; } ScrollBar::add-line { width: 0; }";
+        let rules = parse_css(css).unwrap();
+
+        assert_eq!(rules.len(), 2);
+        let part = &rules[0].selector.parts[0];
+        assert_eq!(part.pseudo_element, Some(PseudoElement::Handle));
+        assert_eq!(part.pseudo_classes, vec![PseudoClass::Hover]);
+        assert_eq!(rules[0].selector.to_string(), "Slider::handle:hover");
+        assert_eq!(
+            rules[1].selector.parts[0].pseudo_element,
+            Some(PseudoElement::AddLine)
+        );
+
+        // Unknown pseudo-elements invalidate the rule
+        let rules = parse_css("Slider::knob { color: red; }").unwrap();
+        assert!(rules.is_empty());
+    }
+
     #[test]
     fn parse_edge_values_shorthand() {
         let css = "Button { margin: 10px 20px; }";
@@ -1112,4 +1876,181 @@ mod tests {
         let rules = parse_css(css).unwrap();
         assert!(rules[0].properties.color.is_set());
     }
+
+    #[test]
+    fn parse_custom_properties_and_var_references() {
+        let css = ":root { --accent: rgb(0, 128, 255); --gap: 4px 8px; } \
+                   Button { color: var(--accent); padding: var(--gap, 2px); margin: 1px; }";
+        let rules = parse_css(css).unwrap();
+
+        assert_eq!(rules.len(), 2);
+        assert!(
+            rules[0].selector.parts[0]
+                .pseudo_classes
+                .contains(&PseudoClass::Root)
+        );
+        assert_eq!(
+            rules[0].properties.custom_properties.get("accent"),
+            Some(&"rgb(0, 128, 255)".to_string())
+        );
+        assert_eq!(
+            rules[0].properties.custom_properties.get("gap"),
+            Some(&"4px 8px".to_string())
+        );
+
+        let button = &rules[1].properties;
+        assert!(!button.color.is_set());
+        assert!(button.margin.is_set());
+        assert_eq!(
+            button.variable_declarations,
+            vec![
+                ("color".to_string(), "var(--accent)".to_string()),
+                ("padding".to_string(), "var(--gap, 2px)".to_string()),
+                // Kept after the var() declarations to preserve source order
+                ("margin".to_string(), "1px".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_single_declaration() {
+        let props = parse_declaration("border-color", "#00ff00").unwrap();
+        assert_eq!(
+            props.border_color.as_set(),
+            Some(&Color::from_rgb8(0, 255, 0))
+        );
+    }
+
+    #[test]
+    fn parse_transition_list() {
+        let props = parse_declaration(
+            "transition",
+            "background-color 150ms ease-out, opacity 0.2s linear 50ms",
+        )
+        .unwrap();
+        let transitions = props.transition.as_set().unwrap();
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(
+            transitions[0].property,
+            TransitionProperty::Property(AnimatableProperty::Background)
+        );
+        assert_eq!(transitions[0].duration, Duration::from_millis(150));
+        assert_eq!(transitions[0].easing, Easing::EaseOut);
+        assert_eq!(transitions[1].easing, Easing::Linear);
+        assert_eq!(transitions[1].delay, Duration::from_millis(50));
+
+        let props = parse_declaration("transition", "none").unwrap();
+        assert_eq!(props.transition.as_set(), Some(&vec![]));
+    }
+
+    #[test]
+    fn parse_animation_shorthand() {
+        let props =
+            parse_declaration("animation", "pulse 1s ease-in infinite alternate forwards").unwrap();
+        let animations = props.animation.as_set().unwrap();
+        assert_eq!(animations.len(), 1);
+        let animation = &animations[0];
+        assert_eq!(animation.name, "pulse");
+        assert_eq!(animation.duration, Duration::from_secs(1));
+        assert_eq!(animation.easing, Easing::EaseIn);
+        assert_eq!(animation.iteration_count, IterationCount::Infinite);
+        assert_eq!(animation.direction, AnimationDirection::Alternate);
+        assert_eq!(animation.fill_mode, AnimationFillMode::Forwards);
+    }
+
+    #[test]
+    fn parse_keyframes_rule() {
+        let css = "
+            @keyframes pulse {
+                from { opacity: 1; }
+                50%, 75% { opacity: 0.4; }
+                to { opacity: 1; }
+            }
+            @font-face { font-family: Custom; }
+            Button { color: blue; }
+        ";
+        let sheet = parse_stylesheet(css).unwrap();
+        assert_eq!(sheet.rules.len(), 1);
+        assert_eq!(sheet.keyframes.len(), 1);
+
+        let offsets: Vec<f32> = sheet.keyframes[0].frames.iter().map(|f| f.offset).collect();
+        assert_eq!(offsets, vec![0.0, 0.5, 0.75, 1.0]);
+    }
+
+    #[test]
+    fn parse_media_blocks() {
+        let css = "
+            Button { color: black; }
+            @media (prefers-color-scheme: dark) {
+                Button { color: white; }
+                @media screen and (min-width: 800px) {
+                    Label { color: gray; }
+                }
+            }
+            @media (resolution >= 2dppx), (192dpi <= resolution) { Label { color: red; } }
+            @media (hover: hover) { Label { color: blue; } }
+        ";
+        let rules = parse_css(css).unwrap();
+        assert_eq!(rules.len(), 5);
+        assert!(rules[0].media.is_empty());
+        assert_eq!(
+            rules[1].media[0].queries[0].features,
+            vec![MediaFeature::PrefersColorScheme(
+                ColorSchemePreference::Dark
+            )]
+        );
+
+        let nested = &rules[2].media;
+        assert_eq!(nested.len(), 2);
+        assert_eq!(nested[1].queries[0].media_type, MediaType::Screen);
+        assert_eq!(
+            nested[1].queries[0].features,
+            vec![MediaFeature::Width(MediaComparison::GreaterOrEqual, 800.0)]
+        );
+
+        let resolution = &rules[3].media[0].queries;
+        assert_eq!(resolution.len(), 2);
+        assert_eq!(
+            resolution[0].features,
+            vec![MediaFeature::Resolution(
+                MediaComparison::GreaterOrEqual,
+                2.0
+            )]
+        );
+        assert_eq!(resolution[1].features, resolution[0].features);
+
+        assert!(matches!(
+            rules[4].media[0].queries[0].features[0],
+            MediaFeature::Unknown(_)
+        ));
+        // Source order continues across blocks
+        let orders: Vec<u32> = rules.iter().map(|r| r.order).collect();
+        assert_eq!(orders, vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn parse_media_query_modifiers() {
+        let rules = parse_css(
+            "@media not print and (max-width: 600px), (prefers-contrast: more) { Button { color: red; } }",
+        )
+        .unwrap();
+        let queries = &rules[0].media[0].queries;
+        assert!(queries[0].negated);
+        assert_eq!(queries[0].media_type, MediaType::Print);
+        assert_eq!(
+            queries[0].features,
+            vec![MediaFeature::Width(MediaComparison::LessOrEqual, 600.0)]
+        );
+        assert_eq!(
+            queries[1].features,
+            vec![MediaFeature::PrefersContrast(ContrastPreference::More)]
+        );
+
+        // An invalid query drops the whole block
+        let rules =
+            parse_css("@media (min-width: wide) { Button { color: red; } } Label { color: blue; }")
+                .unwrap();
+        assert_eq!(rules.len(), 1);
+        assert!(rules[0].media.is_empty());
+    }
 }
@@ -8,7 +8,8 @@
 //! The parser supports a subset of CSS syntax designed for widget styling:
 //!
 //! - **Selectors**: Type selectors (`Button`), class selectors (`.primary`),
-//!   ID selectors (`#submit`), and pseudo-classes (`:hover`, `:pressed`, `:disabled`)
+//!   ID selectors (`#submit`), attribute selectors (`[severity="error"]`, `[name^=prefix]`),
+//!   and pseudo-classes (`:hover`, `:pressed`, `:disabled`)
 //! - **Combinators**: Descendant (` `), child (`>`), adjacent sibling (`+`),
 //!   general sibling (`~`)
 //! - **Properties**: Box model (margin, padding, border), colors, fonts, and effects
@@ -52,11 +53,42 @@
 //! - **Colors**: `color`, `background-color`, `background`
 //! - **Typography**: `font-size`, `font-weight`, `font-style`, `font-family`, `text-align`, `line-height`
 //! - **Effects**: `opacity`, `box-shadow`
+//! - **Animation**: `transition`, `animation`
 //! - **Size**: `width`, `height`, `min-width`, `min-height`, `max-width`, `max-height`
 //! - **Interaction**: `cursor`, `pointer-events`
+//!
+//! # Custom Properties
+//!
+//! Declarations starting with `--` define custom properties, which any
+//! property value can reference with `var(--name)` or `var(--name, fallback)`.
+//! References are substituted during cascade, so a value like
+//! `color: var(--primary-color)` picks up theme variables and custom
+//! properties inherited from ancestor widgets.
+//!
+//! # Transitions and Animations
+//!
+//! `transition` takes a comma-separated list of `<property> <duration>
+//! <timing-function> <delay>` entries, such as
+//! `transition: background-color 150ms ease-out, opacity 1s`. `animation`
+//! plays a `@keyframes` rule by name, such as
+//! `animation: pulse 1s ease-in-out infinite alternate`. Keyframe selectors
+//! are `from`, `to` and percentages. See the [`animation`](crate::animation)
+//! module for how the engine applies them.
+//!
+//! # Media Queries
+//!
+//! Rules inside `@media` blocks only apply while the engine's
+//! [`MediaEnvironment`](crate::rules::MediaEnvironment) matches. Supported
+//! features are `prefers-color-scheme` (`light`, `dark`), `prefers-contrast`
+//! (`more`, `less`, `no-preference`), `width` and `height` (in `px`), and
+//! `resolution` (in `dppx`, `x` or `dpi`). Range features accept `min-`/`max-`
+//! prefixes or comparisons such as `(width >= 800px)`. Queries can be combined
+//! with `and`, listed with commas, negated with `not`, and blocks may nest.
+//! Unknown features never match.
 
 mod css_parser;
 mod error;
 
-pub use css_parser::parse_css;
+pub(crate) use css_parser::parse_declaration;
+pub use css_parser::{ParsedStyleSheet, parse_css, parse_stylesheet};
 pub use error::ParseError;
@@ -1,6 +1,6 @@
 //! Style caching for performance.
 
-use crate::selector::WidgetState;
+use crate::selector::{PseudoElement, WidgetState};
 use crate::style::ComputedStyle;
 use horizon_lattice_core::ObjectId;
 use std::collections::HashMap;
@@ -8,8 +8,8 @@ use std::hash::{Hash, Hasher};
 
 /// Cache key for computed styles.
 ///
-/// The key combines widget ID with relevant state to ensure cache
-/// invalidation when state changes affect styling.
+/// The key combines widget ID with relevant state and attributes to ensure
+/// cache invalidation when state or dynamic property changes affect styling.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct StyleCacheKey {
     widget_id: ObjectId,
@@ -31,6 +31,41 @@ impl StyleCacheKey {
             state_hash: hasher.finish(),
         }
     }
+
+    /// Include widget attributes (dynamic properties) in the key.
+    pub fn with_attributes(mut self, attributes: &[(String, String)]) -> Self {
+        if !attributes.is_empty() {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            self.state_hash.hash(&mut hasher);
+            attributes.hash(&mut hasher);
+            self.state_hash = hasher.finish();
+        }
+        self
+    }
+
+    /// Key the style of a sub-control instead of the widget itself.
+    ///
+    /// The state passed to [`new`](Self::new) is the sub-control's state;
+    /// `sibling_info` tells repeated sub-controls (such as tabs) apart and
+    /// `widget_state` is the state of the widget the sub-control inherits from.
+    pub fn with_sub_control(
+        mut self,
+        element: PseudoElement,
+        sibling_info: Option<(usize, usize)>,
+        widget_state: &WidgetState,
+    ) -> Self {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.state_hash.hash(&mut hasher);
+        element.hash(&mut hasher);
+        sibling_info.hash(&mut hasher);
+        widget_state.hovered.hash(&mut hasher);
+        widget_state.pressed.hash(&mut hasher);
+        widget_state.focused.hash(&mut hasher);
+        widget_state.enabled.hash(&mut hasher);
+        widget_state.checked.hash(&mut hasher);
+        self.state_hash = hasher.finish();
+        self
+    }
 }
 
 /// LRU-like cache for computed styles.
@@ -162,6 +197,29 @@ mod tests {
         assert_eq!(cache.get(&key2).unwrap().font_size, 16.0);
     }
 
+    #[test]
+    fn cache_attribute_differentiation() {
+        let widget_id = make_widget_id();
+        let state = WidgetState::default();
+        let error = vec![("severity".to_string(), "error".to_string())];
+        let warning = vec![("severity".to_string(), "warning".to_string())];
+
+        let plain = StyleCacheKey::new(widget_id, &state);
+        let key1 = StyleCacheKey::new(widget_id, &state).with_attributes(&error);
+        let key2 = StyleCacheKey::new(widget_id, &state).with_attributes(&warning);
+
+        assert_eq!(
+            plain,
+            StyleCacheKey::new(widget_id, &state).with_attributes(&[])
+        );
+        assert_ne!(plain, key1);
+        assert_ne!(key1, key2);
+        assert_eq!(
+            key1,
+            StyleCacheKey::new(widget_id, &state).with_attributes(&error)
+        );
+    }
+
     #[test]
     fn cache_invalidation() {
         let mut cache = StyleCache::new();
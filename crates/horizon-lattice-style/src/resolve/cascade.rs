@@ -1,17 +1,24 @@
 //! Property cascading logic.
 
 use crate::style::StyleProperties;
+use crate::types::StyleValue;
 
 /// Cascade source properties onto target.
 ///
-/// Only explicitly set values from `source` will be copied to `target`.
-/// This is the core of CSS cascading - later rules override earlier ones.
+/// Only values declared in `source` will be copied to `target`, including
+/// the `inherit`, `initial` and `unset` keywords. This is the core of CSS
+/// cascading - later rules override earlier ones.
+///
+/// Custom properties are copied as well. Declarations that reference `var()`
+/// are not; the [`StyleEngine`](super::StyleEngine) substitutes and cascades
+/// them once the custom properties in scope are known.
 pub fn cascade_properties(target: &mut StyleProperties, source: &StyleProperties) {
     macro_rules! cascade_if_set {
         ($($prop:ident),+ $(,)?) => {
             $(
-                if source.$prop.is_set() {
+                if !matches!(source.$prop, StyleValue::Initial) {
                     target.$prop = source.$prop.clone();
+                    target.initial_properties.retain(|field| *field != stringify!($prop));
                 }
             )+
         };
@@ -52,6 +59,21 @@ pub fn cascade_properties(target: &mut StyleProperties, source: &StyleProperties
         // Interaction
         cursor,
         pointer_events,
+        // Animation
+        transition,
+        animation,
+    );
+
+    // `initial` can't be told apart from an undeclared value above
+    for field in &source.initial_properties {
+        target.set_initial(field);
+    }
+
+    target.custom_properties.extend(
+        source
+            .custom_properties
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone())),
     );
 }
 
@@ -59,7 +81,6 @@ pub fn cascade_properties(target: &mut StyleProperties, source: &StyleProperties
 #[allow(clippy::field_reassign_with_default)]
 mod tests {
     use super::*;
-    use crate::types::StyleValue;
     use horizon_lattice_render::Color;
 
     #[test]
@@ -95,4 +116,39 @@ mod tests {
         // opacity should be set
         assert_eq!(target.opacity.as_set(), Some(&0.5));
     }
+
+    #[test]
+    fn cascade_copies_inherit_keyword() {
+        let mut target = StyleProperties::default();
+        target.color = StyleValue::Set(Color::BLACK);
+
+        let mut source = StyleProperties::default();
+        source.color = StyleValue::Inherit;
+
+        cascade_properties(&mut target, &source);
+
+        assert_eq!(target.color, StyleValue::Inherit);
+    }
+
+    #[test]
+    fn cascade_resets_initial_keyword() {
+        let mut target = StyleProperties::default();
+        target.color = StyleValue::Set(Color::BLACK);
+        target.opacity = StyleValue::Set(0.5);
+
+        let mut source = StyleProperties::default();
+        assert!(source.set_initial("color"));
+
+        cascade_properties(&mut target, &source);
+
+        assert_eq!(target.color, StyleValue::Initial);
+        assert_eq!(target.opacity.as_set(), Some(&0.5));
+
+        // A later declaration overrides the reset again
+        let mut later = StyleProperties::default();
+        later.color = StyleValue::Set(Color::RED);
+        cascade_properties(&mut target, &later);
+        assert_eq!(target.color.as_set(), Some(&Color::RED));
+        assert!(target.initial_properties.is_empty());
+    }
 }
@@ -1,14 +1,19 @@
 //! Main style resolution engine.
 
+use crate::animation::{AnimationClock, AnimationKey, Keyframes, StyleAnimator, keyframe_tracks};
 use crate::resolve::cache::{StyleCache, StyleCacheKey};
 use crate::resolve::cascade::cascade_properties;
 use crate::resolve::inheritance::resolve_properties;
-use crate::rules::{StyleRule, StyleSheet};
-use crate::selector::{SelectorMatcher, SpecificityWithOrder};
+use crate::resolve::variables::{apply_variable_declarations, resolve_custom_properties};
+use crate::rules::{
+    ColorSchemePreference, ContrastPreference, MediaEnvironment, StyleRule, StyleSheet,
+};
+use crate::selector::{PseudoElement, SelectorMatcher, SpecificityWithOrder};
 use crate::selector::{SiblingInfo, WidgetMatchContext, WidgetState};
 use crate::style::{ComputedStyle, StyleProperties};
 use crate::theme::Theme;
 use horizon_lattice_core::ObjectId;
+use std::collections::HashMap;
 
 /// Context for style resolution.
 ///
@@ -22,6 +27,8 @@ pub struct StyleContext<'a> {
     pub widget_name: Option<&'a str>,
     /// Widget's CSS classes.
     pub classes: &'a [String],
+    /// Widget attributes as `(name, value)` pairs (for attribute selectors).
+    pub attributes: &'a [(String, String)],
     /// Widget state for pseudo-class matching.
     pub state: WidgetStyleState,
     /// Parent's computed style (for inheritance).
@@ -49,6 +56,9 @@ impl<'a> StyleContext<'a> {
                 .sibling_info
                 .map(|(index, count)| SiblingInfo { index, count }),
             child_count: self.state.child_count,
+            is_root: self.parent_style.is_none(),
+            attributes: self.attributes,
+            pseudo_element: None,
         }
     }
 }
@@ -98,16 +108,31 @@ pub struct StyleEngine {
     theme: Theme,
     /// Root font size (for rem units).
     root_font_size: f32,
+    /// Running transitions and keyframe animations.
+    animator: StyleAnimator,
+    /// Time source for transitions and animations.
+    clock: AnimationClock,
+    /// Whether any stylesheet rule declares a transition or animation.
+    has_animation_rules: bool,
+    /// Environment that `@media` rules are evaluated against.
+    media: MediaEnvironment,
 }
 
 impl StyleEngine {
     /// Create a new style engine with a theme.
+    ///
+    /// The media environment starts out with the color scheme and contrast
+    /// preferences of the theme's mode.
     pub fn new(theme: Theme) -> Self {
         Self {
+            media: MediaEnvironment::for_theme_mode(theme.mode),
             stylesheets: vec![],
             cache: StyleCache::new(),
             theme,
             root_font_size: 16.0,
+            animator: StyleAnimator::default(),
+            clock: AnimationClock::system(),
+            has_animation_rules: false,
         }
     }
 
@@ -132,26 +157,98 @@ impl StyleEngine {
         self.cache.invalidate_all();
     }
 
+    /// Get the environment that `@media` rules are evaluated against.
+    pub fn media_environment(&self) -> &MediaEnvironment {
+        &self.media
+    }
+
+    /// Set the environment that `@media` rules are evaluated against.
+    ///
+    /// Returns `true` if this switched any rule on or off, in which case
+    /// cached styles are invalidated and widgets should be restyled.
+    pub fn set_media_environment(&mut self, env: MediaEnvironment) -> bool {
+        if env == self.media {
+            return false;
+        }
+
+        let changed = self
+            .stylesheets
+            .iter()
+            .flat_map(|s| &s.rules)
+            .filter(|rule| !rule.media.is_empty())
+            .any(|rule| rule.applies_in(&self.media) != rule.applies_in(&env));
+        self.media = env;
+        if changed {
+            self.cache.invalidate_all();
+        }
+        changed
+    }
+
+    /// Set the preferred color scheme for `prefers-color-scheme` queries.
+    ///
+    /// Returns `true` if any rule was switched on or off.
+    pub fn set_color_scheme_preference(&mut self, color_scheme: ColorSchemePreference) -> bool {
+        self.set_media_environment(self.media.with_color_scheme(color_scheme))
+    }
+
+    /// Set the preferred contrast for `prefers-contrast` queries.
+    ///
+    /// Returns `true` if any rule was switched on or off.
+    pub fn set_contrast_preference(&mut self, contrast: ContrastPreference) -> bool {
+        self.set_media_environment(self.media.with_contrast(contrast))
+    }
+
+    /// Set the viewport size (in logical pixels) and scale factor for
+    /// `width`, `height` and `resolution` queries.
+    ///
+    /// Returns `true` if any rule was switched on or off.
+    pub fn set_viewport(&mut self, width: f32, height: f32, scale_factor: f32) -> bool {
+        self.set_media_environment(
+            self.media
+                .with_viewport(width, height)
+                .with_scale_factor(scale_factor),
+        )
+    }
+
     /// Add a stylesheet.
     pub fn add_stylesheet(&mut self, stylesheet: StyleSheet) {
         self.stylesheets.push(stylesheet);
         self.stylesheets.sort_by_key(|s| s.priority);
-        self.cache.invalidate_all();
+        self.stylesheets_changed();
     }
 
     /// Remove stylesheets from a specific source file.
     pub fn remove_stylesheet_by_path(&mut self, path: &std::path::Path) {
         self.stylesheets
             .retain(|s| s.source_path.as_deref() != Some(path));
-        self.cache.invalidate_all();
+        self.stylesheets_changed();
     }
 
     /// Clear all stylesheets.
     pub fn clear_stylesheets(&mut self) {
         self.stylesheets.clear();
+        self.stylesheets_changed();
+    }
+
+    fn stylesheets_changed(&mut self) {
+        self.has_animation_rules =
+            self.stylesheets.iter().flat_map(|s| &s.rules).any(|rule| {
+                rule.properties.transition.is_set() || rule.properties.animation.is_set()
+            });
         self.cache.invalidate_all();
     }
 
+    /// Get a `@keyframes` rule by animation name.
+    ///
+    /// When several stylesheets define the same name, the one with the
+    /// highest priority (or added last) wins.
+    pub fn keyframes(&self, name: &str) -> Option<&Keyframes> {
+        self.stylesheets
+            .iter()
+            .rev()
+            .find_map(|sheet| sheet.keyframes(name))
+    }
+
     /// Compute the style for a widget.
     ///
     /// This performs the full style resolution:
@@ -160,22 +257,191 @@ impl StyleEngine {
     /// 3. Cascade properties
     /// 4. Apply inline styles
     /// 5. Resolve to computed values
+    /// 6. Apply running transitions and animations
     pub fn compute_style(
         &mut self,
         widget_id: ObjectId,
         context: &StyleContext<'_>,
         inline_style: Option<&StyleProperties>,
+    ) -> ComputedStyle {
+        let style = self.compute_static_style(widget_id, context, inline_style);
+        self.animate_style(widget_id, context, style)
+    }
+
+    /// Compute the style for a widget without transitions or animations.
+    ///
+    /// This is the style the widget settles on once its animations finish.
+    /// Children should inherit from this style rather than from an
+    /// intermediate animation frame.
+    pub fn compute_static_style(
+        &mut self,
+        widget_id: ObjectId,
+        context: &StyleContext<'_>,
+        inline_style: Option<&StyleProperties>,
     ) -> ComputedStyle {
         // Check cache first (only if no inline style)
         if inline_style.is_none() {
-            let cache_key = StyleCacheKey::new(widget_id, &context.state.to_widget_state());
+            let cache_key = StyleCacheKey::new(widget_id, &context.state.to_widget_state())
+                .with_attributes(context.attributes);
             if let Some(cached) = self.cache.get(&cache_key) {
                 return cached.clone();
             }
         }
 
+        let computed = self.resolve_style(context, None, inline_style);
+
+        // Cache if no inline style
+        if inline_style.is_none() {
+            let cache_key = StyleCacheKey::new(widget_id, &context.state.to_widget_state())
+                .with_attributes(context.attributes);
+            self.cache.insert(cache_key, computed.clone());
+        }
+
+        computed
+    }
+
+    /// Compute the style of a widget's sub-control (e.g. a slider's handle).
+    ///
+    /// Only rules whose selector targets `element` (such as `Slider::handle`)
+    /// apply, and their pseudo-classes are matched against `state`, the state
+    /// of the sub-control itself. The sub-control inherits from the widget's
+    /// own style, which is computed from `context` and `inline_style`.
+    pub fn compute_sub_control_style(
+        &mut self,
+        widget_id: ObjectId,
+        context: &StyleContext<'_>,
+        element: PseudoElement,
+        state: WidgetStyleState,
+        inline_style: Option<&StyleProperties>,
+    ) -> ComputedStyle {
+        let widget_style = self.compute_static_style(widget_id, context, inline_style);
+        let sub_context = StyleContext {
+            state,
+            parent_style: Some(&widget_style),
+            ..context.clone()
+        };
+
+        // The sub-control inherits from the widget, so the key also covers the widget state
+        let cache_key = StyleCacheKey::new(widget_id, &state.to_widget_state())
+            .with_attributes(context.attributes)
+            .with_sub_control(
+                element,
+                state.sibling_info,
+                &context.state.to_widget_state(),
+            );
+        let cached = inline_style
+            .is_none()
+            .then(|| self.cache.get(&cache_key).cloned())
+            .flatten();
+        let style = match cached {
+            Some(style) => style,
+            None => {
+                let computed = self.resolve_style(&sub_context, Some(element), None);
+                if inline_style.is_none() {
+                    self.cache.insert(cache_key, computed.clone());
+                }
+                computed
+            }
+        };
+
+        let key = AnimationKey::sub_control(widget_id, element, state.sibling_info);
+        self.animate(key, &sub_context, style)
+    }
+
+    /// Apply a widget's transitions and animations to its static style.
+    ///
+    /// `style` is the result of [`compute_static_style`](Self::compute_static_style).
+    /// Properties whose value changed since the previous call start the
+    /// transitions declared by `style`, and newly listed animations start
+    /// playing. Call [`is_animating`](Self::is_animating) afterwards to find
+    /// out whether the widget needs another frame.
+    pub fn animate_style(
+        &mut self,
+        widget_id: ObjectId,
+        context: &StyleContext<'_>,
+        style: ComputedStyle,
+    ) -> ComputedStyle {
+        self.animate(AnimationKey::widget(widget_id), context, style)
+    }
+
+    fn animate(
+        &mut self,
+        key: AnimationKey,
+        context: &StyleContext<'_>,
+        style: ComputedStyle,
+    ) -> ComputedStyle {
+        let track = self.has_animation_rules
+            || !style.transitions.is_empty()
+            || !style.animations.is_empty();
+        let now = self.clock.elapsed();
+        let stylesheets = &self.stylesheets;
+
+        self.animator
+            .update(key, style, now, track, |spec, target| {
+                let keyframes = stylesheets
+                    .iter()
+                    .rev()
+                    .find_map(|sheet| sheet.keyframes(&spec.name))?;
+                Some(keyframe_tracks(keyframes, |props| {
+                    let mut cascaded = StyleProperties::default();
+                    cascade_properties(&mut cascaded, props);
+                    apply_variable_declarations(&mut cascaded, props, &target.custom_properties);
+                    resolve_properties(&cascaded, context.parent_style, context.root_font_size)
+                }))
+            })
+    }
+
+    /// Check whether a widget or any of its sub-controls has transitions or
+    /// animations running.
+    ///
+    /// While this returns `true`, the widget should keep repainting so that
+    /// its style is recomputed at the current time.
+    pub fn is_animating(&self, widget_id: ObjectId) -> bool {
+        self.animator.is_animating(widget_id)
+    }
+
+    /// Check whether any widget has transitions or animations running.
+    pub fn has_running_animations(&self) -> bool {
+        self.animator.has_running_animations()
+    }
+
+    /// Stop and forget the transitions and animations of a widget and its
+    /// sub-controls.
+    ///
+    /// Call this when the widget is destroyed.
+    pub fn remove_animations(&mut self, widget_id: ObjectId) {
+        self.animator.remove_widget(widget_id);
+    }
+
+    /// Get the clock that drives transitions and animations.
+    pub fn clock(&self) -> &AnimationClock {
+        &self.clock
+    }
+
+    /// Get the animation clock mutably, e.g. to advance a manual clock.
+    pub fn clock_mut(&mut self) -> &mut AnimationClock {
+        &mut self.clock
+    }
+
+    /// Replace the animation clock.
+    ///
+    /// Running transitions and animations are dropped, since their start
+    /// times refer to the old clock.
+    pub fn set_clock(&mut self, clock: AnimationClock) {
+        self.clock = clock;
+        self.animator.clear();
+    }
+
+    /// Resolve a style without consulting the cache.
+    fn resolve_style(
+        &self,
+        context: &StyleContext<'_>,
+        pseudo_element: Option<PseudoElement>,
+        inline_style: Option<&StyleProperties>,
+    ) -> ComputedStyle {
         // Collect all matching rules with specificity
-        let match_context = context.to_match_context();
+        let mut match_context = context.to_match_context();
+        match_context.pseudo_element = pseudo_element;
         let mut matched_rules: Vec<(&StyleRule, SpecificityWithOrder)> = vec![];
         let mut global_order = 0u32;
 
@@ -183,7 +449,9 @@ impl StyleEngine {
             let priority_offset = stylesheet.priority.as_order_offset();
 
             for rule in &stylesheet.rules {
-                if SelectorMatcher::matches_subject(&rule.selector, &match_context) {
+                if rule.applies_in(&self.media)
+                    && SelectorMatcher::matches_subject(&rule.selector, &match_context)
+                {
                     let order = priority_offset | global_order;
                     matched_rules.push((rule, rule.specificity.with_order(order)));
                     global_order += 1;
@@ -194,33 +462,66 @@ impl StyleEngine {
         // Sort by specificity (lower specificity first, so later ones override)
         matched_rules.sort_by_key(|(_, spec)| *spec);
 
+        // Collect custom properties in scope: theme variables, then those
+        // inherited from the parent, then declarations on this widget
+        let mut variables: HashMap<String, String> = self
+            .theme
+            .variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        if let Some(parent) = context.parent_style {
+            variables.extend(
+                parent
+                    .custom_properties
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+        }
+        for (rule, _) in &matched_rules {
+            variables.extend(
+                rule.properties
+                    .custom_properties
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+        }
+        if let Some(inline) = inline_style {
+            variables.extend(
+                inline
+                    .custom_properties
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.clone())),
+            );
+        }
+        let variables = resolve_custom_properties(&variables);
+
         // Cascade properties
         let mut cascaded = StyleProperties::default();
 
-        // Apply theme defaults first
-        if let Some(theme_props) = self.theme.widget_defaults.get(context.widget_type) {
+        // Apply theme defaults first (these describe the widget, not its sub-controls)
+        if pseudo_element.is_none()
+            && let Some(theme_props) = self.theme.widget_defaults.get(context.widget_type)
+        {
             cascade_properties(&mut cascaded, theme_props);
         }
 
-        // Apply matched rules in order
+        // Apply matched rules in order, substituting var() references
         for (rule, _) in &matched_rules {
             cascade_properties(&mut cascaded, &rule.properties);
+            apply_variable_declarations(&mut cascaded, &rule.properties, &variables);
         }
 
         // Apply inline styles (highest priority)
         if let Some(inline) = inline_style {
             cascade_properties(&mut cascaded, inline);
+            apply_variable_declarations(&mut cascaded, inline, &variables);
         }
 
         // Resolve to computed style
-        let computed = resolve_properties(&cascaded, context.parent_style, context.root_font_size);
-
-        // Cache if no inline style
-        if inline_style.is_none() {
-            let cache_key = StyleCacheKey::new(widget_id, &context.state.to_widget_state());
-            self.cache.insert(cache_key, computed.clone());
-        }
-
+        let mut computed =
+            resolve_properties(&cascaded, context.parent_style, context.root_font_size);
+        computed.custom_properties = variables;
         computed
     }
 
@@ -272,13 +573,15 @@ mod tests {
     use super::*;
     use crate::selector::Selector;
     use crate::style::Style;
-    use horizon_lattice_render::Color;
+    use horizon_lattice_render::{Color, Paint};
+    use std::time::Duration;
 
     fn make_context<'a>(widget_type: &'a str, classes: &'a [String]) -> StyleContext<'a> {
         StyleContext {
             widget_type,
             widget_name: None,
             classes,
+            attributes: &[],
             state: WidgetStyleState {
                 enabled: true,
                 ..Default::default()
@@ -380,4 +683,339 @@ mod tests {
         let _ = engine.compute_style(widget_id, &context, None);
         assert_eq!(engine.cache_size(), 1); // Still 1
     }
+
+    fn engine_with_css(css: &str) -> StyleEngine {
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(
+            StyleSheet::from_css(css, crate::rules::StylePriority::Application).unwrap(),
+        );
+        engine
+    }
+
+    #[test]
+    fn engine_resolves_theme_variables() {
+        let mut engine = engine_with_css("Button { background-color: var(--primary-color); }");
+
+        let classes = vec![];
+        let context = make_context("Button", &classes);
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(
+            computed.background,
+            Paint::Solid(engine.theme().palette.primary)
+        );
+
+        // Switching themes re-resolves the reference
+        engine.set_theme(Theme::dark());
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(
+            computed.background,
+            Paint::Solid(Theme::dark().palette.primary)
+        );
+    }
+
+    #[test]
+    fn engine_custom_properties_inherit_from_root() {
+        let mut engine = engine_with_css(
+            ":root { --accent: #ff0000; } \
+             .override { --accent: #0000ff; } \
+             Label { color: var(--accent); font-size: var(--missing, 20px); }",
+        );
+
+        let classes = vec![];
+        let root =
+            engine.compute_style(ObjectId::default(), &make_context("Window", &classes), None);
+        assert_eq!(root.custom_property("--accent"), Some("#ff0000"));
+
+        // All widgets share one ID here, so drop cached styles between them
+        engine.invalidate_all();
+        let mut context = make_context("Label", &classes);
+        context.parent_style = Some(&root);
+        let label = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(label.color, Color::from_rgb8(255, 0, 0));
+        assert_eq!(label.font_size, 20.0);
+
+        // A closer ancestor overrides the root declaration
+        engine.invalidate_all();
+        let override_classes = vec!["override".to_string()];
+        let mut context = make_context("Container", &override_classes);
+        context.parent_style = Some(&root);
+        let container = engine.compute_style(ObjectId::default(), &context, None);
+
+        let mut context = make_context("Label", &classes);
+        context.parent_style = Some(&container);
+        engine.invalidate_all();
+        let label = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(label.color, Color::from_rgb8(0, 0, 255));
+    }
+
+    #[test]
+    fn engine_later_declaration_overrides_var() {
+        let mut engine =
+            engine_with_css("Button { color: var(--text-primary); } .plain { color: #00ff00; }");
+
+        let classes = vec!["plain".to_string()];
+        let context = make_context("Button", &classes);
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(computed.color, Color::from_rgb8(0, 255, 0));
+    }
+
+    #[test]
+    fn engine_applies_var_declarations_in_source_order() {
+        let mut engine = engine_with_css(
+            "Button { --a: #0000ff; color: var(--a); color: #ff0000; } \
+             Label { color: #ff0000; color: var(--a, #00ff00); }",
+        );
+
+        let classes = vec![];
+        let context = make_context("Button", &classes);
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(computed.color, Color::from_rgb8(255, 0, 0));
+
+        let context = make_context("Label", &classes);
+        engine.invalidate_all();
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(computed.color, Color::from_rgb8(0, 255, 0));
+    }
+
+    #[test]
+    fn engine_unresolved_var_is_unset() {
+        let mut engine = engine_with_css(
+            "Container { color: #0000ff; } \
+             Label { color: #ff0000; background-color: #ff0000; } \
+             .broken { color: var(--missing); background-color: var(--missing); }",
+        );
+
+        let classes = vec![];
+        let context = make_context("Container", &classes);
+        let parent = engine.compute_style(ObjectId::default(), &context, None);
+
+        // Color inherits and the background takes its initial value, rather
+        // than keeping the values cascaded from the Label rule
+        let classes = vec!["broken".to_string()];
+        let mut context = make_context("Label", &classes);
+        context.parent_style = Some(&parent);
+        engine.invalidate_all();
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(computed.color, Color::from_rgb8(0, 0, 255));
+        assert_eq!(computed.background.as_solid(), Some(Color::TRANSPARENT));
+    }
+
+    #[test]
+    fn engine_initial_keyword_overrides_earlier_rules() {
+        let mut engine = engine_with_css(
+            "Label { color: #ff0000; padding: 4px; opacity: 0.5; } \
+             .plain { color: initial; padding: initial; opacity: initial; opacity: 0.25; }",
+        );
+
+        let classes = vec!["plain".to_string()];
+        let context = make_context("Label", &classes);
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(computed.color, Color::BLACK);
+        assert_eq!(computed.padding_top, 0.0);
+        assert_eq!(computed.padding_left, 0.0);
+        // A later declaration in the same block replaces `initial`
+        assert_eq!(computed.opacity, 0.25);
+    }
+
+    #[test]
+    fn engine_matches_attribute_selectors() {
+        let mut engine = engine_with_css(
+            r#"LineEdit { color: #000000; } LineEdit[severity="error"] { color: #ff0000; }"#,
+        );
+
+        let classes = vec![];
+        let mut context = make_context("LineEdit", &classes);
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(computed.color, Color::from_rgb8(0, 0, 0));
+
+        // A changed attribute produces a different cache key, so no invalidation is needed
+        let attributes = vec![("severity".to_string(), "error".to_string())];
+        context.attributes = &attributes;
+        let computed = engine.compute_style(ObjectId::default(), &context, None);
+        assert_eq!(computed.color, Color::from_rgb8(255, 0, 0));
+    }
+
+    #[test]
+    fn engine_computes_sub_control_styles() {
+        let mut engine = engine_with_css(
+            "Slider { color: #0000ff; } \
+             Slider::handle { background-color: #ffffff; color: inherit; } \
+             Slider::handle:hover { background-color: #ff0000; } \
+             Slider:hover { color: #00ff00; }",
+        );
+
+        let classes = vec![];
+        let context = make_context("Slider", &classes);
+        let widget_id = ObjectId::default();
+
+        let widget = engine.compute_style(widget_id, &context, None);
+        assert_eq!(widget.background.as_solid(), Some(Color::TRANSPARENT));
+
+        let handle_state = WidgetStyleState {
+            enabled: true,
+            ..Default::default()
+        };
+        let handle = engine.compute_sub_control_style(
+            widget_id,
+            &context,
+            PseudoElement::Handle,
+            handle_state,
+            None,
+        );
+        assert_eq!(handle.background.as_solid(), Some(Color::WHITE));
+        // Inherited from the widget's style
+        assert_eq!(handle.color, Color::from_rgb8(0, 0, 255));
+
+        let hovered = WidgetStyleState {
+            hovered: true,
+            ..handle_state
+        };
+        let handle = engine.compute_sub_control_style(
+            widget_id,
+            &context,
+            PseudoElement::Handle,
+            hovered,
+            None,
+        );
+        assert_eq!(
+            handle.background.as_solid(),
+            Some(Color::from_rgb8(255, 0, 0))
+        );
+
+        let groove = engine.compute_sub_control_style(
+            widget_id,
+            &context,
+            PseudoElement::Groove,
+            handle_state,
+            None,
+        );
+        assert_eq!(groove.background.as_solid(), Some(Color::TRANSPARENT));
+
+        // Hovering the widget restyles the inheriting handle without explicit invalidation
+        let mut context = context;
+        context.state.hovered = true;
+        let handle = engine.compute_sub_control_style(
+            widget_id,
+            &context,
+            PseudoElement::Handle,
+            handle_state,
+            None,
+        );
+        assert_eq!(handle.color, Color::from_rgb8(0, 255, 0));
+    }
+
+    #[test]
+    fn engine_transitions_changed_properties() {
+        let mut engine = StyleEngine::light();
+        engine.set_clock(AnimationClock::manual());
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "Button { opacity: 1; transition: opacity 100ms linear; }
+                 Button:hover { opacity: 0; }",
+                crate::rules::StylePriority::Application,
+            )
+            .unwrap(),
+        );
+
+        let widget_id = ObjectId::default();
+        let classes = vec![];
+        let mut context = make_context("Button", &classes);
+        assert_eq!(engine.compute_style(widget_id, &context, None).opacity, 1.0);
+        assert!(!engine.is_animating(widget_id));
+
+        context.state.hovered = true;
+        assert_eq!(engine.compute_style(widget_id, &context, None).opacity, 1.0);
+        assert!(engine.is_animating(widget_id));
+
+        engine.clock_mut().advance(Duration::from_millis(25));
+        let computed = engine.compute_style(widget_id, &context, None);
+        assert!((computed.opacity - 0.75).abs() < 1e-4);
+
+        // The static style is unaffected by the running transition
+        let settled = engine.compute_static_style(widget_id, &context, None);
+        assert_eq!(settled.opacity, 0.0);
+
+        engine.clock_mut().advance(Duration::from_millis(100));
+        assert_eq!(engine.compute_style(widget_id, &context, None).opacity, 0.0);
+        assert!(!engine.is_animating(widget_id));
+    }
+
+    #[test]
+    fn engine_plays_keyframe_animations() {
+        let mut engine = StyleEngine::light();
+        engine.set_clock(AnimationClock::manual());
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "@keyframes fade { from { opacity: 0; } to { opacity: 0.5; } }
+                 Label { animation: fade 200ms linear; }",
+                crate::rules::StylePriority::Application,
+            )
+            .unwrap(),
+        );
+        assert!(engine.keyframes("fade").is_some());
+
+        let widget_id = ObjectId::default();
+        let classes = vec![];
+        let context = make_context("Label", &classes);
+        assert_eq!(engine.compute_style(widget_id, &context, None).opacity, 0.0);
+
+        engine.clock_mut().advance(Duration::from_millis(100));
+        let computed = engine.compute_style(widget_id, &context, None);
+        assert!((computed.opacity - 0.25).abs() < 1e-4);
+
+        // Without a fill mode the animation stops applying once finished
+        engine.clock_mut().advance(Duration::from_millis(200));
+        assert_eq!(engine.compute_style(widget_id, &context, None).opacity, 1.0);
+        assert!(!engine.has_running_animations());
+    }
+
+    #[test]
+    fn engine_switches_media_rules() {
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "Label { color: #000000; }
+                 @media (prefers-color-scheme: dark) { Label { color: #ffffff; } }
+                 @media (prefers-contrast: more) { Label { font-size: 20px; } }
+                 @media (min-width: 800px) and (resolution >= 2dppx) { Label { margin: 8px; } }",
+                crate::rules::StylePriority::Application,
+            )
+            .unwrap(),
+        );
+
+        let classes = vec![];
+        let context = make_context("Label", &classes);
+        let widget_id = ObjectId::default();
+        let computed = engine.compute_style(widget_id, &context, None);
+        assert_eq!(computed.color, Color::BLACK);
+        assert_ne!(computed.font_size, 20.0);
+
+        assert!(engine.set_color_scheme_preference(ColorSchemePreference::Dark));
+        assert!(engine.set_contrast_preference(ContrastPreference::More));
+        let computed = engine.compute_style(widget_id, &context, None);
+        assert_eq!(computed.color, Color::WHITE);
+        assert_eq!(computed.font_size, 20.0);
+
+        // Only a wide, high-density viewport enables the margin rule
+        assert!(!engine.set_viewport(1024.0, 768.0, 1.0));
+        assert_eq!(
+            engine.compute_style(widget_id, &context, None).margin_top,
+            0.0
+        );
+        assert!(engine.set_viewport(1024.0, 768.0, 2.0));
+        assert_eq!(
+            engine.compute_style(widget_id, &context, None).margin_top,
+            8.0
+        );
+        assert!(!engine.set_viewport(1024.0, 768.0, 2.0));
+    }
+
+    #[test]
+    fn engine_media_follows_theme_mode() {
+        let engine = StyleEngine::dark();
+        assert_eq!(
+            engine.media_environment().color_scheme,
+            ColorSchemePreference::Dark
+        );
+    }
 }
@@ -111,6 +111,10 @@ pub fn resolve_properties(
     // === Interaction ===
     computed.pointer_events = resolve_non_inheritable(&props.pointer_events, true);
 
+    // === Animation (not inheritable) ===
+    computed.transitions = resolve_non_inheritable(&props.transition, vec![]);
+    computed.animations = resolve_non_inheritable(&props.animation, vec![]);
+
     computed
 }
 
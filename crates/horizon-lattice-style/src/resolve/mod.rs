@@ -4,6 +4,8 @@ mod cache;
 mod cascade;
 mod engine;
 mod inheritance;
+mod variables;
 
 pub use cascade::cascade_properties;
 pub use engine::{StyleContext, StyleEngine, WidgetStyleState};
+pub use variables::substitute_variables;
@@ -0,0 +1,205 @@
+//! Custom property (`var()`) substitution.
+
+use std::collections::HashMap;
+
+use crate::parser::parse_declaration;
+use crate::resolve::cascade::cascade_properties;
+use crate::style::StyleProperties;
+
+/// Maximum nesting of `var()` references before a value is treated as cyclic.
+const MAX_SUBSTITUTION_DEPTH: usize = 32;
+
+/// Substitute every `var(--name)` or `var(--name, fallback)` reference in a value.
+///
+/// Variable names are looked up without the `--` prefix. Values of referenced
+/// variables may themselves contain `var()` references.
+///
+/// Returns `None` if a reference is undefined and has no fallback, if the
+/// references form a cycle, or if the value is malformed. As in CSS, such a
+/// declaration is invalid and should be ignored.
+pub fn substitute_variables(value: &str, variables: &HashMap<String, String>) -> Option<String> {
+    substitute(value, variables, 0)
+}
+
+/// Resolve `var()` references inside custom property values.
+///
+/// Custom properties that cannot be resolved are dropped.
+pub(crate) fn resolve_custom_properties(
+    variables: &HashMap<String, String>,
+) -> HashMap<String, String> {
+    variables
+        .iter()
+        .filter_map(|(name, value)| {
+            substitute_variables(value, variables).map(|value| (name.clone(), value))
+        })
+        .collect()
+}
+
+/// Substitute and cascade the `var()` declarations of `source` onto `target`.
+///
+/// Declarations are applied in source order. As in CSS, a declaration that
+/// cannot be resolved or parsed is invalid at computed-value time: its
+/// property is `unset`, so it inherits or takes its initial value.
+pub(crate) fn apply_variable_declarations(
+    target: &mut StyleProperties,
+    source: &StyleProperties,
+    variables: &HashMap<String, String>,
+) {
+    for (name, raw) in &source.variable_declarations {
+        let props = substitute_variables(raw, variables)
+            .and_then(|value| parse_declaration(name, &value))
+            .or_else(|| {
+                tracing::debug!("Invalid var() declaration '{}: {}'", name, raw);
+                parse_declaration(name, "unset")
+            });
+        if let Some(props) = props {
+            cascade_properties(target, &props);
+        }
+    }
+}
+
+fn substitute(value: &str, variables: &HashMap<String, String>, depth: usize) -> Option<String> {
+    if depth > MAX_SUBSTITUTION_DEPTH {
+        return None;
+    }
+
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(start) = find_var(rest) {
+        result.push_str(&rest[..start]);
+
+        let args_start = start + "var(".len();
+        let args_len = closing_paren(&rest[args_start..])?;
+        let args = &rest[args_start..args_start + args_len];
+
+        let (name, fallback) = match top_level_comma(args) {
+            Some(comma) => (&args[..comma], Some(args[comma + 1..].trim())),
+            None => (args, None),
+        };
+        let name = name.trim().strip_prefix("--")?;
+
+        let replacement = variables
+            .get(name)
+            .and_then(|value| substitute(value, variables, depth + 1))
+            .or_else(|| substitute(fallback?, variables, depth + 1))?;
+        result.push_str(&replacement);
+
+        rest = &rest[args_start + args_len + 1..];
+    }
+
+    result.push_str(rest);
+    Some(result)
+}
+
+/// Find the byte offset of the next `var(` function.
+fn find_var(value: &str) -> Option<usize> {
+    let lower = value.to_ascii_lowercase();
+    let mut offset = 0;
+    while let Some(pos) = lower[offset..].find("var(") {
+        let start = offset + pos;
+        // Skip identifiers that merely end in "var", like "somevar("
+        let preceded_by_ident = lower[..start]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !preceded_by_ident {
+            return Some(start);
+        }
+        offset = start + "var(".len();
+    }
+    None
+}
+
+/// Find the offset of the parenthesis closing an already opened one.
+fn closing_paren(value: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Find the first comma that is not nested inside parentheses.
+fn top_level_comma(value: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn substitutes_simple_reference() {
+        let variables = vars(&[("accent", "#ff0000")]);
+        assert_eq!(
+            substitute_variables("var(--accent)", &variables).as_deref(),
+            Some("#ff0000")
+        );
+        assert_eq!(
+            substitute_variables("1px solid var(--accent)", &variables).as_deref(),
+            Some("1px solid #ff0000")
+        );
+    }
+
+    #[test]
+    fn uses_fallback_for_undefined() {
+        let variables = vars(&[]);
+        assert_eq!(
+            substitute_variables("var(--missing, 4px 8px)", &variables).as_deref(),
+            Some("4px 8px")
+        );
+        assert_eq!(
+            substitute_variables("rgb(var(--r, 10), 20, 30)", &variables).as_deref(),
+            Some("rgb(10, 20, 30)")
+        );
+        assert_eq!(substitute_variables("var(--missing)", &variables), None);
+    }
+
+    #[test]
+    fn resolves_nested_references() {
+        let variables = vars(&[("base", "#00ff00"), ("accent", "var(--base)")]);
+        assert_eq!(
+            substitute_variables("var(--accent)", &variables).as_deref(),
+            Some("#00ff00")
+        );
+        assert_eq!(
+            substitute_variables("var(--missing, var(--base))", &variables).as_deref(),
+            Some("#00ff00")
+        );
+    }
+
+    #[test]
+    fn cycles_are_invalid() {
+        let variables = vars(&[("a", "var(--b)"), ("b", "var(--a)")]);
+        assert_eq!(substitute_variables("var(--a)", &variables), None);
+        assert_eq!(
+            substitute_variables("var(--a, red)", &variables).as_deref(),
+            Some("red")
+        );
+
+        let resolved = resolve_custom_properties(&variables);
+        assert!(resolved.is_empty());
+    }
+}
@@ -0,0 +1,297 @@
+//! Media queries for conditional rules.
+//!
+//! Rules inside an `@media` block only apply while the engine's
+//! [`MediaEnvironment`] matches the block's query list:
+//!
+//! ```css
+//! @media (prefers-color-scheme: dark) {
+//!     Window { background-color: #202020; }
+//! }
+//!
+//! @media (prefers-contrast: more), (min-width: 800px) and (resolution >= 2dppx) {
+//!     PushButton { border-width: 2px; }
+//! }
+//! ```
+
+use crate::theme::ThemeMode;
+
+/// The color scheme the user prefers (`prefers-color-scheme`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColorSchemePreference {
+    /// Dark text on a light background.
+    #[default]
+    Light,
+    /// Light text on a dark background.
+    Dark,
+}
+
+/// The contrast the user prefers (`prefers-contrast`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ContrastPreference {
+    /// No particular contrast preference.
+    #[default]
+    NoPreference,
+    /// Increased contrast, e.g. a system high contrast mode.
+    More,
+    /// Reduced contrast.
+    Less,
+}
+
+/// The environment media queries are evaluated against.
+///
+/// The viewport size is in logical pixels; `resolution` queries compare
+/// against `scale_factor` in `dppx`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MediaEnvironment {
+    /// Preferred color scheme.
+    pub color_scheme: ColorSchemePreference,
+    /// Preferred contrast.
+    pub contrast: ContrastPreference,
+    /// Viewport (window) width in logical pixels.
+    pub width: f32,
+    /// Viewport (window) height in logical pixels.
+    pub height: f32,
+    /// Device pixels per logical pixel.
+    pub scale_factor: f32,
+}
+
+impl Default for MediaEnvironment {
+    fn default() -> Self {
+        Self {
+            color_scheme: ColorSchemePreference::Light,
+            contrast: ContrastPreference::NoPreference,
+            width: 0.0,
+            height: 0.0,
+            scale_factor: 1.0,
+        }
+    }
+}
+
+impl MediaEnvironment {
+    /// Create an environment whose preferences match a theme mode.
+    pub fn for_theme_mode(mode: ThemeMode) -> Self {
+        let (color_scheme, contrast) = match mode {
+            ThemeMode::Light => (
+                ColorSchemePreference::Light,
+                ContrastPreference::NoPreference,
+            ),
+            ThemeMode::Dark => (
+                ColorSchemePreference::Dark,
+                ContrastPreference::NoPreference,
+            ),
+            ThemeMode::HighContrast => (ColorSchemePreference::Light, ContrastPreference::More),
+        };
+        Self {
+            color_scheme,
+            contrast,
+            ..Self::default()
+        }
+    }
+
+    /// Set the color scheme preference.
+    pub fn with_color_scheme(mut self, color_scheme: ColorSchemePreference) -> Self {
+        self.color_scheme = color_scheme;
+        self
+    }
+
+    /// Set the contrast preference.
+    pub fn with_contrast(mut self, contrast: ContrastPreference) -> Self {
+        self.contrast = contrast;
+        self
+    }
+
+    /// Set the viewport size in logical pixels.
+    pub fn with_viewport(mut self, width: f32, height: f32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Set the scale factor.
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+}
+
+/// The media type of a query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MediaType {
+    /// Any device (`all`, or no type given).
+    #[default]
+    All,
+    /// Screens (`screen`).
+    Screen,
+    /// Printed output (`print`), which never matches.
+    Print,
+}
+
+/// How a range feature compares the environment against its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaComparison {
+    /// `feature: value`
+    Equal,
+    /// `feature < value`
+    Less,
+    /// `feature <= value`, or `max-feature: value`
+    LessOrEqual,
+    /// `feature > value`
+    Greater,
+    /// `feature >= value`, or `min-feature: value`
+    GreaterOrEqual,
+}
+
+impl MediaComparison {
+    fn compare(&self, actual: f32, value: f32) -> bool {
+        match self {
+            Self::Equal => (actual - value).abs() < 1e-3,
+            Self::Less => actual < value,
+            Self::LessOrEqual => actual <= value,
+            Self::Greater => actual > value,
+            Self::GreaterOrEqual => actual >= value,
+        }
+    }
+
+    /// The comparison with its operands swapped (`800px <= width`).
+    pub fn flipped(&self) -> Self {
+        match self {
+            Self::Equal => Self::Equal,
+            Self::Less => Self::Greater,
+            Self::LessOrEqual => Self::GreaterOrEqual,
+            Self::Greater => Self::Less,
+            Self::GreaterOrEqual => Self::LessOrEqual,
+        }
+    }
+}
+
+/// A single parenthesized media feature test.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MediaFeature {
+    /// `(prefers-color-scheme: dark)`
+    PrefersColorScheme(ColorSchemePreference),
+    /// `(prefers-contrast: more)`
+    PrefersContrast(ContrastPreference),
+    /// `(min-width: 800px)`, `(width >= 800px)`; in logical pixels.
+    Width(MediaComparison, f32),
+    /// `(max-height: 600px)`, `(height < 600px)`; in logical pixels.
+    Height(MediaComparison, f32),
+    /// `(min-resolution: 2dppx)`, `(resolution >= 192dpi)`; in dppx.
+    Resolution(MediaComparison, f32),
+    /// A feature this engine does not support, which never matches.
+    Unknown(String),
+}
+
+impl MediaFeature {
+    /// Check whether the feature matches an environment.
+    pub fn matches(&self, env: &MediaEnvironment) -> bool {
+        match self {
+            Self::PrefersColorScheme(scheme) => env.color_scheme == *scheme,
+            Self::PrefersContrast(contrast) => env.contrast == *contrast,
+            Self::Width(cmp, value) => cmp.compare(env.width, *value),
+            Self::Height(cmp, value) => cmp.compare(env.height, *value),
+            Self::Resolution(cmp, value) => cmp.compare(env.scale_factor, *value),
+            Self::Unknown(_) => false,
+        }
+    }
+}
+
+/// A single media query: an optional media type and features joined by `and`.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaQuery {
+    /// Whether the query is negated with `not`.
+    pub negated: bool,
+    /// The media type.
+    pub media_type: MediaType,
+    /// Features that must all match.
+    pub features: Vec<MediaFeature>,
+}
+
+impl MediaQuery {
+    /// Check whether the query matches an environment.
+    pub fn matches(&self, env: &MediaEnvironment) -> bool {
+        let matches = self.media_type != MediaType::Print
+            && self.features.iter().all(|feature| feature.matches(env));
+        matches != self.negated
+    }
+}
+
+/// A comma-separated list of media queries, matching if any query matches.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MediaQueryList {
+    /// The queries in the list.
+    pub queries: Vec<MediaQuery>,
+}
+
+impl MediaQueryList {
+    /// Create a list from queries.
+    pub fn new(queries: Vec<MediaQuery>) -> Self {
+        Self { queries }
+    }
+
+    /// Check whether the list matches an environment.
+    ///
+    /// An empty list matches every environment, as in CSS.
+    pub fn matches(&self, env: &MediaEnvironment) -> bool {
+        self.queries.is_empty() || self.queries.iter().any(|query| query.matches(env))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(features: Vec<MediaFeature>) -> MediaQuery {
+        MediaQuery {
+            features,
+            ..MediaQuery::default()
+        }
+    }
+
+    #[test]
+    fn features_match_environment() {
+        let env = MediaEnvironment::default()
+            .with_color_scheme(ColorSchemePreference::Dark)
+            .with_viewport(1024.0, 768.0)
+            .with_scale_factor(2.0);
+
+        assert!(MediaFeature::PrefersColorScheme(ColorSchemePreference::Dark).matches(&env));
+        assert!(!MediaFeature::PrefersContrast(ContrastPreference::More).matches(&env));
+        assert!(MediaFeature::Width(MediaComparison::GreaterOrEqual, 800.0).matches(&env));
+        assert!(!MediaFeature::Height(MediaComparison::Greater, 768.0).matches(&env));
+        assert!(MediaFeature::Resolution(MediaComparison::Equal, 2.0).matches(&env));
+        assert!(!MediaFeature::Unknown("hover".into()).matches(&env));
+    }
+
+    #[test]
+    fn query_lists_combine_features() {
+        let env = MediaEnvironment::default().with_viewport(640.0, 480.0);
+        let wide = MediaFeature::Width(MediaComparison::GreaterOrEqual, 800.0);
+        let light = MediaFeature::PrefersColorScheme(ColorSchemePreference::Light);
+
+        assert!(!query(vec![light.clone(), wide.clone()]).matches(&env));
+        assert!(
+            MediaQueryList::new(vec![query(vec![wide.clone()]), query(vec![light])]).matches(&env)
+        );
+
+        let not_wide = MediaQuery {
+            negated: true,
+            ..query(vec![wide])
+        };
+        assert!(not_wide.matches(&env));
+
+        let print = MediaQuery {
+            media_type: MediaType::Print,
+            ..MediaQuery::default()
+        };
+        assert!(!print.matches(&env));
+        assert!(MediaQueryList::default().matches(&env));
+    }
+
+    #[test]
+    fn environment_follows_theme_mode() {
+        let env = MediaEnvironment::for_theme_mode(ThemeMode::HighContrast);
+        assert_eq!(env.contrast, ContrastPreference::More);
+        let env = MediaEnvironment::for_theme_mode(ThemeMode::Dark);
+        assert_eq!(env.color_scheme, ColorSchemePreference::Dark);
+    }
+}
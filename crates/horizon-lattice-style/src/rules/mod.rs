@@ -1,7 +1,12 @@
 //! Style rules and stylesheets.
 
+mod media;
 mod rule;
 mod stylesheet;
 
+pub use media::{
+    ColorSchemePreference, ContrastPreference, MediaComparison, MediaEnvironment, MediaFeature,
+    MediaQuery, MediaQueryList, MediaType,
+};
 pub use rule::StyleRule;
 pub use stylesheet::{StylePriority, StyleSheet};
@@ -1,5 +1,6 @@
 //! Single style rule definition.
 
+use super::{MediaEnvironment, MediaQueryList};
 use crate::selector::{Selector, Specificity};
 use crate::style::StyleProperties;
 
@@ -10,6 +11,7 @@ use crate::style::StyleProperties;
 /// - Properties to apply when the selector matches
 /// - Pre-computed specificity for efficient sorting
 /// - Source order for tie-breaking
+/// - Media queries from enclosing `@media` blocks
 #[derive(Debug, Clone)]
 pub struct StyleRule {
     /// The selector for matching widgets.
@@ -20,6 +22,9 @@ pub struct StyleRule {
     pub specificity: Specificity,
     /// Source order (for tie-breaking when specificity is equal).
     pub order: u32,
+    /// Media query lists of the enclosing `@media` blocks, outermost first.
+    /// The rule only applies while all of them match.
+    pub media: Vec<MediaQueryList>,
 }
 
 impl StyleRule {
@@ -31,9 +36,21 @@ impl StyleRule {
             properties,
             specificity,
             order,
+            media: vec![],
         }
     }
 
+    /// Restrict the rule to environments matching a media query list.
+    pub fn with_media(mut self, media: MediaQueryList) -> Self {
+        self.media.push(media);
+        self
+    }
+
+    /// Check whether the rule's media queries match an environment.
+    pub fn applies_in(&self, env: &MediaEnvironment) -> bool {
+        self.media.iter().all(|media| media.matches(env))
+    }
+
     /// Create a rule with a type selector.
     pub fn for_type(
         widget_type: impl Into<String>,
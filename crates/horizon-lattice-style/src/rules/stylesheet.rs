@@ -1,7 +1,9 @@
 //! Stylesheet collection and management.
 
+use crate::animation::Keyframes;
 use crate::rules::StyleRule;
 use crate::{Error, Result};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 /// Priority level for style sources.
@@ -32,6 +34,8 @@ impl StylePriority {
 pub struct StyleSheet {
     /// The rules in this stylesheet.
     pub rules: Vec<StyleRule>,
+    /// `@keyframes` rules, keyed by animation name.
+    pub keyframes: HashMap<String, Keyframes>,
     /// Priority level.
     pub priority: StylePriority,
     /// Source file path (for hot-reload tracking).
@@ -43,6 +47,7 @@ impl StyleSheet {
     pub fn new(priority: StylePriority) -> Self {
         Self {
             rules: vec![],
+            keyframes: HashMap::new(),
             priority,
             source_path: None,
         }
@@ -78,12 +83,13 @@ impl StyleSheet {
 
     /// Parse a stylesheet from CSS text.
     pub fn from_css(css: &str, priority: StylePriority) -> Result<Self> {
-        let rules = crate::parser::parse_css(css)?;
-        Ok(Self {
-            rules,
-            priority,
-            source_path: None,
-        })
+        let parsed = crate::parser::parse_stylesheet(css)?;
+        let mut sheet = Self::new(priority);
+        sheet.rules = parsed.rules;
+        for keyframes in parsed.keyframes {
+            sheet.add_keyframes(keyframes);
+        }
+        Ok(sheet)
     }
 
     /// Add a rule to the stylesheet.
@@ -104,6 +110,16 @@ impl StyleSheet {
         self.rules.push(rule);
     }
 
+    /// Add a `@keyframes` rule, replacing any with the same name.
+    pub fn add_keyframes(&mut self, keyframes: Keyframes) {
+        self.keyframes.insert(keyframes.name.clone(), keyframes);
+    }
+
+    /// Get a `@keyframes` rule by animation name.
+    pub fn keyframes(&self, name: &str) -> Option<&Keyframes> {
+        self.keyframes.get(name)
+    }
+
     /// Get the number of rules.
     pub fn len(&self) -> usize {
         self.rules.len()
@@ -119,9 +135,10 @@ impl StyleSheet {
         self.rules.iter()
     }
 
-    /// Clear all rules.
+    /// Clear all rules and `@keyframes`.
     pub fn clear(&mut self) {
         self.rules.clear();
+        self.keyframes.clear();
     }
 }
 
@@ -170,4 +187,18 @@ mod tests {
         assert_eq!(sheet.rules[1].order, 1);
         assert_eq!(sheet.rules[2].order, 2);
     }
+
+    #[test]
+    fn stylesheet_collects_keyframes() {
+        let mut sheet = StyleSheet::from_css(
+            "@keyframes pulse { 50% { opacity: 0.5; } } Button { color: red; }",
+            StylePriority::Application,
+        )
+        .unwrap();
+        assert_eq!(sheet.len(), 1);
+        assert_eq!(sheet.keyframes("pulse").map(|k| k.frames.len()), Some(1));
+
+        sheet.clear();
+        assert!(sheet.keyframes("pulse").is_none());
+    }
 }
@@ -1,6 +1,6 @@
 //! Selector matching algorithm.
 
-use super::{Combinator, PseudoClass, Selector, SelectorPart, TypeSelector};
+use super::{Combinator, PseudoClass, PseudoElement, Selector, SelectorPart, TypeSelector};
 
 /// Widget state for selector matching.
 #[derive(Debug, Clone, Default)]
@@ -17,6 +17,24 @@ pub struct WidgetMatchContext<'a> {
     pub sibling_info: Option<SiblingInfo>,
     /// Number of children (for :empty).
     pub child_count: usize,
+    /// Whether the widget is the root of the styled tree (for :root).
+    pub is_root: bool,
+    /// Attributes as `(name, value)` pairs (for [attr] selectors).
+    pub attributes: &'a [(String, String)],
+    /// The sub-control being styled (for ::handle etc.), or `None` for the widget itself.
+    ///
+    /// When set, `state` describes the sub-control rather than the widget.
+    pub pseudo_element: Option<PseudoElement>,
+}
+
+impl WidgetMatchContext<'_> {
+    /// Get the value of an attribute, if the widget has it.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
 }
 
 /// Widget interaction state.
@@ -77,7 +95,15 @@ impl SelectorMatcher {
     }
 
     /// Check if a selector part matches the widget.
+    ///
+    /// A part with a pseudo-element only matches when that sub-control is
+    /// being styled, and a part without one only matches the widget itself.
     pub fn part_matches(part: &SelectorPart, context: &WidgetMatchContext<'_>) -> bool {
+        part.pseudo_element == context.pseudo_element && Self::compound_matches(part, context)
+    }
+
+    /// Check the simple selectors of a part, ignoring its pseudo-element.
+    fn compound_matches(part: &SelectorPart, context: &WidgetMatchContext<'_>) -> bool {
         // Check type selector
         if let Some(type_sel) = &part.type_selector {
             match type_sel {
@@ -105,6 +131,13 @@ impl SelectorMatcher {
             }
         }
 
+        // Check attribute selectors (all must match)
+        for attribute in &part.attributes {
+            if !attribute.matches(context.attribute(&attribute.name)) {
+                return false;
+            }
+        }
+
         // Check pseudo-class selectors (all must match)
         for pseudo in &part.pseudo_classes {
             if !Self::pseudo_matches(pseudo, context) {
@@ -134,8 +167,9 @@ impl SelectorMatcher {
                 .map(|s| expr.matches(s.index))
                 .unwrap_or(false),
             PseudoClass::Empty => context.child_count == 0,
+            PseudoClass::Root => context.is_root,
 
-            PseudoClass::Not(inner) => !Self::part_matches(inner, context),
+            PseudoClass::Not(inner) => !Self::compound_matches(inner, context),
         }
     }
 }
@@ -251,6 +285,7 @@ pub fn matches_full<A: AncestorProvider>(
 #[allow(clippy::field_reassign_with_default)]
 mod tests {
     use super::*;
+    use crate::selector::{AttributeOperator, AttributeSelector};
 
     fn make_context<'a>(
         widget_type: &'a str,
@@ -264,6 +299,9 @@ mod tests {
             state,
             sibling_info: None,
             child_count: 0,
+            is_root: false,
+            attributes: &[],
+            pseudo_element: None,
         }
     }
 
@@ -394,4 +432,82 @@ mod tests {
 
         assert!(SelectorMatcher::part_matches(&part, &context));
     }
+
+    #[test]
+    fn attribute_selector_matches() {
+        let classes = vec![];
+        let attributes = vec![
+            ("severity".to_string(), "error".to_string()),
+            ("modified".to_string(), "true".to_string()),
+            ("tags".to_string(), "alpha beta".to_string()),
+        ];
+        let mut context = make_context("LineEdit", &classes, WidgetState::default());
+        context.attributes = &attributes;
+
+        let part = SelectorPart::type_only("LineEdit")
+            .with_attribute(AttributeSelector::equals("severity", "error"));
+        assert!(SelectorMatcher::part_matches(&part, &context));
+
+        let part = SelectorPart::new().with_attribute(AttributeSelector::exists("modified"));
+        assert!(SelectorMatcher::part_matches(&part, &context));
+
+        let part = SelectorPart::new().with_attribute(AttributeSelector::exists("missing"));
+        assert!(!SelectorMatcher::part_matches(&part, &context));
+
+        let part =
+            SelectorPart::new().with_attribute(AttributeSelector::equals("severity", "warning"));
+        assert!(!SelectorMatcher::part_matches(&part, &context));
+
+        let part = SelectorPart::new().with_attribute(AttributeSelector {
+            name: "severity".into(),
+            operator: AttributeOperator::Prefix("err".into()),
+        });
+        assert!(SelectorMatcher::part_matches(&part, &context));
+
+        let part = SelectorPart::new().with_attribute(AttributeSelector {
+            name: "tags".into(),
+            operator: AttributeOperator::Includes("beta".into()),
+        });
+        assert!(SelectorMatcher::part_matches(&part, &context));
+
+        let part = SelectorPart::new().with_attribute(AttributeSelector {
+            name: "tags".into(),
+            operator: AttributeOperator::Includes("bet".into()),
+        });
+        assert!(!SelectorMatcher::part_matches(&part, &context));
+    }
+
+    #[test]
+    fn pseudo_element_matches_only_sub_control() {
+        let classes = vec![];
+        let mut state = WidgetState::default();
+        state.hovered = true;
+        let mut context = make_context("Slider", &classes, state);
+
+        let widget_rule = SelectorPart::type_only("Slider");
+        let handle_rule = SelectorPart::type_only("Slider")
+            .with_pseudo_element(PseudoElement::Handle)
+            .with_pseudo(PseudoClass::Hover);
+
+        assert!(SelectorMatcher::part_matches(&widget_rule, &context));
+        assert!(!SelectorMatcher::part_matches(&handle_rule, &context));
+
+        context.pseudo_element = Some(PseudoElement::Handle);
+        assert!(!SelectorMatcher::part_matches(&widget_rule, &context));
+        assert!(SelectorMatcher::part_matches(&handle_rule, &context));
+
+        context.pseudo_element = Some(PseudoElement::Groove);
+        assert!(!SelectorMatcher::part_matches(&handle_rule, &context));
+
+        // Pseudo-classes apply to the sub-control's state
+        context.pseudo_element = Some(PseudoElement::Handle);
+        context.state.hovered = false;
+        assert!(!SelectorMatcher::part_matches(&handle_rule, &context));
+
+        // :not() on a sub-control checks the widget's simple selectors
+        let not_rule = SelectorPart::type_only("Slider")
+            .with_pseudo_element(PseudoElement::Handle)
+            .with_pseudo(PseudoClass::Not(Box::new(SelectorPart::class_only("flat"))));
+        assert!(SelectorMatcher::part_matches(&not_rule, &context));
+    }
 }
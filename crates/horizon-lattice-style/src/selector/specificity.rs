@@ -47,8 +47,14 @@ impl Specificity {
             *a += 1;
         }
 
-        // Class selectors
+        // Class and attribute selectors
         *b += part.classes.len() as u32;
+        *b += part.attributes.len() as u32;
+
+        // Pseudo-elements count like type selectors
+        if part.pseudo_element.is_some() {
+            *c += 1;
+        }
 
         // Pseudo-classes (except :not)
         for pseudo in &part.pseudo_classes {
@@ -114,7 +120,7 @@ pub struct SpecificityWithOrder {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::selector::{PseudoClass, SelectorPart};
+    use crate::selector::{AttributeSelector, PseudoClass, PseudoElement, SelectorPart};
 
     #[test]
     fn specificity_calculation() {
@@ -155,6 +161,27 @@ mod tests {
             combinators: vec![],
         };
         assert_eq!(Specificity::of_selector(&sel), Specificity(1, 2, 0));
+
+        // LineEdit[severity="error"] -> (0,1,1)
+        let sel = Selector {
+            parts: vec![
+                SelectorPart::type_only("LineEdit")
+                    .with_attribute(AttributeSelector::equals("severity", "error")),
+            ],
+            combinators: vec![],
+        };
+        assert_eq!(Specificity::of_selector(&sel), Specificity(0, 1, 1));
+
+        // Slider::handle:hover -> (0,1,2)
+        let sel = Selector {
+            parts: vec![
+                SelectorPart::type_only("Slider")
+                    .with_pseudo_element(PseudoElement::Handle)
+                    .with_pseudo(PseudoClass::Hover),
+            ],
+            combinators: vec![],
+        };
+        assert_eq!(Specificity::of_selector(&sel), Specificity(0, 1, 2));
     }
 
     #[test]
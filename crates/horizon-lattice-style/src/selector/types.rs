@@ -1,7 +1,8 @@
 //! Selector type definitions.
 //!
 //! This module provides CSS-like selector types for matching widgets by type,
-//! class, ID, and state.
+//! class, ID, attributes, and state, and for targeting the sub-controls of
+//! composite widgets with pseudo-elements.
 //!
 //! # Example
 //!
@@ -150,6 +151,13 @@ pub struct SelectorPart {
     pub classes: Vec<String>,
     /// Pseudo-class selectors (:hover, :pressed, etc.).
     pub pseudo_classes: Vec<PseudoClass>,
+    /// Attribute selectors ([name], [name="value"], etc.).
+    pub attributes: Vec<AttributeSelector>,
+    /// Sub-control pseudo-element (::handle, ::groove, etc.).
+    ///
+    /// Pseudo-classes on a part with a pseudo-element match the state of the
+    /// sub-control rather than the widget.
+    pub pseudo_element: Option<PseudoElement>,
 }
 
 impl SelectorPart {
@@ -214,12 +222,26 @@ impl SelectorPart {
         self
     }
 
+    /// Set the sub-control pseudo-element.
+    pub fn with_pseudo_element(mut self, element: PseudoElement) -> Self {
+        self.pseudo_element = Some(element);
+        self
+    }
+
+    /// Add an attribute selector.
+    pub fn with_attribute(mut self, attribute: AttributeSelector) -> Self {
+        self.attributes.push(attribute);
+        self
+    }
+
     /// Check if this is a universal selector with no other constraints.
     pub fn is_universal_only(&self) -> bool {
         matches!(self.type_selector, Some(TypeSelector::Universal))
             && self.id.is_none()
             && self.classes.is_empty()
             && self.pseudo_classes.is_empty()
+            && self.attributes.is_empty()
+            && self.pseudo_element.is_none()
     }
 }
 
@@ -239,6 +261,14 @@ impl fmt::Display for SelectorPart {
             write!(f, ".{}", class)?;
         }
 
+        for attribute in &self.attributes {
+            write!(f, "{}", attribute)?;
+        }
+
+        if let Some(element) = &self.pseudo_element {
+            write!(f, "::{}", element)?;
+        }
+
         for pseudo in &self.pseudo_classes {
             write!(f, ":{}", pseudo)?;
         }
@@ -247,6 +277,94 @@ impl fmt::Display for SelectorPart {
     }
 }
 
+/// Attribute selector - matches widget dynamic properties.
+///
+/// Widgets expose their dynamic properties (set through the object registry)
+/// as attributes, so `LineEdit[severity="error"]` matches a line edit whose
+/// `severity` property is `"error"`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeSelector {
+    /// Attribute (dynamic property) name.
+    pub name: String,
+    /// How the attribute value is compared.
+    pub operator: AttributeOperator,
+}
+
+impl AttributeSelector {
+    /// Create a selector matching when the attribute is present (`[name]`).
+    pub fn exists(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            operator: AttributeOperator::Exists,
+        }
+    }
+
+    /// Create a selector matching an exact value (`[name="value"]`).
+    pub fn equals(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            operator: AttributeOperator::Equals(value.into()),
+        }
+    }
+
+    /// Check if the selector matches an attribute value.
+    ///
+    /// `value` is `None` when the widget does not have the attribute.
+    pub fn matches(&self, value: Option<&str>) -> bool {
+        let Some(value) = value else {
+            return false;
+        };
+        match &self.operator {
+            AttributeOperator::Exists => true,
+            AttributeOperator::Equals(expected) => value == expected,
+            AttributeOperator::Includes(word) => value.split_whitespace().any(|w| w == word),
+            AttributeOperator::DashMatch(prefix) => {
+                value == prefix
+                    || value
+                        .strip_prefix(prefix.as_str())
+                        .is_some_and(|rest| rest.starts_with('-'))
+            }
+            AttributeOperator::Prefix(prefix) => !prefix.is_empty() && value.starts_with(prefix),
+            AttributeOperator::Suffix(suffix) => !suffix.is_empty() && value.ends_with(suffix),
+            AttributeOperator::Substring(part) => !part.is_empty() && value.contains(part),
+        }
+    }
+}
+
+impl fmt::Display for AttributeSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (op, value) = match &self.operator {
+            AttributeOperator::Exists => return write!(f, "[{}]", self.name),
+            AttributeOperator::Equals(v) => ("=", v),
+            AttributeOperator::Includes(v) => ("~=", v),
+            AttributeOperator::DashMatch(v) => ("|=", v),
+            AttributeOperator::Prefix(v) => ("^=", v),
+            AttributeOperator::Suffix(v) => ("$=", v),
+            AttributeOperator::Substring(v) => ("*=", v),
+        };
+        write!(f, "[{}{}\"{}\"]", self.name, op, value)
+    }
+}
+
+/// Comparison performed by an [`AttributeSelector`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AttributeOperator {
+    /// `[name]` - the attribute is present.
+    Exists,
+    /// `[name=value]` - the value is exactly `value`.
+    Equals(String),
+    /// `[name~=value]` - the value is a whitespace-separated list containing `value`.
+    Includes(String),
+    /// `[name|=value]` - the value is `value` or starts with `value-`.
+    DashMatch(String),
+    /// `[name^=value]` - the value starts with `value`.
+    Prefix(String),
+    /// `[name$=value]` - the value ends with `value`.
+    Suffix(String),
+    /// `[name*=value]` - the value contains `value`.
+    Substring(String),
+}
+
 /// Type selector - matches widget type.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TypeSelector {
@@ -296,6 +414,8 @@ pub enum PseudoClass {
     OnlyChild,
     /// :empty - has no children.
     Empty,
+    /// :root - the top of the styled widget tree.
+    Root,
     /// :not(selector) - negation.
     Not(Box<SelectorPart>),
 }
@@ -315,6 +435,7 @@ impl fmt::Display for PseudoClass {
             PseudoClass::NthChild(expr) => write!(f, "nth-child({})", expr),
             PseudoClass::OnlyChild => write!(f, "only-child"),
             PseudoClass::Empty => write!(f, "empty"),
+            PseudoClass::Root => write!(f, "root"),
             PseudoClass::Not(inner) => write!(f, "not({})", inner),
         }
     }
@@ -335,6 +456,109 @@ impl PseudoClass {
             "last-child" => Some(Self::LastChild),
             "only-child" => Some(Self::OnlyChild),
             "empty" => Some(Self::Empty),
+            "root" => Some(Self::Root),
+            _ => None,
+        }
+    }
+}
+
+/// Pseudo-elements naming the sub-controls of composite widgets.
+///
+/// A rule such as `Slider::handle:hover` styles only the handle of a slider,
+/// and only while the handle itself is hovered. Widgets compute these styles
+/// separately from their own style, inheriting from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PseudoElement {
+    /// ::handle - slider thumb, scroll bar slider, or splitter handle.
+    Handle,
+    /// ::groove - slider track.
+    Groove,
+    /// ::add-page - track area after the handle.
+    AddPage,
+    /// ::sub-page - track area before the handle.
+    SubPage,
+    /// ::add-line - scroll bar button that increases the value.
+    AddLine,
+    /// ::sub-line - scroll bar button that decreases the value.
+    SubLine,
+    /// ::up-button - spin box increment button.
+    UpButton,
+    /// ::down-button - spin box decrement button.
+    DownButton,
+    /// ::up-arrow - arrow inside an up button.
+    UpArrow,
+    /// ::down-arrow - arrow inside a down button or drop-down.
+    DownArrow,
+    /// ::drop-down - combo box drop-down button.
+    DropDown,
+    /// ::indicator - check box or radio button indicator.
+    Indicator,
+    /// ::tab - a tab in a tab bar.
+    Tab,
+    /// ::close-button - close button on a tab.
+    CloseButton,
+    /// ::section - a header view section.
+    Section,
+    /// ::item - an item in a view or menu.
+    Item,
+    /// ::chunk - the filled part of a progress bar.
+    Chunk,
+    /// ::title - the title of a group box or dock widget.
+    Title,
+}
+
+impl fmt::Display for PseudoElement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl PseudoElement {
+    /// Get the CSS name of the pseudo-element (without `::`).
+    pub fn name(&self) -> &'static str {
+        match self {
+            PseudoElement::Handle => "handle",
+            PseudoElement::Groove => "groove",
+            PseudoElement::AddPage => "add-page",
+            PseudoElement::SubPage => "sub-page",
+            PseudoElement::AddLine => "add-line",
+            PseudoElement::SubLine => "sub-line",
+            PseudoElement::UpButton => "up-button",
+            PseudoElement::DownButton => "down-button",
+            PseudoElement::UpArrow => "up-arrow",
+            PseudoElement::DownArrow => "down-arrow",
+            PseudoElement::DropDown => "drop-down",
+            PseudoElement::Indicator => "indicator",
+            PseudoElement::Tab => "tab",
+            PseudoElement::CloseButton => "close-button",
+            PseudoElement::Section => "section",
+            PseudoElement::Item => "item",
+            PseudoElement::Chunk => "chunk",
+            PseudoElement::Title => "title",
+        }
+    }
+
+    /// Parse a pseudo-element from CSS string (without `::`).
+    pub fn from_css(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "handle" => Some(Self::Handle),
+            "groove" => Some(Self::Groove),
+            "add-page" => Some(Self::AddPage),
+            "sub-page" => Some(Self::SubPage),
+            "add-line" => Some(Self::AddLine),
+            "sub-line" => Some(Self::SubLine),
+            "up-button" => Some(Self::UpButton),
+            "down-button" => Some(Self::DownButton),
+            "up-arrow" => Some(Self::UpArrow),
+            "down-arrow" => Some(Self::DownArrow),
+            "drop-down" => Some(Self::DropDown),
+            "indicator" => Some(Self::Indicator),
+            "tab" => Some(Self::Tab),
+            "close-button" => Some(Self::CloseButton),
+            "section" => Some(Self::Section),
+            "item" => Some(Self::Item),
+            "chunk" => Some(Self::Chunk),
+            "title" => Some(Self::Title),
             _ => None,
         }
     }
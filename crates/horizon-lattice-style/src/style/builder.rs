@@ -1,6 +1,7 @@
 //! Type-safe style builder DSL.
 
 use super::StyleProperties;
+use crate::animation::{AnimationSpec, TransitionSpec};
 use crate::types::{BorderStyle, Cursor, EdgeValues, LengthValue, StyleValue, TextAlign};
 use horizon_lattice_render::{
     BoxShadow, Color, CornerRadii, Paint,
@@ -321,6 +322,43 @@ impl Style {
         self
     }
 
+    // === Animation ===
+
+    /// Set transitions, replacing any previously set.
+    pub fn transitions(mut self, transitions: Vec<TransitionSpec>) -> Self {
+        self.props.transition = StyleValue::Set(transitions);
+        self
+    }
+
+    /// Add a transition.
+    pub fn transition(mut self, transition: TransitionSpec) -> Self {
+        let mut transitions = self.props.transition.as_set().cloned().unwrap_or_default();
+        transitions.push(transition);
+        self.props.transition = StyleValue::Set(transitions);
+        self
+    }
+
+    /// Add a keyframe animation.
+    pub fn animation(mut self, animation: AnimationSpec) -> Self {
+        let mut animations = self.props.animation.as_set().cloned().unwrap_or_default();
+        animations.push(animation);
+        self.props.animation = StyleValue::Set(animations);
+        self
+    }
+
+    // === Custom Properties ===
+
+    /// Declare a custom property, usable as `var(--name)` by this widget
+    /// and its descendants.
+    ///
+    /// The leading `--` is optional.
+    pub fn custom_property(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        let name = name.strip_prefix("--").map(str::to_string).unwrap_or(name);
+        self.props.custom_properties.insert(name, value.into());
+        self
+    }
+
     // === Special Values ===
 
     /// Set a property to inherit from parent.
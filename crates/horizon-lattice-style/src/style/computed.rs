@@ -1,5 +1,8 @@
 //! Computed style with all values resolved.
 
+use std::collections::HashMap;
+
+use crate::animation::{AnimationSpec, TransitionSpec};
 use crate::types::{BorderStyle, Cursor, TextAlign};
 use horizon_lattice_render::{
     BoxShadow, Color, CornerRadii, Paint, Rect,
@@ -95,6 +98,17 @@ pub struct ComputedStyle {
     pub cursor: Cursor,
     /// Whether pointer events are enabled.
     pub pointer_events: bool,
+
+    // === Animation ===
+    /// Transitions to run when a property's computed value changes.
+    pub transitions: Vec<TransitionSpec>,
+    /// Keyframe animations to play.
+    pub animations: Vec<AnimationSpec>,
+
+    // === Custom Properties ===
+    /// Custom properties in scope for the widget, with `var()` references
+    /// already substituted. Children inherit these.
+    pub custom_properties: HashMap<String, String>,
 }
 
 impl Default for ComputedStyle {
@@ -147,6 +161,13 @@ impl Default for ComputedStyle {
             // Interaction
             cursor: Cursor::Default,
             pointer_events: true,
+
+            // Animation
+            transitions: vec![],
+            animations: vec![],
+
+            // Custom properties
+            custom_properties: HashMap::new(),
         }
     }
 }
@@ -249,6 +270,14 @@ impl ComputedStyle {
     pub fn has_border_radius(&self) -> bool {
         !self.border_radius.is_zero()
     }
+
+    /// Get the value of a custom property in scope for the widget.
+    ///
+    /// The leading `--` is optional.
+    pub fn custom_property(&self, name: &str) -> Option<&str> {
+        let name = name.strip_prefix("--").unwrap_or(name);
+        self.custom_properties.get(name).map(String::as_str)
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,8 @@
 //! Style properties definition.
 
+use std::collections::HashMap;
+
+use crate::animation::{AnimationSpec, TransitionSpec};
 use crate::types::{BorderStyle, Cursor, EdgeValues, LengthValue, StyleValue, TextAlign};
 use horizon_lattice_render::{
     BoxShadow, Color, CornerRadii, Paint,
@@ -80,6 +83,28 @@ pub struct StyleProperties {
     pub cursor: StyleValue<Cursor>,
     /// Whether widget receives pointer events.
     pub pointer_events: StyleValue<bool>,
+
+    // === Animation ===
+    /// Transitions started when a property's computed value changes.
+    pub transition: StyleValue<Vec<TransitionSpec>>,
+    /// Keyframe animations to play.
+    pub animation: StyleValue<Vec<AnimationSpec>>,
+
+    // === Custom Properties ===
+    /// Custom property declarations (`--name: value`), keyed without the `--` prefix.
+    pub custom_properties: HashMap<String, String>,
+    /// Declarations whose value references `var()`, as `(property, raw value)`.
+    ///
+    /// These are substituted and parsed during cascade, once the custom
+    /// properties in scope for the widget are known. Declarations following
+    /// the first `var()` one in a block are listed too, in source order, so
+    /// they still override it.
+    pub variable_declarations: Vec<(String, String)>,
+    /// Fields of the properties declared with the `initial` keyword.
+    ///
+    /// [`StyleValue::Initial`] is also the value of undeclared properties,
+    /// so cascading resets the fields listed here instead of skipping them.
+    pub initial_properties: Vec<&'static str>,
 }
 
 /// Properties that can be inherited from parent to child.
@@ -112,7 +137,8 @@ impl StyleProperties {
 
     /// Merge another set of properties into this one.
     ///
-    /// Only explicitly set values from `other` will be copied.
+    /// Only explicitly set values from `other` will be copied. Custom
+    /// properties and `var()` declarations from `other` are added.
     pub fn merge(&mut self, other: &StyleProperties) {
         macro_rules! merge_if_set {
             ($($prop:ident),+ $(,)?) => {
@@ -159,7 +185,86 @@ impl StyleProperties {
             // Interaction
             cursor,
             pointer_events,
+            // Animation
+            transition,
+            animation,
+        );
+
+        self.custom_properties.extend(
+            other
+                .custom_properties
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone())),
         );
+        self.variable_declarations
+            .extend(other.variable_declarations.iter().cloned());
+        for field in &other.initial_properties {
+            self.set_initial(field);
+        }
+    }
+
+    /// Declare the property stored in `field` as `initial`.
+    ///
+    /// The field is set to [`StyleValue::Initial`] and listed in
+    /// [`initial_properties`](Self::initial_properties), so it overrides
+    /// earlier values when cascaded. Returns `false` if there is no such
+    /// field.
+    pub fn set_initial(&mut self, field: &str) -> bool {
+        macro_rules! set_initial {
+            ($($prop:ident),+ $(,)?) => {
+                match field {
+                    $(stringify!($prop) => {
+                        self.$prop = StyleValue::Initial;
+                        stringify!($prop)
+                    })+
+                    _ => return false,
+                }
+            };
+        }
+
+        let field = set_initial!(
+            // Box model
+            margin,
+            padding,
+            border_width,
+            border_color,
+            border_style,
+            border_radius,
+            // Background
+            background,
+            background_color,
+            // Size
+            min_width,
+            min_height,
+            max_width,
+            max_height,
+            width,
+            height,
+            // Typography
+            font_family,
+            font_size,
+            font_weight,
+            font_style,
+            font_stretch,
+            color,
+            text_align,
+            line_height,
+            letter_spacing,
+            text_decoration,
+            // Effects
+            opacity,
+            box_shadow,
+            // Interaction
+            cursor,
+            pointer_events,
+            // Animation
+            transition,
+            animation,
+        );
+        if !self.initial_properties.contains(&field) {
+            self.initial_properties.push(field);
+        }
+        true
     }
 }
 
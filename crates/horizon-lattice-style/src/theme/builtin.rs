@@ -202,6 +202,16 @@ fn create_widget_defaults(palette: &ColorPalette) -> HashMap<String, StyleProper
         Style::new().background_color(palette.divider).build(),
     );
 
+    // Built-in widgets match by their concrete type names
+    for (widget_type, generic) in [
+        ("PushButton", "Button"),
+        ("LineEdit", "TextInput"),
+        ("CheckBox", "Checkbox"),
+    ] {
+        let props = defaults[generic].clone();
+        defaults.insert(widget_type.to_string(), props);
+    }
+
     defaults
 }
 
@@ -225,6 +235,8 @@ mod tests {
         assert!(theme.widget_defaults.contains_key("Button"));
         assert!(theme.widget_defaults.contains_key("Label"));
         assert!(theme.widget_defaults.contains_key("TextInput"));
+        assert!(theme.widget_defaults.contains_key("PushButton"));
+        assert!(theme.widget_defaults.contains_key("LineEdit"));
     }
 
     #[test]
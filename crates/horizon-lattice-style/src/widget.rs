@@ -261,6 +261,19 @@ pub fn paint_styled_box(ctx: &mut dyn StylePaintContext, rect: Rect, style: &Com
     // 4. Paint inset box shadows (not yet implemented - would go here)
 }
 
+/// Check whether a style paints a visible box (background or border).
+///
+/// Widgets use this to decide whether a stylesheet has restyled a part, such
+/// as a sub-control, or whether to keep their built-in appearance.
+pub fn has_visible_box(style: &ComputedStyle) -> bool {
+    let has_background = match &style.background {
+        horizon_lattice_render::Paint::Solid(color) => color.a > 0.0,
+        _ => true,
+    };
+    let has_border = style.border_top_width > 0.0 && style.border_color.a > 0.0;
+    has_background || has_border
+}
+
 /// Calculate the content rectangle given a bounding rect and computed style.
 ///
 /// This accounts for padding, border, and margin to determine where
@@ -369,4 +382,18 @@ mod tests {
         assert_eq!(margin.width(), 110.0);
         assert_eq!(margin.height(), 90.0);
     }
+
+    #[test]
+    fn test_has_visible_box() {
+        let mut style = ComputedStyle::default();
+        assert!(!has_visible_box(&style));
+
+        style.background = horizon_lattice_render::Paint::Solid(Color::WHITE);
+        assert!(has_visible_box(&style));
+
+        let mut style = ComputedStyle::default();
+        style.border_top_width = 1.0;
+        style.border_color = Color::BLACK;
+        assert!(has_visible_box(&style));
+    }
 }
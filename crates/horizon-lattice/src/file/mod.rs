@@ -250,6 +250,8 @@ pub use compression::{
     extract_tar_gz_with_options, extract_tar_with_options, extract_zip, extract_zip_with_options,
     list_tar, list_tar_gz, list_zip, read_gzip, write_gzip, write_gzip_with_options,
 };
+#[cfg(feature = "async-runtime")]
+pub(crate) use directory::glob_to_regex;
 pub use directory::{
     DirEntry, DirIterator, FilteredDirIterator, GlobDirIterator, WalkDir, WalkDirOptions,
     WalkEntry, count_entries, create_dir, create_dir_all, dir_size, is_dir_empty, list_dir,
@@ -46,7 +46,10 @@ use std::time::Duration;
 
 use notify::event::{ModifyKind, RenameMode};
 use notify::{EventKind, RecommendedWatcher, RecursiveMode};
-use notify_debouncer_full::{DebouncedEvent, Debouncer, RecommendedCache, new_debouncer};
+use notify_debouncer_full::{
+    DebounceEventHandler, DebounceEventResult, DebouncedEvent, Debouncer, RecommendedCache,
+    new_debouncer,
+};
 
 use horizon_lattice_core::signal::Signal;
 
@@ -199,7 +202,7 @@ pub struct FileWatcher {
     /// The debounced watcher instance.
     debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
     /// Channel receiver for debounced events.
-    rx: Receiver<Result<Vec<DebouncedEvent>, Vec<notify::Error>>>,
+    rx: Receiver<DebounceEventResult>,
     /// Set of paths being watched with their watch modes.
     watched_paths: HashSet<PathBuf>,
     /// Maps paths to their watch modes.
@@ -217,10 +220,34 @@ impl FileWatcher {
     /// Create a new file watcher with custom options.
     pub fn with_options(options: WatchOptions) -> FileResult<Self> {
         let (tx, rx) = mpsc::channel();
+        Self::with_handler(options, rx, tx)
+    }
+
+    /// Create a file watcher that calls `wakeup` when changes are pending.
+    ///
+    /// `wakeup` runs on the watcher's background thread, so it should only
+    /// arrange for [`poll`](Self::poll) or [`process`](Self::process) to be
+    /// called, e.g. by posting a task to the event loop.
+    pub fn with_wakeup<F>(options: WatchOptions, wakeup: F) -> FileResult<Self>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel();
+        Self::with_handler(options, rx, move |result: DebounceEventResult| {
+            if tx.send(result).is_ok() {
+                wakeup();
+            }
+        })
+    }
 
+    fn with_handler(
+        options: WatchOptions,
+        rx: Receiver<DebounceEventResult>,
+        handler: impl DebounceEventHandler,
+    ) -> FileResult<Self> {
         // Use tick_rate = None to let the debouncer choose (1/4 of timeout)
         // RecommendedCache automatically enables file ID tracking on Windows/macOS
-        let debouncer = new_debouncer(options.debounce_duration, None, tx).map_err(|e| {
+        let debouncer = new_debouncer(options.debounce_duration, None, handler).map_err(|e| {
             FileError::new(
                 FileErrorKind::Other,
                 None,
@@ -727,4 +754,27 @@ mod tests {
             WatchEventKind::Modified | WatchEventKind::Created
         ));
     }
+
+    // Note: This test may be flaky on some systems due to timing
+    #[test]
+    #[ignore] // Enable manually for integration testing
+    fn test_wakeup_called_for_pending_changes() {
+        let dir = temp_dir();
+        let wakeups = Arc::new(Mutex::new(0));
+
+        let counter = wakeups.clone();
+        let mut watcher =
+            FileWatcher::with_wakeup(WatchOptions::default(), move || *counter.lock() += 1)
+                .unwrap();
+        watcher.watch(dir.path()).unwrap();
+        assert_eq!(*wakeups.lock(), 0);
+
+        fs::write(dir.path().join("new.txt"), "content").unwrap();
+
+        // Wait for debounce
+        std::thread::sleep(Duration::from_millis(300));
+
+        assert!(*wakeups.lock() > 0, "Expected a wakeup");
+        assert!(!watcher.poll().is_empty(), "Expected pending events");
+    }
 }
@@ -93,6 +93,11 @@ pub mod render {
     pub use horizon_lattice_render::*;
 }
 
+/// Styling system module (CSS-like stylesheets and themes).
+pub mod style {
+    pub use horizon_lattice_style::*;
+}
+
 /// File I/O operations and utilities.
 pub mod file;
 
@@ -0,0 +1,462 @@
+//! Incrementally loaded list model.
+//!
+//! `AsyncListModel<T>` loads its rows in batches from a background task as
+//! views scroll towards the end of the loaded rows. While a batch is being
+//! loaded, the model shows a placeholder row after the loaded rows.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use horizon_lattice::model::{AsyncListModel, BatchRequest};
+//!
+//! // Load log records 500 at a time on the async runtime.
+//! let model = AsyncListModel::with_runtime(500, |request: BatchRequest| async move {
+//!     load_records(request.offset, request.count).await
+//! });
+//!
+//! let mut view = ListView::new();
+//! view.set_model(Some(model.clone()));
+//! ```
+
+use parking_lot::{Mutex, RwLock};
+#[cfg(feature = "async-runtime")]
+use std::future::Future;
+use std::sync::{Arc, Weak};
+
+#[cfg(feature = "async-runtime")]
+use horizon_lattice_core::async_runtime::AsyncRuntime;
+use horizon_lattice_core::worker::Worker;
+
+use super::index::ModelIndex;
+use super::list_model::ListItem;
+use super::role::{ItemData, ItemRole};
+use super::traits::{ItemFlags, ItemModel, ModelSignals};
+
+/// The rows requested from a batch loader.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BatchRequest {
+    /// Index of the first requested row.
+    pub offset: usize,
+    /// Number of requested rows.
+    pub count: usize,
+}
+
+/// Delivers one batch of rows to an [`AsyncListModel`].
+///
+/// The model passes a sink to its fetcher with every [`BatchRequest`]. The
+/// fetcher starts the background work and hands the loaded rows to
+/// [`deliver`](Self::deliver) on the UI thread, for example from the callback
+/// of `Worker::send_with_callback` or `AsyncRuntime::spawn_with_callback`.
+pub struct BatchSink<T> {
+    model: Weak<AsyncListModel<T>>,
+    generation: u64,
+    request: BatchRequest,
+}
+
+impl<T: ListItem + 'static> BatchSink<T> {
+    /// Returns the request this sink answers.
+    pub fn request(&self) -> BatchRequest {
+        self.request
+    }
+
+    /// Replaces the placeholder row with `rows`.
+    ///
+    /// A batch with fewer rows than requested marks the end of the data, after
+    /// which the model stops fetching. Batches for a model that has been reset
+    /// or dropped since the request are discarded.
+    pub fn deliver(self, rows: Vec<T>) {
+        if let Some(model) = self.model.upgrade() {
+            model.finish_batch(self.generation, Some((rows, self.request.count)));
+        }
+    }
+
+    /// Removes the placeholder row without adding rows.
+    ///
+    /// The model can fetch the same rows again later, so use this when a
+    /// batch fails to load.
+    pub fn cancel(self) {
+        if let Some(model) = self.model.upgrade() {
+            model.finish_batch(self.generation, None);
+        }
+    }
+}
+
+type Fetcher<T> = Box<dyn Fn(BatchRequest, BatchSink<T>) + Send + Sync>;
+
+struct State<T> {
+    items: Vec<T>,
+    loading: bool,
+    complete: bool,
+    /// Incremented on every reset so that batches requested before it are dropped.
+    generation: u64,
+}
+
+/// A list model that loads its rows in batches on demand.
+///
+/// Views call `fetch_more` when they scroll near the last loaded row. The
+/// model then shows a placeholder row (see [`set_placeholder_text`]) and asks
+/// its fetcher for the next [`BatchRequest`]. When the rows arrive through the
+/// [`BatchSink`], the placeholder is removed and the rows are appended with
+/// the usual `rows_inserted` signals.
+///
+/// Rows provide their data through [`ListItem`], as in `ListModel`.
+///
+/// [`set_placeholder_text`]: Self::set_placeholder_text
+pub struct AsyncListModel<T> {
+    state: RwLock<State<T>>,
+    batch_size: usize,
+    placeholder_text: RwLock<String>,
+    fetcher: Fetcher<T>,
+    this: Weak<Self>,
+    signals: ModelSignals,
+}
+
+impl<T: ListItem + 'static> AsyncListModel<T> {
+    /// Creates a model that requests `batch_size` rows at a time from `fetcher`.
+    ///
+    /// The fetcher must eventually call `deliver` or `cancel` on the sink;
+    /// until it does, no further batches are requested.
+    pub fn new<F>(batch_size: usize, fetcher: F) -> Arc<Self>
+    where
+        F: Fn(BatchRequest, BatchSink<T>) + Send + Sync + 'static,
+    {
+        Arc::new_cyclic(|this| Self {
+            state: RwLock::new(State {
+                items: Vec::new(),
+                loading: false,
+                complete: false,
+                generation: 0,
+            }),
+            batch_size: batch_size.max(1),
+            placeholder_text: RwLock::new("Loading\u{2026}".to_string()),
+            fetcher: Box::new(fetcher),
+            this: this.clone(),
+            signals: ModelSignals::new(),
+        })
+    }
+
+    /// Creates a model that loads batches with `load` on a [`Worker`] thread.
+    pub fn with_worker<F>(batch_size: usize, worker: Arc<Worker<Vec<T>>>, load: F) -> Arc<Self>
+    where
+        F: Fn(BatchRequest) -> Vec<T> + Send + Sync + 'static,
+    {
+        let load = Arc::new(load);
+        Self::new(batch_size, move |request, sink| {
+            let load = load.clone();
+            // Keep the sink reachable so the batch can be cancelled if the
+            // worker has stopped and drops the task.
+            let sink = Arc::new(Mutex::new(Some(sink)));
+            let pending = sink.clone();
+            let queued = worker.send_with_callback(
+                move || load(request),
+                move |rows| {
+                    if let Some(sink) = pending.lock().take() {
+                        sink.deliver(rows);
+                    }
+                },
+            );
+            if !queued && let Some(sink) = sink.lock().take() {
+                sink.cancel();
+            }
+        })
+    }
+
+    /// Creates a model that loads batches with the future returned by `load`
+    /// on the global [`AsyncRuntime`].
+    #[cfg(feature = "async-runtime")]
+    pub fn with_runtime<F, Fut>(batch_size: usize, load: F) -> Arc<Self>
+    where
+        F: Fn(BatchRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<T>> + Send + 'static,
+    {
+        Self::new(batch_size, move |request, sink| {
+            AsyncRuntime::global()
+                .spawn_with_callback(load(request), move |rows| sink.deliver(rows));
+        })
+    }
+
+    /// Returns the number of rows requested per batch.
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the number of loaded rows, excluding the placeholder.
+    pub fn len(&self) -> usize {
+        self.state.read().items.len()
+    }
+
+    /// Returns `true` if no rows have been loaded.
+    pub fn is_empty(&self) -> bool {
+        self.state.read().items.is_empty()
+    }
+
+    /// Returns a reference to the loaded rows (read-only access).
+    pub fn items(&self) -> impl std::ops::Deref<Target = Vec<T>> + '_ {
+        parking_lot::RwLockReadGuard::map(self.state.read(), |state| &state.items)
+    }
+
+    /// Returns `true` while a batch is being loaded.
+    pub fn is_loading(&self) -> bool {
+        self.state.read().loading
+    }
+
+    /// Returns `true` once a short batch has marked the end of the data.
+    pub fn is_complete(&self) -> bool {
+        self.state.read().complete
+    }
+
+    /// Returns the text of the placeholder row.
+    pub fn placeholder_text(&self) -> String {
+        self.placeholder_text.read().clone()
+    }
+
+    /// Sets the text of the placeholder row shown while a batch is loading.
+    pub fn set_placeholder_text(&self, text: impl Into<String>) {
+        *self.placeholder_text.write() = text.into();
+        let state = self.state.read();
+        if state.loading {
+            let index = ModelIndex::new(state.items.len(), 0, ModelIndex::invalid());
+            drop(state);
+            self.signals
+                .emit_data_changed_single(index, vec![ItemRole::Display]);
+        }
+    }
+
+    /// Returns `true` if `index` is the placeholder row.
+    pub fn is_placeholder(&self, index: &ModelIndex) -> bool {
+        let state = self.state.read();
+        index.is_valid() && state.loading && index.row() == state.items.len()
+    }
+
+    /// Removes all rows and starts loading from the beginning again.
+    ///
+    /// Batches still in flight are discarded when they arrive.
+    pub fn reset(&self) {
+        self.signals.emit_reset(|| {
+            let mut state = self.state.write();
+            state.items.clear();
+            state.loading = false;
+            state.complete = false;
+            state.generation += 1;
+        });
+    }
+
+    fn finish_batch(&self, generation: u64, batch: Option<(Vec<T>, usize)>) {
+        let placeholder = {
+            let state = self.state.read();
+            if state.generation != generation || !state.loading {
+                return;
+            }
+            state.items.len()
+        };
+        let root = ModelIndex::invalid();
+
+        self.signals
+            .emit_rows_removed(root.clone(), placeholder, placeholder, || {
+                self.state.write().loading = false;
+            });
+
+        let Some((rows, requested)) = batch else {
+            return;
+        };
+        let received = rows.len();
+        if received > 0 {
+            self.signals
+                .emit_rows_inserted(root, placeholder, placeholder + received - 1, || {
+                    self.state.write().items.extend(rows)
+                });
+        }
+        if received < requested {
+            self.state.write().complete = true;
+        }
+    }
+}
+
+impl<T: ListItem + 'static> ItemModel for AsyncListModel<T> {
+    fn row_count(&self, parent: &ModelIndex) -> usize {
+        if parent.is_valid() {
+            return 0;
+        }
+        let state = self.state.read();
+        state.items.len() + usize::from(state.loading)
+    }
+
+    fn column_count(&self, _parent: &ModelIndex) -> usize {
+        1
+    }
+
+    fn data(&self, index: &ModelIndex, role: ItemRole) -> ItemData {
+        if !index.is_valid() {
+            return ItemData::None;
+        }
+        let state = self.state.read();
+        let Some(item) = state.items.get(index.row()) else {
+            if state.loading && index.row() == state.items.len() && role == ItemRole::Display {
+                return ItemData::from(self.placeholder_text());
+            }
+            return ItemData::None;
+        };
+
+        match role {
+            ItemRole::Display => item.display(),
+            ItemRole::Decoration => item.decoration(),
+            ItemRole::ToolTip => item.tooltip(),
+            ItemRole::Edit => item.edit(),
+            _ => item.data(role),
+        }
+    }
+
+    fn index(&self, row: usize, column: usize, parent: &ModelIndex) -> ModelIndex {
+        if parent.is_valid() || column > 0 || row >= self.row_count(parent) {
+            return ModelIndex::invalid();
+        }
+        ModelIndex::new(row, column, ModelIndex::invalid())
+    }
+
+    fn parent(&self, _index: &ModelIndex) -> ModelIndex {
+        ModelIndex::invalid()
+    }
+
+    fn signals(&self) -> &ModelSignals {
+        &self.signals
+    }
+
+    /// The placeholder row is disabled and can't be selected.
+    fn flags(&self, index: &ModelIndex) -> ItemFlags {
+        let state = self.state.read();
+        match state.items.get(index.row()) {
+            Some(item) if index.is_valid() => item.flags(),
+            _ => ItemFlags::disabled(),
+        }
+    }
+
+    fn can_fetch_more(&self, parent: &ModelIndex) -> bool {
+        let state = self.state.read();
+        !parent.is_valid() && !state.loading && !state.complete
+    }
+
+    /// Shows the placeholder row and requests the next batch from the fetcher.
+    fn fetch_more(&self, parent: &ModelIndex) {
+        if !self.can_fetch_more(parent) {
+            return;
+        }
+        let (offset, generation) = {
+            let state = self.state.read();
+            (state.items.len(), state.generation)
+        };
+
+        self.signals
+            .emit_rows_inserted(ModelIndex::invalid(), offset, offset, || {
+                self.state.write().loading = true;
+            });
+
+        let sink = BatchSink {
+            model: self.this.clone(),
+            generation,
+            request: BatchRequest {
+                offset,
+                count: self.batch_size,
+            },
+        };
+        (self.fetcher)(sink.request, sink);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deferred_model(
+        total: usize,
+    ) -> (
+        Arc<AsyncListModel<String>>,
+        Arc<Mutex<Vec<BatchSink<String>>>>,
+    ) {
+        let pending = Arc::new(Mutex::new(Vec::new()));
+        let pending_clone = pending.clone();
+        let model = AsyncListModel::new(2, move |request: BatchRequest, sink| {
+            assert!(request.offset < total);
+            pending_clone.lock().push(sink);
+        });
+        (model, pending)
+    }
+
+    fn rows(request: BatchRequest, total: usize) -> Vec<String> {
+        (request.offset..(request.offset + request.count).min(total))
+            .map(|row| format!("row {row}"))
+            .collect()
+    }
+
+    #[test]
+    fn test_batches_replace_placeholder() {
+        let (model, pending) = deferred_model(3);
+        let root = ModelIndex::invalid();
+
+        let inserted = Arc::new(Mutex::new(Vec::new()));
+        let inserted_clone = inserted.clone();
+        model
+            .signals()
+            .rows_inserted
+            .connect(move |(_, first, last)| inserted_clone.lock().push((*first, *last)));
+
+        assert!(model.can_fetch_more(&root));
+        model.fetch_more(&root);
+        assert!(model.is_loading());
+        assert!(!model.can_fetch_more(&root));
+        assert_eq!(model.row_count(&root), 1);
+        let placeholder = model.index(0, 0, &root);
+        assert!(model.is_placeholder(&placeholder));
+        assert_eq!(model.display_text(&placeholder).unwrap(), "Loading\u{2026}");
+        assert!(!model.flags(&placeholder).enabled);
+
+        let sink = pending.lock().pop().unwrap();
+        let request = sink.request();
+        sink.deliver(rows(request, 3));
+        assert_eq!(model.row_count(&root), 2);
+        assert_eq!(*model.items(), vec!["row 0", "row 1"]);
+
+        // A short batch marks the end of the data.
+        model.fetch_more(&root);
+        let sink = pending.lock().pop().unwrap();
+        assert_eq!(
+            sink.request(),
+            BatchRequest {
+                offset: 2,
+                count: 2
+            }
+        );
+        let request = sink.request();
+        sink.deliver(rows(request, 3));
+        assert_eq!(model.row_count(&root), 3);
+        assert!(model.is_complete());
+        assert!(!model.can_fetch_more(&root));
+
+        assert_eq!(*inserted.lock(), vec![(0, 0), (0, 1), (2, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_reset_discards_pending_batch() {
+        let (model, pending) = deferred_model(10);
+        let root = ModelIndex::invalid();
+
+        model.fetch_more(&root);
+        model.reset();
+        assert_eq!(model.row_count(&root), 0);
+
+        let sink = pending.lock().pop().unwrap();
+        sink.deliver(rows(
+            BatchRequest {
+                offset: 0,
+                count: 2,
+            },
+            10,
+        ));
+        assert_eq!(model.row_count(&root), 0);
+
+        // A cancelled batch can be fetched again.
+        model.fetch_more(&root);
+        pending.lock().pop().unwrap().cancel();
+        assert_eq!(model.row_count(&root), 0);
+        assert!(model.can_fetch_more(&root));
+    }
+}
@@ -0,0 +1,92 @@
+//! Tracking of structural model changes for views.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use super::traits::{ItemModel, ModelSignals};
+
+/// Disconnects one slot from the model's signals.
+type Disconnector = Box<dyn Fn(&ModelSignals) + Send + Sync>;
+
+/// Records when a model inserts, removes or moves rows or columns, or resets.
+///
+/// Views keep one for their model and re-run their layout when
+/// [`take_changed`](Self::take_changed) reports a change, so rows that a
+/// model adds on its own (for example from `fetch_more`) show up without
+/// the application relaying the signals.
+pub(crate) struct ModelChangeTracker {
+    model: Arc<dyn ItemModel>,
+    changed: Arc<AtomicBool>,
+    disconnectors: Vec<Disconnector>,
+}
+
+impl ModelChangeTracker {
+    /// Connects to the structural signals of `model`.
+    pub(crate) fn new(model: Arc<dyn ItemModel>) -> Self {
+        let changed = Arc::new(AtomicBool::new(false));
+        let signals = model.signals();
+        let mut disconnectors: Vec<Disconnector> = Vec::new();
+
+        macro_rules! track {
+            ($($signal:ident),*) => {$({
+                let changed = changed.clone();
+                let id = signals.$signal.connect(move |_| {
+                    changed.store(true, Ordering::Release);
+                });
+                disconnectors.push(Box::new(move |signals: &ModelSignals| {
+                    signals.$signal.disconnect(id);
+                }));
+            })*};
+        }
+
+        track!(
+            rows_inserted,
+            rows_removed,
+            rows_moved,
+            columns_inserted,
+            columns_removed,
+            layout_changed,
+            model_reset
+        );
+
+        Self {
+            model,
+            changed,
+            disconnectors,
+        }
+    }
+
+    /// Returns `true` if the model changed since the last call.
+    pub(crate) fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl Drop for ModelChangeTracker {
+    fn drop(&mut self) {
+        let signals = self.model.signals();
+        for disconnect in self.disconnectors.drain(..) {
+            disconnect(signals);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::ListModel;
+
+    #[test]
+    fn test_take_changed() {
+        let model = Arc::new(ListModel::new(vec!["a".to_string()]));
+        let tracker = ModelChangeTracker::new(model.clone());
+        assert!(!tracker.take_changed());
+
+        model.push("b".to_string());
+        assert!(tracker.take_changed());
+        assert!(!tracker.take_changed());
+
+        drop(tracker);
+        assert_eq!(model.signals().rows_inserted.connection_count(), 0);
+    }
+}
@@ -42,13 +42,16 @@
 //! - View-specific settings (alternating row colors, etc.)
 
 use horizon_lattice_render::{
-    Color, Font, FontFamily, FontSystem, GpuRenderer, HorizontalAlign, Icon, Point, Rect, Renderer,
-    Size, TextLayout, TextLayoutOptions, TextRenderer, VerticalAlign,
+    Color, DynRenderer, Font, FontFamily, FontSystem, GpuRenderer, HorizontalAlign, Icon, Point,
+    Rect, Renderer, Size, TextLayout, TextLayoutOptions, TextRenderer, VerticalAlign,
 };
 
 use super::index::ModelIndex;
-use super::role::{CheckState, ItemData, TextAlignment};
-use super::traits::ItemFlags;
+use super::item_editor::{ItemEditor, create_default_editor};
+use super::role::{
+    CheckState, HorizontalAlignment, ItemData, ItemRole, TextAlignment, VerticalAlignment,
+};
+use super::traits::{ItemFlags, ItemModel};
 
 /// Visual state flags for an item being rendered.
 ///
@@ -316,26 +319,91 @@ impl StyleOptionViewItem {
     }
 }
 
+/// Text laid out by a delegate, collected by [`DelegatePaintContext::draw_text`].
+#[derive(Debug, Clone)]
+pub struct DelegateText {
+    /// The laid out text.
+    pub layout: TextLayout,
+    /// Position of the layout's top-left corner.
+    pub position: Point,
+    /// Color of glyphs the layout doesn't color itself.
+    pub color: Color,
+}
+
 /// Context for delegate painting operations.
 ///
-/// Wraps a GPU renderer and provides the current item's rect.
+/// Wraps a renderer and provides the current item's rect.
+///
+/// Renderers draw shapes only, so a view that wants a delegate's text passes
+/// a font system with [`with_font_system`](Self::with_font_system). The
+/// delegate then lays its text out with that font system and hands it to
+/// [`draw_text`](Self::draw_text), and the view takes it back with
+/// [`take_text`](Self::take_text) to draw it, for example into a
+/// [`Picture`](horizon_lattice_render::Picture) when printing.
 pub struct DelegatePaintContext<'a> {
-    renderer: &'a mut GpuRenderer,
+    renderer: &'a mut (dyn DynRenderer + 'a),
     rect: Rect,
+    font_system: Option<&'a mut FontSystem>,
+    text: Vec<DelegateText>,
 }
 
 impl<'a> DelegatePaintContext<'a> {
     /// Creates a new paint context.
-    pub fn new(renderer: &'a mut GpuRenderer, rect: Rect) -> Self {
-        Self { renderer, rect }
+    pub fn new(renderer: &'a mut (dyn DynRenderer + 'a), rect: Rect) -> Self {
+        Self {
+            renderer,
+            rect,
+            font_system: None,
+            text: Vec::new(),
+        }
+    }
+
+    /// Collects the delegate's text, laid out with `font_system` (builder
+    /// pattern).
+    pub fn with_font_system(mut self, font_system: &'a mut FontSystem) -> Self {
+        self.font_system = Some(font_system);
+        self
+    }
+
+    /// Gets the font system to lay text out with, if the view collects text.
+    #[inline]
+    pub fn font_system(&mut self) -> Option<&mut FontSystem> {
+        self.font_system.as_deref_mut()
+    }
+
+    /// Hands laid out text to the view.
+    ///
+    /// The text is only kept when the view collects text, see
+    /// [`with_font_system`](Self::with_font_system).
+    pub fn draw_text(&mut self, layout: TextLayout, position: Point, color: Color) {
+        if self.font_system.is_some() {
+            self.text.push(DelegateText {
+                layout,
+                position,
+                color,
+            });
+        }
+    }
+
+    /// Takes the text handed over by [`draw_text`](Self::draw_text), in the
+    /// order it was drawn.
+    pub fn take_text(&mut self) -> Vec<DelegateText> {
+        std::mem::take(&mut self.text)
     }
 
     /// Gets the renderer for drawing.
     #[inline]
-    pub fn renderer(&mut self) -> &mut GpuRenderer {
+    pub fn renderer(&mut self) -> &mut (dyn DynRenderer + 'a) {
         self.renderer
     }
 
+    /// Gets the renderer as a [`GpuRenderer`], or `None` when painting into
+    /// another renderer, for example when printing.
+    #[inline]
+    pub fn gpu_renderer(&mut self) -> Option<&mut GpuRenderer> {
+        self.renderer.as_gpu_renderer()
+    }
+
     /// Gets the item's bounding rectangle.
     #[inline]
     pub fn rect(&self) -> Rect {
@@ -419,40 +487,97 @@ pub trait ItemDelegate: Send + Sync {
     /// * `option` - Style information about the item
     fn size_hint(&self, option: &StyleOptionViewItem) -> (f32, f32);
 
-    /// Called when editing should start for an item.
+    /// Creates the editor widget for an item.
+    ///
+    /// `data` is the item's `ItemRole::Edit` data, or its display data if
+    /// the model provides no edit data. Return `None` if the item can't be
+    /// edited in place. The default picks an editor from the data's variant
+    /// with [`create_default_editor`].
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - Style information about the item to edit
+    /// * `data` - The item's current value
+    fn create_editor(
+        &self,
+        _option: &StyleOptionViewItem,
+        data: &ItemData,
+    ) -> Option<Box<dyn ItemEditor>> {
+        create_default_editor(data)
+    }
+
+    /// Loads an item's value into its editor.
+    fn set_editor_data(&self, editor: &mut dyn ItemEditor, data: &ItemData) {
+        editor.set_editor_value(data);
+    }
+
+    /// Writes the editor's value back to the model.
+    ///
+    /// Returns `true` if the model accepted the value. The default calls
+    /// [`ItemModel::set_data`] with `ItemRole::Edit`.
+    fn set_model_data(
+        &self,
+        editor: &mut dyn ItemEditor,
+        model: &dyn ItemModel,
+        index: &ModelIndex,
+    ) -> bool {
+        editor.finish_input();
+        model.set_data(index, editor.editor_value(), ItemRole::Edit)
+    }
+
+    /// Positions the editor over the item.
+    ///
+    /// Called when editing starts and whenever the item moves, with
+    /// `option.rect` in view coordinates. The default fills the item rect.
+    fn set_editor_geometry(&self, editor: &mut dyn ItemEditor, option: &StyleOptionViewItem) {
+        editor.set_geometry(option.rect);
+    }
+
+    /// Called when a view opens an editor for an item.
     ///
-    /// Return `true` if editing was successfully started.
-    /// The default returns `false` (editing not supported).
+    /// Views ignore the return value. The default returns `false`.
     ///
     /// # Arguments
     ///
     /// * `option` - Style information about the item to edit
+    #[deprecated(note = "views open editors from `create_editor`; this is only a notification")]
     fn start_editing(&self, _option: &StyleOptionViewItem) -> bool {
         false
     }
 
-    /// Called when editing should commit.
+    /// Called when a view commits an edit.
     ///
-    /// Return the edited value to set on the model, or `None` to cancel.
+    /// Return the edited value to set on the model instead of the editor's,
+    /// or `None` to commit through [`set_model_data`](Self::set_model_data).
     /// The default returns `None`.
+    #[deprecated(note = "override `set_model_data` instead")]
     fn commit_editing(&self) -> Option<ItemData> {
         None
     }
 
-    /// Called when editing should be cancelled.
+    /// Called when a view closes an editor without committing it.
+    #[deprecated(note = "views close their editors themselves")]
     fn cancel_editing(&self) {}
 
     /// Returns `true` if the delegate is currently editing.
+    ///
+    /// Views track their own editors, so the default always returns `false`.
+    #[deprecated(note = "use the view's `is_editing` instead")]
     fn is_editing(&self) -> bool {
         false
     }
 
     /// Returns the editor widget's rectangle if editing.
+    ///
+    /// Views track their own editors, so the default always returns `None`.
+    #[deprecated(note = "use the editor's `geometry` instead")]
     fn editor_rect(&self) -> Option<Rect> {
         None
     }
 
-    /// Updates the editor size if the item rect changes during editing.
+    /// Called with the item rect after [`set_editor_geometry`](Self::set_editor_geometry)
+    /// positions the editor.
+    #[deprecated(note = "override `set_editor_geometry` instead")]
     fn update_editor_geometry(&self, _rect: Rect) {}
 
     /// Handles a click event on the item.
@@ -758,10 +883,14 @@ impl DefaultItemDelegate {
 
     /// Paint the text content.
     ///
-    /// Note: Full text rendering requires integration with the view's render pass system.
+    /// When the view collects text, the text is laid out on one line with the
+    /// view's font system, aligned by `option.text_alignment`, and handed to
+    /// [`DelegatePaintContext::draw_text`].
+    ///
+    /// Note: On screen, full text rendering requires integration with the view's render pass system.
     /// This implementation prepares text layout and stores glyphs for the view to render.
     /// For now, text is prepared but actual glyph rendering must be integrated by the view.
-    fn paint_text(&self, _ctx: &mut DelegatePaintContext<'_>, option: &StyleOptionViewItem) {
+    fn paint_text(&self, ctx: &mut DelegatePaintContext<'_>, option: &StyleOptionViewItem) {
         let text = match &option.text {
             Some(t) if !t.is_empty() => t,
             _ => return,
@@ -771,6 +900,28 @@ impl DefaultItemDelegate {
         let text_color = self.text_color(option);
         let font = option.font.as_ref().unwrap_or(&self.default_font);
 
+        if let Some(font_system) = ctx.font_system() {
+            let options = TextLayoutOptions::default().max_width(content_rect.width().max(1.0));
+            let layout = TextLayout::with_options(font_system, text, font, options);
+            let alignment = option.text_alignment;
+            let x = match alignment.horizontal {
+                HorizontalAlignment::Left | HorizontalAlignment::Justify => content_rect.left(),
+                HorizontalAlignment::Center => {
+                    content_rect.left() + (content_rect.width() - layout.width()) / 2.0
+                }
+                HorizontalAlignment::Right => content_rect.right() - layout.width(),
+            };
+            let y = match alignment.vertical {
+                VerticalAlignment::Top | VerticalAlignment::Baseline => content_rect.top(),
+                VerticalAlignment::Center => {
+                    content_rect.top() + (content_rect.height() - layout.height()) / 2.0
+                }
+                VerticalAlignment::Bottom => content_rect.bottom() - layout.height(),
+            };
+            ctx.draw_text(layout, Point::new(x, y), text_color);
+            return;
+        }
+
         // Calculate text position based on alignment
         let text_x = content_rect.origin.x;
         let text_y = content_rect.center().y;
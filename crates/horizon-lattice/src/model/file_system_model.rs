@@ -0,0 +1,910 @@
+//! File system model.
+//!
+//! `FileSystemModel` presents a directory tree as an [`ItemModel`]. Directory
+//! contents are listed on the async runtime when a view calls `fetch_more`,
+//! and changes reported by a [`FileWatcher`] are applied as incremental row
+//! inserts and removals.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use horizon_lattice::model::FileSystemModel;
+//!
+//! let model = FileSystemModel::new();
+//! model.set_root_path("assets")?;
+//! model.set_name_filters(&["*.png", "*.jpg"])?;
+//!
+//! let mut tree = TreeView::new();
+//! tree.set_model(Some(model.clone()));
+//! ```
+
+use chrono::{DateTime, Local};
+use parking_lot::{Mutex, RwLock};
+use regex::Regex;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Weak};
+
+use horizon_lattice_core::Application;
+use horizon_lattice_core::async_runtime::AsyncRuntime;
+use horizon_lattice_core::signal::Signal;
+use horizon_lattice_render::{Icon, IconSize};
+use horizon_lattice_style::icon_theme::{IconContext, IconLookup, IconName, IconResolver};
+
+use crate::file::{
+    FileError, FileResult, FileWatchEvent, FileWatcher, WatchOptions, glob_to_regex, read_dir_async,
+};
+use crate::widget::widgets::FileEntry;
+
+use super::index::ModelIndex;
+use super::role::{ItemData, ItemRole, TextAlignment};
+use super::traits::{ItemFlags, ItemModel, ModelSignals, Orientation};
+
+/// A node ID for internal tracking.
+type NodeId = u64;
+
+/// The node of the root directory, which maps to the invalid index.
+const ROOT: NodeId = 0;
+
+/// How far a directory's children have been loaded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FetchState {
+    NotFetched,
+    Fetching,
+    Fetched,
+}
+
+struct FsNode {
+    entry: FileEntry,
+    parent: Option<NodeId>,
+    children: Vec<NodeId>,
+    fetch: FetchState,
+}
+
+struct FsState {
+    nodes: HashMap<NodeId, FsNode>,
+    by_path: HashMap<PathBuf, NodeId>,
+    next_id: NodeId,
+    /// Incremented on every reset so that listings started before it are dropped.
+    generation: u64,
+    show_hidden: bool,
+    name_filters: Vec<(String, Regex)>,
+}
+
+impl FsState {
+    fn reset(&mut self, root: Option<FileEntry>) {
+        self.nodes.clear();
+        self.by_path.clear();
+        self.generation += 1;
+        if let Some(entry) = root {
+            self.by_path.insert(entry.path.clone(), ROOT);
+            self.nodes.insert(
+                ROOT,
+                FsNode {
+                    entry,
+                    parent: None,
+                    children: Vec::new(),
+                    fetch: FetchState::NotFetched,
+                },
+            );
+        }
+    }
+
+    /// Returns `true` if `entry` passes the hidden-file and name filters.
+    ///
+    /// Name filters only apply to files, so every directory stays reachable.
+    fn accepts(&self, entry: &FileEntry) -> bool {
+        (self.show_hidden || !entry.is_hidden)
+            && (entry.is_dir
+                || self.name_filters.is_empty()
+                || self
+                    .name_filters
+                    .iter()
+                    .any(|(_, regex)| regex.is_match(&entry.name)))
+    }
+
+    fn node_for_index(&self, index: &ModelIndex) -> Option<NodeId> {
+        let id = if index.is_valid() {
+            index.internal_id()
+        } else {
+            ROOT
+        };
+        self.nodes.contains_key(&id).then_some(id)
+    }
+
+    fn row_of(&self, id: NodeId) -> Option<usize> {
+        let parent = self.nodes.get(&id)?.parent?;
+        self.nodes[&parent]
+            .children
+            .iter()
+            .position(|&child| child == id)
+    }
+
+    fn index_for_node(&self, id: NodeId) -> ModelIndex {
+        let Some(parent) = self.nodes.get(&id).and_then(|node| node.parent) else {
+            return ModelIndex::invalid();
+        };
+        match self.row_of(id) {
+            Some(row) => ModelIndex::with_internal_id(row, 0, self.index_for_node(parent), id),
+            None => ModelIndex::invalid(),
+        }
+    }
+
+    /// Returns the row at which `entry` belongs among `parent`'s children.
+    fn insert_position(&self, parent: NodeId, entry: &FileEntry) -> usize {
+        self.nodes[&parent]
+            .children
+            .partition_point(|child| compare_entries(&self.nodes[child].entry, entry).is_lt())
+    }
+
+    fn add_child(&mut self, parent: NodeId, row: usize, entry: FileEntry) {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.by_path.insert(entry.path.clone(), id);
+        self.nodes.insert(
+            id,
+            FsNode {
+                entry,
+                parent: Some(parent),
+                children: Vec::new(),
+                fetch: FetchState::NotFetched,
+            },
+        );
+        if let Some(parent) = self.nodes.get_mut(&parent) {
+            parent.children.insert(row, id);
+        }
+    }
+
+    /// Removes a node and its descendants, returning the watched directories among them.
+    fn remove_subtree(&mut self, id: NodeId) -> Vec<PathBuf> {
+        if let Some(parent) = self.nodes.get(&id).and_then(|node| node.parent)
+            && let Some(parent) = self.nodes.get_mut(&parent)
+        {
+            parent.children.retain(|&child| child != id);
+        }
+
+        let mut watched = Vec::new();
+        let mut stack = vec![id];
+        while let Some(id) = stack.pop() {
+            let Some(node) = self.nodes.remove(&id) else {
+                continue;
+            };
+            self.by_path.remove(&node.entry.path);
+            if node.fetch == FetchState::Fetched {
+                watched.push(node.entry.path);
+            }
+            stack.extend(node.children);
+        }
+        watched
+    }
+}
+
+/// Icons resolved from the icon theme, cached by icon name.
+struct IconCache {
+    resolver: Option<IconResolver>,
+    size: IconSize,
+    icons: HashMap<&'static str, Option<Icon>>,
+}
+
+/// Orders directories before files, then by case-insensitive name.
+fn compare_entries(a: &FileEntry, b: &FileEntry) -> Ordering {
+    b.is_dir
+        .cmp(&a.is_dir)
+        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        .then_with(|| a.name.cmp(&b.name))
+}
+
+/// Returns the freedesktop icon name for an entry.
+fn icon_name(entry: &FileEntry) -> &'static str {
+    if entry.is_dir {
+        return IconName::FOLDER;
+    }
+    let extension = entry.extension().map(str::to_lowercase);
+    match extension.as_deref() {
+        Some("png" | "jpg" | "jpeg" | "gif" | "bmp" | "webp" | "svg" | "ico" | "tif" | "tiff") => {
+            "image-x-generic"
+        }
+        Some("wav" | "mp3" | "ogg" | "flac" | "aac" | "m4a" | "opus") => "audio-x-generic",
+        Some("mp4" | "mkv" | "avi" | "mov" | "webm") => "video-x-generic",
+        Some("zip" | "tar" | "gz" | "tgz" | "bz2" | "xz" | "7z" | "rar") => "package-x-generic",
+        Some("rs" | "c" | "h" | "cpp" | "py" | "js" | "ts" | "sh" | "lua" | "wgsl" | "glsl") => {
+            "text-x-script"
+        }
+        _ => "text-x-generic",
+    }
+}
+
+/// Lists a directory, following symlinks so linked directories can be expanded.
+async fn read_entries(path: PathBuf) -> FileResult<Vec<FileEntry>> {
+    let mut entries = Vec::new();
+    let mut dir = read_dir_async(&path).await?;
+    while let Some(entry) = dir.next().await {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let path = entry.path();
+        let metadata = match tokio::fs::metadata(&path).await {
+            Ok(metadata) => metadata,
+            // Dangling symlinks are listed as the link itself.
+            Err(_) => match entry.metadata().await {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            },
+        };
+
+        let mut file = FileEntry::new(path, metadata.is_dir());
+        file.size = metadata.len();
+        file.modified = metadata.modified().ok();
+        file.is_symlink = entry.is_symlink().await.unwrap_or(false);
+        entries.push(file);
+    }
+    Ok(entries)
+}
+
+/// An item model over a directory tree.
+///
+/// Each row is a file or directory, with the columns name, size, type and
+/// date modified. The root directory set with [`set_root_path`] maps to the
+/// invalid index, so a `TreeView` shows its contents as top-level rows.
+///
+/// Directories are loaded lazily: `can_fetch_more` is `true` for a directory
+/// that hasn't been listed, and `fetch_more` lists it on the global
+/// [`AsyncRuntime`]. The rows are inserted on the UI thread once the listing
+/// completes, followed by [`directory_loaded`].
+///
+/// Loaded directories are watched for changes, which are applied from the
+/// event loop while an [`Application`] is running.
+///
+/// [`set_root_path`]: Self::set_root_path
+/// [`directory_loaded`]: Self::directory_loaded
+/// [`process_file_changes`]: Self::process_file_changes
+pub struct FileSystemModel {
+    state: RwLock<FsState>,
+    icons: Mutex<IconCache>,
+    watcher: Mutex<Option<FileWatcher>>,
+    this: Weak<Self>,
+    signals: ModelSignals,
+    /// Emitted with a directory's path after its contents have been loaded.
+    pub directory_loaded: Signal<PathBuf>,
+}
+
+impl FileSystemModel {
+    /// Column showing the file name and icon.
+    pub const NAME_COLUMN: usize = 0;
+    /// Column showing the file size.
+    pub const SIZE_COLUMN: usize = 1;
+    /// Column showing the file type.
+    pub const TYPE_COLUMN: usize = 2;
+    /// Column showing the last modification time.
+    pub const MODIFIED_COLUMN: usize = 3;
+
+    const COLUMN_COUNT: usize = 4;
+
+    /// Creates an empty model with no root path.
+    pub fn new() -> Arc<Self> {
+        Arc::new_cyclic(|this| Self {
+            state: RwLock::new(FsState {
+                nodes: HashMap::new(),
+                by_path: HashMap::new(),
+                next_id: ROOT + 1,
+                generation: 0,
+                show_hidden: false,
+                name_filters: Vec::new(),
+            }),
+            icons: Mutex::new(IconCache {
+                resolver: None,
+                size: IconSize::Size16,
+                icons: HashMap::new(),
+            }),
+            watcher: Mutex::new(None),
+            this: this.clone(),
+            signals: ModelSignals::new(),
+            directory_loaded: Signal::new(),
+        })
+    }
+
+    /// Sets the directory whose contents are shown at the top level.
+    ///
+    /// Resets the model; the new root is listed on the next `fetch_more`.
+    pub fn set_root_path(&self, path: impl AsRef<Path>) -> FileResult<()> {
+        let path = path.as_ref();
+        let root = path
+            .canonicalize()
+            .and_then(FileEntry::from_path)
+            .map_err(|e| FileError::from_io(e, path))?;
+
+        self.signals
+            .emit_reset(|| self.state.write().reset(Some(root)));
+        *self.watcher.lock() = None;
+        Ok(())
+    }
+
+    /// Returns the canonical root directory, if set.
+    pub fn root_path(&self) -> Option<PathBuf> {
+        self.state
+            .read()
+            .nodes
+            .get(&ROOT)
+            .map(|node| node.entry.path.clone())
+    }
+
+    /// Returns whether hidden files are shown.
+    pub fn show_hidden(&self) -> bool {
+        self.state.read().show_hidden
+    }
+
+    /// Sets whether hidden files are shown.
+    ///
+    /// Resets the model if the setting changes.
+    pub fn set_show_hidden(&self, show: bool) {
+        if self.state.read().show_hidden == show {
+            return;
+        }
+        self.state.write().show_hidden = show;
+        self.reload();
+    }
+
+    /// Returns the glob patterns that file names must match.
+    pub fn name_filters(&self) -> Vec<String> {
+        self.state
+            .read()
+            .name_filters
+            .iter()
+            .map(|(pattern, _)| pattern.clone())
+            .collect()
+    }
+
+    /// Sets glob patterns (such as `*.png`) that file names must match.
+    ///
+    /// Directories are always shown. An empty list shows all files. Matching
+    /// is case-sensitive. Resets the model.
+    pub fn set_name_filters(&self, patterns: &[&str]) -> FileResult<()> {
+        let filters = patterns
+            .iter()
+            .map(|pattern| Ok((pattern.to_string(), glob_to_regex(pattern)?)))
+            .collect::<FileResult<Vec<_>>>()?;
+
+        self.state.write().name_filters = filters;
+        self.reload();
+        Ok(())
+    }
+
+    /// Sets the icon theme resolver used for item icons.
+    ///
+    /// Without a resolver, items have no decoration.
+    pub fn set_icon_resolver(&self, resolver: Option<IconResolver>) {
+        let mut icons = self.icons.lock();
+        icons.resolver = resolver;
+        icons.icons.clear();
+    }
+
+    /// Sets the size of the item icons.
+    pub fn set_icon_size(&self, size: IconSize) {
+        let mut icons = self.icons.lock();
+        if icons.size != size {
+            icons.size = size;
+            icons.icons.clear();
+        }
+    }
+
+    /// Returns the entry at `index`.
+    pub fn file_entry(&self, index: &ModelIndex) -> Option<FileEntry> {
+        let state = self.state.read();
+        let id = state.node_for_index(index)?;
+        Some(state.nodes[&id].entry.clone())
+    }
+
+    /// Returns the path of the entry at `index`.
+    pub fn file_path(&self, index: &ModelIndex) -> Option<PathBuf> {
+        let state = self.state.read();
+        let id = state.node_for_index(index)?;
+        Some(state.nodes[&id].entry.path.clone())
+    }
+
+    /// Returns `true` if the entry at `index` is a directory.
+    pub fn is_dir(&self, index: &ModelIndex) -> bool {
+        let state = self.state.read();
+        state
+            .node_for_index(index)
+            .is_some_and(|id| state.nodes[&id].entry.is_dir)
+    }
+
+    /// Returns the index of a loaded entry by path.
+    ///
+    /// Returns the invalid index for the root directory and for paths that
+    /// haven't been loaded.
+    pub fn index_for_path(&self, path: impl AsRef<Path>) -> ModelIndex {
+        let state = self.state.read();
+        match state.by_path.get(path.as_ref()) {
+            Some(&id) => state.index_for_node(id),
+            None => ModelIndex::invalid(),
+        }
+    }
+
+    /// Applies pending changes from the file watcher.
+    ///
+    /// Created, removed, renamed and modified entries in loaded directories
+    /// are inserted, removed or updated in place. This is done from the event
+    /// loop as changes arrive; call it directly when no [`Application`] is
+    /// running, e.g. in tests.
+    ///
+    /// Returns the number of events processed.
+    pub fn process_file_changes(&self) -> usize {
+        let events = match self.watcher.lock().as_mut() {
+            Some(watcher) => watcher.poll(),
+            None => return 0,
+        };
+        for event in &events {
+            self.apply_watch_event(event);
+        }
+        events.len()
+    }
+
+    /// Drops all loaded directories after a filter change.
+    fn reload(&self) {
+        self.signals.emit_reset(|| {
+            let mut state = self.state.write();
+            let root = state.nodes.get(&ROOT).map(|node| node.entry.clone());
+            state.reset(root);
+        });
+        *self.watcher.lock() = None;
+    }
+
+    /// Marks the directory at `parent` as being fetched.
+    ///
+    /// Returns its node, the current generation and its path, or `None` if
+    /// there is nothing to fetch.
+    fn begin_fetch(&self, parent: &ModelIndex) -> Option<(NodeId, u64, PathBuf)> {
+        let mut state = self.state.write();
+        let id = state.node_for_index(parent)?;
+        let generation = state.generation;
+        let node = state.nodes.get_mut(&id)?;
+        if !node.entry.is_dir || node.fetch != FetchState::NotFetched {
+            return None;
+        }
+        node.fetch = FetchState::Fetching;
+        Some((id, generation, node.entry.path.clone()))
+    }
+
+    /// Inserts the listing of a directory started by `begin_fetch`.
+    fn finish_fetch(&self, id: NodeId, generation: u64, listing: FileResult<Vec<FileEntry>>) {
+        let (parent, entries, path) = {
+            let state = self.state.read();
+            let Some(node) = state
+                .nodes
+                .get(&id)
+                .filter(|_| state.generation == generation)
+            else {
+                return;
+            };
+            // Unreadable directories are shown as empty.
+            let mut entries: Vec<FileEntry> = listing
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|entry| state.accepts(entry))
+                .collect();
+            entries.sort_by(compare_entries);
+            (state.index_for_node(id), entries, node.entry.path.clone())
+        };
+
+        if !entries.is_empty() {
+            let last = entries.len() - 1;
+            self.signals.emit_rows_inserted(parent, 0, last, || {
+                let mut state = self.state.write();
+                for (row, entry) in entries.into_iter().enumerate() {
+                    state.add_child(id, row, entry);
+                }
+            });
+        }
+        if let Some(node) = self.state.write().nodes.get_mut(&id) {
+            node.fetch = FetchState::Fetched;
+        }
+
+        // Live updates are best-effort; without a watcher the listing is static.
+        let mut watcher = self.watcher.lock();
+        if watcher.is_none() {
+            *watcher = self.create_watcher().ok();
+        }
+        if let Some(watcher) = watcher.as_mut() {
+            let _ = watcher.watch(&path);
+        }
+        drop(watcher);
+
+        self.directory_loaded.emit(path);
+    }
+
+    /// Creates a watcher whose changes are applied from the event loop.
+    fn create_watcher(&self) -> FileResult<FileWatcher> {
+        let this = self.this.clone();
+        FileWatcher::with_wakeup(WatchOptions::default(), move || {
+            if let Some(app) = Application::try_instance() {
+                let this = this.clone();
+                app.post_task(move || {
+                    if let Some(model) = this.upgrade() {
+                        model.process_file_changes();
+                    }
+                });
+            }
+        })
+    }
+
+    fn apply_watch_event(&self, event: &FileWatchEvent) {
+        if let Some(old_path) = &event.old_path {
+            self.sync_path(old_path);
+        }
+        self.sync_path(&event.path);
+    }
+
+    /// Brings the row for `path` in line with the file system.
+    fn sync_path(&self, path: &Path) {
+        let entry = FileEntry::from_path(path.to_path_buf()).ok();
+        let state = self.state.read();
+        let existing = state.by_path.get(path).copied().filter(|&id| id != ROOT);
+        let entry = entry.filter(|entry| state.accepts(entry));
+
+        // A modified entry of the same kind keeps its row.
+        if let (Some(id), Some(entry)) = (existing, &entry)
+            && state.nodes[&id].entry.is_dir == entry.is_dir
+        {
+            let top_left = state.index_for_node(id);
+            let bottom_right = ModelIndex::with_internal_id(
+                top_left.row(),
+                Self::COLUMN_COUNT - 1,
+                top_left.parent(),
+                id,
+            );
+            drop(state);
+            if let Some(node) = self.state.write().nodes.get_mut(&id) {
+                node.entry = entry.clone();
+            }
+            self.signals
+                .data_changed
+                .emit((top_left, bottom_right, Vec::new()));
+            return;
+        }
+        drop(state);
+
+        if let Some(id) = existing {
+            self.remove_node(id);
+        }
+        if let Some(entry) = entry {
+            self.insert_entry(entry);
+        }
+    }
+
+    /// Inserts `entry` into its parent directory if that has been loaded.
+    fn insert_entry(&self, entry: FileEntry) {
+        let Some(parent_path) = entry.path.parent() else {
+            return;
+        };
+        let (parent_id, row, parent) = {
+            let state = self.state.read();
+            let Some(&parent_id) = state.by_path.get(parent_path) else {
+                return;
+            };
+            if state.nodes[&parent_id].fetch != FetchState::Fetched {
+                return;
+            }
+            (
+                parent_id,
+                state.insert_position(parent_id, &entry),
+                state.index_for_node(parent_id),
+            )
+        };
+
+        self.signals.emit_rows_inserted(parent, row, row, || {
+            self.state.write().add_child(parent_id, row, entry);
+        });
+    }
+
+    fn remove_node(&self, id: NodeId) {
+        let (parent, row) = {
+            let state = self.state.read();
+            let Some(parent) = state.nodes.get(&id).and_then(|node| node.parent) else {
+                return;
+            };
+            let Some(row) = state.row_of(id) else {
+                return;
+            };
+            (state.index_for_node(parent), row)
+        };
+
+        let mut watched = Vec::new();
+        self.signals.emit_rows_removed(parent, row, row, || {
+            watched = self.state.write().remove_subtree(id);
+        });
+        if let Some(watcher) = self.watcher.lock().as_mut() {
+            for path in watched {
+                let _ = watcher.unwatch(path);
+            }
+        }
+    }
+
+    fn icon(&self, entry: &FileEntry) -> Option<Icon> {
+        let mut icons = self.icons.lock();
+        let IconCache {
+            resolver,
+            size,
+            icons,
+        } = &mut *icons;
+        let resolver = resolver.as_mut()?;
+        let name = icon_name(entry);
+        icons
+            .entry(name)
+            .or_insert_with(|| {
+                let context = if entry.is_dir {
+                    IconContext::Places
+                } else {
+                    IconContext::MimeTypes
+                };
+                resolver.resolve(&IconLookup::new(name, *size).with_context(context))
+            })
+            .clone()
+    }
+}
+
+impl ItemModel for FileSystemModel {
+    fn row_count(&self, parent: &ModelIndex) -> usize {
+        let state = self.state.read();
+        state
+            .node_for_index(parent)
+            .map_or(0, |id| state.nodes[&id].children.len())
+    }
+
+    fn column_count(&self, _parent: &ModelIndex) -> usize {
+        Self::COLUMN_COUNT
+    }
+
+    fn data(&self, index: &ModelIndex, role: ItemRole) -> ItemData {
+        if !index.is_valid() {
+            return ItemData::None;
+        }
+        let state = self.state.read();
+        let Some(node) = state.nodes.get(&index.internal_id()) else {
+            return ItemData::None;
+        };
+        let entry = &node.entry;
+
+        match (role, index.column()) {
+            (ItemRole::Display, Self::NAME_COLUMN) => ItemData::from(entry.name.as_str()),
+            (ItemRole::Display, Self::SIZE_COLUMN) => ItemData::from(entry.size_string()),
+            (ItemRole::Display, Self::TYPE_COLUMN) => ItemData::from(if entry.is_dir {
+                "Folder".to_string()
+            } else {
+                match entry.extension() {
+                    Some(extension) => format!("{} File", extension.to_uppercase()),
+                    None => "File".to_string(),
+                }
+            }),
+            (ItemRole::Display, Self::MODIFIED_COLUMN) => match entry.modified {
+                Some(time) => ItemData::from(
+                    DateTime::<Local>::from(time)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string(),
+                ),
+                None => ItemData::None,
+            },
+            (ItemRole::Decoration, Self::NAME_COLUMN) => {
+                self.icon(entry).map_or(ItemData::None, ItemData::from)
+            }
+            (ItemRole::ToolTip, _) => ItemData::from(entry.path.display().to_string()),
+            (ItemRole::TextAlignment, Self::SIZE_COLUMN) => ItemData::from(TextAlignment::right()),
+            _ => ItemData::None,
+        }
+    }
+
+    fn index(&self, row: usize, column: usize, parent: &ModelIndex) -> ModelIndex {
+        if column >= Self::COLUMN_COUNT {
+            return ModelIndex::invalid();
+        }
+        let state = self.state.read();
+        let child = state
+            .node_for_index(parent)
+            .and_then(|id| state.nodes[&id].children.get(row).copied());
+        match child {
+            Some(id) => ModelIndex::with_internal_id(row, column, parent.clone(), id),
+            None => ModelIndex::invalid(),
+        }
+    }
+
+    fn parent(&self, index: &ModelIndex) -> ModelIndex {
+        if !index.is_valid() {
+            return ModelIndex::invalid();
+        }
+        let state = self.state.read();
+        match state
+            .nodes
+            .get(&index.internal_id())
+            .and_then(|node| node.parent)
+        {
+            Some(parent) => state.index_for_node(parent),
+            None => ModelIndex::invalid(),
+        }
+    }
+
+    fn signals(&self) -> &ModelSignals {
+        &self.signals
+    }
+
+    fn flags(&self, index: &ModelIndex) -> ItemFlags {
+        let mut flags = ItemFlags::new();
+        flags.never_has_children = !self.is_dir(index);
+        flags
+    }
+
+    /// Unlisted directories are assumed to have children.
+    fn has_children(&self, parent: &ModelIndex) -> bool {
+        let state = self.state.read();
+        state.node_for_index(parent).is_some_and(|id| {
+            let node = &state.nodes[&id];
+            node.entry.is_dir && (node.fetch != FetchState::Fetched || !node.children.is_empty())
+        })
+    }
+
+    fn header_data(&self, section: usize, orientation: Orientation, role: ItemRole) -> ItemData {
+        if orientation != Orientation::Horizontal || role != ItemRole::Display {
+            return ItemData::None;
+        }
+        match section {
+            Self::NAME_COLUMN => ItemData::from("Name"),
+            Self::SIZE_COLUMN => ItemData::from("Size"),
+            Self::TYPE_COLUMN => ItemData::from("Type"),
+            Self::MODIFIED_COLUMN => ItemData::from("Date Modified"),
+            _ => ItemData::None,
+        }
+    }
+
+    fn can_fetch_more(&self, parent: &ModelIndex) -> bool {
+        let state = self.state.read();
+        state.node_for_index(parent).is_some_and(|id| {
+            let node = &state.nodes[&id];
+            node.entry.is_dir && node.fetch == FetchState::NotFetched
+        })
+    }
+
+    /// Lists the directory at `parent` on the global [`AsyncRuntime`].
+    ///
+    /// The rows are inserted when the listing is delivered to the UI thread.
+    fn fetch_more(&self, parent: &ModelIndex) {
+        let Some((id, generation, path)) = self.begin_fetch(parent) else {
+            return;
+        };
+        let this = self.this.clone();
+        AsyncRuntime::global().spawn_with_callback(read_entries(path), move |listing| {
+            if let Some(model) = this.upgrade() {
+                model.finish_fetch(id, generation, listing);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file::{TempDirectory, WatchEventKind};
+    use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+
+    /// Runs a listing on the calling thread, as `fetch_more` would in the background.
+    fn fetch(model: &FileSystemModel, parent: &ModelIndex) {
+        let (id, generation, path) = model.begin_fetch(parent).unwrap();
+        let listing = AsyncRuntime::global().block_on(read_entries(path));
+        model.finish_fetch(id, generation, listing);
+    }
+
+    fn names(model: &FileSystemModel, parent: &ModelIndex) -> Vec<String> {
+        (0..model.row_count(parent))
+            .map(|row| {
+                let index = model.index(row, 0, parent);
+                model.display_text(&index).unwrap()
+            })
+            .collect()
+    }
+
+    fn setup_dir() -> TempDirectory {
+        let dir = TempDirectory::new().unwrap();
+        std::fs::create_dir(dir.path().join("assets")).unwrap();
+        std::fs::write(dir.path().join("b.txt"), "hello").unwrap();
+        std::fs::write(dir.path().join("A.png"), "").unwrap();
+        std::fs::write(dir.path().join(".hidden"), "").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_lazy_listing() {
+        let dir = setup_dir();
+        let model = FileSystemModel::new();
+        model.set_root_path(dir.path()).unwrap();
+        let root = ModelIndex::invalid();
+
+        let loaded = Arc::new(AtomicUsize::new(0));
+        let loaded_clone = loaded.clone();
+        model.directory_loaded.connect(move |_| {
+            loaded_clone.fetch_add(1, AtomicOrdering::SeqCst);
+        });
+
+        assert_eq!(model.row_count(&root), 0);
+        assert!(model.has_children(&root));
+        assert!(model.can_fetch_more(&root));
+
+        fetch(&model, &root);
+        assert!(!model.can_fetch_more(&root));
+        assert_eq!(names(&model, &root), vec!["assets", "A.png", "b.txt"]);
+        assert_eq!(loaded.load(AtomicOrdering::SeqCst), 1);
+
+        let text = model.index(2, FileSystemModel::SIZE_COLUMN, &root);
+        assert_eq!(model.display_text(&text).unwrap(), "5 B");
+        let text = model.index(2, FileSystemModel::TYPE_COLUMN, &root);
+        assert_eq!(model.display_text(&text).unwrap(), "TXT File");
+        assert!(model.flags(&text).never_has_children);
+
+        let assets = model.index(0, 0, &root);
+        assert!(model.is_dir(&assets));
+        assert!(model.has_children(&assets));
+        fetch(&model, &assets);
+        assert_eq!(model.row_count(&assets), 0);
+        assert!(!model.has_children(&assets));
+
+        let path = model.file_path(&assets).unwrap();
+        assert_eq!(model.index_for_path(&path), assets);
+    }
+
+    #[test]
+    fn test_filters() {
+        let dir = setup_dir();
+        let model = FileSystemModel::new();
+        model.set_root_path(dir.path()).unwrap();
+        let root = ModelIndex::invalid();
+        fetch(&model, &root);
+
+        model.set_show_hidden(true);
+        assert_eq!(model.row_count(&root), 0);
+        fetch(&model, &root);
+        assert_eq!(
+            names(&model, &root),
+            vec!["assets", ".hidden", "A.png", "b.txt"]
+        );
+
+        model.set_name_filters(&["*.png"]).unwrap();
+        fetch(&model, &root);
+        assert_eq!(names(&model, &root), vec!["assets", "A.png"]);
+        assert_eq!(model.name_filters(), vec!["*.png"]);
+    }
+
+    #[test]
+    fn test_watch_events() {
+        let dir = setup_dir();
+        let model = FileSystemModel::new();
+        model.set_root_path(dir.path()).unwrap();
+        let root = ModelIndex::invalid();
+        fetch(&model, &root);
+        let root_path = model.root_path().unwrap();
+
+        let inserted = Arc::new(Mutex::new(Vec::new()));
+        let inserted_clone = inserted.clone();
+        model
+            .signals()
+            .rows_inserted
+            .connect(move |(_, first, _)| inserted_clone.lock().push(*first));
+
+        let path = root_path.join("c.txt");
+        std::fs::write(&path, "").unwrap();
+        model.apply_watch_event(&FileWatchEvent::new(path, WatchEventKind::Created));
+        assert_eq!(*inserted.lock(), vec![3]);
+        assert_eq!(
+            names(&model, &root),
+            vec!["assets", "A.png", "b.txt", "c.txt"]
+        );
+
+        let path = root_path.join("b.txt");
+        std::fs::write(&path, "hello world").unwrap();
+        model.apply_watch_event(&FileWatchEvent::new(path.clone(), WatchEventKind::Modified));
+        let size = model.index(2, FileSystemModel::SIZE_COLUMN, &root);
+        assert_eq!(model.display_text(&size).unwrap(), "11 B");
+
+        let renamed = root_path.join("assets").join("b.txt");
+        std::fs::rename(&path, &renamed).unwrap();
+        model.apply_watch_event(&FileWatchEvent::renamed(path, renamed));
+        assert_eq!(names(&model, &root), vec!["assets", "A.png", "c.txt"]);
+    }
+}
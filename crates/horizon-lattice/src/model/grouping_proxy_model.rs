@@ -0,0 +1,1113 @@
+//! Proxy model that groups the rows of a flat model into a tree.
+//!
+//! `GroupingProxyModel` turns the rows of a flat source model into a tree of
+//! group header rows, one level per grouping column, with the source rows as
+//! the leaves. Header rows show the group's value and per-group aggregates
+//! of other columns, and the proxy follows the source model's change signals
+//! incrementally.
+
+use parking_lot::{Mutex, RwLock};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::index::ModelIndex;
+use super::proxy_model::compare_item_data;
+use super::role::{ItemData, ItemRole};
+use super::traits::{ItemFlags, ItemModel, ModelSignals, Orientation};
+use super::tree_proxy_model::row_path;
+
+/// Disconnects one slot from the source model's signals.
+type Disconnector = Box<dyn Fn(&ModelSignals) + Send + Sync>;
+
+/// Internal ids of group header rows have the top bit set, so that they
+/// don't collide with the ids of leaf rows, which are their source rows.
+const GROUP_ID_BIT: u64 = 1 << 63;
+
+/// A summary of one column over the rows of a group.
+///
+/// Aggregates are computed from the `ItemRole::Display` data of the source
+/// rows and shown in the group's header row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aggregate {
+    /// The number of rows in the group.
+    Count,
+    /// The sum of the numeric values. Integer if all values are integers.
+    Sum,
+    /// The smallest value.
+    Min,
+    /// The largest value.
+    Max,
+    /// The mean of the numeric values.
+    Average,
+}
+
+impl Aggregate {
+    /// Computes the aggregate over `values`, skipping empty values.
+    ///
+    /// Returns `ItemData::None` if there are no values to aggregate.
+    fn compute(self, values: &[ItemData]) -> ItemData {
+        let present = values.iter().filter(|v| !v.is_none());
+        match self {
+            Aggregate::Count => ItemData::Int(values.len() as i64),
+            Aggregate::Min => present
+                .min_by(|a, b| compare_values(a, b))
+                .cloned()
+                .unwrap_or_default(),
+            Aggregate::Max => present
+                .max_by(|a, b| compare_values(a, b))
+                .cloned()
+                .unwrap_or_default(),
+            Aggregate::Sum | Aggregate::Average => {
+                let mut int_sum: i64 = 0;
+                let mut float_sum = 0.0;
+                let mut all_ints = true;
+                let mut count = 0;
+                for value in present {
+                    match value {
+                        ItemData::Int(n) => {
+                            int_sum = int_sum.wrapping_add(*n);
+                            float_sum += *n as f64;
+                        }
+                        ItemData::Float(n) => {
+                            all_ints = false;
+                            float_sum += n;
+                        }
+                        _ => continue,
+                    }
+                    count += 1;
+                }
+                match (self, count) {
+                    (_, 0) => ItemData::None,
+                    (Aggregate::Sum, _) if all_ints => ItemData::Int(int_sum),
+                    (Aggregate::Sum, _) => ItemData::Float(float_sum),
+                    _ => ItemData::Float(float_sum / count as f64),
+                }
+            }
+        }
+    }
+}
+
+/// Compares two values, comparing integers and floats numerically.
+fn compare_values(a: &ItemData, b: &ItemData) -> std::cmp::Ordering {
+    match (a, b) {
+        (ItemData::Int(n), ItemData::Float(f)) => (*n as f64).total_cmp(f),
+        (ItemData::Float(f), ItemData::Int(n)) => f.total_cmp(&(*n as f64)),
+        _ => compare_item_data(a, b),
+    }
+}
+
+/// Formats aggregate values and group keys for display.
+fn format_value(value: &ItemData) -> String {
+    match value {
+        ItemData::String(s) => s.clone(),
+        ItemData::Int(n) => n.to_string(),
+        ItemData::Float(n) => format!("{n:.2}"),
+        ItemData::Bool(b) => b.to_string(),
+        ItemData::Date(d) => d.to_string(),
+        _ => String::new(),
+    }
+}
+
+/// The children of a group: subgroups, or source rows on the last level.
+enum GroupChildren {
+    Groups(Vec<Group>),
+    /// Source rows, in ascending order.
+    Rows(Vec<usize>),
+}
+
+/// A group header row, or the root of the grouping tree.
+struct Group {
+    /// Internal id of the header row, stable across rebuilds.
+    id: u64,
+    /// Display text of the grouping column shared by the group's rows.
+    key: String,
+    children: GroupChildren,
+    /// Number of source rows in the group, including subgroups.
+    row_count: usize,
+    /// Aggregate values, keyed by column.
+    aggregates: HashMap<usize, ItemData>,
+}
+
+impl Group {
+    fn new(id: u64, key: String, leaf: bool) -> Self {
+        Self {
+            id,
+            key,
+            children: if leaf {
+                GroupChildren::Rows(Vec::new())
+            } else {
+                GroupChildren::Groups(Vec::new())
+            },
+            row_count: 0,
+            aggregates: HashMap::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match &self.children {
+            GroupChildren::Groups(groups) => groups.len(),
+            GroupChildren::Rows(rows) => rows.len(),
+        }
+    }
+
+    fn subgroups(&self) -> &[Group] {
+        match &self.children {
+            GroupChildren::Groups(groups) => groups,
+            GroupChildren::Rows(_) => &[],
+        }
+    }
+
+    /// Finds the group at the given path of group positions.
+    fn node(&self, path: &[usize]) -> Option<&Group> {
+        path.iter()
+            .try_fold(self, |group, &i| group.subgroups().get(i))
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut Group> {
+        path.iter()
+            .try_fold(self, |group, &i| match &mut group.children {
+                GroupChildren::Groups(groups) => groups.get_mut(i),
+                GroupChildren::Rows(_) => None,
+            })
+    }
+
+    /// Appends the source rows of the group to `out`.
+    fn collect_rows(&self, out: &mut Vec<usize>) {
+        match &self.children {
+            GroupChildren::Groups(groups) => groups.iter().for_each(|g| g.collect_rows(out)),
+            GroupChildren::Rows(rows) => out.extend_from_slice(rows),
+        }
+    }
+
+    /// Finds the leaf group path and position of a source row.
+    fn find_row(&self, row: usize) -> Option<(Vec<usize>, usize)> {
+        match &self.children {
+            GroupChildren::Rows(rows) => rows.binary_search(&row).ok().map(|pos| (Vec::new(), pos)),
+            GroupChildren::Groups(groups) => groups.iter().enumerate().find_map(|(i, group)| {
+                let (mut path, pos) = group.find_row(row)?;
+                path.insert(0, i);
+                Some((path, pos))
+            }),
+        }
+    }
+
+    /// Adds `delta` to every source row at or after `from`.
+    fn shift_rows(&mut self, from: usize, delta: isize) {
+        match &mut self.children {
+            GroupChildren::Groups(groups) => {
+                groups.iter_mut().for_each(|g| g.shift_rows(from, delta))
+            }
+            GroupChildren::Rows(rows) => {
+                for row in rows.iter_mut().filter(|row| **row >= from) {
+                    *row = row.wrapping_add_signed(delta);
+                }
+            }
+        }
+    }
+
+    /// Finds the id of the group with the given keys, for reuse after a rebuild.
+    fn id_for(&self, keys: &[String]) -> Option<u64> {
+        keys.iter()
+            .try_fold(self, |group, key| {
+                group.subgroups().iter().find(|g| &g.key == key)
+            })
+            .map(|group| group.id)
+    }
+}
+
+/// A proxy model that groups the rows of a flat source model into a tree.
+///
+/// Rows are grouped by the display text of the grouping columns: the first
+/// grouping column forms the top-level group header rows, the next one forms
+/// subgroups within each group, and so on. The source rows are the leaves of
+/// the deepest groups. Groups are listed in the order their first row appears
+/// in the source, and rows keep their source order within their group.
+/// Without grouping columns, the proxy shows the source rows unchanged.
+///
+/// Header rows show the group's value in column 0 and the
+/// [`Aggregate`]s set with [`set_aggregate`](Self::set_aggregate) in the
+/// aggregated columns. An aggregate of column 0 is appended to the group's
+/// value in parentheses, so `Aggregate::Count` on column 0 shows headers
+/// like "Alice (3)".
+///
+/// The proxy listens to the source model's signals: inserted and removed
+/// rows are added to or removed from their groups, creating and removing
+/// group header rows as needed, and a row whose grouping column changes moves
+/// to its new group. Aggregates are updated along the way. Header rows keep
+/// their internal ids across model resets, so a [`TreeView`] keeps groups
+/// expanded.
+///
+/// [`TreeView`]: crate::widget::widgets::TreeView
+///
+/// # Example
+///
+/// ```ignore
+/// use horizon_lattice::model::{Aggregate, GroupingProxyModel};
+///
+/// // Columns: customer, status, amount
+/// let proxy = GroupingProxyModel::new(orders.clone());
+/// proxy.set_group_columns(vec![0, 1]);
+/// proxy.set_aggregate(0, Aggregate::Count);
+/// proxy.set_aggregate(2, Aggregate::Sum);
+///
+/// let mut tree = TreeView::new();
+/// tree.set_model(Some(proxy));
+/// ```
+pub struct GroupingProxyModel<S: ItemModel> {
+    source: Arc<S>,
+    group_columns: RwLock<Vec<usize>>,
+    aggregates: RwLock<HashMap<usize, Aggregate>>,
+    root: RwLock<Group>,
+    next_group_id: AtomicU64,
+    signals: ModelSignals,
+    /// Slots connected to the source model, disconnected on drop.
+    disconnectors: Mutex<Vec<Disconnector>>,
+}
+
+/// What a proxy index refers to.
+enum Target {
+    /// A group header row, by path of group positions.
+    Group(Vec<usize>),
+    /// A leaf row, by source row.
+    Row(usize),
+}
+
+impl<S: ItemModel + 'static> GroupingProxyModel<S> {
+    /// Creates a new proxy model wrapping the given source.
+    ///
+    /// The proxy starts without grouping columns. It is returned in an `Arc`
+    /// because it connects to the source model's signals; the connections
+    /// hold only a weak reference.
+    pub fn new(source: Arc<S>) -> Arc<Self> {
+        let proxy = Arc::new(Self {
+            source,
+            group_columns: RwLock::new(Vec::new()),
+            aggregates: RwLock::new(HashMap::new()),
+            root: RwLock::new(Group::new(0, String::new(), true)),
+            next_group_id: AtomicU64::new(1),
+            signals: ModelSignals::new(),
+            disconnectors: Mutex::new(Vec::new()),
+        });
+        proxy.rebuild();
+        proxy.connect_source();
+        proxy
+    }
+
+    /// Returns a reference to the source model.
+    pub fn source(&self) -> &Arc<S> {
+        &self.source
+    }
+
+    /// Returns the grouping columns, outermost first.
+    pub fn group_columns(&self) -> Vec<usize> {
+        self.group_columns.read().clone()
+    }
+
+    /// Sets the grouping columns, outermost first, and resets the model.
+    pub fn set_group_columns(&self, columns: Vec<usize>) {
+        *self.group_columns.write() = columns;
+        self.signals.emit_reset(|| self.rebuild());
+    }
+
+    /// Returns the aggregate shown for `column` in group header rows.
+    pub fn aggregate(&self, column: usize) -> Option<Aggregate> {
+        self.aggregates.read().get(&column).copied()
+    }
+
+    /// Sets the aggregate shown for `column` in group header rows.
+    pub fn set_aggregate(&self, column: usize, aggregate: Aggregate) {
+        self.aggregates.write().insert(column, aggregate);
+        self.refresh_aggregates();
+    }
+
+    /// Stops showing an aggregate for `column`.
+    pub fn clear_aggregate(&self, column: usize) {
+        if self.aggregates.write().remove(&column).is_some() {
+            self.refresh_aggregates();
+        }
+    }
+
+    /// Returns `true` if the index refers to a group header row.
+    pub fn is_group(&self, index: &ModelIndex) -> bool {
+        index.is_valid() && matches!(self.resolve(index), Some(Target::Group(_)))
+    }
+
+    /// Returns the grouping column value of a group header row.
+    pub fn group_key(&self, index: &ModelIndex) -> Option<String> {
+        self.with_group(index, |group| group.key.clone())
+    }
+
+    /// Returns the number of source rows in a group, including subgroups.
+    pub fn group_row_count(&self, index: &ModelIndex) -> usize {
+        self.with_group(index, |group| group.row_count).unwrap_or(0)
+    }
+
+    /// Returns the unformatted aggregate value of `column` for a group.
+    ///
+    /// Returns `None` if the index isn't a group header row or the column
+    /// has no aggregate.
+    pub fn aggregate_value(&self, index: &ModelIndex, column: usize) -> Option<ItemData> {
+        self.with_group(index, |group| group.aggregates.get(&column).cloned())
+            .flatten()
+    }
+
+    /// Maps a proxy index to a source index.
+    ///
+    /// Returns an invalid index for group header rows.
+    pub fn map_to_source(&self, proxy_index: &ModelIndex) -> ModelIndex {
+        match self.resolve(proxy_index) {
+            Some(Target::Row(row)) => {
+                self.source
+                    .index(row, proxy_index.column(), &ModelIndex::invalid())
+            }
+            _ => ModelIndex::invalid(),
+        }
+    }
+
+    /// Maps a source index to the proxy index of its leaf row.
+    pub fn map_from_source(&self, source_index: &ModelIndex) -> ModelIndex {
+        if !source_index.is_valid() {
+            return ModelIndex::invalid();
+        }
+        let location = self.root.read().find_row(source_index.row());
+        match location {
+            Some((path, pos)) => self.leaf_index(&path, pos, source_index.column()),
+            None => ModelIndex::invalid(),
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Index resolution
+    // -------------------------------------------------------------------------
+
+    /// Resolves a proxy index to the group or source row it refers to.
+    fn resolve(&self, index: &ModelIndex) -> Option<Target> {
+        let rows = row_path(index);
+        let root = self.root.read();
+        let mut group = &*root;
+        let mut path = Vec::with_capacity(rows.len());
+        for (level, &row) in rows.iter().enumerate() {
+            match &group.children {
+                GroupChildren::Groups(groups) => {
+                    group = groups.get(row)?;
+                    path.push(row);
+                }
+                GroupChildren::Rows(source_rows) => {
+                    return (level + 1 == rows.len())
+                        .then(|| source_rows.get(row).copied())
+                        .flatten()
+                        .map(Target::Row);
+                }
+            }
+        }
+        Some(Target::Group(path))
+    }
+
+    fn with_group<T>(&self, index: &ModelIndex, f: impl FnOnce(&Group) -> T) -> Option<T> {
+        if !index.is_valid() {
+            return None;
+        }
+        let Some(Target::Group(path)) = self.resolve(index) else {
+            return None;
+        };
+        self.root.read().node(&path).map(f)
+    }
+
+    /// Builds the proxy index of the group header at `path`.
+    fn group_index(&self, path: &[usize], column: usize) -> ModelIndex {
+        let root = self.root.read();
+        let mut group = &*root;
+        let mut index = ModelIndex::invalid();
+        for (level, &i) in path.iter().enumerate() {
+            let Some(child) = group.subgroups().get(i) else {
+                return ModelIndex::invalid();
+            };
+            let column = if level + 1 == path.len() { column } else { 0 };
+            index = ModelIndex::with_internal_id(i, column, index, child.id);
+            group = child;
+        }
+        index
+    }
+
+    /// Builds the proxy index of the leaf row at `pos` in the group at `path`.
+    fn leaf_index(&self, path: &[usize], pos: usize, column: usize) -> ModelIndex {
+        let source_row = match &self.root.read().node(path).map(|g| &g.children) {
+            Some(GroupChildren::Rows(rows)) => rows.get(pos).copied(),
+            _ => None,
+        };
+        let Some(source_row) = source_row else {
+            return ModelIndex::invalid();
+        };
+        ModelIndex::with_internal_id(pos, column, self.group_index(path, 0), source_row as u64)
+    }
+
+    // -------------------------------------------------------------------------
+    // Grouping
+    // -------------------------------------------------------------------------
+
+    /// Returns the group keys of a source row, one per grouping column.
+    fn row_keys(&self, row: usize) -> Vec<String> {
+        let root = ModelIndex::invalid();
+        self.group_columns
+            .read()
+            .iter()
+            .map(|&column| {
+                let index = self.source.index(row, column, &root);
+                format_value(&self.source.data(&index, ItemRole::Display))
+            })
+            .collect()
+    }
+
+    /// Creates a group for `keys[level]`, reusing the id it had in `previous`.
+    fn new_group(&self, keys: &[String], level: usize, previous: Option<&Group>) -> Group {
+        let id = previous
+            .and_then(|previous| previous.id_for(&keys[..=level]))
+            .unwrap_or_else(|| GROUP_ID_BIT | self.next_group_id.fetch_add(1, Ordering::Relaxed));
+        Group::new(id, keys[level].clone(), level + 1 == keys.len())
+    }
+
+    /// Adds a source row to the grouping tree without emitting signals.
+    fn place(&self, root: &mut Group, keys: &[String], row: usize, previous: Option<&Group>) {
+        let mut group = root;
+        for level in 0..keys.len() {
+            let GroupChildren::Groups(groups) = &mut group.children else {
+                return;
+            };
+            let i = match groups.iter().position(|g| g.key == keys[level]) {
+                Some(i) => i,
+                None => {
+                    groups.push(self.new_group(keys, level, previous));
+                    groups.len() - 1
+                }
+            };
+            group = &mut groups[i];
+        }
+        if let GroupChildren::Rows(rows) = &mut group.children {
+            let pos = rows.partition_point(|&r| r < row);
+            rows.insert(pos, row);
+        }
+    }
+
+    fn rebuild(&self) {
+        let previous =
+            std::mem::replace(&mut *self.root.write(), Group::new(0, String::new(), true));
+        let mut root = Group::new(0, String::new(), self.group_columns.read().is_empty());
+        for row in 0..self.source.row_count(&ModelIndex::invalid()) {
+            let keys = self.row_keys(row);
+            self.place(&mut root, &keys, row, Some(&previous));
+        }
+        self.compute_all_aggregates(&mut root);
+        *self.root.write() = root;
+    }
+
+    // -------------------------------------------------------------------------
+    // Aggregates
+    // -------------------------------------------------------------------------
+
+    /// Recomputes the row count and aggregates of one group.
+    fn compute_aggregates(&self, group: &mut Group) {
+        let mut rows = Vec::new();
+        group.collect_rows(&mut rows);
+        group.row_count = rows.len();
+        let root = ModelIndex::invalid();
+        group.aggregates = self
+            .aggregates
+            .read()
+            .iter()
+            .map(|(&column, &aggregate)| {
+                let values: Vec<ItemData> = rows
+                    .iter()
+                    .map(|&row| {
+                        self.source
+                            .data(&self.source.index(row, column, &root), ItemRole::Display)
+                    })
+                    .collect();
+                (column, aggregate.compute(&values))
+            })
+            .collect();
+    }
+
+    fn compute_all_aggregates(&self, group: &mut Group) {
+        if let GroupChildren::Groups(groups) = &mut group.children {
+            for child in groups {
+                self.compute_all_aggregates(child);
+            }
+        }
+        self.compute_aggregates(group);
+    }
+
+    /// Recomputes every group's aggregates and notifies views of all headers.
+    fn refresh_aggregates(&self) {
+        self.compute_all_aggregates(&mut self.root.write());
+        self.emit_groups_changed(&ModelIndex::invalid());
+    }
+
+    fn emit_groups_changed(&self, parent: &ModelIndex) {
+        let last_column = self.column_count(parent).saturating_sub(1);
+        for row in 0..self.row_count(parent) {
+            let first = self.index(row, 0, parent);
+            if !self.is_group(&first) {
+                return;
+            }
+            let last = self.index(row, last_column, parent);
+            self.signals
+                .data_changed
+                .emit((first.clone(), last, vec![ItemRole::Display]));
+            self.emit_groups_changed(&first);
+        }
+    }
+
+    /// Recomputes the aggregates of the groups along `path` and notifies
+    /// views of their header rows.
+    fn update_groups(&self, path: &[usize]) {
+        let last_column = self
+            .source
+            .column_count(&ModelIndex::invalid())
+            .saturating_sub(1);
+        for depth in (1..=path.len()).rev() {
+            let mut root = self.root.write();
+            let Some(group) = root.node_mut(&path[..depth]) else {
+                continue;
+            };
+            self.compute_aggregates(group);
+            drop(root);
+            self.signals.data_changed.emit((
+                self.group_index(&path[..depth], 0),
+                self.group_index(&path[..depth], last_column),
+                vec![ItemRole::Display],
+            ));
+        }
+        self.compute_aggregates(&mut self.root.write());
+    }
+
+    // -------------------------------------------------------------------------
+    // Incremental updates
+    // -------------------------------------------------------------------------
+
+    /// Adds a source row to its group, creating groups as needed.
+    fn insert_row(&self, row: usize) {
+        let keys = self.row_keys(row);
+        let mut path = Vec::new();
+        {
+            let root = self.root.read();
+            let mut group = &*root;
+            for key in &keys {
+                match group.subgroups().iter().position(|g| &g.key == key) {
+                    Some(i) => {
+                        path.push(i);
+                        group = &group.subgroups()[i];
+                    }
+                    None => break,
+                }
+            }
+        }
+        let parent = self.group_index(&path, 0);
+
+        if path.len() < keys.len() {
+            // Create the missing groups with the row in a single insertion.
+            let level = path.len();
+            let mut group = self.new_group(&keys, level, None);
+            self.place(&mut group, &keys[level + 1..], row, None);
+            self.compute_all_aggregates(&mut group);
+            let position = self.root.read().node(&path).map_or(0, Group::len);
+            self.signals
+                .emit_rows_inserted(parent, position, position, || {
+                    if let Some(GroupChildren::Groups(groups)) =
+                        self.root.write().node_mut(&path).map(|g| &mut g.children)
+                    {
+                        groups.push(group);
+                    }
+                });
+        } else {
+            let position = match self.root.read().node(&path).map(|g| &g.children) {
+                Some(GroupChildren::Rows(rows)) => rows.partition_point(|&r| r < row),
+                _ => return,
+            };
+            self.signals
+                .emit_rows_inserted(parent, position, position, || {
+                    if let Some(GroupChildren::Rows(rows)) =
+                        self.root.write().node_mut(&path).map(|g| &mut g.children)
+                    {
+                        rows.insert(position, row);
+                    }
+                });
+        }
+        self.update_groups(&path);
+    }
+
+    /// Removes a source row from its group, removing groups that become empty.
+    fn remove_row(&self, row: usize) {
+        let Some((path, pos)) = self.root.read().find_row(row) else {
+            return;
+        };
+
+        // Find the outermost group that only contains this row.
+        let mut depth = path.len();
+        while depth > 0
+            && self
+                .root
+                .read()
+                .node(&path[..depth])
+                .is_some_and(|g| g.len() == 1)
+        {
+            depth -= 1;
+        }
+
+        let parent = &path[..depth];
+        let position = if depth == path.len() {
+            pos
+        } else {
+            path[depth]
+        };
+        self.signals
+            .emit_rows_removed(self.group_index(parent, 0), position, position, || {
+                if let Some(group) = self.root.write().node_mut(parent) {
+                    match &mut group.children {
+                        GroupChildren::Groups(groups) => {
+                            groups.remove(position);
+                        }
+                        GroupChildren::Rows(rows) => {
+                            rows.remove(position);
+                        }
+                    }
+                }
+            });
+        self.update_groups(parent);
+    }
+
+    // -------------------------------------------------------------------------
+    // Source model signals
+    // -------------------------------------------------------------------------
+
+    fn connect_source(self: &Arc<Self>) {
+        let signals = self.source.signals();
+        let mut disconnectors = self.disconnectors.lock();
+
+        macro_rules! forward {
+            ($signal:ident => $handler:ident) => {{
+                let proxy = Arc::downgrade(self);
+                let id = signals.$signal.connect(move |args| {
+                    if let Some(proxy) = proxy.upgrade() {
+                        proxy.$handler(args);
+                    }
+                });
+                disconnectors.push(Box::new(move |signals: &ModelSignals| {
+                    signals.$signal.disconnect(id);
+                }));
+            }};
+        }
+
+        forward!(rows_inserted => source_rows_inserted);
+        forward!(rows_about_to_be_removed => source_rows_about_to_be_removed);
+        forward!(rows_removed => source_rows_removed);
+        forward!(rows_moved => source_structure_changed);
+        forward!(columns_inserted => source_columns_changed);
+        forward!(columns_removed => source_columns_changed);
+        forward!(data_changed => source_data_changed);
+        forward!(header_data_changed => source_header_data_changed);
+        forward!(layout_about_to_change => source_layout_about_to_change);
+        forward!(layout_changed => source_layout_changed);
+        forward!(model_about_to_reset => source_model_about_to_reset);
+        forward!(model_reset => source_model_reset);
+    }
+
+    fn source_rows_inserted(&self, (parent, first, last): &(ModelIndex, usize, usize)) {
+        if parent.is_valid() {
+            return;
+        }
+        let count = last - first + 1;
+        self.root.write().shift_rows(*first, count as isize);
+        for row in *first..=*last {
+            self.insert_row(row);
+        }
+    }
+
+    fn source_rows_about_to_be_removed(&self, (parent, first, last): &(ModelIndex, usize, usize)) {
+        if parent.is_valid() {
+            return;
+        }
+        for row in (*first..=*last).rev() {
+            self.remove_row(row);
+        }
+    }
+
+    fn source_rows_removed(&self, (parent, first, last): &(ModelIndex, usize, usize)) {
+        if parent.is_valid() {
+            return;
+        }
+        let count = last - first + 1;
+        self.root.write().shift_rows(last + 1, -(count as isize));
+    }
+
+    fn source_structure_changed(&self, _: &(ModelIndex, usize, usize, ModelIndex, usize)) {
+        self.signals.emit_reset(|| self.rebuild());
+    }
+
+    fn source_columns_changed(&self, _: &(ModelIndex, usize, usize)) {
+        self.signals.emit_reset(|| self.rebuild());
+    }
+
+    fn source_data_changed(
+        &self,
+        (top_left, bottom_right, roles): &(ModelIndex, ModelIndex, Vec<ItemRole>),
+    ) {
+        if !top_left.is_valid() || top_left.parent().is_valid() {
+            return;
+        }
+        let columns = top_left.column()..=bottom_right.column();
+        let regroup = self
+            .group_columns
+            .read()
+            .iter()
+            .any(|column| columns.contains(column));
+        let reaggregate = self
+            .aggregates
+            .read()
+            .keys()
+            .any(|column| columns.contains(column));
+
+        for row in top_left.row()..=bottom_right.row() {
+            let Some((path, pos)) = self.root.read().find_row(row) else {
+                continue;
+            };
+
+            if regroup {
+                let keys = self.row_keys(row);
+                let root = self.root.read();
+                let moved = (1..=path.len()).any(|depth| {
+                    root.node(&path[..depth])
+                        .is_none_or(|g| g.key != keys[depth - 1])
+                });
+                drop(root);
+                if moved {
+                    self.remove_row(row);
+                    self.insert_row(row);
+                    continue;
+                }
+            }
+
+            self.signals.data_changed.emit((
+                self.leaf_index(&path, pos, top_left.column()),
+                self.leaf_index(&path, pos, bottom_right.column()),
+                roles.clone(),
+            ));
+            if reaggregate {
+                self.update_groups(&path);
+            }
+        }
+    }
+
+    fn source_header_data_changed(&self, args: &(Orientation, usize, usize)) {
+        self.signals.header_data_changed.emit(*args);
+    }
+
+    fn source_layout_about_to_change(&self, _: &()) {
+        self.signals.layout_about_to_change.emit(());
+    }
+
+    fn source_layout_changed(&self, _: &()) {
+        self.rebuild();
+        self.signals.layout_changed.emit(());
+    }
+
+    fn source_model_about_to_reset(&self, _: &()) {
+        self.signals.model_about_to_reset.emit(());
+    }
+
+    fn source_model_reset(&self, _: &()) {
+        self.rebuild();
+        self.signals.model_reset.emit(());
+    }
+}
+
+impl<S: ItemModel> Drop for GroupingProxyModel<S> {
+    fn drop(&mut self) {
+        let signals = self.source.signals();
+        for disconnect in self.disconnectors.get_mut().drain(..) {
+            disconnect(signals);
+        }
+    }
+}
+
+impl<S: ItemModel + 'static> ItemModel for GroupingProxyModel<S> {
+    fn row_count(&self, parent: &ModelIndex) -> usize {
+        if parent.is_valid() && parent.column() > 0 {
+            return 0;
+        }
+        match self.resolve(parent) {
+            Some(Target::Group(path)) => self.root.read().node(&path).map_or(0, Group::len),
+            _ => 0,
+        }
+    }
+
+    fn column_count(&self, _parent: &ModelIndex) -> usize {
+        self.source.column_count(&ModelIndex::invalid())
+    }
+
+    fn data(&self, index: &ModelIndex, role: ItemRole) -> ItemData {
+        match self.resolve(index) {
+            Some(Target::Row(row)) => {
+                let source_index = self
+                    .source
+                    .index(row, index.column(), &ModelIndex::invalid());
+                self.source.data(&source_index, role)
+            }
+            Some(Target::Group(path)) if index.is_valid() && role == ItemRole::Display => {
+                let root = self.root.read();
+                let Some(group) = root.node(&path) else {
+                    return ItemData::None;
+                };
+                let aggregate = group.aggregates.get(&index.column()).map(format_value);
+                match (index.column(), aggregate) {
+                    (0, Some(aggregate)) => format!("{} ({aggregate})", group.key).into(),
+                    (0, None) => group.key.clone().into(),
+                    (_, Some(aggregate)) => aggregate.into(),
+                    (_, None) => ItemData::None,
+                }
+            }
+            _ => ItemData::None,
+        }
+    }
+
+    fn index(&self, row: usize, column: usize, parent: &ModelIndex) -> ModelIndex {
+        if (parent.is_valid() && parent.column() > 0) || column >= self.column_count(parent) {
+            return ModelIndex::invalid();
+        }
+        let Some(Target::Group(path)) = self.resolve(parent) else {
+            return ModelIndex::invalid();
+        };
+        let root = self.root.read();
+        let internal_id = match root.node(&path).map(|g| &g.children) {
+            Some(GroupChildren::Groups(groups)) => groups.get(row).map(|g| g.id),
+            Some(GroupChildren::Rows(rows)) => rows.get(row).map(|&source_row| source_row as u64),
+            None => None,
+        };
+        match internal_id {
+            Some(id) => ModelIndex::with_internal_id(row, column, parent.clone(), id),
+            None => ModelIndex::invalid(),
+        }
+    }
+
+    fn parent(&self, index: &ModelIndex) -> ModelIndex {
+        index.parent()
+    }
+
+    fn signals(&self) -> &ModelSignals {
+        &self.signals
+    }
+
+    fn set_data(&self, index: &ModelIndex, value: ItemData, role: ItemRole) -> bool {
+        let source_index = self.map_to_source(index);
+        source_index.is_valid() && self.source.set_data(&source_index, value, role)
+    }
+
+    fn flags(&self, index: &ModelIndex) -> ItemFlags {
+        match self.resolve(index) {
+            Some(Target::Row(_)) => self.source.flags(&self.map_to_source(index)),
+            _ => ItemFlags::new(),
+        }
+    }
+
+    fn has_children(&self, parent: &ModelIndex) -> bool {
+        self.row_count(parent) > 0
+    }
+
+    fn header_data(&self, section: usize, orientation: Orientation, role: ItemRole) -> ItemData {
+        self.source.header_data(section, orientation, role)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::table_model::SimpleTableModel;
+
+    /// Orders with columns customer, status, amount.
+    fn create_orders() -> Arc<SimpleTableModel> {
+        let order = |customer: &str, status: &str, amount: i64| {
+            vec![
+                ItemData::from(customer),
+                ItemData::from(status),
+                ItemData::Int(amount),
+            ]
+        };
+        Arc::new(SimpleTableModel::from_data(vec![
+            order("Alice", "open", 10),
+            order("Bob", "paid", 20),
+            order("Alice", "paid", 30),
+            order("Alice", "open", 40),
+        ]))
+    }
+
+    /// Lists the proxy's rows depth-first, with columns 0 and 2 joined by `|`.
+    fn rows<M: ItemModel>(model: &M) -> Vec<String> {
+        fn walk<M: ItemModel>(model: &M, parent: &ModelIndex, depth: usize, out: &mut Vec<String>) {
+            for row in 0..model.row_count(parent) {
+                let index = model.index(row, 0, parent);
+                let text = |column| {
+                    format_value(&model.data(&model.index(row, column, parent), ItemRole::Display))
+                };
+                out.push(format!("{}{}|{}", "  ".repeat(depth), text(0), text(2)));
+                walk(model, &index, depth + 1, out);
+            }
+        }
+        let mut out = Vec::new();
+        walk(model, &ModelIndex::invalid(), 0, &mut out);
+        out
+    }
+
+    #[test]
+    fn test_multi_level_grouping_with_aggregates() {
+        let orders = create_orders();
+        let proxy = GroupingProxyModel::new(orders.clone());
+        assert_eq!(proxy.row_count(&ModelIndex::invalid()), 4);
+
+        proxy.set_group_columns(vec![0, 1]);
+        proxy.set_aggregate(0, Aggregate::Count);
+        proxy.set_aggregate(2, Aggregate::Sum);
+        assert_eq!(
+            rows(&*proxy),
+            [
+                "Alice (3)|80",
+                "  open (2)|50",
+                "    Alice|10",
+                "    Alice|40",
+                "  paid (1)|30",
+                "    Alice|30",
+                "Bob (1)|20",
+                "  paid (1)|20",
+                "    Bob|20",
+            ]
+        );
+
+        let alice = proxy.index(0, 0, &ModelIndex::invalid());
+        assert!(proxy.is_group(&alice));
+        assert_eq!(proxy.group_key(&alice).as_deref(), Some("Alice"));
+        assert_eq!(proxy.group_row_count(&alice), 3);
+        assert!(!proxy.map_to_source(&alice).is_valid());
+
+        proxy.set_aggregate(2, Aggregate::Average);
+        assert_eq!(
+            proxy.aggregate_value(&alice, 2).and_then(|v| v.as_float()),
+            Some(80.0 / 3.0)
+        );
+
+        let open = proxy.index(0, 0, &alice);
+        let last_open = proxy.index(1, 2, &open);
+        assert!(!proxy.is_group(&last_open));
+        let source = proxy.map_to_source(&last_open);
+        assert_eq!((source.row(), source.column()), (3, 2));
+        assert_eq!(proxy.map_from_source(&source), last_open);
+    }
+
+    #[test]
+    fn test_aggregates() {
+        let values = [ItemData::Int(3), ItemData::None, ItemData::Float(1.5)];
+        assert_eq!(format_value(&Aggregate::Count.compute(&values)), "3");
+        assert_eq!(format_value(&Aggregate::Sum.compute(&values)), "4.50");
+        assert_eq!(format_value(&Aggregate::Min.compute(&values[..2])), "3");
+        assert_eq!(format_value(&Aggregate::Max.compute(&values)), "3");
+        assert_eq!(format_value(&Aggregate::Average.compute(&values)), "2.25");
+        assert!(Aggregate::Sum.compute(&[ItemData::None]).is_none());
+    }
+
+    #[test]
+    fn test_source_changes_are_applied_incrementally() {
+        let orders = create_orders();
+        let proxy = GroupingProxyModel::new(orders.clone());
+        proxy.set_group_columns(vec![0]);
+        proxy.set_aggregate(2, Aggregate::Sum);
+        let alice_id = proxy.index(0, 0, &ModelIndex::invalid()).internal_id();
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let log = events.clone();
+        proxy
+            .signals()
+            .rows_inserted
+            .connect(move |(parent, first, last)| {
+                log.lock()
+                    .push(("inserted", parent.row(), parent.is_valid(), *first, *last));
+            });
+        let log = events.clone();
+        proxy
+            .signals()
+            .rows_removed
+            .connect(move |(parent, first, last)| {
+                log.lock()
+                    .push(("removed", parent.row(), parent.is_valid(), *first, *last));
+            });
+        let log = events.clone();
+        proxy
+            .signals()
+            .model_reset
+            .connect(move |_| log.lock().push(("reset", 0, false, 0, 0)));
+
+        // A row for a new customer creates its group; a row for an existing
+        // customer joins its group.
+        orders.append_row(vec![
+            ItemData::from("Carol"),
+            ItemData::from("open"),
+            ItemData::Int(5),
+        ]);
+        orders.append_row(vec![
+            ItemData::from("Bob"),
+            ItemData::from("open"),
+            ItemData::Int(1),
+        ]);
+        assert_eq!(
+            rows(&*proxy),
+            [
+                "Alice|80",
+                "  Alice|10",
+                "  Alice|30",
+                "  Alice|40",
+                "Bob|21",
+                "  Bob|20",
+                "  Bob|1",
+                "Carol|5",
+                "  Carol|5",
+            ]
+        );
+
+        // Changing an amount updates the sum; changing the customer moves the row.
+        orders.set_cell(3, 2, ItemData::Int(100));
+        orders.set_cell(5, 0, ItemData::from("Carol"));
+        assert_eq!(
+            rows(&*proxy),
+            [
+                "Alice|140",
+                "  Alice|10",
+                "  Alice|30",
+                "  Alice|100",
+                "Bob|20",
+                "  Bob|20",
+                "Carol|6",
+                "  Carol|5",
+                "  Carol|1",
+            ]
+        );
+
+        // Removing the last row of a group removes the group, and later rows
+        // map to their shifted source rows.
+        orders.remove_row(1);
+        assert_eq!(rows(&*proxy)[4..], ["Carol|6", "  Carol|5", "  Carol|1"]);
+        let carol = proxy.index(1, 0, &ModelIndex::invalid());
+        assert_eq!(proxy.map_to_source(&proxy.index(0, 0, &carol)).row(), 3);
+        assert_eq!(
+            proxy.index(0, 0, &ModelIndex::invalid()).internal_id(),
+            alice_id
+        );
+
+        assert_eq!(
+            *events.lock(),
+            [
+                ("inserted", 0, false, 2, 2),
+                ("inserted", 1, true, 1, 1),
+                ("removed", 1, true, 1, 1),
+                ("inserted", 2, true, 1, 1),
+                ("removed", 0, false, 1, 1),
+            ]
+        );
+    }
+}
@@ -0,0 +1,252 @@
+//! Editor widgets for in-place item editing.
+//!
+//! Views open an [`ItemEditor`] over an item when editing starts. The
+//! [`ItemDelegate`](super::ItemDelegate) creates the editor, loads the item's
+//! value into it and writes the edited value back to the model.
+//!
+//! [`create_default_editor`] picks an editor from the [`ItemData`] variant:
+//!
+//! | Data                    | Editor          |
+//! |-------------------------|-----------------|
+//! | `String` / `None`       | `LineEdit`      |
+//! | `Int`                   | `SpinBox`       |
+//! | `Int` beyond `i32`      | `LineEdit`      |
+//! | `Float`                 | `DoubleSpinBox` |
+//! | `Bool` / `CheckState`   | `CheckBox`      |
+//! | `Date`                  | `DateEdit`      |
+//! | [`EditChoices`]         | `ComboBox`      |
+
+use chrono::Local;
+
+use super::role::{CheckState, ItemData};
+use crate::widget::Widget;
+use crate::widget::widgets::{
+    CheckBox, CheckState as CheckBoxState, ComboBox, DateEdit, DoubleSpinBox, LineEdit, SpinBox,
+};
+
+/// A widget that can edit an item's value in place.
+pub trait ItemEditor: Widget {
+    /// Loads `value` into the editor.
+    fn set_editor_value(&mut self, value: &ItemData);
+
+    /// Returns the value currently entered in the editor.
+    fn editor_value(&self) -> ItemData;
+
+    /// Applies text that was typed but not yet accepted by the editor.
+    ///
+    /// Called before [`editor_value`](Self::editor_value) when an edit is
+    /// committed.
+    fn finish_input(&mut self) {}
+
+    /// Returns `true` while the editor uses Enter, Escape and Tab itself,
+    /// for example while a popup list is open.
+    fn captures_navigation_keys(&self) -> bool {
+        false
+    }
+}
+
+/// A fixed set of options for an item, edited with a combo box.
+///
+/// Return it as [`ItemData::new`] from a model's `ItemRole::Edit` data. The
+/// editor commits the chosen option's text as `ItemData::String`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EditChoices {
+    /// The options to choose from.
+    pub options: Vec<String>,
+    /// Index of the currently chosen option.
+    pub current: Option<usize>,
+}
+
+impl EditChoices {
+    /// Creates a set of options with the option at `current` chosen.
+    pub fn new(
+        options: impl IntoIterator<Item = impl Into<String>>,
+        current: Option<usize>,
+    ) -> Self {
+        Self {
+            options: options.into_iter().map(Into::into).collect(),
+            current,
+        }
+    }
+}
+
+/// Creates the standard editor for `data`.
+///
+/// Returns `None` for data that has no standard editor, such as colors or
+/// icons.
+pub fn create_default_editor(data: &ItemData) -> Option<Box<dyn ItemEditor>> {
+    let mut editor: Box<dyn ItemEditor> = match data {
+        ItemData::None | ItemData::String(_) => Box::new(LineEdit::new()),
+        // Too wide for a spin box; edit the number as text instead.
+        ItemData::Int(n) if i32::try_from(*n).is_err() => Box::new(LineEdit::new()),
+        ItemData::Int(_) => {
+            let mut spin_box = SpinBox::new();
+            spin_box.set_range(i32::MIN, i32::MAX);
+            Box::new(spin_box)
+        }
+        ItemData::Float(_) => {
+            let mut spin_box = DoubleSpinBox::new();
+            spin_box.set_range(f64::MIN, f64::MAX);
+            Box::new(spin_box)
+        }
+        ItemData::Bool(_) => Box::new(CheckBox::new("")),
+        ItemData::CheckState(_) => Box::new(CheckBox::new("").with_tri_state(true)),
+        ItemData::Date(_) => Box::new(DateEdit::new()),
+        ItemData::Custom(_) if data.downcast::<EditChoices>().is_some() => {
+            Box::new(ComboBox::new())
+        }
+        _ => return None,
+    };
+    editor.set_editor_value(data);
+    Some(editor)
+}
+
+impl ItemEditor for LineEdit {
+    fn set_editor_value(&mut self, value: &ItemData) {
+        match value {
+            ItemData::Int(n) => self.set_text(n.to_string()),
+            _ => self.set_text(value.as_string().unwrap_or_default()),
+        }
+        // Typing replaces the old value.
+        self.select_all();
+    }
+
+    fn editor_value(&self) -> ItemData {
+        ItemData::String(self.text().to_string())
+    }
+}
+
+impl ItemEditor for SpinBox {
+    fn set_editor_value(&mut self, value: &ItemData) {
+        let value = value.as_int().unwrap_or(0);
+        self.set_value(value.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+    }
+
+    fn editor_value(&self) -> ItemData {
+        ItemData::Int(self.value() as i64)
+    }
+
+    fn finish_input(&mut self) {
+        self.finish_editing();
+    }
+}
+
+impl ItemEditor for DoubleSpinBox {
+    fn set_editor_value(&mut self, value: &ItemData) {
+        self.set_value(value.as_float().unwrap_or(0.0));
+    }
+
+    fn editor_value(&self) -> ItemData {
+        ItemData::Float(self.value())
+    }
+
+    fn finish_input(&mut self) {
+        self.finish_editing();
+    }
+}
+
+impl ItemEditor for CheckBox {
+    fn set_editor_value(&mut self, value: &ItemData) {
+        let state = match value {
+            ItemData::Bool(true) | ItemData::CheckState(CheckState::Checked) => {
+                CheckBoxState::Checked
+            }
+            ItemData::CheckState(CheckState::PartiallyChecked) => CheckBoxState::PartiallyChecked,
+            _ => CheckBoxState::Unchecked,
+        };
+        self.set_check_state(state);
+    }
+
+    fn editor_value(&self) -> ItemData {
+        if self.is_tri_state() {
+            ItemData::CheckState(match self.check_state() {
+                CheckBoxState::Unchecked => CheckState::Unchecked,
+                CheckBoxState::PartiallyChecked => CheckState::PartiallyChecked,
+                CheckBoxState::Checked => CheckState::Checked,
+            })
+        } else {
+            ItemData::Bool(self.is_checked())
+        }
+    }
+}
+
+impl ItemEditor for DateEdit {
+    fn set_editor_value(&mut self, value: &ItemData) {
+        self.set_date(value.as_date().unwrap_or_else(|| Local::now().date_naive()));
+    }
+
+    fn editor_value(&self) -> ItemData {
+        ItemData::Date(self.date())
+    }
+
+    fn captures_navigation_keys(&self) -> bool {
+        self.is_popup_visible()
+    }
+}
+
+impl ItemEditor for ComboBox {
+    fn set_editor_value(&mut self, value: &ItemData) {
+        if let Some(choices) = value.downcast::<EditChoices>() {
+            self.clear();
+            self.add_items(choices.options.iter().cloned());
+            self.set_current_index(choices.current.map_or(-1, |i| i as i32));
+        } else if let Some(text) = value.as_string()
+            && let Some(index) = self.find_text(text)
+        {
+            self.set_current_index(index as i32);
+        }
+    }
+
+    fn editor_value(&self) -> ItemData {
+        ItemData::String(self.current_text())
+    }
+
+    fn captures_navigation_keys(&self) -> bool {
+        self.is_popup_visible()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use horizon_lattice_core::init_global_registry;
+
+    #[test]
+    fn test_default_editor_round_trips_value() {
+        init_global_registry();
+
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        for data in [
+            ItemData::from("text"),
+            ItemData::Int(-42),
+            ItemData::Float(2.5),
+            ItemData::Bool(true),
+            ItemData::CheckState(CheckState::PartiallyChecked),
+            ItemData::Date(date),
+        ] {
+            let editor = create_default_editor(&data).unwrap();
+            assert_eq!(format!("{:?}", editor.editor_value()), format!("{data:?}"));
+        }
+
+        // Values a spin box can't hold survive a round trip unchanged.
+        let wide = i64::from(i32::MAX) + 1;
+        let editor = create_default_editor(&ItemData::Int(wide)).unwrap();
+        assert_eq!(editor.editor_value().as_string(), Some("2147483648"));
+        let editor = create_default_editor(&ItemData::Int(i64::MIN)).unwrap();
+        assert_eq!(
+            editor.editor_value().as_string(),
+            Some(i64::MIN.to_string().as_str())
+        );
+        for value in [1e12, -1e300, f64::MAX] {
+            let editor = create_default_editor(&ItemData::Float(value)).unwrap();
+            assert_eq!(editor.editor_value().as_float(), Some(value));
+        }
+
+        let choices = ItemData::new(EditChoices::new(["Low", "High"], Some(1)));
+        let editor = create_default_editor(&choices).unwrap();
+        assert_eq!(editor.editor_value().as_string(), Some("High"));
+
+        assert!(create_default_editor(&ItemData::Size(1.0, 1.0)).is_none());
+    }
+}
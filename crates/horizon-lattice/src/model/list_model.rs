@@ -6,7 +6,10 @@
 use parking_lot::RwLock;
 use std::sync::Arc;
 
+use crate::widget::drag_drop::{DragData, DropAction};
+
 use super::index::ModelIndex;
+use super::mime::DraggedItems;
 use super::role::{ItemData, ItemRole};
 use super::traits::{ItemFlags, ItemModel, ModelSignals};
 
@@ -59,6 +62,17 @@ pub trait ListItem: Send + Sync {
     fn flags(&self) -> ItemFlags {
         ItemFlags::new()
     }
+
+    /// Creates an item from one line of dropped text.
+    ///
+    /// `ListModel` uses this to accept items copied from other views. The
+    /// default returns `None`, so only moves within the same model are accepted.
+    fn from_text(_text: &str) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 /// Implement ListItem for String for convenience.
@@ -66,6 +80,10 @@ impl ListItem for String {
     fn display(&self) -> ItemData {
         ItemData::from(self.as_str())
     }
+
+    fn from_text(text: &str) -> Option<Self> {
+        Some(text.to_string())
+    }
 }
 
 /// Type alias for a data extractor function.
@@ -208,6 +226,25 @@ impl<T: Send + Sync + 'static> ListModel<T> {
         removed.unwrap()
     }
 
+    /// Moves the items at `rows` so they are consecutive, starting at `destination`.
+    ///
+    /// `destination` is a position in the list before the move, as for a
+    /// drop between two items. Rows past the end of the list are ignored.
+    pub fn move_rows(&self, rows: &[usize], destination: usize) {
+        let len = self.len();
+        let mut rows: Vec<usize> = rows.iter().copied().filter(|&row| row < len).collect();
+        rows.sort_unstable();
+        rows.dedup();
+
+        let mut moved: Vec<T> = rows.iter().rev().map(|&row| self.remove(row)).collect();
+        moved.reverse();
+        let destination =
+            destination.min(len) - rows.iter().filter(|&&row| row < destination).count();
+        for (offset, item) in moved.into_iter().enumerate() {
+            self.insert(destination + offset, item);
+        }
+    }
+
     /// Removes all items from the model.
     pub fn clear(&self) {
         self.signals.emit_reset(|| {
@@ -413,6 +450,56 @@ impl<T: ListItem + 'static> ItemModel for ListModel<T> {
 
         items[index.row()].flags()
     }
+
+    fn supported_drop_actions(&self) -> DropAction {
+        DropAction::COPY | DropAction::MOVE
+    }
+
+    fn can_drop_mime_data(
+        &self,
+        data: &DragData,
+        action: DropAction,
+        _row: usize,
+        _column: usize,
+        parent: &ModelIndex,
+    ) -> bool {
+        if parent.is_valid() {
+            return false;
+        }
+        if action == DropAction::MOVE && DraggedItems::from_model(data, self).is_some() {
+            return true;
+        }
+        data.text().is_some_and(|text| {
+            !text.is_empty() && text.lines().all(|line| T::from_text(line).is_some())
+        })
+    }
+
+    fn drop_mime_data(
+        &self,
+        data: &DragData,
+        action: DropAction,
+        row: usize,
+        column: usize,
+        parent: &ModelIndex,
+    ) -> bool {
+        if !self.can_drop_mime_data(data, action, row, column, parent) {
+            return false;
+        }
+        let row = row.min(self.len());
+
+        if action == DropAction::MOVE
+            && let Some(items) = DraggedItems::from_model(data, self)
+        {
+            self.move_rows(&items.rows(), row);
+            return true;
+        }
+
+        let text = data.text().unwrap_or_default();
+        for (offset, item) in text.lines().filter_map(T::from_text).enumerate() {
+            self.insert(row + offset, item);
+        }
+        true
+    }
 }
 
 /// A list model that uses closures for data extraction.
@@ -702,4 +789,71 @@ mod tests {
         let display = model.data(&index, ItemRole::Display);
         assert_eq!(display.as_string(), Some("Modified"));
     }
+
+    #[test]
+    fn test_drop_mime_data() {
+        let strings = |model: &ListModel<String>| model.items().clone();
+        let model = ListModel::new(vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+        let root = ModelIndex::invalid();
+
+        // Moving rows within the model reorders them.
+        let data = model.mime_data(&[model.index(0, 0, &root), model.index(2, 0, &root)]);
+        assert!(model.drop_mime_data(&data, DropAction::MOVE, 2, 0, &root));
+        assert_eq!(strings(&model), ["b", "a", "c"]);
+
+        // Other models copy the dragged text.
+        let other = ListModel::new(vec!["x".to_string()]);
+        let data = model.mime_data(&[model.index(1, 0, &root)]);
+        assert!(!other.can_drop_mime_data(
+            &data,
+            DropAction::COPY,
+            0,
+            0,
+            &model.index(0, 0, &root)
+        ));
+        assert!(other.drop_mime_data(&data, DropAction::MOVE, 0, 0, &root));
+        assert_eq!(strings(&other), ["a", "x"]);
+        assert_eq!(strings(&model), ["b", "a", "c"]);
+
+        // Items that can't be created from text only accept moves.
+        let items = ListModel::new(vec![TestItem {
+            name: "t".into(),
+            value: 0,
+        }]);
+        assert!(!items.can_drop_mime_data(&data, DropAction::COPY, 0, 0, &root));
+    }
+
+    #[test]
+    fn test_model_tester() {
+        let model = Arc::new(ListModel::new(vec![
+            TestItem {
+                name: "a".into(),
+                value: 1,
+            },
+            TestItem {
+                name: "b".into(),
+                value: 2,
+            },
+        ]));
+        let tester = crate::model::ModelTester::new(model.clone());
+
+        model.push(TestItem {
+            name: "c".into(),
+            value: 3,
+        });
+        model.insert(
+            0,
+            TestItem {
+                name: "d".into(),
+                value: 4,
+            },
+        );
+        model.remove(1);
+        model.move_rows(&[0, 2], 3);
+        model.modify(0, |item| item.value = 5);
+        model.swap(0, 1);
+        model.sort_by(|a, b| a.name.cmp(&b.name));
+        model.clear();
+        tester.check();
+    }
 }
@@ -0,0 +1,152 @@
+//! Drag and drop data transfer for item models.
+//!
+//! Models serialize dragged items with [`ItemModel::mime_data`] and accept
+//! drops with [`ItemModel::drop_mime_data`]. The default `mime_data` stores
+//! the items' display text as `text/plain` and attaches a [`DraggedItems`]
+//! record, which lets a model recognize drags of its own items and move them
+//! instead of copying.
+
+use crate::widget::drag_drop::{DragData, mime};
+
+use super::index::ModelIndex;
+use super::traits::ItemModel;
+
+/// The items of a drag started from an item model.
+///
+/// [`ItemModel::mime_data`] attaches this to the [`DragData`] as user data.
+/// It identifies the source model, so a drop target can tell a drag of its
+/// own items (which it may move) from a drag out of another model (which it
+/// may only copy from the serialized formats).
+#[derive(Debug, Clone)]
+pub struct DraggedItems {
+    /// Address of the source model, used only for identity comparisons.
+    model: usize,
+    indexes: Vec<ModelIndex>,
+}
+
+impl DraggedItems {
+    /// Creates a record of `indexes` dragged out of `model`.
+    pub fn new<M: ItemModel + ?Sized>(model: &M, indexes: Vec<ModelIndex>) -> Self {
+        Self {
+            model: model_address(model),
+            indexes,
+        }
+    }
+
+    /// Returns the dragged items of `data` if they were dragged out of `model`.
+    pub fn from_model<'a, M: ItemModel + ?Sized>(
+        data: &'a DragData,
+        model: &M,
+    ) -> Option<&'a Self> {
+        data.user_data::<Self>()
+            .filter(|items| items.is_from(model))
+    }
+
+    /// Returns `true` if the items were dragged out of `model`.
+    pub fn is_from<M: ItemModel + ?Sized>(&self, model: &M) -> bool {
+        self.model == model_address(model)
+    }
+
+    /// Returns the dragged indexes, in the order they were passed to `mime_data`.
+    pub fn indexes(&self) -> &[ModelIndex] {
+        &self.indexes
+    }
+
+    /// Returns the distinct top-level rows of the dragged items in ascending order.
+    pub fn rows(&self) -> Vec<usize> {
+        let mut rows: Vec<usize> = self
+            .indexes
+            .iter()
+            .filter(|index| index.is_valid() && !index.parent().is_valid())
+            .map(|index| index.row())
+            .collect();
+        rows.sort_unstable();
+        rows.dedup();
+        rows
+    }
+}
+
+fn model_address<M: ?Sized>(model: &M) -> usize {
+    model as *const M as *const () as usize
+}
+
+/// Builds the default drag data for `indexes`.
+///
+/// The display text of each item is stored as `text/plain`, one line per
+/// row with cells of the same row separated by tabs. The indexes' row paths
+/// are stored under [`mime::MODEL_ITEMS`], and a [`DraggedItems`] record is
+/// attached as user data.
+pub(super) fn default_mime_data<M: ItemModel + ?Sized>(
+    model: &M,
+    indexes: &[ModelIndex],
+) -> DragData {
+    let mut data = DragData::new();
+    if indexes.is_empty() {
+        return data;
+    }
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut previous: Option<&ModelIndex> = None;
+    for index in indexes {
+        let text = model.display_text(index).unwrap_or_default();
+        match (previous, lines.last_mut()) {
+            (Some(prev), Some(line))
+                if prev.row() == index.row() && prev.parent() == index.parent() =>
+            {
+                line.push('\t');
+                line.push_str(&text);
+            }
+            _ => lines.push(text),
+        }
+        previous = Some(index);
+    }
+    data.set_text(lines.join("\n"));
+
+    let paths: Vec<String> = indexes
+        .iter()
+        .map(|index| {
+            let mut rows: Vec<String> = index
+                .ancestors()
+                .iter()
+                .rev()
+                .map(|a| a.row().to_string())
+                .collect();
+            rows.push(index.row().to_string());
+            format!("{}:{}", rows.join("/"), index.column())
+        })
+        .collect();
+    data.set_data(mime::MODEL_ITEMS, paths.join("\n"));
+
+    data.set_user_data(DraggedItems::new(model, indexes.to_vec()));
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::SimpleTableModel;
+    use crate::model::role::ItemData;
+
+    #[test]
+    fn test_default_mime_data() {
+        let model = SimpleTableModel::from_data(vec![
+            vec![ItemData::from("a"), ItemData::from("b")],
+            vec![ItemData::from("c"), ItemData::from("d")],
+        ]);
+        let root = ModelIndex::invalid();
+        let indexes = vec![
+            model.index(0, 0, &root),
+            model.index(0, 1, &root),
+            model.index(1, 1, &root),
+        ];
+
+        let data = model.mime_data(&indexes);
+        assert_eq!(data.text().unwrap(), "a\tb\nd");
+        assert_eq!(data.get_data(mime::MODEL_ITEMS).unwrap(), b"0:0\n0:1\n1:1");
+
+        let items = DraggedItems::from_model(&data, &model).unwrap();
+        assert_eq!(items.rows(), vec![0, 1]);
+        let other = SimpleTableModel::new(2);
+        assert!(DraggedItems::from_model(&data, &other).is_none());
+    }
+}
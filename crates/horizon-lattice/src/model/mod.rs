@@ -15,6 +15,10 @@
 //! - `ItemData`: Type-erased container for item data
 //! - `ItemModel`: The trait that models implement
 //! - `ModelSignals`: Signals for change notifications
+//! - `DraggedItems`: Items dragged out of a model, for drag and drop between views
+//! - `ItemEditor`: Widget that edits an item in place, created by the item delegate
+//! - `ModelRow`: A struct shown as a table row, usually via `#[derive(ModelRow)]`
+//! - `ModelTester`: Checks a model's structure and change signals, for testing model implementations
 //!
 //! # Model Implementations
 //!
@@ -22,6 +26,10 @@
 //! - `TableModel`: 2D grid with rows and columns, supports headers
 //! - `TreeModel`: Hierarchical tree structure with parent-child relationships
 //! - `ProxyModel`: Wraps another model to provide filtering and sorting
+//! - `TreeProxyModel`: Filtering and sorting that preserves a tree model's hierarchy
+//! - `GroupingProxyModel`: Groups a flat model's rows into a tree with per-group aggregates
+//! - `FileSystemModel`: Lazily loaded directory tree with live updates from a file watcher
+//! - `AsyncListModel`: List loaded in batches from a background task with a placeholder row
 //!
 //! # Example
 //!
@@ -65,27 +73,51 @@
 //! Views query models using `ModelIndex` and `ItemRole` to get `ItemData`.
 //! Models emit signals when data changes, which views listen to for updates.
 
+mod async_list_model;
+mod change_tracker;
 mod delegate;
+#[cfg(feature = "async-runtime")]
+mod file_system_model;
+mod grouping_proxy_model;
 mod index;
+mod item_editor;
 mod list_model;
+mod mime;
+mod model_row;
+mod model_tester;
 mod proxy_model;
 mod role;
 pub mod selection;
 mod table_model;
 mod traits;
 mod tree_model;
+mod tree_proxy_model;
 
+pub use async_list_model::{AsyncListModel, BatchRequest, BatchSink};
+pub(crate) use change_tracker::ModelChangeTracker;
 pub use delegate::{
-    ClickRegion, DecorationPosition, DefaultItemDelegate, DelegatePaintContext, DelegateTheme,
-    ItemDelegate, StyleOptionViewItem, ViewItemFeatures, ViewItemState,
+    ClickRegion, DecorationPosition, DefaultItemDelegate, DelegatePaintContext, DelegateText,
+    DelegateTheme, ItemDelegate, StyleOptionViewItem, ViewItemFeatures, ViewItemState,
 };
+#[cfg(feature = "async-runtime")]
+pub use file_system_model::FileSystemModel;
+pub use grouping_proxy_model::{Aggregate, GroupingProxyModel};
+/// Derives [`ModelRow`] for a struct; see the [`ModelRow`] trait for the column attributes.
+pub use horizon_lattice_macros::ModelRow;
 pub use index::ModelIndex;
+pub use item_editor::{EditChoices, ItemEditor, create_default_editor};
 pub use list_model::{DataExtractor, ExtractorListModel, FlagsExtractor, ListItem, ListModel};
+pub use mime::DraggedItems;
+pub use model_row::{ColumnValue, ModelRow, column_data, column_flags, set_column_data};
+pub use model_tester::{FailureMode, ModelTester, ModelViolation};
 pub use proxy_model::{CompareFn, FilterFn, ProxyModel, ProxyModelBuilder};
 pub use role::{
     CheckState, HorizontalAlignment, ItemData, ItemRole, TextAlignment, VerticalAlignment,
 };
 pub use selection::{SelectionBehavior, SelectionFlags, SelectionMode, SelectionModel};
-pub use table_model::{CellExtractor, HeaderExtractor, SimpleTableModel, TableModel};
+pub use table_model::{
+    CellExtractor, CellFlagsExtractor, CellSetter, HeaderExtractor, SimpleTableModel, TableModel,
+};
 pub use traits::{ItemFlags, ItemModel, ModelSignals, Orientation};
 pub use tree_model::{ExtractorTreeModel, TreeModel, TreeNodeData};
+pub use tree_proxy_model::TreeProxyModel;
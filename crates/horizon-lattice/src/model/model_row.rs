@@ -0,0 +1,307 @@
+//! Table rows described by their struct fields.
+//!
+//! [`ModelRow`] describes how a struct is shown as a row of a table: its
+//! columns, their headers, the data each column returns for each role, and
+//! how edits are written back. It is usually derived:
+//!
+//! ```ignore
+//! use horizon_lattice::model::{ModelRow, TableModel};
+//!
+//! #[derive(ModelRow)]
+//! struct Task {
+//!     #[column(header = "Title", editable)]
+//!     title: String,
+//!     #[column(header = "Done", role = CheckState, editable)]
+//!     done: bool,
+//!     #[column(header = "Estimate")]
+//!     hours: f64,
+//!     #[column(skip)]
+//!     id: u64,
+//! }
+//!
+//! let model = TableModel::from_rows(tasks);
+//! ```
+//!
+//! Importing `ModelRow` brings in both the trait and the derive macro.
+//! Every field is a column unless marked `skip`. Column attributes:
+//! - `header = "..."`: The column header; defaults to the field name
+//! - `role = Role`: The [`ItemRole`] that returns the field; defaults to
+//!   `Display`. A `CheckState` column shows a `bool` field as a checkbox.
+//! - `editable`: Edits (or checkbox toggles) are written back to the field
+//! - `skip`: The field isn't a column
+//!
+//! Field types implement [`ColumnValue`] to convert to and from [`ItemData`].
+
+use chrono::NaiveDate;
+use horizon_lattice_render::Color;
+
+use super::role::{CheckState, ItemData, ItemRole};
+use super::traits::ItemFlags;
+
+/// A struct shown as a row of a table model.
+///
+/// Use [`TableModel::from_rows`](super::TableModel::from_rows) to build a
+/// model from a `Vec` of rows.
+pub trait ModelRow: Send + Sync + 'static {
+    /// Returns the number of columns.
+    fn column_count() -> usize;
+
+    /// Returns the header data of a column.
+    fn header_data(column: usize, role: ItemRole) -> ItemData;
+
+    /// Returns the data of a column for a role.
+    fn data(&self, column: usize, role: ItemRole) -> ItemData;
+
+    /// Returns the flags of a column.
+    fn flags(&self, column: usize) -> ItemFlags;
+
+    /// Writes an edited value back to a column.
+    ///
+    /// Returns `false` if the column isn't editable, doesn't store `role`, or
+    /// `value` can't be converted to the field's type.
+    fn set_data(&mut self, column: usize, value: ItemData, role: ItemRole) -> bool;
+}
+
+/// A field type that can be shown in a [`ModelRow`] column.
+pub trait ColumnValue: Sized {
+    /// Converts the value to item data.
+    fn to_item_data(&self) -> ItemData;
+
+    /// Converts item data back to a value, or `None` if it doesn't fit.
+    fn from_item_data(data: &ItemData) -> Option<Self>;
+}
+
+impl ColumnValue for String {
+    fn to_item_data(&self) -> ItemData {
+        ItemData::from(self.as_str())
+    }
+
+    fn from_item_data(data: &ItemData) -> Option<Self> {
+        match data {
+            ItemData::String(s) => Some(s.clone()),
+            ItemData::Int(i) => Some(i.to_string()),
+            ItemData::Float(f) => Some(f.to_string()),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! int_column_value {
+    ($($ty:ty),*) => {$(
+        impl ColumnValue for $ty {
+            fn to_item_data(&self) -> ItemData {
+                i64::try_from(*self).map_or(ItemData::None, ItemData::Int)
+            }
+
+            fn from_item_data(data: &ItemData) -> Option<Self> {
+                match data {
+                    ItemData::Int(i) => (*i).try_into().ok(),
+                    ItemData::String(s) => s.trim().parse().ok(),
+                    _ => None,
+                }
+            }
+        }
+    )*};
+}
+
+int_column_value!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! float_column_value {
+    ($($ty:ty),*) => {$(
+        impl ColumnValue for $ty {
+            fn to_item_data(&self) -> ItemData {
+                ItemData::Float(*self as f64)
+            }
+
+            fn from_item_data(data: &ItemData) -> Option<Self> {
+                match data {
+                    ItemData::Float(f) => Some(*f as $ty),
+                    ItemData::Int(i) => Some(*i as $ty),
+                    ItemData::String(s) => s.trim().parse().ok(),
+                    _ => None,
+                }
+            }
+        }
+    )*};
+}
+
+float_column_value!(f32, f64);
+
+impl ColumnValue for bool {
+    fn to_item_data(&self) -> ItemData {
+        ItemData::Bool(*self)
+    }
+
+    fn from_item_data(data: &ItemData) -> Option<Self> {
+        match data {
+            ItemData::Bool(b) => Some(*b),
+            ItemData::CheckState(state) => Some(state.is_checked()),
+            _ => None,
+        }
+    }
+}
+
+impl ColumnValue for CheckState {
+    fn to_item_data(&self) -> ItemData {
+        ItemData::CheckState(*self)
+    }
+
+    fn from_item_data(data: &ItemData) -> Option<Self> {
+        match data {
+            ItemData::CheckState(state) => Some(*state),
+            ItemData::Bool(true) => Some(CheckState::Checked),
+            ItemData::Bool(false) => Some(CheckState::Unchecked),
+            _ => None,
+        }
+    }
+}
+
+impl ColumnValue for Color {
+    fn to_item_data(&self) -> ItemData {
+        ItemData::Color(*self)
+    }
+
+    fn from_item_data(data: &ItemData) -> Option<Self> {
+        data.as_color().copied()
+    }
+}
+
+impl ColumnValue for NaiveDate {
+    fn to_item_data(&self) -> ItemData {
+        ItemData::Date(*self)
+    }
+
+    fn from_item_data(data: &ItemData) -> Option<Self> {
+        data.as_date()
+    }
+}
+
+/// An empty value shows as no data, and no data clears the value.
+impl<T: ColumnValue> ColumnValue for Option<T> {
+    fn to_item_data(&self) -> ItemData {
+        self.as_ref().map_or(ItemData::None, T::to_item_data)
+    }
+
+    fn from_item_data(data: &ItemData) -> Option<Self> {
+        match data {
+            ItemData::None => Some(None),
+            ItemData::String(s) if s.is_empty() => Some(None),
+            data => T::from_item_data(data).map(Some),
+        }
+    }
+}
+
+/// Returns the data of a field for `role`, in a column that stores
+/// `column_role`.
+///
+/// Display columns also return their field as edit data, and `CheckState`
+/// columns show `bool` fields as check states. Used by `#[derive(ModelRow)]`.
+#[doc(hidden)]
+pub fn column_data<V: ColumnValue>(value: &V, column_role: ItemRole, role: ItemRole) -> ItemData {
+    if role != column_role && !(column_role == ItemRole::Display && role == ItemRole::Edit) {
+        return ItemData::None;
+    }
+    match value.to_item_data() {
+        ItemData::Bool(checked) if role == ItemRole::CheckState => {
+            ItemData::CheckState(if checked {
+                CheckState::Checked
+            } else {
+                CheckState::Unchecked
+            })
+        }
+        data => data,
+    }
+}
+
+/// Writes an edited value to a field of a column that stores `column_role`.
+///
+/// Used by `#[derive(ModelRow)]`.
+#[doc(hidden)]
+pub fn set_column_data<V: ColumnValue>(
+    field: &mut V,
+    column_role: ItemRole,
+    value: &ItemData,
+    role: ItemRole,
+) -> bool {
+    if role != column_role && !(column_role == ItemRole::Display && role == ItemRole::Edit) {
+        return false;
+    }
+    match V::from_item_data(value) {
+        Some(new_value) => {
+            *field = new_value;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns the flags of a column that stores `column_role`.
+///
+/// Used by `#[derive(ModelRow)]`.
+#[doc(hidden)]
+pub fn column_flags(column_role: ItemRole, editable: bool) -> ItemFlags {
+    if column_role == ItemRole::CheckState {
+        // The checkbox is shown from the data either way; only editable
+        // columns let the user toggle it.
+        ItemFlags::new().with_checkable(editable)
+    } else {
+        ItemFlags::new().with_editable(editable)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_values_round_trip() {
+        assert_eq!(42u32.to_item_data().as_int(), Some(42));
+        assert_eq!(u8::from_item_data(&ItemData::Int(300)), None);
+        assert_eq!(i32::from_item_data(&ItemData::from(" 7 ")), Some(7));
+        assert_eq!(f32::from_item_data(&ItemData::Int(2)), Some(2.0));
+        assert_eq!(
+            bool::from_item_data(&ItemData::CheckState(CheckState::Checked)),
+            Some(true)
+        );
+        assert_eq!(
+            Option::<String>::from_item_data(&ItemData::from("")),
+            Some(None)
+        );
+        assert!(Option::<i64>::None.to_item_data().is_none());
+    }
+
+    #[test]
+    fn test_column_roles() {
+        let data = column_data(&true, ItemRole::CheckState, ItemRole::CheckState);
+        assert_eq!(data.as_check_state(), Some(CheckState::Checked));
+        assert!(column_data(&true, ItemRole::CheckState, ItemRole::Display).is_none());
+        assert_eq!(
+            column_data(&String::from("a"), ItemRole::Display, ItemRole::Edit).as_string(),
+            Some("a")
+        );
+
+        let mut field = 1i64;
+        assert!(!set_column_data(
+            &mut field,
+            ItemRole::Display,
+            &ItemData::from("x"),
+            ItemRole::Edit
+        ));
+        assert!(set_column_data(
+            &mut field,
+            ItemRole::Display,
+            &ItemData::Int(5),
+            ItemRole::Edit
+        ));
+        assert_eq!(field, 5);
+    }
+
+    #[test]
+    fn test_column_flags() {
+        assert!(column_flags(ItemRole::CheckState, true).checkable);
+        assert!(!column_flags(ItemRole::CheckState, false).checkable);
+        assert!(!column_flags(ItemRole::CheckState, true).editable);
+        assert!(column_flags(ItemRole::Display, true).editable);
+        assert!(!column_flags(ItemRole::Display, false).editable);
+    }
+}
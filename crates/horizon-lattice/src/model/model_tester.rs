@@ -0,0 +1,858 @@
+//! Consistency checks for `ItemModel` implementations.
+//!
+//! [`ModelTester`] attaches to a model's signals and checks that the model's
+//! structure agrees with the changes it announces, and walks the whole tree
+//! to check that indices, parents and row counts are consistent. Use it in
+//! the tests of custom models:
+//!
+//! ```ignore
+//! use horizon_lattice::model::ModelTester;
+//!
+//! let model = Arc::new(MyModel::new());
+//! let tester = ModelTester::new(model.clone());
+//!
+//! model.add_item("new");   // panics if the model's signals don't match
+//! model.remove_item(0);
+//! tester.check();          // full-tree walk
+//! ```
+
+use parking_lot::Mutex;
+use std::fmt;
+use std::sync::Arc;
+
+use super::index::ModelIndex;
+use super::role::{ItemData, ItemRole};
+use super::traits::{ItemModel, ModelSignals};
+
+/// Disconnects one slot from the model's signals.
+type Disconnector = Box<dyn Fn(&ModelSignals) + Send + Sync>;
+
+/// Depth at which the tree walk stops, guarding against cyclic parents.
+const MAX_DEPTH: usize = 64;
+
+/// What a [`ModelTester`] does when it finds a violation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailureMode {
+    /// Panic with the violation, failing the test at the offending call.
+    #[default]
+    Panic,
+    /// Record the violation, to be read with [`ModelTester::violations`].
+    Collect,
+}
+
+/// An inconsistency found by a [`ModelTester`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelViolation {
+    /// The check that failed, such as `"rows_inserted"` or `"tree walk"`.
+    pub check: &'static str,
+    /// What was wrong.
+    pub message: String,
+}
+
+impl fmt::Display for ModelViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.check, self.message)
+    }
+}
+
+/// A row or column change announced by an `about_to` signal.
+struct PendingChange {
+    parent: ModelIndex,
+    first: usize,
+    last: usize,
+    /// Row or column count of the parent before the change.
+    count: usize,
+    /// Data of the row or column before `first`.
+    before: Option<String>,
+    /// Data of the row or column after the changed range.
+    after: Option<String>,
+}
+
+#[derive(Default)]
+struct TesterState {
+    /// Top-level row and column counts after the last announced change.
+    rows: usize,
+    columns: usize,
+    row_inserts: Vec<PendingChange>,
+    row_removes: Vec<PendingChange>,
+    column_inserts: Vec<PendingChange>,
+    column_removes: Vec<PendingChange>,
+    /// Source parent and row count of announced moves.
+    moves: Vec<(ModelIndex, usize)>,
+    layout_changes: usize,
+    resets: usize,
+}
+
+/// Checks that an [`ItemModel`] is consistent.
+///
+/// The tester connects to the model's signals and checks, for every change:
+///
+/// - `about_to` signals are paired with their completion signals, with the
+///   same arguments;
+/// - announced ranges are within bounds, and the row or column count changes
+///   by exactly the size of the range;
+/// - the rows or columns next to the range are the same before and after;
+/// - the top-level row and column counts don't change without a signal;
+/// - `data_changed` ranges are valid and share a parent.
+///
+/// [`check`](Self::check) walks the whole tree (without fetching more rows)
+/// and checks that every index in range is valid and points back to its
+/// position and parent, that indices out of range are invalid,
+/// `has_children` agrees with `row_count`, and that standard roles return
+/// data of the documented type. The walk also runs when the tester is
+/// created and after every structural change.
+///
+/// Internal ids are not compared, since models may create new ones for the
+/// same position.
+pub struct ModelTester {
+    model: Arc<dyn ItemModel>,
+    failure_mode: FailureMode,
+    state: Mutex<TesterState>,
+    violations: Mutex<Vec<ModelViolation>>,
+    /// Slots connected to the model, disconnected on drop.
+    disconnectors: Mutex<Vec<Disconnector>>,
+}
+
+impl ModelTester {
+    /// Attaches a tester that panics on the first violation.
+    pub fn new(model: Arc<dyn ItemModel>) -> Arc<Self> {
+        Self::with_failure_mode(model, FailureMode::Panic)
+    }
+
+    /// Attaches a tester with the given failure mode.
+    pub fn with_failure_mode(model: Arc<dyn ItemModel>, failure_mode: FailureMode) -> Arc<Self> {
+        let tester = Arc::new(Self {
+            model,
+            failure_mode,
+            state: Mutex::new(TesterState::default()),
+            violations: Mutex::new(Vec::new()),
+            disconnectors: Mutex::new(Vec::new()),
+        });
+        tester.snapshot_counts();
+        tester.check();
+        tester.connect();
+        tester
+    }
+
+    /// Returns the model under test.
+    pub fn model(&self) -> &Arc<dyn ItemModel> {
+        &self.model
+    }
+
+    /// Returns the failure mode.
+    pub fn failure_mode(&self) -> FailureMode {
+        self.failure_mode
+    }
+
+    /// Returns the violations found so far (in [`FailureMode::Collect`]).
+    pub fn violations(&self) -> Vec<ModelViolation> {
+        self.violations.lock().clone()
+    }
+
+    /// Returns and clears the violations found so far.
+    pub fn take_violations(&self) -> Vec<ModelViolation> {
+        std::mem::take(&mut *self.violations.lock())
+    }
+
+    /// Walks the whole model and checks its structure.
+    pub fn check(&self) {
+        let root = ModelIndex::invalid();
+        let (rows, columns) = (self.model.row_count(&root), self.model.column_count(&root));
+        let (expected_rows, expected_columns) = {
+            let state = self.state.lock();
+            (state.rows, state.columns)
+        };
+        if self.is_idle() {
+            if rows != expected_rows {
+                self.report(
+                    "row count",
+                    format!(
+                        "top-level row count changed from {expected_rows} to {rows} without rows_inserted or rows_removed"
+                    ),
+                );
+            }
+            if columns != expected_columns {
+                self.report(
+                    "column count",
+                    format!(
+                        "top-level column count changed from {expected_columns} to {columns} without columns_inserted or columns_removed"
+                    ),
+                );
+            }
+            self.snapshot_counts();
+        }
+        self.check_children(&root, 0);
+    }
+
+    fn report(&self, check: &'static str, message: String) {
+        let violation = ModelViolation { check, message };
+        match self.failure_mode {
+            FailureMode::Panic => panic!("model violation: {violation}"),
+            FailureMode::Collect => self.violations.lock().push(violation),
+        }
+    }
+
+    /// Returns `true` if no change is in progress.
+    fn is_idle(&self) -> bool {
+        let state = self.state.lock();
+        state.row_inserts.is_empty()
+            && state.row_removes.is_empty()
+            && state.column_inserts.is_empty()
+            && state.column_removes.is_empty()
+            && state.moves.is_empty()
+            && state.layout_changes == 0
+            && state.resets == 0
+    }
+
+    fn snapshot_counts(&self) {
+        let root = ModelIndex::invalid();
+        let mut state = self.state.lock();
+        state.rows = self.model.row_count(&root);
+        state.columns = self.model.column_count(&root);
+    }
+
+    // -------------------------------------------------------------------------
+    // Tree walk
+    // -------------------------------------------------------------------------
+
+    fn check_children(&self, parent: &ModelIndex, depth: usize) {
+        if depth > MAX_DEPTH {
+            self.report(
+                "tree walk",
+                format!("tree is deeper than {MAX_DEPTH} levels below {parent:?}"),
+            );
+            return;
+        }
+        let model = &*self.model;
+        let rows = model.row_count(parent);
+        let columns = model.column_count(parent);
+
+        if rows > 0 && !model.has_children(parent) {
+            self.report(
+                "has_children",
+                format!("{parent:?} has {rows} rows but has_children is false"),
+            );
+        }
+        if rows == 0 && model.has_children(parent) && !model.can_fetch_more(parent) {
+            self.report(
+                "has_children",
+                format!("{parent:?} has no rows and can't fetch more, but has_children is true"),
+            );
+        }
+        if rows > 0 && columns == 0 {
+            self.report(
+                "column_count",
+                format!("{parent:?} has {rows} rows but no columns"),
+            );
+        }
+        if model.index(rows, 0, parent).is_valid() {
+            self.report(
+                "index",
+                format!("index({rows}, 0) past the last row of {parent:?} is valid"),
+            );
+        }
+        if rows > 0 && model.index(0, columns, parent).is_valid() {
+            self.report(
+                "index",
+                format!("index(0, {columns}) past the last column of {parent:?} is valid"),
+            );
+        }
+
+        for row in 0..rows {
+            for column in 0..columns {
+                let index = model.index(row, column, parent);
+                if !index.is_valid() {
+                    self.report(
+                        "index",
+                        format!("index({row}, {column}) of {parent:?} is invalid"),
+                    );
+                    continue;
+                }
+                if index.row() != row || index.column() != column {
+                    self.report(
+                        "index",
+                        format!(
+                            "index({row}, {column}) of {parent:?} has row {} and column {}",
+                            index.row(),
+                            index.column()
+                        ),
+                    );
+                }
+                let index_parent = model.parent(&index);
+                if !same_position(&index_parent, parent) {
+                    self.report(
+                        "parent",
+                        format!(
+                            "parent of index({row}, {column}) is {index_parent:?}, expected {parent:?}"
+                        ),
+                    );
+                }
+                if !same_position(&model.index(row, column, parent), &index) {
+                    self.report(
+                        "index",
+                        format!("index({row}, {column}) of {parent:?} isn't stable"),
+                    );
+                }
+                self.check_data(&index);
+            }
+
+            let child = model.index(row, 0, parent);
+            if child.is_valid() {
+                self.check_children(&child, depth + 1);
+            }
+        }
+    }
+
+    /// Checks that standard roles return data of the documented type.
+    fn check_data(&self, index: &ModelIndex) {
+        let is_string = |data: &ItemData| matches!(data, ItemData::None | ItemData::String(_));
+        let checks: [(ItemRole, fn(&ItemData) -> bool, &str); 10] = [
+            (ItemRole::ToolTip, is_string, "String"),
+            (ItemRole::StatusTip, is_string, "String"),
+            (ItemRole::WhatsThis, is_string, "String"),
+            (ItemRole::AccessibleText, is_string, "String"),
+            (ItemRole::AccessibleDescription, is_string, "String"),
+            (
+                ItemRole::Decoration,
+                |d| matches!(d, ItemData::None | ItemData::Icon(_) | ItemData::Color(_)),
+                "Icon or Color",
+            ),
+            (
+                ItemRole::Font,
+                |d| matches!(d, ItemData::None | ItemData::Font(_)),
+                "Font",
+            ),
+            (
+                ItemRole::TextAlignment,
+                |d| matches!(d, ItemData::None | ItemData::TextAlignment(_)),
+                "TextAlignment",
+            ),
+            (
+                ItemRole::CheckState,
+                |d| matches!(d, ItemData::None | ItemData::CheckState(_)),
+                "CheckState",
+            ),
+            (
+                ItemRole::SizeHint,
+                |d| matches!(d, ItemData::None | ItemData::Size(..)),
+                "Size",
+            ),
+        ];
+        for (role, is_valid, expected) in checks {
+            let data = self.model.data(index, role);
+            if !is_valid(&data) {
+                self.report(
+                    "data",
+                    format!("{role:?} data of {index:?} is {data:?}, expected {expected}"),
+                );
+            }
+        }
+        for role in [ItemRole::BackgroundColor, ItemRole::ForegroundColor] {
+            let data = self.model.data(index, role);
+            if !matches!(data, ItemData::None | ItemData::Color(_)) {
+                self.report(
+                    "data",
+                    format!("{role:?} data of {index:?} is {data:?}, expected Color"),
+                );
+            }
+        }
+    }
+
+    // -------------------------------------------------------------------------
+    // Signals
+    // -------------------------------------------------------------------------
+
+    fn connect(self: &Arc<Self>) {
+        let signals = self.model.signals();
+        let mut disconnectors = self.disconnectors.lock();
+
+        macro_rules! forward {
+            ($signal:ident => $handler:ident) => {{
+                let tester = Arc::downgrade(self);
+                let id = signals.$signal.connect(move |args| {
+                    if let Some(tester) = tester.upgrade() {
+                        tester.$handler(args);
+                    }
+                });
+                disconnectors.push(Box::new(move |signals: &ModelSignals| {
+                    signals.$signal.disconnect(id);
+                }));
+            }};
+        }
+
+        forward!(rows_about_to_be_inserted => rows_about_to_be_inserted);
+        forward!(rows_inserted => rows_inserted);
+        forward!(rows_about_to_be_removed => rows_about_to_be_removed);
+        forward!(rows_removed => rows_removed);
+        forward!(rows_about_to_be_moved => rows_about_to_be_moved);
+        forward!(rows_moved => rows_moved);
+        forward!(columns_about_to_be_inserted => columns_about_to_be_inserted);
+        forward!(columns_inserted => columns_inserted);
+        forward!(columns_about_to_be_removed => columns_about_to_be_removed);
+        forward!(columns_removed => columns_removed);
+        forward!(data_changed => data_changed);
+        forward!(layout_about_to_change => layout_about_to_change);
+        forward!(layout_changed => layout_changed);
+        forward!(model_about_to_reset => model_about_to_reset);
+        forward!(model_reset => model_reset);
+    }
+
+    /// Display data of a row (by column 0) or column (by row 0), for
+    /// checking that rows next to a change are kept.
+    fn item_text(&self, parent: &ModelIndex, row: usize, column: usize) -> Option<String> {
+        let index = self.model.index(row, column, parent);
+        index
+            .is_valid()
+            .then(|| format!("{:?}", self.model.data(&index, ItemRole::Display)))
+    }
+
+    /// Records a change announced by an `about_to` signal.
+    ///
+    /// `rows` selects whether the change is to rows or columns; `remove`
+    /// whether it removes them.
+    fn begin_change(
+        &self,
+        check: &'static str,
+        (parent, first, last): &(ModelIndex, usize, usize),
+        rows: bool,
+        remove: bool,
+    ) -> PendingChange {
+        if !parent.is_valid() && self.is_idle() {
+            self.check_unannounced_change();
+        }
+
+        let count = if rows {
+            self.model.row_count(parent)
+        } else {
+            self.model.column_count(parent)
+        };
+        if first > last {
+            self.report(check, format!("first {first} is after last {last}"));
+        }
+        if remove && *last >= count {
+            self.report(
+                check,
+                format!("removes up to {last} but {parent:?} has only {count}"),
+            );
+        }
+        if !remove && *first > count {
+            self.report(
+                check,
+                format!("inserts at {first} but {parent:?} has only {count}"),
+            );
+        }
+
+        let text = |i: usize| {
+            if rows {
+                self.item_text(parent, i, 0)
+            } else {
+                self.item_text(parent, 0, i)
+            }
+        };
+        PendingChange {
+            parent: parent.clone(),
+            first: *first,
+            last: *last,
+            count,
+            before: first.checked_sub(1).and_then(text),
+            after: if remove { text(last + 1) } else { text(*first) },
+        }
+    }
+
+    /// Checks a completed change against the change announced for it.
+    fn end_change(
+        &self,
+        check: &'static str,
+        pending: Option<PendingChange>,
+        (parent, first, last): &(ModelIndex, usize, usize),
+        rows: bool,
+        remove: bool,
+    ) {
+        let Some(pending) = pending else {
+            self.report(check, "emitted without the matching about_to signal".into());
+            return;
+        };
+        if !same_position(&pending.parent, parent)
+            || pending.first != *first
+            || pending.last != *last
+        {
+            self.report(
+                check,
+                format!(
+                    "({parent:?}, {first}, {last}) doesn't match the announced ({:?}, {}, {})",
+                    pending.parent, pending.first, pending.last
+                ),
+            );
+            return;
+        }
+
+        let changed = last - first + 1;
+        let expected = if remove {
+            pending.count.saturating_sub(changed)
+        } else {
+            pending.count + changed
+        };
+        let count = if rows {
+            self.model.row_count(parent)
+        } else {
+            self.model.column_count(parent)
+        };
+        if count != expected {
+            self.report(
+                check,
+                format!(
+                    "count of {parent:?} is {count} after changing {changed} of {}, expected {expected}",
+                    pending.count
+                ),
+            );
+        }
+
+        let text = |i: usize| {
+            if rows {
+                self.item_text(parent, i, 0)
+            } else {
+                self.item_text(parent, 0, i)
+            }
+        };
+        if first
+            .checked_sub(1)
+            .is_some_and(|before| text(before) != pending.before)
+        {
+            self.report(check, format!("the item before {first} changed"));
+        }
+        let after = if remove { *first } else { last + 1 };
+        if text(after) != pending.after {
+            self.report(check, format!("the item after {last} changed"));
+        }
+    }
+
+    /// Reports a top-level count change that happened without a signal.
+    fn check_unannounced_change(&self) {
+        let root = ModelIndex::invalid();
+        let state = self.state.lock();
+        let (expected_rows, expected_columns) = (state.rows, state.columns);
+        drop(state);
+        let rows = self.model.row_count(&root);
+        let columns = self.model.column_count(&root);
+        if rows != expected_rows || columns != expected_columns {
+            self.report(
+                "row count",
+                format!(
+                    "top-level size changed from {expected_rows}x{expected_columns} to {rows}x{columns} without a signal"
+                ),
+            );
+        }
+    }
+
+    /// Re-checks the model once no change is in progress.
+    fn finish(&self) {
+        if self.is_idle() {
+            self.snapshot_counts();
+            self.check_children(&ModelIndex::invalid(), 0);
+        }
+    }
+
+    fn rows_about_to_be_inserted(&self, args: &(ModelIndex, usize, usize)) {
+        let pending = self.begin_change("rows_about_to_be_inserted", args, true, false);
+        self.state.lock().row_inserts.push(pending);
+    }
+
+    fn rows_inserted(&self, args: &(ModelIndex, usize, usize)) {
+        let pending = self.state.lock().row_inserts.pop();
+        self.end_change("rows_inserted", pending, args, true, false);
+        self.finish();
+    }
+
+    fn rows_about_to_be_removed(&self, args: &(ModelIndex, usize, usize)) {
+        let pending = self.begin_change("rows_about_to_be_removed", args, true, true);
+        self.state.lock().row_removes.push(pending);
+    }
+
+    fn rows_removed(&self, args: &(ModelIndex, usize, usize)) {
+        let pending = self.state.lock().row_removes.pop();
+        self.end_change("rows_removed", pending, args, true, true);
+        self.finish();
+    }
+
+    fn columns_about_to_be_inserted(&self, args: &(ModelIndex, usize, usize)) {
+        let pending = self.begin_change("columns_about_to_be_inserted", args, false, false);
+        self.state.lock().column_inserts.push(pending);
+    }
+
+    fn columns_inserted(&self, args: &(ModelIndex, usize, usize)) {
+        let pending = self.state.lock().column_inserts.pop();
+        self.end_change("columns_inserted", pending, args, false, false);
+        self.finish();
+    }
+
+    fn columns_about_to_be_removed(&self, args: &(ModelIndex, usize, usize)) {
+        let pending = self.begin_change("columns_about_to_be_removed", args, false, true);
+        self.state.lock().column_removes.push(pending);
+    }
+
+    fn columns_removed(&self, args: &(ModelIndex, usize, usize)) {
+        let pending = self.state.lock().column_removes.pop();
+        self.end_change("columns_removed", pending, args, false, true);
+        self.finish();
+    }
+
+    fn rows_about_to_be_moved(
+        &self,
+        (parent, first, last, _, _): &(ModelIndex, usize, usize, ModelIndex, usize),
+    ) {
+        let count = self.model.row_count(parent);
+        if first > last || *last >= count {
+            self.report(
+                "rows_about_to_be_moved",
+                format!("moves {first}..={last} but {parent:?} has only {count} rows"),
+            );
+        }
+        self.state.lock().moves.push((parent.clone(), count));
+    }
+
+    fn rows_moved(
+        &self,
+        (parent, first, last, destination, _): &(ModelIndex, usize, usize, ModelIndex, usize),
+    ) {
+        let Some((announced, count)) = self.state.lock().moves.pop() else {
+            self.report(
+                "rows_moved",
+                "emitted without rows_about_to_be_moved".into(),
+            );
+            return;
+        };
+        if !same_position(&announced, parent) {
+            self.report(
+                "rows_moved",
+                format!("source parent {parent:?} doesn't match the announced {announced:?}"),
+            );
+        }
+        let moved = last - first + 1;
+        let expected = if same_position(parent, destination) {
+            count
+        } else {
+            count.saturating_sub(moved)
+        };
+        let rows = self.model.row_count(parent);
+        if rows != expected {
+            self.report(
+                "rows_moved",
+                format!("source parent has {rows} rows after the move, expected {expected}"),
+            );
+        }
+        self.finish();
+    }
+
+    fn data_changed(&self, (top_left, bottom_right, _): &(ModelIndex, ModelIndex, Vec<ItemRole>)) {
+        if !top_left.is_valid() || !bottom_right.is_valid() {
+            self.report(
+                "data_changed",
+                format!("invalid range {top_left:?} to {bottom_right:?}"),
+            );
+            return;
+        }
+        let parent = self.model.parent(top_left);
+        if !same_position(&parent, &self.model.parent(bottom_right)) {
+            self.report(
+                "data_changed",
+                format!("{top_left:?} and {bottom_right:?} have different parents"),
+            );
+        }
+        if top_left.row() > bottom_right.row() || top_left.column() > bottom_right.column() {
+            self.report(
+                "data_changed",
+                format!("{top_left:?} is below or right of {bottom_right:?}"),
+            );
+        }
+        let (rows, columns) = (
+            self.model.row_count(&parent),
+            self.model.column_count(&parent),
+        );
+        if bottom_right.row() >= rows || bottom_right.column() >= columns {
+            self.report(
+                "data_changed",
+                format!("{bottom_right:?} is outside the {rows}x{columns} items of its parent"),
+            );
+        }
+    }
+
+    fn layout_about_to_change(&self, _: &()) {
+        self.state.lock().layout_changes += 1;
+    }
+
+    fn layout_changed(&self, _: &()) {
+        let balanced = {
+            let mut state = self.state.lock();
+            let balanced = state.layout_changes > 0;
+            state.layout_changes = state.layout_changes.saturating_sub(1);
+            balanced
+        };
+        if !balanced {
+            self.report(
+                "layout_changed",
+                "emitted without layout_about_to_change".into(),
+            );
+        }
+        self.finish();
+    }
+
+    fn model_about_to_reset(&self, _: &()) {
+        self.state.lock().resets += 1;
+    }
+
+    fn model_reset(&self, _: &()) {
+        let balanced = {
+            let mut state = self.state.lock();
+            let balanced = state.resets > 0;
+            state.resets = state.resets.saturating_sub(1);
+            balanced
+        };
+        if !balanced {
+            self.report("model_reset", "emitted without model_about_to_reset".into());
+        }
+        self.finish();
+    }
+}
+
+impl Drop for ModelTester {
+    fn drop(&mut self) {
+        let signals = self.model.signals();
+        for disconnect in self.disconnectors.get_mut().drain(..) {
+            disconnect(signals);
+        }
+    }
+}
+
+impl fmt::Debug for ModelTester {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ModelTester")
+            .field("failure_mode", &self.failure_mode)
+            .field("violations", &self.violations.lock().len())
+            .finish()
+    }
+}
+
+/// Returns `true` if both indices refer to the same position, ignoring
+/// internal ids.
+fn same_position(a: &ModelIndex, b: &ModelIndex) -> bool {
+    match (a.is_valid(), b.is_valid()) {
+        (false, false) => true,
+        (true, true) => {
+            a.row() == b.row()
+                && a.column() == b.column()
+                && same_position(&a.parent(), &b.parent())
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::traits::ItemFlags;
+    use parking_lot::RwLock;
+
+    /// A list model with switchable bugs.
+    #[derive(Default)]
+    struct FaultyModel {
+        items: RwLock<Vec<String>>,
+        signals: ModelSignals,
+    }
+
+    impl FaultyModel {
+        fn push_silently(&self, item: &str) {
+            self.items.write().push(item.to_string());
+        }
+
+        fn push_with_wrong_range(&self, item: &str) {
+            let len = self.items.read().len();
+            self.signals
+                .emit_rows_inserted(ModelIndex::invalid(), len + 1, len + 1, || {
+                    self.items.write().push(item.to_string());
+                });
+        }
+    }
+
+    impl ItemModel for FaultyModel {
+        fn row_count(&self, parent: &ModelIndex) -> usize {
+            if parent.is_valid() {
+                0
+            } else {
+                self.items.read().len()
+            }
+        }
+
+        fn column_count(&self, _parent: &ModelIndex) -> usize {
+            1
+        }
+
+        fn data(&self, index: &ModelIndex, role: ItemRole) -> ItemData {
+            match role {
+                ItemRole::Display => self.items.read().get(index.row()).cloned().into(),
+                // Wrong type for the role.
+                ItemRole::ToolTip if index.row() == 2 => ItemData::Int(2),
+                _ => ItemData::None,
+            }
+        }
+
+        fn index(&self, row: usize, column: usize, parent: &ModelIndex) -> ModelIndex {
+            // Bug: doesn't check the row against the row count.
+            if parent.is_valid() || column > 0 {
+                return ModelIndex::invalid();
+            }
+            ModelIndex::new(row, column, ModelIndex::invalid())
+        }
+
+        fn parent(&self, _index: &ModelIndex) -> ModelIndex {
+            ModelIndex::invalid()
+        }
+
+        fn signals(&self) -> &ModelSignals {
+            &self.signals
+        }
+
+        fn flags(&self, _index: &ModelIndex) -> ItemFlags {
+            ItemFlags::new()
+        }
+    }
+
+    #[test]
+    fn test_reports_violations() {
+        let model = Arc::new(FaultyModel::default());
+        let tester = ModelTester::with_failure_mode(model.clone(), FailureMode::Collect);
+        let checks = |tester: &ModelTester| -> Vec<&'static str> {
+            tester.take_violations().iter().map(|v| v.check).collect()
+        };
+        assert_eq!(checks(&tester), ["index"]);
+
+        model.push_silently("a");
+        tester.check();
+        assert_eq!(checks(&tester), ["row count", "index"]);
+
+        model.push_with_wrong_range("b");
+        assert_eq!(
+            checks(&tester),
+            ["rows_about_to_be_inserted", "rows_inserted", "index"]
+        );
+
+        model.push_with_wrong_range("c");
+        let violations = tester.take_violations();
+        assert!(violations.iter().any(|v| v.check == "data"
+            && v.message.contains("ToolTip")
+            && v.message.contains("expected String")));
+    }
+
+    #[test]
+    #[should_panic(expected = "model violation: rows_inserted")]
+    fn test_panics_on_violation() {
+        let tester = ModelTester::new(Arc::new(crate::model::ListModel::new(vec![
+            "a".to_string(),
+        ])));
+        // A completion signal without its announcement.
+        tester
+            .model()
+            .signals()
+            .rows_inserted
+            .emit((ModelIndex::invalid(), 0, 0));
+    }
+}
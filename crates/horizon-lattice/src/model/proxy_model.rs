@@ -105,10 +105,9 @@ pub struct ProxyModel<S: ItemModel> {
     /// for hierarchical models, but we keep it simple for now.
     mapping: RwLock<RowMapping>,
     signals: ModelSignals,
-    /// Column used for sorting (for simple sort comparators).
-    sort_column: RwLock<Option<usize>>,
-    /// Whether sort is descending.
-    sort_descending: RwLock<bool>,
+    /// Columns used for sorting, most significant first, with whether each
+    /// is descending.
+    sort_columns: RwLock<Vec<(usize, bool)>>,
 }
 
 impl<S: ItemModel + 'static> ProxyModel<S> {
@@ -120,8 +119,7 @@ impl<S: ItemModel + 'static> ProxyModel<S> {
             compare: RwLock::new(None),
             mapping: RwLock::new(RowMapping::new()),
             signals: ModelSignals::new(),
-            sort_column: RwLock::new(None),
-            sort_descending: RwLock::new(false),
+            sort_columns: RwLock::new(Vec::new()),
         };
         proxy.rebuild_mapping();
         proxy
@@ -220,15 +218,27 @@ impl<S: ItemModel + 'static> ProxyModel<S> {
     ///
     /// This sorts by comparing the Display role of the specified column.
     pub fn sort_by_column(&self, column: usize, descending: bool) {
-        *self.sort_column.write() = Some(column);
-        *self.sort_descending.write() = descending;
-        self.rebuild_mapping();
+        self.sort_by_columns(&[(column, descending)]);
+    }
+
+    /// Sets column-based sorting by several columns, most significant first.
+    ///
+    /// Each entry is a column and whether it sorts in descending order. Rows
+    /// that compare equal by one column are ordered by the next.
+    pub fn sort_by_columns(&self, columns: &[(usize, bool)]) {
+        *self.sort_columns.write() = columns.to_vec();
+        self.invalidate();
+    }
+
+    /// Returns the columns used for column-based sorting.
+    pub fn sort_columns(&self) -> Vec<(usize, bool)> {
+        self.sort_columns.read().clone()
     }
 
     /// Clears sorting.
     pub fn clear_sort(&self) {
-        *self.sort_column.write() = None;
-        self.rebuild_mapping();
+        self.sort_columns.write().clear();
+        self.invalidate();
     }
 
     /// Forces a rebuild of the proxy mapping.
@@ -304,19 +314,27 @@ impl<S: ItemModel + 'static> ProxyModel<S> {
         let compare_guard = self.compare.read();
         if let Some(ref compare) = *compare_guard {
             visible_rows.sort_by(|&a, &b| compare(&self.source, a, b, &parent));
-        } else if let Some(column) = *self.sort_column.read() {
+        } else {
             // Simple column-based sorting
-            let descending = *self.sort_descending.read();
-            visible_rows.sort_by(|&a, &b| {
-                let index_a = self.source.index(a, column, &parent);
-                let index_b = self.source.index(b, column, &parent);
-
-                let data_a = self.source.data(&index_a, ItemRole::Display);
-                let data_b = self.source.data(&index_b, ItemRole::Display);
-
-                let cmp = compare_item_data(&data_a, &data_b);
-                if descending { cmp.reverse() } else { cmp }
-            });
+            let columns = self.sort_columns.read();
+            if !columns.is_empty() {
+                visible_rows.sort_by(|&a, &b| {
+                    columns
+                        .iter()
+                        .map(|&(column, descending)| {
+                            let index_a = self.source.index(a, column, &parent);
+                            let index_b = self.source.index(b, column, &parent);
+
+                            let data_a = self.source.data(&index_a, ItemRole::Display);
+                            let data_b = self.source.data(&index_b, ItemRole::Display);
+
+                            let cmp = compare_item_data(&data_a, &data_b);
+                            if descending { cmp.reverse() } else { cmp }
+                        })
+                        .find(|cmp| cmp.is_ne())
+                        .unwrap_or(Ordering::Equal)
+                });
+            }
         }
 
         // Build the mapping
@@ -328,12 +346,14 @@ impl<S: ItemModel + 'static> ProxyModel<S> {
 }
 
 /// Compares two ItemData values for sorting.
-fn compare_item_data(a: &ItemData, b: &ItemData) -> Ordering {
+pub(super) fn compare_item_data(a: &ItemData, b: &ItemData) -> Ordering {
     match (a, b) {
         (ItemData::String(sa), ItemData::String(sb)) => sa.cmp(sb),
         (ItemData::Int(ia), ItemData::Int(ib)) => ia.cmp(ib),
         (ItemData::Float(fa), ItemData::Float(fb)) => fa.partial_cmp(fb).unwrap_or(Ordering::Equal),
         (ItemData::Bool(ba), ItemData::Bool(bb)) => ba.cmp(bb),
+        (ItemData::CheckState(ca), ItemData::CheckState(cb)) => (*ca as u8).cmp(&(*cb as u8)),
+        (ItemData::Date(da), ItemData::Date(db)) => da.cmp(db),
         // For other types, consider them equal or compare by debug string
         _ => Ordering::Equal,
     }
@@ -392,6 +412,10 @@ impl<S: ItemModel + 'static> ItemModel for ProxyModel<S> {
     fn header_data(&self, section: usize, orientation: Orientation, role: ItemRole) -> ItemData {
         self.source.header_data(section, orientation, role)
     }
+
+    fn sort(&self, columns: &[(usize, bool)]) {
+        self.sort_by_columns(columns);
+    }
 }
 
 /// Builder pattern for creating proxy models.
@@ -778,4 +802,62 @@ mod tests {
 
         assert_eq!(names, vec!["Charlie", "Alice", "Bob", "David"]);
     }
+
+    #[test]
+    fn test_model_tester() {
+        let source = create_test_model();
+        let proxy = Arc::new(ProxyModel::new(source.clone()));
+        let tester = crate::model::ModelTester::new(proxy.clone());
+
+        proxy.set_filter(|model, row, parent| {
+            let index = model.index(row, 0, parent);
+            model.data(&index, ItemRole::User(0)).as_int().unwrap_or(0) >= 25
+        });
+        proxy.sort_by_column(0, false);
+        proxy.set_sort(|model, a, b, parent| {
+            let age = |row| {
+                let index = model.index(row, 0, parent);
+                model.data(&index, ItemRole::User(0)).as_int()
+            };
+            age(a).cmp(&age(b))
+        });
+        source.push(Person {
+            name: "Eve".into(),
+            age: 40,
+        });
+        proxy.invalidate();
+        proxy.clear_filter();
+        source.remove(0);
+        proxy.invalidate();
+        tester.check();
+    }
+
+    #[test]
+    fn test_proxy_sort_by_columns() {
+        use crate::model::SimpleTableModel;
+
+        let source = Arc::new(SimpleTableModel::from_data(vec![
+            vec![ItemData::from("b"), ItemData::from(1i64)],
+            vec![ItemData::from("a"), ItemData::from(1i64)],
+            vec![ItemData::from("a"), ItemData::from(2i64)],
+        ]));
+        let proxy = ProxyModel::new(source);
+        let rows = |proxy: &ProxyModel<SimpleTableModel>| {
+            (0..3)
+                .map(|row| {
+                    proxy
+                        .map_to_source(&proxy.index(row, 0, &ModelIndex::invalid()))
+                        .row()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        proxy.sort_by_columns(&[(1, true), (0, false)]);
+        assert_eq!(rows(&proxy), [2, 1, 0]);
+
+        // Sorting through the trait, as views do.
+        ItemModel::sort(&proxy, &[(0, true), (1, false)]);
+        assert_eq!(proxy.sort_columns(), [(0, true), (1, false)]);
+        assert_eq!(rows(&proxy), [0, 1, 2]);
+    }
 }
@@ -4,6 +4,7 @@
 //! Each item can have multiple pieces of data associated with it, distinguished
 //! by their role.
 
+use chrono::NaiveDate;
 use horizon_lattice_render::{Color, Font, Icon};
 
 /// Standard roles for accessing different aspects of item data.
@@ -297,6 +298,8 @@ pub enum ItemData {
     CheckState(CheckState),
     /// Size data (width, height).
     Size(f32, f32),
+    /// Calendar date data.
+    Date(NaiveDate),
     /// Custom data (type-erased).
     Custom(Box<dyn std::any::Any + Send + Sync>),
 }
@@ -315,6 +318,7 @@ impl Clone for ItemData {
             ItemData::TextAlignment(a) => ItemData::TextAlignment(*a),
             ItemData::CheckState(s) => ItemData::CheckState(*s),
             ItemData::Size(w, h) => ItemData::Size(*w, *h),
+            ItemData::Date(d) => ItemData::Date(*d),
             // Custom data cannot be cloned; becomes None
             ItemData::Custom(_) => ItemData::None,
         }
@@ -425,6 +429,14 @@ impl ItemData {
         }
     }
 
+    /// Attempts to get the data as a date.
+    pub fn as_date(&self) -> Option<NaiveDate> {
+        match self {
+            ItemData::Date(d) => Some(*d),
+            _ => None,
+        }
+    }
+
     /// Attempts to downcast custom data to the specified type.
     pub fn downcast<T: std::any::Any>(&self) -> Option<&T> {
         match self {
@@ -514,6 +526,12 @@ impl From<CheckState> for ItemData {
     }
 }
 
+impl From<NaiveDate> for ItemData {
+    fn from(d: NaiveDate) -> Self {
+        ItemData::Date(d)
+    }
+}
+
 impl From<Option<String>> for ItemData {
     fn from(opt: Option<String>) -> Self {
         match opt {
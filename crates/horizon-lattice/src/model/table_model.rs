@@ -6,9 +6,14 @@
 use parking_lot::RwLock;
 use std::sync::Arc;
 
+use crate::widget::drag_drop::{DragData, DropAction};
+
 use super::index::ModelIndex;
+use super::mime::DraggedItems;
+use super::model_row::ModelRow;
+use super::proxy_model::compare_item_data;
 use super::role::{ItemData, ItemRole};
-use super::traits::{ItemModel, ModelSignals, Orientation};
+use super::traits::{ItemFlags, ItemModel, ModelSignals, Orientation};
 
 /// Type alias for a cell data extractor function.
 pub type CellExtractor<T> = Arc<dyn Fn(&T, usize, ItemRole) -> ItemData + Send + Sync>;
@@ -16,6 +21,12 @@ pub type CellExtractor<T> = Arc<dyn Fn(&T, usize, ItemRole) -> ItemData + Send +
 /// Type alias for a header data function.
 pub type HeaderExtractor = Arc<dyn Fn(usize, Orientation, ItemRole) -> ItemData + Send + Sync>;
 
+/// Type alias for a cell flags function.
+pub type CellFlagsExtractor<T> = Arc<dyn Fn(&T, usize) -> ItemFlags + Send + Sync>;
+
+/// Type alias for a function that writes an edited cell back to its row.
+pub type CellSetter<T> = Arc<dyn Fn(&mut T, usize, ItemData, ItemRole) -> bool + Send + Sync>;
+
 /// A table model for displaying 2D grid data.
 ///
 /// `TableModel` stores rows of data, where each row can have multiple columns.
@@ -63,11 +74,16 @@ pub type HeaderExtractor = Arc<dyn Fn(usize, Orientation, ItemRole) -> ItemData
 ///     }
 /// });
 /// ```
+///
+/// Rows that implement [`ModelRow`], usually with `#[derive(ModelRow)]`,
+/// give an editable, sortable model with [`TableModel::from_rows`].
 pub struct TableModel<T> {
     rows: RwLock<Vec<T>>,
     column_count: usize,
     cell_extractor: CellExtractor<T>,
     header_extractor: Option<HeaderExtractor>,
+    flags_extractor: Option<CellFlagsExtractor<T>>,
+    cell_setter: Option<CellSetter<T>>,
     signals: ModelSignals,
 }
 
@@ -88,10 +104,31 @@ impl<T: Send + Sync + 'static> TableModel<T> {
             column_count,
             cell_extractor: Arc::new(cell_extractor),
             header_extractor: None,
+            flags_extractor: None,
+            cell_setter: None,
             signals: ModelSignals::new(),
         }
     }
 
+    /// Creates a table model whose rows describe their own columns.
+    ///
+    /// Columns, headers, cell data, flags and edits all come from the
+    /// [`ModelRow`] implementation, and the model sorts by cell data.
+    pub fn from_rows(rows: Vec<T>) -> Self
+    where
+        T: ModelRow,
+    {
+        Self::new(rows, T::column_count(), |row, column, role| {
+            row.data(column, role)
+        })
+        .with_headers(|section, orientation, role| match orientation {
+            Orientation::Horizontal => T::header_data(section, role),
+            Orientation::Vertical => ItemData::None,
+        })
+        .with_flags(|row, column| row.flags(column))
+        .with_setter(|row, column, value, role| row.set_data(column, value, role))
+    }
+
     /// Creates an empty table model with the specified column count.
     pub fn empty(column_count: usize) -> Self
     where
@@ -102,6 +139,8 @@ impl<T: Send + Sync + 'static> TableModel<T> {
             column_count,
             cell_extractor: Arc::new(|_, _, _| ItemData::None),
             header_extractor: None,
+            flags_extractor: None,
+            cell_setter: None,
             signals: ModelSignals::new(),
         }
     }
@@ -126,6 +165,30 @@ impl<T: Send + Sync + 'static> TableModel<T> {
         self.header_extractor = Some(Arc::new(header_extractor));
     }
 
+    /// Adds a flags function to the model: (row_data, column) -> flags
+    ///
+    /// Without one, every cell is selectable and enabled but not editable.
+    pub fn with_flags<F>(mut self, flags_extractor: F) -> Self
+    where
+        F: Fn(&T, usize) -> ItemFlags + Send + Sync + 'static,
+    {
+        self.flags_extractor = Some(Arc::new(flags_extractor));
+        self
+    }
+
+    /// Adds a setter that writes edited cells back to their rows.
+    ///
+    /// The setter is called for `set_data` requests:
+    /// (row_data, column, value, role) -> accepted. The model emits
+    /// `data_changed` for the cell when the setter accepts the value.
+    pub fn with_setter<F>(mut self, cell_setter: F) -> Self
+    where
+        F: Fn(&mut T, usize, ItemData, ItemRole) -> bool + Send + Sync + 'static,
+    {
+        self.cell_setter = Some(Arc::new(cell_setter));
+        self
+    }
+
     /// Returns the number of rows.
     pub fn row_count_value(&self) -> usize {
         self.rows.read().len()
@@ -168,6 +231,25 @@ impl<T: Send + Sync + 'static> TableModel<T> {
         removed.unwrap()
     }
 
+    /// Moves the rows at `rows` so they are consecutive, starting at `destination`.
+    ///
+    /// `destination` is a position in the table before the move, as for a
+    /// drop between two rows. Rows past the end of the table are ignored.
+    pub fn move_rows(&self, rows: &[usize], destination: usize) {
+        let len = self.row_count_value();
+        let mut rows: Vec<usize> = rows.iter().copied().filter(|&row| row < len).collect();
+        rows.sort_unstable();
+        rows.dedup();
+
+        let mut moved: Vec<T> = rows.iter().rev().map(|&row| self.remove_row(row)).collect();
+        moved.reverse();
+        let destination =
+            destination.min(len) - rows.iter().filter(|&&row| row < destination).count();
+        for (offset, row) in moved.into_iter().enumerate() {
+            self.insert_row(destination + offset, row);
+        }
+    }
+
     /// Removes all rows from the table.
     pub fn clear(&self) {
         self.signals.emit_reset(|| {
@@ -282,6 +364,109 @@ impl<T: Send + Sync + 'static> ItemModel for TableModel<T> {
             ItemData::None
         }
     }
+
+    fn flags(&self, index: &ModelIndex) -> ItemFlags {
+        let Some(ref extractor) = self.flags_extractor else {
+            return ItemFlags::new();
+        };
+        let rows = self.rows.read();
+        match rows.get(index.row()) {
+            Some(row) if index.is_valid() && index.column() < self.column_count => {
+                extractor(row, index.column())
+            }
+            _ => ItemFlags::new(),
+        }
+    }
+
+    fn set_data(&self, index: &ModelIndex, value: ItemData, role: ItemRole) -> bool {
+        let Some(ref setter) = self.cell_setter else {
+            return false;
+        };
+        if !index.is_valid() || index.column() >= self.column_count {
+            return false;
+        }
+        let accepted = match self.rows.write().get_mut(index.row()) {
+            Some(row) => setter(row, index.column(), value, role),
+            None => false,
+        };
+        if accepted {
+            let roles = if role == ItemRole::Edit {
+                vec![ItemRole::Display, ItemRole::Edit]
+            } else {
+                vec![role]
+            };
+            self.signals.emit_data_changed_single(index.clone(), roles);
+        }
+        accepted
+    }
+
+    /// Sorts rows by the display data of each column, or by check state for
+    /// columns without display data.
+    fn sort(&self, columns: &[(usize, bool)]) {
+        let columns: Vec<(usize, bool)> = columns
+            .iter()
+            .copied()
+            .filter(|&(column, _)| column < self.column_count)
+            .collect();
+        if columns.is_empty() {
+            return;
+        }
+        let extractor = self.cell_extractor.clone();
+        let key = move |row: &T, column: usize| match extractor(row, column, ItemRole::Display) {
+            ItemData::None => extractor(row, column, ItemRole::CheckState),
+            data => data,
+        };
+        self.sort_by(|a, b| {
+            columns
+                .iter()
+                .map(|&(column, descending)| {
+                    let ordering = compare_item_data(&key(a, column), &key(b, column));
+                    if descending {
+                        ordering.reverse()
+                    } else {
+                        ordering
+                    }
+                })
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+
+    fn supported_drop_actions(&self) -> DropAction {
+        DropAction::MOVE
+    }
+
+    /// Rows can't be created from dropped data, so only moves of the
+    /// table's own rows are accepted.
+    fn can_drop_mime_data(
+        &self,
+        data: &DragData,
+        action: DropAction,
+        _row: usize,
+        _column: usize,
+        parent: &ModelIndex,
+    ) -> bool {
+        !parent.is_valid()
+            && action == DropAction::MOVE
+            && DraggedItems::from_model(data, self).is_some()
+    }
+
+    fn drop_mime_data(
+        &self,
+        data: &DragData,
+        action: DropAction,
+        row: usize,
+        column: usize,
+        parent: &ModelIndex,
+    ) -> bool {
+        if !self.can_drop_mime_data(data, action, row, column, parent) {
+            return false;
+        }
+        if let Some(items) = DraggedItems::from_model(data, self) {
+            self.move_rows(&items.rows(), row);
+        }
+        true
+    }
 }
 
 /// A simple table model that stores data in a 2D vector.
@@ -291,6 +476,7 @@ pub struct SimpleTableModel {
     data: RwLock<Vec<Vec<ItemData>>>,
     column_count: usize,
     headers: RwLock<Vec<String>>,
+    editable: bool,
     signals: ModelSignals,
 }
 
@@ -301,6 +487,7 @@ impl SimpleTableModel {
             data: RwLock::new(Vec::new()),
             column_count,
             headers: RwLock::new(vec![String::new(); column_count]),
+            editable: false,
             signals: ModelSignals::new(),
         }
     }
@@ -312,10 +499,22 @@ impl SimpleTableModel {
             data: RwLock::new(data),
             column_count,
             headers: RwLock::new(vec![String::new(); column_count]),
+            editable: false,
             signals: ModelSignals::new(),
         }
     }
 
+    /// Makes the cells editable in views, with edits stored via `set_data`.
+    pub fn with_editable(mut self, editable: bool) -> Self {
+        self.editable = editable;
+        self
+    }
+
+    /// Returns `true` if the cells are editable.
+    pub fn is_editable(&self) -> bool {
+        self.editable
+    }
+
     /// Sets the column headers.
     pub fn set_headers(&self, headers: Vec<String>) {
         *self.headers.write() = headers;
@@ -399,7 +598,7 @@ impl ItemModel for SimpleTableModel {
     }
 
     fn data(&self, index: &ModelIndex, role: ItemRole) -> ItemData {
-        if !index.is_valid() || role != ItemRole::Display {
+        if !index.is_valid() || !matches!(role, ItemRole::Display | ItemRole::Edit) {
             return ItemData::None;
         }
 
@@ -439,6 +638,28 @@ impl ItemModel for SimpleTableModel {
         &self.signals
     }
 
+    fn set_data(&self, index: &ModelIndex, value: ItemData, role: ItemRole) -> bool {
+        if !self.editable || !matches!(role, ItemRole::Display | ItemRole::Edit) {
+            return false;
+        }
+        let in_bounds = self
+            .data
+            .read()
+            .get(index.row())
+            .is_some_and(|row| index.column() < row.len());
+        if in_bounds {
+            self.set_cell(index.row(), index.column(), value);
+        }
+        in_bounds
+    }
+
+    fn flags(&self, _index: &ModelIndex) -> ItemFlags {
+        ItemFlags {
+            editable: self.editable,
+            ..ItemFlags::new()
+        }
+    }
+
     fn header_data(&self, section: usize, orientation: Orientation, role: ItemRole) -> ItemData {
         if orientation != Orientation::Horizontal || role != ItemRole::Display {
             return ItemData::None;
@@ -627,4 +848,63 @@ mod tests {
             Some("Second")
         );
     }
+
+    #[test]
+    fn test_drop_moves_rows() {
+        let model = TableModel::new(
+            (0..4).map(|i| i.to_string()).collect::<Vec<_>>(),
+            1,
+            |row, _, _| ItemData::from(row.as_str()),
+        );
+        let root = ModelIndex::invalid();
+        let data = model.mime_data(&[model.index(3, 0, &root)]);
+
+        assert!(!model.can_drop_mime_data(&data, DropAction::COPY, 0, 0, &root));
+        assert!(model.drop_mime_data(&data, DropAction::MOVE, 1, 0, &root));
+        assert_eq!(*model.rows(), ["0", "3", "1", "2"]);
+    }
+
+    #[test]
+    fn test_model_tester() {
+        let row = |name: &str, value| TestRow {
+            name: name.into(),
+            value,
+            active: value > 0,
+        };
+        let model = Arc::new(TableModel::new(
+            vec![row("a", 1), row("b", 2)],
+            3,
+            |row, col, role| match (col, role) {
+                (0, ItemRole::Display) => ItemData::from(row.name.as_str()),
+                (1, ItemRole::Display) => ItemData::from(row.value as i64),
+                (2, ItemRole::CheckState) => ItemData::from(if row.active {
+                    crate::model::CheckState::Checked
+                } else {
+                    crate::model::CheckState::Unchecked
+                }),
+                _ => ItemData::None,
+            },
+        ));
+        let tester = crate::model::ModelTester::new(model.clone());
+
+        model.push_row(row("c", 3));
+        model.insert_row(1, row("d", -4));
+        model.remove_row(0);
+        model.move_rows(&[2], 0);
+        model.modify_row(1, |r| r.value = 5);
+        model.sort_by(|a, b| a.value.cmp(&b.value));
+        model.clear();
+        tester.check();
+
+        let simple = Arc::new(SimpleTableModel::from_data(vec![
+            vec![ItemData::from("a"), ItemData::from(1i64)],
+            vec![ItemData::from("b"), ItemData::from(2i64)],
+        ]));
+        let tester = crate::model::ModelTester::new(simple.clone());
+        simple.append_row(vec![ItemData::from("c"), ItemData::from(3i64)]);
+        simple.set_cell(2, 1, ItemData::from(4i64));
+        simple.remove_row(0);
+        simple.clear();
+        tester.check();
+    }
 }
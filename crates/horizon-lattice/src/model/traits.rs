@@ -5,7 +5,10 @@
 
 use horizon_lattice_core::Signal;
 
+use crate::widget::drag_drop::{DragData, DropAction, mime};
+
 use super::index::ModelIndex;
+use super::mime::default_mime_data;
 use super::role::{CheckState, ItemData, ItemRole};
 
 /// Flags indicating what operations are allowed on an item.
@@ -276,6 +279,77 @@ pub trait ItemModel: Send + Sync {
     /// The default does nothing.
     fn fetch_more(&self, _parent: &ModelIndex) {}
 
+    /// Sorts the model by the given columns, most significant first.
+    ///
+    /// Each entry is a column and whether it sorts in descending order.
+    /// Views call this when the user sorts by clicking headers.
+    /// The default does nothing, for models that can't be sorted.
+    fn sort(&self, _columns: &[(usize, bool)]) {}
+
+    // -------------------------------------------------------------------------
+    // Drag and drop
+    // -------------------------------------------------------------------------
+
+    /// Returns the MIME types produced by `mime_data`.
+    fn mime_types(&self) -> Vec<String> {
+        vec![mime::TEXT_PLAIN.to_string(), mime::MODEL_ITEMS.to_string()]
+    }
+
+    /// Serializes the given items for a drag.
+    ///
+    /// The default stores the items' display text as `text/plain`, one line
+    /// per row with cells separated by tabs, and attaches a
+    /// [`DraggedItems`](super::DraggedItems) record so that the model can
+    /// recognize its own items when they are dropped back onto it.
+    fn mime_data(&self, indexes: &[ModelIndex]) -> DragData {
+        default_mime_data(self, indexes)
+    }
+
+    /// Returns the actions supported when dragging items out of this model.
+    ///
+    /// The default allows copying and moving.
+    fn supported_drag_actions(&self) -> DropAction {
+        DropAction::COPY | DropAction::MOVE
+    }
+
+    /// Returns the actions supported when dropping data onto this model.
+    ///
+    /// The default allows copying.
+    fn supported_drop_actions(&self) -> DropAction {
+        DropAction::COPY
+    }
+
+    /// Returns `true` if `data` can be dropped at `row` under `parent`.
+    ///
+    /// `row` is the insertion position among the children of `parent`; a
+    /// value equal to the row count appends. The default returns `false`.
+    fn can_drop_mime_data(
+        &self,
+        _data: &DragData,
+        _action: DropAction,
+        _row: usize,
+        _column: usize,
+        _parent: &ModelIndex,
+    ) -> bool {
+        false
+    }
+
+    /// Handles `data` dropped at `row` under `parent`.
+    ///
+    /// Returns `true` if the data was inserted. For a move within the same
+    /// model, the model moves the dragged items itself, so views don't remove
+    /// anything afterwards. The default returns `false`.
+    fn drop_mime_data(
+        &self,
+        _data: &DragData,
+        _action: DropAction,
+        _row: usize,
+        _column: usize,
+        _parent: &ModelIndex,
+    ) -> bool {
+        false
+    }
+
     // -------------------------------------------------------------------------
     // Convenience methods
     // -------------------------------------------------------------------------
@@ -7,7 +7,10 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicU64, Ordering};
 
+use crate::widget::drag_drop::{DragData, DropAction};
+
 use super::index::ModelIndex;
+use super::mime::DraggedItems;
 use super::role::{ItemData, ItemRole};
 use super::traits::{ItemFlags, ItemModel, ModelSignals};
 
@@ -47,6 +50,17 @@ pub trait TreeNodeData: Send + Sync {
     fn flags(&self) -> ItemFlags {
         ItemFlags::new()
     }
+
+    /// Creates a node from one line of dropped text.
+    ///
+    /// `TreeModel` uses this to accept nodes copied from other views. The
+    /// default returns `None`, so only moves within the same model are accepted.
+    fn from_text(_text: &str) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
 }
 
 /// Implement TreeNodeData for String for convenience.
@@ -54,6 +68,10 @@ impl TreeNodeData for String {
     fn display(&self) -> ItemData {
         ItemData::from(self.as_str())
     }
+
+    fn from_text(text: &str) -> Option<Self> {
+        Some(text.to_string())
+    }
 }
 
 /// A node in the tree structure.
@@ -119,6 +137,50 @@ impl<T> TreeStorage<T> {
         }
     }
 
+    fn insert_node(&mut self, parent_id: Option<NodeId>, row: usize, data: T) -> Option<NodeId> {
+        let node = TreeNode::new(data, parent_id);
+        let id = node.id;
+        let siblings = self.children_of_mut(parent_id)?;
+        siblings.insert(row.min(siblings.len()), id);
+        self.nodes.insert(id, node);
+        Some(id)
+    }
+
+    /// Detaches a node from its parent and inserts it at `row` under `parent_id`.
+    ///
+    /// `row` is a position among the new siblings after the node is detached.
+    fn move_node(&mut self, id: NodeId, parent_id: Option<NodeId>, row: usize) {
+        let old_parent = self.parent_of(id);
+        if let Some(siblings) = self.children_of_mut(old_parent) {
+            siblings.retain(|&child_id| child_id != id);
+        }
+        if let Some(siblings) = self.children_of_mut(parent_id) {
+            siblings.insert(row.min(siblings.len()), id);
+        }
+        if let Some(node) = self.nodes.get_mut(&id) {
+            node.parent = parent_id;
+        }
+    }
+
+    fn children_of_mut(&mut self, parent_id: Option<NodeId>) -> Option<&mut Vec<NodeId>> {
+        match parent_id {
+            None => Some(&mut self.root_children),
+            Some(id) => self.nodes.get_mut(&id).map(|n| &mut n.children),
+        }
+    }
+
+    /// Returns `true` if `id` is `ancestor` or one of its descendants.
+    fn is_within(&self, id: NodeId, ancestor: NodeId) -> bool {
+        let mut current = Some(id);
+        while let Some(node_id) = current {
+            if node_id == ancestor {
+                return true;
+            }
+            current = self.parent_of(node_id);
+        }
+        false
+    }
+
     fn remove_node(&mut self, id: NodeId) -> Option<T> {
         // First, remove from parent's children list
         if let Some(node) = self.nodes.get(&id) {
@@ -260,43 +322,111 @@ impl<T: Send + Sync + 'static> TreeModel<T> {
 
     /// Adds a root-level node and returns its ID.
     pub fn add_root(&self, data: T) -> NodeId {
-        let id;
-        let row;
-        {
-            let mut storage = self.storage.write();
-            row = storage.root_children.len();
-            id = storage.add_root(data);
-        }
+        let row = self.storage.read().root_children.len();
+        let mut id = None;
         self.signals
-            .rows_inserted
-            .emit((ModelIndex::invalid(), row, row));
-        id
+            .emit_rows_inserted(ModelIndex::invalid(), row, row, || {
+                id = Some(self.storage.write().add_root(data));
+            });
+        id.unwrap()
     }
 
     /// Adds a child node to the specified parent and returns its ID.
     ///
     /// Returns `None` if the parent doesn't exist.
     pub fn add_child(&self, parent_id: NodeId, data: T) -> Option<NodeId> {
-        let id;
-        let row;
-        let parent_index;
-        {
-            let mut storage = self.storage.write();
+        let (row, parent_index) = {
+            let storage = self.storage.read();
+            let row = storage.nodes.get(&parent_id)?.children.len();
+            (row, self.create_index_for_id(&storage, parent_id)?)
+        };
+        let mut id = None;
+        self.signals.emit_rows_inserted(parent_index, row, row, || {
+            id = self.storage.write().add_child(parent_id, data);
+        });
+        id
+    }
 
-            // Get the parent's row before adding
-            row = storage
-                .nodes
-                .get(&parent_id)
-                .map(|n| n.children.len())
-                .unwrap_or(0);
+    /// Inserts a node at `row` under `parent_id` (or at the root level for
+    /// `None`) and returns its ID.
+    ///
+    /// Rows past the end append. Returns `None` if the parent doesn't exist.
+    pub fn insert_child(&self, parent_id: Option<NodeId>, row: usize, data: T) -> Option<NodeId> {
+        let (row, parent_index) = {
+            let storage = self.storage.read();
+            let parent_index = match parent_id {
+                Some(pid) => self.create_index_for_id(&storage, pid)?,
+                None => ModelIndex::invalid(),
+            };
+            (row.min(storage.child_count(parent_id)), parent_index)
+        };
+        let mut id = None;
+        self.signals.emit_rows_inserted(parent_index, row, row, || {
+            id = self.storage.write().insert_node(parent_id, row, data);
+        });
+        id
+    }
 
-            // Create parent index
-            parent_index = self.create_index_for_id(&storage, parent_id)?;
+    /// Moves a node and its subtree to `row` under `parent_id` (or to the
+    /// root level for `None`).
+    ///
+    /// `row` is a position among the new parent's children before the move,
+    /// as for a drop between two rows. Returns `false` if either node doesn't
+    /// exist or the new parent is inside the moved subtree.
+    pub fn move_node(&self, id: NodeId, parent_id: Option<NodeId>, row: usize) -> bool {
+        let (source_parent, source_row, destination_parent, destination_row) = {
+            let storage = self.storage.read();
+            let Some(source_row) = storage.row_of(id) else {
+                return false;
+            };
+            if parent_id
+                .is_some_and(|pid| !storage.nodes.contains_key(&pid) || storage.is_within(pid, id))
+            {
+                return false;
+            }
+            let index_of = |node_id: Option<NodeId>| match node_id {
+                Some(node_id) => self.create_index_for_id(&storage, node_id),
+                None => Some(ModelIndex::invalid()),
+            };
+            let (Some(source_parent), Some(destination_parent)) =
+                (index_of(storage.parent_of(id)), index_of(parent_id))
+            else {
+                return false;
+            };
+            let row = row.min(storage.child_count(parent_id));
 
-            id = storage.add_child(parent_id, data)?;
+            // Dropping a node next to itself leaves it in place.
+            if storage.parent_of(id) == parent_id && (row == source_row || row == source_row + 1) {
+                return true;
+            }
+            (source_parent, source_row, destination_parent, row)
+        };
+
+        self.signals.rows_about_to_be_moved.emit((
+            source_parent.clone(),
+            source_row,
+            source_row,
+            destination_parent.clone(),
+            destination_row,
+        ));
+        {
+            let mut storage = self.storage.write();
+            let same_parent = storage.parent_of(id) == parent_id;
+            let row = if same_parent && source_row < destination_row {
+                destination_row - 1
+            } else {
+                destination_row
+            };
+            storage.move_node(id, parent_id, row);
         }
-        self.signals.rows_inserted.emit((parent_index, row, row));
-        Some(id)
+        self.signals.rows_moved.emit((
+            source_parent,
+            source_row,
+            source_row,
+            destination_parent,
+            destination_row,
+        ));
+        true
     }
 
     /// Removes a node and all its descendants.
@@ -517,6 +647,92 @@ impl<T: TreeNodeData + 'static> ItemModel for TreeModel<T> {
     fn has_children(&self, parent: &ModelIndex) -> bool {
         self.row_count(parent) > 0
     }
+
+    fn supported_drop_actions(&self) -> DropAction {
+        DropAction::COPY | DropAction::MOVE
+    }
+
+    fn can_drop_mime_data(
+        &self,
+        data: &DragData,
+        action: DropAction,
+        _row: usize,
+        _column: usize,
+        parent: &ModelIndex,
+    ) -> bool {
+        let storage = self.storage.read();
+        let parent_id = self.parent_node_id(parent);
+        if parent_id.is_some_and(|pid| !storage.nodes.contains_key(&pid)) {
+            return false;
+        }
+
+        if action == DropAction::MOVE
+            && let Some(items) = DraggedItems::from_model(data, self)
+        {
+            // A node can't be moved into its own subtree.
+            return items.indexes().iter().all(|index| {
+                let id = index.internal_id();
+                storage.nodes.contains_key(&id)
+                    && parent_id.is_none_or(|pid| !storage.is_within(pid, id))
+            });
+        }
+        data.text().is_some_and(|text| {
+            !text.is_empty() && text.lines().all(|line| T::from_text(line).is_some())
+        })
+    }
+
+    fn drop_mime_data(
+        &self,
+        data: &DragData,
+        action: DropAction,
+        row: usize,
+        column: usize,
+        parent: &ModelIndex,
+    ) -> bool {
+        if !self.can_drop_mime_data(data, action, row, column, parent) {
+            return false;
+        }
+        let parent_id = self.parent_node_id(parent);
+
+        if action == DropAction::MOVE
+            && let Some(items) = DraggedItems::from_model(data, self)
+        {
+            // Move each dragged subtree once, keeping the moved nodes together.
+            let mut ids: Vec<NodeId> = Vec::new();
+            for index in items.indexes() {
+                if !ids.contains(&index.internal_id()) {
+                    ids.push(index.internal_id());
+                }
+            }
+            let ids: Vec<NodeId> = {
+                let storage = self.storage.read();
+                ids.iter()
+                    .copied()
+                    .filter(|&id| {
+                        !ids.iter().any(|&other| {
+                            other != id
+                                && storage
+                                    .parent_of(id)
+                                    .is_some_and(|p| storage.is_within(p, other))
+                        })
+                    })
+                    .collect()
+            };
+
+            let mut row = row;
+            for id in ids {
+                self.move_node(id, parent_id, row);
+                row = self.storage.read().row_of(id).map_or(row, |r| r + 1);
+            }
+            return true;
+        }
+
+        let text = data.text().unwrap_or_default();
+        for (offset, node) in text.lines().filter_map(T::from_text).enumerate() {
+            self.insert_child(parent_id, row + offset, node);
+        }
+        true
+    }
 }
 
 /// Extractor-based tree model for when T doesn't implement TreeNodeData.
@@ -850,4 +1066,67 @@ mod tests {
             Some("Value: 100")
         );
     }
+
+    #[test]
+    fn test_drop_mime_data() {
+        let model = TreeModel::<String>::new();
+        let src = model.add_root("src".into());
+        let main = model.add_child(src, "main.rs".into()).unwrap();
+        let docs = model.add_root("docs".into());
+        let root = ModelIndex::invalid();
+        let names = |parent: &ModelIndex| -> Vec<String> {
+            (0..model.row_count(parent))
+                .map(|row| model.display_text(&model.index(row, 0, parent)).unwrap())
+                .collect()
+        };
+
+        // Move "main.rs" under "docs".
+        let src_index = model.index(0, 0, &root);
+        let data = model.mime_data(&[model.index(0, 0, &src_index)]);
+        let docs_index = model.index(1, 0, &root);
+        assert!(model.drop_mime_data(&data, DropAction::MOVE, 0, 0, &docs_index));
+        assert_eq!(names(&src_index), Vec::<String>::new());
+        assert_eq!(names(&docs_index), ["main.rs"]);
+        assert_eq!(model.with_node(main, |n| n.clone()).unwrap(), "main.rs");
+
+        // A node can't be dropped into its own subtree.
+        let data = model.mime_data(&[model.index(1, 0, &root)]);
+        let main_index = model.index(0, 0, &docs_index);
+        assert!(!model.can_drop_mime_data(&data, DropAction::MOVE, 0, 0, &main_index));
+
+        // Reorder roots: move "docs" before "src".
+        assert!(model.drop_mime_data(&data, DropAction::MOVE, 0, 0, &root));
+        assert_eq!(names(&root), ["docs", "src"]);
+
+        // Copies from another model insert new nodes from the dragged text.
+        let other = TreeModel::<String>::new();
+        other.add_root("README.md".into());
+        let data = other.mime_data(&[other.index(0, 0, &root)]);
+        assert!(model.drop_mime_data(&data, DropAction::COPY, 1, 0, &root));
+        assert_eq!(names(&root), ["docs", "README.md", "src"]);
+        assert!(model.with_node(docs, |_| ()).is_some());
+    }
+
+    #[test]
+    fn test_model_tester() {
+        let node = |name: &str, is_dir| FileNode {
+            name: name.into(),
+            is_dir,
+        };
+        let model = Arc::new(TreeModel::new());
+        let tester = crate::model::ModelTester::new(model.clone());
+
+        let docs = model.add_root(node("docs", true));
+        let src = model.add_root(node("src", true));
+        let readme = model.add_child(docs, node("README.md", false)).unwrap();
+        model.add_child(src, node("main.rs", false)).unwrap();
+        model.insert_child(Some(src), 0, node("lib.rs", false));
+        model.insert_child(None, 1, node("build.rs", false));
+        model.move_node(readme, Some(src), 1);
+        model.move_node(docs, None, 3);
+        model.modify_node(src, |n| n.name = "source".into());
+        model.remove(src);
+        model.clear();
+        tester.check();
+    }
 }
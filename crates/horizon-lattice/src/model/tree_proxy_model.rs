@@ -0,0 +1,1044 @@
+//! Hierarchical proxy model for filtering and sorting trees.
+//!
+//! [`ProxyModel`](super::ProxyModel) keeps a single flat row mapping, so it
+//! flattens hierarchical sources. `TreeProxyModel` keeps one mapping per
+//! source parent, which preserves the tree structure, and follows the source
+//! model's change signals incrementally.
+
+use parking_lot::{Mutex, RwLock};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+use super::index::ModelIndex;
+use super::proxy_model::{CompareFn, FilterFn, compare_item_data};
+use super::role::{ItemData, ItemRole};
+use super::traits::{ItemFlags, ItemModel, ModelSignals, Orientation};
+
+/// Disconnects one slot from the source model's signals.
+type Disconnector = Box<dyn Fn(&ModelSignals) + Send + Sync>;
+
+/// Row mapping for the children of one source parent.
+#[derive(Default)]
+struct Mapping {
+    /// Mapping from proxy row index to source row index.
+    proxy_to_source: Vec<usize>,
+    /// Mapping from source row index to proxy row index (None if filtered out).
+    source_to_proxy: Vec<Option<usize>>,
+    /// Mappings for the children of visible rows, keyed by source row.
+    children: HashMap<usize, Mapping>,
+}
+
+impl Mapping {
+    /// Finds the mapping for the parent at the given source row path.
+    fn node(&self, path: &[usize]) -> Option<&Mapping> {
+        path.iter()
+            .try_fold(self, |mapping, row| mapping.children.get(row))
+    }
+
+    fn node_mut(&mut self, path: &[usize]) -> Option<&mut Mapping> {
+        path.iter()
+            .try_fold(self, |mapping, row| mapping.children.get_mut(row))
+    }
+
+    /// Rebuilds `source_to_proxy` from `proxy_to_source`.
+    fn reindex(&mut self) {
+        self.source_to_proxy.fill(None);
+        for (proxy_row, &source_row) in self.proxy_to_source.iter().enumerate() {
+            self.source_to_proxy[source_row] = Some(proxy_row);
+        }
+    }
+
+    /// Makes room for `count` source rows inserted at `first`.
+    fn insert_source_rows(&mut self, first: usize, count: usize) {
+        let first = first.min(self.source_to_proxy.len());
+        self.source_to_proxy
+            .splice(first..first, std::iter::repeat_n(None, count));
+        for row in &mut self.proxy_to_source {
+            if *row >= first {
+                *row += count;
+            }
+        }
+        self.children = std::mem::take(&mut self.children)
+            .into_iter()
+            .map(|(row, mapping)| (if row >= first { row + count } else { row }, mapping))
+            .collect();
+        self.reindex();
+    }
+
+    /// Drops source rows `first..=last`, which must already be hidden.
+    fn remove_source_rows(&mut self, first: usize, last: usize) {
+        let last = last.min(self.source_to_proxy.len().saturating_sub(1));
+        if first > last {
+            return;
+        }
+        let count = last - first + 1;
+        self.source_to_proxy.drain(first..=last);
+        self.proxy_to_source
+            .retain(|row| !(first..=last).contains(row));
+        for row in &mut self.proxy_to_source {
+            if *row > last {
+                *row -= count;
+            }
+        }
+        self.children = std::mem::take(&mut self.children)
+            .into_iter()
+            .filter(|(row, _)| !(first..=last).contains(row))
+            .map(|(row, mapping)| (if row > last { row - count } else { row }, mapping))
+            .collect();
+        self.reindex();
+    }
+}
+
+/// Returns the source rows leading from the root to `index`.
+pub(super) fn row_path(index: &ModelIndex) -> Vec<usize> {
+    if !index.is_valid() {
+        return Vec::new();
+    }
+    let mut path: Vec<usize> = index.ancestors().iter().rev().map(|a| a.row()).collect();
+    path.push(index.row());
+    path
+}
+
+/// Groups sorted rows into contiguous `(first, last)` ranges.
+fn contiguous_ranges(rows: &[usize]) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &row in rows {
+        match ranges.last_mut() {
+            Some((_, last)) if *last + 1 == row => *last = row,
+            _ => ranges.push((row, row)),
+        }
+    }
+    ranges
+}
+
+/// A proxy model that filters and sorts a hierarchical source model.
+///
+/// Unlike [`ProxyModel`](super::ProxyModel), `TreeProxyModel` keeps the
+/// source's parent/child structure: the filter is applied to every level,
+/// and siblings are sorted within their parent. A row whose parent is
+/// filtered out is hidden along with it, unless recursive filtering is
+/// enabled:
+///
+/// - With [`set_recursive_filtering_enabled`](Self::set_recursive_filtering_enabled),
+///   a row is kept when any of its descendants passes the filter, so matches
+///   stay reachable through their ancestors.
+/// - With [`set_auto_accept_child_rows`](Self::set_auto_accept_child_rows),
+///   all descendants of a row that passes the filter are kept.
+///
+/// The proxy listens to the source model's signals and translates insertions,
+/// removals and data changes into the corresponding proxy row insertions and
+/// removals, so views keep their state instead of being reset. Changing the
+/// filter does the same; changing the sort order emits a layout change.
+///
+/// # Example
+///
+/// ```ignore
+/// use horizon_lattice::model::{ItemModel, TreeModel, TreeProxyModel};
+///
+/// let tree = Arc::new(TreeModel::<String>::new());
+/// let src = tree.add_root("src".to_string());
+/// tree.add_child(src, "main.rs".to_string());
+/// tree.add_root("README.md".to_string());
+///
+/// let proxy = TreeProxyModel::new(tree.clone());
+/// proxy.set_recursive_filtering_enabled(true);
+/// proxy.set_filter(|model, row, parent| {
+///     let index = model.index(row, 0, parent);
+///     model.display_text(&index).is_some_and(|name| name.ends_with(".rs"))
+/// });
+///
+/// // "src" is kept because "main.rs" matches.
+/// assert_eq!(proxy.row_count(&ModelIndex::invalid()), 1);
+/// ```
+pub struct TreeProxyModel<S: ItemModel> {
+    source: Arc<S>,
+    filter: RwLock<Option<FilterFn<S>>>,
+    compare: RwLock<Option<CompareFn<S>>>,
+    /// Column used for sorting (for simple sort comparators).
+    sort_column: RwLock<Option<usize>>,
+    /// Whether sort is descending.
+    sort_descending: RwLock<bool>,
+    /// Keep ancestors of rows that pass the filter.
+    recursive_filtering: RwLock<bool>,
+    /// Keep descendants of rows that pass the filter.
+    auto_accept_child_rows: RwLock<bool>,
+    /// Mapping for the root level, holding the mappings of visible parents.
+    root: RwLock<Mapping>,
+    signals: ModelSignals,
+    /// Slots connected to the source model, disconnected on drop.
+    disconnectors: Mutex<Vec<Disconnector>>,
+}
+
+impl<S: ItemModel + 'static> TreeProxyModel<S> {
+    /// Creates a new proxy model wrapping the given source.
+    ///
+    /// The proxy is returned in an `Arc` because it connects to the source
+    /// model's signals; the connections hold only a weak reference.
+    pub fn new(source: Arc<S>) -> Arc<Self> {
+        let proxy = Arc::new(Self {
+            source,
+            filter: RwLock::new(None),
+            compare: RwLock::new(None),
+            sort_column: RwLock::new(None),
+            sort_descending: RwLock::new(false),
+            recursive_filtering: RwLock::new(false),
+            auto_accept_child_rows: RwLock::new(false),
+            root: RwLock::new(Mapping::default()),
+            signals: ModelSignals::new(),
+            disconnectors: Mutex::new(Vec::new()),
+        });
+        proxy.rebuild();
+        proxy.connect_source();
+        proxy
+    }
+
+    /// Sets the filter function.
+    ///
+    /// The filter function receives the source model, source row index, and
+    /// source parent index. Return `true` to include the row, `false` to
+    /// filter it out. Rows that are hidden or revealed by the new filter are
+    /// removed from or inserted into the proxy.
+    pub fn set_filter<F>(&self, filter: F)
+    where
+        F: Fn(&S, usize, &ModelIndex) -> bool + Send + Sync + 'static,
+    {
+        *self.filter.write() = Some(Arc::new(filter));
+        self.invalidate_filter();
+    }
+
+    /// Clears the filter, showing all rows from the source model.
+    pub fn clear_filter(&self) {
+        *self.filter.write() = None;
+        self.invalidate_filter();
+    }
+
+    /// Sets whether rows are kept when any of their descendants pass the filter.
+    pub fn set_recursive_filtering_enabled(&self, enabled: bool) {
+        *self.recursive_filtering.write() = enabled;
+        self.invalidate_filter();
+    }
+
+    /// Returns `true` if rows are kept when any of their descendants pass the filter.
+    pub fn is_recursive_filtering_enabled(&self) -> bool {
+        *self.recursive_filtering.read()
+    }
+
+    /// Sets whether all descendants of rows that pass the filter are kept.
+    pub fn set_auto_accept_child_rows(&self, enabled: bool) {
+        *self.auto_accept_child_rows.write() = enabled;
+        self.invalidate_filter();
+    }
+
+    /// Returns `true` if all descendants of rows that pass the filter are kept.
+    pub fn auto_accept_child_rows(&self) -> bool {
+        *self.auto_accept_child_rows.read()
+    }
+
+    /// Sets the sort comparator.
+    ///
+    /// The comparator receives the source model, two source row indices and
+    /// their source parent. Siblings are sorted within each parent.
+    pub fn set_sort<F>(&self, compare: F)
+    where
+        F: Fn(&S, usize, usize, &ModelIndex) -> Ordering + Send + Sync + 'static,
+    {
+        *self.compare.write() = Some(Arc::new(compare));
+        self.invalidate();
+    }
+
+    /// Clears the custom sort comparator.
+    ///
+    /// Note: This does not clear column-based sorting set via `sort_by_column`.
+    pub fn clear_custom_sort(&self) {
+        *self.compare.write() = None;
+        self.invalidate();
+    }
+
+    /// Sets simple column-based sorting.
+    ///
+    /// This sorts siblings by comparing the Display role of the specified column.
+    pub fn sort_by_column(&self, column: usize, descending: bool) {
+        *self.sort_column.write() = Some(column);
+        *self.sort_descending.write() = descending;
+        self.invalidate();
+    }
+
+    /// Clears column-based sorting.
+    pub fn clear_sort(&self) {
+        *self.sort_column.write() = None;
+        self.invalidate();
+    }
+
+    /// Forces a rebuild of the proxy mapping, emitting a layout change.
+    pub fn invalidate(&self) {
+        self.signals.emit_layout_changed(|| {
+            self.rebuild();
+        });
+    }
+
+    /// Re-applies the filter to every row.
+    ///
+    /// Call this when the filter's criteria change outside of `set_filter`,
+    /// for example when it captures shared state. Rows are removed and
+    /// inserted incrementally.
+    pub fn invalidate_filter(&self) {
+        self.refresh(&ModelIndex::invalid(), 0..=usize::MAX, true);
+    }
+
+    /// Returns a reference to the source model.
+    pub fn source(&self) -> &Arc<S> {
+        &self.source
+    }
+
+    /// Maps a proxy index to a source index.
+    pub fn map_to_source(&self, proxy_index: &ModelIndex) -> ModelIndex {
+        match self.source_path(proxy_index) {
+            Some(path) if !path.is_empty() => self.source_index(&path, proxy_index.column()),
+            _ => ModelIndex::invalid(),
+        }
+    }
+
+    /// Maps a source index to a proxy index.
+    ///
+    /// Returns an invalid index if the source row or one of its ancestors is
+    /// filtered out.
+    pub fn map_from_source(&self, source_index: &ModelIndex) -> ModelIndex {
+        if !source_index.is_valid() {
+            return ModelIndex::invalid();
+        }
+
+        let mut chain = source_index.ancestors();
+        chain.reverse();
+        chain.push(source_index.clone());
+
+        let root = self.root.read();
+        let mut mapping = &*root;
+        let mut proxy_index = ModelIndex::invalid();
+        for (level, index) in chain.iter().enumerate() {
+            let Some(proxy_row) = mapping.source_to_proxy.get(index.row()).copied().flatten()
+            else {
+                return ModelIndex::invalid();
+            };
+            proxy_index = ModelIndex::with_internal_id(
+                proxy_row,
+                index.column(),
+                proxy_index,
+                index.internal_id(),
+            );
+            if level + 1 < chain.len() {
+                match mapping.children.get(&index.row()) {
+                    Some(child) => mapping = child,
+                    None => return ModelIndex::invalid(),
+                }
+            }
+        }
+        proxy_index
+    }
+
+    /// Resolves a proxy index to the source row path leading to it.
+    ///
+    /// Returns an empty path for the invalid (root) index.
+    fn source_path(&self, proxy_index: &ModelIndex) -> Option<Vec<usize>> {
+        if !proxy_index.is_valid() {
+            return Some(Vec::new());
+        }
+        let proxy_rows = row_path(proxy_index);
+
+        let root = self.root.read();
+        let mut mapping = &*root;
+        let mut path = Vec::with_capacity(proxy_rows.len());
+        for (level, &proxy_row) in proxy_rows.iter().enumerate() {
+            let source_row = *mapping.proxy_to_source.get(proxy_row)?;
+            path.push(source_row);
+            if level + 1 < proxy_rows.len() {
+                mapping = mapping.children.get(&source_row)?;
+            }
+        }
+        Some(path)
+    }
+
+    /// Builds the source index for a source row path.
+    fn source_index(&self, path: &[usize], column: usize) -> ModelIndex {
+        let mut index = ModelIndex::invalid();
+        for (level, &row) in path.iter().enumerate() {
+            let column = if level + 1 == path.len() { column } else { 0 };
+            index = self.source.index(row, column, &index);
+        }
+        index
+    }
+
+    // -------------------------------------------------------------------------
+    // Filtering and sorting
+    // -------------------------------------------------------------------------
+
+    /// Returns `true` if the row passes the filter function itself.
+    fn filter_accepts(&self, row: usize, source_parent: &ModelIndex) -> bool {
+        let filter = self.filter.read().clone();
+        filter.is_none_or(|filter| filter(&self.source, row, source_parent))
+    }
+
+    /// Returns `true` if the row should be visible.
+    ///
+    /// `ancestor_matched` is whether an ancestor of the row passes the filter.
+    fn accepts_row(&self, row: usize, source_parent: &ModelIndex, ancestor_matched: bool) -> bool {
+        if (ancestor_matched && self.auto_accept_child_rows())
+            || self.filter_accepts(row, source_parent)
+        {
+            return true;
+        }
+        self.is_recursive_filtering_enabled()
+            && self.has_matching_descendant(&self.source.index(row, 0, source_parent))
+    }
+
+    fn has_matching_descendant(&self, source_index: &ModelIndex) -> bool {
+        (0..self.source.row_count(source_index)).any(|row| {
+            self.filter_accepts(row, source_index)
+                || self.has_matching_descendant(&self.source.index(row, 0, source_index))
+        })
+    }
+
+    /// Returns `true` if children of `source_parent` are kept because the
+    /// parent or one of its ancestors passes the filter.
+    fn ancestor_matches(&self, source_parent: &ModelIndex) -> bool {
+        if !self.auto_accept_child_rows() {
+            return false;
+        }
+        let mut current = source_parent.clone();
+        while current.is_valid() {
+            let parent = current.parent();
+            if self.filter_accepts(current.row(), &parent) {
+                return true;
+            }
+            current = parent;
+        }
+        false
+    }
+
+    /// Compares two sibling source rows, falling back to source order.
+    fn compare_rows(&self, a: usize, b: usize, source_parent: &ModelIndex) -> Ordering {
+        let compare = self.compare.read().clone();
+        let ordering = if let Some(compare) = compare {
+            compare(&self.source, a, b, source_parent)
+        } else if let Some(column) = *self.sort_column.read() {
+            let index_a = self.source.index(a, column, source_parent);
+            let index_b = self.source.index(b, column, source_parent);
+            let data_a = self.source.data(&index_a, ItemRole::Display);
+            let data_b = self.source.data(&index_b, ItemRole::Display);
+            let cmp = compare_item_data(&data_a, &data_b);
+            if *self.sort_descending.read() {
+                cmp.reverse()
+            } else {
+                cmp
+            }
+        } else {
+            Ordering::Equal
+        };
+        ordering.then(a.cmp(&b))
+    }
+
+    /// Builds the mapping for the children of `source_parent` and all visible descendants.
+    fn build(&self, source_parent: &ModelIndex, ancestor_matched: bool) -> Mapping {
+        let count = self.source.row_count(source_parent);
+        let mut rows: Vec<usize> = (0..count)
+            .filter(|&row| self.accepts_row(row, source_parent, ancestor_matched))
+            .collect();
+        rows.sort_by(|&a, &b| self.compare_rows(a, b, source_parent));
+
+        let children = rows
+            .iter()
+            .map(|&row| (row, self.build_child(row, source_parent, ancestor_matched)))
+            .collect();
+        let mut mapping = Mapping {
+            proxy_to_source: rows,
+            source_to_proxy: vec![None; count],
+            children,
+        };
+        mapping.reindex();
+        mapping
+    }
+
+    fn build_child(
+        &self,
+        row: usize,
+        source_parent: &ModelIndex,
+        ancestor_matched: bool,
+    ) -> Mapping {
+        let matched = self.auto_accept_child_rows()
+            && (ancestor_matched || self.filter_accepts(row, source_parent));
+        self.build(&self.source.index(row, 0, source_parent), matched)
+    }
+
+    fn rebuild(&self) {
+        let mapping = self.build(&ModelIndex::invalid(), false);
+        *self.root.write() = mapping;
+    }
+
+    /// Re-applies the filter to the given children of `source_parent`.
+    ///
+    /// Rows that no longer pass are removed, rows that now pass are inserted
+    /// at their sorted position, and a layout change is emitted if retained
+    /// siblings need reordering. With `deep`, the retained rows' descendants
+    /// are refreshed as well.
+    fn refresh(&self, source_parent: &ModelIndex, rows: RangeInclusive<usize>, deep: bool) {
+        let path = row_path(source_parent);
+        let proxy_parent = self.map_from_source(source_parent);
+        if source_parent.is_valid() && !proxy_parent.is_valid() {
+            return;
+        }
+        let Some(current) = self
+            .root
+            .read()
+            .node(&path)
+            .map(|m| m.proxy_to_source.clone())
+        else {
+            return;
+        };
+
+        let count = self.source.row_count(source_parent);
+        let rows = *rows.start()..rows.end().saturating_add(1).min(count);
+        let ancestor_matched = self.ancestor_matches(source_parent);
+        let accepted: Vec<(usize, bool)> = rows
+            .clone()
+            .map(|row| (row, self.accepts_row(row, source_parent, ancestor_matched)))
+            .collect();
+        let is_accepted = |row: usize| rows.contains(&row) && accepted[row - rows.start].1;
+
+        // Remove the last ranges first so earlier proxy rows stay valid.
+        let removed: Vec<usize> = current
+            .iter()
+            .enumerate()
+            .filter(|&(_, &row)| rows.contains(&row) && !is_accepted(row))
+            .map(|(proxy_row, _)| proxy_row)
+            .collect();
+        for &(first, last) in contiguous_ranges(&removed).iter().rev() {
+            self.remove_proxy_rows(&path, &proxy_parent, first, last);
+        }
+
+        self.resort(&path, source_parent);
+
+        let visible = self
+            .root
+            .read()
+            .node(&path)
+            .map(|m| m.source_to_proxy.clone())
+            .unwrap_or_default();
+        for &(row, ok) in &accepted {
+            if ok && visible.get(row).copied().flatten().is_none() {
+                self.insert_proxy_row(&path, &proxy_parent, source_parent, row, ancestor_matched);
+            }
+        }
+
+        if deep {
+            for row in current {
+                if is_accepted(row) {
+                    let index = self.source.index(row, 0, source_parent);
+                    self.refresh(&index, 0..=usize::MAX, true);
+                }
+            }
+        }
+    }
+
+    /// Refreshes each ancestor of `source_parent` whose visibility may
+    /// depend on its descendants.
+    fn refresh_ancestors(&self, source_parent: &ModelIndex) {
+        if !self.is_recursive_filtering_enabled() {
+            return;
+        }
+        let mut current = source_parent.clone();
+        while current.is_valid() {
+            let parent = current.parent();
+            self.refresh(&parent, current.row()..=current.row(), false);
+            current = parent;
+        }
+    }
+
+    /// Re-sorts the children of a parent if their order changed.
+    fn resort(&self, path: &[usize], source_parent: &ModelIndex) {
+        let Some(rows) = self
+            .root
+            .read()
+            .node(path)
+            .map(|m| m.proxy_to_source.clone())
+        else {
+            return;
+        };
+        if rows.is_sorted_by(|&a, &b| self.compare_rows(a, b, source_parent) != Ordering::Greater) {
+            return;
+        }
+        let mut sorted = rows;
+        sorted.sort_by(|&a, &b| self.compare_rows(a, b, source_parent));
+        self.signals.emit_layout_changed(|| {
+            if let Some(mapping) = self.root.write().node_mut(path) {
+                mapping.proxy_to_source = sorted;
+                mapping.reindex();
+            }
+        });
+    }
+
+    fn insert_proxy_row(
+        &self,
+        path: &[usize],
+        proxy_parent: &ModelIndex,
+        source_parent: &ModelIndex,
+        row: usize,
+        ancestor_matched: bool,
+    ) {
+        let position = {
+            let root = self.root.read();
+            let Some(mapping) = root.node(path) else {
+                return;
+            };
+            mapping.proxy_to_source.partition_point(|&other| {
+                self.compare_rows(other, row, source_parent) == Ordering::Less
+            })
+        };
+        let children = self.build_child(row, source_parent, ancestor_matched);
+        self.signals
+            .emit_rows_inserted(proxy_parent.clone(), position, position, || {
+                if let Some(mapping) = self.root.write().node_mut(path) {
+                    mapping.proxy_to_source.insert(position, row);
+                    mapping.children.insert(row, children);
+                    mapping.reindex();
+                }
+            });
+    }
+
+    fn remove_proxy_rows(
+        &self,
+        path: &[usize],
+        proxy_parent: &ModelIndex,
+        first: usize,
+        last: usize,
+    ) {
+        self.signals
+            .emit_rows_removed(proxy_parent.clone(), first, last, || {
+                if let Some(mapping) = self.root.write().node_mut(path) {
+                    let removed: Vec<usize> = mapping.proxy_to_source.drain(first..=last).collect();
+                    for row in removed {
+                        mapping.children.remove(&row);
+                    }
+                    mapping.reindex();
+                }
+            });
+    }
+
+    // -------------------------------------------------------------------------
+    // Source model signals
+    // -------------------------------------------------------------------------
+
+    fn connect_source(self: &Arc<Self>) {
+        let signals = self.source.signals();
+        let mut disconnectors = self.disconnectors.lock();
+
+        macro_rules! forward {
+            ($signal:ident => $handler:ident) => {{
+                let proxy = Arc::downgrade(self);
+                let id = signals.$signal.connect(move |args| {
+                    if let Some(proxy) = proxy.upgrade() {
+                        proxy.$handler(args);
+                    }
+                });
+                disconnectors.push(Box::new(move |signals: &ModelSignals| {
+                    signals.$signal.disconnect(id);
+                }));
+            }};
+        }
+
+        forward!(rows_inserted => source_rows_inserted);
+        forward!(rows_about_to_be_removed => source_rows_about_to_be_removed);
+        forward!(rows_removed => source_rows_removed);
+        forward!(rows_moved => source_rows_moved);
+        forward!(columns_inserted => source_columns_changed);
+        forward!(columns_removed => source_columns_changed);
+        forward!(data_changed => source_data_changed);
+        forward!(header_data_changed => source_header_data_changed);
+        forward!(layout_about_to_change => source_layout_about_to_change);
+        forward!(layout_changed => source_layout_changed);
+        forward!(model_about_to_reset => source_model_about_to_reset);
+        forward!(model_reset => source_model_reset);
+    }
+
+    fn source_rows_inserted(&self, (parent, first, last): &(ModelIndex, usize, usize)) {
+        let path = row_path(parent);
+        let mapped = self
+            .root
+            .write()
+            .node_mut(&path)
+            .map(|mapping| mapping.insert_source_rows(*first, last - first + 1))
+            .is_some();
+        if mapped {
+            self.refresh(parent, *first..=*last, false);
+        }
+        self.refresh_ancestors(parent);
+    }
+
+    fn source_rows_about_to_be_removed(&self, (parent, first, last): &(ModelIndex, usize, usize)) {
+        let path = row_path(parent);
+        let proxy_parent = self.map_from_source(parent);
+        if parent.is_valid() && !proxy_parent.is_valid() {
+            return;
+        }
+        let mut proxy_rows: Vec<usize> = match self.root.read().node(&path) {
+            Some(mapping) => (*first..=*last)
+                .filter_map(|row| mapping.source_to_proxy.get(row).copied().flatten())
+                .collect(),
+            None => return,
+        };
+        proxy_rows.sort_unstable();
+        for &(first, last) in contiguous_ranges(&proxy_rows).iter().rev() {
+            self.remove_proxy_rows(&path, &proxy_parent, first, last);
+        }
+    }
+
+    fn source_rows_removed(&self, (parent, first, last): &(ModelIndex, usize, usize)) {
+        if let Some(mapping) = self.root.write().node_mut(&row_path(parent)) {
+            mapping.remove_source_rows(*first, *last);
+        }
+        self.refresh_ancestors(parent);
+    }
+
+    fn source_rows_moved(&self, _: &(ModelIndex, usize, usize, ModelIndex, usize)) {
+        self.invalidate();
+    }
+
+    fn source_columns_changed(&self, _: &(ModelIndex, usize, usize)) {
+        self.signals.emit_reset(|| self.rebuild());
+    }
+
+    fn source_data_changed(
+        &self,
+        (top_left, bottom_right, roles): &(ModelIndex, ModelIndex, Vec<ItemRole>),
+    ) {
+        if !top_left.is_valid() {
+            return;
+        }
+        let parent = top_left.parent();
+        let rows = top_left.row()..=bottom_right.row();
+
+        // A changed row may now pass or fail the filter, move within its
+        // siblings, or (with auto-accepted children) change its subtree.
+        self.refresh(&parent, rows.clone(), self.auto_accept_child_rows());
+        self.refresh_ancestors(&parent);
+
+        for row in rows {
+            let first = self.map_from_source(&self.source.index(row, top_left.column(), &parent));
+            let last =
+                self.map_from_source(&self.source.index(row, bottom_right.column(), &parent));
+            if first.is_valid() && last.is_valid() {
+                self.signals.data_changed.emit((first, last, roles.clone()));
+            }
+        }
+    }
+
+    fn source_header_data_changed(&self, args: &(Orientation, usize, usize)) {
+        self.signals.header_data_changed.emit(*args);
+    }
+
+    fn source_layout_about_to_change(&self, _: &()) {
+        self.signals.layout_about_to_change.emit(());
+    }
+
+    fn source_layout_changed(&self, _: &()) {
+        self.rebuild();
+        self.signals.layout_changed.emit(());
+    }
+
+    fn source_model_about_to_reset(&self, _: &()) {
+        self.signals.model_about_to_reset.emit(());
+    }
+
+    fn source_model_reset(&self, _: &()) {
+        self.rebuild();
+        self.signals.model_reset.emit(());
+    }
+}
+
+impl<S: ItemModel> Drop for TreeProxyModel<S> {
+    fn drop(&mut self) {
+        let signals = self.source.signals();
+        for disconnect in self.disconnectors.get_mut().drain(..) {
+            disconnect(signals);
+        }
+    }
+}
+
+impl<S: ItemModel + 'static> ItemModel for TreeProxyModel<S> {
+    fn row_count(&self, parent: &ModelIndex) -> usize {
+        if parent.is_valid() && parent.column() > 0 {
+            return 0;
+        }
+        let Some(path) = self.source_path(parent) else {
+            return 0;
+        };
+        self.root
+            .read()
+            .node(&path)
+            .map_or(0, |mapping| mapping.proxy_to_source.len())
+    }
+
+    fn column_count(&self, parent: &ModelIndex) -> usize {
+        self.source.column_count(&self.map_to_source(parent))
+    }
+
+    fn data(&self, index: &ModelIndex, role: ItemRole) -> ItemData {
+        let source_index = self.map_to_source(index);
+        self.source.data(&source_index, role)
+    }
+
+    fn index(&self, row: usize, column: usize, parent: &ModelIndex) -> ModelIndex {
+        if parent.is_valid() && parent.column() > 0 {
+            return ModelIndex::invalid();
+        }
+        let Some(path) = self.source_path(parent) else {
+            return ModelIndex::invalid();
+        };
+        let Some(source_row) = self
+            .root
+            .read()
+            .node(&path)
+            .and_then(|mapping| mapping.proxy_to_source.get(row).copied())
+        else {
+            return ModelIndex::invalid();
+        };
+
+        let source_parent = self.source_index(&path, 0);
+        if column >= self.source.column_count(&source_parent) {
+            return ModelIndex::invalid();
+        }
+        let source_index = self.source.index(source_row, column, &source_parent);
+        ModelIndex::with_internal_id(row, column, parent.clone(), source_index.internal_id())
+    }
+
+    fn parent(&self, index: &ModelIndex) -> ModelIndex {
+        index.parent()
+    }
+
+    fn signals(&self) -> &ModelSignals {
+        &self.signals
+    }
+
+    fn set_data(&self, index: &ModelIndex, value: ItemData, role: ItemRole) -> bool {
+        let source_index = self.map_to_source(index);
+        self.source.set_data(&source_index, value, role)
+    }
+
+    fn flags(&self, index: &ModelIndex) -> ItemFlags {
+        let source_index = self.map_to_source(index);
+        self.source.flags(&source_index)
+    }
+
+    fn has_children(&self, parent: &ModelIndex) -> bool {
+        self.row_count(parent) > 0
+    }
+
+    fn header_data(&self, section: usize, orientation: Orientation, role: ItemRole) -> ItemData {
+        self.source.header_data(section, orientation, role)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::tree_model::TreeModel;
+
+    /// Builds:
+    ///
+    /// ```text
+    /// src
+    /// ├── main.rs
+    /// └── lib.rs
+    /// docs
+    /// └── guide.md
+    /// ```
+    fn create_test_tree() -> Arc<TreeModel<String>> {
+        let tree = Arc::new(TreeModel::new());
+        let src = tree.add_root("src".to_string());
+        tree.add_child(src, "main.rs".to_string());
+        tree.add_child(src, "lib.rs".to_string());
+        let docs = tree.add_root("docs".to_string());
+        tree.add_child(docs, "guide.md".to_string());
+        tree
+    }
+
+    fn name<M: ItemModel>(model: &M, row: usize, parent: &ModelIndex) -> String {
+        model
+            .display_text(&model.index(row, 0, parent))
+            .unwrap_or_default()
+    }
+
+    fn name_contains(
+        text: &'static str,
+    ) -> impl Fn(&TreeModel<String>, usize, &ModelIndex) -> bool {
+        move |model, row, parent| name(model, row, parent).contains(text)
+    }
+
+    /// Lists the proxy's rows depth-first as `name` or `parent/name`.
+    fn rows<M: ItemModel>(model: &M) -> Vec<String> {
+        fn walk<M: ItemModel>(model: &M, parent: &ModelIndex, prefix: &str, out: &mut Vec<String>) {
+            for row in 0..model.row_count(parent) {
+                let index = model.index(row, 0, parent);
+                let path = format!("{prefix}{}", name(model, row, parent));
+                out.push(path.clone());
+                walk(model, &index, &format!("{path}/"), out);
+            }
+        }
+        let mut out = Vec::new();
+        walk(model, &ModelIndex::invalid(), "", &mut out);
+        out
+    }
+
+    #[test]
+    fn test_filter_keeps_hierarchy() {
+        let tree = create_test_tree();
+        let proxy = TreeProxyModel::new(tree.clone());
+        assert_eq!(
+            rows(&*proxy),
+            ["src", "src/main.rs", "src/lib.rs", "docs", "docs/guide.md"]
+        );
+
+        proxy.set_filter(|model, row, parent| name(model, row, parent) != "main.rs");
+        assert_eq!(
+            rows(&*proxy),
+            ["src", "src/lib.rs", "docs", "docs/guide.md"]
+        );
+
+        let src = proxy.index(0, 0, &ModelIndex::invalid());
+        let lib = proxy.index(0, 0, &src);
+        assert_eq!(proxy.parent(&lib), src);
+        assert_eq!(
+            tree.display_text(&proxy.map_to_source(&lib)).unwrap(),
+            "lib.rs"
+        );
+        assert_eq!(proxy.map_to_source(&lib).row(), 1);
+
+        let source_lib = tree.index(1, 0, &tree.index(0, 0, &ModelIndex::invalid()));
+        assert_eq!(proxy.map_from_source(&source_lib), lib);
+        let source_main = tree.index(0, 0, &tree.index(0, 0, &ModelIndex::invalid()));
+        assert!(!proxy.map_from_source(&source_main).is_valid());
+
+        // Hiding a parent hides its subtree.
+        proxy.set_filter(|model, row, parent| name(model, row, parent) != "docs");
+        assert_eq!(rows(&*proxy), ["src", "src/main.rs", "src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_recursive_filtering() {
+        let tree = create_test_tree();
+        let proxy = TreeProxyModel::new(tree);
+        proxy.set_filter(name_contains("lib"));
+        assert!(rows(&*proxy).is_empty());
+
+        proxy.set_recursive_filtering_enabled(true);
+        assert_eq!(rows(&*proxy), ["src", "src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_auto_accept_child_rows() {
+        let tree = create_test_tree();
+        let proxy = TreeProxyModel::new(tree);
+        proxy.set_filter(name_contains("src"));
+        assert_eq!(rows(&*proxy), ["src"]);
+
+        proxy.set_auto_accept_child_rows(true);
+        assert_eq!(rows(&*proxy), ["src", "src/main.rs", "src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_sort_per_level() {
+        let tree = create_test_tree();
+        let proxy = TreeProxyModel::new(tree.clone());
+        proxy.sort_by_column(0, false);
+        assert_eq!(
+            rows(&*proxy),
+            ["docs", "docs/guide.md", "src", "src/lib.rs", "src/main.rs"]
+        );
+
+        proxy.sort_by_column(0, true);
+        assert_eq!(
+            rows(&*proxy),
+            ["src", "src/main.rs", "src/lib.rs", "docs", "docs/guide.md"]
+        );
+
+        // New rows are inserted at their sorted position.
+        let src = tree.index(0, 0, &ModelIndex::invalid()).internal_id();
+        tree.add_child(src, "mod.rs".to_string());
+        assert_eq!(
+            rows(&*proxy),
+            [
+                "src",
+                "src/mod.rs",
+                "src/main.rs",
+                "src/lib.rs",
+                "docs",
+                "docs/guide.md"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_source_changes_are_forwarded_incrementally() {
+        let tree = create_test_tree();
+        let proxy = TreeProxyModel::new(tree.clone());
+        proxy.set_recursive_filtering_enabled(true);
+        proxy.set_filter(name_contains("lib"));
+        assert_eq!(rows(&*proxy), ["src", "src/lib.rs"]);
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let log = events.clone();
+        proxy
+            .signals()
+            .rows_inserted
+            .connect(move |(parent, first, last)| {
+                log.lock()
+                    .push(("inserted", parent.is_valid(), *first, *last));
+            });
+        let log = events.clone();
+        proxy
+            .signals()
+            .rows_removed
+            .connect(move |(parent, first, last)| {
+                log.lock()
+                    .push(("removed", parent.is_valid(), *first, *last));
+            });
+        let log = events.clone();
+        proxy
+            .signals()
+            .model_reset
+            .connect(move |_| log.lock().push(("reset", false, 0, 0)));
+
+        // A match under a hidden parent reveals the parent.
+        let docs = tree.index(1, 0, &ModelIndex::invalid()).internal_id();
+        let libs = tree.add_child(docs, "libraries.md".to_string()).unwrap();
+        assert_eq!(
+            rows(&*proxy),
+            ["src", "src/lib.rs", "docs", "docs/libraries.md"]
+        );
+
+        // Renaming the only match hides it and its parent again.
+        tree.modify_node(libs, |name| *name = "links.md".to_string());
+        assert_eq!(rows(&*proxy), ["src", "src/lib.rs"]);
+
+        // Removing a hidden row shifts the mapping of later siblings.
+        let src = tree.index(0, 0, &ModelIndex::invalid());
+        tree.remove(tree.index(0, 0, &src).internal_id());
+        assert_eq!(rows(&*proxy), ["src", "src/lib.rs"]);
+        let lib = proxy.index(0, 0, &proxy.index(0, 0, &ModelIndex::invalid()));
+        assert_eq!(proxy.map_to_source(&lib).row(), 0);
+
+        tree.remove(tree.index(0, 0, &src).internal_id());
+        assert!(rows(&*proxy).is_empty());
+
+        assert_eq!(
+            *events.lock(),
+            [
+                ("inserted", false, 1, 1),
+                ("removed", true, 0, 0),
+                ("removed", false, 1, 1),
+                ("removed", true, 0, 0),
+                ("removed", false, 0, 0),
+            ]
+        );
+    }
+}
@@ -72,6 +72,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
 
 use horizon_lattice_core::Signal;
+use horizon_lattice_style::prelude::{ColorSchemePreference, ContrastPreference};
 
 use super::HighContrast;
 
@@ -172,6 +173,25 @@ impl ColorScheme {
     pub fn is_light(&self) -> bool {
         matches!(self, ColorScheme::Light)
     }
+
+    /// Convert to the preference matched by `@media (prefers-color-scheme)`
+    /// rules, or `None` if the scheme is unknown.
+    pub fn to_media_preference(&self) -> Option<ColorSchemePreference> {
+        match self {
+            ColorScheme::Light => Some(ColorSchemePreference::Light),
+            ColorScheme::Dark => Some(ColorSchemePreference::Dark),
+            ColorScheme::Unknown => None,
+        }
+    }
+}
+
+/// The preference matched by `@media (prefers-contrast)` rules.
+fn contrast_preference(high_contrast: bool) -> ContrastPreference {
+    if high_contrast {
+        ContrastPreference::More
+    } else {
+        ContrastPreference::NoPreference
+    }
 }
 
 // ============================================================================
@@ -839,6 +859,9 @@ async fn linux_theme_watch_loop(inner: &ThemeWatcherInner) -> Result<(), SystemT
 ///
 /// This connects a `ThemeWatcher` to a `StyleEngine`, automatically switching
 /// between light and dark themes when the user changes their system preferences.
+/// It also keeps the engine's media environment in sync, so stylesheet rules in
+/// `@media (prefers-color-scheme: dark)` and `@media (prefers-contrast: more)`
+/// blocks follow the system color scheme and high contrast mode.
 ///
 /// # Example
 ///
@@ -891,6 +914,17 @@ impl ThemeAutoUpdater {
 
             let mut engine = engine_ref.write();
             engine.set_theme(new_theme);
+            if let Some(preference) = scheme.to_media_preference() {
+                engine.set_color_scheme_preference(preference);
+            }
+        });
+
+        // Switch `prefers-contrast` rules with the system high contrast mode
+        let engine_ref = style_engine.clone();
+        watcher.high_contrast_changed().connect(move |enabled| {
+            engine_ref
+                .write()
+                .set_contrast_preference(contrast_preference(*enabled));
         });
 
         Self {
@@ -939,6 +973,12 @@ impl ThemeAutoUpdater {
 
         let mut engine = self.style_engine.write();
         engine.set_theme(new_theme);
+        engine.set_color_scheme_preference(
+            scheme
+                .to_media_preference()
+                .unwrap_or(ColorSchemePreference::Light),
+        );
+        engine.set_contrast_preference(contrast_preference(SystemTheme::is_high_contrast()));
     }
 }
 
@@ -966,6 +1006,17 @@ mod tests {
         assert!(!ColorScheme::Unknown.is_light());
     }
 
+    #[test]
+    fn test_media_preferences() {
+        assert_eq!(
+            ColorScheme::Dark.to_media_preference(),
+            Some(ColorSchemePreference::Dark)
+        );
+        assert_eq!(ColorScheme::Unknown.to_media_preference(), None);
+        assert_eq!(contrast_preference(true), ContrastPreference::More);
+        assert_eq!(contrast_preference(false), ContrastPreference::NoPreference);
+    }
+
     #[test]
     fn test_accent_color_new() {
         let color = AccentColor::new(255, 128, 0);
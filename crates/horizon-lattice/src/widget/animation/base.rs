@@ -0,0 +1,354 @@
+//! The common animation trait and its shared state.
+//!
+//! Every animation has a *play time* that runs from zero to its total
+//! duration (one loop's duration times the loop count). [`Animation::set_current_time`]
+//! splits the play time into a loop index and a loop-local time, applies the
+//! direction, and hands the result to [`Animation::update_current_time`].
+
+use std::time::Duration;
+
+use horizon_lattice_core::Signal;
+
+/// How many times an animation repeats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LoopCount {
+    /// Play a fixed number of loops. `Count(0)` finishes immediately.
+    Count(u32),
+    /// Repeat until stopped.
+    Infinite,
+}
+
+impl Default for LoopCount {
+    fn default() -> Self {
+        Self::Count(1)
+    }
+}
+
+/// The direction an animation plays in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AnimationDirection {
+    /// From the start value to the end value.
+    #[default]
+    Forward,
+    /// From the end value back to the start value.
+    Backward,
+}
+
+/// The playback state of an animation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AnimationState {
+    /// Not playing.
+    #[default]
+    Stopped,
+    /// Paused at the current time.
+    Paused,
+    /// Playing.
+    Running,
+}
+
+/// State shared by all animations.
+pub struct AnimationBase {
+    loop_count: LoopCount,
+    direction: AnimationDirection,
+    state: AnimationState,
+    current_time: Duration,
+    current_loop: u32,
+    finished: Signal<()>,
+    state_changed: Signal<AnimationState>,
+    current_loop_changed: Signal<u32>,
+}
+
+impl Default for AnimationBase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AnimationBase {
+    /// Create a stopped, single-loop, forward animation state.
+    pub fn new() -> Self {
+        Self {
+            loop_count: LoopCount::default(),
+            direction: AnimationDirection::default(),
+            state: AnimationState::Stopped,
+            current_time: Duration::ZERO,
+            current_loop: 0,
+            finished: Signal::new(),
+            state_changed: Signal::new(),
+            current_loop_changed: Signal::new(),
+        }
+    }
+
+    /// Get the loop count.
+    pub fn loop_count(&self) -> LoopCount {
+        self.loop_count
+    }
+
+    /// Set the loop count.
+    pub fn set_loop_count(&mut self, loop_count: LoopCount) {
+        self.loop_count = loop_count;
+    }
+
+    /// Get the direction.
+    pub fn direction(&self) -> AnimationDirection {
+        self.direction
+    }
+
+    /// Set the direction.
+    pub fn set_direction(&mut self, direction: AnimationDirection) {
+        self.direction = direction;
+    }
+
+    /// Get the playback state.
+    pub fn state(&self) -> AnimationState {
+        self.state
+    }
+
+    /// Get the play time since the start of the first loop.
+    pub fn current_time(&self) -> Duration {
+        self.current_time
+    }
+
+    /// Get the zero-based index of the current loop.
+    pub fn current_loop(&self) -> u32 {
+        self.current_loop
+    }
+
+    /// Signal emitted when the animation runs to its end.
+    ///
+    /// Not emitted when the animation is stopped explicitly.
+    pub fn finished(&self) -> &Signal<()> {
+        &self.finished
+    }
+
+    /// Signal emitted when the playback state changes.
+    pub fn state_changed(&self) -> &Signal<AnimationState> {
+        &self.state_changed
+    }
+
+    /// Signal emitted when the animation enters a new loop.
+    pub fn current_loop_changed(&self) -> &Signal<u32> {
+        &self.current_loop_changed
+    }
+}
+
+/// An animation that can be started, paused and advanced through time.
+///
+/// Implementors provide the duration of a single loop and apply
+/// loop-local times in [`update_current_time`](Self::update_current_time);
+/// looping, direction and state handling are provided.
+pub trait Animation: Send + 'static {
+    /// Get the shared animation state.
+    fn animation_base(&self) -> &AnimationBase;
+
+    /// Get the shared animation state mutably.
+    fn animation_base_mut(&mut self) -> &mut AnimationBase;
+
+    /// Get the duration of a single loop.
+    fn duration(&self) -> Duration;
+
+    /// Apply the animation at a time within the current loop.
+    ///
+    /// `time` is in `0..=duration()` and already accounts for the direction.
+    fn update_current_time(&mut self, time: Duration);
+
+    /// Called when the playback state changes, before `state_changed` is
+    /// emitted.
+    fn update_state(&mut self, new_state: AnimationState, old_state: AnimationState) {
+        let _ = (new_state, old_state);
+    }
+
+    /// Get the duration of all loops, or `None` for infinite loops.
+    fn total_duration(&self) -> Option<Duration> {
+        match self.animation_base().loop_count() {
+            LoopCount::Count(count) => Some(self.duration().saturating_mul(count)),
+            LoopCount::Infinite => None,
+        }
+    }
+
+    /// Get the playback state.
+    fn state(&self) -> AnimationState {
+        self.animation_base().state()
+    }
+
+    /// Check whether the animation is running.
+    fn is_running(&self) -> bool {
+        self.state() == AnimationState::Running
+    }
+
+    /// Get the play time since the start of the first loop.
+    fn current_time(&self) -> Duration {
+        self.animation_base().current_time()
+    }
+
+    /// Get the zero-based index of the current loop.
+    fn current_loop(&self) -> u32 {
+        self.animation_base().current_loop()
+    }
+
+    /// Seek to a play time.
+    ///
+    /// The time is clamped to the total duration. A running animation that
+    /// reaches its end stops and emits `finished`.
+    fn set_current_time(&mut self, time: Duration) {
+        let total = self.total_duration();
+        let time = total.map_or(time, |total| time.min(total));
+        let duration = self.duration();
+
+        let (current_loop, local) = if duration.is_zero() {
+            (0, Duration::ZERO)
+        } else {
+            let loops = (time.as_nanos() / duration.as_nanos()).min(u32::MAX as u128) as u32;
+            if loops > 0 && total == Some(time) {
+                // The end of the last loop belongs to that loop.
+                (loops - 1, duration)
+            } else {
+                (loops, time - duration.saturating_mul(loops))
+            }
+        };
+
+        let base = self.animation_base_mut();
+        base.current_time = time;
+        if base.current_loop != current_loop {
+            base.current_loop = current_loop;
+            base.current_loop_changed.emit(current_loop);
+        }
+        let local = match base.direction {
+            AnimationDirection::Forward => local,
+            AnimationDirection::Backward => duration - local,
+        };
+        self.update_current_time(local);
+
+        if self.is_running() && total.is_some_and(|total| time >= total) {
+            set_state(self, AnimationState::Stopped);
+            self.animation_base().finished.emit(());
+        }
+    }
+
+    /// Start playing from the beginning.
+    ///
+    /// Does nothing if the animation is already running.
+    fn start(&mut self) {
+        if self.is_running() {
+            return;
+        }
+        self.animation_base_mut().current_time = Duration::ZERO;
+        set_state(self, AnimationState::Running);
+        self.set_current_time(Duration::ZERO);
+    }
+
+    /// Stop playing, leaving the current value in place.
+    fn stop(&mut self) {
+        set_state(self, AnimationState::Stopped);
+    }
+
+    /// Pause a running animation.
+    fn pause(&mut self) {
+        if self.is_running() {
+            set_state(self, AnimationState::Paused);
+        }
+    }
+
+    /// Resume a paused animation.
+    fn resume(&mut self) {
+        if self.state() == AnimationState::Paused {
+            set_state(self, AnimationState::Running);
+        }
+    }
+
+    /// Advance a running animation by `delta`.
+    fn advance(&mut self, delta: Duration) {
+        if self.is_running() {
+            let time = self.current_time().saturating_add(delta);
+            self.set_current_time(time);
+        }
+    }
+}
+
+fn set_state<A: Animation + ?Sized>(animation: &mut A, state: AnimationState) {
+    let old_state = animation.state();
+    if old_state == state {
+        return;
+    }
+    animation.animation_base_mut().state = state;
+    animation.update_state(state, old_state);
+    animation.animation_base().state_changed.emit(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder {
+        base: AnimationBase,
+        times: Arc<Mutex<Vec<u64>>>,
+    }
+
+    impl Animation for Recorder {
+        fn animation_base(&self) -> &AnimationBase {
+            &self.base
+        }
+
+        fn animation_base_mut(&mut self) -> &mut AnimationBase {
+            &mut self.base
+        }
+
+        fn duration(&self) -> Duration {
+            Duration::from_millis(100)
+        }
+
+        fn update_current_time(&mut self, time: Duration) {
+            self.times.lock().unwrap().push(time.as_millis() as u64);
+        }
+    }
+
+    fn recorder() -> (Recorder, Arc<Mutex<Vec<u64>>>) {
+        let times = Arc::new(Mutex::new(Vec::new()));
+        let recorder = Recorder {
+            base: AnimationBase::new(),
+            times: times.clone(),
+        };
+        (recorder, times)
+    }
+
+    #[test]
+    fn test_loops_and_direction() {
+        let (mut animation, times) = recorder();
+        animation.base.set_loop_count(LoopCount::Count(2));
+        animation.base.set_direction(AnimationDirection::Backward);
+
+        let finished = Arc::new(Mutex::new(0));
+        let finished_clone = finished.clone();
+        animation.base.finished().connect(move |_| {
+            *finished_clone.lock().unwrap() += 1;
+        });
+
+        animation.start();
+        animation.advance(Duration::from_millis(30));
+        animation.advance(Duration::from_millis(100));
+        assert_eq!(animation.current_loop(), 1);
+        animation.advance(Duration::from_millis(500));
+
+        assert_eq!(*times.lock().unwrap(), vec![100, 70, 70, 0]);
+        assert_eq!(animation.current_time(), Duration::from_millis(200));
+        assert_eq!(animation.state(), AnimationState::Stopped);
+        assert_eq!(*finished.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_pause_and_resume() {
+        let (mut animation, times) = recorder();
+        animation.start();
+        animation.pause();
+        animation.advance(Duration::from_millis(50));
+        assert_eq!(animation.current_time(), Duration::ZERO);
+
+        animation.resume();
+        animation.advance(Duration::from_millis(50));
+        assert!(animation.is_running());
+        animation.stop();
+        assert_eq!(*times.lock().unwrap(), vec![0, 50]);
+        assert_eq!(animation.state(), AnimationState::Stopped);
+    }
+}
@@ -0,0 +1,209 @@
+//! Driving animations from the application's timers.
+//!
+//! Animations started through an [`AnimationHandle`] are advanced by a single
+//! repeating timer on the [`Application`]'s timer manager. The timer is
+//! started when the first animation is registered and stopped when the last
+//! one stops, so idle applications do not tick.
+
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+
+use horizon_lattice_core::{Application, TimerId};
+use parking_lot::{Mutex, MutexGuard};
+
+use super::{Animation, AnimationState};
+
+/// Interval between animation ticks, about 60 frames per second.
+pub const ANIMATION_TICK_INTERVAL: Duration = Duration::from_millis(16);
+
+type SharedAnimation = Arc<Mutex<dyn Animation>>;
+
+#[derive(Default)]
+struct AnimationDriver {
+    animations: Vec<SharedAnimation>,
+    timer: Option<TimerId>,
+    last_tick: Option<Instant>,
+}
+
+fn driver() -> &'static Mutex<AnimationDriver> {
+    static DRIVER: OnceLock<Mutex<AnimationDriver>> = OnceLock::new();
+    DRIVER.get_or_init(|| Mutex::new(AnimationDriver::default()))
+}
+
+fn register(animation: SharedAnimation) {
+    let mut driver = driver().lock();
+    let ptr = Arc::as_ptr(&animation) as *const ();
+    if !driver
+        .animations
+        .iter()
+        .any(|a| Arc::as_ptr(a) as *const () == ptr)
+    {
+        driver.animations.push(animation);
+    }
+    if driver.timer.is_none()
+        && let Some(app) = Application::try_instance()
+    {
+        driver.last_tick = Some(Instant::now());
+        driver.timer = Some(app.start_repeating_timer_with(ANIMATION_TICK_INTERVAL, tick));
+    }
+}
+
+fn tick() {
+    let elapsed = {
+        let mut driver = driver().lock();
+        let now = Instant::now();
+        driver
+            .last_tick
+            .replace(now)
+            .map_or(Duration::ZERO, |last| now - last)
+    };
+    advance_animations(elapsed);
+}
+
+/// Advance every registered animation by `elapsed`.
+///
+/// Called by the animation timer; also useful to step animations manually,
+/// for example in tests or when no [`Application`] is running. Animations
+/// that are no longer running are unregistered, and the timer stops once
+/// none remain.
+pub fn advance_animations(elapsed: Duration) {
+    // Advance without holding the driver lock so handlers may start animations.
+    let animations = driver().lock().animations.clone();
+    for animation in &animations {
+        animation.lock().advance(elapsed);
+    }
+
+    let mut driver = driver().lock();
+    driver.animations.retain(|a| a.lock().is_running());
+    if driver.animations.is_empty() {
+        driver.last_tick = None;
+        if let Some(timer) = driver.timer.take()
+            && let Some(app) = Application::try_instance()
+        {
+            let _ = app.stop_timer(timer);
+        }
+    }
+}
+
+/// Get the number of animations currently driven by the animation timer.
+pub fn running_animation_count() -> usize {
+    driver().lock().animations.len()
+}
+
+/// A shared handle that plays an animation on the application's timers.
+///
+/// Signal handlers connected to the animation run while it is locked, so
+/// they must not lock the same handle again.
+///
+/// # Example
+///
+/// ```ignore
+/// let handle = AnimationHandle::new(
+///     PropertyAnimation::for_property(geometry.clone()).with_end_value(target),
+/// );
+/// handle.lock().animation_base().finished().connect(|_| println!("done"));
+/// handle.start();
+/// ```
+pub struct AnimationHandle<A: Animation> {
+    animation: Arc<Mutex<A>>,
+}
+
+impl<A: Animation> Clone for AnimationHandle<A> {
+    fn clone(&self) -> Self {
+        Self {
+            animation: self.animation.clone(),
+        }
+    }
+}
+
+impl<A: Animation> From<A> for AnimationHandle<A> {
+    fn from(animation: A) -> Self {
+        Self::new(animation)
+    }
+}
+
+impl<A: Animation> AnimationHandle<A> {
+    /// Wrap an animation.
+    pub fn new(animation: A) -> Self {
+        Self {
+            animation: Arc::new(Mutex::new(animation)),
+        }
+    }
+
+    /// Lock the animation to configure or inspect it.
+    pub fn lock(&self) -> MutexGuard<'_, A> {
+        self.animation.lock()
+    }
+
+    /// Start the animation and drive it from the animation timer.
+    pub fn start(&self) {
+        self.animation.lock().start();
+        self.drive();
+    }
+
+    /// Stop the animation.
+    pub fn stop(&self) {
+        self.animation.lock().stop();
+    }
+
+    /// Pause the animation.
+    pub fn pause(&self) {
+        self.animation.lock().pause();
+    }
+
+    /// Resume a paused animation.
+    pub fn resume(&self) {
+        self.animation.lock().resume();
+        self.drive();
+    }
+
+    /// Get the playback state.
+    pub fn state(&self) -> AnimationState {
+        self.animation.lock().state()
+    }
+
+    /// Check whether the animation is running.
+    pub fn is_running(&self) -> bool {
+        self.state() == AnimationState::Running
+    }
+
+    fn drive(&self) {
+        if self.is_running() {
+            register(self.animation.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::animation::PropertyAnimation;
+    use horizon_lattice_core::Property;
+
+    #[test]
+    fn test_handles_are_driven_until_finished() {
+        let opacity = Arc::new(Property::new(0.0f32));
+        let handle = AnimationHandle::new(
+            PropertyAnimation::for_property(opacity.clone())
+                .with_end_value(1.0)
+                .with_duration(Duration::from_millis(100)),
+        );
+
+        handle.start();
+        handle.start();
+        assert_eq!(running_animation_count(), 1);
+
+        advance_animations(Duration::from_millis(50));
+        assert_eq!(opacity.get(), 0.5);
+
+        handle.pause();
+        advance_animations(Duration::from_millis(10));
+        assert_eq!(running_animation_count(), 0);
+
+        handle.resume();
+        advance_animations(Duration::from_millis(60));
+        assert_eq!(opacity.get(), 1.0);
+        assert!(!handle.is_running());
+        assert_eq!(running_animation_count(), 0);
+    }
+}
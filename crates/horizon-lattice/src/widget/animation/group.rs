@@ -0,0 +1,408 @@
+//! Animation groups and pauses.
+//!
+//! Groups are animations themselves, so they can be nested, looped and
+//! reversed like any other animation.
+
+use std::time::Duration;
+
+use super::{Animation, AnimationBase, AnimationDirection, AnimationState, LoopCount};
+
+/// The total duration of a child, treating infinite loops as unbounded.
+fn child_duration(animation: &dyn Animation) -> Duration {
+    animation.total_duration().unwrap_or(Duration::MAX)
+}
+
+/// An animation that does nothing for a fixed duration.
+///
+/// Used to insert delays into a [`SequentialAnimationGroup`].
+pub struct PauseAnimation {
+    base: AnimationBase,
+    duration: Duration,
+}
+
+impl PauseAnimation {
+    /// Create a pause of the given duration.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            base: AnimationBase::new(),
+            duration,
+        }
+    }
+
+    /// Set the duration.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+}
+
+impl Animation for PauseAnimation {
+    fn animation_base(&self) -> &AnimationBase {
+        &self.base
+    }
+
+    fn animation_base_mut(&mut self) -> &mut AnimationBase {
+        &mut self.base
+    }
+
+    fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn update_current_time(&mut self, _time: Duration) {}
+}
+
+/// Runs child animations one after another.
+///
+/// Children that are skipped over by a seek are set to their end (or, when
+/// seeking backwards, their start) so the targets are left consistent.
+pub struct SequentialAnimationGroup {
+    base: AnimationBase,
+    animations: Vec<Box<dyn Animation>>,
+    current: Option<usize>,
+}
+
+impl Default for SequentialAnimationGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SequentialAnimationGroup {
+    /// Create an empty group.
+    pub fn new() -> Self {
+        Self {
+            base: AnimationBase::new(),
+            animations: Vec::new(),
+            current: None,
+        }
+    }
+
+    /// Append an animation.
+    pub fn add_animation(&mut self, animation: impl Animation) {
+        self.animations.push(Box::new(animation));
+    }
+
+    /// Append an animation using builder pattern.
+    pub fn with_animation(mut self, animation: impl Animation) -> Self {
+        self.add_animation(animation);
+        self
+    }
+
+    /// Append a pause.
+    pub fn add_pause(&mut self, duration: Duration) {
+        self.add_animation(PauseAnimation::new(duration));
+    }
+
+    /// Append a pause using builder pattern.
+    pub fn with_pause(mut self, duration: Duration) -> Self {
+        self.add_pause(duration);
+        self
+    }
+
+    /// Set the loop count using builder pattern.
+    pub fn with_loop_count(mut self, loop_count: LoopCount) -> Self {
+        self.base.set_loop_count(loop_count);
+        self
+    }
+
+    /// Set the direction using builder pattern.
+    pub fn with_direction(mut self, direction: AnimationDirection) -> Self {
+        self.base.set_direction(direction);
+        self
+    }
+
+    /// Get the number of child animations.
+    pub fn animation_count(&self) -> usize {
+        self.animations.len()
+    }
+
+    /// Get a child animation.
+    pub fn animation(&self, index: usize) -> Option<&dyn Animation> {
+        self.animations.get(index).map(|a| a.as_ref())
+    }
+
+    /// Get a child animation mutably.
+    pub fn animation_mut(&mut self, index: usize) -> Option<&mut (dyn Animation + 'static)> {
+        self.animations.get_mut(index).map(|a| a.as_mut())
+    }
+
+    /// Get the index of the child animation at the current time.
+    pub fn current_animation_index(&self) -> Option<usize> {
+        self.current
+    }
+
+    /// Find the child at a group-local time and the time within that child.
+    fn child_at(&self, time: Duration) -> Option<(usize, Duration)> {
+        let mut start = Duration::ZERO;
+        let last = self.animations.len().checked_sub(1)?;
+        for (index, animation) in self.animations.iter().enumerate() {
+            let end = start.saturating_add(child_duration(animation.as_ref()));
+            if time < end || index == last {
+                return Some((index, time.saturating_sub(start)));
+            }
+            start = end;
+        }
+        None
+    }
+}
+
+impl Animation for SequentialAnimationGroup {
+    fn animation_base(&self) -> &AnimationBase {
+        &self.base
+    }
+
+    fn animation_base_mut(&mut self) -> &mut AnimationBase {
+        &mut self.base
+    }
+
+    fn duration(&self) -> Duration {
+        self.animations.iter().fold(Duration::ZERO, |sum, a| {
+            sum.saturating_add(child_duration(a.as_ref()))
+        })
+    }
+
+    fn update_current_time(&mut self, time: Duration) {
+        let Some((index, local)) = self.child_at(time) else {
+            return;
+        };
+
+        match self.current {
+            Some(previous) if previous < index => {
+                for animation in &mut self.animations[previous..index] {
+                    animation.set_current_time(child_duration(animation.as_ref()));
+                    animation.stop();
+                }
+            }
+            Some(previous) if previous > index => {
+                for animation in self.animations[index + 1..=previous].iter_mut().rev() {
+                    animation.set_current_time(Duration::ZERO);
+                    animation.stop();
+                }
+            }
+            None => {
+                for animation in &mut self.animations[..index] {
+                    animation.set_current_time(child_duration(animation.as_ref()));
+                }
+            }
+            _ => {}
+        }
+        self.current = Some(index);
+
+        let running = self.is_running();
+        let animation = &mut self.animations[index];
+        if running && animation.state() == AnimationState::Stopped {
+            animation.start();
+        }
+        animation.set_current_time(local);
+    }
+
+    fn update_state(&mut self, new_state: AnimationState, old_state: AnimationState) {
+        if old_state == AnimationState::Stopped {
+            self.current = None;
+        }
+        let Some(animation) = self.current.and_then(|i| self.animations.get_mut(i)) else {
+            return;
+        };
+        match new_state {
+            AnimationState::Stopped => animation.stop(),
+            AnimationState::Paused => animation.pause(),
+            AnimationState::Running => animation.resume(),
+        }
+    }
+}
+
+/// Runs child animations at the same time.
+///
+/// The group lasts as long as its longest child.
+pub struct ParallelAnimationGroup {
+    base: AnimationBase,
+    animations: Vec<Box<dyn Animation>>,
+    last_loop: u32,
+}
+
+impl Default for ParallelAnimationGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParallelAnimationGroup {
+    /// Create an empty group.
+    pub fn new() -> Self {
+        Self {
+            base: AnimationBase::new(),
+            animations: Vec::new(),
+            last_loop: 0,
+        }
+    }
+
+    /// Add an animation.
+    pub fn add_animation(&mut self, animation: impl Animation) {
+        self.animations.push(Box::new(animation));
+    }
+
+    /// Add an animation using builder pattern.
+    pub fn with_animation(mut self, animation: impl Animation) -> Self {
+        self.add_animation(animation);
+        self
+    }
+
+    /// Set the loop count using builder pattern.
+    pub fn with_loop_count(mut self, loop_count: LoopCount) -> Self {
+        self.base.set_loop_count(loop_count);
+        self
+    }
+
+    /// Set the direction using builder pattern.
+    pub fn with_direction(mut self, direction: AnimationDirection) -> Self {
+        self.base.set_direction(direction);
+        self
+    }
+
+    /// Get the number of child animations.
+    pub fn animation_count(&self) -> usize {
+        self.animations.len()
+    }
+
+    /// Get a child animation.
+    pub fn animation(&self, index: usize) -> Option<&dyn Animation> {
+        self.animations.get(index).map(|a| a.as_ref())
+    }
+
+    /// Get a child animation mutably.
+    pub fn animation_mut(&mut self, index: usize) -> Option<&mut (dyn Animation + 'static)> {
+        self.animations.get_mut(index).map(|a| a.as_mut())
+    }
+
+    fn restart_children(&mut self) {
+        for animation in &mut self.animations {
+            if animation.is_running() {
+                // Let the child complete the previous loop first.
+                animation.set_current_time(child_duration(animation.as_ref()));
+            }
+            animation.stop();
+            animation.start();
+        }
+    }
+}
+
+impl Animation for ParallelAnimationGroup {
+    fn animation_base(&self) -> &AnimationBase {
+        &self.base
+    }
+
+    fn animation_base_mut(&mut self) -> &mut AnimationBase {
+        &mut self.base
+    }
+
+    fn duration(&self) -> Duration {
+        self.animations
+            .iter()
+            .map(|a| child_duration(a.as_ref()))
+            .max()
+            .unwrap_or(Duration::ZERO)
+    }
+
+    fn update_current_time(&mut self, time: Duration) {
+        let current_loop = self.current_loop();
+        if self.is_running() && current_loop != self.last_loop {
+            self.restart_children();
+        }
+        self.last_loop = current_loop;
+
+        for animation in &mut self.animations {
+            let end = child_duration(animation.as_ref());
+            let local = time.min(end);
+            let done = animation.state() == AnimationState::Stopped
+                && animation.current_time() == end
+                && local == end;
+            if !done {
+                animation.set_current_time(local);
+            }
+        }
+    }
+
+    fn update_state(&mut self, new_state: AnimationState, old_state: AnimationState) {
+        match (old_state, new_state) {
+            (AnimationState::Stopped, AnimationState::Running) => {
+                self.last_loop = 0;
+                self.restart_children();
+            }
+            (_, AnimationState::Stopped) => self.animations.iter_mut().for_each(|a| a.stop()),
+            (_, AnimationState::Paused) => self.animations.iter_mut().for_each(|a| a.pause()),
+            (_, AnimationState::Running) => self.animations.iter_mut().for_each(|a| a.resume()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::animation::PropertyAnimation;
+    use horizon_lattice_core::Property;
+    use std::sync::Arc;
+
+    fn animation(property: &Arc<Property<f32>>, to: f32, millis: u64) -> PropertyAnimation<f32> {
+        PropertyAnimation::for_property(property.clone())
+            .with_end_value(to)
+            .with_duration(Duration::from_millis(millis))
+    }
+
+    #[test]
+    fn test_sequential_group() {
+        let x = Arc::new(Property::new(0.0f32));
+        let y = Arc::new(Property::new(0.0f32));
+        let mut group = SequentialAnimationGroup::new()
+            .with_animation(animation(&x, 100.0, 100))
+            .with_pause(Duration::from_millis(50))
+            .with_animation(animation(&y, 10.0, 100));
+        assert_eq!(group.duration(), Duration::from_millis(250));
+
+        group.start();
+        group.advance(Duration::from_millis(50));
+        assert_eq!((x.get(), y.get()), (50.0, 0.0));
+        assert_eq!(group.current_animation_index(), Some(0));
+
+        // Skipping past the first child leaves it at its end value.
+        group.advance(Duration::from_millis(150));
+        assert_eq!((x.get(), y.get()), (100.0, 5.0));
+        assert_eq!(group.current_animation_index(), Some(2));
+        assert_eq!(group.animation(0).unwrap().state(), AnimationState::Stopped);
+        assert!(group.animation(2).unwrap().is_running());
+
+        group.advance(Duration::from_millis(100));
+        assert_eq!(y.get(), 10.0);
+        assert_eq!(group.state(), AnimationState::Stopped);
+        assert!(!group.animation(2).unwrap().is_running());
+    }
+
+    #[test]
+    fn test_parallel_group_loops() {
+        let x = Arc::new(Property::new(0.0f32));
+        let y = Arc::new(Property::new(0.0f32));
+        let mut group = ParallelAnimationGroup::new()
+            .with_animation(animation(&x, 100.0, 100))
+            .with_animation(animation(&y, 10.0, 200))
+            .with_loop_count(LoopCount::Count(2));
+        assert_eq!(group.total_duration(), Some(Duration::from_millis(400)));
+
+        group.start();
+        group.advance(Duration::from_millis(150));
+        assert_eq!((x.get(), y.get()), (100.0, 7.5));
+        assert_eq!(group.animation(0).unwrap().state(), AnimationState::Stopped);
+
+        group.pause();
+        assert_eq!(group.animation(1).unwrap().state(), AnimationState::Paused);
+        group.resume();
+
+        // Entering the second loop completes the first, then restarts both
+        // children from their current values.
+        group.advance(Duration::from_millis(100));
+        assert_eq!(group.current_loop(), 1);
+        assert!(group.animation(0).unwrap().is_running());
+        assert_eq!(y.get(), 10.0);
+        group.advance(Duration::from_millis(500));
+        assert_eq!((x.get(), y.get()), (100.0, 10.0));
+        assert_eq!(group.state(), AnimationState::Stopped);
+    }
+}
@@ -0,0 +1,79 @@
+//! Value interpolation for property animations.
+
+use horizon_lattice_render::{Color, Point, Rect, Size};
+
+/// A value that can be animated by blending between two endpoints.
+///
+/// `t` is the eased progress, usually in 0.0..=1.0. Easing curves that
+/// overshoot (such as elastic or back curves) may pass values outside that
+/// range, which extrapolates past the endpoints.
+pub trait Interpolate: Clone + Send + Sync + 'static {
+    /// Blend from `self` towards `to` at progress `t`.
+    fn interpolate(&self, to: &Self, t: f32) -> Self;
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * t
+    }
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self + (to - self) * f64::from(t)
+    }
+}
+
+impl Interpolate for Point {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Point::new(self.x.interpolate(&to.x, t), self.y.interpolate(&to.y, t))
+    }
+}
+
+impl Interpolate for Size {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Size::new(
+            self.width.interpolate(&to.width, t),
+            self.height.interpolate(&to.height, t),
+        )
+    }
+}
+
+impl Interpolate for Rect {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        Rect {
+            origin: self.origin.interpolate(&to.origin, t),
+            size: self.size.interpolate(&to.size, t),
+        }
+    }
+}
+
+impl Interpolate for Color {
+    fn interpolate(&self, to: &Self, t: f32) -> Self {
+        self.lerp(*to, t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interpolate_geometry() {
+        let from = Rect::new(0.0, 0.0, 100.0, 50.0);
+        let to = Rect::new(10.0, 20.0, 200.0, 150.0);
+        assert_eq!(
+            from.interpolate(&to, 0.5),
+            Rect::new(5.0, 10.0, 150.0, 100.0)
+        );
+        assert_eq!(from.interpolate(&to, 1.0), to);
+        assert_eq!(2.0f64.interpolate(&4.0, 0.25), 2.5);
+    }
+
+    #[test]
+    fn test_interpolate_color() {
+        let mid = Color::BLACK.interpolate(&Color::WHITE, 0.5);
+        assert!((mid.r - 0.5).abs() < 1e-6);
+        assert_eq!(mid.a, 1.0);
+    }
+}
@@ -1,7 +1,7 @@
 //! Animation and transition support for Horizon Lattice.
 //!
-//! This module provides easing functions and transition primitives for smooth
-//! animations in the UI.
+//! This module provides easing functions, transition primitives and a general
+//! property animation framework for smooth animations in the UI.
 //!
 //! # Easing Functions
 //!
@@ -16,9 +16,44 @@
 //! let progress = 0.5;
 //! let eased = ease(Easing::EaseInOut, progress);
 //! ```
+//!
+//! # Property Animations
+//!
+//! [`PropertyAnimation`] interpolates any [`Interpolate`] value (`f32`,
+//! `Point`, `Size`, `Rect`, `Color`, ...) into a `Property<T>`, a meta
+//! property set by name, or a setter closure. Animations can be combined
+//! with [`SequentialAnimationGroup`], [`ParallelAnimationGroup`] and
+//! [`PauseAnimation`], and are played on the application's timers through
+//! an [`AnimationHandle`].
+//!
+//! ```ignore
+//! use std::time::Duration;
+//! use horizon_lattice::widget::animation::*;
+//!
+//! let slide_in = SequentialAnimationGroup::new()
+//!     .with_pause(Duration::from_millis(100))
+//!     .with_animation(
+//!         ParallelAnimationGroup::new()
+//!             .with_animation(PropertyAnimation::for_property(geometry).with_end_value(target))
+//!             .with_animation(PropertyAnimation::for_property(opacity).with_end_value(1.0)),
+//!     );
+//!
+//! AnimationHandle::new(slide_in).start();
+//! ```
 
-mod easing;
+mod base;
+mod driver;
+mod group;
+mod interpolate;
+mod property_animation;
 mod transition;
 
-pub use easing::{Easing, ease};
+pub use base::{Animation, AnimationBase, AnimationDirection, AnimationState, LoopCount};
+pub use driver::{
+    ANIMATION_TICK_INTERVAL, AnimationHandle, advance_animations, running_animation_count,
+};
+pub use group::{ParallelAnimationGroup, PauseAnimation, SequentialAnimationGroup};
+pub use horizon_lattice_core::easing::{Easing, ease};
+pub use interpolate::Interpolate;
+pub use property_animation::PropertyAnimation;
 pub use transition::{Transition, TransitionState, TransitionType};
@@ -0,0 +1,412 @@
+//! Animations that interpolate a single value.
+
+use std::any::TypeId;
+use std::sync::Arc;
+use std::time::Duration;
+
+use horizon_lattice_core::{MetaError, MetaResult, Object, Property, Signal};
+use parking_lot::Mutex;
+
+use super::{
+    Animation, AnimationBase, AnimationDirection, AnimationState, Easing, Interpolate, LoopCount,
+    ease,
+};
+
+type Getter<T> = Box<dyn Fn() -> Option<T> + Send>;
+type Setter<T> = Box<dyn FnMut(T) + Send>;
+
+/// Animates a value from a start value to an end value.
+///
+/// The target is a [`Property`], a [`MetaProperty`](horizon_lattice_core::MetaProperty)
+/// looked up by name, or an arbitrary setter. When no start value is set, the
+/// target's value at the time the animation starts is used.
+///
+/// # Example
+///
+/// ```ignore
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use horizon_lattice::widget::animation::{AnimationHandle, Easing, PropertyAnimation};
+///
+/// let opacity = Arc::new(Property::new(0.0f32));
+/// let fade_in = PropertyAnimation::for_property(opacity.clone())
+///     .with_end_value(1.0)
+///     .with_duration(Duration::from_millis(300))
+///     .with_easing(Easing::EaseOut);
+///
+/// AnimationHandle::new(fade_in).start();
+/// ```
+pub struct PropertyAnimation<T: Interpolate> {
+    base: AnimationBase,
+    getter: Option<Getter<T>>,
+    setter: Setter<T>,
+    duration: Duration,
+    easing: Easing,
+    start_value: Option<T>,
+    end_value: Option<T>,
+    /// The start value in effect for the current run.
+    effective_start: Option<T>,
+    current_value: Option<T>,
+    value_changed: Signal<T>,
+}
+
+impl<T: Interpolate> PropertyAnimation<T> {
+    /// Create an animation that passes each value to `setter`.
+    ///
+    /// Without a getter the start value must be set explicitly.
+    pub fn new(setter: impl FnMut(T) + Send + 'static) -> Self {
+        Self::with_target(None, Box::new(setter))
+    }
+
+    /// Create an animation of a [`Property`].
+    pub fn for_property(property: Arc<Property<T>>) -> Self {
+        let source = property.clone();
+        Self::with_target(
+            Some(Box::new(move || Some(source.get()))),
+            Box::new(move |value| {
+                property.set_silent(value);
+                property.notify_bindings();
+            }),
+        )
+    }
+
+    /// Create an animation of an object's property, set through its
+    /// [`MetaObject`](horizon_lattice_core::MetaObject).
+    ///
+    /// Fails if the object has no such property, the property is read-only,
+    /// or its type is not `T`.
+    pub fn for_meta_property<O: Object>(object: Arc<Mutex<O>>, name: &str) -> MetaResult<Self> {
+        let not_found = || MetaError::PropertyNotFound {
+            name: name.to_string(),
+        };
+        let (meta, property) = {
+            let guard = object.lock();
+            let meta = guard.meta_object().ok_or_else(not_found)?;
+            let property = meta.property(name).ok_or_else(not_found)?;
+            (meta, property)
+        };
+        if property.read_only || property.setter.is_none() {
+            return Err(MetaError::PropertyReadOnly {
+                name: name.to_string(),
+            });
+        }
+        if property.type_id != TypeId::of::<T>() {
+            return Err(MetaError::PropertyTypeMismatch {
+                expected: property.type_name,
+                got: std::any::type_name::<T>(),
+            });
+        }
+
+        let name = property.name;
+        let source = object.clone();
+        let getter = move || {
+            let guard = source.lock();
+            let value = meta.get_property(&*guard, name).ok()?;
+            value.downcast::<T>().ok().map(|value| *value)
+        };
+        let setter = move |value: T| {
+            let mut guard = object.lock();
+            if let Err(e) = meta.set_property(&mut *guard, name, Box::new(value)) {
+                tracing::warn!(target: "horizon_lattice::animation", "Failed to animate property {}: {}", name, e);
+            }
+        };
+        Ok(Self::with_target(Some(Box::new(getter)), Box::new(setter)))
+    }
+
+    fn with_target(getter: Option<Getter<T>>, setter: Setter<T>) -> Self {
+        Self {
+            base: AnimationBase::new(),
+            getter,
+            setter,
+            duration: Duration::from_millis(250),
+            easing: Easing::Linear,
+            start_value: None,
+            end_value: None,
+            effective_start: None,
+            current_value: None,
+            value_changed: Signal::new(),
+        }
+    }
+
+    /// Get the duration of a single loop.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// Set the duration of a single loop.
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration = duration;
+    }
+
+    /// Set the duration using builder pattern.
+    pub fn with_duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Get the easing curve.
+    pub fn easing(&self) -> Easing {
+        self.easing
+    }
+
+    /// Set the easing curve.
+    pub fn set_easing(&mut self, easing: Easing) {
+        self.easing = easing;
+    }
+
+    /// Set the easing curve using builder pattern.
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Get the explicit start value, if any.
+    pub fn start_value(&self) -> Option<&T> {
+        self.start_value.as_ref()
+    }
+
+    /// Set the start value. `None` starts from the target's current value.
+    pub fn set_start_value(&mut self, value: Option<T>) {
+        self.start_value = value;
+        self.effective_start = None;
+    }
+
+    /// Set the start value using builder pattern.
+    pub fn with_start_value(mut self, value: T) -> Self {
+        self.set_start_value(Some(value));
+        self
+    }
+
+    /// Get the end value, if set.
+    pub fn end_value(&self) -> Option<&T> {
+        self.end_value.as_ref()
+    }
+
+    /// Set the end value.
+    pub fn set_end_value(&mut self, value: T) {
+        self.end_value = Some(value);
+    }
+
+    /// Set the end value using builder pattern.
+    pub fn with_end_value(mut self, value: T) -> Self {
+        self.end_value = Some(value);
+        self
+    }
+
+    /// Set the loop count using builder pattern.
+    pub fn with_loop_count(mut self, loop_count: LoopCount) -> Self {
+        self.base.set_loop_count(loop_count);
+        self
+    }
+
+    /// Set the direction using builder pattern.
+    pub fn with_direction(mut self, direction: AnimationDirection) -> Self {
+        self.base.set_direction(direction);
+        self
+    }
+
+    /// Get the most recently applied value.
+    pub fn current_value(&self) -> Option<&T> {
+        self.current_value.as_ref()
+    }
+
+    /// Signal emitted with each value applied to the target.
+    pub fn value_changed(&self) -> &Signal<T> {
+        &self.value_changed
+    }
+
+    fn resolve_start(&mut self) -> Option<T> {
+        if self.effective_start.is_none() {
+            self.effective_start = self
+                .start_value
+                .clone()
+                .or_else(|| self.getter.as_ref().and_then(|get| get()));
+        }
+        self.effective_start.clone()
+    }
+}
+
+impl<T: Interpolate> Animation for PropertyAnimation<T> {
+    fn animation_base(&self) -> &AnimationBase {
+        &self.base
+    }
+
+    fn animation_base_mut(&mut self) -> &mut AnimationBase {
+        &mut self.base
+    }
+
+    fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    fn update_current_time(&mut self, time: Duration) {
+        let Some(end) = self.end_value.clone() else {
+            return;
+        };
+        let Some(start) = self.resolve_start() else {
+            return;
+        };
+        let progress = if self.duration.is_zero() {
+            1.0
+        } else {
+            (time.as_secs_f64() / self.duration.as_secs_f64()).clamp(0.0, 1.0) as f32
+        };
+        let value = start.interpolate(&end, ease(self.easing, progress));
+        (self.setter)(value.clone());
+        self.current_value = Some(value.clone());
+        self.value_changed.emit(value);
+    }
+
+    fn update_state(&mut self, new_state: AnimationState, old_state: AnimationState) {
+        if new_state == AnimationState::Running && old_state == AnimationState::Stopped {
+            // Capture the target's value afresh for each run.
+            self.effective_start = None;
+            self.resolve_start();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use horizon_lattice_core::{
+        MetaObject, MetaProperty, ObjectBase, ObjectId, init_global_registry, object_cast,
+        object_cast_mut,
+    };
+    use horizon_lattice_render::Color;
+    use std::any::Any;
+
+    #[test]
+    fn test_animates_property() {
+        let property = Arc::new(Property::new(10.0f32));
+        let mut animation = PropertyAnimation::for_property(property.clone())
+            .with_end_value(20.0)
+            .with_duration(Duration::from_millis(100));
+
+        let values = Arc::new(Mutex::new(Vec::new()));
+        let values_clone = values.clone();
+        animation
+            .value_changed()
+            .connect(move |v| values_clone.lock().push(*v));
+
+        animation.start();
+        assert_eq!(property.get(), 10.0);
+        animation.advance(Duration::from_millis(25));
+        assert_eq!(property.get(), 12.5);
+        animation.advance(Duration::from_millis(100));
+        assert_eq!(property.get(), 20.0);
+        assert_eq!(animation.state(), AnimationState::Stopped);
+        assert_eq!(*values.lock(), vec![10.0, 12.5, 20.0]);
+
+        // A second run starts from the property's new value.
+        animation.set_end_value(0.0);
+        animation.start();
+        animation.advance(Duration::from_millis(50));
+        assert_eq!(property.get(), 10.0);
+    }
+
+    #[test]
+    fn test_setter_with_easing_and_direction() {
+        let applied = Arc::new(Mutex::new(Color::BLACK));
+        let applied_clone = applied.clone();
+        let mut animation = PropertyAnimation::new(move |c| *applied_clone.lock() = c)
+            .with_start_value(Color::BLACK)
+            .with_end_value(Color::WHITE)
+            .with_easing(Easing::EaseIn)
+            .with_direction(AnimationDirection::Backward);
+
+        animation.start();
+        assert_eq!(*applied.lock(), Color::WHITE);
+        animation.set_current_time(Duration::from_millis(125));
+        let eased = ease(Easing::EaseIn, 0.5);
+        assert!((applied.lock().r - eased).abs() < 1e-6);
+    }
+
+    struct Slider {
+        base: ObjectBase,
+        position: f32,
+    }
+
+    impl Object for Slider {
+        fn object_id(&self) -> ObjectId {
+            self.base.id()
+        }
+
+        fn meta_object(&self) -> Option<&'static MetaObject> {
+            Some(&SLIDER_META)
+        }
+    }
+
+    fn slider_get_position(obj: &dyn Object) -> Box<dyn Any> {
+        Box::new(object_cast::<Slider>(obj).unwrap().position)
+    }
+
+    fn slider_set_position(obj: &mut dyn Object, value: Box<dyn Any>) -> MetaResult<()> {
+        let position = value
+            .downcast::<f32>()
+            .map_err(|_| MetaError::PropertyTypeMismatch {
+                expected: "f32",
+                got: "unknown",
+            })?;
+        object_cast_mut::<Slider>(obj).unwrap().position = *position;
+        Ok(())
+    }
+
+    static SLIDER_META: MetaObject = MetaObject {
+        type_id: TypeId::of::<Slider>(),
+        type_name: "Slider",
+        parent: None,
+        properties: &[
+            MetaProperty {
+                name: "position",
+                type_name: "f32",
+                type_id: TypeId::of::<f32>(),
+                read_only: false,
+                notify_signal: None,
+                getter: slider_get_position,
+                setter: Some(slider_set_position),
+            },
+            MetaProperty {
+                name: "maximum",
+                type_name: "f32",
+                type_id: TypeId::of::<f32>(),
+                read_only: true,
+                notify_signal: None,
+                getter: slider_get_position,
+                setter: None,
+            },
+        ],
+        signals: &[],
+        methods: &[],
+        create: None,
+    };
+
+    #[test]
+    fn test_meta_property_target() {
+        init_global_registry();
+        let slider = Arc::new(Mutex::new(Slider {
+            base: ObjectBase::new::<Slider>(),
+            position: 4.0,
+        }));
+
+        let mut animation = PropertyAnimation::<f32>::for_meta_property(slider.clone(), "position")
+            .unwrap()
+            .with_end_value(8.0);
+        animation.start();
+        animation.set_current_time(Duration::from_millis(125));
+        assert_eq!(slider.lock().position, 6.0);
+
+        assert!(matches!(
+            PropertyAnimation::<f32>::for_meta_property(slider.clone(), "missing"),
+            Err(MetaError::PropertyNotFound { .. })
+        ));
+        assert!(matches!(
+            PropertyAnimation::<f32>::for_meta_property(slider.clone(), "maximum"),
+            Err(MetaError::PropertyReadOnly { .. })
+        ));
+        assert!(matches!(
+            PropertyAnimation::<f64>::for_meta_property(slider, "position"),
+            Err(MetaError::PropertyTypeMismatch { .. })
+        ));
+    }
+}
@@ -4,7 +4,7 @@
 
 use std::time::{Duration, Instant};
 
-use super::easing::{Easing, ease};
+use super::{Easing, ease};
 
 /// Type of transition effect.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -4,13 +4,19 @@
 //! for all widgets. It handles geometry, visibility, enabled state, and
 //! coordinates with the object system.
 
+use std::any::Any;
+
 use horizon_lattice_core::{
     Object, ObjectBase, ObjectId, ObjectResult, Signal, WidgetState, global_registry,
 };
 use horizon_lattice_render::{Point, Rect, Size};
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, StyleProperties, WidgetStyleState,
+};
 
 use super::cursor::CursorShape;
 use super::geometry::{SizePolicy, SizePolicyPair};
+use super::style::{self as widget_style, StyleRequest};
 
 /// Focus policy for a widget.
 ///
@@ -91,6 +97,7 @@ pub enum ContextMenuPolicy {
 /// - Visibility and enabled state
 /// - Coordinate mapping
 /// - Event filtering
+/// - Style identity (type name, classes, inline style) for the style engine
 ///
 /// Widget implementations typically include this as a field and delegate
 /// common operations to it.
@@ -212,6 +219,15 @@ pub struct WidgetBase {
     /// For example, "Press Enter to submit the form".
     accessible_description: Option<String>,
 
+    /// Type name used for type selector matching (e.g. "PushButton").
+    style_type_name: &'static str,
+
+    /// CSS classes used for class selector matching.
+    style_classes: Vec<String>,
+
+    /// Inline style applied on top of all stylesheet rules.
+    inline_style: Option<StyleProperties>,
+
     /// Signal emitted when the geometry changes.
     pub geometry_changed: Signal<Rect>,
 
@@ -308,6 +324,9 @@ impl WidgetBase {
             accepts_drops: false,
             accessible_name: None,
             accessible_description: None,
+            style_type_name: short_type_name(std::any::type_name::<T>()),
+            style_classes: Vec::new(),
+            inline_style: None,
             geometry_changed: Signal::new(),
             pressed_changed: Signal::new(),
             visible_changed: Signal::new(),
@@ -334,8 +353,11 @@ impl WidgetBase {
     }
 
     /// Set the widget's name.
+    ///
+    /// The name is also used for `#id` selector matching.
     pub fn set_name(&self, name: impl Into<String>) {
         self.object_base.set_name(name);
+        widget_style::invalidate_widget_style(self.object_id());
     }
 
     /// Get the parent widget's object ID.
@@ -344,8 +366,13 @@ impl WidgetBase {
     }
 
     /// Set the parent widget.
+    ///
+    /// Reparenting invalidates the style of this widget and its descendants,
+    /// since inherited values and the applicable style engine may change.
     pub fn set_parent(&self, parent: Option<ObjectId>) -> ObjectResult<()> {
-        self.object_base.set_parent(parent)
+        self.object_base.set_parent(parent)?;
+        widget_style::invalidate_widget_style(self.object_id());
+        Ok(())
     }
 
     /// Get the IDs of child widgets.
@@ -688,6 +715,7 @@ impl WidgetBase {
             self.enabled = enabled;
             self.needs_repaint = true;
             self.enabled_changed.emit(enabled);
+            widget_style::invalidate_descendant_styles(self.object_id());
 
             // Sync to registry for state propagation queries
             if let Ok(registry) = global_registry() {
@@ -810,6 +838,7 @@ impl WidgetBase {
             self.focused = focused;
             self.needs_repaint = true;
             self.focus_changed.emit(focused);
+            widget_style::invalidate_descendant_styles(self.object_id());
         }
     }
 
@@ -1048,6 +1077,7 @@ impl WidgetBase {
         if self.hovered != hovered {
             self.hovered = hovered;
             self.needs_repaint = true;
+            widget_style::invalidate_descendant_styles(self.object_id());
         }
     }
 
@@ -1072,6 +1102,7 @@ impl WidgetBase {
             self.pressed = pressed;
             self.needs_repaint = true;
             self.pressed_changed.emit(pressed);
+            widget_style::invalidate_descendant_styles(self.object_id());
         }
     }
 
@@ -1208,6 +1239,273 @@ impl WidgetBase {
         Some(region)
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Get the type name used for type selector matching.
+    ///
+    /// Defaults to the unqualified Rust type name passed to [`WidgetBase::new`]
+    /// (e.g. `"Label"`). Widgets built on a shared base override it with
+    /// [`set_style_type_name`](Self::set_style_type_name).
+    #[inline]
+    pub fn style_type_name(&self) -> &'static str {
+        self.style_type_name
+    }
+
+    /// Set the type name used for type selector matching.
+    pub fn set_style_type_name(&mut self, type_name: &'static str) {
+        if self.style_type_name != type_name {
+            self.style_type_name = type_name;
+            self.invalidate_style();
+        }
+    }
+
+    /// Get the widget's CSS classes.
+    #[inline]
+    pub fn style_classes(&self) -> &[String] {
+        &self.style_classes
+    }
+
+    /// Add a CSS class to the widget.
+    ///
+    /// Adding a class that is already present has no effect.
+    pub fn add_class(&mut self, class: impl Into<String>) {
+        let class = class.into();
+        if !self.has_class(&class) {
+            self.style_classes.push(class);
+            self.invalidate_style();
+        }
+    }
+
+    /// Remove a CSS class from the widget.
+    ///
+    /// Returns `true` if the class was found and removed.
+    pub fn remove_class(&mut self, class: &str) -> bool {
+        if let Some(pos) = self.style_classes.iter().position(|c| c == class) {
+            self.style_classes.remove(pos);
+            self.invalidate_style();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Check if the widget has a specific CSS class.
+    pub fn has_class(&self, class: &str) -> bool {
+        self.style_classes.iter().any(|c| c == class)
+    }
+
+    /// Toggle a CSS class on the widget.
+    ///
+    /// Returns `true` if the class was added, `false` if it was removed.
+    pub fn toggle_class(&mut self, class: &str) -> bool {
+        if self.remove_class(class) {
+            false
+        } else {
+            self.add_class(class);
+            true
+        }
+    }
+
+    /// Get the widget's inline style.
+    #[inline]
+    pub fn inline_style(&self) -> Option<&StyleProperties> {
+        self.inline_style.as_ref()
+    }
+
+    /// Set the widget's inline style.
+    ///
+    /// Inline styles have the highest priority and override all stylesheet rules.
+    pub fn set_inline_style(&mut self, style: Option<StyleProperties>) {
+        self.inline_style = style;
+        self.invalidate_style();
+    }
+
+    /// Set a dynamic property on the widget.
+    ///
+    /// Dynamic properties are stored in the object registry and can be
+    /// matched by attribute selectors such as `LineEdit[severity="error"]`.
+    /// Setting a property through this method also invalidates the widget's
+    /// style; properties set directly on the registry require a call to
+    /// [`invalidate_style`](Self::invalidate_style) to restyle descendants.
+    pub fn set_property<T: Any + Send + Sync>(&mut self, name: &str, value: T) {
+        if let Ok(registry) = global_registry() {
+            let _ = registry.set_dynamic_property(self.object_id(), name, value);
+        }
+        self.invalidate_style();
+    }
+
+    /// Get a dynamic property of the widget.
+    ///
+    /// Returns `None` if the property is not set or has a different type.
+    pub fn property<T: Any + Clone>(&self, name: &str) -> Option<T> {
+        global_registry().ok()?.with_read(|registry| {
+            registry
+                .dynamic_property::<T>(self.object_id(), name)
+                .ok()
+                .flatten()
+                .cloned()
+        })
+    }
+
+    /// Remove a dynamic property from the widget.
+    ///
+    /// Returns `true` if the property was set.
+    pub fn remove_property(&mut self, name: &str) -> bool {
+        let removed = global_registry()
+            .ok()
+            .and_then(|registry| {
+                registry
+                    .remove_dynamic_property(self.object_id(), name)
+                    .ok()
+            })
+            .flatten()
+            .is_some();
+        if removed {
+            self.invalidate_style();
+        }
+        removed
+    }
+
+    /// Get the widget's current state for pseudo-class matching.
+    ///
+    /// The `checked` field is always `None`; checkable widgets fill it in
+    /// before calling [`compute_style_with`](Self::compute_style_with).
+    pub fn style_state(&self) -> WidgetStyleState {
+        let (sibling_info, child_count) = match global_registry() {
+            Ok(registry) => {
+                let id = self.object_id();
+                let sibling_info = registry
+                    .sibling_index(id)
+                    .ok()
+                    .flatten()
+                    .zip(registry.siblings(id).ok())
+                    .map(|(index, siblings)| (index, siblings.len() + 1));
+                let child_count = registry.children(id).map(|c| c.len()).unwrap_or(0);
+                (sibling_info, child_count)
+            }
+            Err(_) => (None, 0),
+        };
+
+        WidgetStyleState {
+            hovered: self.hovered,
+            pressed: self.pressed,
+            focused: self.focused,
+            enabled: self.is_effectively_enabled(),
+            checked: None,
+            sibling_info,
+            child_count,
+        }
+    }
+
+    /// Compute the widget's style from the attached style engine.
+    ///
+    /// Returns `None` if no style engine applies to this widget, in which case
+    /// the widget should paint with its built-in appearance.
+    pub fn compute_style(&self) -> Option<ComputedStyle> {
+        self.compute_style_with(self.style_state(), &[])
+    }
+
+    /// Compute the widget's style with an explicit state and extra classes.
+    ///
+    /// Widgets use this to expose internal state to selectors, such as the
+    /// checked state of a button or its variant as a class.
+    pub fn compute_style_with(
+        &self,
+        state: WidgetStyleState,
+        extra_classes: &[&str],
+    ) -> Option<ComputedStyle> {
+        let name = self.name();
+        let classes: Vec<String>;
+        let classes = if extra_classes.is_empty() {
+            &self.style_classes
+        } else {
+            classes = self
+                .style_classes
+                .iter()
+                .cloned()
+                .chain(extra_classes.iter().map(|c| c.to_string()))
+                .collect();
+            &classes
+        };
+
+        widget_style::compute_widget_style(&StyleRequest {
+            widget_id: self.object_id(),
+            type_name: self.style_type_name,
+            name: (!name.is_empty()).then_some(name.as_str()),
+            classes,
+            state,
+            inline_style: self.inline_style.as_ref(),
+            sub_control: None,
+        })
+    }
+
+    /// Compute the style of one of the widget's sub-controls.
+    ///
+    /// Only rules targeting `element` (e.g. `Slider::handle:hover`) apply,
+    /// with pseudo-classes matched against `state`, the sub-control's own
+    /// state. The sub-control inherits from the widget's current style.
+    ///
+    /// Returns `None` if no style engine applies to this widget.
+    pub fn compute_sub_control_style(
+        &self,
+        element: PseudoElement,
+        state: WidgetStyleState,
+    ) -> Option<ComputedStyle> {
+        self.compute_sub_control_style_with(element, state, self.style_state(), &[])
+    }
+
+    /// Compute a sub-control style with an explicit widget state and extra classes.
+    ///
+    /// `widget_state` and `extra_classes` describe the widget itself, as in
+    /// [`compute_style_with`](Self::compute_style_with).
+    pub fn compute_sub_control_style_with(
+        &self,
+        element: PseudoElement,
+        state: WidgetStyleState,
+        widget_state: WidgetStyleState,
+        extra_classes: &[&str],
+    ) -> Option<ComputedStyle> {
+        let name = self.name();
+        let classes: Vec<String> = self
+            .style_classes
+            .iter()
+            .cloned()
+            .chain(extra_classes.iter().map(|c| c.to_string()))
+            .collect();
+
+        widget_style::compute_widget_style(&StyleRequest {
+            widget_id: self.object_id(),
+            type_name: self.style_type_name,
+            name: (!name.is_empty()).then_some(name.as_str()),
+            classes: &classes,
+            state: widget_state,
+            inline_style: self.inline_style.as_ref(),
+            sub_control: Some((element, state)),
+        })
+    }
+
+    /// Get the default state of a sub-control.
+    ///
+    /// The sub-control is enabled like the widget, and otherwise not hovered,
+    /// pressed, focused, or checked; widgets set those flags from their own
+    /// hit testing.
+    pub fn sub_control_state(&self) -> WidgetStyleState {
+        WidgetStyleState {
+            enabled: self.is_effectively_enabled(),
+            ..Default::default()
+        }
+    }
+
+    /// Invalidate the cached style of this widget and its descendants.
+    ///
+    /// This also schedules a repaint.
+    pub fn invalidate_style(&mut self) {
+        widget_style::invalidate_widget_style(self.object_id());
+        self.update();
+    }
+
     // =========================================================================
     // Coordinate Mapping
     // =========================================================================
@@ -1402,7 +1700,16 @@ impl Drop for WidgetBase {
         // Clean up any timers owned by this widget.
         super::widget_timer::remove_timers_for_widget(id);
 
+        // Drop resolved styles and any subtree style engine rooted here.
+        widget_style::forget_widget(id);
+
         // ObjectBase::drop() will run after this, which removes the object from
         // the registry. The signal was emitted while the object was still valid.
     }
 }
+
+/// Strip the module path and generic arguments from a type name.
+fn short_type_name(full: &'static str) -> &'static str {
+    let base = full.split('<').next().unwrap_or(full);
+    base.rsplit("::").next().unwrap_or(base)
+}
@@ -28,13 +28,51 @@
 //! let mut line_edit = LineEdit::new();
 //! line_edit.set_completer(Some(completer));
 //! ```
-
-use horizon_lattice_core::Signal;
+//!
+//! # Match Modes
+//!
+//! By default, completions must start with the typed text. [`MatchMode`]
+//! selects substring or fuzzy matching instead, which rank the completions
+//! by how well they match:
+//!
+//! ```ignore
+//! use horizon_lattice::widget::completer::{Completer, MatchMode};
+//!
+//! // "opf" completes "open_file" and "OpenFolder".
+//! let completer = Completer::with_strings(commands).with_match_mode(MatchMode::Fuzzy);
+//! ```
+//!
+//! Completions can also come from a column of an [`ItemModel`] (see
+//! [`ColumnCompleterModel`]) or be fetched in the background (see
+//! [`AsyncCompletionSource`]).
+
+#[cfg(feature = "async-runtime")]
+use std::future::Future;
+use std::ops::Range;
+#[cfg(feature = "async-runtime")]
+use std::pin::Pin;
+use std::sync::Arc;
+#[cfg(feature = "async-runtime")]
+use std::sync::Weak;
+#[cfg(feature = "async-runtime")]
+use std::time::Duration;
+
+use parking_lot::Mutex;
+
+#[cfg(feature = "async-runtime")]
+use horizon_lattice_core::ConnectionId;
+#[cfg(feature = "async-runtime")]
+use horizon_lattice_core::async_runtime::{AsyncCancellationToken, AsyncRuntime};
+use horizon_lattice_core::{ObjectId, Signal};
 use horizon_lattice_render::{
-    Color, Font, FontFamily, FontSystem, GpuRenderer, Point, Rect, Renderer, Size, Stroke,
-    TextLayout, TextLayoutOptions, TextRenderer,
+    Color, DynRenderer, Font, FontFamily, FontSystem, Point, Rect, Renderer, Size, Stroke,
+    TextLayout, TextLayoutOptions, TextRenderer, TextSpan,
 };
 
+#[cfg(feature = "async-runtime")]
+use super::widget_timer::start_widget_timer;
+use crate::model::{ItemModel, ItemRole, ModelIndex};
+
 // ============================================================================
 // Case Sensitivity
 // ============================================================================
@@ -49,6 +87,203 @@ pub enum CaseSensitivity {
     CaseInsensitive,
 }
 
+// ============================================================================
+// Match Mode
+// ============================================================================
+
+/// Controls how completion candidates are matched against the typed text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    /// Candidates that start with the text (e.g., "app" matches "apple").
+    #[default]
+    Prefix,
+    /// Candidates that contain the text anywhere (e.g., "pp" matches "apple").
+    ///
+    /// Candidates where the text appears earlier are listed first.
+    Contains,
+    /// Candidates that contain the characters of the text in order, with
+    /// gaps allowed (e.g., "opf" matches "open_file").
+    ///
+    /// Candidates are ranked by how closely they match: consecutive
+    /// characters and characters at the start of words score higher.
+    Fuzzy,
+}
+
+/// A completion candidate that matched the typed text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionMatch {
+    /// The completion text.
+    pub text: String,
+    /// Ranking score. Matches with higher scores are listed first.
+    pub score: i32,
+    /// Byte ranges of `text` that matched the typed text, for highlighting.
+    pub highlights: Vec<Range<usize>>,
+}
+
+impl CompletionMatch {
+    /// Create a match with a zero score and no highlighted characters.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            score: 0,
+            highlights: Vec::new(),
+        }
+    }
+}
+
+/// Match a single completion candidate against the typed text.
+///
+/// Returns `None` if `candidate` doesn't match `query` in the given mode.
+/// An empty query matches every candidate.
+pub fn match_completion(
+    candidate: &str,
+    query: &str,
+    mode: MatchMode,
+    case_sensitivity: CaseSensitivity,
+) -> Option<CompletionMatch> {
+    if query.is_empty() {
+        return Some(CompletionMatch::new(candidate));
+    }
+
+    match mode {
+        MatchMode::Prefix => {
+            let end = match_at(candidate, 0, query, case_sensitivity)?;
+            Some(CompletionMatch {
+                text: candidate.to_string(),
+                score: 0,
+                highlights: std::iter::once(0..end).collect(),
+            })
+        }
+        MatchMode::Contains => {
+            candidate
+                .char_indices()
+                .enumerate()
+                .find_map(|(char_pos, (start, _))| {
+                    let end = match_at(candidate, start, query, case_sensitivity)?;
+                    Some(CompletionMatch {
+                        text: candidate.to_string(),
+                        score: -(char_pos as i32),
+                        highlights: std::iter::once(start..end).collect(),
+                    })
+                })
+        }
+        MatchMode::Fuzzy => fuzzy_match(candidate, query, case_sensitivity),
+    }
+}
+
+/// Match completion candidates against the typed text and rank them.
+///
+/// Returns the matching candidates, best match first. Candidates with equal
+/// scores keep their original order.
+pub fn rank_completions(
+    candidates: impl IntoIterator<Item = String>,
+    query: &str,
+    mode: MatchMode,
+    case_sensitivity: CaseSensitivity,
+) -> Vec<CompletionMatch> {
+    let mut matches: Vec<CompletionMatch> = candidates
+        .into_iter()
+        .filter_map(|candidate| match_completion(&candidate, query, mode, case_sensitivity))
+        .collect();
+    matches.sort_by_key(|m| std::cmp::Reverse(m.score));
+    matches
+}
+
+fn chars_match(a: char, b: char, case_sensitivity: CaseSensitivity) -> bool {
+    a == b
+        || (case_sensitivity == CaseSensitivity::CaseInsensitive
+            && a.to_lowercase().eq(b.to_lowercase()))
+}
+
+/// Returns the end of `query` if it matches `candidate` at byte `start`.
+fn match_at(
+    candidate: &str,
+    start: usize,
+    query: &str,
+    case_sensitivity: CaseSensitivity,
+) -> Option<usize> {
+    let mut chars = candidate[start..].char_indices();
+    let mut end = start;
+    for q in query.chars() {
+        let (offset, c) = chars.next()?;
+        if !chars_match(c, q, case_sensitivity) {
+            return None;
+        }
+        end = start + offset + c.len_utf8();
+    }
+    Some(end)
+}
+
+// Fuzzy scoring weights.
+const FUZZY_MATCH: i32 = 16;
+const FUZZY_WORD_START: i32 = 8;
+const FUZZY_CONSECUTIVE: i32 = 4;
+const FUZZY_GAP: i32 = 3;
+
+fn fuzzy_match(
+    candidate: &str,
+    query: &str,
+    case_sensitivity: CaseSensitivity,
+) -> Option<CompletionMatch> {
+    let chars: Vec<(usize, char)> = candidate.char_indices().collect();
+    let query: Vec<char> = query.chars().collect();
+
+    // Find where the earliest in-order match of the query ends...
+    let mut matched = 0;
+    let end = chars.iter().position(|&(_, c)| {
+        if chars_match(c, query[matched], case_sensitivity) {
+            matched += 1;
+        }
+        matched == query.len()
+    })?;
+
+    // ...then match backwards from there, which picks the tightest window.
+    let mut positions = vec![0; query.len()];
+    let mut remaining = query.len();
+    for pos in (0..=end).rev() {
+        if chars_match(chars[pos].1, query[remaining - 1], case_sensitivity) {
+            remaining -= 1;
+            positions[remaining] = pos;
+            if remaining == 0 {
+                break;
+            }
+        }
+    }
+
+    let mut score = -(positions[0] as i32);
+    let mut highlights: Vec<Range<usize>> = Vec::new();
+    for (i, &pos) in positions.iter().enumerate() {
+        score += FUZZY_MATCH;
+        let is_word_start = pos == 0 || {
+            let prev = chars[pos - 1].1;
+            !prev.is_alphanumeric() || (prev.is_lowercase() && chars[pos].1.is_uppercase())
+        };
+        if is_word_start {
+            score += FUZZY_WORD_START;
+        }
+        if i > 0 {
+            let gap = pos - positions[i - 1] - 1;
+            if gap == 0 {
+                score += FUZZY_CONSECUTIVE;
+            } else {
+                score -= FUZZY_GAP + gap as i32;
+            }
+        }
+
+        let (start, c) = chars[pos];
+        match highlights.last_mut() {
+            Some(last) if last.end == start => last.end = start + c.len_utf8(),
+            _ => highlights.push(start..start + c.len_utf8()),
+        }
+    }
+
+    Some(CompletionMatch {
+        text: candidate.to_string(),
+        score,
+        highlights,
+    })
+}
+
 // ============================================================================
 // Completer Model Trait
 // ============================================================================
@@ -69,6 +304,37 @@ pub trait CompleterModel: Send + Sync {
     /// A vector of matching completion strings, ordered by relevance.
     fn completions(&self, prefix: &str, case_sensitivity: CaseSensitivity) -> Vec<String>;
 
+    /// Get ranked completions matching the given text in the given mode.
+    ///
+    /// The default implementation uses [`completions`](Self::completions):
+    /// in [`MatchMode::Prefix`] it keeps the model's results and order, and
+    /// in the other modes it ranks all completions (those returned for an
+    /// empty prefix) with [`rank_completions`]. Override it to match against
+    /// a data source that can't list all of its items.
+    fn matches(
+        &self,
+        query: &str,
+        mode: MatchMode,
+        case_sensitivity: CaseSensitivity,
+    ) -> Vec<CompletionMatch> {
+        match mode {
+            MatchMode::Prefix => self
+                .completions(query, case_sensitivity)
+                .into_iter()
+                .map(|text| {
+                    match_completion(&text, query, mode, case_sensitivity)
+                        .unwrap_or_else(|| CompletionMatch::new(text))
+                })
+                .collect(),
+            _ => rank_completions(
+                self.completions("", case_sensitivity),
+                query,
+                mode,
+                case_sensitivity,
+            ),
+        }
+    }
+
     /// Get the total number of items in the model (before filtering).
     ///
     /// Returns `None` if the count is unknown or expensive to compute.
@@ -129,26 +395,7 @@ impl StringListModel {
 
 impl CompleterModel for StringListModel {
     fn completions(&self, prefix: &str, case_sensitivity: CaseSensitivity) -> Vec<String> {
-        if prefix.is_empty() {
-            return self.items.clone();
-        }
-
-        match case_sensitivity {
-            CaseSensitivity::CaseSensitive => self
-                .items
-                .iter()
-                .filter(|item| item.starts_with(prefix))
-                .cloned()
-                .collect(),
-            CaseSensitivity::CaseInsensitive => {
-                let prefix_lower = prefix.to_lowercase();
-                self.items
-                    .iter()
-                    .filter(|item| item.to_lowercase().starts_with(&prefix_lower))
-                    .cloned()
-                    .collect()
-            }
-        }
+        filter_by_prefix(self.items.iter().cloned(), prefix, case_sensitivity)
     }
 
     fn count(&self) -> Option<usize> {
@@ -174,6 +421,296 @@ impl From<Vec<&str>> for StringListModel {
     }
 }
 
+/// Keep the items that start with `prefix`.
+fn filter_by_prefix(
+    items: impl Iterator<Item = String>,
+    prefix: &str,
+    case_sensitivity: CaseSensitivity,
+) -> Vec<String> {
+    if prefix.is_empty() {
+        return items.collect();
+    }
+
+    match case_sensitivity {
+        CaseSensitivity::CaseSensitive => items.filter(|item| item.starts_with(prefix)).collect(),
+        CaseSensitivity::CaseInsensitive => {
+            let prefix_lower = prefix.to_lowercase();
+            items
+                .filter(|item| item.to_lowercase().starts_with(&prefix_lower))
+                .collect()
+        }
+    }
+}
+
+// ============================================================================
+// Item Model Column
+// ============================================================================
+
+/// A completer model that completes from one column of an [`ItemModel`].
+///
+/// The completions are the text of the given role (by default
+/// [`ItemRole::Display`]) of the model's top-level rows, read each time the
+/// completions are requested, so changes to the model show up immediately.
+///
+/// # Example
+///
+/// ```ignore
+/// use horizon_lattice::widget::completer::{ColumnCompleterModel, Completer};
+///
+/// // Complete customer names from the second column of a table model.
+/// let completer = Completer::new(Box::new(ColumnCompleterModel::new(customers, 1)));
+/// ```
+pub struct ColumnCompleterModel {
+    model: Arc<dyn ItemModel>,
+    column: usize,
+    role: ItemRole,
+}
+
+impl ColumnCompleterModel {
+    /// Create a completer model for `column` of `model`.
+    pub fn new(model: Arc<dyn ItemModel>, column: usize) -> Self {
+        Self {
+            model,
+            column,
+            role: ItemRole::Display,
+        }
+    }
+
+    /// Set the role to read completions from using builder pattern.
+    pub fn with_role(mut self, role: ItemRole) -> Self {
+        self.role = role;
+        self
+    }
+
+    /// Get the item model.
+    pub fn model(&self) -> &Arc<dyn ItemModel> {
+        &self.model
+    }
+
+    /// Get the column that completions are read from.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Set the column that completions are read from.
+    pub fn set_column(&mut self, column: usize) {
+        self.column = column;
+    }
+
+    /// Get the role that completions are read from.
+    pub fn role(&self) -> ItemRole {
+        self.role
+    }
+
+    /// Set the role that completions are read from.
+    pub fn set_role(&mut self, role: ItemRole) {
+        self.role = role;
+    }
+
+    fn items(&self) -> impl Iterator<Item = String> + '_ {
+        let root = ModelIndex::invalid();
+        (0..self.model.row_count(&root)).filter_map(move |row| {
+            let index = self.model.index(row, self.column, &root);
+            self.model.data(&index, self.role).into_string()
+        })
+    }
+}
+
+impl CompleterModel for ColumnCompleterModel {
+    fn completions(&self, prefix: &str, case_sensitivity: CaseSensitivity) -> Vec<String> {
+        filter_by_prefix(self.items(), prefix, case_sensitivity)
+    }
+
+    fn count(&self) -> Option<usize> {
+        Some(self.model.row_count(&ModelIndex::invalid()))
+    }
+}
+
+impl std::fmt::Debug for ColumnCompleterModel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColumnCompleterModel")
+            .field("column", &self.column)
+            .field("role", &self.role)
+            .finish()
+    }
+}
+
+// ============================================================================
+// Async Completion Source
+// ============================================================================
+
+#[cfg(feature = "async-runtime")]
+type CompletionFetcher =
+    Arc<dyn Fn(String) -> Pin<Box<dyn Future<Output = Vec<String>> + Send>> + Send + Sync>;
+
+#[cfg(feature = "async-runtime")]
+struct AsyncSourceState {
+    debounce: Duration,
+    /// Incremented on every request so that results of older requests are dropped.
+    generation: u64,
+    /// Cancels the request in flight.
+    token: Option<AsyncCancellationToken>,
+    pending: bool,
+    /// The latest results, with the query they answer.
+    results: Option<(String, Vec<String>)>,
+}
+
+/// Completions fetched in the background, such as from a server.
+///
+/// Each [`request`](Self::request) waits for the debounce interval and then
+/// runs the fetch future on the global [`AsyncRuntime`]. A newer request
+/// cancels the one in flight, so typing quickly only fetches completions for
+/// the text the user settled on, and results for outdated text are never
+/// shown. When results arrive, [`results_ready`](Self::results_ready) is
+/// emitted on the UI thread with the query they answer.
+///
+/// Attach a source to a [`Completer`] with [`Completer::set_async_source`];
+/// the completer then requests completions as the user types and shows the
+/// results once they arrive.
+///
+/// # Example
+///
+/// ```ignore
+/// use horizon_lattice::widget::completer::{AsyncCompletionSource, Completer, MatchMode};
+///
+/// let source = AsyncCompletionSource::new(|query: String| async move {
+///     search_server(&query).await.unwrap_or_default()
+/// });
+/// let completer = Completer::with_strings(Vec::new())
+///     .with_match_mode(MatchMode::Fuzzy)
+///     .with_async_source(source);
+/// ```
+#[cfg(feature = "async-runtime")]
+pub struct AsyncCompletionSource {
+    fetch: CompletionFetcher,
+    state: Mutex<AsyncSourceState>,
+    this: Weak<Self>,
+
+    /// Signal emitted on the UI thread when results for a query arrive.
+    pub results_ready: Signal<String>,
+}
+
+#[cfg(feature = "async-runtime")]
+impl AsyncCompletionSource {
+    /// Default delay between the last request and the start of the fetch.
+    pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(250);
+
+    /// Create a source that fetches completions for a query with `fetch`.
+    pub fn new<F, Fut>(fetch: F) -> Arc<Self>
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Vec<String>> + Send + 'static,
+    {
+        Arc::new_cyclic(|this| Self {
+            fetch: Arc::new(move |query| Box::pin(fetch(query))),
+            state: Mutex::new(AsyncSourceState {
+                debounce: Self::DEFAULT_DEBOUNCE,
+                generation: 0,
+                token: None,
+                pending: false,
+                results: None,
+            }),
+            this: this.clone(),
+            results_ready: Signal::new(),
+        })
+    }
+
+    /// Get the delay between the last request and the start of the fetch.
+    pub fn debounce(&self) -> Duration {
+        self.state.lock().debounce
+    }
+
+    /// Set the delay between the last request and the start of the fetch.
+    pub fn set_debounce(&self, debounce: Duration) {
+        self.state.lock().debounce = debounce;
+    }
+
+    /// Check if a request is waiting for its results.
+    pub fn is_pending(&self) -> bool {
+        self.state.lock().pending
+    }
+
+    /// Request completions for `query`, cancelling any earlier request.
+    pub fn request(&self, query: &str) {
+        let (generation, token, debounce) = self.begin_request();
+        let fetch = self.fetch.clone();
+        let query = query.to_string();
+        let this = self.this.clone();
+
+        AsyncRuntime::global().spawn_with_callback(
+            {
+                let query = query.clone();
+                async move {
+                    tokio::select! {
+                        _ = token.cancelled() => None,
+                        results = async {
+                            tokio::time::sleep(debounce).await;
+                            fetch(query).await
+                        } => Some(results),
+                    }
+                }
+            },
+            move |results| {
+                if let Some(results) = results
+                    && let Some(this) = this.upgrade()
+                {
+                    this.finish_request(generation, query, results);
+                }
+            },
+        );
+    }
+
+    /// Cancel the request in flight, if any.
+    pub fn cancel(&self) {
+        let mut state = self.state.lock();
+        state.generation += 1;
+        state.pending = false;
+        if let Some(token) = state.token.take() {
+            token.cancel();
+        }
+    }
+
+    /// Take the latest results and the query they answer.
+    pub fn take_results(&self) -> Option<(String, Vec<String>)> {
+        self.state.lock().results.take()
+    }
+
+    fn begin_request(&self) -> (u64, AsyncCancellationToken, Duration) {
+        let mut state = self.state.lock();
+        state.generation += 1;
+        state.pending = true;
+        let token = AsyncCancellationToken::new();
+        if let Some(previous) = state.token.replace(token.clone()) {
+            previous.cancel();
+        }
+        (state.generation, token, state.debounce)
+    }
+
+    fn finish_request(&self, generation: u64, query: String, results: Vec<String>) {
+        {
+            let mut state = self.state.lock();
+            if state.generation != generation {
+                return;
+            }
+            state.pending = false;
+            state.token = None;
+            state.results = Some((query.clone(), results));
+        }
+        self.results_ready.emit(query);
+    }
+}
+
+#[cfg(feature = "async-runtime")]
+impl std::fmt::Debug for AsyncCompletionSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.state.lock();
+        f.debug_struct("AsyncCompletionSource")
+            .field("debounce", &state.debounce)
+            .field("pending", &state.pending)
+            .finish()
+    }
+}
+
 // ============================================================================
 // Completer Popup State
 // ============================================================================
@@ -185,6 +722,8 @@ pub(crate) struct CompleterPopupState {
     pub visible: bool,
     /// Current filtered completions.
     pub completions: Vec<String>,
+    /// Matched byte ranges of each completion, parallel to `completions`.
+    pub highlights: Vec<Vec<Range<usize>>>,
     /// Currently selected index (-1 means no selection).
     pub selected_index: i32,
     /// The prefix used for current completions.
@@ -206,6 +745,7 @@ impl Default for CompleterPopupState {
         Self {
             visible: false,
             completions: Vec::new(),
+            highlights: Vec::new(),
             selected_index: -1,
             current_prefix: String::new(),
             position: Point::new(0.0, 0.0),
@@ -239,6 +779,15 @@ impl CompleterPopupState {
         }
     }
 
+    /// Replace the completions with `matches`.
+    pub fn set_matches(&mut self, matches: Vec<CompletionMatch>) {
+        (self.completions, self.highlights) =
+            matches.into_iter().map(|m| (m.text, m.highlights)).unzip();
+        self.selected_index = if self.completions.is_empty() { -1 } else { 0 };
+        self.scroll_offset = 0;
+        self.size.height = self.calculate_height();
+    }
+
     /// Calculate popup height based on number of items.
     pub fn calculate_height(&self) -> f32 {
         let visible_count = self.completions.len().min(self.max_visible_items);
@@ -258,8 +807,10 @@ impl CompleterPopupState {
 ///
 /// # Features
 ///
-/// - Prefix-based completion matching
+/// - Prefix, substring and fuzzy matching (see [`MatchMode`]), with the
+///   matched characters highlighted in the popup
 /// - Case sensitivity control
+/// - Completions fetched in the background (see [`AsyncCompletionSource`])
 /// - Popup list of suggestions with keyboard navigation
 /// - Customizable completion models
 ///
@@ -274,6 +825,20 @@ pub struct Completer {
     /// Case sensitivity for matching.
     case_sensitivity: CaseSensitivity,
 
+    /// How completions are matched against the typed text.
+    match_mode: MatchMode,
+
+    /// Background source of completions, if any.
+    #[cfg(feature = "async-runtime")]
+    async_source: Option<Arc<AsyncCompletionSource>>,
+
+    /// Connection to the async source's `results_ready` signal.
+    #[cfg(feature = "async-runtime")]
+    async_connection: Option<ConnectionId>,
+
+    /// Widget notified with a timer event when async results arrive.
+    owner: Arc<Mutex<Option<ObjectId>>>,
+
     /// Minimum characters before showing completions.
     min_chars: usize,
 
@@ -315,6 +880,12 @@ impl Completer {
         Self {
             model,
             case_sensitivity: CaseSensitivity::CaseInsensitive,
+            match_mode: MatchMode::Prefix,
+            #[cfg(feature = "async-runtime")]
+            async_source: None,
+            #[cfg(feature = "async-runtime")]
+            async_connection: None,
+            owner: Arc::new(Mutex::new(None)),
             min_chars: 1,
             popup_state: CompleterPopupState::default(),
             font: Font::new(FontFamily::SansSerif, 14.0),
@@ -334,6 +905,11 @@ impl Completer {
         Self::new(Box::new(StringListModel::new(items)))
     }
 
+    /// Create a completer that completes from `column` of an item model.
+    pub fn with_item_model(model: Arc<dyn ItemModel>, column: usize) -> Self {
+        Self::new(Box::new(ColumnCompleterModel::new(model, column)))
+    }
+
     // =========================================================================
     // Configuration
     // =========================================================================
@@ -354,6 +930,22 @@ impl Completer {
         self
     }
 
+    /// Get the match mode.
+    pub fn match_mode(&self) -> MatchMode {
+        self.match_mode
+    }
+
+    /// Set how completions are matched against the typed text.
+    pub fn set_match_mode(&mut self, mode: MatchMode) {
+        self.match_mode = mode;
+    }
+
+    /// Set the match mode using builder pattern.
+    pub fn with_match_mode(mut self, mode: MatchMode) -> Self {
+        self.match_mode = mode;
+        self
+    }
+
     /// Get the minimum number of characters before showing completions.
     pub fn min_chars(&self) -> usize {
         self.min_chars
@@ -400,9 +992,57 @@ impl Completer {
         self.model = model;
         // Clear current completions as model has changed
         self.popup_state.completions.clear();
+        self.popup_state.highlights.clear();
         self.popup_state.selected_index = -1;
     }
 
+    /// Get the async completion source, if any.
+    #[cfg(feature = "async-runtime")]
+    pub fn async_source(&self) -> Option<&Arc<AsyncCompletionSource>> {
+        self.async_source.as_ref()
+    }
+
+    /// Set a source that fetches completions in the background.
+    ///
+    /// While a source is set, the completer requests completions from it as
+    /// the user types, in addition to matching its model. Results replace
+    /// the popup's completions when they arrive, if the text hasn't changed
+    /// in the meantime.
+    #[cfg(feature = "async-runtime")]
+    pub fn set_async_source(&mut self, source: Option<Arc<AsyncCompletionSource>>) {
+        if let Some(old) = self.async_source.take() {
+            old.cancel();
+            if let Some(id) = self.async_connection.take() {
+                old.results_ready.disconnect(id);
+            }
+        }
+
+        if let Some(source) = &source {
+            let owner = self.owner.clone();
+            self.async_connection = Some(source.results_ready.connect(move |_| {
+                if let Some(widget) = *owner.lock() {
+                    start_widget_timer(widget, Duration::ZERO);
+                }
+            }));
+        }
+        self.async_source = source;
+    }
+
+    /// Set the async completion source using builder pattern.
+    #[cfg(feature = "async-runtime")]
+    pub fn with_async_source(mut self, source: Arc<AsyncCompletionSource>) -> Self {
+        self.set_async_source(Some(source));
+        self
+    }
+
+    /// Set the widget that receives a timer event when async results arrive.
+    ///
+    /// The widget should call [`apply_async_results`](Self::apply_async_results)
+    /// when it handles the event.
+    pub(crate) fn set_owner(&mut self, widget: Option<ObjectId>) {
+        *self.owner.lock() = widget;
+    }
+
     // =========================================================================
     // Popup Control
     // =========================================================================
@@ -420,17 +1060,18 @@ impl Completer {
     pub fn show_popup(&mut self, prefix: &str, anchor_rect: Rect) {
         self.update_completions(prefix);
 
-        if self.popup_state.completions.is_empty() {
-            self.hide_popup();
-            return;
-        }
-
         // Position below the anchor
         self.popup_state.position = Point::new(anchor_rect.origin.x, anchor_rect.bottom());
         self.popup_state.size = Size::new(
             anchor_rect.size.width.max(150.0),
             self.popup_state.calculate_height(),
         );
+
+        if self.popup_state.completions.is_empty() {
+            self.hide_popup();
+            return;
+        }
+
         self.popup_state.visible = true;
         self.popup_state.selected_index = 0;
         self.popup_state.scroll_offset = 0;
@@ -453,19 +1094,61 @@ impl Completer {
     pub fn update_completions(&mut self, prefix: &str) {
         if prefix.len() < self.min_chars {
             self.popup_state.completions.clear();
+            self.popup_state.highlights.clear();
             self.popup_state.current_prefix.clear();
+            #[cfg(feature = "async-runtime")]
+            if let Some(source) = &self.async_source {
+                source.cancel();
+            }
             return;
         }
 
         self.popup_state.current_prefix = prefix.to_string();
-        self.popup_state.completions = self.model.completions(prefix, self.case_sensitivity);
-        self.popup_state.selected_index = if self.popup_state.completions.is_empty() {
-            -1
-        } else {
-            0
+        let matches = self
+            .model
+            .matches(prefix, self.match_mode, self.case_sensitivity);
+        self.popup_state.set_matches(matches);
+
+        #[cfg(feature = "async-runtime")]
+        if let Some(source) = &self.async_source {
+            source.request(prefix);
+        }
+    }
+
+    /// Show the latest results of the async source in the popup.
+    ///
+    /// Results for text other than the current text are dropped. Returns
+    /// true if the popup changed.
+    #[cfg(feature = "async-runtime")]
+    pub fn apply_async_results(&mut self) -> bool {
+        let Some((query, results)) = self.async_source.as_ref().and_then(|s| s.take_results())
+        else {
+            return false;
         };
-        self.popup_state.scroll_offset = 0;
-        self.popup_state.size.height = self.popup_state.calculate_height();
+        if query != self.popup_state.current_prefix {
+            return false;
+        }
+
+        // The source may match differently than the completer (e.g. on the
+        // server), so its results are kept in order and only highlighted.
+        let matches = results
+            .into_iter()
+            .map(|text| {
+                match_completion(&text, &query, self.match_mode, self.case_sensitivity)
+                    .unwrap_or_else(|| CompletionMatch::new(text))
+            })
+            .collect();
+        self.popup_state.set_matches(matches);
+
+        if self.popup_state.completions.is_empty() {
+            let was_visible = self.popup_state.visible;
+            self.hide_popup();
+            return was_visible;
+        }
+
+        self.popup_state.visible = true;
+        self.emit_highlighted();
+        true
     }
 
     /// Complete the text and return the selected completion.
@@ -666,7 +1349,7 @@ impl Completer {
     ///
     /// This should be called during the widget's paint phase if a completer
     /// is attached.
-    pub fn paint(&self, renderer: &mut GpuRenderer) {
+    pub fn paint(&self, renderer: &mut dyn DynRenderer) {
         if !self.popup_state.visible {
             return;
         }
@@ -710,13 +1393,39 @@ impl Completer {
                 renderer.fill_rect(item_rect, self.hover_color);
             }
 
-            // Draw text
-            let layout = TextLayout::with_options(
-                &mut font_system,
-                item,
-                &self.font,
-                TextLayoutOptions::new(),
-            );
+            // Draw text, with the matched characters in bold
+            let highlights = self
+                .popup_state
+                .highlights
+                .get(actual_idx)
+                .map_or(&[][..], Vec::as_slice);
+            let layout = if highlights.is_empty() {
+                TextLayout::with_options(
+                    &mut font_system,
+                    item,
+                    &self.font,
+                    TextLayoutOptions::new(),
+                )
+            } else {
+                let mut spans = Vec::new();
+                let mut pos = 0;
+                for range in highlights {
+                    if range.start > pos {
+                        spans.push(TextSpan::new(&item[pos..range.start]));
+                    }
+                    spans.push(TextSpan::new(&item[range.clone()]).bold(&self.font));
+                    pos = range.end;
+                }
+                if pos < item.len() {
+                    spans.push(TextSpan::new(&item[pos..]));
+                }
+                TextLayout::rich_text(
+                    &mut font_system,
+                    &spans,
+                    &self.font,
+                    TextLayoutOptions::new(),
+                )
+            };
 
             let text_x = item_rect.origin.x + 4.0;
             let text_y =
@@ -803,10 +1512,18 @@ impl Completer {
     }
 }
 
+#[cfg(feature = "async-runtime")]
+impl Drop for Completer {
+    fn drop(&mut self) {
+        self.set_async_source(None);
+    }
+}
+
 impl std::fmt::Debug for Completer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Completer")
             .field("case_sensitivity", &self.case_sensitivity)
+            .field("match_mode", &self.match_mode)
             .field("min_chars", &self.min_chars)
             .field("popup_visible", &self.popup_state.visible)
             .field("completions_count", &self.popup_state.completions.len())
@@ -946,6 +1663,153 @@ mod tests {
         assert_eq!(state.visible_range(), 2..5);
     }
 
+    #[test]
+    fn test_match_modes() {
+        let ci = CaseSensitivity::CaseInsensitive;
+
+        let m = match_completion("Apple", "ap", MatchMode::Prefix, ci).unwrap();
+        assert_eq!(m.highlights, vec![0..2]);
+        assert!(match_completion("pineapple", "ap", MatchMode::Prefix, ci).is_none());
+
+        let m = match_completion("pineapple", "ap", MatchMode::Contains, ci).unwrap();
+        assert_eq!(m.highlights, vec![4..6]);
+        assert!(
+            match_completion(
+                "pineapple",
+                "Ap",
+                MatchMode::Contains,
+                CaseSensitivity::CaseSensitive
+            )
+            .is_none()
+        );
+
+        let m = match_completion("open_file", "opf", MatchMode::Fuzzy, ci).unwrap();
+        assert_eq!(m.highlights, vec![0..2, 5..6]);
+        assert!(match_completion("open_file", "fo", MatchMode::Fuzzy, ci).is_none());
+
+        // Multi-byte characters are highlighted on character boundaries.
+        let m = match_completion("café au lait", "éa", MatchMode::Fuzzy, ci).unwrap();
+        assert_eq!(&m.text[m.highlights[0].clone()], "é");
+    }
+
+    #[test]
+    fn test_fuzzy_ranking() {
+        let candidates = ["fabric", "foo_bar", "xfb", "FooBar", "unrelated"].map(String::from);
+        let ranked = rank_completions(
+            candidates,
+            "fb",
+            MatchMode::Fuzzy,
+            CaseSensitivity::CaseInsensitive,
+        );
+        let texts: Vec<&str> = ranked.iter().map(|m| m.text.as_str()).collect();
+        // Matches at word starts, including camelCase humps, rank first.
+        assert_eq!(texts, vec!["FooBar", "foo_bar", "fabric", "xfb"]);
+
+        let candidates = ["lapp", "apple", "snapping"].map(String::from);
+        let ranked = rank_completions(
+            candidates,
+            "app",
+            MatchMode::Contains,
+            CaseSensitivity::CaseInsensitive,
+        );
+        let texts: Vec<&str> = ranked.iter().map(|m| m.text.as_str()).collect();
+        assert_eq!(texts, vec!["apple", "lapp", "snapping"]);
+    }
+
+    #[test]
+    fn test_completer_match_mode() {
+        let model = StringListModel::from(vec!["open_file", "close_file", "reopen"]);
+        let mut completer = Completer::new(Box::new(model)).with_match_mode(MatchMode::Contains);
+
+        completer.update_completions("open");
+        assert_eq!(
+            completer.popup_state.completions,
+            vec!["open_file", "reopen"]
+        );
+        assert_eq!(
+            completer.popup_state.highlights,
+            vec![vec![0..4], vec![2..6]]
+        );
+
+        completer.set_match_mode(MatchMode::Fuzzy);
+        completer.update_completions("cf");
+        assert_eq!(completer.popup_state.completions, vec!["close_file"]);
+        assert_eq!(completer.popup_state.highlights, vec![vec![0..1, 6..7]]);
+    }
+
+    #[test]
+    fn test_column_completer_model() {
+        use crate::model::{ItemData, SimpleTableModel};
+
+        let table = SimpleTableModel::from_data(vec![
+            vec![ItemData::from("1"), ItemData::from("Alice")],
+            vec![ItemData::from("2"), ItemData::from("Bob")],
+            vec![ItemData::from("3"), ItemData::from("Alfred")],
+        ]);
+        let model = ColumnCompleterModel::new(Arc::new(table), 1);
+        assert_eq!(model.count(), Some(3));
+        assert_eq!(
+            model.completions("al", CaseSensitivity::CaseInsensitive),
+            vec!["Alice", "Alfred"]
+        );
+        let matches = model.matches("ob", MatchMode::Contains, CaseSensitivity::CaseInsensitive);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].text, "Bob");
+    }
+
+    #[test]
+    #[cfg(feature = "async-runtime")]
+    fn test_async_source_drops_stale_results() {
+        let source = AsyncCompletionSource::new(|query: String| async move { vec![query] });
+        let (first, first_token, _) = source.begin_request();
+        let (second, _, _) = source.begin_request();
+        assert!(first_token.is_cancelled());
+        assert!(source.is_pending());
+
+        source.finish_request(first, "a".into(), vec!["apple".into()]);
+        assert!(source.take_results().is_none());
+        assert!(source.is_pending());
+
+        source.finish_request(second, "ap".into(), vec!["apple".into()]);
+        assert_eq!(
+            source.take_results(),
+            Some(("ap".to_string(), vec!["apple".to_string()]))
+        );
+        assert!(!source.is_pending());
+    }
+
+    #[test]
+    #[cfg(feature = "async-runtime")]
+    fn test_completer_applies_async_results() {
+        let source = AsyncCompletionSource::new(|_: String| async { Vec::new() });
+        source.set_debounce(Duration::from_secs(60));
+        let mut completer = Completer::with_strings(Vec::new()).with_async_source(source.clone());
+
+        let anchor = Rect::new(0.0, 0.0, 100.0, 30.0);
+        completer.show_popup("ap", anchor);
+        assert!(!completer.is_popup_visible());
+        assert!(source.is_pending());
+
+        // Results for text the user has typed past are dropped.
+        let generation = source.state.lock().generation;
+        source.finish_request(generation, "a".into(), vec!["avocado".into()]);
+        assert!(!completer.apply_async_results());
+
+        source.finish_request(
+            generation,
+            "ap".into(),
+            vec!["grape".into(), "apricot".into()],
+        );
+        assert!(completer.apply_async_results());
+        assert!(completer.is_popup_visible());
+        assert_eq!(completer.popup_state.completions, vec!["grape", "apricot"]);
+        assert_eq!(completer.popup_state.highlights, vec![vec![], vec![0..2]]);
+
+        // Text shorter than the minimum cancels the request in flight.
+        completer.update_completions("");
+        assert!(!source.is_pending());
+    }
+
     #[test]
     fn test_popup_state_ensure_selected_visible() {
         let mut state = CompleterPopupState::default();
@@ -81,6 +81,8 @@ pub mod mime {
     pub const IMAGE_RGBA: &str = "image/x-rgba";
     /// Custom application data prefix.
     pub const APPLICATION_PREFIX: &str = "application/x-horizon-lattice-";
+    /// Items dragged out of an item model (see `ItemModel::mime_data`).
+    pub const MODEL_ITEMS: &str = "application/x-horizon-lattice-model-items";
 }
 
 /// Actions that can be performed during a drop operation.
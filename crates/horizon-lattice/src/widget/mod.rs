@@ -157,6 +157,7 @@ mod modal;
 pub mod mouse;
 mod painting;
 mod shortcut;
+pub mod style;
 pub mod touch;
 mod traits;
 pub mod validator;
@@ -14,7 +14,13 @@
 //! 3. At frame time, `FrameRenderer::render_frame()` is called
 //! 4. Widgets are painted in depth-first preorder (parents before children)
 //! 5. Opaque widgets cause parent regions underneath them to be skipped
-//! 6. Dirty regions are clipped to minimize overdraw
+//! 6. Dirty regions are clipped to minimize overdraw. Separate dirty areas
+//!    stay separate: [`RepaintManager::dirty_region`] collects them into a
+//!    [`Region`], and [`FrameRenderer::render_frame_region`] passes each of its
+//!    rectangles to the renderer as damage, which it redraws through its own
+//!    scissor rectangle
+//! 7. Widgets with running style transitions or animations are scheduled for
+//!    another repaint, counted in [`FrameStats::animations_running`]
 //!
 //! # Example
 //!
@@ -32,11 +38,12 @@
 use std::collections::HashMap;
 
 use horizon_lattice_core::ObjectId;
-use horizon_lattice_render::{GpuRenderer, Point, Rect, Renderer};
+use horizon_lattice_render::{GpuRenderer, Point, Rect, Region, Renderer};
 
-use super::WidgetAccess;
 use super::events::{PaintEvent, WidgetEvent};
+use super::style::is_style_animating;
 use super::traits::PaintContext;
+use super::{WidgetAccess, WidgetBase};
 
 /// Manages repaint requests and coalesces updates.
 ///
@@ -118,6 +125,20 @@ impl RepaintManager {
         self.pending.iter().map(|(&id, &rect)| (id, rect))
     }
 
+    /// Get the combined dirty region of all pending repaints.
+    ///
+    /// Dirty areas of different widgets aren't merged into one bounding box,
+    /// so distant updates stay cheap to repaint. Check
+    /// [`needs_full_repaint`](Self::needs_full_repaint) first; the region
+    /// doesn't include a full repaint.
+    pub fn dirty_region(&self) -> Region {
+        let mut region = Region::new();
+        for rect in self.pending.values() {
+            region.union_rect(*rect);
+        }
+        region
+    }
+
     /// Get the number of pending repaints.
     pub fn pending_count(&self) -> usize {
         self.pending.len()
@@ -141,6 +162,11 @@ pub struct FrameStats {
     pub widgets_skipped: u32,
     /// Number of regions skipped due to opaque widget optimization.
     pub opaque_optimizations: u32,
+    /// Number of painted widgets whose style is still animating.
+    ///
+    /// These widgets are already scheduled for repaint; when this is non-zero
+    /// the caller should request another frame.
+    pub animations_running: u32,
 }
 
 /// Renders widget trees with proper paint order and dirty region handling.
@@ -171,15 +197,16 @@ impl FrameRenderer {
     ///
     /// * `storage` - Widget storage implementing `WidgetAccess`.
     /// * `root_id` - The root widget to start painting from.
-    /// * `renderer` - The GPU renderer to paint with.
+    /// * `renderer` - The renderer to paint with, such as a
+    ///   [`GpuRenderer`] or a [`SoftwareRenderer`](horizon_lattice_render::SoftwareRenderer).
     ///
     /// # Returns
     ///
     /// Statistics about the frame rendering.
-    pub fn render_frame<S: WidgetAccess>(
+    pub fn render_frame<S: WidgetAccess, R: Renderer>(
         storage: &mut S,
         root_id: ObjectId,
-        renderer: &mut GpuRenderer,
+        renderer: &mut R,
     ) -> FrameStats {
         Self::render_frame_with_alt(storage, root_id, renderer, false)
     }
@@ -193,16 +220,16 @@ impl FrameRenderer {
     ///
     /// * `storage` - Widget storage implementing `WidgetAccess`.
     /// * `root_id` - The root widget to start painting from.
-    /// * `renderer` - The GPU renderer to paint with.
+    /// * `renderer` - The renderer to paint with.
     /// * `alt_held` - Whether the Alt key is currently held (for mnemonic display).
     ///
     /// # Returns
     ///
     /// Statistics about the frame rendering.
-    pub fn render_frame_with_alt<S: WidgetAccess>(
+    pub fn render_frame_with_alt<S: WidgetAccess, R: Renderer>(
         storage: &mut S,
         root_id: ObjectId,
-        renderer: &mut GpuRenderer,
+        renderer: &mut R,
         alt_held: bool,
     ) -> FrameStats {
         let mut stats = FrameStats::default();
@@ -228,18 +255,23 @@ impl FrameRenderer {
     /// Render a frame with a specific dirty region.
     ///
     /// Only widgets that intersect with the dirty region will be painted.
+    /// Each rectangle of the region is added to the renderer's damage, and
+    /// the renderer redraws each of them through its own scissor rectangle,
+    /// so the space between them is left alone. The whole frame is still
+    /// presented.
     ///
     /// # Arguments
     ///
     /// * `storage` - Widget storage implementing `WidgetAccess`.
     /// * `root_id` - The root widget to start painting from.
     /// * `renderer` - The GPU renderer to paint with.
-    /// * `dirty_region` - The region that needs repainting (in window coordinates).
+    /// * `dirty_region` - The region that needs repainting (in window coordinates),
+    ///   such as a [`Rect`] or [`RepaintManager::dirty_region`].
     pub fn render_frame_region<S: WidgetAccess>(
         storage: &mut S,
         root_id: ObjectId,
         renderer: &mut GpuRenderer,
-        dirty_region: Rect,
+        dirty_region: impl Into<Region>,
     ) -> FrameStats {
         Self::render_frame_region_with_alt(storage, root_id, renderer, dirty_region, false)
     }
@@ -260,14 +292,24 @@ impl FrameRenderer {
         storage: &mut S,
         root_id: ObjectId,
         renderer: &mut GpuRenderer,
-        dirty_region: Rect,
+        dirty_region: impl Into<Region>,
         alt_held: bool,
     ) -> FrameStats {
         let mut stats = FrameStats::default();
+        let dirty_region = dirty_region.into();
+        let Some(bounds) = dirty_region.bounding_rect() else {
+            return stats;
+        };
+
+        // The renderer scissors each dirty rectangle separately
+        for rect in dirty_region.rects() {
+            renderer.add_damage(*rect);
+        }
 
-        // Set up clipping for the dirty region
+        // Clip to the bounding box on the CPU side; the damage rectangles
+        // above do the exact clipping when the renderer draws the frame
         renderer.save();
-        renderer.clip_rect(dirty_region);
+        renderer.clip_rect(bounds);
 
         // Collect the paint order
         let paint_order = Self::collect_paint_order(storage, root_id);
@@ -321,11 +363,19 @@ impl FrameRenderer {
         }
     }
 
+    /// Schedule another repaint if the widget's style is still animating.
+    fn continue_style_animation(base: &mut WidgetBase, stats: &mut FrameStats) {
+        if is_style_animating(base.object_id()) {
+            base.update();
+            stats.animations_running += 1;
+        }
+    }
+
     /// Paint a single widget and its subtree.
-    fn paint_widget<S: WidgetAccess>(
+    fn paint_widget<S: WidgetAccess, R: Renderer>(
         storage: &mut S,
         widget_id: ObjectId,
-        renderer: &mut GpuRenderer,
+        renderer: &mut R,
         parent_offset: Point,
         alt_held: bool,
         stats: &mut FrameStats,
@@ -378,6 +428,7 @@ impl FrameRenderer {
             // Clear the repaint flag
             if let Some(widget) = storage.get_widget_mut(widget_id) {
                 widget.widget_base_mut().clear_repaint_flag();
+                Self::continue_style_animation(widget.widget_base_mut(), stats);
             }
 
             stats.widgets_painted += 1;
@@ -397,12 +448,12 @@ impl FrameRenderer {
     }
 
     /// Paint a widget with dirty region clipping.
-    fn paint_widget_with_clip<S: WidgetAccess>(
+    fn paint_widget_with_clip<S: WidgetAccess, R: Renderer>(
         storage: &mut S,
         widget_id: ObjectId,
-        renderer: &mut GpuRenderer,
+        renderer: &mut R,
         parent_offset: Point,
-        dirty_region: &Rect,
+        dirty_region: &Region,
         alt_held: bool,
         stats: &mut FrameStats,
     ) {
@@ -437,7 +488,9 @@ impl FrameRenderer {
         );
 
         // Check if widget intersects with dirty region
-        let Some(intersect) = window_rect.intersect(dirty_region) else {
+        let mut widget_dirty = dirty_region.clone();
+        widget_dirty.intersect_rect(&window_rect);
+        let Some(intersect) = widget_dirty.bounding_rect() else {
             stats.widgets_skipped += 1;
             return;
         };
@@ -477,6 +530,7 @@ impl FrameRenderer {
 
             // Clear repaint flag
             widget.widget_base_mut().clear_repaint_flag();
+            Self::continue_style_animation(widget.widget_base_mut(), stats);
         }
 
         renderer.restore();
@@ -545,31 +599,16 @@ impl FrameRenderer {
             return vec![parent_rect];
         }
 
-        // For now, use simple subtraction (just return parent rect if no full coverage)
-        // A more sophisticated implementation would compute actual region difference
         Self::subtract_rects(parent_rect, &opaque_rects)
     }
 
-    /// Simple rect subtraction. Returns regions of `rect` not covered by `subtract`.
-    ///
-    /// This is a simplified implementation that handles common cases.
-    /// For fully general region subtraction, a more complex algorithm would be needed.
+    /// Rect subtraction. Returns regions of `rect` not covered by `subtract`.
     fn subtract_rects(rect: Rect, subtract: &[Rect]) -> Vec<Rect> {
-        // For now, check if any subtraction rect fully covers the rect
+        let mut region = Region::from_rect(rect);
         for sub in subtract {
-            if sub.origin.x <= rect.origin.x
-                && sub.origin.y <= rect.origin.y
-                && sub.right() >= rect.right()
-                && sub.bottom() >= rect.bottom()
-            {
-                // Fully covered, nothing to paint
-                return vec![];
-            }
+            region.subtract_rect(sub);
         }
-
-        // Otherwise, just return the original rect
-        // A full implementation would compute the difference regions
-        vec![rect]
+        region.rects().to_vec()
     }
 }
 
@@ -646,6 +685,28 @@ mod tests {
         assert!(!mgr.has_pending());
     }
 
+    #[test]
+    fn test_repaint_manager_dirty_region() {
+        let mut mgr = RepaintManager::new();
+
+        // A caret in one corner and a spinner in the other
+        mgr.mark_dirty(test_object_id(1, 1), Rect::new(0.0, 0.0, 2.0, 16.0));
+        mgr.mark_dirty(test_object_id(2, 1), Rect::new(780.0, 580.0, 20.0, 20.0));
+
+        let region = mgr.dirty_region();
+        assert_eq!(region.rects().len(), 2);
+        assert_eq!(region.area(), 32.0 + 400.0);
+    }
+
+    #[test]
+    fn test_subtract_rects_partial_coverage() {
+        let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let subtract = vec![Rect::new(0.0, 0.0, 100.0, 40.0)]; // Covers the top
+
+        let result = FrameRenderer::subtract_rects(rect, &subtract);
+        assert_eq!(result, vec![Rect::new(0.0, 40.0, 100.0, 60.0)]);
+    }
+
     #[test]
     fn test_subtract_rects_full_coverage() {
         let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
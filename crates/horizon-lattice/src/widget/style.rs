@@ -0,0 +1,795 @@
+//! Style engine integration for the widget tree.
+//!
+//! This module attaches a [`StyleEngine`] to the widget tree so that built-in
+//! widgets resolve their appearance from stylesheets and the active theme
+//! instead of hardcoded colors.
+//!
+//! Only some widgets do so far. `PushButton`, `Label`, `Frame` and
+//! `Container` paint from their computed style, and `CheckBox`, `ComboBox`,
+//! `HeaderView`, `ScrollBar`, `Slider`, `SpinBox`, `Splitter` and `TabBar`
+//! style their sub-controls. The other built-in widgets keep their built-in
+//! appearance.
+//!
+//! An engine can be installed application-wide with
+//! [`set_application_style_engine`], or for a single widget subtree (usually a
+//! window) with [`set_tree_style_engine`]. When a widget paints, it asks its
+//! [`WidgetBase`](super::WidgetBase) for a computed style; the lookup walks up
+//! the parent chain to find the nearest engine, using the parent's last
+//! resolved style for inherited properties.
+//!
+//! When no engine is attached, [`WidgetBase::compute_style`](super::WidgetBase::compute_style)
+//! returns `None` and widgets fall back to their built-in appearance.
+//!
+//! Dynamic properties set on a widget through the object registry are exposed
+//! to attribute selectors, so `LineEdit[severity="error"]` matches a line edit
+//! after [`WidgetBase::set_property`](super::WidgetBase::set_property)`("severity", "error")`.
+//! Properties of type `String`, `&'static str`, `bool`, `char`, and the
+//! primitive numeric types are matched by their `Display` text; others are
+//! ignored.
+//!
+//! Composite widgets style their internal parts through sub-control
+//! pseudo-elements such as `Slider::handle:hover` or `ScrollBar::add-line`.
+//! These styles are computed with
+//! [`WidgetBase::compute_sub_control_style`](super::WidgetBase::compute_sub_control_style)
+//! and inherit from the widget's own style.
+//!
+//! `@media` rules are evaluated against each engine's
+//! [`MediaEnvironment`]. [`update_media_environment`] applies system-wide
+//! preferences such as the color scheme to every engine, while
+//! [`set_window_viewport`] reports a window's size and scale factor to the
+//! engine that styles it. Give each window its own engine with
+//! [`set_tree_style_engine`] when width or resolution queries should follow
+//! that window.
+//!
+//! Transitions and `@keyframes` animations declared in stylesheets are applied
+//! each time a style is computed, at the engine's current clock time. Children
+//! inherit from the widget's static style, not from an animation frame. While
+//! [`is_style_animating`] returns `true` for a widget, it keeps repainting so
+//! the animation advances.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use parking_lot::RwLock;
+//! use horizon_lattice::style::prelude::*;
+//! use horizon_lattice::widget::style::set_application_style_engine;
+//!
+//! let mut engine = StyleEngine::light();
+//! engine.add_stylesheet(StyleSheet::from_css(
+//!     "PushButton { background-color: #2e7d32; }",
+//!     StylePriority::Application,
+//! )?);
+//!
+//! set_application_style_engine(Some(Arc::new(RwLock::new(engine))));
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use horizon_lattice_core::{ObjectId, ObjectRegistry, global_registry};
+use horizon_lattice_render::{Color, CornerRadii, Rect, Renderer, RoundedRect, Stroke};
+use horizon_lattice_style::prelude::{
+    ComputedStyle, MediaEnvironment, PseudoElement, StyleContext, StyleEngine, StylePaintContext,
+    StyleProperties, WidgetStyleState,
+};
+use parking_lot::RwLock;
+
+use super::traits::PaintContext;
+
+/// A style engine shared between the application and its widgets.
+pub type SharedStyleEngine = Arc<RwLock<StyleEngine>>;
+
+/// Global style state for the widget tree.
+static STYLE_TREE: OnceLock<RwLock<StyleTree>> = OnceLock::new();
+
+/// Engines attached to the widget tree and the last resolved style per widget.
+#[derive(Default)]
+struct StyleTree {
+    /// Engine used by widgets that have no subtree engine.
+    application_engine: Option<SharedStyleEngine>,
+    /// Engines attached to specific subtrees, keyed by the subtree root.
+    tree_engines: HashMap<ObjectId, SharedStyleEngine>,
+    /// Most recently resolved style for each widget, used as the parent
+    /// style when resolving inherited properties of its children.
+    resolved: HashMap<ObjectId, ComputedStyle>,
+}
+
+/// Get the global style tree, initializing it if necessary.
+fn style_tree() -> &'static RwLock<StyleTree> {
+    STYLE_TREE.get_or_init(|| RwLock::new(StyleTree::default()))
+}
+
+/// Install (or remove) the application-wide style engine.
+///
+/// Every widget without a subtree engine resolves its style through this
+/// engine. Passing `None` restores the built-in widget appearance.
+pub fn set_application_style_engine(engine: Option<SharedStyleEngine>) {
+    let mut tree = style_tree().write();
+    tree.application_engine = engine;
+    tree.resolved.clear();
+}
+
+/// Get the application-wide style engine, if one is installed.
+pub fn application_style_engine() -> Option<SharedStyleEngine> {
+    style_tree().read().application_engine.clone()
+}
+
+/// Attach (or detach) a style engine to the subtree rooted at `root`.
+///
+/// Subtree engines take precedence over the application engine, which allows
+/// individual windows to use a different theme or stylesheet set.
+pub fn set_tree_style_engine(root: ObjectId, engine: Option<SharedStyleEngine>) {
+    {
+        let mut tree = style_tree().write();
+        match engine {
+            Some(engine) => {
+                tree.tree_engines.insert(root, engine);
+            }
+            None => {
+                tree.tree_engines.remove(&root);
+            }
+        }
+    }
+    forget_subtree(root);
+}
+
+/// Update the media environment of every attached engine.
+///
+/// Use this for system-wide preferences, such as the color scheme or
+/// contrast, that `@media` rules react to. Widgets restyle on their next
+/// paint.
+pub fn update_media_environment(update: impl Fn(&mut MediaEnvironment)) {
+    let engines: Vec<SharedStyleEngine> = {
+        let tree = style_tree().read();
+        tree.application_engine
+            .iter()
+            .chain(tree.tree_engines.values())
+            .cloned()
+            .collect()
+    };
+
+    let mut changed = false;
+    for engine in engines {
+        let mut engine = engine.write();
+        let mut env = *engine.media_environment();
+        update(&mut env);
+        changed |= engine.set_media_environment(env);
+    }
+
+    if changed {
+        style_tree().write().resolved.clear();
+    }
+}
+
+/// Report the size (in logical pixels) and scale factor of a window to the
+/// engine that styles it, for `width`, `height` and `resolution` queries.
+///
+/// `root` is the window's root widget. Returns `true` if any `@media` rule
+/// was switched on or off, in which case the window should be repainted.
+pub fn set_window_viewport(root: ObjectId, width: f32, height: f32, scale_factor: f32) -> bool {
+    let Some(engine) = style_engine_for(root) else {
+        return false;
+    };
+    if !engine.write().set_viewport(width, height, scale_factor) {
+        return false;
+    }
+
+    let is_application_engine =
+        application_style_engine().is_some_and(|application| Arc::ptr_eq(&application, &engine));
+    if is_application_engine {
+        style_tree().write().resolved.clear();
+    } else {
+        forget_subtree(root);
+    }
+    true
+}
+
+/// Get the style engine that applies to a widget.
+///
+/// This walks from the widget up to the root, returning the first subtree
+/// engine found, or the application engine if no subtree has one.
+pub fn style_engine_for(widget_id: ObjectId) -> Option<SharedStyleEngine> {
+    let tree = style_tree().read();
+
+    if !tree.tree_engines.is_empty() {
+        if let Some(engine) = tree.tree_engines.get(&widget_id) {
+            return Some(engine.clone());
+        }
+        if let Ok(registry) = global_registry()
+            && let Ok(ancestors) = registry.ancestors(widget_id)
+        {
+            for ancestor in ancestors {
+                if let Some(engine) = tree.tree_engines.get(&ancestor) {
+                    return Some(engine.clone());
+                }
+            }
+        }
+    }
+
+    tree.application_engine.clone()
+}
+
+/// Get the most recently resolved style of a widget.
+pub fn resolved_style(widget_id: ObjectId) -> Option<ComputedStyle> {
+    style_tree().read().resolved.get(&widget_id).cloned()
+}
+
+/// The selector-relevant description of a widget being styled.
+pub(crate) struct StyleRequest<'a> {
+    /// The widget being styled.
+    pub widget_id: ObjectId,
+    /// The type name used for type selectors.
+    pub type_name: &'a str,
+    /// The object name used for ID selectors.
+    pub name: Option<&'a str>,
+    /// CSS classes on the widget.
+    pub classes: &'a [String],
+    /// Interaction state for pseudo-class matching.
+    pub state: WidgetStyleState,
+    /// Inline style declared on the widget.
+    pub inline_style: Option<&'a StyleProperties>,
+    /// The sub-control to style and its state, or `None` to style the widget.
+    pub sub_control: Option<(PseudoElement, WidgetStyleState)>,
+}
+
+/// Resolve the style for a widget or one of its sub-controls through its
+/// nearest engine.
+///
+/// Only widget styles are recorded as resolved; sub-control styles are not
+/// inherited by children.
+///
+/// Returns `None` when no engine applies to the widget.
+pub(crate) fn compute_widget_style(request: &StyleRequest<'_>) -> Option<ComputedStyle> {
+    let engine = style_engine_for(request.widget_id)?;
+
+    let parent_style = global_registry()
+        .ok()
+        .and_then(|registry| registry.parent(request.widget_id).ok().flatten())
+        .and_then(resolved_style);
+
+    let attributes = style_attributes(request.widget_id);
+
+    let computed = {
+        let mut engine = engine.write();
+        let context = StyleContext {
+            widget_type: request.type_name,
+            widget_name: request.name,
+            classes: request.classes,
+            attributes: &attributes,
+            state: request.state,
+            parent_style: parent_style.as_ref(),
+            root_font_size: engine.root_font_size(),
+        };
+        if let Some((element, state)) = request.sub_control {
+            return Some(engine.compute_sub_control_style(
+                request.widget_id,
+                &context,
+                element,
+                state,
+                request.inline_style,
+            ));
+        }
+
+        let computed =
+            engine.compute_static_style(request.widget_id, &context, request.inline_style);
+        style_tree()
+            .write()
+            .resolved
+            .insert(request.widget_id, computed.clone());
+        engine.animate_style(request.widget_id, &context, computed)
+    };
+
+    Some(computed)
+}
+
+/// Check whether a widget has style transitions or animations running.
+///
+/// Returns `false` when no engine applies to the widget.
+pub fn is_style_animating(widget_id: ObjectId) -> bool {
+    style_engine_for(widget_id).is_some_and(|engine| engine.read().is_animating(widget_id))
+}
+
+/// Collect a widget's dynamic properties as attribute selector values.
+///
+/// The result is sorted by name so it hashes consistently for cache keys.
+fn style_attributes(widget_id: ObjectId) -> Vec<(String, String)> {
+    let Ok(registry) = global_registry() else {
+        return Vec::new();
+    };
+
+    registry.with_read(|registry| {
+        let Ok(names) = registry.dynamic_property_names(widget_id) else {
+            return Vec::new();
+        };
+
+        let mut attributes: Vec<(String, String)> = names
+            .into_iter()
+            .filter_map(|name| {
+                attribute_value(registry, widget_id, name).map(|value| (name.to_string(), value))
+            })
+            .collect();
+        attributes.sort();
+        attributes
+    })
+}
+
+/// Convert a dynamic property to its attribute text, if it has a textual form.
+fn attribute_value(registry: &ObjectRegistry, id: ObjectId, name: &str) -> Option<String> {
+    macro_rules! try_types {
+        ($($ty:ty),* $(,)?) => {
+            $(
+                if let Ok(Some(value)) = registry.dynamic_property::<$ty>(id, name) {
+                    return Some(value.to_string());
+                }
+            )*
+        };
+    }
+
+    try_types!(
+        String,
+        &'static str,
+        bool,
+        char,
+        i8,
+        i16,
+        i32,
+        i64,
+        isize,
+        u8,
+        u16,
+        u32,
+        u64,
+        usize,
+        f32,
+        f64,
+    );
+    None
+}
+
+/// Invalidate the cached style of a widget and all of its descendants.
+///
+/// Call this when something that affects selector matching or inheritance
+/// changes, such as classes, the object name, or the widget's parent.
+pub fn invalidate_widget_style(widget_id: ObjectId) {
+    let subtree = subtree_ids(widget_id);
+    invalidate_ids(widget_id, &subtree);
+}
+
+/// Invalidate the cached styles of a widget's descendants, but not the widget.
+///
+/// Used after interaction state changes: the widget's own cache entry is
+/// keyed by its state, but children may inherit values from it.
+pub(crate) fn invalidate_descendant_styles(widget_id: ObjectId) {
+    let subtree = subtree_ids(widget_id);
+    if subtree.len() > 1 {
+        invalidate_ids(widget_id, &subtree[1..]);
+    }
+}
+
+/// Drop all style state held for a destroyed widget.
+pub(crate) fn forget_widget(widget_id: ObjectId) {
+    let Some(lock) = STYLE_TREE.get() else {
+        return;
+    };
+    let engines: Vec<SharedStyleEngine> = {
+        let mut tree = lock.write();
+        tree.resolved.remove(&widget_id);
+        tree.tree_engines.remove(&widget_id);
+        tree.application_engine
+            .iter()
+            .chain(tree.tree_engines.values())
+            .cloned()
+            .collect()
+    };
+
+    // The widget may already be detached, so its engine cannot be looked up
+    for engine in engines {
+        engine.write().remove_animations(widget_id);
+    }
+}
+
+/// Collect a widget and its descendants in pre-order.
+fn subtree_ids(widget_id: ObjectId) -> Vec<ObjectId> {
+    global_registry()
+        .ok()
+        .and_then(|registry| registry.depth_first_preorder(widget_id).ok())
+        .unwrap_or_else(|| vec![widget_id])
+}
+
+/// Remove resolved styles and engine cache entries for the given widgets.
+fn invalidate_ids(widget_id: ObjectId, ids: &[ObjectId]) {
+    if STYLE_TREE.get().is_none() {
+        return;
+    }
+
+    let engine = style_engine_for(widget_id);
+
+    {
+        let mut tree = style_tree().write();
+        for id in ids {
+            tree.resolved.remove(id);
+        }
+    }
+
+    if let Some(engine) = engine {
+        let mut engine = engine.write();
+        for id in ids {
+            engine.invalidate(*id);
+        }
+    }
+}
+
+/// Forget the resolved styles of a subtree whose engine changed.
+fn forget_subtree(root: ObjectId) {
+    let ids = subtree_ids(root);
+    let mut tree = style_tree().write();
+    for id in ids {
+        tree.resolved.remove(&id);
+    }
+}
+
+// ============================================================================
+// Painting
+// ============================================================================
+
+impl StylePaintContext for PaintContext<'_> {
+    fn fill_rect(&mut self, rect: Rect, color: Color) {
+        self.renderer().fill_rect(rect, color);
+    }
+
+    fn fill_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: Color) {
+        self.renderer()
+            .fill_rounded_rect(RoundedRect::with_radii(rect, radii), color);
+    }
+
+    fn stroke_rect(&mut self, rect: Rect, color: Color, width: f32) {
+        self.renderer()
+            .stroke_rect(rect, &Stroke::new(color, width));
+    }
+
+    fn stroke_rounded_rect(&mut self, rect: Rect, radii: CornerRadii, color: Color, width: f32) {
+        self.renderer().stroke_rounded_rect(
+            RoundedRect::with_radii(rect, radii),
+            &Stroke::new(color, width),
+        );
+    }
+
+    fn save_clip(&mut self) {
+        self.renderer().save();
+    }
+
+    fn restore_clip(&mut self) {
+        self.renderer().restore();
+    }
+
+    fn clip_rect(&mut self, rect: Rect) {
+        Renderer::clip_rect(self.renderer(), rect);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::widgets::{Label, PushButton};
+    use crate::widget::{Widget, WidgetBase};
+    use horizon_lattice_core::{Object, init_global_registry};
+    use horizon_lattice_style::prelude::*;
+
+    fn setup() {
+        init_global_registry();
+    }
+
+    fn engine_with_css(css: &str) -> SharedStyleEngine {
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(StyleSheet::from_css(css, StylePriority::Application).unwrap());
+        Arc::new(RwLock::new(engine))
+    }
+
+    struct TestWidget {
+        base: WidgetBase,
+    }
+
+    impl TestWidget {
+        fn new() -> Self {
+            Self {
+                base: WidgetBase::new::<Self>(),
+            }
+        }
+    }
+
+    impl Object for TestWidget {
+        fn object_id(&self) -> ObjectId {
+            self.base.object_id()
+        }
+    }
+
+    impl Widget for TestWidget {
+        fn widget_base(&self) -> &WidgetBase {
+            &self.base
+        }
+
+        fn widget_base_mut(&mut self) -> &mut WidgetBase {
+            &mut self.base
+        }
+
+        fn size_hint(&self) -> crate::widget::SizeHint {
+            crate::widget::SizeHint::default()
+        }
+
+        fn paint(&self, _ctx: &mut PaintContext<'_>) {}
+    }
+
+    #[test]
+    fn test_no_engine_returns_none() {
+        setup();
+        let widget = TestWidget::new();
+        // Tests only attach subtree engines; this widget is in none of them.
+        assert!(application_style_engine().is_none());
+        assert!(widget.base.compute_style().is_none());
+    }
+
+    #[test]
+    fn test_type_name_defaults_to_short_type_name() {
+        setup();
+        let widget = TestWidget::new();
+        assert_eq!(widget.base.style_type_name(), "TestWidget");
+
+        let button = PushButton::new("OK");
+        assert_eq!(button.widget_base().style_type_name(), "PushButton");
+    }
+
+    #[test]
+    fn test_tree_engine_resolves_type_class_and_id() {
+        setup();
+        let mut widget = TestWidget::new();
+        set_tree_style_engine(
+            widget.object_id(),
+            Some(engine_with_css(
+                "TestWidget { color: #ff0000; } \
+                 .accent { background-color: #00ff00; } \
+                 #main { font-size: 20px; }",
+            )),
+        );
+
+        let style = widget.base.compute_style().unwrap();
+        assert_eq!(style.color, Color::from_rgb8(255, 0, 0));
+        assert!(!style.has_background());
+
+        widget.base.add_class("accent");
+        widget.base.set_name("main");
+        let style = widget.base.compute_style().unwrap();
+        assert_eq!(
+            style.background,
+            horizon_lattice_render::Paint::Solid(Color::from_rgb8(0, 255, 0))
+        );
+        assert_eq!(style.font_size, 20.0);
+    }
+
+    #[test]
+    fn test_pseudo_class_follows_widget_state() {
+        setup();
+        let mut widget = TestWidget::new();
+        set_tree_style_engine(
+            widget.object_id(),
+            Some(engine_with_css(
+                "TestWidget { color: #000000; } TestWidget:hover { color: #0000ff; }",
+            )),
+        );
+
+        assert_eq!(widget.base.compute_style().unwrap().color, Color::BLACK);
+
+        widget.base.set_hovered(true);
+        assert_eq!(
+            widget.base.compute_style().unwrap().color,
+            Color::from_rgb8(0, 0, 255)
+        );
+
+        widget.base.set_hovered(false);
+        assert_eq!(widget.base.compute_style().unwrap().color, Color::BLACK);
+    }
+
+    #[test]
+    fn test_child_inherits_and_reparenting_invalidates() {
+        setup();
+        let mut parent_a = TestWidget::new();
+        let parent_b = TestWidget::new();
+        let child = Label::new("child");
+
+        let engine = engine_with_css(
+            ".red { color: #ff0000; } .blue { color: #0000ff; } Label { color: inherit; }",
+        );
+        set_tree_style_engine(parent_a.object_id(), Some(engine.clone()));
+        set_tree_style_engine(parent_b.object_id(), Some(engine));
+
+        parent_a.base.add_class("red");
+        let mut parent_b = parent_b;
+        parent_b.base.add_class("blue");
+
+        child
+            .widget_base()
+            .set_parent(Some(parent_a.object_id()))
+            .unwrap();
+        parent_a.base.compute_style();
+        assert_eq!(
+            child.widget_base().compute_style().unwrap().color,
+            Color::from_rgb8(255, 0, 0)
+        );
+
+        child
+            .widget_base()
+            .set_parent(Some(parent_b.object_id()))
+            .unwrap();
+        parent_b.base.compute_style();
+        assert_eq!(
+            child.widget_base().compute_style().unwrap().color,
+            Color::from_rgb8(0, 0, 255)
+        );
+    }
+
+    #[test]
+    fn test_class_change_invalidates_children() {
+        setup();
+        let mut parent = TestWidget::new();
+        let child = TestWidget::new();
+        child.base.set_parent(Some(parent.object_id())).unwrap();
+
+        set_tree_style_engine(
+            parent.object_id(),
+            Some(engine_with_css(
+                "TestWidget { color: inherit; } .dark { color: #ffffff; }",
+            )),
+        );
+
+        parent.base.compute_style();
+        assert_eq!(child.base.compute_style().unwrap().color, Color::BLACK);
+
+        parent.base.add_class("dark");
+        parent.base.compute_style();
+        assert_eq!(child.base.compute_style().unwrap().color, Color::WHITE);
+    }
+
+    #[test]
+    fn test_custom_properties_inherit_down_the_tree() {
+        setup();
+        let mut parent = TestWidget::new();
+        let child = TestWidget::new();
+        child.base.set_parent(Some(parent.object_id())).unwrap();
+
+        set_tree_style_engine(
+            parent.object_id(),
+            Some(engine_with_css(
+                ".dark { --fg: #ffffff; } TestWidget { color: var(--fg, #000000); }",
+            )),
+        );
+
+        parent.base.compute_style();
+        assert_eq!(child.base.compute_style().unwrap().color, Color::BLACK);
+
+        parent.base.add_class("dark");
+        parent.base.compute_style();
+        assert_eq!(child.base.compute_style().unwrap().color, Color::WHITE);
+    }
+
+    #[test]
+    fn test_inline_style_overrides_stylesheet() {
+        setup();
+        let mut widget = TestWidget::new();
+        set_tree_style_engine(
+            widget.object_id(),
+            Some(engine_with_css("TestWidget { color: #ff0000; }")),
+        );
+
+        widget
+            .base
+            .set_inline_style(Some(Style::new().color(Color::GREEN).build()));
+        assert_eq!(widget.base.compute_style().unwrap().color, Color::GREEN);
+    }
+
+    #[test]
+    fn test_attribute_selectors_match_dynamic_properties() {
+        setup();
+        let mut widget = TestWidget::new();
+        set_tree_style_engine(
+            widget.object_id(),
+            Some(engine_with_css(
+                r#"TestWidget { color: #000000; }
+                TestWidget[severity="error"] { color: #ff0000; }
+                TestWidget[modified="true"] { font-size: 20px; }"#,
+            )),
+        );
+
+        assert_eq!(widget.base.compute_style().unwrap().color, Color::BLACK);
+
+        widget.base.set_property("severity", "error".to_string());
+        widget.base.set_property("modified", true);
+        assert_eq!(widget.base.property::<bool>("modified"), Some(true));
+        let style = widget.base.compute_style().unwrap();
+        assert_eq!(style.color, Color::RED);
+        assert_eq!(style.font_size, 20.0);
+
+        // Properties set directly on the registry are picked up as well
+        global_registry()
+            .unwrap()
+            .set_dynamic_property(widget.object_id(), "severity", "warning")
+            .unwrap();
+        assert_eq!(widget.base.compute_style().unwrap().color, Color::BLACK);
+
+        assert!(widget.base.remove_property("modified"));
+        assert!(!widget.base.remove_property("modified"));
+        assert_ne!(widget.base.compute_style().unwrap().font_size, 20.0);
+    }
+
+    #[test]
+    fn test_style_transitions_animate_widgets() {
+        setup();
+        let mut widget = TestWidget::new();
+        let engine = engine_with_css(
+            "TestWidget { opacity: 1; transition: opacity 100ms linear; }
+             TestWidget:hover { opacity: 0; }",
+        );
+        engine.write().set_clock(AnimationClock::manual());
+        set_tree_style_engine(widget.object_id(), Some(engine.clone()));
+
+        assert_eq!(widget.base.compute_style().unwrap().opacity, 1.0);
+        assert!(!is_style_animating(widget.object_id()));
+
+        widget.base.set_hovered(true);
+        engine
+            .write()
+            .clock_mut()
+            .advance(std::time::Duration::from_millis(50));
+        let style = widget.base.compute_style().unwrap();
+        assert_eq!(style.opacity, 1.0);
+        assert!(is_style_animating(widget.object_id()));
+
+        // Children inherit from the settled style, not the animation frame
+        assert_eq!(resolved_style(widget.object_id()).unwrap().opacity, 0.0);
+
+        engine
+            .write()
+            .clock_mut()
+            .advance(std::time::Duration::from_millis(50));
+        assert!((widget.base.compute_style().unwrap().opacity - 0.5).abs() < 1e-4);
+
+        engine
+            .write()
+            .clock_mut()
+            .advance(std::time::Duration::from_millis(100));
+        assert_eq!(widget.base.compute_style().unwrap().opacity, 0.0);
+        assert!(!is_style_animating(widget.object_id()));
+
+        set_tree_style_engine(widget.object_id(), None);
+    }
+
+    #[test]
+    fn test_media_rules_follow_environment() {
+        setup();
+        let widget = TestWidget::new();
+        let engine = engine_with_css(
+            "TestWidget { color: #000000; }
+             @media (min-width: 800px) { TestWidget { font-size: 20px; } }
+             @media (prefers-contrast: more) { TestWidget { color: #0000ff; } }",
+        );
+        set_tree_style_engine(widget.object_id(), Some(engine.clone()));
+
+        assert!(!set_window_viewport(widget.object_id(), 640.0, 480.0, 1.0));
+        assert_ne!(widget.base.compute_style().unwrap().font_size, 20.0);
+
+        assert!(set_window_viewport(widget.object_id(), 1024.0, 768.0, 1.0));
+        assert!(resolved_style(widget.object_id()).is_none());
+        assert_eq!(widget.base.compute_style().unwrap().font_size, 20.0);
+
+        update_media_environment(|env| env.contrast = ContrastPreference::More);
+        assert_eq!(
+            widget.base.compute_style().unwrap().color,
+            Color::from_rgb8(0, 0, 255)
+        );
+        assert_eq!(
+            engine.read().media_environment().contrast,
+            ContrastPreference::More
+        );
+
+        set_tree_style_engine(widget.object_id(), None);
+    }
+}
@@ -2102,4 +2102,43 @@ mod tests {
         assert!(!CursorShape::IBeam.is_resize_cursor());
         assert!(!CursorShape::Wait.is_resize_cursor());
     }
+
+    // =========================================================================
+    // Headless Painting Tests
+    // =========================================================================
+
+    #[test]
+    fn test_paint_widget_tree_into_software_renderer() {
+        use crate::widget::FrameRenderer;
+        use crate::widget::widgets::PushButton;
+        use horizon_lattice_render::{Renderer, SoftwareRenderer};
+
+        setup();
+
+        let mut button = PushButton::new("");
+        button.set_geometry(Rect::new(10.0, 20.0, 60.0, 30.0));
+        let button_id = button.object_id();
+
+        let mut storage = TestWidgetStorage::new();
+        storage.add(button);
+
+        let mut renderer = SoftwareRenderer::new();
+        renderer.begin_frame(Color::WHITE, Size::new(100.0, 60.0));
+        let stats = FrameRenderer::render_frame(&mut storage, button_id, &mut renderer);
+        renderer.end_frame();
+
+        assert_eq!(stats.widgets_painted, 1);
+        let pixel_is = |x, y, expected: Color| {
+            let pixel = renderer.pixel(x, y).unwrap();
+            (pixel.r - expected.r).abs() < 1e-3
+                && (pixel.g - expected.g).abs() < 1e-3
+                && (pixel.b - expected.b).abs() < 1e-3
+                && (pixel.a - expected.a).abs() < 1e-3
+        };
+        // The primary button background fills its geometry...
+        assert!(pixel_is(40, 35, Color::from_rgb8(0, 122, 255)));
+        // ...and nothing is drawn outside it.
+        assert!(pixel_is(5, 5, Color::WHITE));
+        assert!(pixel_is(80, 35, Color::WHITE));
+    }
 }
@@ -18,7 +18,7 @@
 //! - [`super::Layout`] - Layout management for child widgets
 
 use horizon_lattice_core::{Object, ObjectId};
-use horizon_lattice_render::{GpuRenderer, Point, Rect, Renderer, Size};
+use horizon_lattice_render::{DynRenderer, GpuRenderer, Point, Rect, Renderer, Size};
 
 use super::base::WidgetBase;
 use super::events::WidgetEvent;
@@ -29,13 +29,17 @@ use super::geometry::{SizeHint, SizePolicyPair};
 /// This wraps a renderer and provides the widget's geometry information
 /// for convenient access during the paint operation. Passed to [`Widget::paint`].
 ///
+/// The renderer is held as a [`DynRenderer`], so widgets paint the same way
+/// into a [`GpuRenderer`](horizon_lattice_render::GpuRenderer) on screen or a
+/// [`SoftwareRenderer`](horizon_lattice_render::SoftwareRenderer) headlessly.
+///
 /// # Related
 ///
 /// - [`Widget::paint`] - Receives this context
-/// - [`GpuRenderer`](horizon_lattice_render::GpuRenderer) - The underlying renderer
+/// - [`Renderer`] - The drawing API available through [`PaintContext::renderer`]
 pub struct PaintContext<'a> {
     /// The renderer to draw with.
-    renderer: &'a mut GpuRenderer,
+    renderer: &'a mut (dyn DynRenderer + 'a),
     /// The widget's local rectangle (origin always 0,0).
     widget_rect: Rect,
     /// Whether the Alt key is currently held (for mnemonic underline display).
@@ -46,7 +50,7 @@ pub struct PaintContext<'a> {
 
 impl<'a> PaintContext<'a> {
     /// Create a new paint context.
-    pub fn new(renderer: &'a mut GpuRenderer, widget_rect: Rect) -> Self {
+    pub fn new(renderer: &'a mut (dyn DynRenderer + 'a), widget_rect: Rect) -> Self {
         Self {
             renderer,
             widget_rect,
@@ -90,10 +94,20 @@ impl<'a> PaintContext<'a> {
 
     /// Get the renderer.
     #[inline]
-    pub fn renderer(&mut self) -> &mut GpuRenderer {
+    pub fn renderer(&mut self) -> &mut (dyn DynRenderer + 'a) {
         self.renderer
     }
 
+    /// Get the renderer as a [`GpuRenderer`], for drawing that the
+    /// [`Renderer`] trait doesn't cover.
+    ///
+    /// Returns `None` when painting into any other renderer, such as a
+    /// [`SoftwareRenderer`](horizon_lattice_render::SoftwareRenderer).
+    #[inline]
+    pub fn gpu_renderer(&mut self) -> Option<&mut GpuRenderer> {
+        self.renderer.as_gpu_renderer()
+    }
+
     /// Get the widget's local rectangle.
     #[inline]
     pub fn rect(&self) -> Rect {
@@ -97,6 +97,22 @@ pub enum ButtonVariant {
     Outlined,
 }
 
+impl ButtonVariant {
+    /// Get the CSS class that identifies this variant in stylesheets.
+    ///
+    /// Buttons expose their variant as a class, so `PushButton.danger`
+    /// matches a button using [`ButtonVariant::Danger`].
+    pub fn style_class(self) -> &'static str {
+        match self {
+            ButtonVariant::Primary => "primary",
+            ButtonVariant::Secondary => "secondary",
+            ButtonVariant::Danger => "danger",
+            ButtonVariant::Flat => "flat",
+            ButtonVariant::Outlined => "outlined",
+        }
+    }
+}
+
 /// Common functionality for all button widgets.
 ///
 /// This struct encapsulates the shared behavior of buttons:
@@ -30,6 +30,9 @@ use horizon_lattice_render::{
     Color, Font, FontSystem, Path, Point, Rect, Renderer, RoundedRect, Stroke, TextLayout,
     TextRenderer,
 };
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, has_visible_box, paint_styled_box,
+};
 
 use super::abstract_button::AbstractButton;
 use crate::widget::{PaintContext, SizeHint, Widget, WidgetBase, WidgetEvent};
@@ -150,6 +153,7 @@ impl CheckBox {
     /// Create a new checkbox with the specified label text.
     pub fn new(text: impl Into<String>) -> Self {
         let mut inner = AbstractButton::new(text);
+        inner.widget_base_mut().set_style_type_name("CheckBox");
         // Checkboxes are always checkable
         inner.set_checkable(true);
         // Default text color to near-black for readability
@@ -435,6 +439,39 @@ impl CheckBox {
         &self.inner.released
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Compute the style of a checkbox sub-control from the attached style engine.
+    ///
+    /// The checkbox paints [`PseudoElement::Indicator`], the check box itself,
+    /// whose `color` is used for the checkmark. The indicator shares the
+    /// checkbox's hover, pressed, and focus state, and matches `:checked` or
+    /// `:unchecked` (neither while partially checked).
+    ///
+    /// Returns `None` if no style engine applies to the checkbox.
+    pub fn sub_control_style(&self, element: PseudoElement) -> Option<ComputedStyle> {
+        let base = self.inner.widget_base();
+        let checked = match self.check_state {
+            CheckState::Checked => Some(true),
+            CheckState::Unchecked => Some(false),
+            CheckState::PartiallyChecked => None,
+        };
+
+        let mut widget_state = base.style_state();
+        widget_state.checked = checked;
+
+        let mut state = base.sub_control_state();
+        if element == PseudoElement::Indicator {
+            state.hovered = widget_state.hovered;
+            state.pressed = widget_state.pressed;
+            state.focused = widget_state.focused;
+            state.checked = checked;
+        }
+        base.compute_sub_control_style_with(element, state, widget_state, &[])
+    }
+
     // =========================================================================
     // Rendering Helpers
     // =========================================================================
@@ -496,7 +533,16 @@ impl CheckBox {
         } else {
             Color::from_rgb8(158, 158, 158)
         };
+        self.draw_indicator_mark(ctx, indicator_rect, check_color);
+    }
 
+    /// Draw the checkmark or dash for the current state in the given color.
+    fn draw_indicator_mark(
+        &self,
+        ctx: &mut PaintContext<'_>,
+        indicator_rect: Rect,
+        check_color: Color,
+    ) {
         match self.check_state {
             CheckState::Checked => {
                 // Draw a checkmark
@@ -615,23 +661,32 @@ impl Widget for CheckBox {
             self.indicator_size,
         );
 
-        // Draw indicator background
-        let bg_color = self.indicator_color();
-        let border_color = self.effective_border_color();
+        if let Some(style) = self
+            .sub_control_style(PseudoElement::Indicator)
+            .filter(has_visible_box)
+        {
+            // Draw the styled indicator with the style's color for the mark
+            paint_styled_box(ctx, indicator_rect, &style);
+            self.draw_indicator_mark(ctx, indicator_rect, style.color);
+        } else {
+            // Draw indicator background
+            let bg_color = self.indicator_color();
+            let border_color = self.effective_border_color();
 
-        let rrect = RoundedRect::new(indicator_rect, self.indicator_radius);
+            let rrect = RoundedRect::new(indicator_rect, self.indicator_radius);
 
-        // Fill background if checked or hovered
-        if bg_color != Color::TRANSPARENT {
-            ctx.renderer().fill_rounded_rect(rrect, bg_color);
-        }
+            // Fill background if checked or hovered
+            if bg_color != Color::TRANSPARENT {
+                ctx.renderer().fill_rounded_rect(rrect, bg_color);
+            }
 
-        // Draw border
-        let border_stroke = Stroke::new(border_color, 1.5);
-        ctx.renderer().stroke_rounded_rect(rrect, &border_stroke);
+            // Draw border
+            let border_stroke = Stroke::new(border_color, 1.5);
+            ctx.renderer().stroke_rounded_rect(rrect, &border_stroke);
 
-        // Draw check indicator (checkmark or dash)
-        self.draw_indicator(ctx, indicator_rect);
+            // Draw check indicator (checkmark or dash)
+            self.draw_indicator(ctx, indicator_rect);
+        }
 
         // Draw label text
         if !self.inner.text().is_empty() {
@@ -979,4 +1034,37 @@ mod tests {
         assert_eq!(CheckState::from(true), CheckState::Checked);
         assert_eq!(CheckState::from(false), CheckState::Unchecked);
     }
+
+    #[test]
+    fn test_checkbox_indicator_sub_control_style() {
+        use crate::widget::style::set_tree_style_engine;
+        use horizon_lattice_style::prelude::{StyleEngine, StylePriority, StyleSheet};
+        use parking_lot::RwLock;
+
+        setup();
+        let mut checkbox = CheckBox::new("Styled");
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "CheckBox::indicator { background-color: #ffffff; } \
+                 CheckBox::indicator:checked { background-color: #00ff00; }",
+                StylePriority::Application,
+            )
+            .unwrap(),
+        );
+        set_tree_style_engine(checkbox.object_id(), Some(Arc::new(RwLock::new(engine))));
+
+        let indicator = checkbox
+            .sub_control_style(PseudoElement::Indicator)
+            .unwrap();
+        assert_eq!(indicator.background.as_solid(), Some(Color::WHITE));
+
+        checkbox.set_checked(true);
+        let indicator = checkbox
+            .sub_control_style(PseudoElement::Indicator)
+            .unwrap();
+        assert_eq!(indicator.background.as_solid(), Some(Color::GREEN));
+
+        set_tree_style_engine(checkbox.object_id(), None);
+    }
 }
@@ -37,6 +37,9 @@ use horizon_lattice_render::{
     Color, Font, FontFamily, FontSystem, Icon, ImageScaleMode, Point, Rect, Renderer, RoundedRect,
     Size, Stroke, TextLayout, TextLayoutOptions, TextRenderer,
 };
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, has_visible_box, paint_styled_box,
+};
 
 use crate::widget::{
     FocusPolicy, Key, KeyPressEvent, MouseButton, MouseMoveEvent, MousePressEvent,
@@ -1036,6 +1039,27 @@ impl ComboBox {
         }
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Compute the style of a combo box sub-control from the attached style engine.
+    ///
+    /// The combo box paints [`PseudoElement::DropDown`], the drop-down button,
+    /// whose `color` is used for its arrow. [`PseudoElement::DownArrow`] gets
+    /// the same state. Both are hovered while the mouse is over the button,
+    /// and pressed while it is held or the popup is open.
+    ///
+    /// Returns `None` if no style engine applies to the combo box.
+    pub fn sub_control_style(&self, element: PseudoElement) -> Option<ComputedStyle> {
+        let mut state = self.base.sub_control_state();
+        if matches!(element, PseudoElement::DropDown | PseudoElement::DownArrow) {
+            state.hovered = self.hover_part == ComboBoxPart::Arrow;
+            state.pressed = self.pressed_part == ComboBoxPart::Arrow || self.popup_visible;
+        }
+        self.base.compute_sub_control_style(element, state)
+    }
+
     // =========================================================================
     // Geometry Helpers
     // =========================================================================
@@ -1454,6 +1478,17 @@ impl ComboBox {
             &sep_stroke,
         );
 
+        // Draw a styled drop-down button instead of the built-in one
+        if let Some(style) = self
+            .sub_control_style(PseudoElement::DropDown)
+            .filter(has_visible_box)
+        {
+            paint_styled_box(ctx, self.arrow_rect(), &style);
+            self.paint_arrow(ctx, style.color);
+            self.paint_text(ctx);
+            return;
+        }
+
         // Draw arrow button background on hover
         if matches!(self.hover_part, ComboBoxPart::Arrow) {
             let arrow_rect = Rect::new(
@@ -24,6 +24,7 @@
 
 use horizon_lattice_core::{Object, ObjectId, Signal};
 use horizon_lattice_render::{Color, Rect, Renderer, Size};
+use horizon_lattice_style::prelude::paint_styled_box;
 
 use crate::widget::dispatcher::WidgetAccess;
 use crate::widget::layout::{ContentMargins, LayoutItem, LayoutKind};
@@ -98,6 +99,7 @@ impl ContainerWidget {
             SizePolicy::Expanding,
             SizePolicy::Expanding,
         ));
+        base.set_style_type_name("Container");
 
         Self {
             base,
@@ -432,9 +434,11 @@ impl Widget for ContainerWidget {
     fn paint(&self, ctx: &mut PaintContext<'_>) {
         let rect = ctx.rect();
 
-        // Draw background if set
+        // Draw background if set, otherwise use the stylesheet box
         if let Some(bg_color) = self.background_color {
             ctx.renderer().fill_rect(rect, bg_color);
+        } else if let Some(style) = self.base.compute_style() {
+            paint_styled_box(ctx, rect, &style);
         }
 
         // Child widgets are painted separately by the paint system
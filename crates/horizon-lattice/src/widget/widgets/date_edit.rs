@@ -391,6 +391,11 @@ impl DateEdit {
         }
     }
 
+    /// Check if the calendar popup is currently shown.
+    pub fn is_popup_visible(&self) -> bool {
+        self.popup_visible
+    }
+
     /// Toggle the calendar popup.
     pub fn toggle_popup(&mut self) {
         if self.popup_visible {
@@ -941,7 +941,7 @@ impl DoubleSpinBox {
     }
 
     /// Finish editing and apply the value.
-    fn finish_editing(&mut self) {
+    pub(crate) fn finish_editing(&mut self) {
         if !self.editing {
             return;
         }
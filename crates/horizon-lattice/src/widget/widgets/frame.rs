@@ -20,6 +20,7 @@
 
 use horizon_lattice_core::{Object, ObjectId, Signal};
 use horizon_lattice_render::{Color, Point, Rect, Renderer, Size, Stroke};
+use horizon_lattice_style::prelude::{paint_background, paint_border};
 
 use crate::widget::layout::ContentMargins;
 use crate::widget::{PaintContext, SizeHint, Widget, WidgetBase, WidgetEvent};
@@ -564,10 +565,18 @@ impl Widget for Frame {
 
     fn paint(&self, ctx: &mut PaintContext<'_>) {
         let rect = ctx.rect();
+        let style = self.base.compute_style();
 
-        // Draw background if set
+        // Draw background if set, otherwise use the stylesheet background
         if let Some(bg_color) = self.background_color {
             ctx.renderer().fill_rect(rect, bg_color);
+        } else if let Some(style) = &style {
+            paint_background(ctx, rect, style);
+        }
+
+        // Stylesheet borders are drawn beneath the frame decoration
+        if let Some(style) = &style {
+            paint_border(ctx, rect, style);
         }
 
         // Draw frame
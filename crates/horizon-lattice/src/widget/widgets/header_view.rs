@@ -6,8 +6,9 @@
 //! - Section resizing (interactive or automatic)
 //! - Section reordering via drag
 //! - Section hiding
-//! - Sort indicator display
+//! - Sort indicator display, marked by priority when sorting by several sections
 //! - Click-to-sort functionality
+//! - Double-click on a section divider to fit the section to its contents
 //!
 //! # Example
 //!
@@ -29,11 +30,15 @@ use std::sync::Arc;
 
 use horizon_lattice_core::{Object, ObjectId, Signal};
 use horizon_lattice_render::{Color, Point, Rect, Renderer, Stroke};
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, has_visible_box, paint_styled_box,
+};
 
 use crate::model::{ItemModel, ItemRole, Orientation};
 use crate::widget::{
-    ContextMenuEvent, FocusPolicy, MouseButton, MouseMoveEvent, MousePressEvent, MouseReleaseEvent,
-    PaintContext, SizeHint, SizePolicy, SizePolicyPair, Widget, WidgetBase, WidgetEvent,
+    ContextMenuEvent, FocusPolicy, MouseButton, MouseDoubleClickEvent, MouseMoveEvent,
+    MousePressEvent, MouseReleaseEvent, PaintContext, SizeHint, SizePolicy, SizePolicyPair, Widget,
+    WidgetBase, WidgetEvent,
 };
 
 /// Resize mode for header sections.
@@ -64,6 +69,24 @@ const DEFAULT_SECTION_SIZE: f32 = 100.0;
 const MINIMUM_SECTION_SIZE: f32 = 20.0;
 const HEADER_HEIGHT: f32 = 24.0;
 const RESIZE_HANDLE_WIDTH: f32 = 5.0;
+const SECTION_PADDING: f32 = 4.0;
+/// Space reserved for the sort indicator at the end of a section.
+const SORT_INDICATOR_SPACE: f32 = 12.0;
+
+/// Spacing between the centers of sort priority dots.
+const SORT_PRIORITY_DOT_SPACING: f32 = 3.0;
+
+/// Centers of the dots marking a sort key's priority (`0` = one dot),
+/// centered below the sort indicator arrow.
+fn sort_priority_dots(rect: Rect, priority: usize) -> impl Iterator<Item = Point> {
+    let indicator_size = 6.0;
+    let center_x = rect.origin.x + rect.width() - 6.0 - indicator_size / 2.0;
+    let y = rect.origin.y + (rect.height() + indicator_size) / 2.0 + 3.0;
+    let first_x = center_x - priority as f32 * SORT_PRIORITY_DOT_SPACING / 2.0;
+    (0..=priority).map(move |i| Point::new(first_x + i as f32 * SORT_PRIORITY_DOT_SPACING, y))
+}
+/// Estimated width of a header text character.
+const HEADER_CHAR_WIDTH: f32 = 6.5;
 
 /// A header view widget for table and tree views.
 ///
@@ -77,6 +100,8 @@ const RESIZE_HANDLE_WIDTH: f32 = 5.0;
 /// - `section_resized((usize, f32, f32))`: Emitted when a section is resized (section, old, new)
 /// - `section_moved((usize, usize, usize))`: Emitted when a section is moved (logical, old_visual, new_visual)
 /// - `sort_indicator_changed((usize, SortOrder))`: Emitted when sort indicator changes
+/// - `sort_indicators_changed(Vec<(usize, SortOrder)>)`: Emitted when any sort key changes
+/// - `section_handle_double_clicked(usize)`: Emitted when a section divider is double-clicked
 pub struct HeaderView {
     base: WidgetBase,
 
@@ -95,6 +120,9 @@ pub struct HeaderView {
     /// Resize mode for each section.
     section_resize_modes: Vec<ResizeMode>,
 
+    /// Resize mode for new sections.
+    default_resize_mode: ResizeMode,
+
     /// Which sections are hidden.
     section_hidden: Vec<bool>,
 
@@ -132,11 +160,8 @@ pub struct HeaderView {
     /// Currently pressed section (logical index).
     pressed_section: Option<usize>,
 
-    /// Section showing sort indicator (logical index).
-    sort_indicator_section: Option<usize>,
-
-    /// Sort order for the indicator.
-    sort_indicator_order: SortOrder,
+    /// Sections showing a sort indicator (logical index), most significant first.
+    sort_indicators: Vec<(usize, SortOrder)>,
 
     /// Whether to show sort indicator.
     sort_indicator_shown: bool,
@@ -177,6 +202,14 @@ pub struct HeaderView {
     /// Emitted when sort indicator changes.
     pub sort_indicator_changed: Signal<(usize, SortOrder)>,
 
+    /// Emitted when the sort keys change, with the new keys.
+    pub sort_indicators_changed: Signal<Vec<(usize, SortOrder)>>,
+
+    /// Emitted when the divider after a section is double-clicked.
+    ///
+    /// Views resize the section to fit its contents in response.
+    pub section_handle_double_clicked: Signal<usize>,
+
     /// Emitted when a context menu is requested on a section.
     ///
     /// The tuple contains (section logical index or None, position in widget coords).
@@ -212,6 +245,7 @@ impl HeaderView {
             section_sizes: Vec::new(),
             section_positions: Vec::new(),
             section_resize_modes: Vec::new(),
+            default_resize_mode: ResizeMode::Interactive,
             section_hidden: Vec::new(),
             section_visual_indices: Vec::new(),
             section_logical_indices: Vec::new(),
@@ -227,8 +261,7 @@ impl HeaderView {
             sections_movable: false,
             hover_section: None,
             pressed_section: None,
-            sort_indicator_section: None,
-            sort_indicator_order: SortOrder::Ascending,
+            sort_indicators: Vec::new(),
             sort_indicator_shown: true,
             offset: 0,
             header_size: HEADER_HEIGHT,
@@ -242,6 +275,8 @@ impl HeaderView {
             section_resized: Signal::new(),
             section_moved: Signal::new(),
             sort_indicator_changed: Signal::new(),
+            sort_indicators_changed: Signal::new(),
+            section_handle_double_clicked: Signal::new(),
             context_menu_requested: Signal::new(),
         }
     }
@@ -278,7 +313,7 @@ impl HeaderView {
         // Resize vectors
         self.section_sizes.resize(count, self.default_section_size);
         self.section_resize_modes
-            .resize(count, ResizeMode::Interactive);
+            .resize(count, self.default_resize_mode);
         self.section_hidden.resize(count, false);
 
         // Reset visual/logical mappings to identity
@@ -528,8 +563,9 @@ impl HeaderView {
         self.section_resize_modes[logical_index] = mode;
     }
 
-    /// Sets the resize mode for all sections.
+    /// Sets the resize mode for all sections, including sections added later.
     pub fn set_default_resize_mode(&mut self, mode: ResizeMode) {
+        self.default_resize_mode = mode;
         for m in &mut self.section_resize_modes {
             *m = mode;
         }
@@ -554,37 +590,70 @@ impl HeaderView {
     // =========================================================================
 
     /// Returns the section showing the sort indicator, if any.
+    ///
+    /// When sorting by several sections, this is the most significant one.
     pub fn sort_indicator_section(&self) -> Option<usize> {
         if self.sort_indicator_shown {
-            self.sort_indicator_section
+            self.sort_indicators.first().map(|&(section, _)| section)
         } else {
             None
         }
     }
 
-    /// Returns the sort order.
+    /// Returns the sort order of the most significant sort key.
     pub fn sort_indicator_order(&self) -> SortOrder {
-        self.sort_indicator_order
+        self.sort_indicators
+            .first()
+            .map_or(SortOrder::Ascending, |&(_, order)| order)
     }
 
-    /// Sets the sort indicator on a section.
+    /// Sets the sort indicator on a single section.
     pub fn set_sort_indicator(&mut self, section: usize, order: SortOrder) {
-        let changed =
-            self.sort_indicator_section != Some(section) || self.sort_indicator_order != order;
+        self.set_sort_indicators(vec![(section, order)]);
+    }
 
-        self.sort_indicator_section = Some(section);
-        self.sort_indicator_order = order;
+    /// Returns the sort keys, most significant first.
+    pub fn sort_indicators(&self) -> &[(usize, SortOrder)] {
+        &self.sort_indicators
+    }
 
-        if changed {
-            self.sort_indicator_changed.emit((section, order));
-            self.base.update();
+    /// Sets the sort keys, most significant first.
+    ///
+    /// Each section shows an indicator, marked with one dot per priority
+    /// level when there is more than one key. Repeated sections keep their first entry.
+    pub fn set_sort_indicators(&mut self, mut indicators: Vec<(usize, SortOrder)>) {
+        let mut seen = Vec::with_capacity(indicators.len());
+        indicators.retain(|&(section, _)| {
+            let first = !seen.contains(&section);
+            seen.push(section);
+            first
+        });
+        if indicators == self.sort_indicators {
+            return;
+        }
+
+        self.sort_indicators = indicators;
+        if let Some(&primary) = self.sort_indicators.first() {
+            self.sort_indicator_changed.emit(primary);
         }
+        self.sort_indicators_changed
+            .emit(self.sort_indicators.clone());
+        self.base.update();
+    }
+
+    /// Returns the priority of a section's sort key, `0` being the most
+    /// significant, or `None` if the section isn't sorted.
+    pub fn sort_priority(&self, logical_index: usize) -> Option<usize> {
+        self.sort_indicators
+            .iter()
+            .position(|&(section, _)| section == logical_index)
     }
 
     /// Clears the sort indicator.
     pub fn clear_sort_indicator(&mut self) {
-        if self.sort_indicator_section.is_some() {
-            self.sort_indicator_section = None;
+        if !self.sort_indicators.is_empty() {
+            self.sort_indicators.clear();
+            self.sort_indicators_changed.emit(Vec::new());
             self.base.update();
         }
     }
@@ -647,6 +716,19 @@ impl HeaderView {
         }
     }
 
+    /// Returns the size that fits a section's header text and sort indicator.
+    pub fn section_size_hint(&self, logical_index: usize) -> f32 {
+        match self.orientation {
+            Orientation::Horizontal => {
+                let text = self.header_text(logical_index);
+                text.chars().count() as f32 * HEADER_CHAR_WIDTH
+                    + SECTION_PADDING * 2.0
+                    + SORT_INDICATOR_SPACE
+            }
+            Orientation::Vertical => HEADER_HEIGHT,
+        }
+    }
+
     // =========================================================================
     // Internal Layout
     // =========================================================================
@@ -666,7 +748,9 @@ impl HeaderView {
         }
     }
 
-    fn resize_handle_at(&self, pos: f32) -> Option<usize> {
+    /// Returns the interactively resizable section whose end divider is at
+    /// `pos`, in header coordinates.
+    pub fn section_handle_at(&self, pos: f32) -> Option<usize> {
         let adjusted_pos = pos + self.offset as f32;
 
         for visual in 0..self.section_count {
@@ -707,7 +791,7 @@ impl HeaderView {
         }
     }
 
-    fn header_text(&self, logical_index: usize) -> String {
+    pub(crate) fn header_text(&self, logical_index: usize) -> String {
         if let Some(model) = &self.model
             && let Some(text) = model
                 .header_data(logical_index, self.orientation, ItemRole::Display)
@@ -726,6 +810,38 @@ impl HeaderView {
         }
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Compute the style of a section from the attached style engine.
+    ///
+    /// The header paints [`PseudoElement::Section`] for each visible section,
+    /// using `color` for the sort indicator. A section is hovered or pressed
+    /// with the mouse, `:checked` while it shows the sort indicator, and
+    /// matches structural pseudo-classes such as `:first-child` by its visual
+    /// position.
+    ///
+    /// Returns `None` if no style engine applies to the header, `element` is
+    /// not a section, or `logical_index` is out of range.
+    pub fn sub_control_style(
+        &self,
+        element: PseudoElement,
+        logical_index: usize,
+    ) -> Option<ComputedStyle> {
+        if element != PseudoElement::Section || logical_index >= self.section_count {
+            return None;
+        }
+
+        let mut state = self.base.sub_control_state();
+        state.hovered = self.hover_section == Some(logical_index);
+        state.pressed = self.pressed_section == Some(logical_index);
+        state.checked =
+            Some(self.sort_indicator_shown && self.sort_priority(logical_index).is_some());
+        state.sibling_info = Some((self.visual_index(logical_index), self.section_count));
+        self.base.compute_sub_control_style(element, state)
+    }
+
     // =========================================================================
     // Painting
     // =========================================================================
@@ -780,6 +896,52 @@ impl HeaderView {
     }
 
     fn paint_section(&self, ctx: &mut PaintContext<'_>, logical_index: usize, rect: Rect) {
+        let section_style = self
+            .sub_control_style(PseudoElement::Section, logical_index)
+            .filter(has_visible_box);
+        if let Some(style) = &section_style {
+            paint_styled_box(ctx, rect, style);
+        } else {
+            self.paint_section_background(ctx, logical_index, rect);
+        }
+
+        // Header text
+        let text = self.header_text(logical_index);
+        let padding = SECTION_PADDING;
+        let text_rect = Rect::new(
+            rect.origin.x + padding,
+            rect.origin.y + padding,
+            rect.width() - padding * 2.0 - SORT_INDICATOR_SPACE,
+            rect.height() - padding * 2.0,
+        );
+
+        // For now, we'll draw text using a simple approach
+        // In a full implementation, we'd use TextLayout and TextRenderer
+        // For headers, we can indicate text area with a subtle background
+        let _ = text; // Text will be rendered when full text rendering is integrated
+        let _ = text_rect;
+
+        // Sort indicator
+        if self.sort_indicator_shown
+            && let Some(priority) = self.sort_priority(logical_index)
+        {
+            let color = section_style
+                .as_ref()
+                .map_or(self.text_color, |style| style.color);
+            let order = self.sort_indicators[priority].1;
+            self.paint_sort_indicator(ctx, rect, order, color);
+            if self.sort_indicators.len() > 1 {
+                self.paint_sort_priority(ctx, rect, priority, color);
+            }
+        }
+    }
+
+    fn paint_section_background(
+        &self,
+        ctx: &mut PaintContext<'_>,
+        logical_index: usize,
+        rect: Rect,
+    ) {
         // Section background (highlight if hovered or pressed)
         let bg_color = if self.pressed_section == Some(logical_index) {
             Color::from_rgb8(200, 200, 200)
@@ -816,34 +978,19 @@ impl HeaderView {
                 );
             }
         }
-
-        // Header text
-        let text = self.header_text(logical_index);
-        let padding = 4.0;
-        let text_rect = Rect::new(
-            rect.origin.x + padding,
-            rect.origin.y + padding,
-            rect.width() - padding * 2.0 - 12.0, // Reserve space for sort indicator
-            rect.height() - padding * 2.0,
-        );
-
-        // For now, we'll draw text using a simple approach
-        // In a full implementation, we'd use TextLayout and TextRenderer
-        // For headers, we can indicate text area with a subtle background
-        let _ = text; // Text will be rendered when full text rendering is integrated
-        let _ = text_rect;
-
-        // Sort indicator
-        if self.sort_indicator_shown && self.sort_indicator_section == Some(logical_index) {
-            self.paint_sort_indicator(ctx, rect, self.sort_indicator_order);
-        }
     }
 
-    fn paint_sort_indicator(&self, ctx: &mut PaintContext<'_>, rect: Rect, order: SortOrder) {
+    fn paint_sort_indicator(
+        &self,
+        ctx: &mut PaintContext<'_>,
+        rect: Rect,
+        order: SortOrder,
+        color: Color,
+    ) {
         let indicator_size = 6.0;
         let x = rect.origin.x + rect.width() - indicator_size - 6.0;
         let y = rect.origin.y + (rect.height() - indicator_size) / 2.0;
-        let stroke = Stroke::new(self.text_color, 1.5);
+        let stroke = Stroke::new(color, 1.5);
 
         match order {
             SortOrder::Ascending => {
@@ -865,6 +1012,20 @@ impl HeaderView {
         }
     }
 
+    /// Marks a sort indicator with one dot per priority level, below the
+    /// arrow.
+    fn paint_sort_priority(
+        &self,
+        ctx: &mut PaintContext<'_>,
+        rect: Rect,
+        priority: usize,
+        color: Color,
+    ) {
+        for center in sort_priority_dots(rect, priority) {
+            ctx.renderer().fill_circle(center, 1.0, color);
+        }
+    }
+
     // =========================================================================
     // Event Handling
     // =========================================================================
@@ -880,7 +1041,7 @@ impl HeaderView {
         };
 
         // Check for resize handle first
-        if let Some(logical) = self.resize_handle_at(pos) {
+        if let Some(logical) = self.section_handle_at(pos) {
             self.resize_section = Some(logical);
             self.resize_start_pos = pos;
             self.resize_start_size = self.section_size(logical);
@@ -973,7 +1134,7 @@ impl HeaderView {
 
         // Note: Cursor shape changes would be handled here in a full implementation
         // For now, we just track the hover state
-        let _ = self.resize_handle_at(pos);
+        let _ = self.section_handle_at(pos);
 
         if old_hover != self.hover_section {
             self.base.update();
@@ -982,6 +1143,27 @@ impl HeaderView {
         false
     }
 
+    fn handle_double_click(&mut self, event: &MouseDoubleClickEvent) -> bool {
+        if event.button != MouseButton::Left {
+            return false;
+        }
+
+        let pos = match self.orientation {
+            Orientation::Horizontal => event.local_pos.x,
+            Orientation::Vertical => event.local_pos.y,
+        };
+
+        if let Some(logical) = self.section_handle_at(pos) {
+            self.section_handle_double_clicked.emit(logical);
+            return true;
+        }
+        if let Some(logical) = self.section_at(pos) {
+            self.section_double_clicked.emit(logical);
+            return true;
+        }
+        false
+    }
+
     fn handle_context_menu(&mut self, event: &ContextMenuEvent) -> bool {
         let pos = match self.orientation {
             Orientation::Horizontal => event.local_pos.x + self.offset as f32,
@@ -1033,6 +1215,7 @@ impl Widget for HeaderView {
             WidgetEvent::MousePress(e) => self.handle_mouse_press(e),
             WidgetEvent::MouseRelease(e) => self.handle_mouse_release(e),
             WidgetEvent::MouseMove(e) => self.handle_mouse_move(e),
+            WidgetEvent::DoubleClick(e) => self.handle_double_click(e),
             WidgetEvent::ContextMenu(e) => self.handle_context_menu(e),
             _ => false,
         }
@@ -1129,6 +1312,78 @@ mod tests {
         assert!(header.sort_indicator_section().is_none());
     }
 
+    #[test]
+    fn test_multiple_sort_indicators() {
+        horizon_lattice_core::init_global_registry();
+        let mut header = HeaderView::new(Orientation::Horizontal);
+        header.set_section_count(3);
+
+        let changes = Arc::new(parking_lot::Mutex::new(Vec::new()));
+        let sink = changes.clone();
+        header
+            .sort_indicators_changed
+            .connect(move |keys| sink.lock().push(keys.clone()));
+
+        header.set_sort_indicators(vec![
+            (2, SortOrder::Descending),
+            (0, SortOrder::Ascending),
+            (2, SortOrder::Ascending),
+        ]);
+        assert_eq!(
+            header.sort_indicators(),
+            [(2, SortOrder::Descending), (0, SortOrder::Ascending)]
+        );
+        assert_eq!(header.sort_indicator_section(), Some(2));
+        assert_eq!(header.sort_indicator_order(), SortOrder::Descending);
+        assert_eq!(header.sort_priority(0), Some(1));
+        assert_eq!(header.sort_priority(1), None);
+
+        header.set_sort_indicator(1, SortOrder::Ascending);
+        assert_eq!(header.sort_indicators(), [(1, SortOrder::Ascending)]);
+        assert_eq!(changes.lock().len(), 2);
+    }
+
+    #[test]
+    fn test_sort_priority_dots_are_painted() {
+        use horizon_lattice_render::{Size, SoftwareRenderer};
+
+        horizon_lattice_core::init_global_registry();
+        let mut header = HeaderView::new(Orientation::Horizontal);
+        header.set_section_count(2);
+        header.set_sort_indicators(vec![(0, SortOrder::Ascending), (1, SortOrder::Descending)]);
+
+        let size = Size::new(2.0 * DEFAULT_SECTION_SIZE, HEADER_HEIGHT);
+        let mut renderer = SoftwareRenderer::new();
+        renderer.begin_frame(Color::WHITE, size);
+        let mut ctx =
+            PaintContext::new(&mut renderer, Rect::new(0.0, 0.0, size.width, size.height));
+        header.paint(&mut ctx);
+        renderer.end_frame();
+
+        // The second key gets two dots, the first one.
+        let background = renderer.pixel(2, 2);
+        for (section, count) in [(0, 1), (1, 2)] {
+            let dots: Vec<_> = sort_priority_dots(header.section_rect(section), section).collect();
+            assert_eq!(dots.len(), count);
+            for dot in dots {
+                assert_ne!(renderer.pixel(dot.x as u32, dot.y as u32), background);
+            }
+        }
+    }
+
+    #[test]
+    fn test_default_resize_mode_and_size_hint() {
+        horizon_lattice_core::init_global_registry();
+        let mut header = HeaderView::new(Orientation::Horizontal);
+        header.set_default_resize_mode(ResizeMode::ResizeToContents);
+        header.set_section_count(2);
+        assert_eq!(header.resize_mode(1), ResizeMode::ResizeToContents);
+
+        // Column letters fit in a narrow section.
+        assert!(header.section_size_hint(0) < DEFAULT_SECTION_SIZE);
+        assert!(header.section_size_hint(0) > SORT_INDICATOR_SPACE);
+    }
+
     #[test]
     fn test_column_to_letter() {
         assert_eq!(column_to_letter(0), "A");
@@ -1161,4 +1416,49 @@ mod tests {
 
         assert!(signal_received.load(Ordering::SeqCst));
     }
+
+    #[test]
+    fn test_section_sub_control_styles() {
+        use crate::widget::style::set_tree_style_engine;
+        use horizon_lattice_core::init_global_registry;
+        use horizon_lattice_style::prelude::{StyleEngine, StylePriority, StyleSheet};
+        use parking_lot::RwLock;
+
+        init_global_registry();
+        let mut header = HeaderView::new(Orientation::Horizontal);
+        header.set_section_count(3);
+        header.move_section(2, 0);
+
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "HeaderView::section { background-color: #ffffff; } \
+                 HeaderView::section:checked { background-color: #0000ff; } \
+                 HeaderView::section:first-child { font-size: 20px; }",
+                StylePriority::Application,
+            )
+            .unwrap(),
+        );
+        set_tree_style_engine(header.object_id(), Some(Arc::new(RwLock::new(engine))));
+
+        header.set_sort_indicator(1, SortOrder::Ascending);
+        let sorted = header.sub_control_style(PseudoElement::Section, 1).unwrap();
+        assert_eq!(sorted.background.as_solid(), Some(Color::BLUE));
+        let other = header.sub_control_style(PseudoElement::Section, 0).unwrap();
+        assert_eq!(other.background.as_solid(), Some(Color::WHITE));
+
+        // Structural pseudo-classes follow the visual order
+        let first = header.sub_control_style(PseudoElement::Section, 2).unwrap();
+        assert_eq!(first.font_size, 20.0);
+        assert_ne!(other.font_size, 20.0);
+
+        assert!(
+            header
+                .sub_control_style(PseudoElement::Section, 3)
+                .is_none()
+        );
+        assert!(header.sub_control_style(PseudoElement::Handle, 0).is_none());
+
+        set_tree_style_engine(header.object_id(), None);
+    }
 }
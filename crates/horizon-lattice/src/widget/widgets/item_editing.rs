@@ -0,0 +1,266 @@
+//! In-place item editing shared by the item views.
+//!
+//! [`EditTriggers`] selects which user actions open an editor. While an
+//! editor is open, the view routes its events through an [`EditorSession`],
+//! which forwards them to the editor widget and reports when the edit
+//! should be committed, cancelled or moved to another item.
+
+use horizon_lattice_render::{Color, Rect, Renderer};
+
+use crate::model::{
+    ItemDelegate, ItemEditor, ItemModel, ItemRole, ModelIndex, StyleOptionViewItem,
+};
+use crate::widget::{Key, KeyPressEvent, PaintContext, WidgetEvent};
+
+/// User actions that start editing an item in a view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EditTriggers(u8);
+
+impl EditTriggers {
+    /// Items are only edited through the view's `edit` method.
+    pub const NONE: Self = Self(0);
+    /// Double-clicking an item.
+    pub const DOUBLE_CLICKED: Self = Self(1 << 0);
+    /// Clicking an item that is already current and selected.
+    pub const SELECTED_CLICKED: Self = Self(1 << 1);
+    /// Pressing F2 on the current item.
+    pub const EDIT_KEY_PRESSED: Self = Self(1 << 2);
+    /// Typing text on the current item, which replaces its value.
+    pub const ANY_KEY_PRESSED: Self = Self(1 << 3);
+    /// All triggers.
+    pub const ALL: Self = Self(
+        Self::DOUBLE_CLICKED.0
+            | Self::SELECTED_CLICKED.0
+            | Self::EDIT_KEY_PRESSED.0
+            | Self::ANY_KEY_PRESSED.0,
+    );
+
+    /// Returns true if this set contains all triggers in `other`.
+    pub fn contains(self, other: Self) -> bool {
+        (self.0 & other.0) == other.0
+    }
+
+    /// Returns true if `event` starts editing under these triggers.
+    pub(crate) fn accepts_key(self, event: &KeyPressEvent) -> bool {
+        (event.key == Key::F2 && self.contains(Self::EDIT_KEY_PRESSED))
+            || (is_typed_text(event) && self.contains(Self::ANY_KEY_PRESSED))
+    }
+}
+
+impl Default for EditTriggers {
+    fn default() -> Self {
+        Self::DOUBLE_CLICKED | Self::EDIT_KEY_PRESSED
+    }
+}
+
+impl std::ops::BitOr for EditTriggers {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for EditTriggers {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Returns true if `event` types printable text rather than a shortcut.
+fn is_typed_text(event: &KeyPressEvent) -> bool {
+    !event.modifiers.control
+        && !event.modifiers.alt
+        && !event.modifiers.meta
+        && event.text.chars().next().is_some_and(|c| !c.is_control())
+}
+
+/// What the view should do with an event after offering it to the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EditorEvent {
+    /// The editor consumed the event.
+    Handled,
+    /// The event isn't meant for the editor.
+    Ignored,
+    /// Write the value to the model and close the editor.
+    Commit,
+    /// Close the editor without changing the model.
+    Cancel,
+    /// Commit, then edit the next (or previous) editable item.
+    CommitAndMove {
+        /// Move to the previous item instead of the next.
+        backward: bool,
+    },
+    /// The user clicked outside the editor: commit, then let the view
+    /// handle the click.
+    CommitAndContinue,
+}
+
+/// An editor widget open over one item of a view.
+pub(crate) struct EditorSession {
+    index: ModelIndex,
+    editor: Box<dyn ItemEditor>,
+}
+
+impl EditorSession {
+    /// Opens an editor for the item described by `option`.
+    ///
+    /// Returns `None` if the item isn't editable or the delegate provides
+    /// no editor for it.
+    pub(crate) fn open(
+        model: &dyn ItemModel,
+        delegate: &dyn ItemDelegate,
+        option: &StyleOptionViewItem,
+    ) -> Option<Self> {
+        let index = &option.index;
+        if !model.flags(index).editable {
+            return None;
+        }
+        let mut data = model.data(index, ItemRole::Edit);
+        if data.is_none() {
+            data = model.data(index, ItemRole::Display);
+        }
+
+        let mut editor = delegate.create_editor(option, &data)?;
+        delegate.set_editor_data(editor.as_mut(), &data);
+        delegate.set_editor_geometry(editor.as_mut(), option);
+        editor.widget_base_mut().set_focused(true);
+        #[allow(deprecated)]
+        {
+            delegate.update_editor_geometry(option.rect);
+            delegate.start_editing(option);
+        }
+        Some(Self {
+            index: index.clone(),
+            editor,
+        })
+    }
+
+    /// The item being edited.
+    pub(crate) fn index(&self) -> &ModelIndex {
+        &self.index
+    }
+
+    /// Moves the editor after the item's rect changed.
+    pub(crate) fn update_geometry(
+        &mut self,
+        delegate: &dyn ItemDelegate,
+        option: &StyleOptionViewItem,
+    ) {
+        delegate.set_editor_geometry(self.editor.as_mut(), option);
+        #[allow(deprecated)]
+        delegate.update_editor_geometry(option.rect);
+    }
+
+    /// Writes the edited value to the model, returning whether it was accepted.
+    pub(crate) fn commit(mut self, model: &dyn ItemModel, delegate: &dyn ItemDelegate) -> bool {
+        #[allow(deprecated)]
+        if let Some(value) = delegate.commit_editing() {
+            return model.set_data(&self.index, value, ItemRole::Edit);
+        }
+        delegate.set_model_data(self.editor.as_mut(), model, &self.index)
+    }
+
+    /// Closes the editor without changing the model.
+    pub(crate) fn cancel(self, delegate: &dyn ItemDelegate) {
+        #[allow(deprecated)]
+        delegate.cancel_editing();
+    }
+
+    /// Offers a view event to the editor.
+    ///
+    /// Key presses always go to the editor. Mouse events inside the editor
+    /// are forwarded in editor coordinates; a press outside it ends the edit.
+    pub(crate) fn handle_event(&mut self, event: &mut WidgetEvent) -> EditorEvent {
+        let rect = self.editor.geometry();
+        let offset = |p: horizon_lattice_render::Point| {
+            horizon_lattice_render::Point::new(p.x - rect.origin.x, p.y - rect.origin.y)
+        };
+
+        let mut forwarded = match event {
+            WidgetEvent::KeyPress(e) => {
+                if !self.editor.captures_navigation_keys() {
+                    match e.key {
+                        Key::Enter | Key::NumpadEnter => return EditorEvent::Commit,
+                        Key::Escape => return EditorEvent::Cancel,
+                        Key::Tab => {
+                            return EditorEvent::CommitAndMove {
+                                backward: e.modifiers.shift,
+                            };
+                        }
+                        _ => {}
+                    }
+                }
+                self.editor.event(event);
+                return EditorEvent::Handled;
+            }
+            WidgetEvent::KeyRelease(_) => {
+                self.editor.event(event);
+                return EditorEvent::Handled;
+            }
+            WidgetEvent::MousePress(e) => {
+                if !rect.contains(e.local_pos) {
+                    return EditorEvent::CommitAndContinue;
+                }
+                let mut e = *e;
+                e.local_pos = offset(e.local_pos);
+                WidgetEvent::MousePress(e)
+            }
+            WidgetEvent::DoubleClick(e) if rect.contains(e.local_pos) => {
+                let mut e = *e;
+                e.local_pos = offset(e.local_pos);
+                WidgetEvent::DoubleClick(e)
+            }
+            WidgetEvent::MouseRelease(e) if rect.contains(e.local_pos) => {
+                let mut e = *e;
+                e.local_pos = offset(e.local_pos);
+                WidgetEvent::MouseRelease(e)
+            }
+            WidgetEvent::MouseMove(e) if rect.contains(e.local_pos) => {
+                let mut e = *e;
+                e.local_pos = offset(e.local_pos);
+                WidgetEvent::MouseMove(e)
+            }
+            _ => return EditorEvent::Ignored,
+        };
+
+        if self.editor.event(&mut forwarded) {
+            EditorEvent::Handled
+        } else {
+            EditorEvent::Ignored
+        }
+    }
+
+    /// Paints the editor over its item.
+    pub(crate) fn paint(&self, ctx: &mut PaintContext<'_>) {
+        let rect = self.editor.geometry();
+        let renderer = ctx.renderer();
+        renderer.save();
+        renderer.fill_rect(rect, Color::WHITE);
+        renderer.translate(rect.origin.x, rect.origin.y);
+        let mut editor_ctx =
+            PaintContext::new(renderer, Rect::new(0.0, 0.0, rect.width(), rect.height()));
+        self.editor.paint(&mut editor_ctx);
+        ctx.renderer().restore();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::widget::KeyboardModifiers;
+
+    #[test]
+    fn test_edit_triggers_accept_keys() {
+        let f2 = KeyPressEvent::new(Key::F2, KeyboardModifiers::NONE, "", false);
+        let typed = KeyPressEvent::new(Key::A, KeyboardModifiers::NONE, "a", false);
+
+        let triggers = EditTriggers::default();
+        assert!(triggers.accepts_key(&f2));
+        assert!(!triggers.accepts_key(&typed));
+
+        let triggers = EditTriggers::ALL;
+        assert!(triggers.accepts_key(&typed));
+        assert!(!EditTriggers::NONE.accepts_key(&f2));
+    }
+}
@@ -36,6 +36,7 @@ use horizon_lattice_render::{
     Color, Font, FontFamily, FontSystem, HorizontalAlign, Point, Rect, Renderer, RichText,
     RichTextSpan, Size, TextLayout, TextLayoutOptions, TextRenderer, VerticalAlign, WrapMode,
 };
+use horizon_lattice_style::prelude::{content_rect, paint_styled_box};
 
 use crate::widget::{
     CursorShape, FocusPolicy, Key, KeyPressEvent, MouseButton, MouseDoubleClickEvent,
@@ -124,8 +125,10 @@ pub struct Label {
     /// The font to use for text rendering.
     font: Font,
 
-    /// Text color.
-    text_color: Color,
+    /// Explicit text color.
+    ///
+    /// When `None`, the color comes from the style engine, falling back to black.
+    text_color: Option<Color>,
 
     /// Cached text layout for efficient rendering.
     /// Uses RwLock for thread-safe interior mutability since Widget requires Sync.
@@ -304,7 +307,7 @@ impl Label {
             word_wrap: false,
             elide_mode: ElideMode::None,
             font: Font::new(FontFamily::SansSerif, 14.0),
-            text_color: Color::BLACK,
+            text_color: None,
             cached_layout: RwLock::new(None),
             mnemonic_char: parsed.mnemonic_char,
             mnemonic_byte_pos: parsed.mnemonic_byte_pos,
@@ -370,7 +373,7 @@ impl Label {
             word_wrap: false,
             elide_mode: ElideMode::None,
             font: Font::new(FontFamily::SansSerif, 14.0),
-            text_color: Color::BLACK,
+            text_color: None,
             cached_layout: RwLock::new(None),
             // Rich text does not support mnemonics
             mnemonic_char: None,
@@ -694,24 +697,35 @@ impl Label {
     }
 
     /// Get the text color.
+    ///
+    /// Returns the explicitly set color, or black if none was set.
     pub fn text_color(&self) -> Color {
-        self.text_color
+        self.text_color.unwrap_or(Color::BLACK)
     }
 
     /// Set the text color.
+    ///
+    /// An explicit text color overrides the `color` from the style engine.
     pub fn set_text_color(&mut self, color: Color) {
-        if self.text_color != color {
-            self.text_color = color;
+        if self.text_color != Some(color) {
+            self.text_color = Some(color);
             self.base.update();
         }
     }
 
     /// Set text color using builder pattern.
     pub fn with_text_color(mut self, color: Color) -> Self {
-        self.text_color = color;
+        self.text_color = Some(color);
         self
     }
 
+    /// Clear the explicit text color, using the style engine's color instead.
+    pub fn clear_text_color(&mut self) {
+        if self.text_color.take().is_some() {
+            self.base.update();
+        }
+    }
+
     // =========================================================================
     // Selection API
     // =========================================================================
@@ -1753,11 +1767,21 @@ impl Widget for Label {
     }
 
     fn paint(&self, ctx: &mut PaintContext<'_>) {
+        let style = self.base.compute_style();
+        let mut rect = ctx.rect();
+        if let Some(style) = &style {
+            paint_styled_box(ctx, rect, style);
+            rect = content_rect(rect, style);
+        }
+
         if self.text.is_empty() {
             return;
         }
 
-        let rect = ctx.rect();
+        let text_color = self
+            .text_color
+            .or(style.as_ref().map(|style| style.color))
+            .unwrap_or(Color::BLACK);
         let width_constraint = if self.word_wrap || self.elide_mode != ElideMode::None {
             Some(rect.width())
         } else {
@@ -1817,7 +1841,7 @@ impl Widget for Label {
         // Create text renderer and prepare glyphs
         if let Ok(mut text_renderer) = TextRenderer::new()
             && let Ok(prepared_glyphs) =
-                text_renderer.prepare_layout(&mut font_system, &layout, position, self.text_color)
+                text_renderer.prepare_layout(&mut font_system, &layout, position, text_color)
         {
             // In a full implementation, we would render the prepared glyphs
             // through the text render pass. For now, we draw background rectangles
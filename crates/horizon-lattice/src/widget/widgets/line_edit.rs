@@ -799,13 +799,16 @@ impl LineEdit {
     /// let model = StringListModel::new(vec!["apple".to_string(), "banana".to_string()]);
     /// edit.set_completer(Some(Completer::new(Box::new(model))));
     /// ```
-    pub fn set_completer(&mut self, completer: Option<Completer>) {
+    pub fn set_completer(&mut self, mut completer: Option<Completer>) {
+        if let Some(completer) = &mut completer {
+            completer.set_owner(Some(self.base.object_id()));
+        }
         self.completer = completer;
     }
 
     /// Set a completer using builder pattern.
     pub fn with_completer(mut self, completer: Completer) -> Self {
-        self.completer = Some(completer);
+        self.set_completer(Some(completer));
         self
     }
 
@@ -3178,6 +3181,20 @@ impl Widget for LineEdit {
                 event.accept();
                 true
             }
+            #[cfg(feature = "async-runtime")]
+            WidgetEvent::Timer(_) => {
+                // Sent by the completer when its async results arrive.
+                if self
+                    .completer
+                    .as_mut()
+                    .is_some_and(|c| c.apply_async_results())
+                {
+                    self.base.update();
+                    true
+                } else {
+                    false
+                }
+            }
             _ => false,
         }
     }
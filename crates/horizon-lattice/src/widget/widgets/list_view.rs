@@ -33,19 +33,20 @@ use horizon_lattice_render::{Color, Point, Rect, Renderer, Size};
 
 use crate::model::selection::{SelectionFlags, SelectionMode, SelectionModel};
 use crate::model::{
-    DefaultItemDelegate, DelegatePaintContext, ItemDelegate, ItemModel, ItemRole, ModelIndex,
-    StyleOptionViewItem, ViewItemFeatures, ViewItemState,
+    DefaultItemDelegate, DelegatePaintContext, ItemDelegate, ItemModel, ItemRole,
+    ModelChangeTracker, ModelIndex, StyleOptionViewItem, ViewItemFeatures, ViewItemState,
 };
 use crate::widget::drag_drop::{
-    DragData, DragEnterEvent, DragLeaveEvent, DragMoveEvent, DropAction, DropEvent,
-    DropIndicatorState, DropPosition,
+    DragData, DragDropManager, DragEnterEvent, DragLeaveEvent, DragMoveEvent, DropAction,
+    DropEvent, DropIndicatorState, DropPosition,
 };
 use crate::widget::{
-    ContextMenuEvent, FocusPolicy, Key, KeyPressEvent, MouseButton, MouseMoveEvent,
-    MousePressEvent, MouseReleaseEvent, PaintContext, SizeHint, SizePolicy, SizePolicyPair,
-    WheelEvent, Widget, WidgetBase, WidgetEvent,
+    ContextMenuEvent, FocusPolicy, Key, KeyPressEvent, KeyboardModifiers, MouseButton,
+    MouseMoveEvent, MousePressEvent, MouseReleaseEvent, PaintContext, SizeHint, SizePolicy,
+    SizePolicyPair, WheelEvent, Widget, WidgetBase, WidgetEvent,
 };
 
+use super::item_editing::{EditTriggers, EditorEvent, EditorSession};
 use super::scroll_area::ScrollBarPolicy;
 
 /// Display mode for ListView.
@@ -93,6 +94,7 @@ pub enum DragDropMode {
 /// - Multiple selection modes
 /// - Keyboard and mouse navigation
 /// - Custom rendering via ItemDelegate
+/// - In-place editing with delegate-created editors
 ///
 /// # Signals
 ///
@@ -105,6 +107,8 @@ pub struct ListView {
 
     // Model/View
     model: Option<Arc<dyn ItemModel>>,
+    /// Marks the layout stale when the model's rows change.
+    model_changes: Option<ModelChangeTracker>,
     selection_model: SelectionModel,
     delegate: Arc<dyn ItemDelegate>,
 
@@ -146,6 +150,12 @@ pub struct ListView {
     drag_start_pos: Option<Point>,
     dragging_row: Option<usize>,
 
+    // Editing
+    edit_triggers: EditTriggers,
+    editor: Option<EditorSession>,
+    /// Whether the pressed item was already current and selected.
+    pressed_on_selected: bool,
+
     // Signals
     /// Emitted when an item is clicked.
     pub clicked: Signal<ModelIndex>,
@@ -180,6 +190,7 @@ impl ListView {
         Self {
             base,
             model: None,
+            model_changes: None,
             selection_model: SelectionModel::new(),
             delegate: Arc::new(DefaultItemDelegate::new()),
             view_mode: ListViewMode::ListMode,
@@ -206,6 +217,9 @@ impl ListView {
             drop_indicator_state: DropIndicatorState::new(),
             drag_start_pos: None,
             dragging_row: None,
+            edit_triggers: EditTriggers::default(),
+            editor: None,
+            pressed_on_selected: false,
             clicked: Signal::new(),
             double_clicked: Signal::new(),
             activated: Signal::new(),
@@ -253,6 +267,12 @@ impl ListView {
         self
     }
 
+    /// Sets the edit triggers using builder pattern.
+    pub fn with_edit_triggers(mut self, triggers: EditTriggers) -> Self {
+        self.edit_triggers = triggers;
+        self
+    }
+
     // =========================================================================
     // Model
     // =========================================================================
@@ -264,6 +284,8 @@ impl ListView {
 
     /// Sets the model.
     pub fn set_model(&mut self, model: Option<Arc<dyn ItemModel>>) {
+        self.close_editor();
+        self.model_changes = model.clone().map(ModelChangeTracker::new);
         self.model = model;
         self.selection_model.reset();
         self.layout_dirty = true;
@@ -425,6 +447,7 @@ impl ListView {
         if self.scroll_x != new_x || self.scroll_y != new_y {
             self.scroll_x = new_x;
             self.scroll_y = new_y;
+            self.fetch_more_if_needed();
             self.base.update();
         }
     }
@@ -469,6 +492,7 @@ impl ListView {
         let max = self.max_scroll_y();
         if self.scroll_y != max {
             self.scroll_y = max;
+            self.fetch_more_if_needed();
             self.base.update();
         }
     }
@@ -478,6 +502,136 @@ impl ListView {
         self.scroll_to(index);
     }
 
+    // =========================================================================
+    // Editing
+    // =========================================================================
+
+    /// Returns the actions that start editing an item.
+    pub fn edit_triggers(&self) -> EditTriggers {
+        self.edit_triggers
+    }
+
+    /// Sets the actions that start editing an item.
+    pub fn set_edit_triggers(&mut self, triggers: EditTriggers) {
+        self.edit_triggers = triggers;
+    }
+
+    /// Opens an editor over the item at `index`.
+    ///
+    /// Any edit in progress is committed first. Returns `false` if the item
+    /// isn't editable or the delegate provides no editor for it.
+    pub fn edit(&mut self, index: &ModelIndex) -> bool {
+        self.commit_editor();
+        let Some(model) = self.model.clone() else {
+            return false;
+        };
+        self.scroll_to(index);
+        let Some(option) = self.editor_option(index.row()) else {
+            return false;
+        };
+        self.editor = EditorSession::open(model.as_ref(), self.delegate.as_ref(), &option);
+        self.base.update();
+        self.editor.is_some()
+    }
+
+    /// Returns `true` while an editor is open.
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    /// Writes the open editor's value to the model and closes the editor.
+    ///
+    /// Returns `true` if the model accepted the value.
+    pub fn commit_editor(&mut self) -> bool {
+        let Some(session) = self.editor.take() else {
+            return false;
+        };
+        let committed = self
+            .model
+            .as_ref()
+            .is_some_and(|model| session.commit(model.as_ref(), self.delegate.as_ref()));
+        self.base.update();
+        committed
+    }
+
+    /// Closes the open editor without changing the model.
+    pub fn close_editor(&mut self) {
+        if let Some(session) = self.editor.take() {
+            session.cancel(self.delegate.as_ref());
+            self.base.update();
+        }
+    }
+
+    /// Builds the style option for the editor of a row.
+    fn editor_option(&self, row: usize) -> Option<StyleOptionViewItem> {
+        let rect = self.visual_rect(&ModelIndex::new(row, 0, ModelIndex::invalid()))?;
+        Some(self.build_style_option(row, rect))
+    }
+
+    /// Keeps the open editor over its item after scrolling or a layout change.
+    fn update_editor_geometry(&mut self) {
+        let Some(row) = self.editor.as_ref().map(|e| e.index().row()) else {
+            return;
+        };
+        match self.editor_option(row) {
+            Some(option) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.update_geometry(self.delegate.as_ref(), &option);
+                }
+            }
+            // The item is gone.
+            None => self.close_editor(),
+        }
+    }
+
+    /// Finds the next (or previous) editable row.
+    fn next_editable_row(&self, row: usize, backward: bool) -> Option<usize> {
+        let model = self.model.as_ref()?;
+        let root = ModelIndex::invalid();
+        let editable = |r: &usize| model.flags(&model.index(*r, 0, &root)).editable;
+        if backward {
+            (0..row).rev().find(editable)
+        } else {
+            (row + 1..model.row_count(&root)).find(editable)
+        }
+    }
+
+    /// Offers an event to the open editor.
+    ///
+    /// Returns `Some(handled)` if the editor dealt with the event, or `None`
+    /// if the view should process it.
+    fn handle_editor_event(&mut self, event: &mut WidgetEvent) -> Option<bool> {
+        let session = self.editor.as_mut()?;
+        let row = session.index().row();
+        match session.handle_event(event) {
+            EditorEvent::Handled => {
+                self.base.update();
+                Some(true)
+            }
+            EditorEvent::Ignored => None,
+            EditorEvent::Commit => {
+                self.commit_editor();
+                Some(true)
+            }
+            EditorEvent::Cancel => {
+                self.close_editor();
+                Some(true)
+            }
+            EditorEvent::CommitAndMove { backward } => {
+                self.commit_editor();
+                if let Some(next) = self.next_editable_row(row, backward) {
+                    self.move_to_row(next, &KeyboardModifiers::NONE);
+                    self.edit(&ModelIndex::new(next, 0, ModelIndex::invalid()));
+                }
+                Some(true)
+            }
+            EditorEvent::CommitAndContinue => {
+                self.commit_editor();
+                None
+            }
+        }
+    }
+
     fn max_scroll_x(&self) -> i32 {
         let viewport = self.viewport_rect();
         (self.content_size.width - viewport.width()).max(0.0) as i32
@@ -552,34 +706,83 @@ impl ListView {
         )
     }
 
-    /// Creates drag data from the selected items.
+    /// Starts dragging the selected items with the given drag manager.
     ///
-    /// Override this method in subclasses to customize the drag data.
+    /// The drag data is created by the model's `mime_data`, and the allowed
+    /// actions are the model's `supported_drag_actions` (only moves in
+    /// `InternalMove` mode). Returns `false` if dragging is disabled or
+    /// nothing is selected.
+    pub fn start_drag(&self, manager: &mut DragDropManager, position: Point) -> bool {
+        if !self.drag_enabled() {
+            return false;
+        }
+        let Some(model) = &self.model else {
+            return false;
+        };
+        let Some(data) = self.create_drag_data(self.selection_model.selected_indices()) else {
+            return false;
+        };
+
+        let mut actions = model.supported_drag_actions();
+        if self.drag_drop_mode == DragDropMode::InternalMove {
+            actions &= DropAction::MOVE;
+        }
+        if actions == DropAction::NONE {
+            return false;
+        }
+        manager.start_drag(data, actions, position);
+        true
+    }
+
+    /// Creates drag data for the given items using the model's `mime_data`.
     fn create_drag_data(&self, indices: &[ModelIndex]) -> Option<DragData> {
         if indices.is_empty() {
             return None;
         }
+        let model = self.model.as_ref()?;
 
-        let mut data = DragData::new();
+        let mut data = model.mime_data(indices);
+        data.set_source_widget(self.base.object_id());
+        if data.is_empty() { None } else { Some(data) }
+    }
 
-        // Extract text from selected items
-        if let Some(model) = &self.model {
-            let texts: Vec<String> = indices
-                .iter()
-                .filter_map(|index| {
-                    model
-                        .data(index, ItemRole::Display)
-                        .as_string()
-                        .map(String::from)
-                })
-                .collect();
+    /// Chooses the action for dropping `data` onto this view.
+    ///
+    /// Items dragged from this view are moved, and anything else is copied,
+    /// as far as `supported` and the model's drop actions allow.
+    fn drop_action_for(&self, data: &DragData, supported: DropAction) -> DropAction {
+        let Some(model) = &self.model else {
+            return DropAction::NONE;
+        };
+        let internal = data.source_widget() == Some(self.base.object_id());
+        let allowed = model.supported_drop_actions() & supported;
 
-            if !texts.is_empty() {
-                data.set_text(texts.join("\n"));
-            }
+        if self.drag_drop_mode == DragDropMode::InternalMove {
+            return if internal {
+                allowed & DropAction::MOVE
+            } else {
+                DropAction::NONE
+            };
+        }
+        if internal && allowed.can_move() {
+            DropAction::MOVE
+        } else if allowed.can_copy() {
+            DropAction::COPY
+        } else {
+            allowed.preferred()
         }
+    }
 
-        if data.is_empty() { None } else { Some(data) }
+    /// Returns the model row that a drop at `point` inserts at.
+    fn drop_row_for_point(&self, point: Point) -> usize {
+        match self.drop_position_for_point(point) {
+            (Some(row), DropPosition::AboveItem) => row,
+            (Some(row), DropPosition::BelowItem) => row + 1,
+            _ => self
+                .model
+                .as_ref()
+                .map_or(0, |m| m.row_count(&ModelIndex::invalid())),
+        }
     }
 
     /// Returns the drop action for the given position.
@@ -617,14 +820,13 @@ impl ListView {
             return false;
         }
 
-        // Accept the drag if it has text data
-        if event.data().has_text() || event.data().has_urls() {
-            event.accept_proposed_action();
-            event.set_proposed_action(DropAction::COPY);
-            return true;
+        let action = self.drop_action_for(event.data(), event.supported_actions);
+        if action == DropAction::NONE {
+            return false;
         }
-
-        false
+        event.set_proposed_action(action);
+        event.accept_proposed_action();
+        true
     }
 
     /// Handles the DragMove event.
@@ -663,6 +865,15 @@ impl ListView {
             self.drop_indicator_state.clear();
         }
 
+        // Only propose an action the model accepts at this position.
+        let action = self.drop_action_for(event.data(), event.supported_actions);
+        let row = self.drop_row_for_point(event.local_pos);
+        let accepted = action != DropAction::NONE
+            && self.model.as_ref().is_some_and(|m| {
+                m.can_drop_mime_data(event.data(), action, row, 0, &ModelIndex::invalid())
+            });
+        event.set_proposed_action(if accepted { action } else { DropAction::NONE });
+
         event.accept();
         self.base.update();
         true
@@ -676,24 +887,33 @@ impl ListView {
     }
 
     /// Handles the Drop event.
+    ///
+    /// The dropped data is passed to the model's `drop_mime_data`, which
+    /// inserts copies or, for items dragged from this view, moves them.
     fn handle_drop(&mut self, event: &mut DropEvent) -> bool {
         if !self.drop_enabled() {
             return false;
         }
 
-        let (_drop_row, _drop_position) = self.drop_position_for_point(event.local_pos);
+        let row = self.drop_row_for_point(event.local_pos);
         self.drop_indicator_state.clear();
         self.base.update();
 
-        // Process the dropped data
-        if event.data().has_text() || event.data().has_urls() {
-            // For now, just accept the drop
-            // In a real implementation, this would insert items into the model
-            event.accept();
-            return true;
+        let action = self.drop_action_for(event.data(), event.action);
+        let Some(model) = self.model.clone() else {
+            return false;
+        };
+        if action == DropAction::NONE
+            || !model.drop_mime_data(event.data(), action, row, 0, &ModelIndex::invalid())
+        {
+            return false;
         }
 
-        false
+        // Dropped rows invalidate the selected indices.
+        self.selection_model.clear_selection();
+        self.layout_dirty = true;
+        event.accept();
+        true
     }
 
     /// Paints the drop indicator if active.
@@ -784,9 +1004,47 @@ impl ListView {
     // =========================================================================
 
     fn ensure_layout(&mut self) {
+        if self
+            .model_changes
+            .as_ref()
+            .is_some_and(ModelChangeTracker::take_changed)
+        {
+            self.layout_dirty = true;
+        }
         if self.layout_dirty {
             self.update_layout();
         }
+        self.fetch_more_if_needed();
+        self.update_editor_geometry();
+    }
+
+    /// Asks the model for more rows when less than a viewport of loaded
+    /// content remains past the visible area.
+    fn fetch_more_if_needed(&self) {
+        let Some(model) = &self.model else {
+            return;
+        };
+        let root = ModelIndex::invalid();
+        if !model.can_fetch_more(&root) {
+            return;
+        }
+
+        let viewport = self.viewport_rect();
+        let horizontal = self.view_mode == ListViewMode::IconMode && self.flow == Flow::TopToBottom;
+        let (remaining, extent) = if horizontal {
+            (
+                self.content_size.width - self.scroll_x as f32 - viewport.width(),
+                viewport.width(),
+            )
+        } else {
+            (
+                self.content_size.height - self.scroll_y as f32 - viewport.height(),
+                viewport.height(),
+            )
+        };
+        if remaining <= extent {
+            model.fetch_more(&root);
+        }
     }
 
     fn update_layout(&mut self) {
@@ -1068,6 +1326,12 @@ impl ListView {
         if let Some(index) = self.index_at(event.local_pos) {
             let row = index.row();
             self.pressed_row = Some(row);
+            let current = self.selection_model.current_index();
+            self.pressed_on_selected = current.is_valid()
+                && current.row() == row
+                && self.selection_model.is_row_selected(row)
+                && !event.modifiers.shift
+                && !event.modifiers.control;
 
             // Handle selection based on modifiers
             let mode = self.selection_model.selection_mode();
@@ -1138,14 +1402,23 @@ impl ListView {
                 && now.duration_since(last_time).as_millis() < 500
             {
                 self.double_clicked.emit(index.clone());
-                self.activated.emit(index);
+                self.activated.emit(index.clone());
                 self.last_click_time = None;
                 self.last_click_row = None;
+                if self.edit_triggers.contains(EditTriggers::DOUBLE_CLICKED) {
+                    self.edit(&index);
+                }
                 return true;
             }
 
             self.last_click_time = Some(now);
             self.last_click_row = Some(row);
+
+            if self.pressed_on_selected
+                && self.edit_triggers.contains(EditTriggers::SELECTED_CLICKED)
+            {
+                self.edit(&index);
+            }
         }
 
         true
@@ -1173,6 +1446,7 @@ impl ListView {
 
         if self.scroll_y != new_y {
             self.scroll_y = new_y;
+            self.fetch_more_if_needed();
             self.base.update();
             return true;
         }
@@ -1197,6 +1471,16 @@ impl ListView {
             return false;
         }
 
+        if self.edit_triggers.accepts_key(event)
+            && self.edit(&ModelIndex::new(current_row, 0, ModelIndex::invalid()))
+        {
+            // Typed text goes to the new editor, replacing the value.
+            if event.key != Key::F2 {
+                self.handle_editor_event(&mut WidgetEvent::KeyPress(event.clone()));
+            }
+            return true;
+        }
+
         match event.key {
             Key::ArrowUp => {
                 let new_row = current_row.saturating_sub(1);
@@ -1535,6 +1819,9 @@ impl Widget for ListView {
         // For now, paint with potentially stale layout (will be correct after first interaction)
         self.paint_background(ctx);
         self.paint_items(ctx);
+        if let Some(editor) = &self.editor {
+            editor.paint(ctx);
+        }
         self.paint_drop_indicator(ctx);
         self.paint_scrollbars(ctx);
     }
@@ -1543,6 +1830,13 @@ impl Widget for ListView {
         // Ensure layout is up to date before handling events
         self.ensure_layout();
 
+        if let Some(handled) = self.handle_editor_event(event) {
+            if handled {
+                event.accept();
+            }
+            return handled;
+        }
+
         match event {
             WidgetEvent::MousePress(e) => {
                 if self.handle_mouse_press(e) {
@@ -1699,4 +1993,80 @@ mod tests {
 
         assert!(signal_received.load(Ordering::SeqCst));
     }
+
+    #[test]
+    fn test_drop_moves_and_copies_items() {
+        use crate::model::ListModel;
+        setup();
+
+        let source_model = Arc::new(ListModel::new(vec![
+            "a".to_string(),
+            "b".to_string(),
+            "c".to_string(),
+        ]));
+        let mut source = ListView::new();
+        source.set_model(Some(source_model.clone()));
+        source.set_drag_drop_mode(DragDropMode::DragDrop);
+
+        let target_model = Arc::new(ListModel::new(vec!["x".to_string()]));
+        let mut target = ListView::new();
+        target.set_model(Some(target_model.clone()));
+        target.set_drag_drop_mode(DragDropMode::DragDrop);
+
+        // Dropping items dragged from the same view moves them.
+        let data = source
+            .create_drag_data(&[ModelIndex::new(0, 0, ModelIndex::invalid())])
+            .unwrap();
+        let both = DropAction::COPY | DropAction::MOVE;
+        let mut event = DropEvent::new(Arc::new(data), Point::ZERO, Point::ZERO, both);
+        assert!(source.handle_drop(&mut event));
+        assert_eq!(*source_model.items(), vec!["b", "c", "a"]);
+
+        // Dropping them onto another view copies them.
+        let data = source
+            .create_drag_data(&[ModelIndex::new(0, 0, ModelIndex::invalid())])
+            .unwrap();
+        let mut event = DropEvent::new(Arc::new(data), Point::ZERO, Point::ZERO, both);
+        assert!(target.handle_drop(&mut event));
+        assert_eq!(*target_model.items(), vec!["x", "b"]);
+        assert_eq!(source_model.len(), 3);
+
+        // Internal-move views reject drags from other views.
+        target.set_drag_drop_mode(DragDropMode::InternalMove);
+        let data = source
+            .create_drag_data(&[ModelIndex::new(1, 0, ModelIndex::invalid())])
+            .unwrap();
+        let mut event = DropEvent::new(Arc::new(data), Point::ZERO, Point::ZERO, both);
+        assert!(!target.handle_drop(&mut event));
+        assert_eq!(target_model.len(), 2);
+    }
+
+    #[test]
+    fn test_fetches_rows_as_view_scrolls() {
+        use crate::model::AsyncListModel;
+        setup();
+
+        let model = AsyncListModel::new(10, |request, sink| {
+            let rows = (request.offset..request.offset + request.count)
+                .map(|i| format!("Item {i}"))
+                .collect();
+            sink.deliver(rows);
+        });
+        let mut view = ListView::new();
+        view.widget_base_mut()
+            .set_geometry(Rect::new(0.0, 0.0, 200.0, 200.0));
+        view.set_model(Some(model.clone()));
+
+        // Laying out fills the viewport plus one more viewport of rows, then stops.
+        for _ in 0..20 {
+            view.ensure_layout();
+        }
+        let filled = model.len();
+        assert!(filled > 0);
+        assert!(filled < 100);
+
+        // Scrolling to the end requests the next batch.
+        view.scroll_to_bottom();
+        assert_eq!(model.len(), filled + 10);
+    }
 }
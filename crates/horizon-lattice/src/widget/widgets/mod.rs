@@ -46,6 +46,7 @@
 //! - [`AboutDialog`]: Modal dialog for displaying application information
 //! - [`PrintDialog`]: Modal dialog for configuring print settings
 //! - [`PrintPreviewDialog`]: Modal dialog for previewing print output before printing
+//! - [`PrintDocument`]: Paginated document that prints to PDF files and preview thumbnails
 //! - [`FontComboBox`]: Dropdown for selecting font families with preview
 //! - [`KeySequenceEdit`]: Keyboard shortcut capture and editing widget
 //! - [`StatusBar`]: Status bar with temporary messages and permanent widgets
@@ -82,6 +83,7 @@ mod group_box;
 mod header_view;
 mod image_widget;
 mod input_dialog;
+mod item_editing;
 mod key_sequence_edit;
 mod label;
 mod line_edit;
@@ -95,6 +97,7 @@ pub mod native_dialogs;
 mod plain_text_edit;
 mod popup;
 mod print_dialog;
+mod print_document;
 mod progress_bar;
 mod progress_dialog;
 mod push_button;
@@ -153,6 +156,7 @@ pub use frame::{Frame, FrameShadow, FrameShape};
 pub use group_box::GroupBox;
 pub use header_view::{HeaderView, ResizeMode, SortOrder};
 pub use image_widget::{ImageSource, ImageWidget, ImageWidgetState};
+pub use item_editing::EditTriggers;
 pub use label::{ElideMode, Label};
 pub use line_edit::{EchoMode, LineEdit};
 pub use list_view::{Flow, ListView, ListViewMode};
@@ -167,7 +171,12 @@ pub use super::validator::{
     ValidationState, Validator,
 };
 // Re-export completer types for convenience
-pub use super::completer::{CaseSensitivity, Completer, CompleterModel, StringListModel};
+#[cfg(feature = "async-runtime")]
+pub use super::completer::AsyncCompletionSource;
+pub use super::completer::{
+    CaseSensitivity, ColumnCompleterModel, Completer, CompleterModel, CompletionMatch, MatchMode,
+    StringListModel,
+};
 pub use main_window::MainWindow;
 pub use plain_text_edit::{HighlightSpan, LineNumberConfig, PlainTextEdit, SyntaxHighlighter};
 pub use popup::{Popup, PopupFlags, PopupPlacement};
@@ -216,6 +225,7 @@ pub use print_dialog::{
     ColorMode, DuplexMode, PageOrientation, PageRange, PaperSize, PrintDialog, PrintDialogOptions,
     PrintPreviewDialog, PrintSettings, PrinterInfo,
 };
+pub use print_document::{PageInfo, PageLayout, PageMargins, PrintDocument};
 pub use progress_dialog::ProgressDialog;
 pub use spin_box::SpinBox;
 pub use status_bar::{MessagePriority, StatusBar, StatusBarStyle};
@@ -19,7 +19,11 @@
 //! ```
 
 use horizon_lattice_core::{Object, ObjectId, Signal};
-use horizon_lattice_render::{Color, Point, Rect, Renderer, RoundedRect, Size, Stroke};
+use horizon_lattice_render::text::FontSystem;
+use horizon_lattice_render::{
+    Color, Image, ImageBuffer, ImageManager, ImageScaleMode, Picture, Point, Rect, RenderResult,
+    Renderer, RoundedRect, Size, Stroke, Transform2D,
+};
 
 use crate::widget::{
     Key, KeyPressEvent, MouseButton, MouseMoveEvent, MousePressEvent, PaintContext, SizeHint,
@@ -28,6 +32,7 @@ use crate::widget::{
 
 use super::dialog::{Dialog, DialogResult};
 use super::dialog_button_box::StandardButton;
+use super::print_document::{self, PrintDocument};
 
 // ============================================================================
 // Page Orientation
@@ -1696,14 +1701,20 @@ impl Default for PrintDialog {
 ///   Connect to this signal to provide your document rendering logic.
 /// - `print_requested`: Emitted when the user clicks Print.
 ///
+/// The pages shown come from a [`PrintDocument`] passed to
+/// [`set_document`](Self::set_document).
+///
 /// # Example
 ///
 /// ```ignore
-/// let mut preview = PrintPreviewDialog::new(&print_settings);
+/// let mut document = PrintDocument::from_settings(&print_settings);
+/// text_edit.print(&mut document, &mut font_system);
+///
+/// let mut preview = PrintPreviewDialog::new(print_settings).with_document(&document);
+/// preview.prepare_page_images(2.0, &mut image_manager, &mut font_system)?;
 ///
-/// preview.paint_requested.connect(|printer| {
-///     // Render your document to the printer
-///     render_document(printer);
+/// preview.print_requested.connect(move |settings| {
+///     document.print(&settings, &font_system).ok();
 /// });
 ///
 /// preview.open();
@@ -1722,6 +1733,12 @@ pub struct PrintPreviewDialog {
     /// Total pages.
     total_pages: u32,
 
+    /// Recorded pages from the previewed document.
+    pages: Vec<Picture>,
+
+    /// Pages rendered with their text, drawn instead of replaying `pages`.
+    page_images: Vec<Image>,
+
     /// Zoom level (1.0 = 100%).
     zoom: f32,
 
@@ -1768,6 +1785,8 @@ impl PrintPreviewDialog {
             settings,
             current_page: 1,
             total_pages: 1,
+            pages: Vec::new(),
+            page_images: Vec::new(),
             zoom: 1.0,
             zoom_levels: vec![0.25, 0.5, 0.75, 1.0, 1.25, 1.5, 2.0, 3.0, 4.0],
             scroll_x: 0.0,
@@ -1804,6 +1823,12 @@ impl PrintPreviewDialog {
         self
     }
 
+    /// Set the document to preview using builder pattern.
+    pub fn with_document(mut self, document: &PrintDocument) -> Self {
+        self.set_document(document);
+        self
+    }
+
     /// Set the initial zoom level using builder pattern.
     pub fn with_zoom(mut self, zoom: f32) -> Self {
         self.zoom = zoom.clamp(0.1, 10.0);
@@ -1842,6 +1867,64 @@ impl PrintPreviewDialog {
         self.dialog.widget_base_mut().update();
     }
 
+    /// Set the document to preview.
+    ///
+    /// The pages are copied, and the page count is set to the number of pages.
+    /// Images from [`prepare_page_images`](Self::prepare_page_images) are
+    /// discarded.
+    pub fn set_document(&mut self, document: &PrintDocument) {
+        self.pages = document.pages().to_vec();
+        self.page_images.clear();
+        self.set_total_pages(self.pages.len() as u32);
+    }
+
+    /// Render the document's pages, text included, to images shown in the
+    /// preview, `scale` pixels per point.
+    ///
+    /// Renderers draw shapes only, so without these images the preview
+    /// replays the recorded pages without their text. Pass the
+    /// [`ImageManager`] and [`FontSystem`] from your application, and call
+    /// this again after [`set_document`](Self::set_document). The images are
+    /// stretched to the page at every zoom level, so render them at the scale
+    /// of the largest zoom you want to show sharply.
+    pub fn prepare_page_images(
+        &mut self,
+        scale: f32,
+        image_manager: &mut ImageManager,
+        font_system: &mut FontSystem,
+    ) -> RenderResult<()> {
+        let mut images = Vec::with_capacity(self.pages.len());
+        for page in &self.pages {
+            let Some(buffer) = print_document::render_page(page, scale, font_system) else {
+                break;
+            };
+            let (width, height) = buffer.dimensions();
+            images.push(image_manager.load_rgba(&buffer.as_rgba8_bytes(), width, height)?);
+        }
+        self.page_images = images;
+        self.dialog.widget_base_mut().update();
+        Ok(())
+    }
+
+    /// Get the recorded page with the given one-based number.
+    pub fn page(&self, page: u32) -> Option<&Picture> {
+        self.pages.get((page as usize).checked_sub(1)?)
+    }
+
+    /// Render a page of the previewed document to an image, `scale` pixels
+    /// per point.
+    ///
+    /// Use this for page thumbnails. Returns `None` if no document page has
+    /// the given one-based number.
+    pub fn page_thumbnail(
+        &self,
+        page: u32,
+        scale: f32,
+        font_system: &mut FontSystem,
+    ) -> Option<ImageBuffer> {
+        print_document::render_page(self.page(page)?, scale, font_system)
+    }
+
     /// Get the current zoom level.
     pub fn zoom(&self) -> f32 {
         self.zoom
@@ -2330,7 +2413,26 @@ impl PrintPreviewDialog {
         ctx.renderer()
             .stroke_rect(page, &Stroke::new(self.border_color, 1.0));
 
-        // Page content would be rendered by the paint_requested signal handler
+        // Page content
+        if let Some(image) = (self.current_page as usize)
+            .checked_sub(1)
+            .and_then(|index| self.page_images.get(index))
+        {
+            ctx.renderer()
+                .draw_image(image, page, ImageScaleMode::Stretch);
+        } else if let Some(picture) = self.page(self.current_page)
+            && picture.size().width > 0.0
+        {
+            let scale = page.width() / picture.size().width;
+            let renderer = ctx.renderer();
+            renderer.save();
+            renderer.clip_rect(page);
+            renderer.translate(page.origin.x, page.origin.y);
+            renderer.scale(scale, scale);
+            // Renderers can't draw text; the page images include it.
+            picture.replay(renderer, Transform2D::IDENTITY);
+            renderer.restore();
+        }
     }
 
     fn paint_print_button(&self, ctx: &mut PaintContext<'_>) {
@@ -2664,4 +2766,70 @@ mod tests {
         preview.set_zoom(0.001);
         assert!(preview.zoom() >= 0.1);
     }
+
+    #[test]
+    fn test_print_preview_document() {
+        setup();
+        let mut document = PrintDocument::from_settings(&PrintSettings::default());
+        for _ in 0..3 {
+            document.add_block(400.0, |picture, rect| {
+                picture.fill_rect(rect, Color::RED);
+            });
+        }
+        assert_eq!(document.page_count(), 3);
+
+        let mut preview =
+            PrintPreviewDialog::new(PrintSettings::default()).with_document(&document);
+        assert_eq!(preview.total_pages(), 3);
+        preview.last_page();
+        assert_eq!(preview.current_page(), 3);
+        assert!(preview.page(3).is_some());
+        assert!(preview.page(4).is_none());
+
+        let mut font_system = FontSystem::with_config(
+            horizon_lattice_render::text::FontSystemConfig::new().load_system_fonts(false),
+        );
+        let thumbnail = preview.page_thumbnail(1, 0.25, &mut font_system).unwrap();
+        assert_eq!(thumbnail.dimensions(), (153, 198));
+        assert!(preview.page_thumbnail(0, 0.25, &mut font_system).is_none());
+
+        preview.set_document(&PrintDocument::from_settings(&PrintSettings::default()));
+        assert_eq!(preview.total_pages(), 1);
+        assert_eq!(preview.current_page(), 1);
+    }
+
+    #[test]
+    fn test_print_preview_draws_page_image() {
+        use horizon_lattice_render::SoftwareRenderer;
+        setup();
+        let mut document = PrintDocument::from_settings(&PrintSettings::default());
+        document.add_block(100.0, |picture, rect| {
+            picture.fill_rect(rect, Color::RED);
+        });
+        let mut preview =
+            PrintPreviewDialog::new(PrintSettings::default()).with_document(&document);
+
+        let paint_center = |preview: &PrintPreviewDialog| {
+            let rect = preview.dialog.widget_base().rect();
+            let mut renderer = SoftwareRenderer::new();
+            renderer.begin_frame(Color::WHITE, rect.size);
+            let mut ctx = PaintContext::new(&mut renderer, rect);
+            preview.paint_preview_area(&mut ctx);
+            renderer.end_frame();
+            let center = preview.page_rect().center();
+            renderer.pixel(center.x as u32, center.y as u32).unwrap()
+        };
+
+        // Without page images the recorded page is replayed: the block only
+        // covers the top of the page.
+        assert_eq!(paint_center(&preview), Color::WHITE);
+
+        // A page image, rendered with the page's text, replaces the replay.
+        let blue = [0, 0, 255, 255].repeat(4);
+        preview.page_images = vec![Image::from_rgba(&blue, 2, 2).unwrap()];
+        assert_eq!(paint_center(&preview), Color::BLUE);
+
+        preview.set_document(&document);
+        assert_eq!(paint_center(&preview), Color::WHITE);
+    }
 }
@@ -0,0 +1,793 @@
+//! Paginated print documents.
+//!
+//! This module provides [`PrintDocument`], which lays content out on pages of
+//! a fixed size and records each page as a [`Picture`]. The pages can be
+//! written to a PDF file with a [`PdfRenderer`], rendered to thumbnails for a
+//! [`PrintPreviewDialog`](super::PrintPreviewDialog), or both.
+//!
+//! Content is added from top to bottom. Blocks that don't fit on the current
+//! page move to the next one, and text layouts are split between lines. A
+//! page break callback can paint headers and footers on each new page.
+//!
+//! Units are points (1/72 inch), the unit of [`PaperSize::size_in_points`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use horizon_lattice::widget::widgets::{PrintDocument, PrintSettings};
+//! use horizon_lattice_render::text::{Font, FontFamily, FontSystem};
+//! use horizon_lattice_render::Color;
+//!
+//! let settings = PrintSettings {
+//!     output_file: Some("report.pdf".into()),
+//!     ..Default::default()
+//! };
+//! let mut font_system = FontSystem::new();
+//! let font = Font::new(FontFamily::SansSerif, 11.0);
+//!
+//! let mut document = PrintDocument::from_settings(&settings).with_title("Report");
+//! document.on_page_break(|picture, page| {
+//!     // Paint a header line above the content area
+//! });
+//! document.add_paragraph(&mut font_system, "Hello, printer!", &font, Color::BLACK);
+//! document.print(&settings, &font_system)?;
+//! ```
+
+use std::fmt;
+
+use horizon_lattice_render::text::{Font, FontSystem, TextLayout, TextLayoutOptions, WrapMode};
+use horizon_lattice_render::{
+    Color, ImageBuffer, PdfRenderer, Picture, Point, Rect, RenderError, RenderResult, Renderer,
+    Size, SoftwareRenderer, Transform2D,
+};
+
+use super::print_dialog::{PageOrientation, PageRange, PaperSize, PrintSettings};
+
+/// Callback run when a new page begins.
+type PageBreakCallback = Box<dyn FnMut(&mut Picture, &PageInfo)>;
+
+// ============================================================================
+// Page Layout
+// ============================================================================
+
+/// Margins around the content area of a page, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageMargins {
+    /// Left margin.
+    pub left: f32,
+    /// Top margin.
+    pub top: f32,
+    /// Right margin.
+    pub right: f32,
+    /// Bottom margin.
+    pub bottom: f32,
+}
+
+impl PageMargins {
+    /// Create margins with individual values.
+    pub fn new(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+        }
+    }
+
+    /// Create margins with the same value on every side.
+    pub fn uniform(margin: f32) -> Self {
+        Self::new(margin, margin, margin, margin)
+    }
+}
+
+impl Default for PageMargins {
+    /// Half an inch on every side.
+    fn default() -> Self {
+        Self::uniform(36.0)
+    }
+}
+
+/// The size and margins of the pages of a [`PrintDocument`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageLayout {
+    /// Page size in points.
+    pub page_size: Size,
+    /// Margins around the content area.
+    pub margins: PageMargins,
+}
+
+impl PageLayout {
+    /// Create a page layout with default margins.
+    pub fn new(page_size: Size) -> Self {
+        Self {
+            page_size,
+            margins: PageMargins::default(),
+        }
+    }
+
+    /// Create a page layout for the paper size and orientation of `settings`.
+    pub fn from_settings(settings: &PrintSettings) -> Self {
+        Self::new(page_size(settings.paper_size, settings.orientation))
+    }
+
+    /// Set the margins.
+    pub fn with_margins(mut self, margins: PageMargins) -> Self {
+        self.margins = margins;
+        self
+    }
+
+    /// Get the page rectangle, with its origin at the top-left corner.
+    pub fn page_rect(&self) -> Rect {
+        Rect::new(0.0, 0.0, self.page_size.width, self.page_size.height)
+    }
+
+    /// Get the area inside the margins.
+    ///
+    /// The size is clamped to zero if the margins are larger than the page.
+    pub fn content_rect(&self) -> Rect {
+        let m = &self.margins;
+        Rect::new(
+            m.left,
+            m.top,
+            (self.page_size.width - m.left - m.right).max(0.0),
+            (self.page_size.height - m.top - m.bottom).max(0.0),
+        )
+    }
+}
+
+impl Default for PageLayout {
+    fn default() -> Self {
+        Self::from_settings(&PrintSettings::default())
+    }
+}
+
+/// Get the page size of a paper size in the given orientation.
+fn page_size(paper_size: PaperSize, orientation: PageOrientation) -> Size {
+    let (width, height) = paper_size.size_in_points();
+    match orientation {
+        PageOrientation::Portrait => Size::new(width, height),
+        PageOrientation::Landscape => Size::new(height, width),
+    }
+}
+
+/// Information about a page, passed to page break callbacks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageInfo {
+    /// Zero-based index of the page.
+    pub index: usize,
+    /// The page rectangle.
+    pub page_rect: Rect,
+    /// The area inside the margins.
+    pub content_rect: Rect,
+}
+
+impl PageInfo {
+    /// Get the one-based page number.
+    pub fn number(&self) -> usize {
+        self.index + 1
+    }
+}
+
+// ============================================================================
+// Print Document
+// ============================================================================
+
+/// A document laid out on pages for printing.
+///
+/// Pages are created as content is added. Each page is recorded as a
+/// [`Picture`] the size of the page, with the content drawn inside the
+/// margins and clipped to them.
+pub struct PrintDocument {
+    layout: PageLayout,
+    title: Option<String>,
+    pages: Vec<Picture>,
+    /// Offset of the next block from the top of the content area.
+    cursor: f32,
+    /// One-based number of the page printed for [`PageRange::CurrentPage`].
+    current_page: Option<u32>,
+    page_break: Option<PageBreakCallback>,
+}
+
+impl PrintDocument {
+    /// Create an empty document with the given page layout.
+    pub fn new(layout: PageLayout) -> Self {
+        Self {
+            layout,
+            title: None,
+            pages: Vec::new(),
+            cursor: 0.0,
+            current_page: None,
+            page_break: None,
+        }
+    }
+
+    /// Create an empty document with the page layout of `settings`.
+    pub fn from_settings(settings: &PrintSettings) -> Self {
+        Self::new(PageLayout::from_settings(settings))
+    }
+
+    /// Set the document title, written to the PDF metadata.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Get the document title.
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// Set the one-based number of the page printed for
+    /// [`PageRange::CurrentPage`] using builder pattern.
+    pub fn with_current_page(mut self, page: u32) -> Self {
+        self.current_page = Some(page);
+        self
+    }
+
+    /// Get the one-based number of the page printed for
+    /// [`PageRange::CurrentPage`].
+    pub fn current_page(&self) -> Option<u32> {
+        self.current_page
+    }
+
+    /// Set the one-based number of the page printed for
+    /// [`PageRange::CurrentPage`].
+    ///
+    /// Views set this to the page showing their current position, usually the
+    /// same page passed to [`PrintDialog::with_current_page`](super::PrintDialog::with_current_page).
+    pub fn set_current_page(&mut self, page: Option<u32>) {
+        self.current_page = page;
+    }
+
+    /// Get the page layout.
+    pub fn page_layout(&self) -> &PageLayout {
+        &self.layout
+    }
+
+    /// Set a callback run whenever a new page begins, before any content is
+    /// drawn on it.
+    ///
+    /// The callback draws in page coordinates without a clip, so it can paint
+    /// headers and footers in the margins.
+    pub fn on_page_break<F>(&mut self, callback: F)
+    where
+        F: FnMut(&mut Picture, &PageInfo) + 'static,
+    {
+        self.page_break = Some(Box::new(callback));
+    }
+
+    // =========================================================================
+    // Pages
+    // =========================================================================
+
+    /// Get the recorded pages.
+    pub fn pages(&self) -> &[Picture] {
+        &self.pages
+    }
+
+    /// Get the number of pages.
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Get the information of a page.
+    pub fn page_info(&self, index: usize) -> PageInfo {
+        PageInfo {
+            index,
+            page_rect: self.layout.page_rect(),
+            content_rect: self.layout.content_rect(),
+        }
+    }
+
+    /// Start a new page.
+    ///
+    /// Content added afterwards goes to the top of the new page.
+    pub fn new_page(&mut self) {
+        let info = self.page_info(self.pages.len());
+        let mut picture = Picture::new();
+        picture.begin_frame(Color::TRANSPARENT, self.layout.page_size);
+        if let Some(callback) = &mut self.page_break {
+            picture.save();
+            callback(&mut picture, &info);
+            picture.restore();
+        }
+        self.pages.push(picture);
+        self.cursor = 0.0;
+    }
+
+    /// Get the page being added to, starting the first page if there is none.
+    fn last_page(&mut self) -> &mut Picture {
+        if self.pages.is_empty() {
+            self.new_page();
+        }
+        self.pages.last_mut().expect("a page was just added")
+    }
+
+    // =========================================================================
+    // Flow
+    // =========================================================================
+
+    /// Get the y position where the next block will be placed, in page
+    /// coordinates.
+    pub fn cursor(&self) -> f32 {
+        self.layout.content_rect().top() + self.cursor
+    }
+
+    /// Get the height left on the current page.
+    pub fn remaining_height(&self) -> f32 {
+        if self.pages.is_empty() {
+            return self.layout.content_rect().height();
+        }
+        (self.layout.content_rect().height() - self.cursor).max(0.0)
+    }
+
+    /// Check whether a block of `height` fits on the current page.
+    pub fn fits(&self, height: f32) -> bool {
+        height <= self.remaining_height()
+    }
+
+    /// Add vertical space.
+    ///
+    /// Space at the top of a page is dropped.
+    pub fn add_spacing(&mut self, height: f32) {
+        if self.cursor > 0.0 {
+            self.cursor = (self.cursor + height).min(self.layout.content_rect().height());
+        }
+    }
+
+    /// Start a new page if a block of `height` doesn't fit on the current
+    /// one.
+    ///
+    /// Use this to keep content that is added in several steps together.
+    pub fn ensure_space(&mut self, height: f32) {
+        if self.cursor > 0.0 && !self.fits(height) {
+            self.new_page();
+        }
+    }
+
+    /// Add a block of `height` drawn by `paint`, starting a new page first if
+    /// it doesn't fit on the current one.
+    ///
+    /// `paint` gets the page and the block's rectangle in page coordinates,
+    /// with drawing clipped to the content area. A block taller than the
+    /// content area is placed at the top of a page and cut off at the bottom
+    /// margin. Returns the block's rectangle.
+    pub fn add_block<F>(&mut self, height: f32, paint: F) -> Rect
+    where
+        F: FnOnce(&mut Picture, Rect),
+    {
+        self.ensure_space(height);
+        let content = self.layout.content_rect();
+        let rect = Rect::new(
+            content.left(),
+            content.top() + self.cursor,
+            content.width(),
+            height,
+        );
+
+        let picture = self.last_page();
+        picture.save();
+        picture.clip_rect(content);
+        paint(picture, rect);
+        picture.restore();
+
+        self.cursor += height;
+        rect
+    }
+
+    /// Add a text layout at the left edge of the content area.
+    ///
+    /// The layout is split between lines where it crosses a page boundary.
+    /// Glyphs without a color of their own use `color`.
+    pub fn add_text(&mut self, layout: &TextLayout, color: Color) {
+        self.add_text_at(layout, 0.0, color);
+    }
+
+    /// Add a text layout like [`add_text`](Self::add_text), offset `indent`
+    /// points from the left edge of the content area.
+    pub fn add_text_at(&mut self, layout: &TextLayout, indent: f32, color: Color) {
+        let lines = layout.lines();
+        let mut start = 0;
+        while start < lines.len() {
+            let available = self.remaining_height();
+            let top = lines[start].top_y;
+            let mut end = start;
+            while end < lines.len() && lines[end].top_y + lines[end].height - top <= available {
+                end += 1;
+            }
+            if end == start {
+                if self.cursor > 0.0 {
+                    self.new_page();
+                    continue;
+                }
+                // A line taller than the page gets a page of its own
+                end += 1;
+            }
+
+            let slice = layout.slice_lines(start..end);
+            self.add_block(slice.height(), |picture, rect| {
+                paint_text(
+                    picture,
+                    &slice,
+                    Point::new(rect.left() + indent, rect.top()),
+                    color,
+                );
+            });
+            start = end;
+        }
+    }
+
+    /// Lay out `text` wrapped to the width of the content area and add it.
+    pub fn add_paragraph(
+        &mut self,
+        font_system: &mut FontSystem,
+        text: &str,
+        font: &Font,
+        color: Color,
+    ) {
+        let options = TextLayoutOptions::default()
+            .max_width(self.layout.content_rect().width())
+            .wrap(WrapMode::Word);
+        let layout = TextLayout::with_options(font_system, text, font, options);
+        self.add_text(&layout, color);
+    }
+
+    // =========================================================================
+    // Output
+    // =========================================================================
+
+    /// Get the indices of the pages selected by `range`.
+    ///
+    /// [`PageRange::CurrentPage`] selects the page set with
+    /// [`set_current_page`](Self::set_current_page), and is an error if none
+    /// was set. The document doesn't know what is selected, so
+    /// [`PageRange::Selection`] is an error; print the selection into its own
+    /// document and use [`PageRange::All`] instead. Pages past the end of the
+    /// document are ignored.
+    pub fn page_indices(&self, range: &PageRange) -> RenderResult<Vec<usize>> {
+        let count = self.pages.len();
+        let to_index = |page: u32| (page as usize).checked_sub(1).filter(|&i| i < count);
+        match range {
+            PageRange::All => Ok((0..count).collect()),
+            PageRange::CurrentPage => {
+                let page = self
+                    .current_page
+                    .ok_or_else(|| RenderError::Pdf("no current page set".into()))?;
+                Ok(to_index(page).into_iter().collect())
+            }
+            PageRange::Selection => Err(RenderError::Pdf(
+                "a document can't print a selection; print the selection into its own document"
+                    .into(),
+            )),
+            PageRange::Range { from, to } => Ok((*from..=*to).filter_map(to_index).collect()),
+            PageRange::Pages(pages) => Ok(pages.iter().copied().filter_map(to_index).collect()),
+        }
+    }
+
+    /// Write every page to a PDF document.
+    ///
+    /// Fonts are looked up in `font_system` and embedded as subsets.
+    pub fn to_pdf(&self, font_system: &FontSystem) -> RenderResult<Vec<u8>> {
+        self.write_pdf(0..self.pages.len(), font_system)
+    }
+
+    /// Print the document to the PDF file named by `settings.output_file`.
+    ///
+    /// The page range, number of copies and collation are honored. Fails if
+    /// the page range can't be resolved, see [`page_indices`](Self::page_indices). Color and
+    /// duplex modes are printer options and don't apply to PDF files.
+    pub fn print(&self, settings: &PrintSettings, font_system: &FontSystem) -> RenderResult<()> {
+        let Some(path) = &settings.output_file else {
+            return Err(RenderError::Pdf("no output file set".into()));
+        };
+
+        let pages = self.page_indices(&settings.page_range)?;
+        let copies = settings.copies.max(1) as usize;
+        let order: Vec<usize> = if settings.collate {
+            pages
+                .iter()
+                .copied()
+                .cycle()
+                .take(pages.len() * copies)
+                .collect()
+        } else {
+            pages
+                .iter()
+                .flat_map(|&page| std::iter::repeat_n(page, copies))
+                .collect()
+        };
+
+        let bytes = self.write_pdf(order, font_system)?;
+        std::fs::write(path, bytes).map_err(|e| RenderError::Pdf(e.to_string()))
+    }
+
+    /// Write the pages at `indices` to a PDF document.
+    fn write_pdf(
+        &self,
+        indices: impl IntoIterator<Item = usize>,
+        font_system: &FontSystem,
+    ) -> RenderResult<Vec<u8>> {
+        let mut renderer = PdfRenderer::new();
+        if let Some(title) = &self.title {
+            renderer = renderer.with_title(title.clone());
+        }
+        for index in indices {
+            let page = &self.pages[index];
+            renderer.begin_frame(Color::WHITE, page.size());
+            page.replay_with_text(
+                &mut renderer,
+                Transform2D::IDENTITY,
+                |renderer, layout, position, color| {
+                    renderer.draw_text(font_system, layout, position, color);
+                },
+            );
+            renderer.end_frame();
+        }
+        renderer.finish()
+    }
+
+    /// Render a page to an image, `scale` pixels per point.
+    ///
+    /// This is meant for thumbnails and print previews. Returns `None` if
+    /// the page doesn't exist.
+    pub fn render_page(
+        &self,
+        index: usize,
+        scale: f32,
+        font_system: &mut FontSystem,
+    ) -> Option<ImageBuffer> {
+        render_page(self.pages.get(index)?, scale, font_system)
+    }
+}
+
+impl fmt::Debug for PrintDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrintDocument")
+            .field("layout", &self.layout)
+            .field("title", &self.title)
+            .field("page_count", &self.pages.len())
+            .field("cursor", &self.cursor)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Render a recorded page on white, `scale` pixels per point.
+pub(crate) fn render_page(
+    page: &Picture,
+    scale: f32,
+    font_system: &mut FontSystem,
+) -> Option<ImageBuffer> {
+    let size = page.size();
+    let width = (size.width * scale).round().max(1.0);
+    let height = (size.height * scale).round().max(1.0);
+
+    let mut renderer = SoftwareRenderer::new();
+    renderer.begin_frame(Color::WHITE, Size::new(width, height));
+    page.replay_with_text(
+        &mut renderer,
+        Transform2D::scale(scale),
+        |renderer, layout, position, color| {
+            renderer.draw_text(font_system, layout, position, color);
+        },
+    );
+    renderer.end_frame();
+    Some(renderer.to_image_buffer())
+}
+
+/// Record a text layout with its background and decorations.
+fn paint_text(picture: &mut Picture, layout: &TextLayout, position: Point, color: Color) {
+    let rgba = |[r, g, b, a]: [u8; 4]| Color::from_rgba8(r, g, b, a);
+
+    for rect in layout.background_rects() {
+        picture.fill_rect(
+            Rect::new(
+                position.x + rect.x,
+                position.y + rect.y,
+                rect.width,
+                rect.height,
+            ),
+            rgba(rect.color),
+        );
+    }
+
+    picture.draw_text(layout, position, color);
+
+    for line in layout.decoration_lines() {
+        picture.fill_rect(
+            Rect::new(
+                position.x + line.x_start,
+                position.y + line.y - line.thickness / 2.0,
+                line.width(),
+                line.thickness,
+            ),
+            rgba(line.color),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use horizon_lattice_render::DrawCommand;
+    use horizon_lattice_render::text::{FontFamily, FontSystemConfig};
+
+    fn small_layout() -> PageLayout {
+        PageLayout::new(Size::new(200.0, 300.0)).with_margins(PageMargins::uniform(50.0))
+    }
+
+    #[test]
+    fn test_page_layout() {
+        let settings = PrintSettings {
+            paper_size: PaperSize::A4,
+            orientation: PageOrientation::Landscape,
+            ..Default::default()
+        };
+        let layout = PageLayout::from_settings(&settings);
+        assert_eq!(layout.page_size, Size::new(842.0, 595.0));
+        assert_eq!(layout.content_rect(), Rect::new(36.0, 36.0, 770.0, 523.0));
+
+        let layout = small_layout().with_margins(PageMargins::uniform(150.0));
+        assert_eq!(layout.content_rect().size, Size::new(0.0, 0.0));
+    }
+
+    #[test]
+    fn test_blocks_paginate() {
+        let mut document = PrintDocument::new(small_layout());
+        assert_eq!(document.page_count(), 0);
+        assert_eq!(document.remaining_height(), 200.0);
+
+        let first = document.add_block(120.0, |picture, rect| {
+            picture.fill_rect(rect, Color::RED);
+        });
+        assert_eq!(first, Rect::new(50.0, 50.0, 100.0, 120.0));
+        assert!(document.fits(80.0));
+        assert!(!document.fits(81.0));
+
+        // Doesn't fit in the 80 points left
+        let second = document.add_block(100.0, |_, _| {});
+        assert_eq!(document.page_count(), 2);
+        assert_eq!(second.top(), 50.0);
+
+        // Spacing at the top of a page is dropped
+        document.new_page();
+        document.add_spacing(10.0);
+        assert_eq!(document.cursor(), 50.0);
+
+        // A block taller than the page gets a page of its own
+        document.add_block(30.0, |_, _| {});
+        document.add_block(500.0, |_, _| {});
+        assert_eq!(document.page_count(), 4);
+        assert_eq!(document.remaining_height(), 0.0);
+
+        let page = &document.pages()[0];
+        assert_eq!(page.size(), Size::new(200.0, 300.0));
+        assert!(
+            page.commands()
+                .iter()
+                .any(|command| matches!(command, DrawCommand::ClipRect(rect) if *rect == Rect::new(50.0, 50.0, 100.0, 200.0)))
+        );
+    }
+
+    #[test]
+    fn test_page_break_callback() {
+        let mut document = PrintDocument::new(small_layout());
+        let numbers = Rc::new(RefCell::new(Vec::new()));
+        let seen = numbers.clone();
+        document.on_page_break(move |picture, page| {
+            seen.borrow_mut().push(page.number());
+            picture.fill_rect(Rect::new(0.0, 0.0, 200.0, 20.0), Color::BLUE);
+        });
+
+        for _ in 0..5 {
+            document.add_block(90.0, |_, _| {});
+        }
+        assert_eq!(document.page_count(), 3);
+        assert_eq!(*numbers.borrow(), vec![1, 2, 3]);
+        assert!(document.pages()[2].bounds().is_some());
+    }
+
+    #[test]
+    fn test_page_indices() {
+        let mut document = PrintDocument::new(small_layout());
+        for _ in 0..3 {
+            document.new_page();
+        }
+        assert_eq!(
+            document.page_indices(&PageRange::All).unwrap(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(
+            document
+                .page_indices(&PageRange::Range { from: 2, to: 5 })
+                .unwrap(),
+            vec![1, 2]
+        );
+        assert_eq!(
+            document
+                .page_indices(&PageRange::Pages(vec![0, 1, 3, 4]))
+                .unwrap(),
+            vec![0, 2]
+        );
+    }
+
+    #[test]
+    fn test_page_indices_current_page_and_selection() {
+        let mut document = PrintDocument::new(small_layout());
+        for _ in 0..3 {
+            document.new_page();
+        }
+        assert!(document.page_indices(&PageRange::CurrentPage).is_err());
+        assert!(document.page_indices(&PageRange::Selection).is_err());
+
+        document.set_current_page(Some(2));
+        assert_eq!(
+            document.page_indices(&PageRange::CurrentPage).unwrap(),
+            vec![1]
+        );
+        document.set_current_page(Some(7));
+        assert!(
+            document
+                .page_indices(&PageRange::CurrentPage)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_text_splits_between_pages() {
+        let mut font_system = FontSystem::new();
+        let font = Font::new(FontFamily::SansSerif, 12.0);
+        let text = vec!["line"; 40].join("\n");
+        let layout = TextLayout::new(&mut font_system, &text, &font);
+        if layout.glyphs().next().is_none() {
+            // No fonts available
+            return;
+        }
+
+        let mut document = PrintDocument::new(small_layout());
+        document.add_text(&layout, Color::BLACK);
+        assert!(document.page_count() > 1);
+
+        let line_count: usize = document
+            .pages()
+            .iter()
+            .flat_map(|page| page.commands())
+            .map(|command| match command {
+                DrawCommand::Text { layout, .. } => layout.line_count(),
+                _ => 0,
+            })
+            .sum();
+        assert_eq!(line_count, 40);
+
+        let pdf = document.to_pdf(&font_system).unwrap();
+        assert!(pdf.starts_with(b"%PDF"));
+        let thumbnail = document.render_page(0, 0.5, &mut font_system).unwrap();
+        assert_eq!((thumbnail.width(), thumbnail.height()), (100, 150));
+    }
+
+    #[test]
+    fn test_print_to_file() {
+        let mut document = PrintDocument::new(small_layout()).with_title("Test");
+        document.add_block(10.0, |picture, rect| picture.fill_rect(rect, Color::RED));
+        document.new_page();
+        let font_system = FontSystem::with_config(FontSystemConfig::new().load_system_fonts(false));
+
+        let mut settings = PrintSettings::default();
+        assert!(document.print(&settings, &font_system).is_err());
+
+        let path =
+            std::env::temp_dir().join(format!("lattice-print-document-{}.pdf", std::process::id()));
+        settings.output_file = Some(path.to_string_lossy().into_owned());
+        settings.copies = 2;
+        document.print(&settings, &font_system).unwrap();
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        assert!(bytes.starts_with(b"%PDF"));
+        let text = String::from_utf8_lossy(&bytes);
+        assert!(text.contains("/Count 4"));
+    }
+}
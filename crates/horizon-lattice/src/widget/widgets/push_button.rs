@@ -29,6 +29,7 @@ use horizon_lattice_render::{
     Color, Font, FontSystem, Icon, IconMode, IconPosition, ImageScaleMode, Point, Rect, Renderer,
     RoundedRect, Stroke, TextLayout, TextRenderer, icon_tint_for_state,
 };
+use horizon_lattice_style::prelude::{ComputedStyle, paint_styled_box};
 
 use super::abstract_button::{AbstractButton, ButtonVariant};
 use crate::widget::{PaintContext, SizeHint, Widget, WidgetBase, WidgetEvent};
@@ -70,8 +71,10 @@ impl PushButton {
     ///
     /// By default, buttons use [`ButtonVariant::Primary`].
     pub fn new(text: impl Into<String>) -> Self {
+        let mut inner = AbstractButton::new(text);
+        inner.widget_base_mut().set_style_type_name("PushButton");
         Self {
-            inner: AbstractButton::new(text),
+            inner,
             border_radius: 4.0,
         }
     }
@@ -476,6 +479,19 @@ impl PushButton {
     // Private Rendering Helpers
     // =========================================================================
 
+    /// Compute the button's style from the attached style engine.
+    ///
+    /// The variant is exposed as a class and the checked state is matched by
+    /// `:checked` for checkable buttons.
+    fn computed_style(&self) -> Option<ComputedStyle> {
+        let base = self.inner.widget_base();
+        let mut state = base.style_state();
+        if self.inner.is_checkable() {
+            state.checked = Some(self.inner.is_checked());
+        }
+        base.compute_style_with(state, &[self.inner.variant().style_class()])
+    }
+
     /// Get background and border colors based on variant and state.
     ///
     /// Returns (background_color, Option<border_color>).
@@ -641,22 +657,29 @@ impl Widget for PushButton {
         let is_hovered = self.inner.widget_base().is_hovered();
         let is_checked = self.inner.is_checked();
 
-        // Get variant-specific colors
-        let (bg_color, border_color) =
-            self.variant_colors(is_disabled, is_pressed, is_hovered, is_checked);
+        // Resolve style from the style engine, if one is attached
+        let style = self.computed_style();
+
+        if let Some(style) = &style {
+            paint_styled_box(ctx, rect, style);
+        } else {
+            // Get variant-specific colors
+            let (bg_color, border_color) =
+                self.variant_colors(is_disabled, is_pressed, is_hovered, is_checked);
 
-        // Draw rounded rectangle background and/or border based on variant
-        let rrect = RoundedRect::new(rect, self.border_radius);
+            // Draw rounded rectangle background and/or border based on variant
+            let rrect = RoundedRect::new(rect, self.border_radius);
 
-        // Fill background if not transparent
-        if bg_color.a > 0.0 {
-            ctx.renderer().fill_rounded_rect(rrect, bg_color);
-        }
+            // Fill background if not transparent
+            if bg_color.a > 0.0 {
+                ctx.renderer().fill_rounded_rect(rrect, bg_color);
+            }
 
-        // Draw border if color is specified (non-transparent)
-        if let Some(border) = border_color {
-            let stroke = Stroke::new(border, 1.0);
-            ctx.renderer().stroke_rounded_rect(rrect, &stroke);
+            // Draw border if color is specified (non-transparent)
+            if let Some(border) = border_color {
+                let stroke = Stroke::new(border, 1.0);
+                ctx.renderer().stroke_rounded_rect(rrect, &stroke);
+            }
         }
 
         // Calculate content sizes
@@ -762,7 +785,11 @@ impl Widget for PushButton {
             };
 
             let text_pos = Point::new(text_area_x, text_area_y);
-            let text_color = self.inner.effective_text_color();
+            // An explicit text color wins over the stylesheet
+            let text_color = match &style {
+                Some(style) if self.inner.text_color().is_none() => style.color,
+                _ => self.inner.effective_text_color(),
+            };
 
             // Render text
             if let Ok(mut text_renderer) = TextRenderer::new() {
@@ -21,6 +21,9 @@
 
 use horizon_lattice_core::{Object, ObjectId, Signal};
 use horizon_lattice_render::{Color, Point, Rect, Renderer, RoundedRect, Stroke};
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, has_visible_box, paint_styled_box,
+};
 
 use crate::widget::{
     FocusPolicy, Key, KeyPressEvent, MouseButton, MouseMoveEvent, MousePressEvent,
@@ -41,6 +44,16 @@ use super::Orientation;
 /// - **Thumb**: The draggable handle indicating current position and visible portion
 /// - **Step buttons** (optional): Arrow buttons at each end for single-step scrolling
 ///
+/// # Styling
+///
+/// Stylesheets can target the components as sub-controls: `ScrollBar::groove`
+/// (track), `ScrollBar::sub-page` and `ScrollBar::add-page` (the track on
+/// either side of the thumb), `ScrollBar::handle` (thumb), and
+/// `ScrollBar::sub-line` and `ScrollBar::add-line` (step buttons, whose
+/// `color` is used for the arrow). Each matches `:hover`, and the handle
+/// matches `:pressed` while dragged. Components without a styled background
+/// or border keep their built-in appearance.
+///
 /// # Signals
 ///
 /// - `value_changed(i32)`: Emitted when the scroll position changes
@@ -414,6 +427,40 @@ impl ScrollBar {
         self
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Compute the style of a scrollbar sub-control from the attached style engine.
+    ///
+    /// The scrollbar paints [`PseudoElement::Groove`], [`PseudoElement::SubPage`],
+    /// [`PseudoElement::AddPage`], [`PseudoElement::Handle`],
+    /// [`PseudoElement::SubLine`], and [`PseudoElement::AddLine`]. Each is
+    /// hovered while the mouse is over it, and the handle is pressed while it
+    /// is dragged.
+    ///
+    /// Returns `None` if no style engine applies to the scrollbar.
+    pub fn sub_control_style(&self, element: PseudoElement) -> Option<ComputedStyle> {
+        let part = match element {
+            PseudoElement::Handle => ScrollBarPart::Thumb,
+            PseudoElement::SubLine => ScrollBarPart::StepButtonDecrease,
+            PseudoElement::AddLine => ScrollBarPart::StepButtonIncrease,
+            PseudoElement::SubPage => ScrollBarPart::TrackDecrease,
+            PseudoElement::AddPage => ScrollBarPart::TrackIncrease,
+            _ => ScrollBarPart::None,
+        };
+
+        let mut state = self.base.sub_control_state();
+        state.hovered = part != ScrollBarPart::None && self.hover_part == part;
+        state.pressed = part == ScrollBarPart::Thumb && self.dragging;
+        self.base.compute_sub_control_style(element, state)
+    }
+
+    /// Get a sub-control style if a stylesheet gives it a visible box.
+    fn styled_sub_control(&self, element: PseudoElement) -> Option<ComputedStyle> {
+        self.sub_control_style(element).filter(has_visible_box)
+    }
+
     // =========================================================================
     // Geometry Helpers
     // =========================================================================
@@ -767,14 +814,62 @@ impl ScrollBar {
 
     fn paint_track(&self, ctx: &mut PaintContext<'_>) {
         let track = self.track_rect();
-        let track_rrect = RoundedRect::new(track, self.border_radius);
-        ctx.renderer()
-            .fill_rounded_rect(track_rrect, self.track_color);
+        if let Some(style) = self.styled_sub_control(PseudoElement::Groove) {
+            paint_styled_box(ctx, track, &style);
+        } else {
+            let track_rrect = RoundedRect::new(track, self.border_radius);
+            ctx.renderer()
+                .fill_rounded_rect(track_rrect, self.track_color);
+        }
+
+        // Track portions on either side of the thumb, only when styled
+        let thumb = self.thumb_rect();
+        let (sub_page, add_page) = match self.orientation {
+            Orientation::Horizontal => (
+                Rect::new(
+                    track.origin.x,
+                    track.origin.y,
+                    thumb.origin.x - track.origin.x,
+                    track.height(),
+                ),
+                Rect::new(
+                    thumb.origin.x + thumb.width(),
+                    track.origin.y,
+                    track.origin.x + track.width() - (thumb.origin.x + thumb.width()),
+                    track.height(),
+                ),
+            ),
+            Orientation::Vertical => (
+                Rect::new(
+                    track.origin.x,
+                    track.origin.y,
+                    track.width(),
+                    thumb.origin.y - track.origin.y,
+                ),
+                Rect::new(
+                    track.origin.x,
+                    thumb.origin.y + thumb.height(),
+                    track.width(),
+                    track.origin.y + track.height() - (thumb.origin.y + thumb.height()),
+                ),
+            ),
+        };
+        if let Some(style) = self.styled_sub_control(PseudoElement::SubPage) {
+            paint_styled_box(ctx, sub_page, &style);
+        }
+        if let Some(style) = self.styled_sub_control(PseudoElement::AddPage) {
+            paint_styled_box(ctx, add_page, &style);
+        }
     }
 
     fn paint_thumb(&self, ctx: &mut PaintContext<'_>) {
         let thumb = self.thumb_rect();
 
+        if let Some(style) = self.styled_sub_control(PseudoElement::Handle) {
+            paint_styled_box(ctx, thumb, &style);
+            return;
+        }
+
         // Choose color based on state
         let color = if self.dragging {
             self.thumb_pressed_color
@@ -812,36 +907,59 @@ impl ScrollBar {
 
         // Paint decrease button
         if let Some(rect) = self.decrease_button_rect() {
-            let color = if self.hover_part == ScrollBarPart::StepButtonDecrease {
-                self.step_button_hover_color
-            } else {
-                self.step_button_color
+            let arrow_color = match self.styled_sub_control(PseudoElement::SubLine) {
+                Some(style) => {
+                    paint_styled_box(ctx, rect, &style);
+                    style.color
+                }
+                None => {
+                    let color = if self.hover_part == ScrollBarPart::StepButtonDecrease {
+                        self.step_button_hover_color
+                    } else {
+                        self.step_button_color
+                    };
+                    ctx.renderer().fill_rect(rect, color);
+                    Color::from_rgb8(100, 100, 100)
+                }
             };
-            ctx.renderer().fill_rect(rect, color);
 
             // Draw arrow
-            self.paint_arrow(ctx, rect, false);
+            self.paint_arrow(ctx, rect, false, arrow_color);
         }
 
         // Paint increase button
         if let Some(rect) = self.increase_button_rect() {
-            let color = if self.hover_part == ScrollBarPart::StepButtonIncrease {
-                self.step_button_hover_color
-            } else {
-                self.step_button_color
+            let arrow_color = match self.styled_sub_control(PseudoElement::AddLine) {
+                Some(style) => {
+                    paint_styled_box(ctx, rect, &style);
+                    style.color
+                }
+                None => {
+                    let color = if self.hover_part == ScrollBarPart::StepButtonIncrease {
+                        self.step_button_hover_color
+                    } else {
+                        self.step_button_color
+                    };
+                    ctx.renderer().fill_rect(rect, color);
+                    Color::from_rgb8(100, 100, 100)
+                }
             };
-            ctx.renderer().fill_rect(rect, color);
 
             // Draw arrow
-            self.paint_arrow(ctx, rect, true);
+            self.paint_arrow(ctx, rect, true, arrow_color);
         }
     }
 
-    fn paint_arrow(&self, ctx: &mut PaintContext<'_>, rect: Rect, increase: bool) {
+    fn paint_arrow(
+        &self,
+        ctx: &mut PaintContext<'_>,
+        rect: Rect,
+        increase: bool,
+        arrow_color: Color,
+    ) {
         let center_x = rect.origin.x + rect.width() / 2.0;
         let center_y = rect.origin.y + rect.height() / 2.0;
         let arrow_size = 4.0;
-        let arrow_color = Color::from_rgb8(100, 100, 100);
         let stroke = Stroke::new(arrow_color, 1.5);
 
         match (self.orientation, increase) {
@@ -1077,4 +1195,43 @@ mod tests {
         let hint = horizontal.size_hint();
         assert!(hint.preferred.width > hint.preferred.height);
     }
+
+    #[test]
+    fn test_scrollbar_sub_control_styles() {
+        use crate::widget::style::set_tree_style_engine;
+        use horizon_lattice_style::prelude::{StyleEngine, StylePriority, StyleSheet};
+        use parking_lot::RwLock;
+
+        setup();
+        let mut bar = ScrollBar::new(Orientation::Vertical);
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "ScrollBar::handle { background-color: #808080; } \
+                 ScrollBar::handle:hover { background-color: #000000; } \
+                 ScrollBar::add-line:hover { background-color: #ff0000; }",
+                StylePriority::Application,
+            )
+            .unwrap(),
+        );
+        set_tree_style_engine(bar.object_id(), Some(Arc::new(RwLock::new(engine))));
+
+        let handle = bar.sub_control_style(PseudoElement::Handle).unwrap();
+        assert_eq!(
+            handle.background.as_solid(),
+            Some(Color::from_rgb8(128, 128, 128))
+        );
+        assert!(bar.styled_sub_control(PseudoElement::AddLine).is_none());
+
+        bar.hover_part = ScrollBarPart::Thumb;
+        let handle = bar.sub_control_style(PseudoElement::Handle).unwrap();
+        assert_eq!(handle.background.as_solid(), Some(Color::BLACK));
+
+        bar.hover_part = ScrollBarPart::StepButtonIncrease;
+        let add_line = bar.styled_sub_control(PseudoElement::AddLine).unwrap();
+        assert_eq!(add_line.background.as_solid(), Some(Color::RED));
+        assert!(bar.styled_sub_control(PseudoElement::SubLine).is_none());
+
+        set_tree_style_engine(bar.object_id(), None);
+    }
 }
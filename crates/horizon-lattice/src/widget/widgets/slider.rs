@@ -18,9 +18,20 @@
 //!     println!("Value: {}", value);
 //! });
 //! ```
+//!
+//! # Styling
+//!
+//! Besides the slider itself, stylesheets can target its sub-controls:
+//! `Slider::groove`, `Slider::sub-page` (the filled part of the groove),
+//! `Slider::add-page`, and `Slider::handle`, which matches `:hover` and
+//! `:pressed`. Parts without a styled background or border keep their
+//! built-in appearance.
 
 use horizon_lattice_core::{Object, ObjectId, Signal};
 use horizon_lattice_render::{Color, Point, Rect, Renderer, RoundedRect, Stroke};
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, has_visible_box, paint_styled_box,
+};
 
 use crate::widget::{
     FocusPolicy, Key, KeyPressEvent, MouseButton, MouseMoveEvent, MousePressEvent,
@@ -475,6 +486,32 @@ impl Slider {
         self
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Compute the style of a slider sub-control from the attached style engine.
+    ///
+    /// The slider paints [`PseudoElement::Groove`], [`PseudoElement::SubPage`],
+    /// [`PseudoElement::AddPage`], and [`PseudoElement::Handle`]. The handle is
+    /// hovered while the mouse is over it and pressed while it is dragged.
+    ///
+    /// Returns `None` if no style engine applies to the slider.
+    pub fn sub_control_style(&self, element: PseudoElement) -> Option<ComputedStyle> {
+        let mut state = self.base.sub_control_state();
+        if element == PseudoElement::Handle {
+            state.hovered = self.hover_thumb;
+            state.pressed = self.dragging;
+            state.focused = self.base.has_focus();
+        }
+        self.base.compute_sub_control_style(element, state)
+    }
+
+    /// Get a sub-control style if a stylesheet gives it a visible box.
+    fn styled_sub_control(&self, element: PseudoElement) -> Option<ComputedStyle> {
+        self.sub_control_style(element).filter(has_visible_box)
+    }
+
     // =========================================================================
     // Geometry Helpers
     // =========================================================================
@@ -757,33 +794,63 @@ impl Slider {
 
     fn paint_track(&self, ctx: &mut PaintContext<'_>) {
         let track = self.track_rect();
+        let ratio = self.value_ratio();
+
+        // Split the track at the thumb into the filled and remaining portions
+        let (fill_rect, rest_rect) = match self.orientation {
+            Orientation::Horizontal => {
+                let fill_width = track.width() * ratio;
+                (
+                    Rect::new(track.origin.x, track.origin.y, fill_width, track.height()),
+                    Rect::new(
+                        track.origin.x + fill_width,
+                        track.origin.y,
+                        track.width() - fill_width,
+                        track.height(),
+                    ),
+                )
+            }
+            Orientation::Vertical => {
+                let fill_height = track.height() * ratio;
+                (
+                    Rect::new(track.origin.x, track.origin.y, track.width(), fill_height),
+                    Rect::new(
+                        track.origin.x,
+                        track.origin.y + fill_height,
+                        track.width(),
+                        track.height() - fill_height,
+                    ),
+                )
+            }
+        };
 
         // Paint background track
-        let track_rrect = RoundedRect::new(track, self.border_radius);
-        ctx.renderer()
-            .fill_rounded_rect(track_rrect, self.track_color);
+        let groove_styled = match self.styled_sub_control(PseudoElement::Groove) {
+            Some(style) => {
+                paint_styled_box(ctx, track, &style);
+                true
+            }
+            None => {
+                let track_rrect = RoundedRect::new(track, self.border_radius);
+                ctx.renderer()
+                    .fill_rounded_rect(track_rrect, self.track_color);
+                false
+            }
+        };
 
         // Paint filled portion (before thumb)
-        let ratio = self.value_ratio();
-        if ratio > 0.0 {
-            let fill_rect = match self.orientation {
-                Orientation::Horizontal => Rect::new(
-                    track.origin.x,
-                    track.origin.y,
-                    track.width() * ratio,
-                    track.height(),
-                ),
-                Orientation::Vertical => Rect::new(
-                    track.origin.x,
-                    track.origin.y,
-                    track.width(),
-                    track.height() * ratio,
-                ),
-            };
+        if let Some(style) = self.styled_sub_control(PseudoElement::SubPage) {
+            paint_styled_box(ctx, fill_rect, &style);
+        } else if !groove_styled && ratio > 0.0 {
             let fill_rrect = RoundedRect::new(fill_rect, self.border_radius);
             ctx.renderer()
                 .fill_rounded_rect(fill_rrect, self.track_fill_color);
         }
+
+        // Paint remaining portion (after thumb), only when styled
+        if let Some(style) = self.styled_sub_control(PseudoElement::AddPage) {
+            paint_styled_box(ctx, rest_rect, &style);
+        }
     }
 
     fn paint_ticks(&self, ctx: &mut PaintContext<'_>) {
@@ -873,6 +940,19 @@ impl Slider {
         let center = self.thumb_center();
         let radius = self.thumb_size / 2.0;
 
+        if let Some(style) = self.styled_sub_control(PseudoElement::Handle) {
+            let width = style.width.unwrap_or(self.thumb_size);
+            let height = style.height.unwrap_or(self.thumb_size);
+            let handle_rect = Rect::new(
+                center.x - width / 2.0,
+                center.y - height / 2.0,
+                width,
+                height,
+            );
+            paint_styled_box(ctx, handle_rect, &style);
+            return;
+        }
+
         // Choose color based on state
         let color = if self.dragging {
             self.thumb_pressed_color
@@ -1168,4 +1248,36 @@ mod tests {
         slider.set_orientation(Orientation::Vertical);
         assert_eq!(slider.orientation(), Orientation::Vertical);
     }
+
+    #[test]
+    fn test_slider_handle_sub_control_style() {
+        use crate::widget::style::set_tree_style_engine;
+        use horizon_lattice_style::prelude::{StyleEngine, StylePriority, StyleSheet};
+        use parking_lot::RwLock;
+
+        setup();
+        let mut slider = Slider::new(Orientation::Horizontal);
+        assert!(slider.sub_control_style(PseudoElement::Handle).is_none());
+
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "Slider::handle { background-color: #ffffff; } \
+                 Slider::handle:pressed { background-color: #ff0000; }",
+                StylePriority::Application,
+            )
+            .unwrap(),
+        );
+        set_tree_style_engine(slider.object_id(), Some(Arc::new(RwLock::new(engine))));
+
+        let handle = slider.sub_control_style(PseudoElement::Handle).unwrap();
+        assert_eq!(handle.background.as_solid(), Some(Color::WHITE));
+        assert!(slider.styled_sub_control(PseudoElement::Groove).is_none());
+
+        slider.dragging = true;
+        let handle = slider.sub_control_style(PseudoElement::Handle).unwrap();
+        assert_eq!(handle.background.as_solid(), Some(Color::RED));
+
+        set_tree_style_engine(slider.object_id(), None);
+    }
 }
@@ -39,6 +39,9 @@ use horizon_lattice_render::{
     Color, Font, FontFamily, FontSystem, HorizontalAlign, Point, Rect, Renderer, RoundedRect,
     Stroke, TextLayout, TextLayoutOptions, TextRenderer, VerticalAlign,
 };
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, has_visible_box, paint_styled_box,
+};
 
 use crate::widget::{
     FocusPolicy, Key, KeyPressEvent, MouseButton, MouseMoveEvent, MousePressEvent,
@@ -718,7 +721,7 @@ impl SpinBox {
     }
 
     /// Finish editing and apply the value.
-    fn finish_editing(&mut self) {
+    pub(crate) fn finish_editing(&mut self) {
         if !self.editing {
             return;
         }
@@ -1146,6 +1149,39 @@ impl SpinBox {
         false
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Compute the style of a spin box sub-control from the attached style engine.
+    ///
+    /// The spin box paints [`PseudoElement::UpButton`] and
+    /// [`PseudoElement::DownButton`], whose `color` is used for their arrows.
+    /// [`PseudoElement::UpArrow`] and [`PseudoElement::DownArrow`] get the
+    /// state of their button. A button is hovered or pressed with the mouse,
+    /// and disabled when the value cannot step further in its direction.
+    ///
+    /// Returns `None` if no style engine applies to the spin box.
+    pub fn sub_control_style(&self, element: PseudoElement) -> Option<ComputedStyle> {
+        let (part, can_step) = match element {
+            PseudoElement::UpButton | PseudoElement::UpArrow => {
+                (SpinBoxPart::UpButton, self.value < self.maximum)
+            }
+            PseudoElement::DownButton | PseudoElement::DownArrow => {
+                (SpinBoxPart::DownButton, self.value > self.minimum)
+            }
+            _ => (SpinBoxPart::None, true),
+        };
+
+        let mut state = self.base.sub_control_state();
+        if part != SpinBoxPart::None {
+            state.hovered = self.hover_part == part;
+            state.pressed = self.pressed_part == part;
+            state.enabled &= can_step || self.wrapping;
+        }
+        self.base.compute_sub_control_style(element, state)
+    }
+
     // =========================================================================
     // Painting
     // =========================================================================
@@ -1249,34 +1285,40 @@ impl SpinBox {
 
         // Draw up button
         let up_rect = self.up_button_rect();
-        let up_color = if self.pressed_part == SpinBoxPart::UpButton {
-            self.button_pressed_color
-        } else if self.hover_part == SpinBoxPart::UpButton {
-            self.button_hover_color
-        } else {
-            self.button_color
-        };
-        ctx.renderer().fill_rect(up_rect, up_color);
-        self.paint_arrow(ctx, up_rect, true);
+        self.paint_button(ctx, up_rect, SpinBoxPart::UpButton);
 
         // Draw down button
         let down_rect = self.down_button_rect();
-        let down_color = if self.pressed_part == SpinBoxPart::DownButton {
+        self.paint_button(ctx, down_rect, SpinBoxPart::DownButton);
+    }
+
+    fn paint_button(&self, ctx: &mut PaintContext<'_>, rect: Rect, part: SpinBoxPart) {
+        let (element, up) = match part {
+            SpinBoxPart::UpButton => (PseudoElement::UpButton, true),
+            _ => (PseudoElement::DownButton, false),
+        };
+
+        if let Some(style) = self.sub_control_style(element).filter(has_visible_box) {
+            paint_styled_box(ctx, rect, &style);
+            self.paint_arrow(ctx, rect, up, style.color);
+            return;
+        }
+
+        let color = if self.pressed_part == part {
             self.button_pressed_color
-        } else if self.hover_part == SpinBoxPart::DownButton {
+        } else if self.hover_part == part {
             self.button_hover_color
         } else {
             self.button_color
         };
-        ctx.renderer().fill_rect(down_rect, down_color);
-        self.paint_arrow(ctx, down_rect, false);
+        ctx.renderer().fill_rect(rect, color);
+        self.paint_arrow(ctx, rect, up, Color::from_rgb8(80, 80, 80));
     }
 
-    fn paint_arrow(&self, ctx: &mut PaintContext<'_>, rect: Rect, up: bool) {
+    fn paint_arrow(&self, ctx: &mut PaintContext<'_>, rect: Rect, up: bool, arrow_color: Color) {
         let center_x = rect.origin.x + rect.width() / 2.0;
         let center_y = rect.origin.y + rect.height() / 2.0;
         let arrow_size = 4.0;
-        let arrow_color = Color::from_rgb8(80, 80, 80);
         let stroke = Stroke::new(arrow_color, 1.5);
 
         if up {
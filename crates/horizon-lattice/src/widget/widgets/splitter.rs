@@ -26,6 +26,9 @@
 
 use horizon_lattice_core::{Object, ObjectId, Signal};
 use horizon_lattice_render::{Color, Point, Rect, Renderer};
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, has_visible_box, paint_styled_box,
+};
 
 use crate::widget::{
     FocusPolicy, MouseButton, MouseDoubleClickEvent, MouseMoveEvent, MousePressEvent,
@@ -793,6 +796,32 @@ impl Splitter {
         false
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Compute the style of a handle from the attached style engine.
+    ///
+    /// The splitter paints [`PseudoElement::Handle`] between each pair of
+    /// panes. A handle is hovered while the mouse is over it, pressed while
+    /// it is being dragged, and matches structural pseudo-classes such as
+    /// `:first-child` by its position among the handles.
+    ///
+    /// Returns `None` if no style engine applies to the splitter, `element`
+    /// is not a handle, or `index` is out of range.
+    pub fn sub_control_style(&self, element: PseudoElement, index: usize) -> Option<ComputedStyle> {
+        let handle_count = self.children.len().saturating_sub(1);
+        if element != PseudoElement::Handle || index >= handle_count {
+            return None;
+        }
+
+        let mut state = self.base.sub_control_state();
+        state.hovered = self.hover_handle == Some(index);
+        state.pressed = self.dragging_handle == Some(index);
+        state.sibling_info = Some((index, handle_count));
+        self.base.compute_sub_control_style(element, state)
+    }
+
     // =========================================================================
     // Painting
     // =========================================================================
@@ -800,6 +829,14 @@ impl Splitter {
     fn paint_handles(&self, ctx: &mut PaintContext<'_>) {
         for i in 0..self.children.len().saturating_sub(1) {
             if let Some(rect) = self.handle_rect(i) {
+                if let Some(style) = self
+                    .sub_control_style(PseudoElement::Handle, i)
+                    .filter(has_visible_box)
+                {
+                    paint_styled_box(ctx, rect, &style);
+                    continue;
+                }
+
                 let color = if self.dragging_handle == Some(i) {
                     self.handle_pressed_color
                 } else if self.hover_handle == Some(i) {
@@ -1155,4 +1192,53 @@ mod tests {
         assert_eq!(splitter.widget(1), Some(pane2_id));
         assert_eq!(splitter.widget(2), Some(pane3_id));
     }
+
+    #[test]
+    fn test_handle_sub_control_style() {
+        use crate::widget::style::set_tree_style_engine;
+        use horizon_lattice_style::prelude::{StyleEngine, StylePriority, StyleSheet};
+        use parking_lot::RwLock;
+        use std::sync::Arc;
+
+        setup();
+        let mut splitter = Splitter::new(Orientation::Horizontal);
+        let panes: Vec<MockWidget> = (0..3).map(|_| MockWidget::new()).collect();
+        for pane in &panes {
+            splitter.add_widget(pane.object_id());
+        }
+
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "Splitter::handle { background-color: #ffffff; } \
+                 Splitter::handle:hover { background-color: #0000ff; }",
+                StylePriority::Application,
+            )
+            .unwrap(),
+        );
+        set_tree_style_engine(splitter.object_id(), Some(Arc::new(RwLock::new(engine))));
+
+        splitter.hover_handle = Some(1);
+        let idle = splitter
+            .sub_control_style(PseudoElement::Handle, 0)
+            .unwrap();
+        assert_eq!(idle.background.as_solid(), Some(Color::WHITE));
+        let hovered = splitter
+            .sub_control_style(PseudoElement::Handle, 1)
+            .unwrap();
+        assert_eq!(hovered.background.as_solid(), Some(Color::BLUE));
+
+        assert!(
+            splitter
+                .sub_control_style(PseudoElement::Handle, 2)
+                .is_none()
+        );
+        assert!(
+            splitter
+                .sub_control_style(PseudoElement::Section, 0)
+                .is_none()
+        );
+
+        set_tree_style_engine(splitter.object_id(), None);
+    }
 }
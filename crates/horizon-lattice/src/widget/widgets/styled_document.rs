@@ -6,8 +6,31 @@
 
 use std::ops::Range;
 
-use horizon_lattice_render::Color;
-use horizon_lattice_render::text::{FontFamily, FontWeight, HorizontalAlign};
+use horizon_lattice_render::text::{
+    Font, FontFamily, FontStyle, FontSystem, FontWeight, HorizontalAlign, TextDecoration,
+    TextLayout, TextLayoutOptions, TextSpan, WrapMode,
+};
+use horizon_lattice_render::{Color, Point};
+
+use super::print_document::PrintDocument;
+
+/// Convert a Color (premultiplied alpha, f32) to RGBA8 bytes for TextSpan.
+#[inline]
+fn color_to_rgba8(color: Color) -> [u8; 4] {
+    // Unpremultiply alpha for correct byte representation
+    let (r, g, b) = if color.a > 0.0 {
+        (color.r / color.a, color.g / color.a, color.b / color.a)
+    } else {
+        (0.0, 0.0, 0.0)
+    };
+
+    [
+        (r.clamp(0.0, 1.0) * 255.0) as u8,
+        (g.clamp(0.0, 1.0) * 255.0) as u8,
+        (b.clamp(0.0, 1.0) * 255.0) as u8,
+        (color.a.clamp(0.0, 1.0) * 255.0) as u8,
+    ]
+}
 
 /// Character-level formatting attributes.
 ///
@@ -165,6 +188,65 @@ impl CharFormat {
             self.strikethrough = !self.strikethrough;
         }
     }
+
+    /// Build a text span for `text` with this format applied on top of
+    /// `base_font`.
+    pub(crate) fn to_text_span<'a>(&self, text: &'a str, base_font: &Font) -> TextSpan<'a> {
+        let mut span = TextSpan::new(text);
+
+        // Build font with style overrides
+        let mut font = base_font.clone();
+        let mut needs_font = false;
+
+        // Apply font family if specified
+        if let Some(ref family) = self.font_family {
+            font = font.with_family(family.clone());
+            needs_font = true;
+        }
+
+        // Apply font size if specified
+        if let Some(size) = self.font_size {
+            font = font.with_size(size);
+            needs_font = true;
+        }
+
+        // Apply font weight: explicit weight takes precedence over bold flag
+        if let Some(weight) = self.font_weight {
+            font = font.with_weight(weight);
+            needs_font = true;
+        } else if self.bold {
+            font = font.with_weight(FontWeight::BOLD);
+            needs_font = true;
+        }
+
+        // Apply italic style
+        if self.italic {
+            font = font.with_style(FontStyle::Italic);
+            needs_font = true;
+        }
+
+        if needs_font {
+            span = span.with_font(font);
+        }
+
+        // Apply decorations
+        if self.underline {
+            span = span.with_decoration(TextDecoration::underline());
+        }
+        if self.strikethrough {
+            span = span.with_decoration(TextDecoration::strikethrough());
+        }
+
+        // Apply colors
+        if let Some(fg_color) = self.foreground_color {
+            span = span.with_color(color_to_rgba8(fg_color));
+        }
+        if let Some(bg_color) = self.background_color {
+            span = span.with_background_color(color_to_rgba8(bg_color));
+        }
+
+        span
+    }
 }
 
 /// A run of text with a specific format.
@@ -2148,6 +2230,101 @@ impl StyledDocument {
         html
     }
 
+    // =========================================================================
+    // Printing
+    // =========================================================================
+
+    /// Lay the document out on the pages of a print document.
+    ///
+    /// Each paragraph is wrapped to the width of the page's content area
+    /// with its block format applied, and continues on the next page if it
+    /// doesn't fit. List markers are drawn in the indent of the first line.
+    /// Text without a font or color of its own uses `font` and `color`.
+    pub fn print(
+        &self,
+        document: &mut PrintDocument,
+        font_system: &mut FontSystem,
+        font: &Font,
+        color: Color,
+    ) {
+        let width = document.page_layout().content_rect().width();
+
+        let mut runs = Vec::new();
+        let mut pos = 0;
+        for (text, format) in self.to_styled_spans() {
+            runs.push((pos..pos + text.len(), format));
+            pos += text.len();
+        }
+
+        // Track item numbers for numbered lists at each indent level
+        let mut item_counts: Vec<usize> = vec![0; 10];
+
+        let mut start = 0;
+        for (para_idx, paragraph) in self.text.split('\n').enumerate() {
+            let range = start..start + paragraph.len();
+            start = range.end + 1;
+            let block = self.block_format_at(para_idx);
+
+            let mut spans: Vec<TextSpan<'_>> = runs
+                .iter()
+                .filter(|(run, _)| run.start < range.end && run.end > range.start)
+                .map(|(run, format)| {
+                    let text = &self.text[run.start.max(range.start)..run.end.min(range.end)];
+                    format.to_text_span(text, font)
+                })
+                .collect();
+            if spans.is_empty() {
+                // Use a space for empty paragraphs to get line height
+                spans.push(TextSpan::new(" "));
+            }
+
+            let mut indent = block.left_indent;
+            let marker = match &block.list_format {
+                Some(list_format) => {
+                    indent += list_format.left_indent();
+                    let level = list_format.indent_level.min(item_counts.len() - 1);
+                    let marker = list_format.marker(item_counts[level]);
+                    item_counts[level] += 1;
+                    item_counts[level + 1..].fill(0);
+                    Some(marker)
+                }
+                None => {
+                    item_counts.fill(0);
+                    None
+                }
+            };
+
+            let options = TextLayoutOptions::default()
+                .wrap(WrapMode::Word)
+                .max_width((width - indent).max(1.0))
+                .horizontal_align(block.alignment)
+                .first_line_indent(block.first_line_indent)
+                .line_height(block.line_height_multiplier());
+            let layout = TextLayout::rich_text(font_system, &spans, font, options);
+
+            document.add_spacing(block.spacing_before);
+            if let Some(marker) = marker {
+                let marker = TextLayout::new(font_system, &marker, font);
+                let (first_line, offset) = match (layout.lines().first(), marker.lines().first()) {
+                    (Some(line), Some(marker_line)) => {
+                        (line.height, line.baseline_y - marker_line.baseline_y)
+                    }
+                    _ => (0.0, 0.0),
+                };
+
+                // Keep the marker on the page of the first line
+                document.ensure_space(first_line);
+                let x = indent - ListFormat::INDENT_STEP + 4.0;
+                document.add_block(0.0, |picture, rect| {
+                    let position = Point::new(rect.left() + x, rect.top() + offset);
+                    picture.draw_text(&marker, position, color);
+                });
+            }
+            document.add_text_at(&layout, indent, color);
+            document.add_spacing(block.spacing_after);
+        }
+    }
+
     // =========================================================================
     // Undo/Redo Support
     // =========================================================================
@@ -4239,4 +4416,43 @@ mod tests {
         assert!(restored.format_at(0).bold);
         assert!(restored.format_at(7).italic);
     }
+
+    #[test]
+    fn test_print_paginates_paragraphs() {
+        use super::super::print_document::{PageLayout, PageMargins};
+        use horizon_lattice_render::{DrawCommand, Size};
+
+        let text: Vec<String> = (1..=30).map(|i| format!("Paragraph {i}")).collect();
+        let mut doc = StyledDocument::from_text(text.join("\n"));
+        doc.set_format(0..9, CharFormat::bold());
+        doc.toggle_bullet_list(0..3);
+        doc.set_spacing_after(3..30, 4.0);
+
+        let mut font_system = FontSystem::new();
+        let font = Font::new(FontFamily::SansSerif, 12.0);
+        let layout =
+            PageLayout::new(Size::new(200.0, 300.0)).with_margins(PageMargins::uniform(20.0));
+        let mut document = PrintDocument::new(layout);
+        doc.print(&mut document, &mut font_system, &font, Color::BLACK);
+
+        let texts: Vec<&TextLayout> = document
+            .pages()
+            .iter()
+            .flat_map(|page| page.commands())
+            .filter_map(|command| match command {
+                DrawCommand::Text { layout, .. } => Some(layout),
+                _ => None,
+            })
+            .collect();
+        if texts.iter().all(|layout| layout.glyphs().next().is_none()) {
+            // No fonts available
+            return;
+        }
+
+        assert!(document.page_count() > 1);
+        // One layout per paragraph and one per list marker
+        assert_eq!(texts.len(), 33);
+        assert_eq!(texts[0].text(), "•");
+        assert_eq!(texts[1].text(), "Paragraph 1");
+    }
 }
@@ -29,6 +29,9 @@ use horizon_lattice_render::{
     Color, CornerRadii, Font, FontFamily, FontSystem, Icon, ImageScaleMode, Point, Rect, Renderer,
     RoundedRect, Stroke, TextLayout, TextRenderer,
 };
+use horizon_lattice_style::prelude::{
+    ComputedStyle, PseudoElement, has_visible_box, paint_styled_box,
+};
 
 use crate::widget::{
     FocusPolicy, Key, KeyPressEvent, MouseButton, MouseMoveEvent, MousePressEvent,
@@ -1183,6 +1186,42 @@ impl TabBar {
         }
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Compute the style of a tab's sub-control from the attached style engine.
+    ///
+    /// The tab bar paints [`PseudoElement::Tab`] for each tab and
+    /// [`PseudoElement::CloseButton`] for closable tabs; both use `color` for
+    /// their text or cross. A tab is `:checked` when it is the current tab and
+    /// `:unchecked` otherwise, is hovered or pressed with the mouse, is
+    /// disabled when the tab is, and matches structural pseudo-classes such as
+    /// `:first-child` by its position in the bar. A close button is hovered
+    /// only while the mouse is over the button itself.
+    ///
+    /// Returns `None` if no style engine applies to the tab bar or `index` is
+    /// out of range.
+    pub fn sub_control_style(&self, element: PseudoElement, index: usize) -> Option<ComputedStyle> {
+        let tab = self.tabs.get(index)?;
+
+        let mut state = self.base.sub_control_state();
+        state.enabled &= tab.enabled;
+        state.sibling_info = Some((index, self.tabs.len()));
+        match element {
+            PseudoElement::CloseButton => {
+                state.hovered = self.hover_part == TabBarPart::CloseButton(index);
+                state.pressed = self.pressed_part == TabBarPart::CloseButton(index);
+            }
+            _ => {
+                state.hovered = matches!(self.hover_part, TabBarPart::Tab(i) | TabBarPart::CloseButton(i) if i == index);
+                state.pressed = self.pressed_part == TabBarPart::Tab(index);
+                state.checked = Some(index as i32 == self.current_index);
+            }
+        }
+        self.base.compute_sub_control_style(element, state)
+    }
+
     // =========================================================================
     // Painting
     // =========================================================================
@@ -1298,50 +1337,20 @@ impl TabBar {
         let is_hovered = matches!(self.hover_part, TabBarPart::Tab(i) | TabBarPart::CloseButton(i) if i == index);
         let is_pressed = matches!(self.pressed_part, TabBarPart::Tab(i) if i == index);
 
-        // Background color
-        let bg_color = if !tab.enabled {
-            self.tab_disabled_color
-        } else if is_selected {
-            self.tab_selected_color
-        } else if is_pressed {
-            darken_color(self.tab_hover_color, 0.1)
-        } else if is_hovered {
-            self.tab_hover_color
+        let tab_style = self
+            .sub_control_style(PseudoElement::Tab, index)
+            .filter(has_visible_box);
+        if let Some(style) = &tab_style {
+            paint_styled_box(ctx, *rect, style);
         } else {
-            self.tab_color
-        };
-
-        // Draw tab background with rounded corners on the appropriate side
-        let rrect = self.rounded_tab_rect(rect, is_selected);
-        ctx.renderer().fill_rounded_rect(rrect, bg_color);
-
-        // Draw bottom border for selected tab (creates visual connection to content)
-        if is_selected {
-            let border_rect = match self.tab_position {
-                TabPosition::Top => Rect::new(
-                    rect.origin.x,
-                    rect.origin.y + rect.height() - 2.0,
-                    rect.width(),
-                    2.0,
-                ),
-                TabPosition::Bottom => Rect::new(rect.origin.x, rect.origin.y, rect.width(), 2.0),
-                TabPosition::Left => Rect::new(
-                    rect.origin.x + rect.width() - 2.0,
-                    rect.origin.y,
-                    2.0,
-                    rect.height(),
-                ),
-                TabPosition::Right => Rect::new(rect.origin.x, rect.origin.y, 2.0, rect.height()),
-            };
-            ctx.renderer()
-                .fill_rect(border_rect, self.tab_selected_color);
+            self.paint_tab_background(ctx, rect, is_selected, is_hovered, is_pressed, tab.enabled);
         }
 
         // Draw icon and text
-        let text_color = if tab.enabled {
-            self.text_color
-        } else {
-            self.text_disabled_color
+        let text_color = match &tab_style {
+            Some(style) => style.color,
+            None if tab.enabled => self.text_color,
+            None => self.text_disabled_color,
         };
 
         let mut font_system = FontSystem::new();
@@ -1411,6 +1420,56 @@ impl TabBar {
         }
     }
 
+    /// Paint the built-in tab background for an unstyled tab.
+    fn paint_tab_background(
+        &self,
+        ctx: &mut PaintContext<'_>,
+        rect: &Rect,
+        is_selected: bool,
+        is_hovered: bool,
+        is_pressed: bool,
+        enabled: bool,
+    ) {
+        // Background color
+        let bg_color = if !enabled {
+            self.tab_disabled_color
+        } else if is_selected {
+            self.tab_selected_color
+        } else if is_pressed {
+            darken_color(self.tab_hover_color, 0.1)
+        } else if is_hovered {
+            self.tab_hover_color
+        } else {
+            self.tab_color
+        };
+
+        // Draw tab background with rounded corners on the appropriate side
+        let rrect = self.rounded_tab_rect(rect, is_selected);
+        ctx.renderer().fill_rounded_rect(rrect, bg_color);
+
+        // Draw bottom border for selected tab (creates visual connection to content)
+        if is_selected {
+            let border_rect = match self.tab_position {
+                TabPosition::Top => Rect::new(
+                    rect.origin.x,
+                    rect.origin.y + rect.height() - 2.0,
+                    rect.width(),
+                    2.0,
+                ),
+                TabPosition::Bottom => Rect::new(rect.origin.x, rect.origin.y, rect.width(), 2.0),
+                TabPosition::Left => Rect::new(
+                    rect.origin.x + rect.width() - 2.0,
+                    rect.origin.y,
+                    2.0,
+                    rect.height(),
+                ),
+                TabPosition::Right => Rect::new(rect.origin.x, rect.origin.y, 2.0, rect.height()),
+            };
+            ctx.renderer()
+                .fill_rect(border_rect, self.tab_selected_color);
+        }
+    }
+
     fn rounded_tab_rect(&self, rect: &Rect, is_selected: bool) -> RoundedRect {
         let r = if is_selected {
             self.border_radius
@@ -1452,20 +1511,29 @@ impl TabBar {
         let rect = self.close_button_rect(tab_rect);
         let is_hovered = matches!(self.hover_part, TabBarPart::CloseButton(i) if i == index);
 
-        // Background on hover
-        if is_hovered {
-            let rrect = RoundedRect::new(rect, 3.0);
-            ctx.renderer()
-                .fill_rounded_rect(rrect, self.close_button_hover_color);
-        }
+        let color = if let Some(style) = self
+            .sub_control_style(PseudoElement::CloseButton, index)
+            .filter(has_visible_box)
+        {
+            paint_styled_box(ctx, rect, &style);
+            style.color
+        } else {
+            // Background on hover
+            if is_hovered {
+                let rrect = RoundedRect::new(rect, 3.0);
+                ctx.renderer()
+                    .fill_rounded_rect(rrect, self.close_button_hover_color);
+            }
+
+            if is_hovered {
+                Color::WHITE
+            } else {
+                self.close_button_color
+            }
+        };
 
         // Draw X
         let padding = 4.0;
-        let color = if is_hovered {
-            Color::WHITE
-        } else {
-            self.close_button_color
-        };
         let stroke = Stroke::new(color, 1.5);
 
         let p1 = Point::new(rect.origin.x + padding, rect.origin.y + padding);
@@ -1753,4 +1821,45 @@ mod tests {
         assert_eq!(index, 0);
         assert!(bar.tab_icon(0).is_some());
     }
+
+    #[test]
+    fn test_tab_sub_control_styles() {
+        use crate::widget::style::set_tree_style_engine;
+        use horizon_lattice_style::prelude::{StyleEngine, StylePriority, StyleSheet};
+        use parking_lot::RwLock;
+
+        setup();
+        let mut bar = TabBar::new();
+        bar.add_tab("One");
+        bar.add_tab("Two");
+        bar.add_tab("Three");
+
+        let mut engine = StyleEngine::light();
+        engine.add_stylesheet(
+            StyleSheet::from_css(
+                "TabBar::tab { background-color: #ffffff; } \
+                 TabBar::tab:checked { background-color: #0000ff; } \
+                 TabBar::tab:last-child { font-size: 20px; } \
+                 TabBar::tab:disabled { color: #808080; }",
+                StylePriority::Application,
+            )
+            .unwrap(),
+        );
+        set_tree_style_engine(bar.object_id(), Some(Arc::new(RwLock::new(engine))));
+
+        let first = bar.sub_control_style(PseudoElement::Tab, 0).unwrap();
+        assert_eq!(first.background.as_solid(), Some(Color::BLUE));
+        let second = bar.sub_control_style(PseudoElement::Tab, 1).unwrap();
+        assert_eq!(second.background.as_solid(), Some(Color::WHITE));
+        let last = bar.sub_control_style(PseudoElement::Tab, 2).unwrap();
+        assert_eq!(last.font_size, 20.0);
+
+        bar.set_tab_enabled(1, false);
+        let second = bar.sub_control_style(PseudoElement::Tab, 1).unwrap();
+        assert_eq!(second.color, Color::from_rgb8(128, 128, 128));
+
+        assert!(bar.sub_control_style(PseudoElement::Tab, 3).is_none());
+
+        set_tree_style_engine(bar.object_id(), None);
+    }
 }
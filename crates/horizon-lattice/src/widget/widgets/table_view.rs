@@ -29,24 +29,30 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use horizon_lattice_core::{Object, ObjectId, Signal};
-use horizon_lattice_render::{Color, Point, Rect, Renderer, Stroke};
+use horizon_lattice_render::{
+    Color, Font, FontFamily, FontSystem, Picture, Point, Rect, Renderer, Stroke, TextLayout,
+    TextLayoutOptions,
+};
 
 use crate::model::{
-    DefaultItemDelegate, DelegatePaintContext, ItemDelegate, ItemModel, ItemRole, ModelIndex,
-    Orientation, SelectionBehavior, SelectionFlags, SelectionMode, SelectionModel,
-    StyleOptionViewItem, ViewItemFeatures, ViewItemState,
+    DefaultItemDelegate, DelegatePaintContext, DelegateTheme, HorizontalAlignment, ItemData,
+    ItemDelegate, ItemModel, ItemRole, ModelChangeTracker, ModelIndex, Orientation,
+    SelectionBehavior, SelectionFlags, SelectionMode, SelectionModel, StyleOptionViewItem,
+    TextAlignment, VerticalAlignment, ViewItemFeatures, ViewItemState,
 };
 use crate::widget::drag_drop::{
-    DragData, DragEnterEvent, DragLeaveEvent, DragMoveEvent, DropAction, DropEvent,
-    DropIndicatorState, DropPosition,
+    DragData, DragDropManager, DragEnterEvent, DragLeaveEvent, DragMoveEvent, DropAction,
+    DropEvent, DropIndicatorState, DropPosition,
 };
 use crate::widget::{
-    ContextMenuEvent, FocusPolicy, Key, KeyPressEvent, MouseButton, MouseMoveEvent,
-    MousePressEvent, MouseReleaseEvent, PaintContext, SizeHint, SizePolicy, SizePolicyPair,
-    WheelEvent, Widget, WidgetBase, WidgetEvent,
+    ContextMenuEvent, FocusPolicy, Key, KeyPressEvent, KeyboardModifiers, MouseButton,
+    MouseDoubleClickEvent, MouseMoveEvent, MousePressEvent, MouseReleaseEvent, PaintContext,
+    SizeHint, SizePolicy, SizePolicyPair, WheelEvent, Widget, WidgetBase, WidgetEvent,
 };
 
-use super::header_view::{HeaderView, SortOrder};
+use super::header_view::{HeaderView, ResizeMode, SortOrder};
+use super::item_editing::{EditTriggers, EditorEvent, EditorSession};
+use super::print_document::PrintDocument;
 use super::scroll_area::ScrollBarPolicy;
 
 /// Grid line style for TableView.
@@ -104,6 +110,33 @@ pub enum TableContextMenuLocation {
 const DEFAULT_ROW_HEIGHT: f32 = 24.0;
 const DEFAULT_COLUMN_WIDTH: f32 = 100.0;
 const SCROLLBAR_THICKNESS: f32 = 14.0;
+/// Rows measured when fitting a column to its contents.
+const RESIZE_TO_CONTENTS_ROW_LIMIT: usize = 1000;
+/// Font size of the printed column header.
+const PRINT_FONT_SIZE: f32 = 10.0;
+
+/// A block of cells shown as one cell, anchored at its top-left cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CellSpan {
+    row: usize,
+    column: usize,
+    row_count: usize,
+    column_count: usize,
+}
+
+impl CellSpan {
+    fn contains(&self, row: usize, column: usize) -> bool {
+        (self.row..self.row + self.row_count).contains(&row)
+            && (self.column..self.column + self.column_count).contains(&column)
+    }
+
+    fn intersects(&self, other: &CellSpan) -> bool {
+        self.row < other.row + other.row_count
+            && other.row < self.row + self.row_count
+            && self.column < other.column + other.column_count
+            && other.column < self.column + self.column_count
+    }
+}
 
 /// A table view widget for displaying 2D tabular data.
 ///
@@ -113,8 +146,11 @@ const SCROLLBAR_THICKNESS: f32 = 14.0;
 /// - Column resizing, reordering, and hiding
 /// - Row, column, or cell selection modes
 /// - Frozen rows and columns
+/// - Cells spanning several rows and columns
+/// - Columns and rows sized to fit their contents
 /// - Virtualized rendering for large datasets
-/// - Sorting via header clicks
+/// - Sorting via header clicks, by several columns with shift-click
+/// - In-place cell editing with delegate-created editors
 ///
 /// # Signals
 ///
@@ -127,6 +163,8 @@ pub struct TableView {
 
     // Model/View
     model: Option<Arc<dyn ItemModel>>,
+    /// Marks the layout stale when the model's rows change.
+    model_changes: Option<ModelChangeTracker>,
     selection_model: SelectionModel,
     delegate: Arc<dyn ItemDelegate>,
 
@@ -158,6 +196,9 @@ pub struct TableView {
     frozen_row_count: usize,
     frozen_column_count: usize,
 
+    // Spans
+    spans: Vec<CellSpan>,
+
     // Grid
     show_grid: bool,
     grid_style: GridStyle,
@@ -168,6 +209,8 @@ pub struct TableView {
     pressed_cell: Option<(usize, usize)>,
     last_click_time: Option<Instant>,
     last_click_cell: Option<(usize, usize)>,
+    /// Column being resized from its header divider: (column, start x, start width).
+    resizing_column: Option<(usize, f32, f32)>,
 
     // Sorting
     sorting_enabled: bool,
@@ -182,6 +225,12 @@ pub struct TableView {
     drag_start_pos: Option<Point>,
     dragging_cell: Option<(usize, usize)>,
 
+    // Editing
+    edit_triggers: EditTriggers,
+    editor: Option<EditorSession>,
+    /// Whether the pressed cell was already current and selected.
+    pressed_on_selected: bool,
+
     // Signals
     /// Emitted when a cell is clicked.
     pub clicked: Signal<ModelIndex>,
@@ -221,6 +270,7 @@ impl TableView {
         Self {
             base,
             model: None,
+            model_changes: None,
             selection_model,
             delegate: Arc::new(DefaultItemDelegate::new()),
             horizontal_header: HeaderView::new(Orientation::Horizontal),
@@ -241,6 +291,7 @@ impl TableView {
             layout_dirty: true,
             frozen_row_count: 0,
             frozen_column_count: 0,
+            spans: Vec::new(),
             show_grid: true,
             grid_style: GridStyle::Both,
             grid_color: Color::from_rgb8(220, 220, 220),
@@ -248,6 +299,7 @@ impl TableView {
             pressed_cell: None,
             last_click_time: None,
             last_click_cell: None,
+            resizing_column: None,
             sorting_enabled: false,
             background_color: Color::WHITE,
             alternate_row_colors: true,
@@ -255,6 +307,9 @@ impl TableView {
             drop_indicator_state: DropIndicatorState::new(),
             drag_start_pos: None,
             dragging_cell: None,
+            edit_triggers: EditTriggers::default(),
+            editor: None,
+            pressed_on_selected: false,
             clicked: Signal::new(),
             double_clicked: Signal::new(),
             activated: Signal::new(),
@@ -310,6 +365,12 @@ impl TableView {
         self
     }
 
+    /// Sets the edit triggers using builder pattern.
+    pub fn with_edit_triggers(mut self, triggers: EditTriggers) -> Self {
+        self.edit_triggers = triggers;
+        self
+    }
+
     // =========================================================================
     // Model
     // =========================================================================
@@ -321,6 +382,8 @@ impl TableView {
 
     /// Sets the model.
     pub fn set_model(&mut self, model: Option<Arc<dyn ItemModel>>) {
+        self.close_editor();
+        self.model_changes = model.clone().map(ModelChangeTracker::new);
         self.model = model.clone();
         self.selection_model.reset();
         self.layout_dirty = true;
@@ -538,6 +601,182 @@ impl TableView {
         self.base.update();
     }
 
+    // =========================================================================
+    // Resize to Contents
+    // =========================================================================
+
+    /// Returns the width that fits a column's header and cells.
+    ///
+    /// Cells are measured with the delegate's size hint, up to the first
+    /// 1000 rows. Cells spanning several columns are skipped.
+    pub fn size_hint_for_column(&self, column: usize) -> f32 {
+        let mut width = self.horizontal_header.section_size_hint(column);
+        let Some(model) = &self.model else {
+            return width;
+        };
+        let parent = ModelIndex::invalid();
+        for row in 0..self.row_count().min(RESIZE_TO_CONTENTS_ROW_LIMIT) {
+            if self
+                .span_at(row, column)
+                .is_some_and(|s| s.column_count > 1)
+            {
+                continue;
+            }
+            let index = model.index(row, column, &parent);
+            let rect = Rect::new(0.0, 0.0, self.column_width(column), self.row_height(row));
+            let option = self.build_style_option(row, column, rect, &index, model.as_ref());
+            width = width.max(self.delegate.size_hint(&option).0);
+        }
+        width
+    }
+
+    /// Returns the height that fits a row's cells.
+    ///
+    /// Cells spanning several rows are skipped.
+    pub fn size_hint_for_row(&self, row: usize) -> f32 {
+        let mut height = self.default_row_height;
+        let Some(model) = &self.model else {
+            return height;
+        };
+        let parent = ModelIndex::invalid();
+        for column in 0..self.column_count() {
+            if self.is_column_hidden(column)
+                || self.span_at(row, column).is_some_and(|s| s.row_count > 1)
+            {
+                continue;
+            }
+            let index = model.index(row, column, &parent);
+            let rect = Rect::new(0.0, 0.0, self.column_width(column), self.row_height(row));
+            let option = self.build_style_option(row, column, rect, &index, model.as_ref());
+            height = height.max(self.delegate.size_hint(&option).1);
+        }
+        height
+    }
+
+    /// Resizes a column to fit its header and cells.
+    pub fn resize_column_to_contents(&mut self, column: usize) {
+        let width = self.size_hint_for_column(column);
+        self.set_column_width(column, width);
+    }
+
+    /// Resizes all columns to fit their headers and cells.
+    pub fn resize_columns_to_contents(&mut self) {
+        for column in 0..self.column_count() {
+            self.resize_column_to_contents(column);
+        }
+    }
+
+    /// Resizes a row to fit its cells.
+    pub fn resize_row_to_contents(&mut self, row: usize) {
+        self.ensure_layout();
+        let height = self.size_hint_for_row(row);
+        self.set_row_height(row, height);
+    }
+
+    /// Resizes all rows to fit their cells.
+    pub fn resize_rows_to_contents(&mut self) {
+        for row in 0..self.row_count() {
+            self.resize_row_to_contents(row);
+        }
+    }
+
+    // =========================================================================
+    // Spans
+    // =========================================================================
+
+    /// Makes the cell at `row`, `column` span `row_span` rows and
+    /// `column_span` columns.
+    ///
+    /// The spanned cells are painted, hit-tested and selected as the cell at
+    /// `row`, `column`. Spans that overlap the new span are removed, and a
+    /// span of one row and one column removes the cell's span.
+    pub fn set_span(&mut self, row: usize, column: usize, row_span: usize, column_span: usize) {
+        let span = CellSpan {
+            row,
+            column,
+            row_count: row_span.max(1),
+            column_count: column_span.max(1),
+        };
+        self.spans.retain(|other| !other.intersects(&span));
+        if span.row_count > 1 || span.column_count > 1 {
+            self.spans.push(span);
+        }
+        self.update_editor_geometry();
+        self.base.update();
+    }
+
+    /// Returns the number of rows spanned by the cell at `row`, `column`.
+    pub fn row_span(&self, row: usize, column: usize) -> usize {
+        self.span_at(row, column)
+            .filter(|span| span.row == row && span.column == column)
+            .map_or(1, |span| span.row_count)
+    }
+
+    /// Returns the number of columns spanned by the cell at `row`, `column`.
+    pub fn column_span(&self, row: usize, column: usize) -> usize {
+        self.span_at(row, column)
+            .filter(|span| span.row == row && span.column == column)
+            .map_or(1, |span| span.column_count)
+    }
+
+    /// Removes all spans.
+    pub fn clear_spans(&mut self) {
+        if !self.spans.is_empty() {
+            self.spans.clear();
+            self.update_editor_geometry();
+            self.base.update();
+        }
+    }
+
+    /// Returns the span covering a cell.
+    fn span_at(&self, row: usize, column: usize) -> Option<CellSpan> {
+        self.spans
+            .iter()
+            .find(|span| span.contains(row, column))
+            .copied()
+    }
+
+    /// Returns the cell that a cell belongs to: its span's anchor, or itself.
+    fn span_anchor(&self, row: usize, column: usize) -> (usize, usize) {
+        self.span_at(row, column)
+            .map_or((row, column), |span| (span.row, span.column))
+    }
+
+    /// Grows a block of cells until it contains every span it touches.
+    fn expand_to_spans(
+        &self,
+        (mut top, mut left): (usize, usize),
+        (mut bottom, mut right): (usize, usize),
+    ) -> ((usize, usize), (usize, usize)) {
+        loop {
+            let block = CellSpan {
+                row: top,
+                column: left,
+                row_count: bottom - top + 1,
+                column_count: right - left + 1,
+            };
+            let mut grown = false;
+            for span in self.spans.iter().filter(|span| span.intersects(&block)) {
+                let span_bottom = span.row + span.row_count - 1;
+                let span_right = span.column + span.column_count - 1;
+                if span.row < top
+                    || span.column < left
+                    || span_bottom > bottom
+                    || span_right > right
+                {
+                    top = top.min(span.row);
+                    left = left.min(span.column);
+                    bottom = bottom.max(span_bottom);
+                    right = right.max(span_right);
+                    grown = true;
+                }
+            }
+            if !grown {
+                return ((top, left), (bottom, right));
+            }
+        }
+    }
+
     // =========================================================================
     // Frozen Sections
     // =========================================================================
@@ -644,32 +883,80 @@ impl TableView {
         )
     }
 
-    /// Creates drag data from the selected cells.
+    /// Starts dragging the selected cells with the given drag manager.
+    ///
+    /// The drag data is created by the model's `mime_data`, and the allowed
+    /// actions are the model's `supported_drag_actions` (only moves in
+    /// `InternalMove` mode). Returns `false` if dragging is disabled or
+    /// nothing is selected.
+    pub fn start_drag(&self, manager: &mut DragDropManager, position: Point) -> bool {
+        if !self.drag_enabled() {
+            return false;
+        }
+        let Some(model) = &self.model else {
+            return false;
+        };
+        let Some(data) = self.create_drag_data(self.selection_model.selected_indices()) else {
+            return false;
+        };
+
+        let mut actions = model.supported_drag_actions();
+        if self.drag_drop_mode == TableDragDropMode::InternalMove {
+            actions &= DropAction::MOVE;
+        }
+        if actions == DropAction::NONE {
+            return false;
+        }
+        manager.start_drag(data, actions, position);
+        true
+    }
+
+    /// Creates drag data for the given cells using the model's `mime_data`.
     fn create_drag_data(&self, indices: &[ModelIndex]) -> Option<DragData> {
         if indices.is_empty() {
             return None;
         }
+        let model = self.model.as_ref()?;
 
-        let mut data = DragData::new();
+        let mut data = model.mime_data(indices);
+        data.set_source_widget(self.base.object_id());
+        if data.is_empty() { None } else { Some(data) }
+    }
 
-        // Extract text from selected items
-        if let Some(model) = &self.model {
-            let texts: Vec<String> = indices
-                .iter()
-                .filter_map(|index| {
-                    model
-                        .data(index, ItemRole::Display)
-                        .as_string()
-                        .map(String::from)
-                })
-                .collect();
+    /// Chooses the action for dropping `data` onto this view.
+    ///
+    /// Cells dragged from this view are moved, and anything else is copied,
+    /// as far as `supported` and the model's drop actions allow.
+    fn drop_action_for(&self, data: &DragData, supported: DropAction) -> DropAction {
+        let Some(model) = &self.model else {
+            return DropAction::NONE;
+        };
+        let internal = data.source_widget() == Some(self.base.object_id());
+        let allowed = model.supported_drop_actions() & supported;
 
-            if !texts.is_empty() {
-                data.set_text(texts.join("\n"));
-            }
+        if self.drag_drop_mode == TableDragDropMode::InternalMove {
+            return if internal {
+                allowed & DropAction::MOVE
+            } else {
+                DropAction::NONE
+            };
+        }
+        if internal && allowed.can_move() {
+            DropAction::MOVE
+        } else if allowed.can_copy() {
+            DropAction::COPY
+        } else {
+            allowed.preferred()
         }
+    }
 
-        if data.is_empty() { None } else { Some(data) }
+    /// Returns the `(row, column)` that a drop at `point` inserts at.
+    fn drop_target_for_point(&self, point: Point) -> (usize, usize) {
+        match self.drop_position_for_point(point) {
+            (Some((row, col)), DropPosition::AboveItem) => (row, col),
+            (Some((row, col)), DropPosition::BelowItem) => (row + 1, col),
+            _ => (self.row_count(), 0),
+        }
     }
 
     /// Returns the drop position for the given point.
@@ -709,14 +996,13 @@ impl TableView {
             return false;
         }
 
-        // Accept the drag if it has text data
-        if event.data().has_text() || event.data().has_urls() {
-            event.accept_proposed_action();
-            event.set_proposed_action(DropAction::COPY);
-            return true;
+        let action = self.drop_action_for(event.data(), event.supported_actions);
+        if action == DropAction::NONE {
+            return false;
         }
-
-        false
+        event.set_proposed_action(action);
+        event.accept_proposed_action();
+        true
     }
 
     /// Handles the DragMove event.
@@ -749,6 +1035,15 @@ impl TableView {
             self.drop_indicator_state.clear();
         }
 
+        // Only propose an action the model accepts at this position.
+        let action = self.drop_action_for(event.data(), event.supported_actions);
+        let (row, column) = self.drop_target_for_point(event.local_pos);
+        let accepted = action != DropAction::NONE
+            && self.model.as_ref().is_some_and(|m| {
+                m.can_drop_mime_data(event.data(), action, row, column, &ModelIndex::invalid())
+            });
+        event.set_proposed_action(if accepted { action } else { DropAction::NONE });
+
         event.accept();
         self.base.update();
         true
@@ -762,24 +1057,35 @@ impl TableView {
     }
 
     /// Handles the Drop event.
+    ///
+    /// The dropped data is passed to the model's `drop_mime_data`, which
+    /// inserts copies or, for rows dragged from this view, moves them.
     fn handle_drop(&mut self, event: &mut DropEvent) -> bool {
         if !self.drop_enabled() {
             return false;
         }
 
-        let (_drop_cell, _drop_position) = self.drop_position_for_point(event.local_pos);
+        let (row, column) = self.drop_target_for_point(event.local_pos);
         self.drop_indicator_state.clear();
         self.base.update();
 
-        // Process the dropped data
-        if event.data().has_text() || event.data().has_urls() {
-            // Accept the drop
-            // In a real implementation, this would insert rows into the model
-            event.accept();
-            return true;
+        let action = self.drop_action_for(event.data(), event.action);
+        let Some(model) = self.model.clone() else {
+            return false;
+        };
+        if action == DropAction::NONE
+            || !model.drop_mime_data(event.data(), action, row, column, &ModelIndex::invalid())
+        {
+            return false;
         }
 
-        false
+        // Dropped rows invalidate the selected cells.
+        self.selection_model.clear_selection();
+        self.vertical_header
+            .set_section_count(model.row_count(&ModelIndex::invalid()));
+        self.layout_dirty = true;
+        event.accept();
+        true
     }
 
     /// Paints the drop indicator if active.
@@ -867,6 +1173,62 @@ impl TableView {
         self.horizontal_header.set_sort_indicator(column, order);
     }
 
+    /// Returns the sort keys, most significant first.
+    pub fn sort_indicators(&self) -> &[(usize, SortOrder)] {
+        self.horizontal_header.sort_indicators()
+    }
+
+    /// Sorts the model by a column.
+    pub fn sort_by_column(&mut self, column: usize, order: SortOrder) {
+        self.sort_by_columns(vec![(column, order)]);
+    }
+
+    /// Sorts the model by several columns, most significant first.
+    ///
+    /// The header shows the keys as numbered sort indicators, and the keys
+    /// are passed to [`ItemModel::sort`], which a `ProxyModel` implements.
+    pub fn sort_by_columns(&mut self, keys: Vec<(usize, SortOrder)>) {
+        self.horizontal_header.set_sort_indicators(keys);
+        if let Some(model) = &self.model {
+            let columns: Vec<(usize, bool)> = self
+                .horizontal_header
+                .sort_indicators()
+                .iter()
+                .map(|&(column, order)| (column, order == SortOrder::Descending))
+                .collect();
+            model.sort(&columns);
+        }
+        self.layout_dirty = true;
+        self.base.update();
+    }
+
+    /// Sorts by a clicked header section.
+    ///
+    /// A plain click sorts by the column alone, reversing the order if it was
+    /// already the primary key. With `extend` (shift-click), the column is
+    /// added as the least significant key, or its order reversed if it is
+    /// already a key.
+    fn sort_by_header_click(&mut self, column: usize, extend: bool) {
+        let reversed = |order| match order {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        };
+        let mut keys = self.sort_indicators().to_vec();
+        if extend {
+            match keys.iter_mut().find(|(section, _)| *section == column) {
+                Some(key) => key.1 = reversed(key.1),
+                None => keys.push((column, SortOrder::Ascending)),
+            }
+        } else {
+            let order = match keys.first() {
+                Some(&(section, order)) if section == column => reversed(order),
+                _ => SortOrder::Ascending,
+            };
+            keys = vec![(column, order)];
+        }
+        self.sort_by_columns(keys);
+    }
+
     // =========================================================================
     // Scrolling
     // =========================================================================
@@ -896,6 +1258,8 @@ impl TableView {
             self.horizontal_header.set_offset(new_x);
             self.vertical_header.set_offset(new_y);
 
+            self.fetch_more_if_needed();
+            self.update_editor_geometry();
             self.base.update();
         }
     }
@@ -945,6 +1309,148 @@ impl TableView {
         }
     }
 
+    // =========================================================================
+    // Editing
+    // =========================================================================
+
+    /// Returns the actions that start editing a cell.
+    pub fn edit_triggers(&self) -> EditTriggers {
+        self.edit_triggers
+    }
+
+    /// Sets the actions that start editing a cell.
+    pub fn set_edit_triggers(&mut self, triggers: EditTriggers) {
+        self.edit_triggers = triggers;
+    }
+
+    /// Opens an editor over the cell at `index`.
+    ///
+    /// Any edit in progress is committed first. Returns `false` if the cell
+    /// isn't editable or the delegate provides no editor for it.
+    pub fn edit(&mut self, index: &ModelIndex) -> bool {
+        self.commit_editor();
+        let Some(model) = self.model.clone() else {
+            return false;
+        };
+        self.scroll_to(index);
+        let Some(option) = self.editor_option(index.row(), index.column()) else {
+            return false;
+        };
+        self.editor = EditorSession::open(model.as_ref(), self.delegate.as_ref(), &option);
+        self.base.update();
+        self.editor.is_some()
+    }
+
+    /// Returns `true` while an editor is open.
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    /// Writes the open editor's value to the model and closes the editor.
+    ///
+    /// Returns `true` if the model accepted the value.
+    pub fn commit_editor(&mut self) -> bool {
+        let Some(session) = self.editor.take() else {
+            return false;
+        };
+        let committed = self
+            .model
+            .as_ref()
+            .is_some_and(|model| session.commit(model.as_ref(), self.delegate.as_ref()));
+        self.base.update();
+        committed
+    }
+
+    /// Closes the open editor without changing the model.
+    pub fn close_editor(&mut self) {
+        if let Some(session) = self.editor.take() {
+            session.cancel(self.delegate.as_ref());
+            self.base.update();
+        }
+    }
+
+    /// Builds the style option for the editor of a cell.
+    fn editor_option(&self, row: usize, col: usize) -> Option<StyleOptionViewItem> {
+        let model = self.model.as_ref()?;
+        let rect = self.cell_visual_rect(row, col)?;
+        let index = model.index(row, col, &ModelIndex::invalid());
+        Some(self.build_style_option(row, col, rect, &index, model.as_ref()))
+    }
+
+    /// Keeps the open editor over its cell after scrolling or a layout change.
+    fn update_editor_geometry(&mut self) {
+        let Some(index) = self.editor.as_ref().map(|e| e.index().clone()) else {
+            return;
+        };
+        match self.editor_option(index.row(), index.column()) {
+            Some(option) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.update_geometry(self.delegate.as_ref(), &option);
+                }
+            }
+            // The cell is gone.
+            None => self.close_editor(),
+        }
+    }
+
+    /// Finds the next (or previous) editable cell in row-major order.
+    fn next_editable_cell(&self, row: usize, col: usize, backward: bool) -> Option<(usize, usize)> {
+        let model = self.model.as_ref()?;
+        let col_count = self.column_count();
+        let start = row * col_count + col;
+        let editable = |pos: &usize| {
+            let (r, c) = (pos / col_count, pos % col_count);
+            !self.horizontal_header.is_section_hidden(c)
+                && model
+                    .flags(&model.index(r, c, &ModelIndex::invalid()))
+                    .editable
+        };
+        let pos = if backward {
+            (0..start).rev().find(editable)
+        } else {
+            (start + 1..self.row_count() * col_count).find(editable)
+        }?;
+        Some((pos / col_count, pos % col_count))
+    }
+
+    /// Offers an event to the open editor.
+    ///
+    /// Returns `Some(handled)` if the editor dealt with the event, or `None`
+    /// if the view should process it.
+    fn handle_editor_event(&mut self, event: &mut WidgetEvent) -> Option<bool> {
+        let session = self.editor.as_mut()?;
+        let index = session.index().clone();
+        match session.handle_event(event) {
+            EditorEvent::Handled => {
+                self.base.update();
+                Some(true)
+            }
+            EditorEvent::Ignored => None,
+            EditorEvent::Commit => {
+                self.commit_editor();
+                Some(true)
+            }
+            EditorEvent::Cancel => {
+                self.close_editor();
+                Some(true)
+            }
+            EditorEvent::CommitAndMove { backward } => {
+                self.commit_editor();
+                if let Some((row, col)) =
+                    self.next_editable_cell(index.row(), index.column(), backward)
+                {
+                    self.move_to_cell(row, col, &KeyboardModifiers::NONE);
+                    self.edit(&ModelIndex::new(row, col, ModelIndex::invalid()));
+                }
+                Some(true)
+            }
+            EditorEvent::CommitAndContinue => {
+                self.commit_editor();
+                None
+            }
+        }
+    }
+
     fn max_scroll_x(&self) -> i32 {
         let viewport = self.content_area_rect();
         (self.content_width - viewport.width()).max(0.0) as i32
@@ -971,6 +1477,7 @@ impl TableView {
 
         let row = self.row_at_content_y(content_y)?;
         let col = self.column_at_content_x(content_x)?;
+        let (row, col) = self.span_anchor(row, col);
 
         Some(ModelIndex::new(row, col, ModelIndex::invalid()))
     }
@@ -1030,9 +1537,38 @@ impl TableView {
     // =========================================================================
 
     fn ensure_layout(&mut self) {
+        if self
+            .model_changes
+            .as_ref()
+            .is_some_and(ModelChangeTracker::take_changed)
+            && let Some(model) = &self.model
+        {
+            let root = ModelIndex::invalid();
+            self.horizontal_header
+                .set_section_count(model.column_count(&root));
+            self.vertical_header
+                .set_section_count(model.row_count(&root));
+            self.layout_dirty = true;
+        }
         if self.layout_dirty {
             self.update_layout();
             self.layout_dirty = false;
+            self.update_editor_geometry();
+        }
+        self.fetch_more_if_needed();
+    }
+
+    /// Asks the model for more rows when less than a viewport of loaded
+    /// content remains below the visible area.
+    fn fetch_more_if_needed(&self) {
+        let Some(model) = &self.model else {
+            return;
+        };
+        let root = ModelIndex::invalid();
+        let viewport = self.content_area_rect();
+        let remaining = self.content_height - self.scroll_y as f32 - viewport.height();
+        if remaining <= viewport.height() && model.can_fetch_more(&root) {
+            model.fetch_more(&root);
         }
     }
 
@@ -1045,10 +1581,21 @@ impl TableView {
         self.row_positions.clear();
         self.row_positions.reserve(row_count);
 
+        for column in 0..self.column_count() {
+            if self.horizontal_header.resize_mode(column) == ResizeMode::ResizeToContents {
+                let width = self.size_hint_for_column(column);
+                self.horizontal_header.set_section_size(column, width);
+            }
+        }
+
         let mut y = 0.0;
-        for _ in 0..row_count {
+        for row in 0..row_count {
             self.row_positions.push(y);
-            let height = self.default_row_height;
+            let height = if self.vertical_header.resize_mode(row) == ResizeMode::ResizeToContents {
+                self.size_hint_for_row(row)
+            } else {
+                self.default_row_height
+            };
             self.row_heights.push(height);
             y += height;
         }
@@ -1181,23 +1728,47 @@ impl TableView {
         )
     }
 
+    /// Returns a cell's rectangle in content coordinates.
+    ///
+    /// A cell covered by a span gets the rectangle of the whole span.
     fn cell_rect(&self, row: usize, col: usize) -> Option<Rect> {
-        if row >= self.row_count() || col >= self.column_count() {
+        let row_count = self.row_count();
+        let col_count = self.column_count();
+        if row >= row_count || col >= col_count {
             return None;
         }
 
-        let x = self.horizontal_header.section_position(col);
-        let y = self.row_positions.get(row).copied()?;
-        let width = self.horizontal_header.section_size(col);
-        let height = self
-            .row_heights
-            .get(row)
-            .copied()
-            .unwrap_or(self.default_row_height);
+        let span = self.span_at(row, col).unwrap_or(CellSpan {
+            row,
+            column: col,
+            row_count: 1,
+            column_count: 1,
+        });
+        let x = self.horizontal_header.section_position(span.column);
+        let y = self.row_positions.get(span.row).copied()?;
+        let width: f32 = (span.column..(span.column + span.column_count).min(col_count))
+            .filter(|&c| !self.horizontal_header.is_section_hidden(c))
+            .map(|c| self.horizontal_header.section_size(c))
+            .sum();
+        let height: f32 = (span.row..(span.row + span.row_count).min(row_count))
+            .map(|r| self.row_height(r))
+            .sum();
 
         Some(Rect::new(x, y, width, height))
     }
 
+    /// Returns a cell's rectangle in widget coordinates.
+    fn cell_visual_rect(&self, row: usize, col: usize) -> Option<Rect> {
+        let cell_rect = self.cell_rect(row, col)?;
+        let content_area = self.content_area_rect();
+        Some(Rect::new(
+            cell_rect.origin.x - self.scroll_x as f32 + content_area.origin.x,
+            cell_rect.origin.y - self.scroll_y as f32 + content_area.origin.y,
+            cell_rect.width(),
+            cell_rect.height(),
+        ))
+    }
+
     // =========================================================================
     // Painting
     // =========================================================================
@@ -1216,6 +1787,7 @@ impl TableView {
         let (first_col, last_col) = self.visible_columns();
         let content_area = self.content_area_rect();
         let parent = ModelIndex::invalid();
+        let mut painted_spans = Vec::new();
 
         for row in first_row..=last_row {
             for col in first_col..=last_col {
@@ -1223,17 +1795,20 @@ impl TableView {
                     continue;
                 }
 
-                let Some(cell_rect) = self.cell_rect(row, col) else {
-                    continue;
+                // A span is painted once, as its anchor cell, when the first
+                // of its visible cells is reached.
+                let (row, col) = match self.span_at(row, col) {
+                    Some(span) if painted_spans.contains(&span) => continue,
+                    Some(span) => {
+                        painted_spans.push(span);
+                        (span.row, span.column)
+                    }
+                    None => (row, col),
                 };
 
-                // Transform to viewport coordinates
-                let visual_rect = Rect::new(
-                    cell_rect.origin.x - self.scroll_x as f32 + content_area.origin.x,
-                    cell_rect.origin.y - self.scroll_y as f32 + content_area.origin.y,
-                    cell_rect.width(),
-                    cell_rect.height(),
-                );
+                let Some(visual_rect) = self.cell_visual_rect(row, col) else {
+                    continue;
+                };
 
                 // Skip if outside content area
                 if visual_rect.origin.x + visual_rect.width() < content_area.origin.x
@@ -1276,11 +1851,33 @@ impl TableView {
                 if visual_y >= content_area.origin.y
                     && visual_y <= content_area.origin.y + content_area.height()
                 {
-                    ctx.renderer().draw_line(
-                        Point::new(content_area.origin.x, visual_y),
-                        Point::new(content_area.origin.x + content_area.width(), visual_y),
-                        &stroke,
-                    );
+                    if self.spans.is_empty() {
+                        ctx.renderer().draw_line(
+                            Point::new(content_area.origin.x, visual_y),
+                            Point::new(content_area.origin.x + content_area.width(), visual_y),
+                            &stroke,
+                        );
+                        continue;
+                    }
+                    // Break the line where it would cross a span.
+                    for col in first_col..=last_col {
+                        if self.horizontal_header.is_section_hidden(col)
+                            || (row > 0
+                                && self
+                                    .span_at(row, col)
+                                    .is_some_and(|span| span.contains(row - 1, col)))
+                        {
+                            continue;
+                        }
+                        let x = self.horizontal_header.section_position(col) - self.scroll_x as f32
+                            + content_area.origin.x;
+                        let width = self.horizontal_header.section_size(col);
+                        ctx.renderer().draw_line(
+                            Point::new(x, visual_y),
+                            Point::new(x + width, visual_y),
+                            &stroke,
+                        );
+                    }
                 }
             }
         }
@@ -1298,11 +1895,33 @@ impl TableView {
                 if visual_x >= content_area.origin.x
                     && visual_x <= content_area.origin.x + content_area.width()
                 {
-                    ctx.renderer().draw_line(
-                        Point::new(visual_x, content_area.origin.y),
-                        Point::new(visual_x, content_area.origin.y + content_area.height()),
-                        &stroke,
-                    );
+                    if self.spans.is_empty() {
+                        ctx.renderer().draw_line(
+                            Point::new(visual_x, content_area.origin.y),
+                            Point::new(visual_x, content_area.origin.y + content_area.height()),
+                            &stroke,
+                        );
+                        continue;
+                    }
+                    // Break the line where it would cross a span.
+                    for row in first_row..=last_row {
+                        if col > 0
+                            && self
+                                .span_at(row, col)
+                                .is_some_and(|span| span.contains(row, col - 1))
+                        {
+                            continue;
+                        }
+                        let Some(&y) = self.row_positions.get(row) else {
+                            continue;
+                        };
+                        let y = y - self.scroll_y as f32 + content_area.origin.y;
+                        ctx.renderer().draw_line(
+                            Point::new(visual_x, y),
+                            Point::new(visual_x, y + self.row_height(row)),
+                            &stroke,
+                        );
+                    }
                 }
             }
         }
@@ -1479,6 +2098,228 @@ impl TableView {
         }
     }
 
+    // =========================================================================
+    // Printing
+    // =========================================================================
+
+    /// Lays the table out on the pages of a print document.
+    ///
+    /// Visible columns are printed in their visual order, scaled down if
+    /// they're wider than the page's content area. Cells are painted by the
+    /// item delegate, without selection, focus or hover, with the text it
+    /// hands to [`DelegatePaintContext::draw_text`]. The model's font,
+    /// colors and alignment roles are passed to the delegate. Spanned cells
+    /// are printed as one cell, and rows covered by the same span stay on
+    /// one page. Rows that don't fit move to the next page, and the column
+    /// header is repeated at the top of each page.
+    pub fn print(&self, document: &mut PrintDocument, font_system: &mut FontSystem) {
+        let Some(model) = &self.model else {
+            return;
+        };
+        let parent = ModelIndex::invalid();
+
+        let header = &self.horizontal_header;
+        let mut columns = Vec::new();
+        let mut total_width = 0.0;
+        for visual in 0..header.section_count() {
+            let column = header.logical_index(visual);
+            if header.is_section_hidden(column) {
+                continue;
+            }
+            let width = header.section_size(column);
+            columns.push((column, total_width, width));
+            total_width += width;
+        }
+        if columns.is_empty() {
+            return;
+        }
+
+        let content_width = document.page_layout().content_rect().width();
+        let scale = if total_width > content_width {
+            content_width / total_width
+        } else {
+            1.0
+        };
+        let theme = DelegateTheme::default();
+        let font = Font::new(FontFamily::SansSerif, PRINT_FONT_SIZE);
+        let stroke = Stroke::new(self.grid_color, 1.0);
+
+        let header_height = if self.show_horizontal_header {
+            header.header_size()
+        } else {
+            0.0
+        };
+        let print_header = |document: &mut PrintDocument, font_system: &mut FontSystem| {
+            if header_height <= 0.0 {
+                return;
+            }
+            document.add_block(header_height * scale, |picture, rect| {
+                picture.save();
+                picture.translate(rect.left(), rect.top());
+                picture.scale(scale, scale);
+                let row_rect = Rect::new(0.0, 0.0, total_width, header_height);
+                picture.fill_rect(row_rect, Color::from_rgb8(240, 240, 240));
+                for &(column, x, width) in &columns {
+                    let cell = Rect::new(x, 0.0, width, header_height);
+                    let text = header.header_text(column);
+                    let layout = print_cell_layout(font_system, &text, &font, cell.width());
+                    print_cell_text(picture, &layout, cell, TextAlignment::center(), theme.text);
+                    picture.stroke_rect(cell, &stroke);
+                }
+                picture.restore();
+            });
+        };
+
+        let row_count = self.row_count();
+        let mut row = 0;
+        let mut first = true;
+        while row < row_count {
+            // Rows covered by the same span are printed as one block.
+            let last_column = self.column_count().saturating_sub(1);
+            let ((top, _), (bottom, _)) = self.expand_to_spans((row, 0), (row, last_column));
+            let bottom = bottom.min(row_count - 1);
+            let band_height: f32 = (top..=bottom).map(|r| self.row_height(r)).sum();
+
+            if first {
+                document.ensure_space((header_height + band_height) * scale);
+                print_header(document, font_system);
+                first = false;
+            } else if !document.fits(band_height * scale) {
+                document.new_page();
+                print_header(document, font_system);
+            }
+
+            document.add_block(band_height * scale, |picture, rect| {
+                picture.save();
+                picture.translate(rect.left(), rect.top());
+                picture.scale(scale, scale);
+                let mut printed_spans = Vec::new();
+                for cell_row in top..=bottom {
+                    for &(column, _, _) in &columns {
+                        let span = self.span_at(cell_row, column);
+                        if let Some(span) = span {
+                            if printed_spans.contains(&span) {
+                                continue;
+                            }
+                            printed_spans.push(span);
+                        }
+                        let span = span.unwrap_or(CellSpan {
+                            row: cell_row,
+                            column,
+                            row_count: 1,
+                            column_count: 1,
+                        });
+                        let cell = self.print_cell_rect(&span, &columns, top);
+                        let index = model.index(span.row, span.column, &parent);
+                        self.print_cell(
+                            picture,
+                            font_system,
+                            cell,
+                            span.row,
+                            &index,
+                            model.as_ref(),
+                        );
+                        self.print_grid(picture, cell, &stroke);
+                    }
+                }
+                picture.restore();
+            });
+            row = bottom + 1;
+        }
+    }
+
+    /// Returns the printed rectangle of a cell or span, relative to the top
+    /// of the block starting at row `top`.
+    fn print_cell_rect(&self, span: &CellSpan, columns: &[(usize, f32, f32)], top: usize) -> Rect {
+        let spanned = span.column..span.column + span.column_count;
+        let (left, right) = columns
+            .iter()
+            .filter(|(column, _, _)| spanned.contains(column))
+            .fold((f32::MAX, f32::MIN), |(left, right), &(_, x, width)| {
+                (left.min(x), right.max(x + width))
+            });
+        let last_row = (span.row + span.row_count).min(self.row_count());
+        let y: f32 = (top..span.row).map(|r| self.row_height(r)).sum();
+        let height: f32 = (span.row..last_row).map(|r| self.row_height(r)).sum();
+        Rect::new(left, y, right - left, height)
+    }
+
+    /// Paints one printed cell through the item delegate.
+    fn print_cell(
+        &self,
+        picture: &mut Picture,
+        font_system: &mut FontSystem,
+        cell: Rect,
+        row: usize,
+        index: &ModelIndex,
+        model: &dyn ItemModel,
+    ) {
+        let mut option = StyleOptionViewItem {
+            rect: cell,
+            index: index.clone(),
+            state: ViewItemState::new().with_alternate(self.alternate_row_colors && row % 2 == 1),
+            features: ViewItemFeatures::default_for_view(),
+            flags: model.flags(index),
+            text: display_text(&model.data(index, ItemRole::Display)),
+            icon: model.data(index, ItemRole::Decoration).as_icon().cloned(),
+            check_state: model.data(index, ItemRole::CheckState).as_check_state(),
+            font: model.data(index, ItemRole::Font).as_font().cloned(),
+            background_color: model
+                .data(index, ItemRole::BackgroundColor)
+                .as_color()
+                .copied(),
+            foreground_color: model
+                .data(index, ItemRole::ForegroundColor)
+                .as_color()
+                .copied(),
+            ..Default::default()
+        };
+        if let Some(alignment) = model
+            .data(index, ItemRole::TextAlignment)
+            .as_text_alignment()
+        {
+            option.text_alignment = alignment;
+        }
+
+        picture.save();
+        picture.clip_rect(cell);
+        let text = {
+            let mut ctx =
+                DelegatePaintContext::new(&mut *picture, cell).with_font_system(font_system);
+            self.delegate.paint(&mut ctx, &option);
+            ctx.take_text()
+        };
+        for text in text {
+            picture.draw_text(&text.layout, text.position, text.color);
+        }
+        picture.restore();
+    }
+
+    /// Draws the grid lines around one printed cell.
+    fn print_grid(&self, picture: &mut Picture, cell: Rect, stroke: &Stroke) {
+        if !self.show_grid {
+            return;
+        }
+        if matches!(self.grid_style, GridStyle::Both | GridStyle::Horizontal) {
+            for y in [cell.top(), cell.bottom()] {
+                picture.draw_line(
+                    Point::new(cell.left(), y),
+                    Point::new(cell.right(), y),
+                    stroke,
+                );
+            }
+        }
+        if matches!(self.grid_style, GridStyle::Both | GridStyle::Vertical) {
+            for x in [cell.left(), cell.right()] {
+                picture.draw_line(
+                    Point::new(x, cell.top()),
+                    Point::new(x, cell.bottom()),
+                    stroke,
+                );
+            }
+        }
+    }
+
     // =========================================================================
     // Event Handling
     // =========================================================================
@@ -1493,6 +2334,24 @@ impl TableView {
             let row = index.row();
             let col = index.column();
             self.pressed_cell = Some((row, col));
+            let current = self.selection_model.current_index();
+            let was_selected = match self.selection_model.selection_behavior() {
+                SelectionBehavior::SelectItems => {
+                    current.row() == row
+                        && current.column() == col
+                        && self.selection_model.is_cell_selected(row, col)
+                }
+                SelectionBehavior::SelectRows => {
+                    current.row() == row && self.selection_model.is_row_selected(row)
+                }
+                SelectionBehavior::SelectColumns => {
+                    current.column() == col && self.selection_model.is_column_selected(col)
+                }
+            };
+            self.pressed_on_selected = current.is_valid()
+                && was_selected
+                && !event.modifiers.shift
+                && !event.modifiers.control;
 
             // Selection handling based on behavior
             let mode = self.selection_model.selection_mode();
@@ -1519,13 +2378,7 @@ impl TableView {
                         if anchor.is_valid() {
                             match behavior {
                                 SelectionBehavior::SelectItems => {
-                                    self.selection_model.select_range_2d(
-                                        anchor.row(),
-                                        anchor.column(),
-                                        row,
-                                        col,
-                                        SelectionFlags::CLEAR_AND_SELECT,
-                                    );
+                                    self.select_cell_block(anchor.row(), anchor.column(), row, col);
                                 }
                                 SelectionBehavior::SelectRows => {
                                     self.selection_model.select_range(
@@ -1598,6 +2451,13 @@ impl TableView {
 
         // Check for header clicks
         let header_height = self.header_height();
+        if self.show_horizontal_header
+            && event.local_pos.y < header_height
+            && let Some(col) = self.column_divider_at(event.local_pos)
+        {
+            self.resizing_column = Some((col, event.local_pos.x, self.column_width(col)));
+            return true;
+        }
         if self.show_horizontal_header
             && event.local_pos.y < header_height
             && let Some(col) = self.column_at_content_x(
@@ -1607,17 +2467,7 @@ impl TableView {
             self.header_clicked.emit((Orientation::Horizontal, col));
 
             if self.sorting_enabled {
-                // Toggle sort order
-                let current_order = self.horizontal_header.sort_indicator_order();
-                let new_order = if self.horizontal_header.sort_indicator_section() == Some(col) {
-                    match current_order {
-                        SortOrder::Ascending => SortOrder::Descending,
-                        SortOrder::Descending => SortOrder::Ascending,
-                    }
-                } else {
-                    SortOrder::Ascending
-                };
-                self.horizontal_header.set_sort_indicator(col, new_order);
+                self.sort_by_header_click(col, event.modifiers.shift);
             }
             return true;
         }
@@ -1625,11 +2475,41 @@ impl TableView {
         false
     }
 
+    /// Returns the column whose header divider is at a point in widget
+    /// coordinates.
+    fn column_divider_at(&self, point: Point) -> Option<usize> {
+        if !self.show_horizontal_header || point.y >= self.header_height() {
+            return None;
+        }
+        self.horizontal_header
+            .section_handle_at(point.x - self.row_header_width())
+    }
+
+    /// Fits a column to its contents when its header divider is double-clicked.
+    fn handle_double_click(&mut self, event: &MouseDoubleClickEvent) -> bool {
+        if event.button != MouseButton::Left {
+            return false;
+        }
+        let Some(col) = self.column_divider_at(event.local_pos) else {
+            return false;
+        };
+        self.resizing_column = None;
+        self.resize_column_to_contents(col);
+        self.horizontal_header
+            .section_handle_double_clicked
+            .emit(col);
+        true
+    }
+
     fn handle_mouse_release(&mut self, event: &MouseReleaseEvent) -> bool {
         if event.button != MouseButton::Left {
             return false;
         }
 
+        if self.resizing_column.take().is_some() {
+            return true;
+        }
+
         let pressed = self.pressed_cell.take();
         self.base.update();
 
@@ -1648,20 +2528,34 @@ impl TableView {
                 && now.duration_since(last_time).as_millis() < 500
             {
                 self.double_clicked.emit(emit_index.clone());
-                self.activated.emit(emit_index);
+                self.activated.emit(emit_index.clone());
                 self.last_click_time = None;
                 self.last_click_cell = None;
+                if self.edit_triggers.contains(EditTriggers::DOUBLE_CLICKED) {
+                    self.edit(&emit_index);
+                }
                 return true;
             }
 
             self.last_click_time = Some(now);
             self.last_click_cell = Some((row, col));
+
+            if self.pressed_on_selected
+                && self.edit_triggers.contains(EditTriggers::SELECTED_CLICKED)
+            {
+                self.edit(&emit_index);
+            }
         }
 
         true
     }
 
     fn handle_mouse_move(&mut self, event: &MouseMoveEvent) -> bool {
+        if let Some((col, start_x, start_width)) = self.resizing_column {
+            self.set_column_width(col, start_width + event.local_pos.x - start_x);
+            return true;
+        }
+
         let old_hovered = self.hovered_cell;
 
         if let Some(index) = self.index_at(event.local_pos) {
@@ -1703,6 +2597,26 @@ impl TableView {
 
         let current_row = current.row();
         let current_col = current.column();
+        // Moving down or right leaves the current cell's span.
+        let (span_rows, span_cols) =
+            self.span_at(current_row, current_col)
+                .map_or((1, 1), |span| {
+                    (
+                        span.row + span.row_count - current_row,
+                        span.column + span.column_count - current_col,
+                    )
+                });
+
+        if self.edit_triggers.accepts_key(event) {
+            let index = ModelIndex::new(current_row, current_col, ModelIndex::invalid());
+            if self.edit(&index) {
+                // Typed text goes to the new editor, replacing the value.
+                if event.key != Key::F2 {
+                    self.handle_editor_event(&mut WidgetEvent::KeyPress(event.clone()));
+                }
+                return true;
+            }
+        }
 
         match event.key {
             Key::ArrowUp => {
@@ -1711,7 +2625,7 @@ impl TableView {
                 true
             }
             Key::ArrowDown => {
-                let new_row = (current_row + 1).min(row_count - 1);
+                let new_row = (current_row + span_rows).min(row_count - 1);
                 self.move_to_cell(new_row, current_col, &event.modifiers);
                 true
             }
@@ -1721,7 +2635,7 @@ impl TableView {
                 true
             }
             Key::ArrowRight => {
-                let new_col = (current_col + 1).min(col_count - 1);
+                let new_col = (current_col + span_cols).min(col_count - 1);
                 self.move_to_cell(current_row, new_col, &event.modifiers);
                 true
             }
@@ -1768,12 +2682,28 @@ impl TableView {
         }
     }
 
+    /// Selects the block of cells between two corners, grown to whole spans.
+    fn select_cell_block(&mut self, row_a: usize, col_a: usize, row_b: usize, col_b: usize) {
+        let ((top, left), (bottom, right)) = self.expand_to_spans(
+            (row_a.min(row_b), col_a.min(col_b)),
+            (row_a.max(row_b), col_a.max(col_b)),
+        );
+        self.selection_model.select_range_2d(
+            top,
+            left,
+            bottom,
+            right,
+            SelectionFlags::CLEAR_AND_SELECT,
+        );
+    }
+
     fn move_to_cell(
         &mut self,
         row: usize,
         col: usize,
         modifiers: &crate::widget::KeyboardModifiers,
     ) {
+        let (row, col) = self.span_anchor(row, col);
         let index = ModelIndex::new(row, col, ModelIndex::invalid());
         let behavior = self.selection_model.selection_behavior();
 
@@ -1783,13 +2713,7 @@ impl TableView {
             if anchor.is_valid() {
                 match behavior {
                     SelectionBehavior::SelectItems => {
-                        self.selection_model.select_range_2d(
-                            anchor.row(),
-                            anchor.column(),
-                            row,
-                            col,
-                            SelectionFlags::CLEAR_AND_SELECT,
-                        );
+                        self.select_cell_block(anchor.row(), anchor.column(), row, col);
                     }
                     SelectionBehavior::SelectRows => {
                         self.selection_model.select_range(
@@ -1872,6 +2796,53 @@ impl TableView {
     }
 }
 
+/// Formats cell data for printing.
+fn display_text(data: &ItemData) -> Option<String> {
+    match data {
+        ItemData::String(text) => Some(text.clone()),
+        ItemData::Int(value) => Some(value.to_string()),
+        ItemData::Float(value) => Some(value.to_string()),
+        ItemData::Bool(value) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Lays out printed cell text on a single line.
+fn print_cell_layout(
+    font_system: &mut FontSystem,
+    text: &str,
+    font: &Font,
+    width: f32,
+) -> TextLayout {
+    TextLayout::with_options(
+        font_system,
+        text,
+        font,
+        TextLayoutOptions::default().max_width(width.max(1.0)),
+    )
+}
+
+/// Records cell text aligned within `rect`.
+fn print_cell_text(
+    picture: &mut Picture,
+    layout: &TextLayout,
+    rect: Rect,
+    alignment: TextAlignment,
+    color: Color,
+) {
+    let x = match alignment.horizontal {
+        HorizontalAlignment::Left | HorizontalAlignment::Justify => rect.left(),
+        HorizontalAlignment::Center => rect.left() + (rect.width() - layout.width()) / 2.0,
+        HorizontalAlignment::Right => rect.right() - layout.width(),
+    };
+    let y = match alignment.vertical {
+        VerticalAlignment::Top | VerticalAlignment::Baseline => rect.top(),
+        VerticalAlignment::Center => rect.top() + (rect.height() - layout.height()) / 2.0,
+        VerticalAlignment::Bottom => rect.bottom() - layout.height(),
+    };
+    picture.draw_text(layout, Point::new(x, y), color);
+}
+
 impl Object for TableView {
     fn object_id(&self) -> ObjectId {
         self.base.object_id()
@@ -1897,6 +2868,9 @@ impl Widget for TableView {
         self.paint_background(ctx);
         self.paint_cells(ctx);
         self.paint_grid(ctx);
+        if let Some(editor) = &self.editor {
+            editor.paint(ctx);
+        }
         self.paint_drop_indicator(ctx);
         self.paint_headers(ctx);
         self.paint_scrollbars(ctx);
@@ -1905,6 +2879,13 @@ impl Widget for TableView {
     fn event(&mut self, event: &mut WidgetEvent) -> bool {
         self.ensure_layout();
 
+        if let Some(handled) = self.handle_editor_event(event) {
+            if handled {
+                event.accept();
+            }
+            return handled;
+        }
+
         match event {
             WidgetEvent::MousePress(e) => {
                 if self.handle_mouse_press(e) {
@@ -1924,6 +2905,12 @@ impl Widget for TableView {
                     return true;
                 }
             }
+            WidgetEvent::DoubleClick(e) => {
+                if self.handle_double_click(e) {
+                    event.accept();
+                    return true;
+                }
+            }
             WidgetEvent::Wheel(e) => {
                 if self.handle_wheel(e) {
                     event.accept();
@@ -2117,4 +3104,406 @@ mod tests {
         // Drop indicator should not be active initially
         assert!(!table.drop_indicator_state.has_indicator());
     }
+
+    #[test]
+    fn test_editing_commits_to_model() {
+        use crate::model::{ItemData, SimpleTableModel};
+        setup();
+
+        let model = Arc::new(
+            SimpleTableModel::from_data(vec![
+                vec![ItemData::from("a"), ItemData::from("b")],
+                vec![ItemData::from("c"), ItemData::from("d")],
+            ])
+            .with_editable(true),
+        );
+        let mut table = TableView::new().with_selection_behavior(SelectionBehavior::SelectItems);
+        table
+            .widget_base_mut()
+            .set_geometry(Rect::new(0.0, 0.0, 400.0, 300.0));
+        table.set_model(Some(model.clone()));
+        table.set_current_index(ModelIndex::new(0, 0, ModelIndex::invalid()));
+
+        let key = |table: &mut TableView, key: Key, text: &str| {
+            let event = KeyPressEvent::new(key, KeyboardModifiers::NONE, text, false);
+            table.event(&mut WidgetEvent::KeyPress(event))
+        };
+
+        // Typing doesn't edit under the default triggers; F2 does.
+        key(&mut table, Key::X, "x");
+        assert!(!table.is_editing());
+        key(&mut table, Key::F2, "");
+        assert!(table.is_editing());
+
+        // Escape discards the edit.
+        key(&mut table, Key::X, "x");
+        key(&mut table, Key::Escape, "");
+        assert!(!table.is_editing());
+        assert_eq!(
+            model
+                .data(
+                    &model.index(0, 0, &ModelIndex::invalid()),
+                    ItemRole::Display
+                )
+                .as_string(),
+            Some("a")
+        );
+
+        // Typed text replaces the value; Tab commits and moves to the next cell.
+        table.set_edit_triggers(EditTriggers::ALL);
+        key(&mut table, Key::X, "x");
+        assert!(table.is_editing());
+        key(&mut table, Key::Tab, "");
+        assert!(table.is_editing());
+        assert_eq!(table.current_index().column(), 1);
+        key(&mut table, Key::Y, "y");
+        key(&mut table, Key::Enter, "");
+        assert!(!table.is_editing());
+
+        let cell = |row, col| {
+            model
+                .data(
+                    &model.index(row, col, &ModelIndex::invalid()),
+                    ItemRole::Display,
+                )
+                .into_string()
+        };
+        assert_eq!(cell(0, 0).as_deref(), Some("x"));
+        assert_eq!(cell(0, 1).as_deref(), Some("y"));
+
+        // Cells of read-only models don't open editors.
+        table.set_model(Some(Arc::new(SimpleTableModel::from_data(vec![vec![
+            ItemData::from("a"),
+        ]]))));
+        assert!(!table.edit(&ModelIndex::new(0, 0, ModelIndex::invalid())));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_editing_calls_deprecated_delegate_hooks() {
+        use crate::model::{
+            DefaultItemDelegate, DelegatePaintContext, ItemData, SimpleTableModel,
+            StyleOptionViewItem,
+        };
+        use std::sync::Mutex;
+        setup();
+
+        #[derive(Default)]
+        struct LegacyDelegate {
+            calls: Mutex<Vec<&'static str>>,
+        }
+
+        impl ItemDelegate for LegacyDelegate {
+            fn paint(&self, ctx: &mut DelegatePaintContext<'_>, option: &StyleOptionViewItem) {
+                DefaultItemDelegate::new().paint(ctx, option);
+            }
+
+            fn size_hint(&self, option: &StyleOptionViewItem) -> (f32, f32) {
+                DefaultItemDelegate::new().size_hint(option)
+            }
+
+            fn start_editing(&self, _option: &StyleOptionViewItem) -> bool {
+                self.calls.lock().unwrap().push("start");
+                true
+            }
+
+            fn commit_editing(&self) -> Option<ItemData> {
+                self.calls.lock().unwrap().push("commit");
+                Some(ItemData::from("legacy"))
+            }
+
+            fn cancel_editing(&self) {
+                self.calls.lock().unwrap().push("cancel");
+            }
+
+            fn update_editor_geometry(&self, _rect: Rect) {
+                self.calls.lock().unwrap().push("geometry");
+            }
+        }
+
+        let model = Arc::new(
+            SimpleTableModel::from_data(vec![vec![ItemData::from("a")]]).with_editable(true),
+        );
+        let delegate = Arc::new(LegacyDelegate::default());
+        let mut table = TableView::new().with_delegate(delegate.clone());
+        table
+            .widget_base_mut()
+            .set_geometry(Rect::new(0.0, 0.0, 400.0, 300.0));
+        table.set_model(Some(model.clone()));
+        let index = model.index(0, 0, &ModelIndex::invalid());
+
+        assert!(table.edit(&index));
+        table.close_editor();
+        assert!(table.edit(&index));
+        assert!(table.commit_editor());
+        assert_eq!(
+            *delegate.calls.lock().unwrap(),
+            ["geometry", "start", "cancel", "geometry", "start", "commit"]
+        );
+        // The value returned by `commit_editing` replaces the editor's.
+        assert_eq!(
+            model.data(&index, ItemRole::Display).as_string(),
+            Some("legacy")
+        );
+    }
+
+    fn press(table: &mut TableView, x: f32, y: f32, modifiers: KeyboardModifiers) {
+        let pos = Point::new(x, y);
+        let event = MousePressEvent::new(MouseButton::Left, pos, pos, pos, modifiers);
+        table.event(&mut WidgetEvent::MousePress(event));
+        let event = MouseReleaseEvent::new(MouseButton::Left, pos, pos, pos, modifiers);
+        table.event(&mut WidgetEvent::MouseRelease(event));
+    }
+
+    #[test]
+    fn test_cell_spans() {
+        use crate::model::{ItemData, SimpleTableModel};
+        setup();
+
+        let model = Arc::new(SimpleTableModel::from_data(
+            (0..4)
+                .map(|row| (0..3).map(|col| ItemData::from(row * 3 + col)).collect())
+                .collect(),
+        ));
+        let mut table = TableView::new().with_selection_behavior(SelectionBehavior::SelectItems);
+        table
+            .widget_base_mut()
+            .set_geometry(Rect::new(0.0, 0.0, 400.0, 300.0));
+        table.set_model(Some(model));
+        table.set_span(0, 0, 2, 2);
+        assert_eq!(table.row_span(0, 0), 2);
+        assert_eq!(table.column_span(0, 0), 2);
+        assert_eq!(table.row_span(1, 1), 1);
+
+        // Any covered cell hits the span's anchor.
+        press(&mut table, 150.0, 60.0, KeyboardModifiers::NONE);
+        assert_eq!(table.current_index().row(), 0);
+        assert_eq!(table.current_index().column(), 0);
+        assert_eq!(
+            table.cell_rect(1, 1),
+            Some(Rect::new(0.0, 0.0, 200.0, 48.0))
+        );
+
+        // Moving right or down leaves the span.
+        let key = |table: &mut TableView, key: Key, modifiers| {
+            let event = KeyPressEvent::new(key, modifiers, "", false);
+            table.event(&mut WidgetEvent::KeyPress(event));
+            (table.current_index().row(), table.current_index().column())
+        };
+        assert_eq!(
+            key(&mut table, Key::ArrowRight, KeyboardModifiers::NONE),
+            (0, 2)
+        );
+        assert_eq!(
+            key(&mut table, Key::ArrowLeft, KeyboardModifiers::NONE),
+            (0, 0)
+        );
+        assert_eq!(
+            key(&mut table, Key::ArrowDown, KeyboardModifiers::NONE),
+            (2, 0)
+        );
+        assert_eq!(
+            key(&mut table, Key::ArrowUp, KeyboardModifiers::NONE),
+            (0, 0)
+        );
+
+        // A range selection touching the span selects all of it.
+        press(&mut table, 250.0, 60.0, KeyboardModifiers::NONE);
+        assert_eq!(
+            key(&mut table, Key::ArrowLeft, KeyboardModifiers::SHIFT),
+            (0, 0)
+        );
+        let selection = table.selection_model();
+        assert!(selection.is_cell_selected(0, 0));
+        assert!(selection.is_cell_selected(1, 2));
+        assert!(selection.is_cell_selected(0, 2));
+        assert!(!selection.is_cell_selected(2, 0));
+
+        // Overlapping spans replace each other.
+        table.set_span(1, 1, 1, 2);
+        assert_eq!(table.row_span(0, 0), 1);
+        assert_eq!(table.column_span(1, 1), 2);
+        table.clear_spans();
+        assert_eq!(table.column_span(1, 1), 1);
+    }
+
+    #[test]
+    fn test_resize_to_contents() {
+        use crate::model::{ItemData, SimpleTableModel};
+        setup();
+
+        let long = "a much longer value than fits in a column";
+        let model = Arc::new(SimpleTableModel::from_data(vec![
+            vec![ItemData::from("a"), ItemData::from(long)],
+            vec![ItemData::from("b"), ItemData::from("c")],
+        ]));
+        let mut table = TableView::new();
+        table
+            .widget_base_mut()
+            .set_geometry(Rect::new(0.0, 0.0, 600.0, 300.0));
+        table.set_model(Some(model));
+        table
+            .horizontal_header_mut()
+            .set_resize_mode(1, ResizeMode::ResizeToContents);
+        table.ensure_layout();
+        assert_eq!(table.column_width(1), table.size_hint_for_column(1));
+        assert!(table.column_width(1) > DEFAULT_COLUMN_WIDTH);
+
+        // Double-clicking a column's header divider fits the column.
+        let divider = Point::new(DEFAULT_COLUMN_WIDTH, 10.0);
+        let event = MouseDoubleClickEvent::new(
+            MouseButton::Left,
+            divider,
+            divider,
+            divider,
+            KeyboardModifiers::NONE,
+        );
+        assert!(table.event(&mut WidgetEvent::DoubleClick(event)));
+        assert_eq!(table.column_width(0), table.size_hint_for_column(0));
+        assert!(table.column_width(0) < DEFAULT_COLUMN_WIDTH);
+    }
+
+    #[test]
+    fn test_multi_column_sort() {
+        use crate::model::{ItemData, ProxyModel, SimpleTableModel};
+        setup();
+
+        let source = Arc::new(SimpleTableModel::from_data(vec![
+            vec![ItemData::from("b"), ItemData::from(1i64)],
+            vec![ItemData::from("a"), ItemData::from(2i64)],
+            vec![ItemData::from("a"), ItemData::from(1i64)],
+        ]));
+        let proxy = Arc::new(ProxyModel::new(source));
+        let mut table = TableView::new().with_sorting_enabled(true);
+        table
+            .widget_base_mut()
+            .set_geometry(Rect::new(0.0, 0.0, 400.0, 300.0));
+        table.set_model(Some(proxy.clone()));
+
+        let rows = || {
+            (0..3)
+                .map(|row| {
+                    let cell = |col| {
+                        let index = proxy.index(row, col, &ModelIndex::invalid());
+                        proxy.data(&index, ItemRole::Display)
+                    };
+                    format!(
+                        "{}{}",
+                        cell(0).as_string().unwrap(),
+                        cell(1).as_int().unwrap()
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        press(&mut table, 50.0, 10.0, KeyboardModifiers::NONE);
+        assert_eq!(proxy.sort_columns(), [(0, false)]);
+        press(&mut table, 150.0, 10.0, KeyboardModifiers::SHIFT);
+        assert_eq!(rows(), ["a1", "a2", "b1"]);
+        press(&mut table, 150.0, 10.0, KeyboardModifiers::SHIFT);
+        assert_eq!(
+            table.sort_indicators(),
+            [(0, SortOrder::Ascending), (1, SortOrder::Descending)]
+        );
+        assert_eq!(proxy.sort_columns(), [(0, false), (1, true)]);
+        assert_eq!(rows(), ["a2", "a1", "b1"]);
+        assert_eq!(table.horizontal_header().sort_priority(1), Some(1));
+
+        // A plain click sorts by one column again.
+        press(&mut table, 150.0, 10.0, KeyboardModifiers::NONE);
+        assert_eq!(table.sort_indicators(), [(1, SortOrder::Ascending)]);
+        assert_eq!(proxy.sort_columns(), [(1, false)]);
+    }
+
+    #[test]
+    fn test_print_repeats_header() {
+        use super::super::print_document::{PageLayout, PageMargins};
+        use crate::model::SimpleTableModel;
+        use horizon_lattice_render::{DrawCommand, Size};
+        setup();
+
+        let rows = (0..40)
+            .map(|i| vec![ItemData::from(format!("row {i}")), ItemData::Int(i)])
+            .collect();
+        let model = SimpleTableModel::from_data(rows);
+        model.set_headers(vec!["Name".into(), "Value".into()]);
+        let mut table = TableView::new();
+        table.set_model(Some(Arc::new(model)));
+        table.set_column_width(0, 300.0);
+        table.set_column_width(1, 300.0);
+
+        let layout =
+            PageLayout::new(Size::new(400.0, 400.0)).with_margins(PageMargins::uniform(50.0));
+        let mut document = PrintDocument::new(layout);
+        let mut font_system = FontSystem::new();
+        table.print(&mut document, &mut font_system);
+
+        // 600 points of columns are scaled to the 300 point content width, so
+        // the header and rows are 12 points high and 24 rows fit per page.
+        assert_eq!(document.page_count(), 2);
+        for page in document.pages() {
+            let texts: Vec<&str> = page
+                .commands()
+                .iter()
+                .filter_map(|command| match command {
+                    DrawCommand::Text { layout, .. } => Some(layout.text()),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(texts[..2], ["Name", "Value"]);
+        }
+        let texts = |page: &Picture| {
+            page.commands()
+                .iter()
+                .filter(|command| matches!(command, DrawCommand::Text { .. }))
+                .count()
+        };
+        assert_eq!(texts(&document.pages()[0]), 2 + 24 * 2);
+        assert_eq!(texts(&document.pages()[1]), 2 + 16 * 2);
+    }
+
+    #[test]
+    fn test_print_spans() {
+        use super::super::print_document::{PageLayout, PageMargins};
+        use crate::model::SimpleTableModel;
+        use horizon_lattice_render::{DrawCommand, Size};
+        setup();
+
+        let rows = (0..3)
+            .map(|i| {
+                vec![
+                    ItemData::from(format!("a{i}")),
+                    ItemData::from(format!("b{i}")),
+                ]
+            })
+            .collect();
+        let mut table = TableView::new();
+        table.set_model(Some(Arc::new(SimpleTableModel::from_data(rows))));
+        table.set_horizontal_header_visible(false);
+        table.set_span(0, 0, 2, 1);
+        table.set_span(2, 0, 1, 2);
+
+        let layout =
+            PageLayout::new(Size::new(400.0, 400.0)).with_margins(PageMargins::uniform(50.0));
+        let mut document = PrintDocument::new(layout);
+        let mut font_system = FontSystem::new();
+        table.print(&mut document, &mut font_system);
+
+        let texts: Vec<(&str, Point)> = document.pages()[0]
+            .commands()
+            .iter()
+            .filter_map(|command| match command {
+                DrawCommand::Text {
+                    layout, position, ..
+                } => Some((layout.text(), *position)),
+                _ => None,
+            })
+            .collect();
+        let names: Vec<&str> = texts.iter().map(|(text, _)| *text).collect();
+        assert_eq!(names, ["a0", "b0", "b1", "a2"]);
+
+        // The text of the two-row span is centered in both rows, below the
+        // text of the first row's other cell.
+        assert!(texts[0].1.y > texts[1].1.y);
+    }
 }
@@ -34,15 +34,15 @@
 use parking_lot::RwLock;
 use unicode_segmentation::UnicodeSegmentation;
 
+use super::print_document::PrintDocument;
 use super::styled_document::{
     BlockRun, CharFormat, FormatRun, LineSpacing, ListFormat, ListStyle, StyledDocument,
 };
 use crate::platform::Clipboard;
 use horizon_lattice_core::{Object, ObjectId, Signal};
 use horizon_lattice_render::{
-    Color, Font, FontFamily, FontStyle, FontSystem, FontWeight, HorizontalAlign, Point, Rect,
-    Renderer, Size, Stroke, TextDecoration, TextLayout, TextLayoutOptions, TextRenderer, TextSpan,
-    WrapMode,
+    Color, Font, FontFamily, FontSystem, FontWeight, HorizontalAlign, Point, Rect, Renderer, Size,
+    Stroke, TextLayout, TextLayoutOptions, TextRenderer, TextSpan, WrapMode,
 };
 
 use crate::widget::{
@@ -51,28 +51,6 @@ use crate::widget::{
     WidgetBase, WidgetEvent,
 };
 
-// =========================================================================
-// Color Conversion Helper
-// =========================================================================
-
-/// Convert a Color (premultiplied alpha, f32) to RGBA8 bytes for TextSpan.
-#[inline]
-fn color_to_rgba8(color: Color) -> [u8; 4] {
-    // Unpremultiply alpha for correct byte representation
-    let (r, g, b) = if color.a > 0.0 {
-        (color.r / color.a, color.g / color.a, color.b / color.a)
-    } else {
-        (0.0, 0.0, 0.0)
-    };
-
-    [
-        (r.clamp(0.0, 1.0) * 255.0) as u8,
-        (g.clamp(0.0, 1.0) * 255.0) as u8,
-        (b.clamp(0.0, 1.0) * 255.0) as u8,
-        (color.a.clamp(0.0, 1.0) * 255.0) as u8,
-    ]
-}
-
 // =========================================================================
 // Undo/Redo System
 // =========================================================================
@@ -2502,6 +2480,19 @@ impl TextEdit {
         self.emit_cursor_position();
     }
 
+    // =========================================================================
+    // Printing
+    // =========================================================================
+
+    /// Lay the text out on the pages of a print document.
+    ///
+    /// Paragraphs keep their character and block formatting and are wrapped
+    /// to the width of the page's content area. See [`StyledDocument::print`].
+    pub fn print(&self, document: &mut PrintDocument, font_system: &mut FontSystem) {
+        self.document
+            .print(document, font_system, &self.font, self.text_color);
+    }
+
     // =========================================================================
     // Undo/Redo
     // =========================================================================
@@ -3746,62 +3737,7 @@ impl TextEdit {
             let styled_spans = self.styled_spans_for_rendering();
             let text_spans: Vec<TextSpan<'_>> = styled_spans
                 .iter()
-                .map(|(text, format)| {
-                    let mut span = TextSpan::new(text);
-
-                    // Build font with style overrides
-                    let mut font = self.font.clone();
-                    let mut needs_font = false;
-
-                    // Apply font family if specified
-                    if let Some(ref family) = format.font_family {
-                        font = font.with_family(family.clone());
-                        needs_font = true;
-                    }
-
-                    // Apply font size if specified
-                    if let Some(size) = format.font_size {
-                        font = font.with_size(size);
-                        needs_font = true;
-                    }
-
-                    // Apply font weight: explicit weight takes precedence over bold flag
-                    if let Some(weight) = format.font_weight {
-                        font = font.with_weight(weight);
-                        needs_font = true;
-                    } else if format.bold {
-                        font = font.with_weight(FontWeight::BOLD);
-                        needs_font = true;
-                    }
-
-                    // Apply italic style
-                    if format.italic {
-                        font = font.with_style(FontStyle::Italic);
-                        needs_font = true;
-                    }
-
-                    if needs_font {
-                        span = span.with_font(font);
-                    }
-
-                    // Apply decorations
-                    if format.underline {
-                        span = span.with_decoration(TextDecoration::underline());
-                    }
-                    if format.strikethrough {
-                        span = span.with_decoration(TextDecoration::strikethrough());
-                    }
-
-                    // Apply colors
-                    if let Some(fg_color) = format.foreground_color {
-                        span = span.with_color(color_to_rgba8(fg_color));
-                    }
-                    if let Some(bg_color) = format.background_color {
-                        span = span.with_background_color(color_to_rgba8(bg_color));
-                    }
-
-                    span
-                })
+                .map(|(text, format)| format.to_text_span(text, &self.font))
                 .collect();
 
             // Get alignment (use uniform alignment if all paragraphs have the same, otherwise left)
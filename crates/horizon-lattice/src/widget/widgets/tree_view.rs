@@ -35,19 +35,20 @@ use horizon_lattice_render::{Color, Point, Rect, Renderer, Size, Stroke};
 
 use crate::model::selection::{SelectionFlags, SelectionMode, SelectionModel};
 use crate::model::{
-    DefaultItemDelegate, DelegatePaintContext, ItemDelegate, ItemModel, ItemRole, ModelIndex,
-    StyleOptionViewItem, ViewItemFeatures, ViewItemState,
+    DefaultItemDelegate, DelegatePaintContext, ItemDelegate, ItemModel, ItemRole,
+    ModelChangeTracker, ModelIndex, StyleOptionViewItem, ViewItemFeatures, ViewItemState,
 };
 use crate::widget::drag_drop::{
-    DragData, DragEnterEvent, DragLeaveEvent, DragMoveEvent, DropAction, DropEvent,
-    DropIndicatorState, DropPosition,
+    DragData, DragDropManager, DragEnterEvent, DragLeaveEvent, DragMoveEvent, DropAction,
+    DropEvent, DropIndicatorState, DropPosition,
 };
 use crate::widget::{
-    ContextMenuEvent, FocusPolicy, Key, KeyPressEvent, MouseButton, MouseMoveEvent,
-    MousePressEvent, MouseReleaseEvent, PaintContext, SizeHint, SizePolicy, SizePolicyPair,
-    WheelEvent, Widget, WidgetBase, WidgetEvent,
+    ContextMenuEvent, FocusPolicy, Key, KeyPressEvent, KeyboardModifiers, MouseButton,
+    MouseMoveEvent, MousePressEvent, MouseReleaseEvent, PaintContext, SizeHint, SizePolicy,
+    SizePolicyPair, WheelEvent, Widget, WidgetBase, WidgetEvent,
 };
 
+use super::item_editing::{EditTriggers, EditorEvent, EditorSession};
 use super::scroll_area::ScrollBarPolicy;
 
 /// Configuration for tree indentation style.
@@ -102,6 +103,7 @@ struct FlattenedRow {
 /// - Indentation based on hierarchy depth
 /// - Optional tree branch lines
 /// - All standard view features (selection, scrolling, keyboard navigation)
+/// - In-place editing with delegate-created editors
 ///
 /// # Signals
 ///
@@ -116,6 +118,8 @@ pub struct TreeView {
 
     // Model/View
     model: Option<Arc<dyn ItemModel>>,
+    /// Marks the layout stale when the model's rows change.
+    model_changes: Option<ModelChangeTracker>,
     selection_model: SelectionModel,
     delegate: Arc<dyn ItemDelegate>,
 
@@ -164,6 +168,12 @@ pub struct TreeView {
     drag_start_pos: Option<Point>,
     dragging_row: Option<usize>,
 
+    // Editing
+    edit_triggers: EditTriggers,
+    editor: Option<EditorSession>,
+    /// Whether the pressed item was already current and selected.
+    pressed_on_selected: bool,
+
     // Signals
     /// Emitted when an item is clicked.
     pub clicked: Signal<ModelIndex>,
@@ -202,6 +212,7 @@ impl TreeView {
         Self {
             base,
             model: None,
+            model_changes: None,
             selection_model: SelectionModel::new(),
             delegate: Arc::new(DefaultItemDelegate::new()),
             expanded_ids: HashSet::new(),
@@ -233,6 +244,9 @@ impl TreeView {
             drop_indicator_state: DropIndicatorState::new(),
             drag_start_pos: None,
             dragging_row: None,
+            edit_triggers: EditTriggers::default(),
+            editor: None,
+            pressed_on_selected: false,
             clicked: Signal::new(),
             double_clicked: Signal::new(),
             activated: Signal::new(),
@@ -291,6 +305,12 @@ impl TreeView {
         self
     }
 
+    /// Sets the edit triggers using builder pattern.
+    pub fn with_edit_triggers(mut self, triggers: EditTriggers) -> Self {
+        self.edit_triggers = triggers;
+        self
+    }
+
     // =========================================================================
     // Model
     // =========================================================================
@@ -302,6 +322,8 @@ impl TreeView {
 
     /// Sets the model.
     pub fn set_model(&mut self, model: Option<Arc<dyn ItemModel>>) {
+        self.close_editor();
+        self.model_changes = model.clone().map(ModelChangeTracker::new);
         self.model = model;
         self.selection_model.reset();
         self.expanded_ids.clear();
@@ -374,6 +396,10 @@ impl TreeView {
         }
 
         if self.expanded_ids.insert(index.internal_id()) {
+            // Lazily loaded children are requested on first expansion.
+            if model.can_fetch_more(index) {
+                model.fetch_more(index);
+            }
             self.layout_dirty = true;
             self.expanded.emit(index.clone());
             self.base.update();
@@ -595,75 +621,130 @@ impl TreeView {
         )
     }
 
-    /// Creates drag data from the selected items.
+    /// Starts dragging the selected items with the given drag manager.
+    ///
+    /// The drag data is created by the model's `mime_data`, and the allowed
+    /// actions are the model's `supported_drag_actions` (only moves in
+    /// `InternalMove` mode). Returns `false` if dragging is disabled or
+    /// nothing is selected.
+    pub fn start_drag(&self, manager: &mut DragDropManager, position: Point) -> bool {
+        if !self.drag_enabled() {
+            return false;
+        }
+        let Some(model) = &self.model else {
+            return false;
+        };
+        let Some(data) = self.create_drag_data(self.selection_model.selected_indices()) else {
+            return false;
+        };
+
+        let mut actions = model.supported_drag_actions();
+        if self.drag_drop_mode == TreeDragDropMode::InternalMove {
+            actions &= DropAction::MOVE;
+        }
+        if actions == DropAction::NONE {
+            return false;
+        }
+        manager.start_drag(data, actions, position);
+        true
+    }
+
+    /// Creates drag data for the given items using the model's `mime_data`.
     fn create_drag_data(&self, indices: &[ModelIndex]) -> Option<DragData> {
         if indices.is_empty() {
             return None;
         }
+        let model = self.model.as_ref()?;
 
-        let mut data = DragData::new();
+        let mut data = model.mime_data(indices);
+        data.set_source_widget(self.base.object_id());
+        if data.is_empty() { None } else { Some(data) }
+    }
 
-        // Extract text from selected items
-        if let Some(model) = &self.model {
-            let texts: Vec<String> = indices
-                .iter()
-                .filter_map(|index| {
-                    model
-                        .data(index, ItemRole::Display)
-                        .as_string()
-                        .map(String::from)
-                })
-                .collect();
+    /// Chooses the action for dropping `data` onto this view.
+    ///
+    /// Items dragged from this view are moved, and anything else is copied,
+    /// as far as `supported` and the model's drop actions allow.
+    fn drop_action_for(&self, data: &DragData, supported: DropAction) -> DropAction {
+        let Some(model) = &self.model else {
+            return DropAction::NONE;
+        };
+        let internal = data.source_widget() == Some(self.base.object_id());
+        let allowed = model.supported_drop_actions() & supported;
 
-            if !texts.is_empty() {
-                data.set_text(texts.join("\n"));
-            }
+        if self.drag_drop_mode == TreeDragDropMode::InternalMove {
+            return if internal {
+                allowed & DropAction::MOVE
+            } else {
+                DropAction::NONE
+            };
+        }
+        if internal && allowed.can_move() {
+            DropAction::MOVE
+        } else if allowed.can_copy() {
+            DropAction::COPY
+        } else {
+            allowed.preferred()
         }
-
-        if data.is_empty() { None } else { Some(data) }
     }
 
     /// Returns the drop position for the given point.
     ///
-    /// This determines where items will be inserted when dropped.
+    /// The upper and lower quarters of a row drop between rows, and the
+    /// middle of a row drops onto the item as a new child. Empty space
+    /// drops at the end of the top level.
     fn drop_position_for_point(&self, point: Point) -> (Option<usize>, DropPosition) {
         if let Some(index) = self.index_at(point)
             && let Some(row_idx) = self.find_flattened_row(&index)
             && let Some(row) = self.flattened_rows.get(row_idx)
         {
-            // Determine if in upper or lower half (for row-based drop)
             let viewport = self.viewport_rect();
             let visual_y = row.rect.origin.y - self.scroll_y as f32 + viewport.origin.y;
-            let mid_y = visual_y + row.rect.height() / 2.0;
-            if point.y < mid_y {
+            let band = row.rect.height() / 4.0;
+            if point.y < visual_y + band {
                 return (Some(row_idx), DropPosition::AboveItem);
-            } else {
+            } else if point.y >= visual_y + row.rect.height() - band {
                 return (Some(row_idx), DropPosition::BelowItem);
+            } else {
+                return (Some(row_idx), DropPosition::OnItem);
             }
         }
 
-        // After last row
-        if !self.flattened_rows.is_empty() {
-            return (Some(self.flattened_rows.len() - 1), DropPosition::BelowItem);
-        }
-
         (None, DropPosition::OnItem)
     }
 
+    /// Returns the `(row, parent)` that a drop at `point` inserts at.
+    fn drop_target_for_point(&self, point: Point) -> (usize, ModelIndex) {
+        let Some(model) = &self.model else {
+            return (0, ModelIndex::invalid());
+        };
+        let (row_idx, position) = self.drop_position_for_point(point);
+        let Some(row) = row_idx.and_then(|idx| self.flattened_rows.get(idx)) else {
+            let root = ModelIndex::invalid();
+            return (model.row_count(&root), root);
+        };
+
+        let index = &row.index;
+        match position {
+            DropPosition::AboveItem => (index.row(), index.parent()),
+            DropPosition::BelowItem => (index.row() + 1, index.parent()),
+            DropPosition::OnItem => (model.row_count(index), index.clone()),
+        }
+    }
+
     /// Handles the DragEnter event.
     fn handle_drag_enter(&mut self, event: &mut DragEnterEvent) -> bool {
         if !self.drop_enabled() {
             return false;
         }
 
-        // Accept the drag if it has text data
-        if event.data().has_text() || event.data().has_urls() {
-            event.accept_proposed_action();
-            event.set_proposed_action(DropAction::COPY);
-            return true;
+        let action = self.drop_action_for(event.data(), event.supported_actions);
+        if action == DropAction::NONE {
+            return false;
         }
-
-        false
+        event.set_proposed_action(action);
+        event.accept_proposed_action();
+        true
     }
 
     /// Handles the DragMove event.
@@ -699,15 +780,35 @@ impl TreeView {
                 })
                 .collect();
 
-            self.drop_indicator_state.update_for_vertical_list(
-                event.local_pos,
-                &item_rects,
-                viewport.width(),
-            );
+            let on_item = match self.drop_position_for_point(event.local_pos) {
+                (Some(idx), DropPosition::OnItem) => {
+                    item_rects.iter().find(|(i, _)| *i == idx).copied()
+                }
+                _ => None,
+            };
+            if let Some((idx, rect)) = on_item {
+                self.drop_indicator_state.update_for_item_drop(idx, rect);
+            } else {
+                self.drop_indicator_state.update_for_vertical_list(
+                    event.local_pos,
+                    &item_rects,
+                    viewport.width(),
+                );
+            }
         } else {
             self.drop_indicator_state.clear();
         }
 
+        // Only propose an action the model accepts at this position.
+        let action = self.drop_action_for(event.data(), event.supported_actions);
+        let (row, parent) = self.drop_target_for_point(event.local_pos);
+        let accepted = action != DropAction::NONE
+            && self
+                .model
+                .as_ref()
+                .is_some_and(|m| m.can_drop_mime_data(event.data(), action, row, 0, &parent));
+        event.set_proposed_action(if accepted { action } else { DropAction::NONE });
+
         event.accept();
         self.base.update();
         true
@@ -721,24 +822,33 @@ impl TreeView {
     }
 
     /// Handles the Drop event.
+    ///
+    /// The dropped data is passed to the model's `drop_mime_data`, which
+    /// inserts copies or, for items dragged from this view, moves them.
     fn handle_drop(&mut self, event: &mut DropEvent) -> bool {
         if !self.drop_enabled() {
             return false;
         }
 
-        let (_drop_row, _drop_position) = self.drop_position_for_point(event.local_pos);
+        let (row, parent) = self.drop_target_for_point(event.local_pos);
         self.drop_indicator_state.clear();
         self.base.update();
 
-        // Process the dropped data
-        if event.data().has_text() || event.data().has_urls() {
-            // Accept the drop
-            // In a real implementation, this would insert items into the model
-            event.accept();
-            return true;
+        let action = self.drop_action_for(event.data(), event.action);
+        let Some(model) = self.model.clone() else {
+            return false;
+        };
+        if action == DropAction::NONE
+            || !model.drop_mime_data(event.data(), action, row, 0, &parent)
+        {
+            return false;
         }
 
-        false
+        // Moved and inserted nodes invalidate the selected indices.
+        self.selection_model.clear_selection();
+        self.layout_dirty = true;
+        event.accept();
+        true
     }
 
     /// Paints the drop indicator if active.
@@ -773,6 +883,7 @@ impl TreeView {
         if self.scroll_x != new_x || self.scroll_y != new_y {
             self.scroll_x = new_x;
             self.scroll_y = new_y;
+            self.fetch_more_if_needed();
             self.base.update();
         }
     }
@@ -815,6 +926,140 @@ impl TreeView {
         }
     }
 
+    // =========================================================================
+    // Editing
+    // =========================================================================
+
+    /// Returns the actions that start editing an item.
+    pub fn edit_triggers(&self) -> EditTriggers {
+        self.edit_triggers
+    }
+
+    /// Sets the actions that start editing an item.
+    pub fn set_edit_triggers(&mut self, triggers: EditTriggers) {
+        self.edit_triggers = triggers;
+    }
+
+    /// Opens an editor over the item at `index`, expanding its ancestors.
+    ///
+    /// Any edit in progress is committed first. Returns `false` if the item
+    /// isn't editable or the delegate provides no editor for it.
+    pub fn edit(&mut self, index: &ModelIndex) -> bool {
+        self.commit_editor();
+        let Some(model) = self.model.clone() else {
+            return false;
+        };
+        self.scroll_to(index);
+        let Some(option) = self.editor_option(index) else {
+            return false;
+        };
+        self.editor = EditorSession::open(model.as_ref(), self.delegate.as_ref(), &option);
+        self.base.update();
+        self.editor.is_some()
+    }
+
+    /// Returns `true` while an editor is open.
+    pub fn is_editing(&self) -> bool {
+        self.editor.is_some()
+    }
+
+    /// Writes the open editor's value to the model and closes the editor.
+    ///
+    /// Returns `true` if the model accepted the value.
+    pub fn commit_editor(&mut self) -> bool {
+        let Some(session) = self.editor.take() else {
+            return false;
+        };
+        let committed = self
+            .model
+            .as_ref()
+            .is_some_and(|model| session.commit(model.as_ref(), self.delegate.as_ref()));
+        self.base.update();
+        committed
+    }
+
+    /// Closes the open editor without changing the model.
+    pub fn close_editor(&mut self) {
+        if let Some(session) = self.editor.take() {
+            session.cancel(self.delegate.as_ref());
+            self.base.update();
+        }
+    }
+
+    /// Builds the style option for the editor of a visible item.
+    fn editor_option(&self, index: &ModelIndex) -> Option<StyleOptionViewItem> {
+        let row_idx = self.find_flattened_row(index)?;
+        let rect = self.visual_rect(index)?;
+        Some(self.build_style_option(row_idx, rect))
+    }
+
+    /// Keeps the open editor over its item after scrolling or a layout change.
+    fn update_editor_geometry(&mut self) {
+        let Some(index) = self.editor.as_ref().map(|e| e.index().clone()) else {
+            return;
+        };
+        match self.editor_option(&index) {
+            Some(option) => {
+                if let Some(editor) = &mut self.editor {
+                    editor.update_geometry(self.delegate.as_ref(), &option);
+                }
+            }
+            // The item was removed or collapsed away.
+            None => self.close_editor(),
+        }
+    }
+
+    /// Finds the next (or previous) visible editable row.
+    fn next_editable_row(&self, index: &ModelIndex, backward: bool) -> Option<usize> {
+        let model = self.model.as_ref()?;
+        let row_idx = self.find_flattened_row(index)?;
+        let editable = |r: &usize| model.flags(&self.flattened_rows[*r].index).editable;
+        if backward {
+            (0..row_idx).rev().find(editable)
+        } else {
+            (row_idx + 1..self.flattened_rows.len()).find(editable)
+        }
+    }
+
+    /// Offers an event to the open editor.
+    ///
+    /// Returns `Some(handled)` if the editor dealt with the event, or `None`
+    /// if the view should process it.
+    fn handle_editor_event(&mut self, event: &mut WidgetEvent) -> Option<bool> {
+        let session = self.editor.as_mut()?;
+        let index = session.index().clone();
+        match session.handle_event(event) {
+            EditorEvent::Handled => {
+                self.base.update();
+                Some(true)
+            }
+            EditorEvent::Ignored => None,
+            EditorEvent::Commit => {
+                self.commit_editor();
+                Some(true)
+            }
+            EditorEvent::Cancel => {
+                self.close_editor();
+                Some(true)
+            }
+            EditorEvent::CommitAndMove { backward } => {
+                // Find the target before committing, which may re-layout.
+                let next = self.next_editable_row(&index, backward);
+                self.commit_editor();
+                if let Some(row_idx) = next {
+                    let next_index = self.flattened_rows[row_idx].index.clone();
+                    self.move_to_row(row_idx, &KeyboardModifiers::NONE);
+                    self.edit(&next_index);
+                }
+                Some(true)
+            }
+            EditorEvent::CommitAndContinue => {
+                self.commit_editor();
+                None
+            }
+        }
+    }
+
     fn max_scroll_x(&self) -> i32 {
         let viewport = self.viewport_rect();
         (self.content_size.width - viewport.width()).max(0.0) as i32
@@ -897,9 +1142,32 @@ impl TreeView {
     // =========================================================================
 
     fn ensure_layout(&mut self) {
+        if self
+            .model_changes
+            .as_ref()
+            .is_some_and(ModelChangeTracker::take_changed)
+        {
+            self.layout_dirty = true;
+        }
         if self.layout_dirty {
             self.update_layout();
         }
+        self.fetch_more_if_needed();
+        self.update_editor_geometry();
+    }
+
+    /// Asks the model for more top-level rows when less than a viewport of
+    /// loaded content remains below the visible area.
+    fn fetch_more_if_needed(&self) {
+        let Some(model) = &self.model else {
+            return;
+        };
+        let root = ModelIndex::invalid();
+        let viewport = self.viewport_rect();
+        let remaining = self.content_size.height - self.scroll_y as f32 - viewport.height();
+        if remaining <= viewport.height() && model.can_fetch_more(&root) {
+            model.fetch_more(&root);
+        }
     }
 
     fn update_layout(&mut self) {
@@ -1211,6 +1479,12 @@ impl TreeView {
 
             // Handle selection
             let index = self.flattened_rows[row_idx].index.clone();
+            let current = self.selection_model.current_index();
+            self.pressed_on_selected = current.is_valid()
+                && current.internal_id() == index.internal_id()
+                && self.selection_model.is_selected(&index)
+                && !event.modifiers.shift
+                && !event.modifiers.control;
             let mode = self.selection_model.selection_mode();
             let flags = match mode {
                 SelectionMode::NoSelection => SelectionFlags::NONE,
@@ -1299,7 +1573,10 @@ impl TreeView {
                     {
                         self.toggle_expanded(&index);
                     } else {
-                        self.activated.emit(index);
+                        self.activated.emit(index.clone());
+                        if self.edit_triggers.contains(EditTriggers::DOUBLE_CLICKED) {
+                            self.edit(&index);
+                        }
                     }
 
                     self.last_click_time = None;
@@ -1309,6 +1586,12 @@ impl TreeView {
 
                 self.last_click_time = Some(now);
                 self.last_click_row = Some(row_idx);
+
+                if self.pressed_on_selected
+                    && self.edit_triggers.contains(EditTriggers::SELECTED_CLICKED)
+                {
+                    self.edit(&index);
+                }
             }
         }
 
@@ -1340,6 +1623,7 @@ impl TreeView {
 
         if self.scroll_y != new_y {
             self.scroll_y = new_y;
+            self.fetch_more_if_needed();
             self.base.update();
             return true;
         }
@@ -1360,6 +1644,17 @@ impl TreeView {
 
         let row_count = self.flattened_rows.len();
 
+        if let Some(row_idx) = current_row
+            && self.edit_triggers.accepts_key(event)
+            && self.edit(&self.flattened_rows[row_idx].index.clone())
+        {
+            // Typed text goes to the new editor, replacing the value.
+            if event.key != Key::F2 {
+                self.handle_editor_event(&mut WidgetEvent::KeyPress(event.clone()));
+            }
+            return true;
+        }
+
         match event.key {
             Key::ArrowUp => {
                 let new_row = current_row.map(|r| r.saturating_sub(1)).unwrap_or(0);
@@ -1858,6 +2153,9 @@ impl Widget for TreeView {
     fn paint(&self, ctx: &mut PaintContext<'_>) {
         self.paint_background(ctx);
         self.paint_items(ctx);
+        if let Some(editor) = &self.editor {
+            editor.paint(ctx);
+        }
         self.paint_drop_indicator(ctx);
         self.paint_scrollbars(ctx);
     }
@@ -1866,6 +2164,13 @@ impl Widget for TreeView {
         // Ensure layout is up to date before handling events
         self.ensure_layout();
 
+        if let Some(handled) = self.handle_editor_event(event) {
+            if handled {
+                event.accept();
+            }
+            return handled;
+        }
+
         match event {
             WidgetEvent::MousePress(e) => {
                 if self.handle_mouse_press(e) {
@@ -34,6 +34,7 @@ use horizon_lattice_core::{Object, ObjectId, Signal};
 use horizon_lattice_render::{Color, Point, Rect, Renderer, Size, Stroke};
 
 use crate::widget::layout::ContentMargins;
+use crate::widget::style::{self, SharedStyleEngine};
 use crate::widget::{
     CloseEvent, FocusManager, FocusPolicy, FocusReason, Key, KeyPressEvent, KeyReleaseEvent,
     MouseButton, MouseDoubleClickEvent, MouseMoveEvent, MousePressEvent, MouseReleaseEvent,
@@ -433,6 +434,10 @@ pub struct Window {
     button_pressed_color: Color,
     /// Close button hover color (red).
     close_button_hover_color: Color,
+    /// Style engine attached to this window's widget tree.
+    style_engine: Option<SharedStyleEngine>,
+    /// Display scale factor, reported to `resolution` media queries.
+    scale_factor: f32,
 
     // Interaction state
     /// Button states.
@@ -629,6 +634,8 @@ impl Window {
             button_hover_color: Color::from_rgb8(220, 220, 220),
             button_pressed_color: Color::from_rgb8(200, 200, 200),
             close_button_hover_color: Color::from_rgb8(232, 17, 35),
+            style_engine: None,
+            scale_factor: 1.0,
             minimize_button_state: ButtonState::default(),
             maximize_button_state: ButtonState::default(),
             close_button_state: ButtonState::default(),
@@ -690,6 +697,66 @@ impl Window {
         self
     }
 
+    // =========================================================================
+    // Styling
+    // =========================================================================
+
+    /// Get the style engine attached to this window, if any.
+    ///
+    /// This returns only an engine set with [`set_style_engine`](Self::set_style_engine),
+    /// not the application-wide engine.
+    pub fn style_engine(&self) -> Option<&SharedStyleEngine> {
+        self.style_engine.as_ref()
+    }
+
+    /// Attach a style engine to this window and all of its widgets.
+    ///
+    /// The window's engine takes precedence over the application-wide engine
+    /// set with [`set_application_style_engine`](style::set_application_style_engine).
+    /// Passing `None` reverts the window to the application engine.
+    pub fn set_style_engine(&mut self, engine: Option<SharedStyleEngine>) {
+        style::set_tree_style_engine(self.base.object_id(), engine.clone());
+        self.style_engine = engine;
+        self.update_style_viewport();
+        self.base.update();
+    }
+
+    /// Set the style engine using builder pattern.
+    pub fn with_style_engine(mut self, engine: SharedStyleEngine) -> Self {
+        self.set_style_engine(Some(engine));
+        self
+    }
+
+    /// Get the display scale factor.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Set the display scale factor.
+    ///
+    /// This is reported to the window's style engine together with the window
+    /// size, so `@media (resolution >= 2dppx)` rules follow the monitor the
+    /// window is on. Call it when the native window's scale factor changes.
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        if self.scale_factor != scale_factor {
+            self.scale_factor = scale_factor;
+            self.update_style_viewport();
+        }
+    }
+
+    /// Report the window size and scale factor to `@media` rules.
+    fn update_style_viewport(&mut self) {
+        let size = self.base.size();
+        if style::set_window_viewport(
+            self.base.object_id(),
+            size.width,
+            size.height,
+            self.scale_factor,
+        ) {
+            self.base.update();
+        }
+    }
+
     // =========================================================================
     // Content Widget
     // =========================================================================
@@ -1057,6 +1124,7 @@ impl Window {
             if self.state == WindowState::Normal {
                 self.normal_geometry.size = Size::new(final_width, final_height);
             }
+            self.update_style_viewport();
         }
     }
 
@@ -2336,6 +2404,7 @@ impl Widget for Window {
             WidgetEvent::Resize(e) => {
                 // Update base geometry and emit signal
                 self.base.set_size(e.new_size);
+                self.update_style_viewport();
                 self.resized.emit(e.new_size);
                 true
             }
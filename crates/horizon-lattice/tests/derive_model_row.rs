@@ -0,0 +1,179 @@
+//! Integration tests for the #[derive(ModelRow)] macro.
+
+use std::sync::Arc;
+
+use horizon_lattice::model::{
+    CheckState, ItemData, ItemModel, ItemRole, ModelIndex, ModelRow, ModelTester, Orientation,
+    TableModel,
+};
+
+#[derive(ModelRow)]
+struct Task {
+    #[column(header = "Title", role = Display, editable)]
+    title: String,
+
+    #[column(header = "Done", role = CheckState, editable)]
+    done: bool,
+
+    hours: f64,
+
+    #[column(header = "Tooltip", role = ToolTip)]
+    note: Option<String>,
+
+    #[column(skip)]
+    id: u64,
+}
+
+fn task(title: &str, done: bool, hours: f64, id: u64) -> Task {
+    Task {
+        title: title.into(),
+        done,
+        hours,
+        note: None,
+        id,
+    }
+}
+
+fn cell(model: &TableModel<Task>, row: usize, column: usize) -> ModelIndex {
+    model.index(row, column, &ModelIndex::invalid())
+}
+
+fn titles(model: &TableModel<Task>) -> Vec<String> {
+    model.rows().iter().map(|task| task.title.clone()).collect()
+}
+
+#[test]
+fn test_columns_and_headers() {
+    assert_eq!(Task::column_count(), 4);
+
+    let model = TableModel::from_rows(vec![task("Write", false, 2.5, 1)]);
+    let root = ModelIndex::invalid();
+    assert_eq!(model.column_count(&root), 4);
+
+    let header = |section| {
+        model
+            .header_data(section, Orientation::Horizontal, ItemRole::Display)
+            .into_string()
+    };
+    assert_eq!(header(0).as_deref(), Some("Title"));
+    assert_eq!(header(1).as_deref(), Some("Done"));
+    assert_eq!(header(2).as_deref(), Some("hours"));
+    assert_eq!(header(3).as_deref(), Some("Tooltip"));
+    assert!(
+        model
+            .header_data(0, Orientation::Vertical, ItemRole::Display)
+            .is_none()
+    );
+}
+
+#[test]
+fn test_data_and_flags() {
+    let mut first = task("Write", true, 2.5, 1);
+    first.note = Some("first draft".into());
+    let model = TableModel::from_rows(vec![first]);
+
+    let title = cell(&model, 0, 0);
+    assert_eq!(
+        model.data(&title, ItemRole::Display).as_string(),
+        Some("Write")
+    );
+    assert_eq!(
+        model.data(&title, ItemRole::Edit).as_string(),
+        Some("Write")
+    );
+    assert!(model.data(&title, ItemRole::ToolTip).is_none());
+
+    let done = cell(&model, 0, 1);
+    assert_eq!(
+        model.data(&done, ItemRole::CheckState).as_check_state(),
+        Some(CheckState::Checked)
+    );
+    assert!(model.data(&done, ItemRole::Display).is_none());
+
+    let hours = cell(&model, 0, 2);
+    assert_eq!(model.data(&hours, ItemRole::Display).as_float(), Some(2.5));
+
+    let note = cell(&model, 0, 3);
+    assert_eq!(
+        model.data(&note, ItemRole::ToolTip).as_string(),
+        Some("first draft")
+    );
+
+    assert!(model.flags(&title).editable);
+    assert!(!model.flags(&title).checkable);
+    assert!(model.flags(&done).checkable);
+    assert!(!model.flags(&hours).editable);
+}
+
+#[test]
+fn test_set_data_writes_back() {
+    let model = TableModel::from_rows(vec![task("Write", false, 2.5, 1)]);
+    let changed = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let changed_clone = changed.clone();
+    model
+        .signals()
+        .data_changed
+        .connect(move |(top_left, _, _)| {
+            changed_clone
+                .lock()
+                .push((top_left.row(), top_left.column()));
+        });
+
+    assert!(model.set_data(
+        &cell(&model, 0, 0),
+        ItemData::from("Review"),
+        ItemRole::Edit
+    ));
+    assert!(model.set_data(
+        &cell(&model, 0, 1),
+        ItemData::CheckState(CheckState::Checked),
+        ItemRole::CheckState
+    ));
+    assert_eq!(model.rows()[0].title, "Review");
+    assert!(model.rows()[0].done);
+    assert_eq!(*changed.lock(), vec![(0, 0), (0, 1)]);
+
+    // Read-only columns, wrong roles and unconvertible values are refused.
+    assert!(!model.set_data(&cell(&model, 0, 2), ItemData::Float(1.0), ItemRole::Edit));
+    assert!(!model.set_data(&cell(&model, 0, 0), ItemData::from("x"), ItemRole::ToolTip));
+    assert!(!model.set_data(
+        &cell(&model, 0, 1),
+        ItemData::from("x"),
+        ItemRole::CheckState
+    ));
+    assert_eq!(model.rows()[0].hours, 2.5);
+    assert_eq!(changed.lock().len(), 2);
+}
+
+#[test]
+fn test_sort() {
+    let model = TableModel::from_rows(vec![
+        task("b", true, 1.0, 1),
+        task("c", false, 3.0, 2),
+        task("a", true, 2.0, 3),
+    ]);
+
+    model.sort(&[(0, false)]);
+    assert_eq!(titles(&model), ["a", "b", "c"]);
+
+    model.sort(&[(2, true)]);
+    assert_eq!(titles(&model), ["c", "a", "b"]);
+
+    model.sort(&[(1, true), (0, true)]);
+    assert_eq!(titles(&model), ["b", "a", "c"]);
+    assert_eq!(model.rows()[0].id, 1);
+}
+
+#[test]
+fn test_model_tester() {
+    let model = Arc::new(TableModel::from_rows(vec![
+        task("b", true, 1.0, 1),
+        task("a", false, 2.0, 2),
+    ]));
+    let tester = ModelTester::new(model.clone());
+    model.push_row(task("c", false, 0.5, 3));
+    model.set_data(&cell(&model, 0, 0), ItemData::from("d"), ItemRole::Edit);
+    model.sort(&[(0, false)]);
+    model.remove_row(1);
+    tester.check();
+}